@@ -0,0 +1,404 @@
+use std::time::Duration;
+
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::job_guard::JobGuard;
+use crate::models::{
+    AlertConfig, AlertConfigSummary, ApiKey, Job, JobFilter, JobMetrics, JobPage, Monitor,
+    MonitorStats, MonitorSummary,
+};
+use crate::types::{
+    AlertConfigData, FinishJobInfo, GenerateKeyInfo, MonitorAssociationData, MonitorData,
+    PauseInfo, RotateKeyInfo,
+};
+
+/// How long to wait for the API before giving up on a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How to authenticate with the API. Monitors, API keys and alert configurations are managed with
+/// a JWT, whilst Jobs are started, finished and sent heartbeats with an API key.
+#[derive(Clone, Debug)]
+pub enum Auth {
+    Jwt(String),
+    ApiKey(String),
+}
+
+/// A typed client for the CronMon API.
+#[derive(Clone, Debug)]
+pub struct Client {
+    base_url: String,
+    auth: Auth,
+    http: reqwest::Client,
+}
+
+/// The envelope that the API returns resources in.
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    data: Vec<T>,
+    paging: Paging,
+}
+
+#[derive(Deserialize)]
+struct Paging {
+    total: usize,
+    next_cursor: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+struct StartedJob {
+    job_id: Uuid,
+}
+
+#[derive(Deserialize)]
+struct GeneratedKey {
+    key: String,
+}
+
+impl Client {
+    /// Create a client for the API at `base_url` (i.e `https://cron-mon.example.com`).
+    pub fn new(base_url: &str, auth: Auth) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            auth,
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// List all Monitors.
+    pub async fn list_monitors(&self) -> Result<Vec<MonitorSummary>, Error> {
+        self.fetch(self.request(Method::GET, "/monitors")).await
+    }
+
+    /// List the Monitors whose labels match a label selector, i.e `team=payments,env!=dev`.
+    pub async fn list_monitors_matching(
+        &self,
+        label_selector: &str,
+    ) -> Result<Vec<MonitorSummary>, Error> {
+        self.fetch(
+            self.request(Method::GET, "/monitors")
+                .query(&[("label_selector", label_selector)]),
+        )
+        .await
+    }
+
+    /// Get a Monitor, along with its Jobs.
+    pub async fn get_monitor(&self, monitor_id: Uuid) -> Result<Monitor, Error> {
+        self.fetch(self.request(Method::GET, &format!("/monitors/{monitor_id}")))
+            .await
+    }
+
+    /// Get statistics describing how long a Monitor's Jobs take, along with suggested durations.
+    pub async fn get_monitor_stats(&self, monitor_id: Uuid) -> Result<MonitorStats, Error> {
+        self.fetch(self.request(Method::GET, &format!("/monitors/{monitor_id}/stats")))
+            .await
+    }
+
+    /// Get the numeric metrics recorded by a Monitor's most recent Jobs.
+    pub async fn get_job_metrics(&self, monitor_id: Uuid) -> Result<JobMetrics, Error> {
+        self.fetch(self.request(Method::GET, &format!("/monitors/{monitor_id}/job-metrics")))
+            .await
+    }
+
+    /// Create a new Monitor.
+    pub async fn create_monitor(&self, monitor: &MonitorData) -> Result<Monitor, Error> {
+        self.fetch(self.request(Method::POST, "/monitors").json(monitor))
+            .await
+    }
+
+    /// Update a Monitor's attributes.
+    pub async fn update_monitor(
+        &self,
+        monitor_id: Uuid,
+        monitor: &MonitorData,
+    ) -> Result<Monitor, Error> {
+        self.fetch(
+            self.request(Method::PATCH, &format!("/monitors/{monitor_id}"))
+                .json(monitor),
+        )
+        .await
+    }
+
+    /// Pause a Monitor, so that it doesn't alert until it's resumed.
+    pub async fn pause_monitor(
+        &self,
+        monitor_id: Uuid,
+        info: &PauseInfo,
+    ) -> Result<Monitor, Error> {
+        self.fetch(
+            self.request(Method::POST, &format!("/monitors/{monitor_id}/pause"))
+                .json(info),
+        )
+        .await
+    }
+
+    /// Resume a paused Monitor.
+    pub async fn resume_monitor(&self, monitor_id: Uuid) -> Result<Monitor, Error> {
+        self.fetch(self.request(Method::POST, &format!("/monitors/{monitor_id}/resume")))
+            .await
+    }
+
+    /// Delete a Monitor, along with its Jobs.
+    pub async fn delete_monitor(&self, monitor_id: Uuid) -> Result<(), Error> {
+        self.send(self.request(Method::DELETE, &format!("/monitors/{monitor_id}")))
+            .await
+    }
+
+    /// List a page of a Monitor's Jobs.
+    pub async fn list_jobs(&self, monitor_id: Uuid, filter: &JobFilter) -> Result<JobPage, Error> {
+        let page: Page<Job> = self
+            .fetch_page(
+                self.request(Method::GET, &format!("/monitors/{monitor_id}/jobs"))
+                    .query(filter),
+            )
+            .await?;
+
+        Ok(JobPage {
+            jobs: page.data,
+            total: page.paging.total,
+            next_cursor: page.paging.next_cursor,
+        })
+    }
+
+    /// Get one of a Monitor's Jobs.
+    pub async fn get_job(&self, monitor_id: Uuid, job_id: Uuid) -> Result<Job, Error> {
+        self.fetch(self.request(
+            Method::GET,
+            &format!("/monitors/{monitor_id}/jobs/{job_id}"),
+        ))
+        .await
+    }
+
+    /// Start a Job for a Monitor, returning the ID of the new Job.
+    pub async fn start_job(&self, monitor_id: Uuid) -> Result<Uuid, Error> {
+        let started_job: StartedJob = self
+            .fetch(self.request(Method::POST, &format!("/monitors/{monitor_id}/jobs/start")))
+            .await?;
+
+        Ok(started_job.job_id)
+    }
+
+    /// Start a Job for a Monitor, returning a guard that finishes the Job if it's dropped before
+    /// being finished explicitly.
+    pub async fn start_guarded_job(&self, monitor_id: Uuid) -> Result<JobGuard, Error> {
+        let job_id = self.start_job(monitor_id).await?;
+
+        Ok(JobGuard::new(self.clone(), monitor_id, job_id))
+    }
+
+    /// Finish one of a Monitor's Jobs.
+    pub async fn finish_job(
+        &self,
+        monitor_id: Uuid,
+        job_id: Uuid,
+        info: &FinishJobInfo,
+    ) -> Result<Job, Error> {
+        self.fetch(
+            self.request(
+                Method::POST,
+                &format!("/monitors/{monitor_id}/jobs/{job_id}/finish"),
+            )
+            .json(info),
+        )
+        .await
+    }
+
+    /// Record a heartbeat for a heartbeat Monitor, returning the Job it was recorded as.
+    pub async fn record_heartbeat(&self, monitor_id: Uuid) -> Result<Job, Error> {
+        self.fetch(self.request(
+            Method::POST,
+            &format!("/monitors/{monitor_id}/jobs/heartbeat"),
+        ))
+        .await
+    }
+
+    /// List all API keys.
+    pub async fn list_keys(&self) -> Result<Vec<ApiKey>, Error> {
+        self.fetch(self.request(Method::GET, "/keys")).await
+    }
+
+    /// Generate a new API key, returning the key itself. This is the only time that the key is
+    /// available.
+    pub async fn generate_key(&self, info: &GenerateKeyInfo) -> Result<String, Error> {
+        let generated_key: GeneratedKey = self
+            .fetch(self.request(Method::POST, "/keys").json(info))
+            .await?;
+
+        Ok(generated_key.key)
+    }
+
+    /// Issue a replacement for an API key, returning the new key. The old key keeps working until
+    /// the overlap has passed.
+    pub async fn rotate_key(
+        &self,
+        api_key_id: Uuid,
+        info: &RotateKeyInfo,
+    ) -> Result<String, Error> {
+        let generated_key: GeneratedKey = self
+            .fetch(
+                self.request(Method::POST, &format!("/keys/{api_key_id}/rotate"))
+                    .json(info),
+            )
+            .await?;
+
+        Ok(generated_key.key)
+    }
+
+    /// Revoke an API key.
+    pub async fn revoke_key(&self, api_key_id: Uuid) -> Result<(), Error> {
+        self.send(self.request(Method::DELETE, &format!("/keys/{api_key_id}")))
+            .await
+    }
+
+    /// List all alert configurations.
+    pub async fn list_alert_configs(&self) -> Result<Vec<AlertConfigSummary>, Error> {
+        self.fetch(self.request(Method::GET, "/alert-configs"))
+            .await
+    }
+
+    /// Get an alert configuration, along with the Monitors using it.
+    pub async fn get_alert_config(&self, alert_config_id: Uuid) -> Result<AlertConfig, Error> {
+        self.fetch(self.request(Method::GET, &format!("/alert-configs/{alert_config_id}")))
+            .await
+    }
+
+    /// Create a new alert configuration.
+    pub async fn create_alert_config(
+        &self,
+        alert_config: &AlertConfigData,
+    ) -> Result<AlertConfig, Error> {
+        self.fetch(
+            self.request(Method::POST, "/alert-configs")
+                .json(alert_config),
+        )
+        .await
+    }
+
+    /// Update an alert configuration.
+    pub async fn update_alert_config(
+        &self,
+        alert_config_id: Uuid,
+        alert_config: &AlertConfigData,
+    ) -> Result<AlertConfig, Error> {
+        self.fetch(
+            self.request(Method::PATCH, &format!("/alert-configs/{alert_config_id}"))
+                .json(alert_config),
+        )
+        .await
+    }
+
+    /// Delete an alert configuration.
+    pub async fn delete_alert_config(&self, alert_config_id: Uuid) -> Result<(), Error> {
+        self.send(self.request(Method::DELETE, &format!("/alert-configs/{alert_config_id}")))
+            .await
+    }
+
+    /// Send a test alert using an alert configuration.
+    pub async fn test_alert_config(&self, alert_config_id: Uuid) -> Result<(), Error> {
+        self.send(self.request(
+            Method::POST,
+            &format!("/alert-configs/{alert_config_id}/test"),
+        ))
+        .await
+    }
+
+    /// List the alert configurations used by a Monitor.
+    pub async fn list_monitor_alert_configs(
+        &self,
+        monitor_id: Uuid,
+    ) -> Result<Vec<AlertConfigSummary>, Error> {
+        self.fetch(self.request(
+            Method::GET,
+            &format!("/monitors/{monitor_id}/alert-configs"),
+        ))
+        .await
+    }
+
+    /// Start using alert configurations for a Monitor.
+    pub async fn associate_alert_configs(
+        &self,
+        monitor_id: Uuid,
+        alert_configs: &MonitorAssociationData,
+    ) -> Result<(), Error> {
+        self.send(
+            self.request(
+                Method::POST,
+                &format!("/monitors/{monitor_id}/alert-configs"),
+            )
+            .json(alert_configs),
+        )
+        .await
+    }
+
+    /// Stop using an alert configuration for a Monitor.
+    pub async fn disassociate_alert_config(
+        &self,
+        monitor_id: Uuid,
+        alert_config_id: Uuid,
+    ) -> Result<(), Error> {
+        self.send(self.request(
+            Method::DELETE,
+            &format!("/monitors/{monitor_id}/alert-configs/{alert_config_id}"),
+        ))
+        .await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}/api/v1{path}", self.base_url));
+
+        match &self.auth {
+            Auth::Jwt(token) => request.bearer_auth(token),
+            Auth::ApiKey(key) => request.header("X-API-Key", key),
+        }
+    }
+
+    /// Send a request, returning the resource from its response.
+    async fn fetch<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, Error> {
+        let data: Data<T> = self.fetch_page(request).await?;
+
+        Ok(data.data)
+    }
+
+    /// Send a request, returning its entire response body.
+    async fn fetch_page<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, Error> {
+        let response = request.send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::from_response(
+                status.as_u16(),
+                &response.text().await?,
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Send a request that doesn't return anything.
+    async fn send(&self, request: RequestBuilder) -> Result<(), Error> {
+        let response = request.send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::from_response(
+                status.as_u16(),
+                &response.text().await?,
+            ));
+        }
+
+        Ok(())
+    }
+}
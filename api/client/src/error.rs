@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter, Result};
+
+use serde::Deserialize;
+
+/// Errors from calling the CronMon API.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// The request couldn't be sent, or its response couldn't be read - for example, if the API
+    /// is unreachable.
+    RequestError(String),
+    /// The API responded with an error.
+    ApiError {
+        status: u16,
+        reason: String,
+        description: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::RequestError(reason) => write!(f, "Failed to call the CronMon API: {reason}"),
+            Self::ApiError {
+                status,
+                reason,
+                description,
+            } => write!(f, "{reason} ({status}): {description}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Self::RequestError(error.to_string())
+    }
+}
+
+/// The body of the API's error responses.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    reason: String,
+    description: String,
+}
+
+impl Error {
+    /// Build an error from an error response. Responses that don't have the API's usual error
+    /// body - from a proxy, for example - are described by their body as it is.
+    pub(crate) fn from_response(status: u16, body: &str) -> Self {
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(ErrorBody { error }) => Self::ApiError {
+                status,
+                reason: error.reason,
+                description: error.description,
+            },
+            Err(_) => Self::ApiError {
+                status,
+                reason: "Unexpected Response".to_owned(),
+                description: body.to_owned(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        r#"{"error": {"code": 404, "reason": "Monitor Not Found", "description": "Failed to find monitor with id 'foo'"}}"#,
+        "Monitor Not Found",
+        "Failed to find monitor with id 'foo'"
+    )]
+    #[case("Bad Gateway", "Unexpected Response", "Bad Gateway")]
+    fn building_errors_from_responses(
+        #[case] body: &str,
+        #[case] expected_reason: &str,
+        #[case] expected_description: &str,
+    ) {
+        let error = Error::from_response(404, body);
+
+        assert_eq!(
+            error,
+            Error::ApiError {
+                status: 404,
+                reason: expected_reason.to_owned(),
+                description: expected_description.to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn displaying_errors() {
+        let error = Error::ApiError {
+            status: 400,
+            reason: "Job Already Finished".to_owned(),
+            description: "Job 'foo' is already finished".to_owned(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Job Already Finished (400): Job 'foo' is already finished"
+        );
+    }
+}
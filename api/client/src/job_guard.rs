@@ -0,0 +1,114 @@
+use std::thread;
+
+use tokio::runtime::Handle;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::models::Job;
+use crate::types::{FinishJobInfo, JobMetadataData};
+
+/// The output recorded for Jobs that are finished because the code running them panicked.
+const PANICKED_OUTPUT: &str = "Job panicked";
+
+/// The output recorded for Jobs that are finished because their guard was dropped otherwise.
+const DROPPED_OUTPUT: &str = "Job guard dropped without being finished";
+
+/// A Job that's in progress, which is finished when the guard is dropped if it hasn't been
+/// finished explicitly.
+///
+/// Only finishing the Job explicitly can report it as having succeeded. Jobs dropped without being
+/// finished - whether whilst panicking or otherwise, for example by returning early with `?` - are
+/// finished as having failed. Finishing on drop happens in the background on the current Tokio
+/// runtime, so may not complete if the runtime is shut down straight afterwards; call `finish`
+/// wherever possible.
+#[derive(Debug)]
+pub struct JobGuard {
+    client: Client,
+    monitor_id: Uuid,
+    job_id: Uuid,
+    finished: bool,
+}
+
+impl JobGuard {
+    pub(crate) fn new(client: Client, monitor_id: Uuid, job_id: Uuid) -> Self {
+        Self {
+            client,
+            monitor_id,
+            job_id,
+            finished: false,
+        }
+    }
+
+    /// The ID of the Job.
+    pub fn job_id(&self) -> Uuid {
+        self.job_id
+    }
+
+    /// Finish the Job.
+    pub async fn finish(self, succeeded: bool, output: Option<String>) -> Result<Job, Error> {
+        self.finish_with_metadata(succeeded, output, JobMetadataData::default())
+            .await
+    }
+
+    /// Finish the Job, recording structured details of how it ran, such as metrics.
+    pub async fn finish_with_metadata(
+        mut self,
+        succeeded: bool,
+        output: Option<String>,
+        metadata: JobMetadataData,
+    ) -> Result<Job, Error> {
+        self.finished = true;
+
+        self.client
+            .finish_job(
+                self.monitor_id,
+                self.job_id,
+                &FinishJobInfo {
+                    succeeded,
+                    output,
+                    metadata,
+                },
+            )
+            .await
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        let output = if thread::panicking() {
+            PANICKED_OUTPUT
+        } else {
+            DROPPED_OUTPUT
+        };
+        let info = FinishJobInfo {
+            succeeded: false,
+            output: Some(output.to_owned()),
+            metadata: JobMetadataData::default(),
+        };
+        let (client, monitor_id, job_id) = (self.client.clone(), self.monitor_id, self.job_id);
+
+        let Ok(runtime) = Handle::try_current() else {
+            warn!(
+                monitor_id = monitor_id.to_string(),
+                job_id = job_id.to_string(),
+                "Dropped Job outside of a Tokio runtime, so it can't be finished"
+            );
+            return;
+        };
+        runtime.spawn(async move {
+            if let Err(error) = client.finish_job(monitor_id, job_id, &info).await {
+                warn!(
+                    monitor_id = monitor_id.to_string(),
+                    job_id = job_id.to_string(),
+                    "Failed to finish dropped Job: {error}"
+                );
+            }
+        });
+    }
+}
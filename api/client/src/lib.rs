@@ -0,0 +1,25 @@
+//! A typed client for the CronMon API.
+//!
+//! ```no_run
+//! use cron_mon_client::{Auth, Client};
+//! use uuid::Uuid;
+//!
+//! # async fn example(monitor_id: Uuid) -> Result<(), cron_mon_client::Error> {
+//! let client = Client::new("https://cron-mon.example.com", Auth::ApiKey("<key>".to_owned()));
+//!
+//! let job = client.start_guarded_job(monitor_id).await?;
+//! // ... do the work ...
+//! job.finish(true, Some("Backed up 10 tables".to_owned())).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod job_guard;
+pub mod models;
+pub mod types;
+
+pub use client::{Auth, Client};
+pub use error::Error;
+pub use job_guard::JobGuard;
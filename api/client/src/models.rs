@@ -0,0 +1,248 @@
+//! The resources returned by the CronMon API.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::types::{
+    AlertThresholdData, AnomalyDetectionData, ApiKeyScope, AutoTuneData, ConcurrencyPolicy,
+    JobMetadataData, JobRetentionData, MaintenanceWindowData, MonitorKind, ScheduleData,
+};
+
+/// A Monitor, along with its history of Jobs.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Monitor {
+    pub monitor_id: Uuid,
+    pub name: String,
+    pub expected_duration: i32,
+    pub grace_duration: i32,
+    pub kind: MonitorKind,
+    pub schedule: Option<ScheduleData>,
+    pub alert_threshold: AlertThresholdData,
+    pub retention: Option<JobRetentionData>,
+    pub labels: BTreeMap<String, String>,
+    /// Whether or not the Monitor is paused, in which case it doesn't alert.
+    pub paused: bool,
+    /// When the Monitor is automatically resumed, if it's paused until a given time.
+    pub resume_at: Option<NaiveDateTime>,
+    pub maintenance_windows: Vec<MaintenanceWindowData>,
+    /// The bounds within which the Monitor's durations are automatically tuned, if they are.
+    #[serde(default)]
+    pub auto_tune: Option<AutoTuneData>,
+    /// How the Monitor detects Jobs that took an anomalous amount of time, if it does.
+    #[serde(default)]
+    pub anomaly_detection: Option<AnomalyDetectionData>,
+    /// What happens when a Job starts while another of the Monitor's Jobs is still in progress.
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    pub jobs: Vec<Job>,
+}
+
+/// A Monitor as it's listed, with only its most recent Jobs.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MonitorSummary {
+    pub monitor_id: Uuid,
+    pub name: String,
+    pub expected_duration: i32,
+    pub grace_duration: i32,
+    pub kind: MonitorKind,
+    pub schedule: Option<ScheduleData>,
+    pub alert_threshold: AlertThresholdData,
+    pub retention: Option<JobRetentionData>,
+    pub labels: BTreeMap<String, String>,
+    /// Whether or not the Monitor is paused, in which case it doesn't alert.
+    pub paused: bool,
+    /// When the Monitor is automatically resumed, if it's paused until a given time.
+    pub resume_at: Option<NaiveDateTime>,
+    pub maintenance_windows: Vec<MaintenanceWindowData>,
+    /// The bounds within which the Monitor's durations are automatically tuned, if they are.
+    #[serde(default)]
+    pub auto_tune: Option<AutoTuneData>,
+    /// How the Monitor detects Jobs that took an anomalous amount of time, if it does.
+    #[serde(default)]
+    pub anomaly_detection: Option<AnomalyDetectionData>,
+    /// What happens when a Job starts while another of the Monitor's Jobs is still in progress.
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    pub last_finished_job: Option<Job>,
+    pub last_started_job: Option<Job>,
+}
+
+/// A monitored Job.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Job {
+    pub job_id: Uuid,
+    pub start_time: NaiveDateTime,
+    /// When the Job finished, if it isn't in progress.
+    pub end_time: Option<NaiveDateTime>,
+    /// Whether or not the Job succeeded, if it isn't in progress.
+    pub succeeded: Option<bool>,
+    pub output: Option<String>,
+    /// How long the Job ran for, in seconds, if it isn't in progress.
+    pub duration: Option<u64>,
+    pub in_progress: bool,
+    pub late: bool,
+    /// Whether or not the Job ran while its Monitor was paused or in a maintenance window.
+    #[serde(default)]
+    pub during_maintenance: bool,
+    /// Whether or not the Job started while another of its Monitor's Jobs was still in progress.
+    #[serde(default)]
+    pub overlapped: bool,
+    /// Structured details of how the Job ran, if it isn't in progress.
+    #[serde(flatten)]
+    pub metadata: JobMetadataData,
+}
+
+/// Statistics describing how long a Monitor's Jobs took, along with suggested durations.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MonitorStats {
+    /// The statistics, unless no Jobs have finished successfully.
+    pub stats: Option<DurationStats>,
+    /// The suggested durations, unless too few Jobs have finished successfully.
+    pub suggested: Option<SuggestedDurations>,
+}
+
+/// Statistics describing the durations of a Monitor's most recent successful Jobs, in seconds.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct DurationStats {
+    pub job_count: usize,
+    pub p50: u64,
+    pub p95: u64,
+    pub p99: u64,
+    pub max: u64,
+    pub std_dev: f64,
+}
+
+/// The numeric metrics recorded by a Monitor's most recent Jobs, as a series of values (oldest
+/// first) for each metric, by name.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct JobMetrics {
+    pub job_count: usize,
+    pub metrics: BTreeMap<String, Vec<MetricPoint>>,
+}
+
+/// The value of a metric recorded by a Job.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MetricPoint {
+    pub job_id: Uuid,
+    pub end_time: NaiveDateTime,
+    pub value: f64,
+}
+
+/// An expected and grace duration suggested for a Monitor, in seconds.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SuggestedDurations {
+    pub expected_duration: i32,
+    pub grace_duration: i32,
+}
+
+/// A page of a Monitor's Jobs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobPage {
+    pub jobs: Vec<Job>,
+    /// The number of Jobs matching the filter, across all pages.
+    pub total: usize,
+    /// The cursor to request the next page with, or `None` if this is the last page.
+    pub next_cursor: Option<Uuid>,
+}
+
+/// Which of a Monitor's Jobs to list, and how.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct JobFilter {
+    pub status: Option<JobStatus>,
+    pub started_after: Option<NaiveDateTime>,
+    pub started_before: Option<NaiveDateTime>,
+    pub order: Option<JobOrder>,
+    /// The most Jobs to list. The API defaults to 25.
+    pub limit: Option<u32>,
+    /// The cursor from the previous page, to list the page after it.
+    pub cursor: Option<Uuid>,
+}
+
+/// The status of a Job, used to filter Jobs.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Succeeded,
+    Failed,
+    Late,
+    InProgress,
+}
+
+/// The order to list Jobs in, by when they started.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobOrder {
+    Desc,
+    Asc,
+}
+
+/// An API key, without the key itself.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ApiKey {
+    pub api_key_id: Uuid,
+    pub name: String,
+    /// The key with all but its first and last few characters masked.
+    pub masked: String,
+    pub scopes: Vec<ApiKeyScope>,
+    /// The only Monitors that the API key can be used for, if it's restricted to some.
+    pub monitor_ids: Option<Vec<Uuid>>,
+    pub last_used: Option<KeyUsage>,
+    pub created: NaiveDateTime,
+    /// When the API key expires, if it does.
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+/// When an API key was last used, and by which Monitor.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct KeyUsage {
+    pub time: NaiveDateTime,
+    pub monitor_id: Uuid,
+    pub monitor_name: String,
+}
+
+/// An alert configuration, along with the Monitors using it.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct AlertConfig {
+    pub alert_config_id: Uuid,
+    pub name: String,
+    pub active: bool,
+    pub on_late: bool,
+    pub on_error: bool,
+    pub on_recovery: bool,
+    pub on_anomaly: bool,
+    /// The label selector that picks out further Monitors to apply to, if there is one.
+    pub label_selector: Option<String>,
+    /// The type of alert and its type-specific configuration.
+    #[serde(rename = "type")]
+    pub type_: serde_json::Value,
+    pub monitors: Vec<AppliedMonitor>,
+}
+
+/// A Monitor using an alert configuration.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct AppliedMonitor {
+    pub monitor_id: Uuid,
+    pub name: String,
+}
+
+/// An alert configuration as it's listed.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct AlertConfigSummary {
+    pub alert_config_id: Uuid,
+    pub name: String,
+    pub active: bool,
+    pub on_late: bool,
+    pub on_error: bool,
+    pub on_recovery: bool,
+    pub on_anomaly: bool,
+    /// The label selector that picks out further Monitors to apply to, if there is one.
+    pub label_selector: Option<String>,
+    /// The type of alert, i.e `slack`.
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// The number of Monitors using the alert configuration.
+    pub monitors: usize,
+}
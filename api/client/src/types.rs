@@ -0,0 +1,396 @@
+//! The request bodies accepted by the CronMon API. These are shared with the API itself, which
+//! deserializes requests into them, so that clients and the API can't drift apart.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The attributes of a Monitor, used when creating and updating Monitors.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MonitorData {
+    /// The Monitor's name (typically the command or filename that the cronjob will invoke).
+    pub name: String,
+    /// The expected duration of the monitored cronjob, in seconds.
+    pub expected_duration: i32,
+    /// The amount of time, in seconds, to allow the monitored cronjob to overrun by before
+    /// considering them late.
+    pub grace_duration: i32,
+    /// The cron schedule that the monitored cronjob is expected to start on, if it has one.
+    pub schedule: Option<ScheduleData>,
+    /// The kind of Monitor, which determines how it expects to hear from the monitored cronjob.
+    #[serde(default)]
+    pub kind: MonitorKind,
+    /// How many late or errored Jobs the Monitor must see before it alerts on them.
+    #[serde(default)]
+    pub alert_threshold: AlertThresholdData,
+    /// How many of the Monitor's Jobs are kept, and for how long, if they aren't kept forever.
+    pub retention: Option<JobRetentionData>,
+    /// Free-form key/value pairs describing the Monitor, i.e `{"team": "payments"}`, which
+    /// Monitors can be selected by.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Recurring windows of planned downtime, during which the Monitor doesn't alert.
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindowData>,
+    /// If set, the bounds within which the Monitor's expected and grace durations are
+    /// automatically tuned to suit how long its Jobs take.
+    #[serde(default)]
+    pub auto_tune: Option<AutoTuneData>,
+    /// If set, how the Monitor detects Jobs that succeeded, but took an anomalous amount of time.
+    #[serde(default)]
+    pub anomaly_detection: Option<AnomalyDetectionData>,
+    /// What happens when a Job starts while another of the Monitor's Jobs is still in progress.
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+}
+
+/// A cron schedule, and the timezone that it's evaluated in.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ScheduleData {
+    /// The cron expression, i.e `0 3 * * *`.
+    pub expression: String,
+    /// The IANA timezone that the cron expression is evaluated in, i.e `Europe/London`. Defaults
+    /// to UTC.
+    pub timezone: Option<String>,
+}
+
+/// A recurring window of planned downtime, which starts on a cron schedule and lasts for a fixed
+/// number of minutes.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MaintenanceWindowData {
+    /// The cron expression that the window starts on, i.e `0 2 * * 0`.
+    pub expression: String,
+    /// The IANA timezone that the cron expression is evaluated in, i.e `Europe/London`. Defaults
+    /// to UTC.
+    pub timezone: Option<String>,
+    /// How long the window lasts for each time it starts, in minutes.
+    pub duration_minutes: i32,
+}
+
+/// The different kinds of Monitor.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorKind {
+    /// A Monitor for cronjobs that report when they start and when they finish.
+    #[default]
+    Job,
+    /// A Monitor for cronjobs that only send a single ping (a heartbeat) each time they run, which
+    /// is recorded as a completed Job. A heartbeat is expected at least every `period` seconds.
+    Heartbeat { period: i32 },
+}
+
+/// What a Monitor does when a Job starts while another of its Jobs is still in progress.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcurrencyPolicy {
+    /// Overlapping Jobs are allowed.
+    #[default]
+    Allow,
+    /// Overlapping Jobs are allowed, but alerted on.
+    Alert,
+    /// Overlapping Jobs are rejected.
+    Reject,
+}
+
+impl ConcurrencyPolicy {
+    /// Every concurrency policy.
+    pub const ALL: [ConcurrencyPolicy; 3] = [
+        ConcurrencyPolicy::Allow,
+        ConcurrencyPolicy::Alert,
+        ConcurrencyPolicy::Reject,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Alert => "alert",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+impl Display for ConcurrencyPolicy {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ConcurrencyPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.as_str() == value)
+            .ok_or_else(|| format!("Unknown concurrency policy '{value}'"))
+    }
+}
+
+/// How many late or errored Jobs a Monitor must see before it alerts on them.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AlertThresholdData {
+    /// The number of consecutive late or errored Jobs needed before alerting.
+    pub consecutive: i32,
+    /// If set, the number of late or errored Jobs that are also needed within the most recent
+    /// Jobs before alerting.
+    pub window: Option<FailureWindow>,
+}
+
+impl Default for AlertThresholdData {
+    /// By default, every late or errored Job is alerted on.
+    fn default() -> Self {
+        Self {
+            consecutive: 1,
+            window: None,
+        }
+    }
+}
+
+/// A number of late or errored Jobs (`failures`) within a number of the most recent Jobs
+/// (`runs`).
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FailureWindow {
+    pub failures: i32,
+    pub runs: i32,
+}
+
+/// How many of a Monitor's Jobs are kept, and for how long, before they're pruned.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct JobRetentionData {
+    /// If set, the number of days that Jobs are kept for after they start.
+    pub max_age_days: Option<i32>,
+    /// If set, the number of most recent Jobs that are kept.
+    pub max_jobs: Option<i32>,
+}
+
+/// The bounds within which a Monitor's expected and grace durations are automatically tuned, in
+/// seconds.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AutoTuneData {
+    pub min_expected_duration: i32,
+    pub max_expected_duration: i32,
+    pub min_grace_duration: i32,
+    pub max_grace_duration: i32,
+}
+
+/// How a Monitor detects Jobs that succeeded, but took an anomalous amount of time. At least one
+/// of the two must be set.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AnomalyDetectionData {
+    /// If set, Jobs that take less than this many seconds are anomalous.
+    #[serde(default)]
+    pub min_duration: Option<i32>,
+    /// If set, Jobs that take more than this many standard deviations more or less than the
+    /// median of recent Jobs are anomalous.
+    #[serde(default)]
+    pub max_deviations: Option<f64>,
+}
+
+/// The outcome of a Job, used when finishing it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FinishJobInfo {
+    /// Whether or not the Job finished successfully.
+    pub succeeded: bool,
+    /// Any output from the Job.
+    pub output: Option<String>,
+    /// Structured details of how the Job ran.
+    #[serde(flatten)]
+    pub metadata: JobMetadataData,
+}
+
+/// Structured details of how a Job ran, all of which are optional.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct JobMetadataData {
+    /// The exit code of the Job's command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// The host that the Job ran on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// The command that the Job ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// The version of the Job that ran, such as a git SHA.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Numeric metrics recorded by the Job, such as the number of rows processed, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, f64>,
+}
+
+/// How to pause a Monitor.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PauseInfo {
+    /// If set, when the Monitor is automatically resumed. Otherwise, the Monitor stays paused
+    /// until it's resumed explicitly.
+    #[serde(default)]
+    pub resume_at: Option<NaiveDateTime>,
+}
+
+/// The attributes of a new API key.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GenerateKeyInfo {
+    /// The name of the API key.
+    pub name: String,
+    /// What the API key can be used for, which mustn't be empty. Defaults to everything.
+    #[serde(default)]
+    pub scopes: Option<Vec<ApiKeyScope>>,
+    /// If set, the only Monitors that the API key can be used for.
+    #[serde(default)]
+    pub monitor_ids: Option<Vec<Uuid>>,
+    /// If set, when the API key expires.
+    #[serde(default)]
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+/// How to rotate an API key.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RotateKeyInfo {
+    /// How long the old API key keeps working for after the replacement is issued, in seconds.
+    /// Defaults to an hour.
+    #[serde(default)]
+    pub overlap_seconds: Option<u32>,
+}
+
+/// Something that an API key can be used for.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum ApiKeyScope {
+    /// Starting and finishing Jobs.
+    #[serde(rename = "jobs:write")]
+    JobsWrite,
+    /// Recording heartbeats.
+    #[serde(rename = "heartbeats:write")]
+    HeartbeatsWrite,
+}
+
+impl ApiKeyScope {
+    /// Every scope, as given to API keys that aren't restricted to particular scopes.
+    pub const ALL: [ApiKeyScope; 2] = [ApiKeyScope::JobsWrite, ApiKeyScope::HeartbeatsWrite];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::JobsWrite => "jobs:write",
+            Self::HeartbeatsWrite => "heartbeats:write",
+        }
+    }
+}
+
+impl Display for ApiKeyScope {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == value)
+            .ok_or_else(|| format!("Unknown API key scope '{value}'"))
+    }
+}
+
+/// The attributes of an alert configuration, used when creating and updating them.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AlertConfigData {
+    /// The name of the alert configuration.
+    pub name: String,
+    /// Whether or not the alert configuration is active.
+    pub active: bool,
+    /// Whether or not to alert on late Jobs.
+    pub on_late: bool,
+    /// Whether or not to alert on errored Jobs.
+    pub on_error: bool,
+    /// Whether or not to alert when a Monitor recovers from late or errored Jobs.
+    #[serde(default)]
+    pub on_recovery: bool,
+    /// Whether or not to alert on Jobs that succeeded, but took an anomalous amount of time.
+    #[serde(default)]
+    pub on_anomaly: bool,
+    /// If set, the alert configuration also applies to every Monitor whose labels match this
+    /// label selector, i.e `team=payments,env!=dev`, without needing to be associated with them.
+    #[serde(default)]
+    pub label_selector: Option<String>,
+    /// The type of alert and its type-specific configuration, i.e
+    /// `{"slack": {"channel": "#alerts", "token": "..."}}`.
+    #[serde(rename = "type")]
+    pub type_: serde_json::Value,
+}
+
+/// The alert configurations to associate with a Monitor.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MonitorAssociationData {
+    /// The IDs of the alert configurations.
+    pub alert_config_ids: Vec<Uuid>,
+}
+
+/// The attributes of a webhook subscription, used when creating and updating them.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WebhookSubscriptionData {
+    /// The name of the webhook subscription.
+    pub name: String,
+    /// The URL that events are sent to.
+    pub url: String,
+    /// The types of event that are sent to the URL.
+    pub event_types: Vec<EventType>,
+    /// Whether or not events are currently sent.
+    pub active: bool,
+    /// An optional secret, used to sign each request body with HMAC-SHA256.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// The types of lifecycle event that webhook subscriptions can be sent.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    MonitorCreated,
+    MonitorUpdated,
+    MonitorDeleted,
+    JobStarted,
+    JobFinished,
+}
+
+impl EventType {
+    /// Every type of lifecycle event.
+    pub const ALL: [EventType; 5] = [
+        EventType::MonitorCreated,
+        EventType::MonitorUpdated,
+        EventType::MonitorDeleted,
+        EventType::JobStarted,
+        EventType::JobFinished,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MonitorCreated => "monitor_created",
+            Self::MonitorUpdated => "monitor_updated",
+            Self::MonitorDeleted => "monitor_deleted",
+            Self::JobStarted => "job_started",
+            Self::JobFinished => "job_finished",
+        }
+    }
+}
+
+impl Display for EventType {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for EventType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == value)
+            .ok_or_else(|| format!("Unknown event type '{value}'"))
+    }
+}
@@ -0,0 +1,39 @@
+use rocket;
+use rocket::response::stream::{Event, EventStream};
+use rocket::{Shutdown, State};
+use tokio::sync::broadcast::{error::RecvError, Sender};
+
+use crate::domain::models::ActivityEvent;
+use crate::infrastructure::auth::Jwt;
+
+#[rocket::get("/activity")]
+pub async fn stream_activity(
+    activity: &State<Sender<ActivityEvent>>,
+    jwt: Jwt,
+    mut shutdown: Shutdown,
+) -> EventStream![] {
+    let mut receiver = activity.subscribe();
+    // Tokens are only checked on connecting, so the stream is closed once the token expires rather
+    // than outliving it.
+    let mut expired = Box::pin(tokio::time::sleep(jwt.expires_in()));
+
+    EventStream! {
+        loop {
+            let event = tokio::select! {
+                event = receiver.recv() => match event {
+                    Ok(event) => event,
+                    Err(RecvError::Closed) => break,
+                    // Clients that can't keep up miss the oldest events, rather than being cut
+                    // off.
+                    Err(RecvError::Lagged(_)) => continue,
+                },
+                _ = &mut shutdown => break,
+                _ = &mut expired => break,
+            };
+
+            if event.tenant == jwt.tenant {
+                yield Event::json(&event).event(event.activity.as_str());
+            }
+        }
+    }
+}
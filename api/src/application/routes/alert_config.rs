@@ -1,8 +1,8 @@
+use cron_mon_client::types::MonitorAssociationData;
 use rocket;
 use rocket::response::status::NoContent;
 use rocket::serde::json::Json;
 use rocket::State;
-use serde::Deserialize;
 use serde_json::{json, Value};
 use uuid::Uuid;
 
@@ -13,17 +13,12 @@ use crate::application::services::{
     get_test_alert_config_service, get_update_alert_config_service,
 };
 use crate::errors::Error;
-use crate::infrastructure::auth::Jwt;
+use crate::infrastructure::auth::{Jwt, Role};
 use crate::infrastructure::database::DbPool;
 use crate::infrastructure::paging::Paging;
 use crate::infrastructure::repositories::alert_config::AlertConfigRepository;
 use crate::infrastructure::repositories::Repository;
 
-#[derive(Deserialize)]
-pub struct MonitorAssociationData {
-    alert_config_ids: Vec<Uuid>,
-}
-
 #[rocket::get("/alert-configs")]
 pub async fn list_alert_configs(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Error> {
     let mut repo = AlertConfigRepository::new(pool);
@@ -36,11 +31,14 @@ pub async fn list_alert_configs(pool: &State<DbPool>, jwt: Jwt) -> Result<Value,
             "active": ac.active,
             "on_late": ac.on_late,
             "on_error": ac.on_error,
+            "on_recovery": ac.on_recovery,
+            "on_anomaly": ac.on_anomaly,
+            "label_selector": ac.label_selector,
             "monitors": ac.monitors.len(),
             "type": ac.type_.to_string()
         }))
         .collect::<Value>(),
-        "paging": Paging { total: alert_configs.len() }
+        "paging": Paging::new(alert_configs.len())
     }))
 }
 
@@ -50,10 +48,12 @@ pub async fn create_alert_config(
     jwt: Jwt,
     new_alert_config: Json<AlertConfigData>,
 ) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut create_alert_config = get_create_alert_config_service(pool);
 
     let alert_config = create_alert_config
-        .create_from_value(&jwt.tenant, new_alert_config.into_inner())
+        .create_from_value(&jwt.tenant, new_alert_config.into_inner(), &jwt.actor())
         .await?;
 
     Ok(json!({"data": alert_config}))
@@ -82,6 +82,8 @@ pub async fn update_alert_config(
     alert_config_id: Uuid,
     updated_alert_config: Json<AlertConfigData>,
 ) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut update_alert_config = get_update_alert_config_service(pool);
 
     let alert_config = update_alert_config
@@ -89,6 +91,7 @@ pub async fn update_alert_config(
             alert_config_id,
             &jwt.tenant,
             updated_alert_config.into_inner(),
+            &jwt.actor(),
         )
         .await?;
 
@@ -101,10 +104,12 @@ pub async fn delete_alert_config(
     jwt: Jwt,
     alert_config_id: Uuid,
 ) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Admin)?;
+
     let mut delete_alert_config = get_delete_alert_config_service(pool);
 
     delete_alert_config
-        .delete_by_id(alert_config_id, &jwt.tenant)
+        .delete_by_id(alert_config_id, &jwt.tenant, &jwt.actor())
         .await?;
 
     Ok(NoContent)
@@ -116,6 +121,8 @@ pub async fn test_alert_config(
     jwt: Jwt,
     alert_config_id: Uuid,
 ) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut test_alert_config = get_test_alert_config_service(pool);
 
     test_alert_config
@@ -144,11 +151,14 @@ pub async fn get_alert_configs_for_monitor(
             "active": ac.active,
             "on_late": ac.on_late,
             "on_error": ac.on_error,
+            "on_recovery": ac.on_recovery,
+            "on_anomaly": ac.on_anomaly,
+            "label_selector": ac.label_selector,
             "monitors": ac.monitors.len(),
             "type": ac.type_.to_string()
         }))
         .collect::<Value>(),
-        "paging": Paging { total: alert_configs.len() }
+        "paging": Paging::new(alert_configs.len())
     }))
 }
 
@@ -159,10 +169,17 @@ pub async fn associate_alert_configs(
     monitor_id: Uuid,
     alert_config_ids: Json<MonitorAssociationData>,
 ) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut service = get_monitor_association_service(pool);
 
     service
-        .associate_alerts(&jwt.tenant, monitor_id, &alert_config_ids.alert_config_ids)
+        .associate_alerts(
+            &jwt.tenant,
+            monitor_id,
+            &alert_config_ids.alert_config_ids,
+            &jwt.actor(),
+        )
         .await?;
 
     Ok(NoContent)
@@ -175,10 +192,12 @@ pub async fn disassociate_alert_config(
     monitor_id: Uuid,
     alert_config_id: Uuid,
 ) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut service = get_monitor_association_service(pool);
 
     service
-        .disassociate_alert(&jwt.tenant, monitor_id, alert_config_id)
+        .disassociate_alert(&jwt.tenant, monitor_id, alert_config_id, &jwt.actor())
         .await?;
 
     Ok(NoContent)
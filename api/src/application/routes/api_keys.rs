@@ -1,3 +1,5 @@
+use chrono::{NaiveDateTime, TimeDelta};
+use cron_mon_client::types::{GenerateKeyInfo, RotateKeyInfo};
 use rocket;
 use rocket::response::status::NoContent;
 use rocket::serde::json::Json;
@@ -6,17 +8,43 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use uuid::Uuid;
 
-use crate::application::services::{get_generate_key_service, get_revoke_key_service};
+use crate::application::services::{
+    get_generate_key_service, get_revoke_key_service, get_rotate_key_service,
+};
+use crate::domain::models::{ApiKey, ApiKeyScope};
 use crate::errors::Error;
-use crate::infrastructure::auth::Jwt;
+use crate::infrastructure::auth::{Jwt, Role};
 use crate::infrastructure::database::DbPool;
 use crate::infrastructure::paging::Paging;
 use crate::infrastructure::repositories::api_key::ApiKeyRepository;
 use crate::infrastructure::repositories::Repository;
 
+/// The attributes of an API key, given by a client when generating it, validated as they're
+/// deserialized.
 #[derive(Deserialize)]
-pub struct GenerateKeyInfo {
-    pub name: String,
+#[serde(try_from = "GenerateKeyInfo")]
+pub struct GenerateKeyAttributes {
+    name: String,
+    scopes: Option<Vec<ApiKeyScope>>,
+    monitor_ids: Option<Vec<Uuid>>,
+    expires_at: Option<NaiveDateTime>,
+}
+
+impl TryFrom<GenerateKeyInfo> for GenerateKeyAttributes {
+    type Error = Error;
+
+    fn try_from(value: GenerateKeyInfo) -> Result<Self, Self::Error> {
+        if let Some(scopes) = &value.scopes {
+            ApiKey::validate_scopes(scopes)?;
+        }
+
+        Ok(Self {
+            name: value.name,
+            scopes: value.scopes,
+            monitor_ids: value.monitor_ids,
+            expires_at: value.expires_at,
+        })
+    }
 }
 
 #[rocket::get("/keys")]
@@ -26,7 +54,7 @@ pub async fn list_api_keys(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Erro
 
     Ok(json!({
         "data": keys,
-        "paging": Paging { total: keys.len() }
+        "paging": Paging::new(keys.len())
     }))
 }
 
@@ -34,18 +62,62 @@ pub async fn list_api_keys(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Erro
 pub async fn generate_key(
     pool: &State<DbPool>,
     jwt: Jwt,
-    info: Json<GenerateKeyInfo>,
+    info: Json<GenerateKeyAttributes>,
 ) -> Result<Value, Error> {
+    jwt.require_role(Role::Admin)?;
+
     let mut service = get_generate_key_service(pool);
-    let key = service.generate_key(&info.name, &jwt.tenant).await?;
+    let key = service
+        .generate_key(
+            &info.name,
+            &jwt.tenant,
+            info.scopes.clone(),
+            info.monitor_ids.clone(),
+            info.expires_at,
+            &jwt.actor(),
+        )
+        .await?;
+
+    Ok(json!({"data": {"key": key}}))
+}
+
+/// How long a rotated API key keeps working for, by default, after its replacement is issued.
+const DEFAULT_ROTATION_OVERLAP_SECONDS: u32 = 60 * 60;
+
+#[rocket::post("/keys/<key_id>/rotate", data = "<info>")]
+pub async fn rotate_key(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    key_id: Uuid,
+    info: Json<RotateKeyInfo>,
+) -> Result<Value, Error> {
+    jwt.require_role(Role::Admin)?;
+
+    let overlap = info
+        .overlap_seconds
+        .unwrap_or(DEFAULT_ROTATION_OVERLAP_SECONDS);
+
+    let mut service = get_rotate_key_service(pool);
+    let key = service
+        .rotate_key(
+            key_id,
+            &jwt.tenant,
+            TimeDelta::seconds(i64::from(overlap)),
+            &jwt.actor(),
+        )
+        .await?;
 
     Ok(json!({"data": {"key": key}}))
 }
 
 #[rocket::delete("/keys/<key_id>")]
 pub async fn revoke_key(pool: &State<DbPool>, jwt: Jwt, key_id: Uuid) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Admin)?;
+
     let mut service = get_revoke_key_service(pool);
-    service.revoke_key(key_id, &jwt.tenant).await?;
+    service
+        .revoke_key(key_id, &jwt.tenant, &jwt.actor())
+        .await?;
 
     Ok(NoContent)
 }
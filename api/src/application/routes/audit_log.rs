@@ -0,0 +1,74 @@
+use rocket;
+use rocket::form::{FromForm, FromFormField};
+use rocket::State;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::application::routes::jobs::OptionalParam;
+use crate::domain::models::audit_query::{DEFAULT_AUDIT_PAGE_SIZE, MAX_AUDIT_PAGE_SIZE};
+use crate::domain::models::{AuditQuery, EntityType};
+use crate::errors::Error;
+use crate::infrastructure::auth::{Jwt, Role};
+use crate::infrastructure::database::DbPool;
+use crate::infrastructure::paging::Paging;
+use crate::infrastructure::repositories::audit_log::{AuditLog, AuditLogRepository};
+
+#[derive(FromForm)]
+pub struct AuditLogParams {
+    entity_type: OptionalParam<EntityTypeParam>,
+    entity_id: OptionalParam<Uuid>,
+    actor: OptionalParam<String>,
+    #[field(
+        default = DEFAULT_AUDIT_PAGE_SIZE,
+        validate = range(1..=MAX_AUDIT_PAGE_SIZE as isize)
+    )]
+    limit: u32,
+    cursor: OptionalParam<Uuid>,
+}
+
+#[derive(FromFormField)]
+pub enum EntityTypeParam {
+    Monitor,
+    #[field(value = "alert_config")]
+    AlertConfig,
+    #[field(value = "api_key")]
+    ApiKey,
+    #[field(value = "webhook_subscription")]
+    WebhookSubscription,
+}
+
+impl From<AuditLogParams> for AuditQuery {
+    fn from(params: AuditLogParams) -> Self {
+        Self {
+            entity_type: params.entity_type.0.map(|entity_type| match entity_type {
+                EntityTypeParam::Monitor => EntityType::Monitor,
+                EntityTypeParam::AlertConfig => EntityType::AlertConfig,
+                EntityTypeParam::ApiKey => EntityType::ApiKey,
+                EntityTypeParam::WebhookSubscription => EntityType::WebhookSubscription,
+            }),
+            entity_id: params.entity_id.0,
+            actor_id: params.actor.0,
+            limit: params.limit,
+            cursor: params.cursor.0,
+        }
+    }
+}
+
+#[rocket::get("/audit-log?<params..>")]
+pub async fn list_audit_events(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    params: AuditLogParams,
+) -> Result<Value, Error> {
+    jwt.require_role(Role::Admin)?;
+
+    let mut repo = AuditLogRepository::new(pool);
+
+    let query = AuditQuery::from(params);
+    let page = repo.get_page(&jwt.tenant, &query).await?;
+
+    Ok(json!({
+        "data": page.events,
+        "paging": Paging::with_cursor(page.total, query.limit, page.next_cursor)
+    }))
+}
@@ -1,4 +1,7 @@
+use chrono::NaiveDateTime;
+use cron_mon_client::types::FinishJobInfo;
 use rocket;
+use rocket::form::{self, FromForm, FromFormField, ValueField};
 use rocket::serde::json::Json;
 use rocket::State;
 use serde::Deserialize;
@@ -6,17 +9,132 @@ use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::application::services::{
-    get_fetch_job_service, get_finish_job_service, get_start_job_service,
+    get_fetch_job_history_service, get_fetch_job_service, get_finish_job_service,
+    get_record_heartbeat_service, get_start_job_service,
 };
+use crate::domain::models::job_query::{DEFAULT_JOB_PAGE_SIZE, MAX_JOB_PAGE_SIZE};
+use crate::domain::models::{JobMetadata, JobOrder, JobQuery, JobStatus};
 use crate::errors::Error;
 use crate::infrastructure::auth::Jwt;
 use crate::infrastructure::database::DbPool;
 use crate::infrastructure::middleware::guards::api_key::ApiKey;
+use crate::infrastructure::paging::Paging;
 
+/// The outcome of a Job given by a client, with its metadata validated as it's deserialized so
+/// that invalid metadata is rejected in the same way as malformed metadata.
 #[derive(Deserialize)]
-pub struct FinishJobInfo {
+#[serde(try_from = "FinishJobInfo")]
+pub struct FinishJobAttributes {
     succeeded: bool,
     output: Option<String>,
+    metadata: JobMetadata,
+}
+
+impl TryFrom<FinishJobInfo> for FinishJobAttributes {
+    type Error = Error;
+
+    fn try_from(value: FinishJobInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            succeeded: value.succeeded,
+            output: value.output,
+            metadata: JobMetadata::try_from(value.metadata)?,
+        })
+    }
+}
+
+#[derive(FromForm)]
+pub struct JobHistoryParams {
+    status: OptionalParam<JobStatusParam>,
+    started_after: OptionalParam<DateTimeParam>,
+    started_before: OptionalParam<DateTimeParam>,
+    order: OptionalParam<JobOrderParam>,
+    #[field(
+        default = DEFAULT_JOB_PAGE_SIZE,
+        validate = range(1..=MAX_JOB_PAGE_SIZE as isize)
+    )]
+    limit: u32,
+    cursor: OptionalParam<Uuid>,
+}
+
+/// An optional query parameter. Unlike `Option`, which treats values that fail to parse as
+/// missing, this rejects them.
+pub struct OptionalParam<T>(pub(crate) Option<T>);
+
+impl<'v, T: FromFormField<'v>> FromFormField<'v> for OptionalParam<T> {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        T::from_value(field).map(|value| OptionalParam(Some(value)))
+    }
+
+    fn default() -> Option<Self> {
+        Some(OptionalParam(None))
+    }
+}
+
+#[derive(FromFormField)]
+pub enum JobStatusParam {
+    Succeeded,
+    Failed,
+    Late,
+    #[field(value = "in_progress")]
+    InProgress,
+}
+
+#[derive(FromFormField)]
+pub enum JobOrderParam {
+    Desc,
+    Asc,
+}
+
+/// A date and time given as a query parameter, in the same format that they're returned in.
+pub struct DateTimeParam(NaiveDateTime);
+
+impl<'v> FromFormField<'v> for DateTimeParam {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        field
+            .value
+            .parse::<NaiveDateTime>()
+            .map(DateTimeParam)
+            .map_err(|error| form::Error::validation(error.to_string()).into())
+    }
+}
+
+impl From<JobHistoryParams> for JobQuery {
+    fn from(params: JobHistoryParams) -> Self {
+        Self {
+            status: params.status.0.map(|status| match status {
+                JobStatusParam::Succeeded => JobStatus::Succeeded,
+                JobStatusParam::Failed => JobStatus::Failed,
+                JobStatusParam::Late => JobStatus::Late,
+                JobStatusParam::InProgress => JobStatus::InProgress,
+            }),
+            started_after: params.started_after.0.map(|param| param.0),
+            started_before: params.started_before.0.map(|param| param.0),
+            order: match params.order.0 {
+                Some(JobOrderParam::Asc) => JobOrder::OldestFirst,
+                Some(JobOrderParam::Desc) | None => JobOrder::NewestFirst,
+            },
+            limit: params.limit,
+            cursor: params.cursor.0,
+        }
+    }
+}
+
+#[rocket::get("/monitors/<monitor_id>/jobs?<params..>")]
+pub async fn list_jobs(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    monitor_id: Uuid,
+    params: JobHistoryParams,
+) -> Result<Value, Error> {
+    let mut service = get_fetch_job_history_service(pool);
+
+    let query = JobQuery::from(params);
+    let page = service.fetch_page(monitor_id, &jwt.tenant, &query).await?;
+
+    Ok(json!({
+        "data": page.jobs,
+        "paging": Paging::with_cursor(page.total, query.limit, page.next_cursor)
+    }))
 }
 
 #[rocket::get("/monitors/<monitor_id>/jobs/<job_id>")]
@@ -54,7 +172,7 @@ pub async fn finish_job(
     key: ApiKey,
     monitor_id: Uuid,
     job_id: Uuid,
-    finish_job_info: Json<FinishJobInfo>,
+    finish_job_info: Json<FinishJobAttributes>,
 ) -> Result<Value, Error> {
     let mut service = get_finish_job_service(pool);
 
@@ -65,8 +183,23 @@ pub async fn finish_job(
             job_id,
             finish_job_info.succeeded,
             &finish_job_info.output,
+            &finish_job_info.metadata,
         )
         .await?;
 
     Ok(json!({"data": job}))
 }
+
+#[rocket::post("/monitors/<monitor_id>/jobs/heartbeat")]
+pub async fn record_heartbeat(
+    pool: &State<DbPool>,
+    key: ApiKey,
+    monitor_id: Uuid,
+) -> Result<Value, Error> {
+    let mut service = get_record_heartbeat_service(pool);
+
+    let heartbeat = service
+        .record_heartbeat_for_monitor(monitor_id, &key.0)
+        .await?;
+    Ok(json!({"data": heartbeat}))
+}
@@ -0,0 +1,31 @@
+use rocket;
+use rocket::http::ContentType;
+use rocket::State;
+
+use crate::application::services::get_fetch_metrics_service;
+use crate::errors::Error;
+use crate::infrastructure::database::DbPool;
+use crate::infrastructure::metrics::{render_metrics, render_process_metrics, CONTENT_TYPE};
+use crate::infrastructure::middleware::guards::scrape_token::Scraper;
+
+#[rocket::get("/metrics")]
+pub async fn metrics(
+    pool: &State<DbPool>,
+    _scraper: Scraper,
+) -> Result<(ContentType, String), Error> {
+    let mut service = get_fetch_metrics_service(pool);
+    let monitors = service.fetch_metrics().await?;
+
+    Ok((content_type(), render_metrics(&monitors)))
+}
+
+/// Serve only this process' metrics, for processes without access to Monitors (such as the alert
+/// loop).
+#[rocket::get("/metrics")]
+pub async fn process_metrics(_scraper: Scraper) -> (ContentType, String) {
+    (content_type(), render_process_metrics())
+}
+
+fn content_type() -> ContentType {
+    ContentType::parse_flexible(CONTENT_TYPE).expect("Metrics content type should be valid")
+}
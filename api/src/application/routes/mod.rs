@@ -1,5 +1,9 @@
+pub mod activity;
 pub mod alert_config;
 pub mod api_keys;
+pub mod audit_log;
 pub mod health;
 pub mod jobs;
+pub mod metrics;
 pub mod monitors;
+pub mod webhook_subscriptions;
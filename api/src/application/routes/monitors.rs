@@ -1,4 +1,7 @@
+use chrono::NaiveDateTime;
+use cron_mon_client::types::{MonitorData, PauseInfo};
 use rocket;
+use rocket::form::{self, FromFormField, ValueField};
 use rocket::response::status::NoContent;
 use rocket::serde::json::Json;
 use rocket::State;
@@ -6,28 +9,109 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use uuid::Uuid;
 
+use crate::application::routes::jobs::OptionalParam;
 use crate::application::services::{
-    get_create_monitor_service, get_delete_monitor_service, get_fetch_monitors_service,
+    get_create_monitor_service, get_delete_monitor_service, get_fetch_duration_stats_service,
+    get_fetch_job_metrics_service, get_fetch_monitors_service, get_pause_monitor_service,
     get_update_monitor_service,
 };
+use crate::domain::models::{
+    AlertThreshold, AnomalyDetection, AutoTune, JobRetention, LabelSelector, Labels,
+    MaintenanceWindow, Monitor, MonitorSettings, Schedule,
+};
 use crate::errors::Error;
-use crate::infrastructure::auth::Jwt;
+use crate::infrastructure::auth::{Jwt, Role};
 use crate::infrastructure::database::DbPool;
 use crate::infrastructure::paging::Paging;
 use crate::infrastructure::repositories::monitor::MonitorRepository;
 use crate::infrastructure::repositories::Repository;
 
+/// The attributes of a Monitor given by a client, validated as they're deserialized so that
+/// invalid attributes are rejected in the same way as malformed ones.
 #[derive(Deserialize)]
-pub struct MonitorData {
+#[serde(try_from = "MonitorData")]
+pub struct MonitorAttributes {
     name: String,
     expected_duration: i32,
     grace_duration: i32,
+    settings: MonitorSettings,
+}
+
+impl TryFrom<MonitorData> for MonitorAttributes {
+    type Error = Error;
+
+    fn try_from(value: MonitorData) -> Result<Self, Self::Error> {
+        Monitor::validate_kind(&value.kind)?;
+
+        Ok(Self {
+            name: value.name,
+            expected_duration: value.expected_duration,
+            grace_duration: value.grace_duration,
+            settings: MonitorSettings {
+                schedule: value.schedule.map(Schedule::try_from).transpose()?,
+                kind: value.kind,
+                alert_threshold: AlertThreshold::try_from(value.alert_threshold)?,
+                retention: value.retention.map(JobRetention::try_from).transpose()?,
+                labels: Labels::try_from(value.labels)?,
+                maintenance_windows: value
+                    .maintenance_windows
+                    .into_iter()
+                    .map(MaintenanceWindow::try_from)
+                    .collect::<Result<_, _>>()?,
+                auto_tune: value.auto_tune.map(AutoTune::try_from).transpose()?,
+                anomaly_detection: value
+                    .anomaly_detection
+                    .map(AnomalyDetection::try_from)
+                    .transpose()?,
+                concurrency_policy: value.concurrency_policy,
+            },
+        })
+    }
+}
+
+/// When to automatically resume a Monitor, given by a client when pausing it, validated as it's
+/// deserialized.
+#[derive(Deserialize)]
+#[serde(try_from = "PauseInfo")]
+pub struct PauseAttributes {
+    resume_at: Option<NaiveDateTime>,
 }
 
-#[rocket::get("/monitors")]
-pub async fn list_monitors(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Error> {
+impl TryFrom<PauseInfo> for PauseAttributes {
+    type Error = Error;
+
+    fn try_from(value: PauseInfo) -> Result<Self, Self::Error> {
+        Monitor::validate_resume_at(value.resume_at)?;
+
+        Ok(Self {
+            resume_at: value.resume_at,
+        })
+    }
+}
+
+/// A label selector given as a query parameter, i.e `?label_selector=team=payments,env!=dev`.
+pub struct LabelSelectorParam(LabelSelector);
+
+impl<'v> FromFormField<'v> for LabelSelectorParam {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        field
+            .value
+            .parse::<LabelSelector>()
+            .map(LabelSelectorParam)
+            .map_err(|error| form::Error::validation(error).into())
+    }
+}
+
+#[rocket::get("/monitors?<label_selector>")]
+pub async fn list_monitors(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    label_selector: OptionalParam<LabelSelectorParam>,
+) -> Result<Value, Error> {
     let mut service = get_fetch_monitors_service(pool);
-    let monitors = service.fetch_all(&jwt.tenant).await?;
+    let monitors = service
+        .fetch_all(&jwt.tenant, label_selector.0.as_ref().map(|param| &param.0))
+        .await?;
 
     Ok(json!({
         "data": monitors
@@ -37,11 +121,22 @@ pub async fn list_monitors(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Erro
                 "name": m.name,
                 "expected_duration": m.expected_duration,
                 "grace_duration": m.grace_duration,
+                "kind": m.kind,
+                "schedule": m.schedule,
+                "alert_threshold": m.alert_threshold,
+                "retention": m.retention,
+                "labels": m.labels,
+                "paused": m.paused,
+                "resume_at": m.resume_at,
+                "maintenance_windows": m.maintenance_windows,
+                "auto_tune": m.auto_tune,
+                "anomaly_detection": m.anomaly_detection,
+                "concurrency_policy": m.concurrency_policy,
                 "last_finished_job": m.last_finished_job(),
                 "last_started_job": m.last_started_job()
             }))
             .collect::<Value>(),
-        "paging": Paging { total: monitors.len() }
+        "paging": Paging::new(monitors.len())
     }))
 }
 
@@ -49,8 +144,10 @@ pub async fn list_monitors(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Erro
 pub async fn create_monitor(
     pool: &State<DbPool>,
     jwt: Jwt,
-    new_monitor: Json<MonitorData>,
+    new_monitor: Json<MonitorAttributes>,
 ) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut service = get_create_monitor_service(pool);
 
     let mon = service
@@ -59,6 +156,8 @@ pub async fn create_monitor(
             &new_monitor.name,
             new_monitor.expected_duration,
             new_monitor.grace_duration,
+            new_monitor.settings.clone(),
+            &jwt.actor(),
         )
         .await?;
 
@@ -77,15 +176,48 @@ pub async fn get_monitor(pool: &State<DbPool>, jwt: Jwt, monitor_id: Uuid) -> Re
     }
 }
 
+#[rocket::get("/monitors/<monitor_id>/stats")]
+pub async fn get_monitor_stats(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    monitor_id: Uuid,
+) -> Result<Value, Error> {
+    let mut service = get_fetch_duration_stats_service(pool);
+    let stats = service.fetch_by_id(monitor_id, &jwt.tenant).await?;
+
+    Ok(json!({
+        "data": {
+            "suggested": stats.as_ref().and_then(|stats| stats.suggest()),
+            "stats": stats
+        }
+    }))
+}
+
+#[rocket::get("/monitors/<monitor_id>/job-metrics")]
+pub async fn get_job_metrics(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    monitor_id: Uuid,
+) -> Result<Value, Error> {
+    let mut service = get_fetch_job_metrics_service(pool);
+    let job_metrics = service.fetch_by_id(monitor_id, &jwt.tenant).await?;
+
+    Ok(json!({"data": job_metrics}))
+}
+
 #[rocket::delete("/monitors/<monitor_id>")]
 pub async fn delete_monitor(
     pool: &State<DbPool>,
     jwt: Jwt,
     monitor_id: Uuid,
 ) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Admin)?;
+
     let mut service = get_delete_monitor_service(pool);
 
-    service.delete_by_id(monitor_id, &jwt.tenant).await?;
+    service
+        .delete_by_id(monitor_id, &jwt.tenant, &jwt.actor())
+        .await?;
 
     Ok(NoContent)
 }
@@ -95,19 +227,62 @@ pub async fn update_monitor(
     pool: &State<DbPool>,
     jwt: Jwt,
     monitor_id: Uuid,
-    updated_monitor: Json<MonitorData>,
+    updated_monitor: Json<MonitorAttributes>,
 ) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
     let mut service = get_update_monitor_service(pool);
 
     let mon = service
         .update_by_id(
             monitor_id,
             &jwt.tenant,
-            &updated_monitor.name,
-            updated_monitor.expected_duration,
-            updated_monitor.grace_duration,
+            |monitor| {
+                monitor.edit_details(
+                    updated_monitor.name.clone(),
+                    updated_monitor.expected_duration,
+                    updated_monitor.grace_duration,
+                    updated_monitor.settings.clone(),
+                )
+            },
+            &jwt.actor(),
         )
         .await?;
 
     Ok(json!({"data": mon}))
 }
+
+#[rocket::post("/monitors/<monitor_id>/pause", data = "<pause_info>")]
+pub async fn pause_monitor(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    monitor_id: Uuid,
+    pause_info: Json<PauseAttributes>,
+) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
+    let mut service = get_pause_monitor_service(pool);
+
+    let mon = service
+        .pause_by_id(monitor_id, &jwt.tenant, pause_info.resume_at, &jwt.actor())
+        .await?;
+
+    Ok(json!({"data": mon}))
+}
+
+#[rocket::post("/monitors/<monitor_id>/resume")]
+pub async fn resume_monitor(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    monitor_id: Uuid,
+) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
+    let mut service = get_pause_monitor_service(pool);
+
+    let mon = service
+        .resume_by_id(monitor_id, &jwt.tenant, &jwt.actor())
+        .await?;
+
+    Ok(json!({"data": mon}))
+}
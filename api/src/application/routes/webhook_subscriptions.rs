@@ -0,0 +1,148 @@
+use rocket;
+use rocket::form::FromForm;
+use rocket::response::status::NoContent;
+use rocket::serde::json::Json;
+use rocket::State;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::application::services::webhook_subscriptions::WebhookSubscriptionData;
+use crate::application::services::{
+    get_create_subscription_service, get_delete_subscription_service,
+    get_update_subscription_service,
+};
+use crate::domain::models::webhook_delivery::{DEFAULT_DELIVERY_PAGE_SIZE, MAX_DELIVERY_PAGE_SIZE};
+use crate::errors::Error;
+use crate::infrastructure::auth::{Jwt, Role};
+use crate::infrastructure::database::DbPool;
+use crate::infrastructure::paging::Paging;
+use crate::infrastructure::repositories::webhook_delivery::{
+    DeliveryQueue, WebhookDeliveryRepository,
+};
+use crate::infrastructure::repositories::webhook_subscription::WebhookSubscriptionRepository;
+use crate::infrastructure::repositories::Repository;
+
+#[derive(FromForm)]
+pub struct DeliveryParams {
+    #[field(
+        default = DEFAULT_DELIVERY_PAGE_SIZE,
+        validate = range(1..=MAX_DELIVERY_PAGE_SIZE as isize)
+    )]
+    limit: u32,
+}
+
+#[rocket::get("/webhook-subscriptions")]
+pub async fn list_subscriptions(pool: &State<DbPool>, jwt: Jwt) -> Result<Value, Error> {
+    let mut repo = WebhookSubscriptionRepository::new(pool);
+    let subscriptions = repo.all(&jwt.tenant).await?;
+
+    Ok(json!({
+        "data": subscriptions,
+        "paging": Paging::new(subscriptions.len())
+    }))
+}
+
+#[rocket::post("/webhook-subscriptions", data = "<new_subscription>")]
+pub async fn create_subscription(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    new_subscription: Json<WebhookSubscriptionData>,
+) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
+    let mut create_subscription = get_create_subscription_service(pool);
+
+    let subscription = create_subscription
+        .create_from_data(&jwt.tenant, new_subscription.into_inner(), &jwt.actor())
+        .await?;
+
+    Ok(json!({"data": subscription}))
+}
+
+#[rocket::get("/webhook-subscriptions/<subscription_id>")]
+pub async fn get_subscription(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    subscription_id: Uuid,
+) -> Result<Value, Error> {
+    let mut repo = WebhookSubscriptionRepository::new(pool);
+    let subscription = repo.get(subscription_id, &jwt.tenant).await?;
+
+    if let Some(subscription) = subscription {
+        Ok(json!({"data": subscription}))
+    } else {
+        Err(Error::SubscriptionNotFound(subscription_id))
+    }
+}
+
+#[rocket::patch(
+    "/webhook-subscriptions/<subscription_id>",
+    data = "<updated_subscription>"
+)]
+pub async fn update_subscription(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    subscription_id: Uuid,
+    updated_subscription: Json<WebhookSubscriptionData>,
+) -> Result<Value, Error> {
+    jwt.require_role(Role::Operator)?;
+
+    let mut update_subscription = get_update_subscription_service(pool);
+
+    let subscription = update_subscription
+        .update_by_id(
+            subscription_id,
+            &jwt.tenant,
+            updated_subscription.into_inner(),
+            &jwt.actor(),
+        )
+        .await?;
+
+    Ok(json!({"data": subscription}))
+}
+
+#[rocket::delete("/webhook-subscriptions/<subscription_id>")]
+pub async fn delete_subscription(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    subscription_id: Uuid,
+) -> Result<NoContent, Error> {
+    jwt.require_role(Role::Admin)?;
+
+    let mut delete_subscription = get_delete_subscription_service(pool);
+
+    delete_subscription
+        .delete_by_id(subscription_id, &jwt.tenant, &jwt.actor())
+        .await?;
+
+    Ok(NoContent)
+}
+
+#[rocket::get("/webhook-subscriptions/<subscription_id>/deliveries?<params..>")]
+pub async fn list_deliveries(
+    pool: &State<DbPool>,
+    jwt: Jwt,
+    subscription_id: Uuid,
+    params: DeliveryParams,
+) -> Result<Value, Error> {
+    // Distinguish between a webhook subscription that doesn't exist, and one that hasn't had any
+    // deliveries yet.
+    let mut subscription_repo = WebhookSubscriptionRepository::new(pool);
+    if subscription_repo
+        .get(subscription_id, &jwt.tenant)
+        .await?
+        .is_none()
+    {
+        return Err(Error::SubscriptionNotFound(subscription_id));
+    }
+
+    let mut delivery_repo = WebhookDeliveryRepository::new(pool);
+    let deliveries = delivery_repo
+        .get_for_subscription(subscription_id, &jwt.tenant, params.limit)
+        .await?;
+
+    Ok(json!({
+        "data": deliveries,
+        "paging": Paging::new(deliveries.len())
+    }))
+}
@@ -1,27 +1,38 @@
 use tracing::info;
 
-use crate::domain::models::{AlertConfig, AlertType};
+use crate::domain::models::{Actor, AlertConfig, AlertType, AuditEvent};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
 use crate::infrastructure::repositories::Repository;
 
-use super::AlertConfigData;
+use super::{parse_alert_type, parse_label_selector, AlertConfigData};
 
-pub struct CreateAlertConfigService<T: Repository<AlertConfig>> {
-    repo: T,
+pub struct CreateAlertConfigService<
+    AlertConfigRepo: Repository<AlertConfig>,
+    AuditLogRepo: AuditLog,
+> {
+    repo: AlertConfigRepo,
+    audit_log: AuditLogRepo,
 }
 
-impl<T: Repository<AlertConfig>> CreateAlertConfigService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<AlertConfigRepo: Repository<AlertConfig>, AuditLogRepo: AuditLog>
+    CreateAlertConfigService<AlertConfigRepo, AuditLogRepo>
+{
+    pub fn new(repo: AlertConfigRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
     }
 
     pub async fn create_from_value(
         &mut self,
         tenant: &str,
         data: AlertConfigData,
+        actor: &Actor,
     ) -> Result<AlertConfig, Error> {
         let alert_config = self.create_alert_config(tenant, data)?;
         self.repo.save(&alert_config).await?;
+        self.audit_log
+            .record(&AuditEvent::created(actor, &alert_config))
+            .await?;
 
         info!(
             alert_config_id = alert_config.alert_config_id.to_string(),
@@ -38,36 +49,82 @@ impl<T: Repository<AlertConfig>> CreateAlertConfigService<T> {
         tenant: &str,
         data: AlertConfigData,
     ) -> Result<AlertConfig, Error> {
-        let alert_type: AlertType = serde_json::from_value(data.type_)
-            .map_err(|error| Error::InvalidAlertConfig(error.to_string()))?;
-
-        match alert_type {
-            AlertType::Slack(slack_data) => Ok(AlertConfig::new_slack_config(
+        let label_selector = parse_label_selector(data.label_selector.as_deref())?;
+        let mut alert_config = match parse_alert_type(data.type_)? {
+            AlertType::Slack(slack_data) => AlertConfig::new_slack_config(
                 data.name.to_owned(),
                 tenant.to_owned(),
                 data.active,
                 data.on_late,
                 data.on_error,
+                data.on_recovery,
+                data.on_anomaly,
                 slack_data.channel.clone(),
                 slack_data.token.clone(),
-            )),
-        }
+            ),
+            AlertType::Webhook(webhook_data) => AlertConfig::new_webhook_config(
+                data.name.to_owned(),
+                tenant.to_owned(),
+                data.active,
+                data.on_late,
+                data.on_error,
+                data.on_recovery,
+                data.on_anomaly,
+                webhook_data,
+            ),
+            AlertType::Email(email_data) => AlertConfig::new_email_config(
+                data.name.to_owned(),
+                tenant.to_owned(),
+                data.active,
+                data.on_late,
+                data.on_error,
+                data.on_recovery,
+                data.on_anomaly,
+                email_data,
+            ),
+            AlertType::PagerDuty(pagerduty_data) => AlertConfig::new_pagerduty_config(
+                data.name.to_owned(),
+                tenant.to_owned(),
+                data.active,
+                data.on_late,
+                data.on_error,
+                data.on_recovery,
+                data.on_anomaly,
+                pagerduty_data,
+            ),
+        };
+        alert_config.label_selector = label_selector;
+
+        Ok(alert_config)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use serde_json::json;
     use tracing::Level;
     use tracing_test::traced_test;
 
     use test_utils::logging::TracingLog;
 
-    use crate::domain::models::SlackAlertConfig;
+    use crate::domain::models::{
+        AuditAction, EmailAlertConfig, EntityType, SlackAlertConfig, WebhookAlertConfig,
+        WebhookMethod,
+    };
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_create_alert_config_service() {
@@ -88,7 +145,21 @@ mod tests {
             })
             .returning(|_| Ok(()));
 
-        let mut service = CreateAlertConfigService::new(mock);
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.tenant == "tenant"
+                    && event.actor == actor()
+                    && event.action == AuditAction::Created
+                    && event.entity.type_ == EntityType::AlertConfig
+                    && event.before.is_none()
+                    && event.after.as_ref().unwrap()["name"] == "name"
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = CreateAlertConfigService::new(mock, audit_log);
 
         let alert_config = service
             .create_from_value(
@@ -98,6 +169,9 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: Some("team=payments".to_string()),
                     type_: json!({
                         "slack": {
                             "channel": "channel",
@@ -105,6 +179,7 @@ mod tests {
                         }
                     }),
                 },
+                &actor(),
             )
             .await
             .unwrap();
@@ -113,6 +188,10 @@ mod tests {
         assert!(alert_config.active);
         assert!(alert_config.on_late);
         assert!(alert_config.on_error);
+        assert_eq!(
+            alert_config.label_selector,
+            Some("team=payments".parse().unwrap())
+        );
         assert_eq!(
             alert_config.type_,
             AlertType::Slack(SlackAlertConfig {
@@ -143,7 +222,7 @@ mod tests {
     async fn test_create_alert_config_service_invalid_alert_config() {
         let mut mock = MockRepository::new();
         mock.expect_save().never();
-        let mut service = CreateAlertConfigService::new(mock);
+        let mut service = CreateAlertConfigService::new(mock, MockAuditLog::new());
 
         let result = service
             .create_from_value(
@@ -153,19 +232,23 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     type_: json!({
                         "ms-teams": {
                             "group": "group"
                         }
                     }),
                 },
+                &actor(),
             )
             .await;
 
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Invalid Alert Configuration: unknown variant `ms-teams`, expected `slack`"
+            "Invalid Alert Configuration: unknown variant `ms-teams`, expected one of `slack`, `webhook`, `email`, `pagerduty`"
         );
 
         logs_assert(|logs| {
@@ -175,6 +258,179 @@ mod tests {
         });
     }
 
+    #[traced_test]
+    #[tokio::test]
+    async fn test_create_webhook_alert_config() {
+        let mut mock = MockRepository::new();
+        mock.expect_save()
+            .once()
+            .withf(|ac: &AlertConfig| {
+                ac.type_
+                    == AlertType::Webhook(WebhookAlertConfig {
+                        url: "https://example.com/hook".to_string(),
+                        method: WebhookMethod::Post,
+                        headers: BTreeMap::from([("X-Team".to_string(), "infra".to_string())]),
+                        secret: Some("shh".to_string()),
+                    })
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().once().returning(|_| Ok(()));
+
+        let mut service = CreateAlertConfigService::new(mock, audit_log);
+
+        let alert_config = service
+            .create_from_value(
+                "tenant",
+                AlertConfigData {
+                    name: "name".to_string(),
+                    active: true,
+                    on_late: true,
+                    on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
+                    type_: json!({
+                        "webhook": {
+                            "url": "https://example.com/hook",
+                            "headers": {"X-Team": "infra"},
+                            "secret": "shh"
+                        }
+                    }),
+                },
+                &actor(),
+            )
+            .await
+            .unwrap();
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(
+                logs[0].body,
+                format!(
+                    "Created new Alert Configuration - name: 'name', type: webhook \
+                        alert_config_id=\"{}\"",
+                    alert_config.alert_config_id
+                )
+            );
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_alert_config_with_invalid_url() {
+        let mut mock = MockRepository::new();
+        mock.expect_save().never();
+        let mut service = CreateAlertConfigService::new(mock, MockAuditLog::new());
+
+        let result = service
+            .create_from_value(
+                "tenant",
+                AlertConfigData {
+                    name: "name".to_string(),
+                    active: true,
+                    on_late: true,
+                    on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
+                    type_: json!({
+                        "webhook": {
+                            "url": "file:///etc/passwd",
+                            "method": "PUT"
+                        }
+                    }),
+                },
+                &actor(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidAlertConfig(
+                "Webhook URL must use http or https".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_email_alert_config() {
+        let mut mock = MockRepository::new();
+        mock.expect_save()
+            .once()
+            .withf(|ac: &AlertConfig| {
+                ac.type_
+                    == AlertType::Email(EmailAlertConfig {
+                        recipients: vec!["oncall@example.com".to_string()],
+                        smtp: None,
+                    })
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().once().returning(|_| Ok(()));
+
+        let mut service = CreateAlertConfigService::new(mock, audit_log);
+
+        let alert_config = service
+            .create_from_value(
+                "tenant",
+                AlertConfigData {
+                    name: "name".to_string(),
+                    active: true,
+                    on_late: true,
+                    on_error: false,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
+                    type_: json!({"email": {"recipients": ["oncall@example.com"]}}),
+                },
+                &actor(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(alert_config.type_.to_string(), "email");
+    }
+
+    #[tokio::test]
+    async fn test_create_alert_config_service_invalid_label_selector() {
+        let mut mock = MockRepository::new();
+        mock.expect_save().never();
+        let mut service = CreateAlertConfigService::new(mock, MockAuditLog::new());
+
+        let result = service
+            .create_from_value(
+                "tenant",
+                AlertConfigData {
+                    name: "name".to_string(),
+                    active: true,
+                    on_late: true,
+                    on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: Some("team=".to_string()),
+                    type_: json!({
+                        "slack": {
+                            "channel": "channel",
+                            "token": "token"
+                        }
+                    }),
+                },
+                &actor(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidAlertConfig(
+                "Invalid label selector requirement 'team='".to_string()
+            ))
+        );
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_create_alert_config_service_save_error() {
@@ -182,7 +438,7 @@ mod tests {
         mock.expect_save()
             .once()
             .returning(|_| Err(Error::RepositoryError("test error".to_string())));
-        let mut service = CreateAlertConfigService::new(mock);
+        let mut service = CreateAlertConfigService::new(mock, MockAuditLog::new());
 
         let result = service
             .create_from_value(
@@ -192,6 +448,9 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     type_: json!({
                         "slack": {
                             "channel": "channel",
@@ -199,6 +458,7 @@ mod tests {
                         }
                     }),
                 },
+                &actor(),
             )
             .await;
 
@@ -1,20 +1,32 @@
 use tracing::info;
 use uuid::Uuid;
 
-use crate::domain::models::AlertConfig;
+use crate::domain::models::{Actor, AlertConfig, AuditEvent};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
 use crate::infrastructure::repositories::Repository;
 
-pub struct DeleteAlertConfigService<T: Repository<AlertConfig>> {
-    repo: T,
+pub struct DeleteAlertConfigService<
+    AlertConfigRepo: Repository<AlertConfig>,
+    AuditLogRepo: AuditLog,
+> {
+    repo: AlertConfigRepo,
+    audit_log: AuditLogRepo,
 }
 
-impl<T: Repository<AlertConfig>> DeleteAlertConfigService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<AlertConfigRepo: Repository<AlertConfig>, AuditLogRepo: AuditLog>
+    DeleteAlertConfigService<AlertConfigRepo, AuditLogRepo>
+{
+    pub fn new(repo: AlertConfigRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
     }
 
-    pub async fn delete_by_id(&mut self, alert_config_id: Uuid, tenant: &str) -> Result<(), Error> {
+    pub async fn delete_by_id(
+        &mut self,
+        alert_config_id: Uuid,
+        tenant: &str,
+        actor: &Actor,
+    ) -> Result<(), Error> {
         let alert_config = self
             .repo
             .get(alert_config_id, tenant)
@@ -22,6 +34,9 @@ impl<T: Repository<AlertConfig>> DeleteAlertConfigService<T> {
             .ok_or(Error::AlertConfigNotFound(vec![alert_config_id]))?;
 
         self.repo.delete(&alert_config).await?;
+        self.audit_log
+            .record(&AuditEvent::deleted(actor, &alert_config))
+            .await?;
         info!(
             alert_config_id = alert_config_id.to_string(),
             "Deleted Alert Configuration('{}')", &alert_config.name
@@ -39,11 +54,19 @@ mod tests {
     use test_utils::gen_uuid;
     use test_utils::logging::TracingLog;
 
-    use crate::domain::models::{AlertConfig, AlertType, SlackAlertConfig};
+    use crate::domain::models::{AlertConfig, AlertType, AuditAction, SlackAlertConfig};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_delete_alert_config_service() {
@@ -61,6 +84,9 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     monitors: vec![],
                     type_: AlertType::Slack(SlackAlertConfig {
                         channel: "channel".to_owned(),
@@ -77,6 +103,9 @@ mod tests {
                 active: true,
                 on_late: true,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
                 monitors: vec![],
                 type_: AlertType::Slack(SlackAlertConfig {
                     channel: "channel".to_owned(),
@@ -85,8 +114,21 @@ mod tests {
             }))
             .returning(|_| Ok(()));
 
-        let mut service = DeleteAlertConfigService::new(mock);
-        let result = service.delete_by_id(existent_id, "tenant").await;
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Deleted
+                    && event.entity.id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.before.as_ref().unwrap()["name"] == "foo"
+                    && event.after.is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = DeleteAlertConfigService::new(mock, audit_log);
+        let result = service.delete_by_id(existent_id, "tenant", &actor()).await;
 
         assert!(result.is_ok());
 
@@ -116,8 +158,10 @@ mod tests {
             .with(eq(non_existent_id), eq("tenant"))
             .returning(|_, _| Ok(None));
 
-        let mut service = DeleteAlertConfigService::new(mock);
-        let result = service.delete_by_id(non_existent_id, "tenant").await;
+        let mut service = DeleteAlertConfigService::new(mock, MockAuditLog::new());
+        let result = service
+            .delete_by_id(non_existent_id, "tenant", &actor())
+            .await;
 
         assert_eq!(
             result,
@@ -147,8 +191,8 @@ mod tests {
             });
         mock.expect_delete().never();
 
-        let mut service = DeleteAlertConfigService::new(mock);
-        let result = service.delete_by_id(existent_id, "tenant").await;
+        let mut service = DeleteAlertConfigService::new(mock, MockAuditLog::new());
+        let result = service.delete_by_id(existent_id, "tenant", &actor()).await;
 
         assert_eq!(
             result,
@@ -179,6 +223,9 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     monitors: vec![],
                     type_: AlertType::Slack(SlackAlertConfig {
                         channel: "channel".to_owned(),
@@ -195,6 +242,9 @@ mod tests {
                 active: true,
                 on_late: true,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
                 monitors: vec![],
                 type_: AlertType::Slack(SlackAlertConfig {
                     channel: "channel".to_owned(),
@@ -207,8 +257,8 @@ mod tests {
                 ))
             });
 
-        let mut service = DeleteAlertConfigService::new(mock);
-        let result = service.delete_by_id(existent_id, "tenant").await;
+        let mut service = DeleteAlertConfigService::new(mock, MockAuditLog::new());
+        let result = service.delete_by_id(existent_id, "tenant", &actor()).await;
 
         assert_eq!(
             result,
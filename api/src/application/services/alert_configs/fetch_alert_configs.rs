@@ -25,26 +25,35 @@ impl<Monitors: Repository<Monitor>, AlertConfigs: GetByMonitors>
         tenant: &str,
     ) -> Result<Vec<AlertConfig>, Error> {
         // Ensure the Monitor exists.
-        let monitor = self.monitor_repo.get(monitor_id, tenant).await?;
-        if monitor.is_none() {
+        let Some(monitor) = self.monitor_repo.get(monitor_id, tenant).await? else {
             return Err(Error::MonitorNotFound(monitor_id));
-        }
+        };
 
-        self.alert_config_repo
+        // Alert configs with a label selector are returned regardless of whether they match the
+        // Monitor's labels, so we need to filter out those that don't.
+        let mut alert_configs = self
+            .alert_config_repo
             .get_by_monitors(&[monitor_id], Some(tenant))
-            .await
+            .await?;
+        alert_configs.retain(|alert_config| alert_config.applies_to_monitor(&monitor));
+
+        Ok(alert_configs)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::vec;
 
     use mockall::predicate::*;
 
     use test_utils::gen_uuid;
 
-    use crate::domain::models::{AlertType, AppliedMonitor, SlackAlertConfig};
+    use crate::domain::models::{
+        AlertThreshold, AlertType, AppliedMonitor, ConcurrencyPolicy, Labels, MonitorKind,
+        SlackAlertConfig,
+    };
     use crate::infrastructure::repositories::{alert_config::MockGetByMonitors, MockRepository};
 
     use super::*;
@@ -66,6 +75,22 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::new(BTreeMap::from([(
+                        "team".to_owned(),
+                        "payments".to_owned(),
+                    )]))
+                    .unwrap(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
@@ -87,6 +112,9 @@ mod tests {
                         active: true,
                         on_late: true,
                         on_error: false,
+                        on_recovery: false,
+                        on_anomaly: false,
+                        label_selector: None,
                         monitors: vec![AppliedMonitor {
                             monitor_id: gen_uuid("6fad996a-df7d-42a3-aaad-a5e7d101ac54"),
                             name: "foo".to_string(),
@@ -103,6 +131,9 @@ mod tests {
                         active: true,
                         on_late: false,
                         on_error: true,
+                        on_recovery: false,
+                        on_anomaly: false,
+                        label_selector: None,
                         monitors: vec![
                             AppliedMonitor {
                                 monitor_id: gen_uuid("6fad996a-df7d-42a3-aaad-a5e7d101ac54"),
@@ -118,6 +149,38 @@ mod tests {
                             token: "123abc456".to_string(),
                         }),
                     },
+                    AlertConfig {
+                        alert_config_id: gen_uuid("8b7a2a4e-1f0c-4f5e-9d1a-3c2b1a0f9e8d"),
+                        name: "payments-config".to_string(),
+                        tenant: "tenant".to_string(),
+                        active: true,
+                        on_late: true,
+                        on_error: true,
+                        on_recovery: false,
+                        on_anomaly: false,
+                        label_selector: Some("team=payments".parse().unwrap()),
+                        monitors: vec![],
+                        type_: AlertType::Slack(SlackAlertConfig {
+                            channel: "#payments-alerts".to_string(),
+                            token: "123abc456".to_string(),
+                        }),
+                    },
+                    AlertConfig {
+                        alert_config_id: gen_uuid("5d0e7c9b-2a3f-4b6c-8e1d-7f9a0b2c4d6e"),
+                        name: "search-config".to_string(),
+                        tenant: "tenant".to_string(),
+                        active: true,
+                        on_late: true,
+                        on_error: true,
+                        on_recovery: false,
+                        on_anomaly: false,
+                        label_selector: Some("team=search".parse().unwrap()),
+                        monitors: vec![],
+                        type_: AlertType::Slack(SlackAlertConfig {
+                            channel: "#search-alerts".to_string(),
+                            token: "123abc456".to_string(),
+                        }),
+                    },
                 ])
             });
         let mut service = FetchAlertConfigs::new(mock_monitor_repo, mock_alert_config_repo);
@@ -131,7 +194,7 @@ mod tests {
             .iter()
             .map(|ac| ac.name.clone())
             .collect::<Vec<String>>();
-        assert_eq!(names, vec!["foo-config", "bar-config"]);
+        assert_eq!(names, vec!["foo-config", "bar-config", "payments-config"]);
     }
 
     #[tokio::test]
@@ -197,6 +260,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
@@ -5,7 +5,15 @@ pub mod monitor_association;
 pub mod test_alert_config;
 pub mod update_alert_config;
 
-use serde::Deserialize;
+use lettre::message::Mailbox;
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::Url;
+
+use crate::domain::models::{AlertType, EmailAlertConfig, LabelSelector, WebhookAlertConfig};
+use crate::errors::Error;
+use crate::infrastructure::notify::webhook::integration::SIGNATURE_HEADER;
+
+pub use cron_mon_client::types::AlertConfigData;
 
 pub use create_alert_config::CreateAlertConfigService;
 pub use delete_alert_config::DeleteAlertConfigService;
@@ -14,12 +22,280 @@ pub use monitor_association::MonitorAssociationService;
 pub use test_alert_config::TestAlertConfigService;
 pub use update_alert_config::UpdateAlertConfigService;
 
-#[derive(Deserialize)]
-pub struct AlertConfigData {
-    pub name: String,
-    pub active: bool,
-    pub on_late: bool,
-    pub on_error: bool,
-    #[serde(rename = "type")]
-    pub type_: serde_json::Value,
+/// Parse and validate the type-specific data of an alert configuration.
+fn parse_alert_type(type_: serde_json::Value) -> Result<AlertType, Error> {
+    let alert_type: AlertType = serde_json::from_value(type_)
+        .map_err(|error| Error::InvalidAlertConfig(error.to_string()))?;
+
+    match &alert_type {
+        AlertType::Webhook(webhook) => validate_webhook(webhook)?,
+        AlertType::Email(email) => validate_email(email)?,
+        AlertType::PagerDuty(pagerduty) => {
+            if pagerduty.routing_key.is_empty() {
+                return Err(Error::InvalidAlertConfig(
+                    "PagerDuty routing key cannot be empty".to_owned(),
+                ));
+            }
+        }
+        AlertType::Slack(_) => {}
+    }
+
+    Ok(alert_type)
+}
+
+/// Parse the label selector that an alert configuration selects monitors with, if it has one.
+fn parse_label_selector(label_selector: Option<&str>) -> Result<Option<LabelSelector>, Error> {
+    label_selector
+        .map(str::parse)
+        .transpose()
+        .map_err(Error::InvalidAlertConfig)
+}
+
+fn validate_webhook(webhook: &WebhookAlertConfig) -> Result<(), Error> {
+    let url = Url::parse(&webhook.url)
+        .map_err(|error| Error::InvalidAlertConfig(format!("Invalid webhook URL: {error}")))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(Error::InvalidAlertConfig(
+            "Webhook URL must use http or https".to_owned(),
+        ));
+    }
+
+    for (name, value) in &webhook.headers {
+        let header_name = HeaderName::try_from(name).map_err(|_| {
+            Error::InvalidAlertConfig(format!("Invalid webhook header name '{name}'"))
+        })?;
+        // These are set by the notifier itself, so can't be overridden.
+        if header_name == CONTENT_TYPE
+            || header_name.as_str().eq_ignore_ascii_case(SIGNATURE_HEADER)
+        {
+            return Err(Error::InvalidAlertConfig(format!(
+                "Webhook header '{name}' cannot be overridden"
+            )));
+        }
+        HeaderValue::try_from(value).map_err(|_| {
+            Error::InvalidAlertConfig(format!("Invalid value for webhook header '{name}'"))
+        })?;
+    }
+
+    if webhook
+        .secret
+        .as_ref()
+        .is_some_and(|secret| secret.is_empty())
+    {
+        return Err(Error::InvalidAlertConfig(
+            "Webhook secret cannot be empty".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_email(email: &EmailAlertConfig) -> Result<(), Error> {
+    if email.recipients.is_empty() {
+        return Err(Error::InvalidAlertConfig(
+            "Email alerts need at least one recipient".to_owned(),
+        ));
+    }
+    for recipient in &email.recipients {
+        validate_email_address(recipient)?;
+    }
+
+    if let Some(smtp) = &email.smtp {
+        if smtp.host.is_empty() {
+            return Err(Error::InvalidAlertConfig(
+                "SMTP host cannot be empty".to_owned(),
+            ));
+        }
+        validate_email_address(&smtp.from)?;
+        if smtp.username.is_some() != smtp.password.is_some() {
+            return Err(Error::InvalidAlertConfig(
+                "SMTP username and password must be provided together".to_owned(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_email_address(address: &str) -> Result<(), Error> {
+    address
+        .parse::<Mailbox>()
+        .map(|_| ())
+        .map_err(|_| Error::InvalidAlertConfig(format!("Invalid email address '{address}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rstest::rstest;
+
+    use serde_json::json;
+
+    use crate::domain::models::{SmtpConfig, SmtpTls, WebhookMethod};
+
+    use super::*;
+
+    #[rstest]
+    #[case::not_a_url("not a url", vec![], None, "Invalid webhook URL: relative URL without a base")]
+    #[case::bad_scheme(
+        "ftp://example.com/hook",
+        vec![],
+        None,
+        "Webhook URL must use http or https"
+    )]
+    #[case::bad_header_name(
+        "https://example.com/hook",
+        vec![("Bad Header", "value")],
+        None,
+        "Invalid webhook header name 'Bad Header'"
+    )]
+    #[case::bad_header_value(
+        "https://example.com/hook",
+        vec![("X-Team", "in\nfra")],
+        None,
+        "Invalid value for webhook header 'X-Team'"
+    )]
+    #[case::content_type_header(
+        "https://example.com/hook",
+        vec![("content-type", "text/plain")],
+        None,
+        "Webhook header 'content-type' cannot be overridden"
+    )]
+    #[case::signature_header(
+        "https://example.com/hook",
+        vec![("X-CronMon-Signature", "sha256=abc")],
+        None,
+        "Webhook header 'X-CronMon-Signature' cannot be overridden"
+    )]
+    #[case::empty_secret(
+        "https://example.com/hook",
+        vec![],
+        Some(""),
+        "Webhook secret cannot be empty"
+    )]
+    fn test_validate_invalid_webhook(
+        #[case] url: &str,
+        #[case] headers: Vec<(&str, &str)>,
+        #[case] secret: Option<&str>,
+        #[case] expected_error: &str,
+    ) {
+        let webhook = WebhookAlertConfig {
+            url: url.to_owned(),
+            method: WebhookMethod::Post,
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+            secret: secret.map(|s| s.to_owned()),
+        };
+
+        assert_eq!(
+            validate_webhook(&webhook),
+            Err(Error::InvalidAlertConfig(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_validate_valid_webhook() {
+        let webhook = WebhookAlertConfig {
+            url: "http://localhost:8080/hook".to_owned(),
+            method: WebhookMethod::Patch,
+            headers: BTreeMap::from([("Authorization".to_owned(), "Bearer foo".to_owned())]),
+            secret: Some("shh".to_owned()),
+        };
+
+        assert_eq!(validate_webhook(&webhook), Ok(()));
+    }
+
+    fn smtp_config(host: &str, from: &str, username: Option<&str>) -> SmtpConfig {
+        SmtpConfig {
+            host: host.to_owned(),
+            port: 587,
+            username: username.map(|u| u.to_owned()),
+            password: None,
+            from: from.to_owned(),
+            tls: SmtpTls::Starttls,
+        }
+    }
+
+    #[rstest]
+    #[case::no_recipients(vec![], None, "Email alerts need at least one recipient")]
+    #[case::bad_recipient(
+        vec!["oncall@example.com", "not an address"],
+        None,
+        "Invalid email address 'not an address'"
+    )]
+    #[case::empty_host(
+        vec!["oncall@example.com"],
+        Some(smtp_config("", "cron-mon@example.com", None)),
+        "SMTP host cannot be empty"
+    )]
+    #[case::bad_from(
+        vec!["oncall@example.com"],
+        Some(smtp_config("smtp.example.com", "cron-mon", None)),
+        "Invalid email address 'cron-mon'"
+    )]
+    #[case::username_without_password(
+        vec!["oncall@example.com"],
+        Some(smtp_config("smtp.example.com", "cron-mon@example.com", Some("user"))),
+        "SMTP username and password must be provided together"
+    )]
+    fn test_validate_invalid_email(
+        #[case] recipients: Vec<&str>,
+        #[case] smtp: Option<SmtpConfig>,
+        #[case] expected_error: &str,
+    ) {
+        let email = EmailAlertConfig {
+            recipients: recipients.into_iter().map(|r| r.to_owned()).collect(),
+            smtp,
+        };
+
+        assert_eq!(
+            validate_email(&email),
+            Err(Error::InvalidAlertConfig(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_validate_valid_email() {
+        let email = EmailAlertConfig {
+            recipients: vec![
+                "oncall@example.com".to_owned(),
+                "Infra Team <infra@example.com>".to_owned(),
+            ],
+            smtp: Some(smtp_config(
+                "smtp.example.com",
+                "CronMon <cron-mon@example.com>",
+                None,
+            )),
+        };
+
+        assert_eq!(validate_email(&email), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_label_selector() {
+        assert_eq!(parse_label_selector(None), Ok(None));
+        assert_eq!(
+            parse_label_selector(Some("team=payments")),
+            Ok(Some("team=payments".parse().unwrap()))
+        );
+        assert_eq!(
+            parse_label_selector(Some("team==payments")),
+            Err(Error::InvalidAlertConfig(
+                "Invalid label selector requirement 'team==payments'".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_pagerduty_with_empty_routing_key() {
+        assert_eq!(
+            parse_alert_type(json!({"pagerduty": {"routing_key": ""}})),
+            Err(Error::InvalidAlertConfig(
+                "PagerDuty routing key cannot be empty".to_owned()
+            ))
+        );
+    }
 }
@@ -3,26 +3,37 @@ use uuid::Uuid;
 
 use std::collections::HashSet;
 
-use crate::domain::models::{AlertConfig, Monitor};
+use crate::domain::models::{Actor, AlertConfig, AuditEvent, Monitor};
 use crate::errors::Error;
 use crate::infrastructure::repositories::alert_config::GetByIDs;
+use crate::infrastructure::repositories::audit_log::AuditLog;
 use crate::infrastructure::repositories::Repository;
 
 pub struct MonitorAssociationService<
     MonitorRepo: Repository<Monitor>,
     AlertConfigRepo: Repository<AlertConfig> + GetByIDs,
+    AuditLogRepo: AuditLog,
 > {
     monitor_repo: MonitorRepo,
     alert_config_repo: AlertConfigRepo,
+    audit_log: AuditLogRepo,
 }
 
-impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig> + GetByIDs>
-    MonitorAssociationService<MonitorRepo, AlertConfigRepo>
+impl<
+        MonitorRepo: Repository<Monitor>,
+        AlertConfigRepo: Repository<AlertConfig> + GetByIDs,
+        AuditLogRepo: AuditLog,
+    > MonitorAssociationService<MonitorRepo, AlertConfigRepo, AuditLogRepo>
 {
-    pub fn new(monitor_repo: MonitorRepo, alert_config_repo: AlertConfigRepo) -> Self {
+    pub fn new(
+        monitor_repo: MonitorRepo,
+        alert_config_repo: AlertConfigRepo,
+        audit_log: AuditLogRepo,
+    ) -> Self {
         Self {
             monitor_repo,
             alert_config_repo,
+            audit_log,
         }
     }
 
@@ -31,9 +42,11 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
         tenant: &str,
         monitor_id: Uuid,
         alert_config_ids: &[Uuid],
+        actor: &Actor,
     ) -> Result<(), Error> {
         let monitor = self.get_monitor(tenant, monitor_id).await?;
         let mut alert_configs = self.get_alert_configs(alert_config_ids, tenant).await?;
+        let originals = alert_configs.clone();
 
         // We want to collect all failures so we can log them, rather than fail on the first error
         let failures = alert_configs
@@ -64,8 +77,9 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
             )));
         }
 
-        for alert_config in alert_configs {
-            self.save_alert_config(&alert_config).await?;
+        for (original, alert_config) in originals.iter().zip(&alert_configs) {
+            self.save_alert_config(original, alert_config, actor)
+                .await?;
         }
 
         Ok(())
@@ -76,6 +90,7 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
         tenant: &str,
         monitor_id: Uuid,
         alert_config_id: Uuid,
+        actor: &Actor,
     ) -> Result<(), Error> {
         let monitor = self.get_monitor(tenant, monitor_id).await?;
 
@@ -84,6 +99,7 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
             .get(alert_config_id, tenant)
             .await?
             .ok_or_else(|| Error::AlertConfigNotFound(vec![alert_config_id]))?;
+        let original = alert_config.clone();
 
         alert_config
             .disassociate_monitor(&monitor)
@@ -97,7 +113,8 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
                 error
             })?;
 
-        self.save_alert_config(&alert_config).await?;
+        self.save_alert_config(&original, &alert_config, actor)
+            .await?;
 
         Ok(())
     }
@@ -137,7 +154,12 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
         }
     }
 
-    async fn save_alert_config(&mut self, alert_config: &AlertConfig) -> Result<(), Error> {
+    async fn save_alert_config(
+        &mut self,
+        original: &AlertConfig,
+        alert_config: &AlertConfig,
+        actor: &Actor,
+    ) -> Result<(), Error> {
         self.alert_config_repo
             .save(alert_config)
             .await
@@ -147,7 +169,12 @@ impl<MonitorRepo: Repository<Monitor>, AlertConfigRepo: Repository<AlertConfig>
                     "Error saving AlertConfig: {:?}", error
                 );
                 Error::RepositoryError(error.to_string())
-            })
+            })?;
+
+        // Associating Monitors is recorded as a change to each of the alert configurations.
+        self.audit_log
+            .record(&AuditEvent::updated(actor, original, alert_config))
+            .await
     }
 }
 
@@ -161,11 +188,22 @@ mod tests {
 
     use test_utils::{gen_uuid, logging::get_tracing_logs};
 
-    use crate::domain::models::{AlertType, AppliedMonitor, SlackAlertConfig};
+    use crate::domain::models::{
+        AlertThreshold, AlertType, AppliedMonitor, AuditAction, ConcurrencyPolicy, Labels,
+        MonitorKind, SlackAlertConfig,
+    };
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     mock! {
         pub AlertConfigRepo {}
 
@@ -196,6 +234,18 @@ mod tests {
                 name: "background-task.sh".to_owned(),
                 expected_duration: 300,
                 grace_duration: 100,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![],
             },
             Monitor {
@@ -204,6 +254,18 @@ mod tests {
                 name: "get-pending-orders | generate invoices".to_owned(),
                 expected_duration: 21_600,
                 grace_duration: 1_800,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![],
             },
         ]
@@ -219,6 +281,9 @@ mod tests {
                 active: true,
                 on_late: true,
                 on_error: false,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
                 monitors: vec![AppliedMonitor {
                     monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                     name: "background-task.sh".to_owned(),
@@ -235,6 +300,9 @@ mod tests {
                 active: true,
                 on_late: false,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
                 monitors: vec![AppliedMonitor {
                     monitor_id: gen_uuid("841bdefb-e45c-4361-a8cb-8d247f4a088b"),
                     name: "get-pending-orders | generate invoices".to_owned(),
@@ -251,6 +319,9 @@ mod tests {
                 active: true,
                 on_late: true,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
                 monitors: vec![],
                 type_: AlertType::Slack(SlackAlertConfig {
                     channel: "bar-channel".to_owned(),
@@ -297,7 +368,28 @@ mod tests {
             })
             .returning(|_| Ok(()));
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut mock_audit_log = MockAuditLog::new();
+        mock_audit_log
+            .expect_record()
+            .times(2)
+            .withf(|event: &AuditEvent| {
+                let monitor_count = |snapshot: &Option<serde_json::Value>| {
+                    snapshot.as_ref().unwrap()["monitors"]
+                        .as_array()
+                        .unwrap()
+                        .len()
+                };
+                event.actor == actor()
+                    && event.action == AuditAction::Updated
+                    && monitor_count(&event.after) == monitor_count(&event.before) + 1
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_audit_log,
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -306,6 +398,7 @@ mod tests {
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                     gen_uuid("f3b3b3b3-3b3b-4b3b-8b3b-3b3b3b3b3b3b"),
                 ],
+                &actor(),
             )
             .await;
         assert!(result.is_ok());
@@ -351,12 +444,30 @@ mod tests {
             })
             .returning(|_| Ok(()));
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut mock_audit_log = MockAuditLog::new();
+        mock_audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Updated
+                    && event.entity.id == gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b")
+                    && event.before.as_ref().unwrap()["monitors"][0]["name"] == "background-task.sh"
+                    && event.after.as_ref().unwrap()["monitors"] == serde_json::json!([])
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_audit_log,
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                &actor(),
             )
             .await;
 
@@ -389,7 +500,11 @@ mod tests {
         mock_alert_config_repo.expect_get().never();
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -398,6 +513,7 @@ mod tests {
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                     gen_uuid("f3b3b3b3-3b3b-4b3b-8b3b-3b3b3b3b3b3b"),
                 ],
+                &actor(),
             )
             .await;
 
@@ -435,12 +551,17 @@ mod tests {
         mock_alert_config_repo.expect_get().never();
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                &actor(),
             )
             .await;
 
@@ -492,7 +613,11 @@ mod tests {
         // Since we couldn't find the alert config, we shouldn't call the save method.
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -501,6 +626,7 @@ mod tests {
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                     gen_uuid("f3b3b3b3-3b3b-4b3b-8b3b-3b3b3b3b3b3b"),
                 ],
+                &actor(),
             )
             .await;
 
@@ -546,12 +672,17 @@ mod tests {
         // Since we couldn't find the alert config, we shouldn't call the save method.
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                &actor(),
             )
             .await;
 
@@ -615,7 +746,20 @@ mod tests {
             })
             .returning(|_| Err(Error::RepositoryError("test error".to_string())));
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut mock_audit_log = MockAuditLog::new();
+        mock_audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.entity.id == gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b")
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_audit_log,
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -624,6 +768,7 @@ mod tests {
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                     gen_uuid("f3b3b3b3-3b3b-4b3b-8b3b-3b3b3b3b3b3b"),
                 ],
+                &actor(),
             )
             .await;
 
@@ -687,12 +832,17 @@ mod tests {
             })
             .returning(|_| Err(Error::RepositoryError("test error".to_string())));
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                &actor(),
             )
             .await;
 
@@ -754,7 +904,11 @@ mod tests {
         // Since we couldn't associate one of the alert config, we shouldn't call the save method.
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -763,6 +917,7 @@ mod tests {
                     gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                 ],
+                &actor(),
             )
             .await;
 
@@ -832,12 +987,17 @@ mod tests {
         mock_alert_config_repo.expect_get().never();
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
+                &actor(),
             )
             .await;
 
@@ -889,7 +1049,11 @@ mod tests {
         mock_alert_config_repo.expect_get_by_ids().never();
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -898,6 +1062,7 @@ mod tests {
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                     gen_uuid("f3b3b3b3-3b3b-4b3b-8b3b-3b3b3b3b3b3b"),
                 ],
+                &actor(),
             )
             .await;
 
@@ -933,12 +1098,17 @@ mod tests {
         mock_alert_config_repo.expect_get().never();
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                &actor(),
             )
             .await;
 
@@ -985,7 +1155,11 @@ mod tests {
         // Since we couldn't find the alert config, we shouldn't call the save method.
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .associate_alerts(
                 "foo-tenant",
@@ -994,6 +1168,7 @@ mod tests {
                     gen_uuid("f2b2b2b2-2b2b-4b2b-8b2b-2b2b2b2b2b2b"),
                     gen_uuid("f3b3b3b3-3b3b-4b3b-8b3b-3b3b3b3b3b3b"),
                 ],
+                &actor(),
             )
             .await;
 
@@ -1037,12 +1212,17 @@ mod tests {
         // Since we couldn't find the alert config, we shouldn't call the save method.
         mock_alert_config_repo.expect_save().never();
 
-        let mut service = MonitorAssociationService::new(mock_monitor_repo, mock_alert_config_repo);
+        let mut service = MonitorAssociationService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            MockAuditLog::new(),
+        );
         let result = service
             .disassociate_alert(
                 "foo-tenant",
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                &actor(),
             )
             .await;
 
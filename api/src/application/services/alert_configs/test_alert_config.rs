@@ -84,6 +84,9 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     monitors: vec![],
                     type_: AlertType::Slack(SlackAlertConfig {
                         token: "token".to_owned(),
@@ -255,6 +258,9 @@ mod tests {
                     active: true,
                     on_late: true,
                     on_error: true,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     monitors: vec![],
                     type_: AlertType::Slack(SlackAlertConfig {
                         token: "token".to_owned(),
@@ -1,19 +1,26 @@
 use tracing::info;
 use uuid::Uuid;
 
-use crate::domain::models::{AlertConfig, AlertType};
+use crate::domain::models::{Actor, AlertConfig, AuditEvent};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
 use crate::infrastructure::repositories::Repository;
 
-use super::AlertConfigData;
+use super::{parse_alert_type, parse_label_selector, AlertConfigData};
 
-pub struct UpdateAlertConfigService<T: Repository<AlertConfig>> {
-    repo: T,
+pub struct UpdateAlertConfigService<
+    AlertConfigRepo: Repository<AlertConfig>,
+    AuditLogRepo: AuditLog,
+> {
+    repo: AlertConfigRepo,
+    audit_log: AuditLogRepo,
 }
 
-impl<T: Repository<AlertConfig>> UpdateAlertConfigService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<AlertConfigRepo: Repository<AlertConfig>, AuditLogRepo: AuditLog>
+    UpdateAlertConfigService<AlertConfigRepo, AuditLogRepo>
+{
+    pub fn new(repo: AlertConfigRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
     }
 
     pub async fn update_by_id(
@@ -21,15 +28,17 @@ impl<T: Repository<AlertConfig>> UpdateAlertConfigService<T> {
         alert_config_id: Uuid,
         tenant: &str,
         new_data: AlertConfigData,
+        actor: &Actor,
     ) -> Result<AlertConfig, Error> {
-        let alert_type: AlertType = serde_json::from_value(new_data.type_)
-            .map_err(|error| Error::InvalidAlertConfig(error.to_string()))?;
+        let alert_type = parse_alert_type(new_data.type_)?;
+        let label_selector = parse_label_selector(new_data.label_selector.as_deref())?;
 
         let mut alert_config = self
             .repo
             .get(alert_config_id, tenant)
             .await?
             .ok_or(Error::AlertConfigNotFound(vec![alert_config_id]))?;
+        let original = alert_config.clone();
 
         // We want to log the original and new values of the alert configuration, so we take the
         // original values herebefore modifying the alert configuration.
@@ -38,6 +47,12 @@ impl<T: Repository<AlertConfig>> UpdateAlertConfigService<T> {
             alert_config.active,
             alert_config.on_late,
             alert_config.on_error,
+            alert_config.on_recovery,
+            alert_config.on_anomaly,
+            alert_config
+                .label_selector
+                .as_ref()
+                .map(ToString::to_string),
             alert_config.type_.clone(),
         );
 
@@ -46,15 +61,27 @@ impl<T: Repository<AlertConfig>> UpdateAlertConfigService<T> {
             new_data.active,
             new_data.on_late,
             new_data.on_error,
+            new_data.on_recovery,
+            new_data.on_anomaly,
+            label_selector,
             alert_type,
         )?;
         self.repo.save(&alert_config).await?;
+        self.audit_log
+            .record(&AuditEvent::updated(actor, &original, &alert_config))
+            .await?;
 
         let new_values = (
             &alert_config.name,
             alert_config.active,
             alert_config.on_late,
             alert_config.on_error,
+            alert_config.on_recovery,
+            alert_config.on_anomaly,
+            alert_config
+                .label_selector
+                .as_ref()
+                .map(ToString::to_string),
             alert_config.type_.clone(),
         );
         info!(
@@ -77,10 +104,19 @@ mod tests {
 
     use test_utils::{gen_uuid, logging::TracingLog};
 
-    use crate::{domain::models::SlackAlertConfig, infrastructure::repositories::MockRepository};
+    use crate::domain::models::{AlertType, AuditAction, SlackAlertConfig};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     #[fixture]
     fn alert_config() -> AlertConfig {
         AlertConfig {
@@ -90,6 +126,9 @@ mod tests {
             active: true,
             on_late: false,
             on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
             monitors: vec![],
             type_: AlertType::Slack(SlackAlertConfig {
                 channel: "channel".to_owned(),
@@ -121,6 +160,9 @@ mod tests {
                 active: false,
                 on_late: false,
                 on_error: false,
+                on_recovery: true,
+                on_anomaly: true,
+                label_selector: Some("team=payments".parse().unwrap()),
                 monitors: vec![],
                 type_: AlertType::Slack(SlackAlertConfig {
                     channel: "new-channel".to_owned(),
@@ -129,7 +171,20 @@ mod tests {
             }))
             .returning(|_| Ok(()));
 
-        let mut service = UpdateAlertConfigService::new(mock_repo);
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Updated
+                    && event.entity.id == gen_uuid("89c15477-0d01-4900-9042-177775e1b247")
+                    && event.before.as_ref().unwrap()["name"] == "name"
+                    && event.after.as_ref().unwrap()["name"] == "new_name"
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = UpdateAlertConfigService::new(mock_repo, audit_log);
 
         let updated_alert_config = service
             .update_by_id(
@@ -140,6 +195,9 @@ mod tests {
                     active: false,
                     on_late: false,
                     on_error: false,
+                    on_recovery: true,
+                    on_anomaly: true,
+                    label_selector: Some("team=payments".to_owned()),
                     type_: serde_json::json!({
                         "slack": {
                             "channel": "new-channel",
@@ -147,6 +205,7 @@ mod tests {
                         }
                     }),
                 },
+                &actor(),
             )
             .await
             .unwrap();
@@ -155,6 +214,12 @@ mod tests {
         assert!(!updated_alert_config.active);
         assert!(!updated_alert_config.on_late);
         assert!(!updated_alert_config.on_error);
+        assert!(updated_alert_config.on_recovery);
+        assert!(updated_alert_config.on_anomaly);
+        assert_eq!(
+            updated_alert_config.label_selector,
+            Some("team=payments".parse().unwrap())
+        );
         assert_eq!(
             updated_alert_config.type_,
             AlertType::Slack(SlackAlertConfig {
@@ -177,12 +242,18 @@ mod tests {
                         true, \
                         false, \
                         true, \
+                        false, \
+                        false, \
+                        None, \
                         Slack(SlackAlertConfig { channel: \"channel\", token: \"token\" })\
                     ) new_values=(\
                         \"new_name\", \
                         false, \
                         false, \
                         false, \
+                        true, \
+                        true, \
+                        Some(\"team=payments\"), \
                         Slack(SlackAlertConfig { channel: \"new-channel\", token: \"new-token\" }))"
             );
 
@@ -201,7 +272,7 @@ mod tests {
             .returning(|_, _| Ok(None));
         mock_repo.expect_save().never();
 
-        let mut service = UpdateAlertConfigService::new(mock_repo);
+        let mut service = UpdateAlertConfigService::new(mock_repo, MockAuditLog::new());
 
         let result = service
             .update_by_id(
@@ -212,6 +283,9 @@ mod tests {
                     active: false,
                     on_late: false,
                     on_error: false,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     type_: serde_json::json!({
                         "slack": {
                             "channel": "new-channel",
@@ -219,6 +293,7 @@ mod tests {
                         }
                     }),
                 },
+                &actor(),
             )
             .await;
 
@@ -248,7 +323,7 @@ mod tests {
             });
         mock_repo.expect_save().never();
 
-        let mut service = UpdateAlertConfigService::new(mock_repo);
+        let mut service = UpdateAlertConfigService::new(mock_repo, MockAuditLog::new());
 
         let result = service
             .update_by_id(
@@ -259,6 +334,9 @@ mod tests {
                     active: false,
                     on_late: false,
                     on_error: false,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     type_: serde_json::json!({
                         "slack": {
                             "channel": "new-channel",
@@ -266,6 +344,7 @@ mod tests {
                         }
                     }),
                 },
+                &actor(),
             )
             .await;
 
@@ -290,7 +369,7 @@ mod tests {
             .once()
             .returning(|_| Err(Error::RepositoryError("Failed to save".to_owned())));
 
-        let mut service = UpdateAlertConfigService::new(mock_repo);
+        let mut service = UpdateAlertConfigService::new(mock_repo, MockAuditLog::new());
 
         let result = service
             .update_by_id(
@@ -301,6 +380,9 @@ mod tests {
                     active: false,
                     on_late: false,
                     on_error: false,
+                    on_recovery: false,
+                    on_anomaly: false,
+                    label_selector: None,
                     type_: serde_json::json!({
                         "slack": {
                             "channel": "new-channel",
@@ -308,6 +390,7 @@ mod tests {
                         }
                     }),
                 },
+                &actor(),
             )
             .await;
 
@@ -1,34 +1,57 @@
-use rand::distr::Alphanumeric;
-use rand::Rng;
+use chrono::NaiveDateTime;
 use tracing::info;
+use uuid::Uuid;
 
-use crate::domain::models::ApiKey;
+use crate::domain::models::{Actor, ApiKey, ApiKeyScope, AuditEvent};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
 use crate::infrastructure::repositories::Repository;
 
-pub struct GenerateKeyService<T: Repository<ApiKey>> {
-    repo: T,
+pub struct GenerateKeyService<ApiKeyRepo: Repository<ApiKey>, AuditLogRepo: AuditLog> {
+    repo: ApiKeyRepo,
+    audit_log: AuditLogRepo,
 }
 
-impl<T: Repository<ApiKey>> GenerateKeyService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<ApiKeyRepo: Repository<ApiKey>, AuditLogRepo: AuditLog>
+    GenerateKeyService<ApiKeyRepo, AuditLogRepo>
+{
+    pub fn new(repo: ApiKeyRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
     }
 
-    pub async fn generate_key(&mut self, name: &str, tenant: &str) -> Result<String, Error> {
+    /// Generate a new API key, which has every scope unless `scopes` is given, can be used for any
+    /// monitor unless `monitor_ids` is given, and never expires unless `expires_at` is given.
+    pub async fn generate_key(
+        &mut self,
+        name: &str,
+        tenant: &str,
+        scopes: Option<Vec<ApiKeyScope>>,
+        monitor_ids: Option<Vec<Uuid>>,
+        expires_at: Option<NaiveDateTime>,
+        actor: &Actor,
+    ) -> Result<String, Error> {
         info!(
             tenant = tenant,
             "Generating new API key - name: '{}'...", &name
         );
 
-        let key: String = rand::rng()
-            .sample_iter(&Alphanumeric)
-            .take(32)
-            .map(char::from)
-            .collect();
+        let scopes = scopes.unwrap_or_else(|| ApiKeyScope::ALL.to_vec());
+        ApiKey::validate_scopes(&scopes)?;
 
-        let api_key = ApiKey::new(name.to_string(), key.clone(), tenant.to_string());
+        let key = ApiKey::random_key();
+
+        let api_key = ApiKey::new(
+            name.to_string(),
+            key.clone(),
+            tenant.to_string(),
+            scopes,
+            monitor_ids,
+            expires_at,
+        );
         self.repo.save(&api_key).await?;
+        self.audit_log
+            .record(&AuditEvent::created(actor, &api_key))
+            .await?;
 
         info!("Generated API key.");
         Ok(key)
@@ -41,22 +64,55 @@ mod tests {
     use tracing_test::traced_test;
 
     use test_utils::logging::TracingLog;
+    use test_utils::{gen_datetime, gen_uuid};
 
+    use crate::domain::models::{AuditAction, EntityType};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_generate_key_service() {
         let mut mock = MockRepository::new();
         mock.expect_save()
             .once()
-            .withf(|key: &ApiKey| key.name == "foo" && key.tenant == "tenant")
+            .withf(|key: &ApiKey| {
+                key.name == "foo"
+                    && key.tenant == "tenant"
+                    && key.scopes == ApiKeyScope::ALL.to_vec()
+                    && key.monitor_ids.is_none()
+                    && key.expires_at.is_none()
+            })
             .returning(|_| Ok(()));
 
-        let mut service = GenerateKeyService::new(mock);
-        let key = service.generate_key("foo", "tenant").await.unwrap();
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.tenant == "tenant"
+                    && event.actor == actor()
+                    && event.action == AuditAction::Created
+                    && event.entity.type_ == EntityType::ApiKey
+                    && event.after.as_ref().unwrap()["name"] == "foo"
+                    && event.after.as_ref().unwrap().get("key").is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = GenerateKeyService::new(mock, audit_log);
+        let key = service
+            .generate_key("foo", "tenant", None, None, None, &actor())
+            .await
+            .unwrap();
 
         assert_eq!(key.len(), 32);
 
@@ -77,6 +133,52 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn test_generate_restricted_key() {
+        let mut mock = MockRepository::new();
+        mock.expect_save()
+            .once()
+            .withf(|key: &ApiKey| {
+                key.scopes == vec![ApiKeyScope::HeartbeatsWrite]
+                    && key.monitor_ids
+                        == Some(vec![gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")])
+                    && key.expires_at == Some(gen_datetime("2025-06-01T00:00:00"))
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().once().returning(|_| Ok(()));
+
+        let mut service = GenerateKeyService::new(mock, audit_log);
+        let key = service
+            .generate_key(
+                "foo",
+                "tenant",
+                Some(vec![ApiKeyScope::HeartbeatsWrite]),
+                Some(vec![gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")]),
+                Some(gen_datetime("2025-06-01T00:00:00")),
+                &actor(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(key.len(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_generate_key_without_scopes() {
+        let mut service = GenerateKeyService::new(MockRepository::new(), MockAuditLog::new());
+        let error = service
+            .generate_key("foo", "tenant", Some(vec![]), None, None, &actor())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::InvalidApiKey("An API key must have at least one scope".to_owned())
+        );
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_generate_key_service_error() {
@@ -85,8 +187,11 @@ mod tests {
             .once()
             .returning(|_| Err(Error::RepositoryError("Failed to save key".to_owned())));
 
-        let mut service = GenerateKeyService::new(mock);
-        let key = service.generate_key("foo", "tenant").await.unwrap_err();
+        let mut service = GenerateKeyService::new(mock, MockAuditLog::new());
+        let key = service
+            .generate_key("foo", "tenant", None, None, None, &actor())
+            .await
+            .unwrap_err();
 
         assert_eq!(key, Error::RepositoryError("Failed to save key".to_owned()));
 
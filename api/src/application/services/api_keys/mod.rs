@@ -1,5 +1,7 @@
 pub mod generate_key;
 pub mod revoke_key;
+pub mod rotate_key;
 
 pub use generate_key::GenerateKeyService;
 pub use revoke_key::RevokeKeyService;
+pub use rotate_key::RotateKeyService;
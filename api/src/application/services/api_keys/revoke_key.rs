@@ -1,25 +1,37 @@
 use tracing::info;
 use uuid::Uuid;
 
-use crate::domain::models::ApiKey;
+use crate::domain::models::{Actor, ApiKey, AuditEvent};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
 use crate::infrastructure::repositories::Repository;
 
-pub struct RevokeKeyService<R: Repository<ApiKey>> {
-    repo: R,
+pub struct RevokeKeyService<ApiKeyRepo: Repository<ApiKey>, AuditLogRepo: AuditLog> {
+    repo: ApiKeyRepo,
+    audit_log: AuditLogRepo,
 }
 
-impl<R: Repository<ApiKey>> RevokeKeyService<R> {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+impl<ApiKeyRepo: Repository<ApiKey>, AuditLogRepo: AuditLog>
+    RevokeKeyService<ApiKeyRepo, AuditLogRepo>
+{
+    pub fn new(repo: ApiKeyRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
     }
 
-    pub async fn revoke_key(&mut self, api_key_id: Uuid, tenant: &str) -> Result<(), Error> {
+    pub async fn revoke_key(
+        &mut self,
+        api_key_id: Uuid,
+        tenant: &str,
+        actor: &Actor,
+    ) -> Result<(), Error> {
         info!(api_key_id = api_key_id.to_string(), "Revoking API key...");
 
         let api_key = self.repo.get(api_key_id, tenant).await?;
         if let Some(key) = api_key {
             self.repo.delete(&key).await?;
+            self.audit_log
+                .record(&AuditEvent::deleted(actor, &key))
+                .await?;
             info!(
                 api_key_id = api_key_id.to_string(),
                 "Revoked API key - '{}'", &key.name
@@ -39,10 +51,19 @@ mod tests {
     use test_utils::gen_uuid;
     use test_utils::logging::TracingLog;
 
+    use crate::domain::models::{ApiKeyScope, AuditAction};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_revoke_key_service() {
@@ -62,6 +83,9 @@ mod tests {
                     last_used: None,
                     last_used_monitor_id: None,
                     last_used_monitor_name: None,
+                    scopes: ApiKeyScope::ALL.to_vec(),
+                    monitor_ids: None,
+                    expires_at: None,
                 }))
             });
         mock.expect_delete()
@@ -69,8 +93,20 @@ mod tests {
             .withf(move |key: &ApiKey| key.api_key_id == api_key_id)
             .returning(|_| Ok(()));
 
-        let mut service = RevokeKeyService::new(mock);
-        let result = service.revoke_key(api_key_id, "tenant").await;
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(move |event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Deleted
+                    && event.entity.id == api_key_id
+                    && event.after.is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = RevokeKeyService::new(mock, audit_log);
+        let result = service.revoke_key(api_key_id, "tenant", &actor()).await;
 
         assert!(result.is_ok());
 
@@ -101,9 +137,9 @@ mod tests {
         let mut mock = MockRepository::new();
         mock.expect_get().once().returning(|_, _| Ok(None));
 
-        let mut service = RevokeKeyService::new(mock);
+        let mut service = RevokeKeyService::new(mock, MockAuditLog::new());
         let api_key_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
-        let result = service.revoke_key(api_key_id, "tenant").await;
+        let result = service.revoke_key(api_key_id, "tenant", &actor()).await;
 
         assert_eq!(result, Err(Error::ApiKeyNotFound(api_key_id)));
 
@@ -138,14 +174,17 @@ mod tests {
                 last_used: None,
                 last_used_monitor_id: None,
                 last_used_monitor_name: None,
+                scopes: ApiKeyScope::ALL.to_vec(),
+                monitor_ids: None,
+                expires_at: None,
             }))
         });
         mock.expect_delete()
             .once()
             .returning(|_| Err(Error::RepositoryError("Failed to delete key".to_owned())));
 
-        let mut service = RevokeKeyService::new(mock);
-        let result = service.revoke_key(api_key_id, "tenant").await;
+        let mut service = RevokeKeyService::new(mock, MockAuditLog::new());
+        let result = service.revoke_key(api_key_id, "tenant", &actor()).await;
 
         assert_eq!(
             result,
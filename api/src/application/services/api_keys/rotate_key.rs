@@ -0,0 +1,216 @@
+use chrono::TimeDelta;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::models::{Actor, ApiKey, AuditEvent};
+use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::Repository;
+
+pub struct RotateKeyService<ApiKeyRepo: Repository<ApiKey>, AuditLogRepo: AuditLog> {
+    repo: ApiKeyRepo,
+    audit_log: AuditLogRepo,
+}
+
+impl<ApiKeyRepo: Repository<ApiKey>, AuditLogRepo: AuditLog>
+    RotateKeyService<ApiKeyRepo, AuditLogRepo>
+{
+    pub fn new(repo: ApiKeyRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
+    }
+
+    /// Issue a replacement for an API key, returning the new key. The old key keeps working for
+    /// `overlap`, so that it can be replaced wherever it's used before it expires.
+    pub async fn rotate_key(
+        &mut self,
+        api_key_id: Uuid,
+        tenant: &str,
+        overlap: TimeDelta,
+        actor: &Actor,
+    ) -> Result<String, Error> {
+        info!(api_key_id = api_key_id.to_string(), "Rotating API key...");
+
+        let mut old_key = match self.repo.get(api_key_id, tenant).await? {
+            Some(key) => key,
+            None => return Err(Error::ApiKeyNotFound(api_key_id)),
+        };
+
+        let original = old_key.clone();
+        let key = ApiKey::random_key();
+        let new_key = old_key.rotate(key.clone(), overlap);
+        self.repo.save(&new_key).await?;
+        self.repo.save(&old_key).await?;
+        self.audit_log
+            .record(&AuditEvent::created(actor, &new_key))
+            .await?;
+        self.audit_log
+            .record(&AuditEvent::updated(actor, &original, &old_key))
+            .await?;
+
+        info!(
+            api_key_id = api_key_id.to_string(),
+            new_api_key_id = new_key.api_key_id.to_string(),
+            "Rotated API key - '{}'",
+            &old_key.name
+        );
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use mockall::Sequence;
+    use tracing_test::traced_test;
+
+    use test_utils::logging::TracingLog;
+    use test_utils::{gen_relative_datetime, gen_uuid};
+
+    use crate::domain::models::{ApiKeyScope, AuditAction};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    fn api_key(api_key_id: Uuid) -> ApiKey {
+        ApiKey {
+            api_key_id,
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            key: "not-a-real-key".to_owned(),
+            masked: "abcd************dcba".to_owned(),
+            created: gen_relative_datetime(-3600),
+            last_used: None,
+            last_used_monitor_id: None,
+            last_used_monitor_name: None,
+            scopes: vec![ApiKeyScope::JobsWrite],
+            monitor_ids: Some(vec![gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")]),
+            expires_at: None,
+        }
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_rotate_key_service() {
+        let api_key_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let mut seq = Sequence::new();
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(eq(api_key_id), eq("tenant"))
+            .returning(move |_, _| Ok(Some(api_key(api_key_id))));
+        mock.expect_save()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(move |key: &ApiKey| {
+                key.api_key_id != api_key_id
+                    && key.name == "foo"
+                    && key.tenant == "tenant"
+                    && key.scopes == vec![ApiKeyScope::JobsWrite]
+                    && key.monitor_ids
+                        == Some(vec![gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")])
+                    && key.expires_at.is_none()
+            })
+            .returning(|_| Ok(()));
+        mock.expect_save()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(move |key: &ApiKey| {
+                // The old key expires once the overlap has passed.
+                key.api_key_id == api_key_id
+                    && key.expires_at.is_some_and(|expires_at| {
+                        (expires_at - gen_relative_datetime(300))
+                            .num_seconds()
+                            .abs()
+                            < 5
+                    })
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(move |event: &AuditEvent| {
+                event.action == AuditAction::Created && event.entity.id != api_key_id
+            })
+            .returning(|_| Ok(()));
+        audit_log
+            .expect_record()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(move |event: &AuditEvent| {
+                event.action == AuditAction::Updated
+                    && event.entity.id == api_key_id
+                    && event.before.as_ref().unwrap()["expires_at"].is_null()
+                    && event.after.as_ref().unwrap()["expires_at"].is_string()
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = RotateKeyService::new(mock, audit_log);
+        let key = service
+            .rotate_key(api_key_id, "tenant", TimeDelta::seconds(300), &actor())
+            .await
+            .unwrap();
+
+        assert_eq!(key.len(), 32);
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 2);
+
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                "Rotating API key... api_key_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
+            );
+
+            assert_eq!(logs[1].level, tracing::Level::INFO);
+            assert!(logs[1].body.starts_with(
+                "Rotated API key - 'foo' api_key_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_rotate_key_service_not_found() {
+        let api_key_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(eq(api_key_id), eq("tenant"))
+            .returning(|_, _| Ok(None));
+        mock.expect_save().never();
+
+        let mut service = RotateKeyService::new(mock, MockAuditLog::new());
+        let result = service
+            .rotate_key(api_key_id, "tenant", TimeDelta::seconds(300), &actor())
+            .await;
+
+        assert_eq!(result, Err(Error::ApiKeyNotFound(api_key_id)));
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                "Rotating API key... api_key_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
+            );
+
+            Ok(())
+        });
+    }
+}
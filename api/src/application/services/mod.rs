@@ -1,52 +1,137 @@
 pub mod alert_configs;
 pub mod api_keys;
 pub mod monitors;
+pub mod webhook_subscriptions;
 
 use crate::domain::models::Monitor;
 use crate::domain::services::get_notifier::GetNotifierService;
 use crate::domain::services::monitors::order_monitors_by_last_started_job;
+use crate::infrastructure::activity::ActivityPublisher;
 use crate::infrastructure::database::DbPool;
 use crate::infrastructure::repositories::alert_config::AlertConfigRepository;
 use crate::infrastructure::repositories::api_key::ApiKeyRepository;
+use crate::infrastructure::repositories::audit_log::AuditLogRepository;
 use crate::infrastructure::repositories::monitor::MonitorRepository;
+use crate::infrastructure::repositories::webhook_delivery::WebhookDeliveryRepository;
+use crate::infrastructure::repositories::webhook_subscription::WebhookSubscriptionRepository;
+use crate::infrastructure::webhooks::WebhookSender;
 
 use alert_configs::{
     CreateAlertConfigService, DeleteAlertConfigService, FetchAlertConfigs,
     MonitorAssociationService, TestAlertConfigService, UpdateAlertConfigService,
 };
-use api_keys::{GenerateKeyService, RevokeKeyService};
+use api_keys::{GenerateKeyService, RevokeKeyService, RotateKeyService};
 use monitors::{
-    AlertErroneousJobsService, CreateMonitorService, DeleteMonitorService, FetchJobService,
-    FetchMonitorsService, FinishJobService, StartJobService, UpdateMonitorService,
+    AlertErroneousJobsService, AlertMissedStartsService, CreateMonitorService,
+    DeleteMonitorService, FetchDurationStatsService, FetchJobHistoryService,
+    FetchJobMetricsService, FetchJobService, FetchMetricsService, FetchMonitorsService,
+    FinishJobService, PauseMonitorService, PruneJobsService, RecordHeartbeatService,
+    StartJobService, UpdateMonitorService,
+};
+use webhook_subscriptions::{
+    CreateSubscriptionService, DeleteSubscriptionService, DeliverWebhooksService,
+    UpdateSubscriptionService,
 };
 
 pub fn get_create_alert_config_service(
     pool: &DbPool,
-) -> CreateAlertConfigService<AlertConfigRepository> {
-    CreateAlertConfigService::new(AlertConfigRepository::new(pool))
+) -> CreateAlertConfigService<AlertConfigRepository<'_>, AuditLogRepository<'_>> {
+    CreateAlertConfigService::new(
+        AlertConfigRepository::new(pool),
+        AuditLogRepository::new(pool),
+    )
 }
 
-pub fn get_create_monitor_service(pool: &DbPool) -> CreateMonitorService<MonitorRepository> {
-    CreateMonitorService::new(MonitorRepository::new(pool))
+pub fn get_create_monitor_service(
+    pool: &DbPool,
+) -> CreateMonitorService<
+    MonitorRepository<'_>,
+    AuditLogRepository<'_>,
+    WebhookDeliveryRepository<'_>,
+> {
+    CreateMonitorService::new(
+        MonitorRepository::new(pool),
+        AuditLogRepository::new(pool),
+        WebhookDeliveryRepository::new(pool),
+    )
+}
+
+pub fn get_create_subscription_service(
+    pool: &DbPool,
+) -> CreateSubscriptionService<WebhookSubscriptionRepository<'_>, AuditLogRepository<'_>> {
+    CreateSubscriptionService::new(
+        WebhookSubscriptionRepository::new(pool),
+        AuditLogRepository::new(pool),
+    )
 }
 
 pub fn get_delete_alert_config_service(
     pool: &DbPool,
-) -> DeleteAlertConfigService<AlertConfigRepository> {
-    DeleteAlertConfigService::new(AlertConfigRepository::new(pool))
+) -> DeleteAlertConfigService<AlertConfigRepository<'_>, AuditLogRepository<'_>> {
+    DeleteAlertConfigService::new(
+        AlertConfigRepository::new(pool),
+        AuditLogRepository::new(pool),
+    )
+}
+
+pub fn get_delete_monitor_service(
+    pool: &DbPool,
+) -> DeleteMonitorService<
+    MonitorRepository<'_>,
+    AuditLogRepository<'_>,
+    WebhookDeliveryRepository<'_>,
+> {
+    DeleteMonitorService::new(
+        MonitorRepository::new(pool),
+        AuditLogRepository::new(pool),
+        WebhookDeliveryRepository::new(pool),
+    )
+}
+
+pub fn get_delete_subscription_service(
+    pool: &DbPool,
+) -> DeleteSubscriptionService<WebhookSubscriptionRepository<'_>, AuditLogRepository<'_>> {
+    DeleteSubscriptionService::new(
+        WebhookSubscriptionRepository::new(pool),
+        AuditLogRepository::new(pool),
+    )
 }
 
-pub fn get_delete_monitor_service(pool: &DbPool) -> DeleteMonitorService<MonitorRepository> {
-    DeleteMonitorService::new(MonitorRepository::new(pool))
+pub fn get_deliver_webhooks_service(
+    pool: &DbPool,
+) -> DeliverWebhooksService<WebhookDeliveryRepository<'_>, WebhookSender> {
+    DeliverWebhooksService::new(WebhookDeliveryRepository::new(pool), WebhookSender::new())
 }
 
-pub fn get_fetch_job_service(pool: &DbPool) -> FetchJobService<MonitorRepository> {
+pub fn get_fetch_duration_stats_service(
+    pool: &DbPool,
+) -> FetchDurationStatsService<MonitorRepository<'_>> {
+    FetchDurationStatsService::new(MonitorRepository::new(pool))
+}
+
+pub fn get_fetch_job_metrics_service(
+    pool: &DbPool,
+) -> FetchJobMetricsService<MonitorRepository<'_>> {
+    FetchJobMetricsService::new(MonitorRepository::new(pool))
+}
+
+pub fn get_fetch_job_service(pool: &DbPool) -> FetchJobService<MonitorRepository<'_>> {
     FetchJobService::new(MonitorRepository::new(pool))
 }
 
+pub fn get_fetch_job_history_service(
+    pool: &DbPool,
+) -> FetchJobHistoryService<MonitorRepository<'_>> {
+    FetchJobHistoryService::new(MonitorRepository::new(pool))
+}
+
+pub fn get_fetch_metrics_service(pool: &DbPool) -> FetchMetricsService<MonitorRepository<'_>> {
+    FetchMetricsService::new(MonitorRepository::new(pool))
+}
+
 pub fn get_fetch_monitors_service<'a>(
     pool: &DbPool,
-) -> FetchMonitorsService<'a, MonitorRepository, impl Fn(&mut [Monitor])> {
+) -> FetchMonitorsService<'a, MonitorRepository<'_>, impl Fn(&mut [Monitor])> {
     FetchMonitorsService::new(
         MonitorRepository::new(pool),
         &order_monitors_by_last_started_job,
@@ -55,7 +140,7 @@ pub fn get_fetch_monitors_service<'a>(
 
 pub fn get_fetch_alert_configs_service(
     pool: &DbPool,
-) -> FetchAlertConfigs<MonitorRepository, AlertConfigRepository> {
+) -> FetchAlertConfigs<MonitorRepository<'_>, AlertConfigRepository<'_>> {
     FetchAlertConfigs::new(
         MonitorRepository::new(pool),
         AlertConfigRepository::new(pool),
@@ -64,55 +149,155 @@ pub fn get_fetch_alert_configs_service(
 
 pub fn get_finish_job_service(
     pool: &DbPool,
-) -> FinishJobService<MonitorRepository, ApiKeyRepository> {
-    FinishJobService::new(MonitorRepository::new(pool), ApiKeyRepository::new(pool))
+) -> FinishJobService<
+    MonitorRepository<'_>,
+    ApiKeyRepository<'_>,
+    ActivityPublisher<'_>,
+    WebhookDeliveryRepository<'_>,
+> {
+    FinishJobService::new(
+        MonitorRepository::new(pool),
+        ApiKeyRepository::new(pool),
+        ActivityPublisher::new(pool),
+        WebhookDeliveryRepository::new(pool),
+    )
 }
 
-pub fn get_generate_key_service(pool: &DbPool) -> GenerateKeyService<ApiKeyRepository> {
-    GenerateKeyService::new(ApiKeyRepository::new(pool))
+pub fn get_generate_key_service(
+    pool: &DbPool,
+) -> GenerateKeyService<ApiKeyRepository<'_>, AuditLogRepository<'_>> {
+    GenerateKeyService::new(ApiKeyRepository::new(pool), AuditLogRepository::new(pool))
 }
 
 pub fn get_alert_erroneous_jobs_service(
     pool: &DbPool,
-) -> AlertErroneousJobsService<MonitorRepository, AlertConfigRepository, GetNotifierService> {
+) -> AlertErroneousJobsService<
+    MonitorRepository<'_>,
+    AlertConfigRepository<'_>,
+    GetNotifierService,
+    ActivityPublisher<'_>,
+> {
     AlertErroneousJobsService::new(
         MonitorRepository::new(pool),
         AlertConfigRepository::new(pool),
         GetNotifierService::new(),
+        ActivityPublisher::new(pool),
+    )
+}
+
+pub fn get_alert_missed_starts_service(
+    pool: &DbPool,
+) -> AlertMissedStartsService<
+    MonitorRepository<'_>,
+    AlertConfigRepository<'_>,
+    GetNotifierService,
+    ActivityPublisher<'_>,
+> {
+    AlertMissedStartsService::new(
+        MonitorRepository::new(pool),
+        AlertConfigRepository::new(pool),
+        GetNotifierService::new(),
+        ActivityPublisher::new(pool),
     )
 }
 
 pub fn get_monitor_association_service(
     pool: &DbPool,
-) -> MonitorAssociationService<MonitorRepository, AlertConfigRepository> {
+) -> MonitorAssociationService<
+    MonitorRepository<'_>,
+    AlertConfigRepository<'_>,
+    AuditLogRepository<'_>,
+> {
     MonitorAssociationService::new(
         MonitorRepository::new(pool),
         AlertConfigRepository::new(pool),
+        AuditLogRepository::new(pool),
     )
 }
 
-pub fn get_revoke_key_service(pool: &DbPool) -> RevokeKeyService<ApiKeyRepository> {
-    RevokeKeyService::new(ApiKeyRepository::new(pool))
+pub fn get_pause_monitor_service(
+    pool: &DbPool,
+) -> PauseMonitorService<MonitorRepository<'_>, AuditLogRepository<'_>, WebhookDeliveryRepository<'_>>
+{
+    PauseMonitorService::new(
+        MonitorRepository::new(pool),
+        AuditLogRepository::new(pool),
+        WebhookDeliveryRepository::new(pool),
+    )
+}
+
+pub fn get_prune_jobs_service(pool: &DbPool) -> PruneJobsService<MonitorRepository<'_>> {
+    PruneJobsService::new(MonitorRepository::new(pool))
+}
+
+pub fn get_revoke_key_service(
+    pool: &DbPool,
+) -> RevokeKeyService<ApiKeyRepository<'_>, AuditLogRepository<'_>> {
+    RevokeKeyService::new(ApiKeyRepository::new(pool), AuditLogRepository::new(pool))
+}
+
+pub fn get_rotate_key_service(
+    pool: &DbPool,
+) -> RotateKeyService<ApiKeyRepository<'_>, AuditLogRepository<'_>> {
+    RotateKeyService::new(ApiKeyRepository::new(pool), AuditLogRepository::new(pool))
+}
+
+pub fn get_record_heartbeat_service(
+    pool: &DbPool,
+) -> RecordHeartbeatService<MonitorRepository<'_>, ApiKeyRepository<'_>> {
+    RecordHeartbeatService::new(MonitorRepository::new(pool), ApiKeyRepository::new(pool))
 }
 
 pub fn get_start_job_service(
     pool: &DbPool,
-) -> StartJobService<MonitorRepository, ApiKeyRepository> {
-    StartJobService::new(MonitorRepository::new(pool), ApiKeyRepository::new(pool))
+) -> StartJobService<
+    MonitorRepository<'_>,
+    ApiKeyRepository<'_>,
+    ActivityPublisher<'_>,
+    WebhookDeliveryRepository<'_>,
+> {
+    StartJobService::new(
+        MonitorRepository::new(pool),
+        ApiKeyRepository::new(pool),
+        ActivityPublisher::new(pool),
+        WebhookDeliveryRepository::new(pool),
+    )
 }
 
 pub fn get_test_alert_config_service(
     pool: &DbPool,
-) -> TestAlertConfigService<AlertConfigRepository, GetNotifierService> {
+) -> TestAlertConfigService<AlertConfigRepository<'_>, GetNotifierService> {
     TestAlertConfigService::new(AlertConfigRepository::new(pool), GetNotifierService::new())
 }
 
 pub fn get_update_alert_config_service(
     pool: &DbPool,
-) -> UpdateAlertConfigService<AlertConfigRepository> {
-    UpdateAlertConfigService::new(AlertConfigRepository::new(pool))
+) -> UpdateAlertConfigService<AlertConfigRepository<'_>, AuditLogRepository<'_>> {
+    UpdateAlertConfigService::new(
+        AlertConfigRepository::new(pool),
+        AuditLogRepository::new(pool),
+    )
+}
+
+pub fn get_update_monitor_service(
+    pool: &DbPool,
+) -> UpdateMonitorService<
+    MonitorRepository<'_>,
+    AuditLogRepository<'_>,
+    WebhookDeliveryRepository<'_>,
+> {
+    UpdateMonitorService::new(
+        MonitorRepository::new(pool),
+        AuditLogRepository::new(pool),
+        WebhookDeliveryRepository::new(pool),
+    )
 }
 
-pub fn get_update_monitor_service(pool: &DbPool) -> UpdateMonitorService<MonitorRepository> {
-    UpdateMonitorService::new(MonitorRepository::new(pool))
+pub fn get_update_subscription_service(
+    pool: &DbPool,
+) -> UpdateSubscriptionService<WebhookSubscriptionRepository<'_>, AuditLogRepository<'_>> {
+    UpdateSubscriptionService::new(
+        WebhookSubscriptionRepository::new(pool),
+        AuditLogRepository::new(pool),
+    )
 }
@@ -1,9 +1,12 @@
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::domain::models::{AlertConfig, Job, Monitor};
+use crate::domain::models::{
+    Activity, ActivityEvent, AlertConfig, AlertKind, DurationAnomaly, FailureCount, Job, Monitor,
+};
 use crate::domain::services::get_notifier::GetNotifier;
 use crate::errors::Error;
+use crate::infrastructure::activity::PublishActivity;
 use crate::infrastructure::repositories::{
     alert_config::GetByMonitors, monitor::GetWithErroneousJobs, Repository,
 };
@@ -12,27 +15,32 @@ pub struct AlertErroneousJobsService<
     MonitorRepo: GetWithErroneousJobs + Repository<Monitor>,
     AlertConfigRepo: GetByMonitors,
     NotifierFactory: GetNotifier,
+    Publisher: PublishActivity,
 > {
     monitor_repo: MonitorRepo,
     alert_config_repo: AlertConfigRepo,
     notifier_factory: NotifierFactory,
+    publisher: Publisher,
 }
 
 impl<
         MonitorRepo: GetWithErroneousJobs + Repository<Monitor>,
         AlertConfigRepo: GetByMonitors,
         NotifierFactory: GetNotifier,
-    > AlertErroneousJobsService<MonitorRepo, AlertConfigRepo, NotifierFactory>
+        Publisher: PublishActivity,
+    > AlertErroneousJobsService<MonitorRepo, AlertConfigRepo, NotifierFactory, Publisher>
 {
     pub fn new(
         monitor_repo: MonitorRepo,
         alert_config_repo: AlertConfigRepo,
         notifier_factory: NotifierFactory,
+        publisher: Publisher,
     ) -> Self {
         Self {
             monitor_repo,
             alert_config_repo,
             notifier_factory,
+            publisher,
         }
     }
 
@@ -94,15 +102,37 @@ impl<
         monitor: &mut Monitor,
         alert_configs: &[AlertConfig],
     ) -> Result<(), Error> {
-        // Get all alert configs for this monitor.
+        // Get all alert configs for this monitor, whether associated directly or by its labels.
         let required_alert_configs: Vec<&AlertConfig> = alert_configs
             .iter()
-            .filter(|alert_config| alert_config.is_associated_with_monitor(monitor))
+            .filter(|alert_config| alert_config.applies_to_monitor(monitor))
             .collect();
 
-        // Get jobs to alert on.
         let monitor_id = monitor.monitor_id;
         let monitor_name = monitor.name.clone();
+        let tenant = monitor.tenant.clone();
+
+        // Resolve alerts sent in previous checks that are no longer relevant. We do this before
+        // alerting, so that alerts are never triggered and resolved in the same check.
+        for job in monitor.jobs_pending_late_resolution() {
+            self.resolve_late(&monitor_id, &monitor_name, job, &required_alert_configs)
+                .await?;
+        }
+        for job in monitor.jobs_pending_error_resolution() {
+            self.resolve_errored(&monitor_id, &monitor_name, job, &required_alert_configs)
+                .await?;
+        }
+
+        // Jobs that fell short of the monitor's alert threshold are never alerted on, so they're
+        // marked as such rather than being evaluated again on every check.
+        for job in monitor.jobs_below_alert_threshold() {
+            self.skip_alerts_below_threshold(&tenant, &monitor_id, &monitor_name, job)
+                .await;
+        }
+
+        // Get jobs to alert on. Alerts for jobs that ran while the monitor was paused or in a
+        // maintenance window are suppressed rather than sent.
+        let in_maintenance = monitor.in_maintenance();
         let jobs_pending_alerts = monitor.jobs_pending_alerts();
         info!(
             monitor_id = ?monitor_id,
@@ -110,13 +140,117 @@ impl<
             jobs_pending_alerts.len(),
             monitor_name
         );
-        for job in jobs_pending_alerts {
-            self.alert_late(&monitor_id, &monitor_name, job, &required_alert_configs)
+        for (job, failures) in jobs_pending_alerts {
+            if in_maintenance || job.during_maintenance {
+                self.suppress_alerts(&tenant, &monitor_id, &monitor_name, job)
+                    .await;
+                continue;
+            }
+
+            let late_alert_sent = job.late_alert_sent;
+            self.alert_late(
+                &monitor_id,
+                &monitor_name,
+                job,
+                &failures,
+                &required_alert_configs,
+            )
+            .await?;
+            // Late alerts are the only record of a Job having become late, so we publish that it
+            // has once it's first alerted on.
+            if job.late_alert_sent && !late_alert_sent {
+                self.publish(ActivityEvent::new(
+                    &tenant,
+                    monitor_id,
+                    &monitor_name,
+                    Some(job.job_id),
+                    Activity::JobLate,
+                ))
+                .await;
+            }
+            self.alert_errored(
+                &monitor_id,
+                &monitor_name,
+                job,
+                &failures,
+                &required_alert_configs,
+            )
+            .await?;
+        }
+
+        // Recoveries are only alerted on by the active alert configurations for this monitor
+        // that have opted in to them.
+        let recovery_alert_configs: Vec<&AlertConfig> = required_alert_configs
+            .iter()
+            .filter(|alert_config| alert_config.active && alert_config.on_recovery)
+            .copied()
+            .collect();
+        for job in monitor.jobs_pending_recovery_alerts() {
+            self.alert_recovered(&monitor_id, &monitor_name, job, &recovery_alert_configs)
                 .await?;
-            self.alert_errored(&monitor_id, &monitor_name, job, &required_alert_configs)
+        }
+
+        // Jobs that started while another was still in progress are alerted on once, by the same
+        // alert configurations that alert on late jobs.
+        let overlap_alert_configs: Vec<&AlertConfig> = required_alert_configs
+            .iter()
+            .filter(|alert_config| alert_config.active && alert_config.on_late)
+            .copied()
+            .collect();
+        for job in monitor.jobs_pending_overlap_alerts() {
+            if in_maintenance || job.during_maintenance {
+                job.during_maintenance = true;
+                job.overlap_alert_sent = true;
+                self.publish_suppressed(
+                    &tenant,
+                    &monitor_id,
+                    &monitor_name,
+                    &job.job_id,
+                    AlertKind::OverlappingJob,
+                )
+                .await;
+                continue;
+            }
+
+            self.alert_overlapping(&monitor_id, &monitor_name, job, &overlap_alert_configs)
                 .await?;
         }
 
+        // Jobs that succeeded are checked once for having taken an anomalous amount of time, and
+        // are likewise only alerted on by the alert configurations that have opted in to it.
+        let anomaly_alert_configs: Vec<&AlertConfig> = required_alert_configs
+            .iter()
+            .filter(|alert_config| alert_config.active && alert_config.on_anomaly)
+            .copied()
+            .collect();
+        for (job, anomaly) in monitor.jobs_pending_anomaly_checks() {
+            match anomaly {
+                Some(_) if in_maintenance || job.during_maintenance => {
+                    job.during_maintenance = true;
+                    self.publish_suppressed(
+                        &tenant,
+                        &monitor_id,
+                        &monitor_name,
+                        &job.job_id,
+                        AlertKind::AnomalousJob,
+                    )
+                    .await;
+                }
+                Some(anomaly) => {
+                    self.alert_anomalous(
+                        &monitor_id,
+                        &monitor_name,
+                        job,
+                        &anomaly,
+                        &anomaly_alert_configs,
+                    )
+                    .await?;
+                }
+                None => {}
+            }
+            job.anomaly_checked = true;
+        }
+
         Ok(())
     }
 
@@ -125,14 +259,23 @@ impl<
         monitor_id: &Uuid,
         monitor_name: &str,
         job: &mut Job,
+        failures: &FailureCount,
         alert_configs: &[&AlertConfig],
     ) -> Result<(), Error> {
         if !alert_configs.is_empty() && !job.late_alert_sent && job.late() {
             for alert_config in alert_configs {
                 let mut notifier = self.notifier_factory.get_notifier(alert_config);
                 notifier
-                    .notify_late_job(monitor_id, monitor_name, job)
+                    .notify_late_job(monitor_id, monitor_name, job, failures)
                     .await?;
+                self.publish(ActivityEvent::alert_sent(
+                    alert_config,
+                    AlertKind::LateJob,
+                    *monitor_id,
+                    monitor_name,
+                    Some(job.job_id),
+                ))
+                .await;
             }
 
             job.late_alert_sent = true;
@@ -146,14 +289,23 @@ impl<
         monitor_id: &Uuid,
         monitor_name: &str,
         job: &mut Job,
+        failures: &FailureCount,
         alert_configs: &[&AlertConfig],
     ) -> Result<(), Error> {
         if !alert_configs.is_empty() && !job.error_alert_sent && job.errored() {
             for alert_config in alert_configs {
                 let mut notifier = self.notifier_factory.get_notifier(alert_config);
                 notifier
-                    .notify_errored_job(monitor_id, monitor_name, job)
+                    .notify_errored_job(monitor_id, monitor_name, job, failures)
                     .await?;
+                self.publish(ActivityEvent::alert_sent(
+                    alert_config,
+                    AlertKind::ErroredJob,
+                    *monitor_id,
+                    monitor_name,
+                    Some(job.job_id),
+                ))
+                .await;
             }
 
             job.error_alert_sent = true;
@@ -161,6 +313,228 @@ impl<
 
         Ok(())
     }
+
+    pub async fn alert_recovered(
+        &self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &mut Job,
+        alert_configs: &[&AlertConfig],
+    ) -> Result<(), Error> {
+        for alert_config in alert_configs {
+            let mut notifier = self.notifier_factory.get_notifier(alert_config);
+            notifier
+                .notify_recovered(monitor_id, monitor_name, job)
+                .await?;
+            self.publish(ActivityEvent::alert_sent(
+                alert_config,
+                AlertKind::Recovered,
+                *monitor_id,
+                monitor_name,
+                Some(job.job_id),
+            ))
+            .await;
+        }
+
+        // Unlike late and errored jobs, we mark the recovery as alerted on even when there's
+        // nothing to alert, since otherwise the monitor would be picked up again on every check.
+        job.recovery_alert_sent = true;
+
+        Ok(())
+    }
+
+    pub async fn alert_anomalous(
+        &self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &Job,
+        anomaly: &DurationAnomaly,
+        alert_configs: &[&AlertConfig],
+    ) -> Result<(), Error> {
+        for alert_config in alert_configs {
+            let mut notifier = self.notifier_factory.get_notifier(alert_config);
+            notifier
+                .notify_anomalous_job(monitor_id, monitor_name, job, anomaly)
+                .await?;
+            self.publish(ActivityEvent::alert_sent(
+                alert_config,
+                AlertKind::AnomalousJob,
+                *monitor_id,
+                monitor_name,
+                Some(job.job_id),
+            ))
+            .await;
+        }
+
+        Ok(())
+    }
+
+    pub async fn alert_overlapping(
+        &self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &mut Job,
+        alert_configs: &[&AlertConfig],
+    ) -> Result<(), Error> {
+        for alert_config in alert_configs {
+            let mut notifier = self.notifier_factory.get_notifier(alert_config);
+            notifier
+                .notify_overlapping_job(monitor_id, monitor_name, job)
+                .await?;
+            self.publish(ActivityEvent::alert_sent(
+                alert_config,
+                AlertKind::OverlappingJob,
+                *monitor_id,
+                monitor_name,
+                Some(job.job_id),
+            ))
+            .await;
+        }
+
+        // As with recoveries, the overlap is marked as alerted on even when there's nothing to
+        // alert, so that the monitor isn't picked up again on every check.
+        job.overlap_alert_sent = true;
+
+        Ok(())
+    }
+
+    pub async fn resolve_late(
+        &self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &mut Job,
+        alert_configs: &[&AlertConfig],
+    ) -> Result<(), Error> {
+        for alert_config in alert_configs {
+            let mut notifier = self.notifier_factory.get_notifier(alert_config);
+            notifier
+                .resolve_late_job(monitor_id, monitor_name, job)
+                .await?;
+        }
+
+        job.late_alert_resolved = true;
+
+        Ok(())
+    }
+
+    pub async fn resolve_errored(
+        &self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &mut Job,
+        alert_configs: &[&AlertConfig],
+    ) -> Result<(), Error> {
+        for alert_config in alert_configs {
+            let mut notifier = self.notifier_factory.get_notifier(alert_config);
+            notifier
+                .resolve_errored_job(monitor_id, monitor_name, job)
+                .await?;
+        }
+
+        job.error_alert_resolved = true;
+
+        Ok(())
+    }
+
+    async fn suppress_alerts(
+        &self,
+        tenant: &str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &mut Job,
+    ) {
+        // Suppressed alerts are marked as sent and resolved so that they're never sent later on,
+        // and the job is flagged as having run during maintenance so that it isn't recovered
+        // from.
+        job.during_maintenance = true;
+        let mut suppressed = Vec::new();
+        if !job.late_alert_sent && job.late() {
+            job.late_alert_sent = true;
+            job.late_alert_resolved = true;
+            suppressed.push(AlertKind::LateJob);
+            self.publish(ActivityEvent::new(
+                tenant,
+                *monitor_id,
+                monitor_name,
+                Some(job.job_id),
+                Activity::JobLate,
+            ))
+            .await;
+        }
+        if !job.error_alert_sent && job.errored() {
+            job.error_alert_sent = true;
+            job.error_alert_resolved = true;
+            suppressed.push(AlertKind::ErroredJob);
+        }
+
+        for alert in suppressed {
+            self.publish_suppressed(tenant, monitor_id, monitor_name, &job.job_id, alert)
+                .await;
+        }
+    }
+
+    async fn skip_alerts_below_threshold(
+        &self,
+        tenant: &str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &mut Job,
+    ) {
+        // As with suppressed alerts, these are marked as sent and resolved so that they're never
+        // sent later on, and the job is flagged so that it isn't recovered from.
+        job.below_threshold = true;
+        if !job.late_alert_sent && job.late() {
+            job.late_alert_sent = true;
+            job.late_alert_resolved = true;
+            self.publish(ActivityEvent::new(
+                tenant,
+                *monitor_id,
+                monitor_name,
+                Some(job.job_id),
+                Activity::JobLate,
+            ))
+            .await;
+        }
+        if !job.error_alert_sent && job.errored() {
+            job.error_alert_sent = true;
+            job.error_alert_resolved = true;
+        }
+    }
+
+    async fn publish_suppressed(
+        &self,
+        tenant: &str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job_id: &Uuid,
+        alert: AlertKind,
+    ) {
+        info!(
+            monitor_id = ?monitor_id,
+            job_id = ?job_id,
+            "Suppressed {:?} alert for Monitor '{}' during maintenance",
+            alert,
+            monitor_name
+        );
+        self.publish(ActivityEvent::new(
+            tenant,
+            *monitor_id,
+            monitor_name,
+            Some(*job_id),
+            Activity::AlertSuppressed { alert },
+        ))
+        .await;
+    }
+
+    async fn publish(&self, event: ActivityEvent) {
+        // By now the alert has been sent, so failing to publish it shouldn't fail the check.
+        if let Err(error) = self.publisher.publish(&event).await {
+            error!(
+                monitor_id = ?event.monitor_id,
+                "Error publishing activity: {:?}", error
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -173,8 +547,12 @@ mod tests {
 
     use test_utils::{gen_relative_datetime, gen_uuid, logging::get_tracing_logs};
 
-    use crate::domain::models::{AlertType, AppliedMonitor, EndState, Job, SlackAlertConfig};
+    use crate::domain::models::{
+        AlertThreshold, AlertType, AnomalyDetection, AppliedMonitor, ConcurrencyPolicy, EndState,
+        Job, JobMetadata, Labels, MaintenanceWindow, MonitorKind, SlackAlertConfig,
+    };
     use crate::domain::services::get_notifier::MockGetNotifier;
+    use crate::infrastructure::activity::MockPublishActivity;
     use crate::infrastructure::notify::MockNotifier;
     use crate::infrastructure::notify::Notifier;
     use crate::infrastructure::repositories::alert_config::MockGetByMonitors;
@@ -200,6 +578,13 @@ mod tests {
         }
     }
 
+    /// A publisher that accepts any activity, for tests that aren't concerned with it.
+    fn any_publisher() -> MockPublishActivity {
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher.expect_publish().returning(|_| Ok(()));
+        mock_publisher
+    }
+
     #[fixture]
     fn monitors() -> Vec<Monitor> {
         vec![
@@ -209,6 +594,18 @@ mod tests {
                 name: "background-task.sh".to_owned(),
                 expected_duration: 300,
                 grace_duration: 100,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![
                     Job {
                         job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
@@ -217,6 +614,14 @@ mod tests {
                         end_state: None,
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                     Job {
                         job_id: gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0"),
@@ -226,9 +631,18 @@ mod tests {
                             end_time: gen_relative_datetime(-550),
                             succeeded: true,
                             output: None,
+                            metadata: JobMetadata::default(),
                         }),
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                     Job {
                         job_id: gen_uuid("051c2f13-20ae-456c-922b-b5799689d4ff"),
@@ -237,6 +651,14 @@ mod tests {
                         end_state: None,
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                 ],
             },
@@ -246,6 +668,18 @@ mod tests {
                 name: "get-pending-orders | generate invoices".to_owned(),
                 expected_duration: 21_600,
                 grace_duration: 1_800,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![
                     Job {
                         job_id: gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04"),
@@ -255,9 +689,18 @@ mod tests {
                             end_time: gen_relative_datetime(0),
                             succeeded: false,
                             output: Some("Failed to connect to database".to_owned()),
+                            metadata: JobMetadata::default(),
                         }),
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                     Job {
                         job_id: gen_uuid("9d90c314-5120-400e-bf03-e6363689f985"),
@@ -266,6 +709,14 @@ mod tests {
                         end_state: None,
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                 ],
             },
@@ -281,6 +732,9 @@ mod tests {
             active: true,
             on_late: true,
             on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
             monitors: vec![
                 AppliedMonitor {
                     monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
@@ -377,12 +831,17 @@ mod tests {
                 mock_notifier
                     .expect_notify_late_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, failures| {
                         monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
                             && name == "background-task.sh"
                             && job.job_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
+                            && failures
+                                == &FailureCount {
+                                    consecutive: 2,
+                                    window: None,
+                                }
                     })
-                    .returning(|_, _, _| Ok(()));
+                    .returning(|_, _, _, _| Ok(()));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
         mock_get_notifier
@@ -394,12 +853,12 @@ mod tests {
                 mock_notifier
                     .expect_notify_late_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, _| {
                         monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
                             && name == "background-task.sh"
                             && job.job_id == gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0")
                     })
-                    .returning(|_, _, _| Ok(()));
+                    .returning(|_, _, _, _| Ok(()));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
         mock_get_notifier
@@ -411,12 +870,12 @@ mod tests {
                 mock_notifier
                     .expect_notify_errored_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, _| {
                         monitor_id == &gen_uuid("841bdefb-e45c-4361-a8cb-8d247f4a088b")
                             && name == "get-pending-orders | generate invoices"
                             && job.job_id == gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04")
                     })
-                    .returning(|_, _, _| Ok(()));
+                    .returning(|_, _, _, _| Ok(()));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
         mock_get_notifier
@@ -428,19 +887,61 @@ mod tests {
                 mock_notifier
                     .expect_notify_late_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, _| {
                         monitor_id == &gen_uuid("841bdefb-e45c-4361-a8cb-8d247f4a088b")
                             && name == "get-pending-orders | generate invoices"
                             && job.job_id == gen_uuid("9d90c314-5120-400e-bf03-e6363689f985")
                     })
-                    .returning(|_, _, _| Ok(()));
+                    .returning(|_, _, _, _| Ok(()));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
 
+        // Each late Job is published as having become late, and each alert as having been sent.
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .times(3)
+            .withf(|event: &ActivityEvent| {
+                event.activity == Activity::JobLate
+                    && [
+                        gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                        gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0"),
+                        gen_uuid("9d90c314-5120-400e-bf03-e6363689f985"),
+                    ]
+                    .contains(&event.job_id.unwrap())
+            })
+            .returning(|_| Ok(()));
+        mock_publisher
+            .expect_publish()
+            .times(3)
+            .withf(|event: &ActivityEvent| {
+                event.activity
+                    == Activity::AlertSent {
+                        alert: AlertKind::LateJob,
+                        alert_config_id: gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                        alert_config_name: "Slack Alert".to_owned(),
+                    }
+            })
+            .returning(|_| Ok(()));
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.job_id == Some(gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04"))
+                    && event.activity
+                        == Activity::AlertSent {
+                            alert: AlertKind::ErroredJob,
+                            alert_config_id: gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                            alert_config_name: "Slack Alert".to_owned(),
+                        }
+            })
+            .returning(|_| Ok(()));
+
         let mut service = AlertErroneousJobsService::new(
             mock_monitor_repo,
             mock_alert_config_repo,
             mock_get_notifier,
+            mock_publisher,
         );
 
         let result = service.send_pending_alerts().await;
@@ -539,12 +1040,12 @@ mod tests {
                 mock_notifier
                     .expect_notify_late_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, _| {
                         monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
                             && name == "background-task.sh"
                             && job.job_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
                     })
-                    .returning(|_, _, _| Ok(()));
+                    .returning(|_, _, _, _| Ok(()));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
         mock_get_notifier
@@ -556,12 +1057,12 @@ mod tests {
                 mock_notifier
                     .expect_notify_late_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, _| {
                         monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
                             && name == "background-task.sh"
                             && job.job_id == gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0")
                     })
-                    .returning(|_, _, _| Ok(()));
+                    .returning(|_, _, _, _| Ok(()));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
         mock_get_notifier
@@ -573,12 +1074,12 @@ mod tests {
                 mock_notifier
                     .expect_notify_errored_job()
                     .once()
-                    .withf(move |monitor_id, name, job| {
+                    .withf(move |monitor_id, name, job, _| {
                         monitor_id == &gen_uuid("841bdefb-e45c-4361-a8cb-8d247f4a088b")
                             && name == "get-pending-orders | generate invoices"
                             && job.job_id == gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04")
                     })
-                    .returning(|_, _, _| Err(Error::NotifyError("Failed to notify".to_owned())));
+                    .returning(|_, _, _, _| Err(Error::NotifyError("Failed to notify".to_owned())));
                 Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
             });
 
@@ -586,6 +1087,7 @@ mod tests {
             mock_monitor_repo,
             mock_alert_config_repo,
             mock_get_notifier,
+            any_publisher(),
         );
 
         let result = service.send_pending_alerts().await;
@@ -628,4 +1130,817 @@ mod tests {
             Ok(())
         });
     }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_resolves_alerts(alert_configs: Vec<AlertConfig>) {
+        let monitor = Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo-tenant".to_owned(),
+            name: "background-task.sh".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![
+                // A successful job, following on from a job that errored.
+                Job {
+                    job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                    start_time: gen_relative_datetime(-500),
+                    max_end_time: gen_relative_datetime(-100),
+                    end_state: Some(EndState {
+                        end_time: gen_relative_datetime(-200),
+                        succeeded: true,
+                        output: None,
+                        metadata: JobMetadata::default(),
+                    }),
+                    late_alert_sent: false,
+                    error_alert_sent: false,
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                },
+                // A job that was alerted on for being late and for erroring, and has since
+                // finished and been followed by a successful job.
+                Job {
+                    job_id: gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0"),
+                    start_time: gen_relative_datetime(-1000),
+                    max_end_time: gen_relative_datetime(-600),
+                    end_state: Some(EndState {
+                        end_time: gen_relative_datetime(-550),
+                        succeeded: false,
+                        output: None,
+                        metadata: JobMetadata::default(),
+                    }),
+                    late_alert_sent: true,
+                    error_alert_sent: true,
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                },
+            ],
+        };
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(move || Ok(vec![monitor.clone()]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                let job = &monitor.jobs[1];
+                job.late_alert_resolved && job.error_alert_resolved
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut sequence = Sequence::new();
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .in_sequence(&mut sequence)
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_resolve_late_job()
+                    .once()
+                    .withf(move |monitor_id, name, job| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && job.job_id == gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0")
+                    })
+                    .returning(|_, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .in_sequence(&mut sequence)
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_resolve_errored_job()
+                    .once()
+                    .withf(move |monitor_id, name, job| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && job.job_id == gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0")
+                    })
+                    .returning(|_, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            any_publisher(),
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_for_recovery(mut alert_configs: Vec<AlertConfig>) {
+        let monitor = Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo-tenant".to_owned(),
+            name: "background-task.sh".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![
+                // A successful job, following on from a job that errored.
+                Job {
+                    job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                    start_time: gen_relative_datetime(-500),
+                    max_end_time: gen_relative_datetime(-100),
+                    end_state: Some(EndState {
+                        end_time: gen_relative_datetime(-200),
+                        succeeded: true,
+                        output: None,
+                        metadata: JobMetadata::default(),
+                    }),
+                    late_alert_sent: false,
+                    error_alert_sent: false,
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                },
+                Job {
+                    job_id: gen_uuid("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0"),
+                    start_time: gen_relative_datetime(-1000),
+                    max_end_time: gen_relative_datetime(-600),
+                    end_state: Some(EndState {
+                        end_time: gen_relative_datetime(-700),
+                        succeeded: false,
+                        output: None,
+                        metadata: JobMetadata::default(),
+                    }),
+                    late_alert_sent: false,
+                    error_alert_sent: true,
+                    late_alert_resolved: false,
+                    error_alert_resolved: true,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                },
+            ],
+        };
+
+        // Only alert configurations that have opted in should be alerted on recovery.
+        alert_configs.push(AlertConfig {
+            alert_config_id: gen_uuid("0bd3c8d4-2ff4-4b5c-9d6a-64b1a0e4e2b1"),
+            name: "Slack Recovery Alert".to_owned(),
+            on_recovery: true,
+            on_anomaly: false,
+            label_selector: None,
+            ..alert_configs[0].clone()
+        });
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(move || Ok(vec![monitor.clone()]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| monitor.jobs[0].recovery_alert_sent)
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .withf(|alert_config| alert_config.name == "Slack Recovery Alert")
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_notify_recovered()
+                    .once()
+                    .withf(move |monitor_id, name, job| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && job.job_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
+                    })
+                    .returning(|_, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            any_publisher(),
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    fn anomalous_monitor(paused: bool) -> Monitor {
+        let succeeded_job = |job_id: &str, start: i64, duration: i64| Job {
+            job_id: gen_uuid(job_id),
+            start_time: gen_relative_datetime(start),
+            max_end_time: gen_relative_datetime(start + 400),
+            end_state: Some(EndState {
+                end_time: gen_relative_datetime(start + duration),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo-tenant".to_owned(),
+            name: "background-task.sh".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: Some(AnomalyDetection::new(Some(120), None).unwrap()),
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![
+                // A job that succeeded far sooner than expected, following one that didn't.
+                succeeded_job("01a92c6c-6803-409d-b675-022fff62575a", -1000, 5),
+                succeeded_job("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0", -2000, 290),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_for_anomalies(mut alert_configs: Vec<AlertConfig>) {
+        // Only alert configurations that have opted in should be alerted on anomalies.
+        alert_configs.push(AlertConfig {
+            alert_config_id: gen_uuid("0bd3c8d4-2ff4-4b5c-9d6a-64b1a0e4e2b1"),
+            name: "Slack Anomaly Alert".to_owned(),
+            on_anomaly: true,
+            ..alert_configs[0].clone()
+        });
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(|| Ok(vec![anomalous_monitor(false)]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| monitor.jobs.iter().all(|job| job.anomaly_checked))
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .withf(|alert_config| alert_config.name == "Slack Anomaly Alert")
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_notify_anomalous_job()
+                    .once()
+                    .withf(move |monitor_id, name, job, anomaly| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && job.job_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
+                            && anomaly
+                                == &DurationAnomaly {
+                                    duration: 5,
+                                    min_duration: 120,
+                                    max_duration: None,
+                                }
+                    })
+                    .returning(|_, _, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.job_id == Some(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"))
+                    && matches!(
+                        event.activity,
+                        Activity::AlertSent {
+                            alert: AlertKind::AnomalousJob,
+                            ..
+                        }
+                    )
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_for_anomalies_during_maintenance(
+        mut alert_configs: Vec<AlertConfig>,
+    ) {
+        alert_configs[0].on_anomaly = true;
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(|| Ok(vec![anomalous_monitor(true)]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                monitor.jobs[0].during_maintenance
+                    && monitor.jobs.iter().all(|job| job.anomaly_checked)
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier.expect_get_notifier().never();
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.activity
+                    == Activity::AlertSuppressed {
+                        alert: AlertKind::AnomalousJob,
+                    }
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    fn overlapping_monitor(paused: bool) -> Monitor {
+        let running_job = |job_id: &str, start: i64, overlapped: bool| Job {
+            job_id: gen_uuid(job_id),
+            start_time: gen_relative_datetime(start),
+            max_end_time: gen_relative_datetime(start + 400),
+            end_state: None,
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped,
+            overlap_alert_sent: false,
+        };
+        Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo-tenant".to_owned(),
+            name: "background-task.sh".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Alert,
+            jobs: vec![
+                // A job that started while the one before it was still running.
+                running_job("01a92c6c-6803-409d-b675-022fff62575a", -100, true),
+                running_job("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0", -200, false),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_for_overlaps(mut alert_configs: Vec<AlertConfig>) {
+        // Only alert configurations that alert on late jobs should be alerted on overlaps.
+        alert_configs.push(AlertConfig {
+            alert_config_id: gen_uuid("0bd3c8d4-2ff4-4b5c-9d6a-64b1a0e4e2b1"),
+            name: "Slack Error Alert".to_owned(),
+            on_late: false,
+            ..alert_configs[0].clone()
+        });
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(|| Ok(vec![overlapping_monitor(false)]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                monitor.jobs[0].overlap_alert_sent && !monitor.jobs[1].overlap_alert_sent
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .withf(|alert_config| alert_config.name == "Slack Alert")
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_notify_overlapping_job()
+                    .once()
+                    .withf(move |monitor_id, name, job| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && job.job_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
+                    })
+                    .returning(|_, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.job_id == Some(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"))
+                    && matches!(
+                        event.activity,
+                        Activity::AlertSent {
+                            alert: AlertKind::OverlappingJob,
+                            ..
+                        }
+                    )
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_for_overlaps_during_maintenance(
+        alert_configs: Vec<AlertConfig>,
+    ) {
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(|| Ok(vec![overlapping_monitor(true)]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                monitor.jobs[0].during_maintenance && monitor.jobs[0].overlap_alert_sent
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier.expect_get_notifier().never();
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.activity
+                    == Activity::AlertSuppressed {
+                        alert: AlertKind::OverlappingJob,
+                    }
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_after_threshold(
+        alert_configs: Vec<AlertConfig>,
+        // Whether the older failure has already been found to be below the threshold, in an
+        // earlier check.
+        #[values(false, true)] below_threshold: bool,
+    ) {
+        let errored_job = |job_id: &str, start: i64| Job {
+            job_id: gen_uuid(job_id),
+            start_time: gen_relative_datetime(start),
+            max_end_time: gen_relative_datetime(start + 400),
+            end_state: Some(EndState {
+                end_time: gen_relative_datetime(start + 300),
+                succeeded: false,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        let monitor = Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo-tenant".to_owned(),
+            name: "background-task.sh".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::new(2, None).unwrap(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![
+                // Only the second failure in a row reaches the threshold, but the first still
+                // counts towards it.
+                errored_job("01a92c6c-6803-409d-b675-022fff62575a", -1000),
+                Job {
+                    error_alert_sent: below_threshold,
+                    error_alert_resolved: below_threshold,
+                    below_threshold,
+                    ..errored_job("3b9f5a89-ebc2-49bf-a9dd-61f52f7a3fa0", -2000)
+                },
+            ],
+        };
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(move || Ok(vec![monitor.clone()]));
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                // The first failure is never alerted on, and isn't evaluated again.
+                monitor.jobs[0].error_alert_sent
+                    && !monitor.jobs[0].below_threshold
+                    && monitor.jobs[1].below_threshold
+                    && monitor.jobs[1].error_alert_sent
+                    && monitor.jobs[1].error_alert_resolved
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_notify_errored_job()
+                    .once()
+                    .withf(move |_, _, job, failures| {
+                        job.job_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
+                            && failures
+                                == &FailureCount {
+                                    consecutive: 2,
+                                    window: None,
+                                }
+                    })
+                    .returning(|_, _, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            any_publisher(),
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test(start_paused = true)]
+    async fn test_send_pending_alerts_during_maintenance(
+        mut monitors: Vec<Monitor>,
+        alert_configs: Vec<AlertConfig>,
+    ) {
+        // One Monitor is paused, and the other is within a maintenance window that's always in
+        // effect.
+        monitors[0].paused = true;
+        monitors[1].maintenance_windows =
+            vec![MaintenanceWindow::new("* * * * *", None, 5).unwrap()];
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_with_erroneous_jobs()
+            .once()
+            .returning(move || Ok(monitors.clone()));
+        mock_monitor_repo
+            .expect_save()
+            .times(2)
+            .withf(|monitor| {
+                monitor
+                    .jobs
+                    .iter()
+                    .filter(|job| job.late() || job.errored())
+                    .all(|job| {
+                        job.during_maintenance
+                            && job.late_alert_sent == job.late()
+                            && job.error_alert_sent == job.errored()
+                    })
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        // Nothing is sent, even though the alert configuration applies to both Monitors.
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier.expect_get_notifier().never();
+
+        // Each late Job is still published as having become late, and each alert as having been
+        // suppressed.
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .times(3)
+            .withf(|event: &ActivityEvent| event.activity == Activity::JobLate)
+            .returning(|_| Ok(()));
+        mock_publisher
+            .expect_publish()
+            .times(3)
+            .withf(|event: &ActivityEvent| {
+                event.activity
+                    == Activity::AlertSuppressed {
+                        alert: AlertKind::LateJob,
+                    }
+            })
+            .returning(|_| Ok(()));
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.job_id == Some(gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04"))
+                    && event.activity
+                        == Activity::AlertSuppressed {
+                            alert: AlertKind::ErroredJob,
+                        }
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertErroneousJobsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
 }
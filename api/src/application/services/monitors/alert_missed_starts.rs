@@ -0,0 +1,781 @@
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::domain::models::{Activity, ActivityEvent, AlertConfig, AlertKind, Monitor};
+use crate::domain::services::get_notifier::GetNotifier;
+use crate::errors::Error;
+use crate::infrastructure::activity::PublishActivity;
+use crate::infrastructure::repositories::{
+    alert_config::GetByMonitors, monitor::GetScheduled, Repository,
+};
+
+/// A service that alerts on scheduled Monitors that have missed a start, i.e. a job that should
+/// have been started according to the Monitor's schedule, but never was. Heartbeat Monitors that
+/// haven't received a heartbeat when one was expected are alerted on in the same way.
+pub struct AlertMissedStartsService<
+    MonitorRepo: GetScheduled + Repository<Monitor>,
+    AlertConfigRepo: GetByMonitors,
+    NotifierFactory: GetNotifier,
+    Publisher: PublishActivity,
+> {
+    monitor_repo: MonitorRepo,
+    alert_config_repo: AlertConfigRepo,
+    notifier_factory: NotifierFactory,
+    publisher: Publisher,
+}
+
+impl<
+        MonitorRepo: GetScheduled + Repository<Monitor>,
+        AlertConfigRepo: GetByMonitors,
+        NotifierFactory: GetNotifier,
+        Publisher: PublishActivity,
+    > AlertMissedStartsService<MonitorRepo, AlertConfigRepo, NotifierFactory, Publisher>
+{
+    pub fn new(
+        monitor_repo: MonitorRepo,
+        alert_config_repo: AlertConfigRepo,
+        notifier_factory: NotifierFactory,
+        publisher: Publisher,
+    ) -> Self {
+        Self {
+            monitor_repo,
+            alert_config_repo,
+            notifier_factory,
+            publisher,
+        }
+    }
+
+    pub async fn send_pending_alerts(&mut self) -> Result<(), Error> {
+        info!("Beginning check for missed starts...");
+        let mut monitors_with_missed_starts: Vec<Monitor> = self
+            .monitor_repo
+            .get_scheduled()
+            .await?
+            .into_iter()
+            .filter(|monitor| monitor.missed_start().is_some())
+            .collect();
+        info!(
+            "Found {} Monitors with missed starts",
+            monitors_with_missed_starts.len()
+        );
+        if monitors_with_missed_starts.is_empty() {
+            info!("Check for missed starts complete");
+            return Ok(());
+        }
+
+        let alert_configs = self
+            .alert_config_repo
+            .get_by_monitors(
+                &monitors_with_missed_starts
+                    .iter()
+                    .map(|mon| mon.monitor_id)
+                    .collect::<Vec<Uuid>>(),
+                None,
+            )
+            .await?;
+
+        let mut failed_monitors = Vec::new();
+        for monitor in monitors_with_missed_starts.as_mut_slice() {
+            if let Err(error) = self.notify_missed_start(monitor, &alert_configs).await {
+                // If we fail to notify then we just want to log the error and continue to the next
+                // monitor.
+                error!(
+                    monitor_id = ?monitor.monitor_id,
+                    "Error notifying missed start: {:?}", error
+                );
+                failed_monitors.push(monitor.monitor_id.to_string());
+                continue;
+            }
+
+            if let Err(error) = self.monitor_repo.save(monitor).await {
+                error!(
+                    monitor_id = ?monitor.monitor_id,
+                    "Error saving Monitor: {:?}", error
+                );
+                failed_monitors.push(monitor.monitor_id.to_string());
+            }
+        }
+
+        let result = if failed_monitors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::MissedStartAlertFailure(format!(
+                "Failed to process missed starts for Monitors: {:?}",
+                failed_monitors
+            )))
+        };
+        info!("Check for missed starts complete");
+
+        result
+    }
+
+    async fn notify_missed_start(
+        &self,
+        monitor: &mut Monitor,
+        alert_configs: &[AlertConfig],
+    ) -> Result<(), Error> {
+        // We've already filtered out Monitors without a missed start.
+        let scheduled_start = monitor.missed_start().unwrap();
+        if monitor.is_heartbeat() {
+            info!(
+                monitor_id = ?monitor.monitor_id,
+                "Monitor '{}' missed a heartbeat expected at {}",
+                &monitor.name,
+                scheduled_start
+            );
+        } else {
+            info!(
+                monitor_id = ?monitor.monitor_id,
+                "Monitor '{}' missed a start scheduled for {}",
+                &monitor.name,
+                scheduled_start
+            );
+        }
+
+        // Missed starts during maintenance are still marked as alerted on, so that they aren't
+        // alerted on once the maintenance is over.
+        if monitor.in_maintenance() {
+            let alert = if monitor.is_heartbeat() {
+                AlertKind::MissedHeartbeat
+            } else {
+                AlertKind::MissedStart
+            };
+            info!(
+                monitor_id = ?monitor.monitor_id,
+                "Suppressed {:?} alert for Monitor '{}' during maintenance",
+                alert,
+                &monitor.name
+            );
+            self.publish(ActivityEvent::new(
+                &monitor.tenant,
+                monitor.monitor_id,
+                &monitor.name,
+                None,
+                Activity::AlertSuppressed { alert },
+            ))
+            .await;
+            monitor.missed_start_alerted(scheduled_start);
+            return Ok(());
+        }
+
+        // Missed starts are effectively late jobs, so they're alerted on by the active alert
+        // configurations for this monitor that alert on late jobs.
+        for alert_config in alert_configs.iter().filter(|alert_config| {
+            alert_config.active && alert_config.on_late && alert_config.applies_to_monitor(monitor)
+        }) {
+            let mut notifier = self.notifier_factory.get_notifier(alert_config);
+            let alert = if monitor.is_heartbeat() {
+                notifier
+                    .notify_missed_heartbeat(&monitor.monitor_id, &monitor.name, &scheduled_start)
+                    .await?;
+                AlertKind::MissedHeartbeat
+            } else {
+                notifier
+                    .notify_missed_start(&monitor.monitor_id, &monitor.name, &scheduled_start)
+                    .await?;
+                AlertKind::MissedStart
+            };
+
+            self.publish(ActivityEvent::alert_sent(
+                alert_config,
+                alert,
+                monitor.monitor_id,
+                &monitor.name,
+                None,
+            ))
+            .await;
+        }
+
+        monitor.missed_start_alerted(scheduled_start);
+
+        Ok(())
+    }
+
+    async fn publish(&self, event: ActivityEvent) {
+        // By now the alert has been sent or suppressed, so failing to publish it shouldn't fail
+        // the check.
+        if let Err(error) = self.publisher.publish(&event).await {
+            error!(
+                monitor_id = ?event.monitor_id,
+                "Error publishing activity: {:?}", error
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::{Duration, NaiveDateTime, Utc};
+    use mockall::{mock, predicate::*};
+    use rstest::{fixture, rstest};
+    use tracing::Level;
+    use tracing_test::traced_test;
+
+    use test_utils::{gen_relative_datetime, gen_uuid, logging::get_tracing_logs};
+
+    use crate::domain::models::{
+        AlertThreshold, AlertType, AppliedMonitor, ConcurrencyPolicy, Job, Labels,
+        MaintenanceWindow, MonitorKind, Schedule, SlackAlertConfig,
+    };
+    use crate::domain::services::get_notifier::MockGetNotifier;
+    use crate::infrastructure::activity::MockPublishActivity;
+    use crate::infrastructure::notify::MockNotifier;
+    use crate::infrastructure::notify::Notifier;
+    use crate::infrastructure::repositories::alert_config::MockGetByMonitors;
+
+    use super::*;
+
+    mock! {
+        pub MonitorRepo {}
+
+        #[async_trait]
+        impl GetScheduled for MonitorRepo {
+            async fn get_scheduled(&mut self) -> Result<Vec<Monitor>, Error>;
+        }
+
+        #[async_trait]
+        impl Repository<Monitor> for MonitorRepo {
+            async fn get(
+                &mut self, monitor_id: uuid::Uuid, tenant: &str
+            ) -> Result<Option<Monitor>, Error>;
+            async fn all(&mut self, tenant: &str) -> Result<Vec<Monitor>, Error>;
+            async fn delete(&mut self, monitor: &Monitor) -> Result<(), Error>;
+            async fn save(&mut self, monitor: &Monitor) -> Result<(), Error>;
+        }
+    }
+
+    fn last_scheduled_start() -> NaiveDateTime {
+        // The monitors below run every minute with a 60 second grace period.
+        let schedule = Schedule::new("* * * * *", None).unwrap();
+        schedule
+            .previous_run(Utc::now().naive_utc() - Duration::seconds(60))
+            .unwrap()
+    }
+
+    #[fixture]
+    fn monitors() -> Vec<Monitor> {
+        vec![
+            // Missed start.
+            Monitor {
+                monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                tenant: "foo-tenant".to_owned(),
+                name: "background-task.sh".to_owned(),
+                expected_duration: 30,
+                grace_duration: 60,
+                kind: MonitorKind::Job,
+                schedule: Some(Schedule::new("* * * * *", None).unwrap()),
+                missed_start_cutoff: Some(gen_relative_datetime(-3_600)),
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
+                jobs: vec![],
+            },
+            // Started on time.
+            Monitor {
+                monitor_id: gen_uuid("841bdefb-e45c-4361-a8cb-8d247f4a088b"),
+                tenant: "bar-tenant".to_owned(),
+                name: "get-pending-orders | generate invoices".to_owned(),
+                expected_duration: 30,
+                grace_duration: 60,
+                kind: MonitorKind::Job,
+                schedule: Some(Schedule::new("* * * * *", None).unwrap()),
+                missed_start_cutoff: Some(gen_relative_datetime(-3_600)),
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
+                jobs: vec![Job {
+                    job_id: gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04"),
+                    start_time: gen_relative_datetime(0),
+                    max_end_time: gen_relative_datetime(90),
+                    end_state: None,
+                    late_alert_sent: false,
+                    error_alert_sent: false,
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                }],
+            },
+        ]
+    }
+
+    #[fixture]
+    fn alert_configs() -> Vec<AlertConfig> {
+        vec![
+            AlertConfig {
+                alert_config_id: gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                tenant: "foo-tenant".to_owned(),
+                name: "Slack Alert".to_owned(),
+                active: true,
+                on_late: true,
+                on_error: false,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
+                monitors: vec![AppliedMonitor {
+                    monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                    name: "background-task.sh".to_owned(),
+                }],
+                type_: AlertType::Slack(SlackAlertConfig {
+                    channel: "foo-channel".to_owned(),
+                    token: "foo-token".to_owned(),
+                }),
+            },
+            AlertConfig {
+                alert_config_id: gen_uuid("5b5b5b5b-5b5b-4b5b-8b5b-5b5b5b5b5b5b"),
+                tenant: "foo-tenant".to_owned(),
+                name: "Inactive Slack Alert".to_owned(),
+                active: false,
+                on_late: true,
+                on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
+                monitors: vec![AppliedMonitor {
+                    monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                    name: "background-task.sh".to_owned(),
+                }],
+                type_: AlertType::Slack(SlackAlertConfig {
+                    channel: "foo-channel".to_owned(),
+                    token: "foo-token".to_owned(),
+                }),
+            },
+        ]
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_send_pending_alerts_service(
+        monitors: Vec<Monitor>,
+        alert_configs: Vec<AlertConfig>,
+    ) {
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_scheduled()
+            .once()
+            .returning(move || Ok(monitors.clone()));
+
+        // Make sure the missed start has been recorded as alerted on.
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                monitor.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && monitor.missed_start_cutoff == Some(last_scheduled_start())
+                    && monitor.missed_start().is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .withf(|ids, tenant| {
+                ids == [gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")] && tenant.is_none()
+            })
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        // Only the active alert configuration should be used.
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .withf(|alert_config| {
+                alert_config.alert_config_id == gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b")
+            })
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_notify_missed_start()
+                    .once()
+                    .withf(move |monitor_id, name, scheduled_start| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && scheduled_start == &last_scheduled_start()
+                    })
+                    .returning(|_, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.tenant == "foo-tenant"
+                    && event.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.job_id.is_none()
+                    && event.activity
+                        == Activity::AlertSent {
+                            alert: AlertKind::MissedStart,
+                            alert_config_id: gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                            alert_config_name: "Slack Alert".to_owned(),
+                        }
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertMissedStartsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+
+        logs_assert(|logs| {
+            let logs = get_tracing_logs(logs);
+
+            assert_eq!(
+                logs.iter().map(|log| log.level).collect::<Vec<Level>>(),
+                vec![Level::INFO, Level::INFO, Level::INFO, Level::INFO]
+            );
+            assert_eq!(
+                logs.iter()
+                    .map(|log| log.body.clone())
+                    .collect::<Vec<String>>(),
+                vec![
+                    "Beginning check for missed starts...".to_owned(),
+                    "Found 1 Monitors with missed starts".to_owned(),
+                    format!(
+                        "Monitor 'background-task.sh' missed a start scheduled for {} \
+                        monitor_id=41ebffb4-a188-48e9-8ec1-61380085cde3",
+                        last_scheduled_start()
+                    ),
+                    "Check for missed starts complete".to_owned(),
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_send_pending_alerts_service_for_heartbeats(alert_configs: Vec<AlertConfig>) {
+        // The last heartbeat was 10 minutes ago, but they're expected every 5 minutes.
+        let last_heartbeat = gen_relative_datetime(-600);
+        let expected_heartbeat = last_heartbeat + Duration::seconds(300);
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_scheduled()
+            .once()
+            .returning(move || {
+                Ok(vec![Monitor {
+                    monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                    tenant: "foo-tenant".to_owned(),
+                    name: "background-task.sh".to_owned(),
+                    expected_duration: 30,
+                    grace_duration: 60,
+                    kind: MonitorKind::Heartbeat { period: 300 },
+                    schedule: None,
+                    missed_start_cutoff: Some(gen_relative_datetime(-3_600)),
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
+                    jobs: vec![Job {
+                        job_id: gen_uuid("7baa4872-4e55-410a-9b3d-1f4b5bef1f04"),
+                        start_time: last_heartbeat,
+                        max_end_time: last_heartbeat + Duration::seconds(90),
+                        end_state: None,
+                        late_alert_sent: false,
+                        error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
+                    }],
+                }])
+            });
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(move |monitor| {
+                monitor.missed_start_cutoff == Some(expected_heartbeat)
+                    && monitor.missed_start().is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .returning(move |_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier.expect_notify_missed_start().never();
+                mock_notifier
+                    .expect_notify_missed_heartbeat()
+                    .once()
+                    .withf(move |monitor_id, name, expected| {
+                        monitor_id == &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                            && name == "background-task.sh"
+                            && expected == &expected_heartbeat
+                    })
+                    .returning(|_, _, _| Ok(()));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.tenant == "foo-tenant"
+                    && event.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.job_id.is_none()
+                    && event.activity
+                        == Activity::AlertSent {
+                            alert: AlertKind::MissedHeartbeat,
+                            alert_config_id: gen_uuid("f1b1b1b1-1b1b-4b1b-8b1b-1b1b1b1b1b1b"),
+                            alert_config_name: "Slack Alert".to_owned(),
+                        }
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertMissedStartsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+
+        logs_assert(|logs| {
+            let logs = get_tracing_logs(logs);
+
+            assert_eq!(
+                logs[2].body,
+                format!(
+                    "Monitor 'background-task.sh' missed a heartbeat expected at {} \
+                    monitor_id=41ebffb4-a188-48e9-8ec1-61380085cde3",
+                    expected_heartbeat
+                )
+            );
+
+            Ok(())
+        });
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_send_pending_alerts_service_with_no_missed_starts(monitors: Vec<Monitor>) {
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_scheduled()
+            .once()
+            .returning(move || Ok(monitors[1..].to_vec()));
+        mock_monitor_repo.expect_save().never();
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo.expect_get_by_monitors().never();
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier.expect_get_notifier().never();
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher.expect_publish().never();
+
+        let mut service = AlertMissedStartsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+
+        logs_assert(|logs| {
+            let logs = get_tracing_logs(logs);
+
+            assert_eq!(
+                logs.iter()
+                    .map(|log| log.body.clone())
+                    .collect::<Vec<String>>(),
+                vec![
+                    "Beginning check for missed starts...",
+                    "Found 0 Monitors with missed starts",
+                    "Check for missed starts complete",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_send_pending_alerts_service_with_failure(
+        monitors: Vec<Monitor>,
+        alert_configs: Vec<AlertConfig>,
+    ) {
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_scheduled()
+            .once()
+            .returning(move || Ok(monitors.clone()));
+
+        // We don't save the monitor as we fail to notify.
+        mock_monitor_repo.expect_save().never();
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier
+            .expect_get_notifier()
+            .once()
+            .returning(|_| {
+                let mut mock_notifier = MockNotifier::new();
+                mock_notifier
+                    .expect_notify_missed_start()
+                    .once()
+                    .returning(|_, _, _| Err(Error::NotifyError("Failed to notify".to_owned())));
+                Box::new(mock_notifier) as Box<dyn Notifier + Sync + Send>
+            });
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher.expect_publish().never();
+
+        let mut service = AlertMissedStartsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert_eq!(
+            result,
+            Err(Error::MissedStartAlertFailure(
+                "Failed to process missed starts for Monitors: \
+                [\"41ebffb4-a188-48e9-8ec1-61380085cde3\"]"
+                    .to_owned()
+            ))
+        );
+
+        logs_assert(|logs| {
+            let logs = get_tracing_logs(logs);
+
+            assert_eq!(
+                logs.iter().map(|log| log.level).collect::<Vec<Level>>(),
+                vec![
+                    Level::INFO,
+                    Level::INFO,
+                    Level::INFO,
+                    Level::ERROR,
+                    Level::INFO
+                ]
+            );
+            assert_eq!(
+                logs[3].body,
+                "Error notifying missed start: NotifyError(\"Failed to notify\") \
+                monitor_id=41ebffb4-a188-48e9-8ec1-61380085cde3"
+            );
+
+            Ok(())
+        });
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_send_pending_alerts_during_maintenance(
+        mut monitors: Vec<Monitor>,
+        alert_configs: Vec<AlertConfig>,
+    ) {
+        monitors[0].maintenance_windows =
+            vec![MaintenanceWindow::new("* * * * *", None, 5).unwrap()];
+
+        let mut mock_monitor_repo = MockMonitorRepo::new();
+        mock_monitor_repo
+            .expect_get_scheduled()
+            .once()
+            .returning(move || Ok(monitors.clone()));
+
+        // The missed start is still recorded as alerted on, so it isn't alerted on later.
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor| {
+                monitor.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && monitor.missed_start_cutoff == Some(last_scheduled_start())
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_alert_config_repo = MockGetByMonitors::new();
+        mock_alert_config_repo
+            .expect_get_by_monitors()
+            .once()
+            .returning(move |_, _| Ok(alert_configs.clone()));
+
+        let mut mock_get_notifier = MockGetNotifier::new();
+        mock_get_notifier.expect_get_notifier().never();
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.activity
+                        == Activity::AlertSuppressed {
+                            alert: AlertKind::MissedStart,
+                        }
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = AlertMissedStartsService::new(
+            mock_monitor_repo,
+            mock_alert_config_repo,
+            mock_get_notifier,
+            mock_publisher,
+        );
+
+        let result = service.send_pending_alerts().await;
+        assert!(result.is_ok());
+    }
+}
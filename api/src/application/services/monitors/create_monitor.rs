@@ -1,16 +1,30 @@
 use tracing::info;
 
-use crate::domain::models::Monitor;
+use crate::domain::models::{Actor, AuditEvent, LifecycleEvent, Monitor, MonitorSettings};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
 use crate::infrastructure::repositories::Repository;
 
-pub struct CreateMonitorService<T: Repository<Monitor>> {
-    repo: T,
+pub struct CreateMonitorService<
+    MonitorRepo: Repository<Monitor>,
+    AuditLogRepo: AuditLog,
+    Queue: DeliveryQueue,
+> {
+    repo: MonitorRepo,
+    audit_log: AuditLogRepo,
+    delivery_queue: Queue,
 }
 
-impl<T: Repository<Monitor>> CreateMonitorService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<MonitorRepo: Repository<Monitor>, AuditLogRepo: AuditLog, Queue: DeliveryQueue>
+    CreateMonitorService<MonitorRepo, AuditLogRepo, Queue>
+{
+    pub fn new(repo: MonitorRepo, audit_log: AuditLogRepo, delivery_queue: Queue) -> Self {
+        Self {
+            repo,
+            audit_log,
+            delivery_queue,
+        }
     }
 
     pub async fn create_by_attributes(
@@ -19,14 +33,23 @@ impl<T: Repository<Monitor>> CreateMonitorService<T> {
         name: &String,
         expected_duration: i32,
         grace_duration: i32,
+        settings: MonitorSettings,
+        actor: &Actor,
     ) -> Result<Monitor, Error> {
         let mon = Monitor::new(
             tenant.to_string(),
             name.clone(),
             expected_duration,
             grace_duration,
-        );
+            settings,
+        )?;
         self.repo.save(&mon).await?;
+        self.audit_log
+            .record(&AuditEvent::created(actor, &mon))
+            .await?;
+        self.delivery_queue
+            .enqueue(&LifecycleEvent::monitor_created(&mon))
+            .await?;
 
         info!(
             monitor_id = mon.monitor_id.to_string(),
@@ -46,6 +69,11 @@ mod tests {
 
     use test_utils::logging::TracingLog;
 
+    use crate::domain::models::{
+        AlertThreshold, AuditAction, EntityType, EventType, MonitorKind, Schedule,
+    };
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
@@ -61,12 +89,55 @@ mod tests {
                     && mon.name == "foo"
                     && mon.expected_duration == 3_600
                     && mon.grace_duration == 300
+                    && mon.schedule == Some(Schedule::new("0 * * * *", None).unwrap())
+                    && mon.kind == MonitorKind::Heartbeat { period: 3_600 }
+                    && mon.alert_threshold == AlertThreshold::new(3, None).unwrap()
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.tenant == "tenant"
+                    && event.actor.id == "1234567890"
+                    && event.action == AuditAction::Created
+                    && event.entity.type_ == EntityType::Monitor
+                    && event.before.is_none()
+                    && event.after.as_ref().unwrap()["name"] == "foo"
+            })
+            .returning(|_| Ok(()));
+
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(|event: &LifecycleEvent| {
+                event.tenant == "tenant"
+                    && event.event_type == EventType::MonitorCreated
+                    && event.data["monitor"]["name"] == "foo"
             })
             .returning(|_| Ok(()));
 
-        let mut service = CreateMonitorService::new(mock);
+        let mut service = CreateMonitorService::new(mock, audit_log, delivery_queue);
         let new_monitor_result = service
-            .create_by_attributes("tenant", &"foo".to_owned(), 3_600, 300)
+            .create_by_attributes(
+                "tenant",
+                &"foo".to_owned(),
+                3_600,
+                300,
+                MonitorSettings {
+                    schedule: Some(Schedule::new("0 * * * *", None).unwrap()),
+                    kind: MonitorKind::Heartbeat { period: 3_600 },
+                    alert_threshold: AlertThreshold::new(3, None).unwrap(),
+                    ..Default::default()
+                },
+                &Actor {
+                    id: "1234567890".to_owned(),
+                    name: "John Doe".to_owned(),
+                },
+            )
             .await;
 
         assert!(new_monitor_result.is_ok());
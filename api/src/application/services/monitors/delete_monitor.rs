@@ -1,23 +1,48 @@
 use tracing::info;
 use uuid::Uuid;
 
-use crate::domain::models::Monitor;
+use crate::domain::models::{Actor, AuditEvent, LifecycleEvent, Monitor};
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
 use crate::infrastructure::repositories::Repository;
 
-pub struct DeleteMonitorService<T: Repository<Monitor>> {
-    repo: T,
+pub struct DeleteMonitorService<
+    MonitorRepo: Repository<Monitor>,
+    AuditLogRepo: AuditLog,
+    Queue: DeliveryQueue,
+> {
+    repo: MonitorRepo,
+    audit_log: AuditLogRepo,
+    delivery_queue: Queue,
 }
 
-impl<T: Repository<Monitor>> DeleteMonitorService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<MonitorRepo: Repository<Monitor>, AuditLogRepo: AuditLog, Queue: DeliveryQueue>
+    DeleteMonitorService<MonitorRepo, AuditLogRepo, Queue>
+{
+    pub fn new(repo: MonitorRepo, audit_log: AuditLogRepo, delivery_queue: Queue) -> Self {
+        Self {
+            repo,
+            audit_log,
+            delivery_queue,
+        }
     }
 
-    pub async fn delete_by_id(&mut self, monitor_id: Uuid, tenant: &str) -> Result<(), Error> {
+    pub async fn delete_by_id(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+        actor: &Actor,
+    ) -> Result<(), Error> {
         let monitor = self.repo.get(monitor_id, tenant).await?;
         if let Some(mon) = monitor {
             self.repo.delete(&mon).await?;
+            self.audit_log
+                .record(&AuditEvent::deleted(actor, &mon))
+                .await?;
+            self.delivery_queue
+                .enqueue(&LifecycleEvent::monitor_deleted(&mon))
+                .await?;
             info!(
                 monitor_id = monitor_id.to_string(),
                 "Deleted Monitor('{}')", &mon.name
@@ -37,10 +62,22 @@ mod tests {
     use test_utils::gen_uuid;
     use test_utils::logging::TracingLog;
 
+    use crate::domain::models::{
+        AlertThreshold, AuditAction, ConcurrencyPolicy, EventType, Labels, MonitorKind,
+    };
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_delete_monitor_service() {
@@ -57,6 +94,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
@@ -68,14 +117,53 @@ mod tests {
                 name: "foo".to_owned(),
                 expected_duration: 300,
                 grace_duration: 100,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![],
             }))
             .returning(|_| Ok(()));
 
-        let mut service = DeleteMonitorService::new(mock);
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Deleted
+                    && event.entity.id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.before.as_ref().unwrap()["name"] == "foo"
+                    && event.after.is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(|event: &LifecycleEvent| {
+                event.event_type == EventType::MonitorDeleted
+                    && event.data["monitor"]["monitor_id"] == "41ebffb4-a188-48e9-8ec1-61380085cde3"
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = DeleteMonitorService::new(mock, audit_log, delivery_queue);
 
         let delete_result = service
-            .delete_by_id(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"), "tenant")
+            .delete_by_id(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "tenant",
+                &actor(),
+            )
             .await;
         assert_eq!(delete_result, Ok(()));
 
@@ -99,10 +187,14 @@ mod tests {
             .once()
             .with(eq(monitor_id), eq("tenant"))
             .returning(|_, _| Ok(None));
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue.expect_enqueue().never();
 
-        let mut service = DeleteMonitorService::new(mock);
+        let mut service = DeleteMonitorService::new(mock, audit_log, delivery_queue);
 
-        let delete_result = service.delete_by_id(monitor_id, "tenant").await;
+        let delete_result = service.delete_by_id(monitor_id, "tenant", &actor()).await;
 
         assert_eq!(delete_result, Err(Error::MonitorNotFound(monitor_id)));
     }
@@ -0,0 +1,133 @@
+use uuid::Uuid;
+
+use crate::domain::models::{DurationStats, Monitor};
+use crate::domain::services::duration_stats::calculate_duration_stats;
+use crate::errors::Error;
+use crate::infrastructure::repositories::Repository;
+
+pub struct FetchDurationStatsService<T: Repository<Monitor>> {
+    repo: T,
+}
+
+impl<T: Repository<Monitor>> FetchDurationStatsService<T> {
+    pub fn new(repo: T) -> Self {
+        Self { repo }
+    }
+
+    /// Calculate statistics describing how long a Monitor's Jobs have taken, or `None` if none of
+    /// them have finished successfully.
+    pub async fn fetch_by_id(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+    ) -> Result<Option<DurationStats>, Error> {
+        match self.repo.get(monitor_id, tenant).await? {
+            Some(monitor) => Ok(calculate_duration_stats(&monitor.jobs)),
+            None => Err(Error::MonitorNotFound(monitor_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use mockall::predicate::*;
+    use pretty_assertions::assert_eq;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EndState, Job, JobMetadata, Labels, MonitorKind,
+    };
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn gen_job(hours_ago: i64, duration: i64) -> Job {
+        let start_time = gen_datetime("2024-05-01T12:00:00") - Duration::hours(hours_ago);
+        Job {
+            job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+            start_time,
+            max_end_time: start_time + Duration::seconds(400),
+            end_state: Some(EndState {
+                end_time: start_time + Duration::seconds(duration),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_duration_stats_service() {
+        let monitor_id = gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f");
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(eq(monitor_id), eq("tenant"))
+            .returning(|_, _| {
+                Ok(Some(Monitor {
+                    monitor_id: gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f"),
+                    tenant: "tenant".to_owned(),
+                    name: "foo".to_owned(),
+                    expected_duration: 300,
+                    grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
+                    jobs: vec![gen_job(1, 240), gen_job(2, 360), gen_job(3, 300)],
+                }))
+            });
+
+        let mut service = FetchDurationStatsService::new(mock);
+
+        let stats = service.fetch_by_id(monitor_id, "tenant").await.unwrap();
+
+        assert_eq!(
+            stats,
+            Some(DurationStats {
+                job_count: 3,
+                p50: 300,
+                p95: 360,
+                p99: 360,
+                max: 360,
+                std_dev: 2400_f64.sqrt(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_duration_stats_when_monitor_doesnt_exist() {
+        let monitor_id = gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f");
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(eq(monitor_id), eq("tenant"))
+            .returning(|_, _| Ok(None));
+
+        let mut service = FetchDurationStatsService::new(mock);
+
+        let stats = service.fetch_by_id(monitor_id, "tenant").await;
+
+        assert_eq!(stats, Err(Error::MonitorNotFound(monitor_id)));
+    }
+}
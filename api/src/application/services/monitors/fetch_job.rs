@@ -41,7 +41,9 @@ mod tests {
 
     use test_utils::{gen_datetime, gen_uuid};
 
-    use crate::domain::models::EndState;
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EndState, JobMetadata, Labels, MonitorKind,
+    };
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
@@ -60,6 +62,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![Job {
                         job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                         start_time: gen_datetime("2024-04-22T22:43:00"),
@@ -68,9 +82,18 @@ mod tests {
                             end_time: gen_datetime("2024-04-22T22:49:00"),
                             succeeded: true,
                             output: None,
+                            metadata: JobMetadata::default(),
                         }),
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     }],
                 }))
             });
@@ -95,9 +118,18 @@ mod tests {
                     end_time: gen_datetime("2024-04-22T22:49:00"),
                     succeeded: true,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             })
         );
     }
@@ -138,6 +170,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
@@ -0,0 +1,109 @@
+use uuid::Uuid;
+
+use crate::domain::models::{JobPage, JobQuery};
+use crate::errors::Error;
+use crate::infrastructure::repositories::monitor::GetJobHistory;
+
+pub struct FetchJobHistoryService<T: GetJobHistory> {
+    repo: T,
+}
+
+impl<T: GetJobHistory> FetchJobHistoryService<T> {
+    pub fn new(repo: T) -> Self {
+        Self { repo }
+    }
+
+    pub async fn fetch_page(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+        query: &JobQuery,
+    ) -> Result<JobPage, Error> {
+        self.repo
+            .get_job_history(monitor_id, tenant, query)
+            .await?
+            .ok_or(Error::MonitorNotFound(monitor_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use pretty_assertions::assert_eq;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{EndState, Job, JobMetadata, JobOrder, JobStatus};
+    use crate::infrastructure::repositories::monitor::MockGetJobHistory;
+
+    use super::*;
+
+    fn query() -> JobQuery {
+        JobQuery {
+            status: Some(JobStatus::Succeeded),
+            order: JobOrder::OldestFirst,
+            limit: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_job_history_service() {
+        let monitor_id = gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f");
+        let job = Job {
+            job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+            start_time: gen_datetime("2024-04-22T22:43:00"),
+            max_end_time: gen_datetime("2024-04-22T22:53:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-04-22T22:49:00"),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+
+        let mut mock = MockGetJobHistory::new();
+        let page = JobPage {
+            jobs: vec![job],
+            total: 2,
+            next_cursor: Some(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")),
+        };
+        let returned_page = page.clone();
+        mock.expect_get_job_history()
+            .once()
+            .with(eq(monitor_id), eq("tenant"), eq(query()))
+            .returning(move |_, _, _| Ok(Some(returned_page.clone())));
+
+        let mut service = FetchJobHistoryService::new(mock);
+
+        let result = service.fetch_page(monitor_id, "tenant", &query()).await;
+
+        assert_eq!(result, Ok(page));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_job_history_when_monitor_doesnt_exist() {
+        let monitor_id = gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f");
+        let mut mock = MockGetJobHistory::new();
+        mock.expect_get_job_history()
+            .once()
+            .with(eq(monitor_id), eq("tenant"), eq(query()))
+            .returning(|_, _, _| Ok(None));
+
+        let mut service = FetchJobHistoryService::new(mock);
+
+        let result = service.fetch_page(monitor_id, "tenant", &query()).await;
+
+        assert_eq!(result, Err(Error::MonitorNotFound(monitor_id)));
+    }
+}
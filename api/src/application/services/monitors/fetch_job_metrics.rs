@@ -0,0 +1,152 @@
+use uuid::Uuid;
+
+use crate::domain::models::{JobMetrics, Monitor};
+use crate::domain::services::job_metrics::collect_job_metrics;
+use crate::errors::Error;
+use crate::infrastructure::repositories::Repository;
+
+pub struct FetchJobMetricsService<T: Repository<Monitor>> {
+    repo: T,
+}
+
+impl<T: Repository<Monitor>> FetchJobMetricsService<T> {
+    pub fn new(repo: T) -> Self {
+        Self { repo }
+    }
+
+    /// Collect the numeric metrics recorded by a Monitor's most recent Jobs, as a series for each
+    /// metric.
+    pub async fn fetch_by_id(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+    ) -> Result<JobMetrics, Error> {
+        match self.repo.get(monitor_id, tenant).await? {
+            Some(monitor) => Ok(collect_job_metrics(&monitor.jobs)),
+            None => Err(Error::MonitorNotFound(monitor_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Duration;
+    use mockall::predicate::*;
+    use pretty_assertions::assert_eq;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EndState, Job, JobMetadata, Labels, MetricPoint,
+        MonitorKind,
+    };
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn gen_job(job_id: &str, hours_ago: i64, rows: f64) -> Job {
+        let start_time = gen_datetime("2024-05-01T12:00:00") - Duration::hours(hours_ago);
+        Job {
+            job_id: gen_uuid(job_id),
+            start_time,
+            max_end_time: start_time + Duration::seconds(400),
+            end_state: Some(EndState {
+                end_time: start_time + Duration::seconds(300),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata {
+                    metrics: BTreeMap::from([("rows".to_owned(), rows)]),
+                    ..Default::default()
+                },
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_job_metrics_service() {
+        let monitor_id = gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f");
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(eq(monitor_id), eq("tenant"))
+            .returning(|_, _| {
+                Ok(Some(Monitor {
+                    monitor_id: gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f"),
+                    tenant: "tenant".to_owned(),
+                    name: "foo".to_owned(),
+                    expected_duration: 300,
+                    grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
+                    jobs: vec![
+                        gen_job("01a92c6c-6803-409d-b675-022fff62575a", 1, 250.0),
+                        gen_job("8106bab7-d643-4ede-bd92-60c79f787344", 2, 200.0),
+                    ],
+                }))
+            });
+
+        let mut service = FetchJobMetricsService::new(mock);
+
+        let job_metrics = service.fetch_by_id(monitor_id, "tenant").await.unwrap();
+
+        assert_eq!(
+            job_metrics,
+            JobMetrics {
+                job_count: 2,
+                metrics: BTreeMap::from([(
+                    "rows".to_owned(),
+                    vec![
+                        MetricPoint {
+                            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+                            end_time: gen_datetime("2024-05-01T10:05:00"),
+                            value: 200.0,
+                        },
+                        MetricPoint {
+                            job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                            end_time: gen_datetime("2024-05-01T11:05:00"),
+                            value: 250.0,
+                        },
+                    ]
+                )]),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_job_metrics_when_monitor_doesnt_exist() {
+        let monitor_id = gen_uuid("71d1c46c-ef86-4fcb-b8b4-b2fee56a4d2f");
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(eq(monitor_id), eq("tenant"))
+            .returning(|_, _| Ok(None));
+
+        let mut service = FetchJobMetricsService::new(mock);
+
+        let job_metrics = service.fetch_by_id(monitor_id, "tenant").await;
+
+        assert_eq!(job_metrics, Err(Error::MonitorNotFound(monitor_id)));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::domain::models::MonitorMetrics;
+use crate::errors::Error;
+use crate::infrastructure::repositories::monitor::GetMetrics;
+
+pub struct FetchMetricsService<T: GetMetrics> {
+    repo: T,
+}
+
+impl<T: GetMetrics> FetchMetricsService<T> {
+    pub fn new(repo: T) -> Self {
+        Self { repo }
+    }
+
+    pub async fn fetch_metrics(&mut self) -> Result<Vec<MonitorMetrics>, Error> {
+        self.repo.get_metrics().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::infrastructure::repositories::monitor::MockGetMetrics;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fetch_metrics_service() {
+        let metrics = vec![MonitorMetrics {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo".to_owned(),
+            name: "db-backup.py".to_owned(),
+            last_started: Some(gen_datetime("2024-05-01T00:20:00")),
+            last_finished: Some(gen_datetime("2024-05-01T00:15:00")),
+            last_duration: Some(300),
+            jobs_started: 10,
+            jobs_succeeded: 7,
+            jobs_failed: 2,
+            jobs_late: 1,
+            jobs_in_progress: 1,
+        }];
+        let returned_metrics = metrics.clone();
+
+        let mut mock = MockGetMetrics::new();
+        mock.expect_get_metrics()
+            .once()
+            .returning(move || Ok(returned_metrics.clone()));
+
+        let mut service = FetchMetricsService::new(mock);
+
+        let result = service.fetch_metrics().await;
+
+        assert_eq!(result, Ok(metrics));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_metrics_service_with_repository_error() {
+        let mut mock = MockGetMetrics::new();
+        mock.expect_get_metrics()
+            .once()
+            .returning(|| Err(Error::RepositoryError("test error".to_owned())));
+
+        let mut service = FetchMetricsService::new(mock);
+
+        let result = service.fetch_metrics().await;
+
+        assert_eq!(result, Err(Error::RepositoryError("test error".to_owned())));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::models::Monitor;
+use crate::domain::models::{LabelSelector, Monitor};
 use crate::errors::Error;
 use crate::infrastructure::repositories::Repository;
 
@@ -15,9 +15,19 @@ impl<'a, T: Repository<Monitor>, F: Fn(&mut [Monitor])> FetchMonitorsService<'a,
         }
     }
 
-    pub async fn fetch_all(&mut self, tenant: &str) -> Result<Vec<Monitor>, Error> {
+    /// Fetch all of the tenant's Monitors, or only those whose labels match a label selector if
+    /// one is given.
+    pub async fn fetch_all(
+        &mut self,
+        tenant: &str,
+        label_selector: Option<&LabelSelector>,
+    ) -> Result<Vec<Monitor>, Error> {
         let mut monitors = self.repo.all(tenant).await?;
 
+        if let Some(selector) = label_selector {
+            monitors.retain(|monitor| selector.matches(&monitor.labels));
+        }
+
         (self.order_monitors)(&mut monitors);
 
         Ok(monitors)
@@ -26,10 +36,13 @@ impl<'a, T: Repository<Monitor>, F: Fn(&mut [Monitor])> FetchMonitorsService<'a,
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use mockall::predicate::*;
 
     use test_utils::gen_uuid;
 
+    use crate::domain::models::{AlertThreshold, ConcurrencyPolicy, Labels, MonitorKind};
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
@@ -38,43 +51,49 @@ mod tests {
         monitors.sort_by(|lh_mon: &Monitor, rh_mon: &Monitor| lh_mon.name.cmp(&rh_mon.name));
     }
 
-    #[tokio::test]
-    async fn test_fetch_job_service() {
+    fn monitor(monitor_id: &str, name: &str, team: &str) -> Monitor {
+        Monitor {
+            monitor_id: gen_uuid(monitor_id),
+            tenant: "tenant".to_owned(),
+            name: name.to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::new(BTreeMap::from([("team".to_owned(), team.to_owned())])).unwrap(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        }
+    }
+
+    fn mock_repo() -> MockRepository<Monitor> {
         let mut mock = MockRepository::new();
         mock.expect_all()
             .once()
             .with(eq("tenant"))
             .returning(move |_| {
                 Ok(vec![
-                    Monitor {
-                        monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
-                        tenant: "tenant".to_owned(),
-                        name: "foo".to_owned(),
-                        expected_duration: 300,
-                        grace_duration: 100,
-                        jobs: vec![],
-                    },
-                    Monitor {
-                        monitor_id: gen_uuid("91bf0865-b1b2-447b-93e1-fe047d2bb218"),
-                        tenant: "tenant".to_owned(),
-                        name: "bar".to_owned(),
-                        expected_duration: 300,
-                        grace_duration: 100,
-                        jobs: vec![],
-                    },
-                    Monitor {
-                        monitor_id: gen_uuid("72ab99e7-d179-4d24-b9a3-cb1a65064a4d"),
-                        tenant: "tenant".to_owned(),
-                        name: "baz".to_owned(),
-                        expected_duration: 300,
-                        grace_duration: 100,
-                        jobs: vec![],
-                    },
+                    monitor("41ebffb4-a188-48e9-8ec1-61380085cde3", "foo", "payments"),
+                    monitor("91bf0865-b1b2-447b-93e1-fe047d2bb218", "bar", "search"),
+                    monitor("72ab99e7-d179-4d24-b9a3-cb1a65064a4d", "baz", "payments"),
                 ])
             });
-        let mut service = FetchMonitorsService::new(mock, &order_monitors);
+        mock
+    }
 
-        let monitors = service.fetch_all("tenant").await.unwrap();
+    #[tokio::test]
+    async fn test_fetch_job_service() {
+        let mut service = FetchMonitorsService::new(mock_repo(), &order_monitors);
+
+        let monitors = service.fetch_all("tenant", None).await.unwrap();
 
         let names = monitors
             .iter()
@@ -82,4 +101,18 @@ mod tests {
             .collect::<Vec<String>>();
         assert_eq!(names, vec!["bar", "baz", "foo"]);
     }
+
+    #[tokio::test]
+    async fn test_fetch_monitors_by_label_selector() {
+        let mut service = FetchMonitorsService::new(mock_repo(), &order_monitors);
+
+        let selector: LabelSelector = "team=payments".parse().unwrap();
+        let monitors = service.fetch_all("tenant", Some(&selector)).await.unwrap();
+
+        let names = monitors
+            .iter()
+            .map(|monitor| monitor.name.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["baz", "foo"]);
+    }
 }
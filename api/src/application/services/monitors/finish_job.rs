@@ -1,26 +1,45 @@
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::domain::models::{ApiKey, Job, Monitor};
+use crate::domain::models::{
+    Activity, ActivityEvent, ApiKey, ApiKeyScope, Job, JobMetadata, LifecycleEvent, Monitor,
+};
 use crate::errors::Error;
+use crate::infrastructure::activity::PublishActivity;
 use crate::infrastructure::repositories::api_key::GetByKey;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
 use crate::infrastructure::repositories::Repository;
 
 pub struct FinishJobService<
     MonitorRepo: Repository<Monitor>,
     ApiKeyRepo: Repository<ApiKey> + GetByKey,
+    Publisher: PublishActivity,
+    Queue: DeliveryQueue,
 > {
     monitor_repo: MonitorRepo,
     api_key_repo: ApiKeyRepo,
+    publisher: Publisher,
+    delivery_queue: Queue,
 }
 
-impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey>
-    FinishJobService<MonitorRepo, ApiKeyRepo>
+impl<
+        MonitorRepo: Repository<Monitor>,
+        ApiKeyRepo: Repository<ApiKey> + GetByKey,
+        Publisher: PublishActivity,
+        Queue: DeliveryQueue,
+    > FinishJobService<MonitorRepo, ApiKeyRepo, Publisher, Queue>
 {
-    pub fn new(monitor_repo: MonitorRepo, api_key_repo: ApiKeyRepo) -> Self {
+    pub fn new(
+        monitor_repo: MonitorRepo,
+        api_key_repo: ApiKeyRepo,
+        publisher: Publisher,
+        delivery_queue: Queue,
+    ) -> Self {
         Self {
             monitor_repo,
             api_key_repo,
+            publisher,
+            delivery_queue,
         }
     }
 
@@ -31,8 +50,10 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
         job_id: Uuid,
         succeeded: bool,
         output: &Option<String>,
+        metadata: &JobMetadata,
     ) -> Result<Job, Error> {
         let mut key = self.validate_key(api_key).await?;
+        key.check_access(ApiKeyScope::JobsWrite, monitor_id)?;
 
         let monitor_opt = self.monitor_repo.get(monitor_id, &key.tenant).await?;
 
@@ -44,13 +65,14 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
                 self.record_monitor_usage(&mut key, &monitor).await?;
 
                 let finished_job = self
-                    .finish_job(&mut monitor, job_id, succeeded, output)
+                    .finish_job(&mut monitor, job_id, succeeded, output, metadata)
                     .await?;
 
                 info!(
                     monitor_id = monitor_id.to_string(),
                     "Finished Job('{}')", job_id
                 );
+                self.publish_finished(&monitor, &finished_job).await;
                 Ok(finished_job)
             }
             None => Err(Error::MonitorNotFound(monitor_id)),
@@ -60,7 +82,10 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
     async fn validate_key(&mut self, key: &str) -> Result<ApiKey, Error> {
         let api_key = self.api_key_repo.get_by_key(&ApiKey::hash_key(key)).await?;
         match api_key {
-            Some(key) => Ok(key),
+            Some(key) => {
+                key.check_expiry()?;
+                Ok(key)
+            }
             None => Err(Error::Unauthorized("Invalid API key".to_owned())),
         }
     }
@@ -80,8 +105,9 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
         job_id: Uuid,
         succeeded: bool,
         output: &Option<String>,
+        metadata: &JobMetadata,
     ) -> Result<Job, Error> {
-        match monitor.finish_job(job_id, succeeded, output.clone()) {
+        match monitor.finish_job(job_id, succeeded, output.clone(), metadata.clone()) {
             Ok(job) => {
                 // Need to clone the Job here, since it's part of the Monitor which is declared as
                 // mutable above, meaning we can't borrow it immutably when saving it.
@@ -98,18 +124,53 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
             }
         }
     }
+
+    async fn publish_finished(&mut self, monitor: &Monitor, job: &Job) {
+        let event = ActivityEvent::new(
+            &monitor.tenant,
+            monitor.monitor_id,
+            &monitor.name,
+            Some(job.job_id),
+            Activity::JobFinished {
+                succeeded: job.succeeded(),
+            },
+        );
+
+        // The Job has already finished, so failing to publish it shouldn't fail the request.
+        if let Err(error) = self.publisher.publish(&event).await {
+            error!(
+                monitor_id = monitor.monitor_id.to_string(),
+                "Error publishing activity: {:?}", error
+            );
+        }
+        if let Err(error) = self
+            .delivery_queue
+            .enqueue(&LifecycleEvent::job_finished(monitor, job))
+            .await
+        {
+            error!(
+                monitor_id = monitor.monitor_id.to_string(),
+                "Error queueing webhook deliveries: {:?}", error
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use mockall::predicate::*;
+    use rstest::rstest;
     use tracing_test::traced_test;
 
     use test_utils::logging::TracingLog;
-    use test_utils::{gen_relative_datetime, gen_uuid};
+    use test_utils::{gen_datetime, gen_relative_datetime, gen_uuid};
 
-    use crate::domain::models::EndState;
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EndState, EventType, Labels, MonitorKind,
+    };
+    use crate::infrastructure::activity::MockPublishActivity;
     use crate::infrastructure::repositories::api_key::MockApiKeyRepo;
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
@@ -132,6 +193,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![Job {
                         job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                         start_time: gen_relative_datetime(-320),
@@ -139,6 +212,14 @@ mod tests {
                         end_state: None,
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     }],
                 }))
             });
@@ -150,10 +231,43 @@ mod tests {
                     && monitor.tenant == "tenant"
                     && !monitor.jobs[0].in_progress()
                     && monitor.jobs[0].duration() == Some(320)
+                    && monitor.jobs[0]
+                        .end_state
+                        .as_ref()
+                        .is_some_and(|end_state| end_state.metadata.exit_code == Some(0))
             })
             .returning(|_| Ok(()));
 
-        let mut service = FinishJobService::new(mock_monitor_repo, setup_mock_api_key_repo());
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.tenant == "tenant"
+                    && event.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.job_id == Some(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"))
+                    && event.activity == Activity::JobFinished { succeeded: true }
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_delivery_queue = MockDeliveryQueue::new();
+        mock_delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(|event: &LifecycleEvent| {
+                event.tenant == "tenant"
+                    && event.event_type == EventType::JobFinished
+                    && event.data["job"]["job_id"] == "01a92c6c-6803-409d-b675-022fff62575a"
+                    && event.data["job"]["succeeded"] == true
+            })
+            .returning(|_| Err(Error::RepositoryError("Connection refused".to_owned())));
+
+        let mut service = FinishJobService::new(
+            mock_monitor_repo,
+            setup_mock_api_key_repo(),
+            mock_publisher,
+            mock_delivery_queue,
+        );
         let job = service
             .finish_job_for_monitor(
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
@@ -161,22 +275,35 @@ mod tests {
                 gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 true,
                 &Some("Job complete".to_owned()),
+                &JobMetadata {
+                    exit_code: Some(0),
+                    ..Default::default()
+                },
             )
             .await
             .unwrap();
 
         assert!(!job.in_progress());
         assert_eq!(job.duration(), Some(320));
+        assert_eq!(job.end_state.unwrap().metadata.exit_code, Some(0));
 
+        // Failing to queue webhook deliveries doesn't fail the request, since the Job has finished.
         logs_assert(|logs| {
             let logs = TracingLog::from_logs(logs);
-            assert_eq!(logs.len(), 1);
+            assert_eq!(logs.len(), 2);
             assert_eq!(logs[0].level, tracing::Level::INFO);
             assert_eq!(
                 logs[0].body,
                 "Finished Job('01a92c6c-6803-409d-b675-022fff62575a') \
                 monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
             );
+            assert_eq!(logs[1].level, tracing::Level::ERROR);
+            assert_eq!(
+                logs[1].body,
+                "Error queueing webhook deliveries: \
+                RepositoryError(\"Connection refused\") \
+                monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
+            );
             Ok(())
         });
     }
@@ -193,7 +320,12 @@ mod tests {
             ))
             .returning(|_| Ok(None));
 
-        let mut service = FinishJobService::new(MockRepository::new(), mock_api_key_repo);
+        let mut service = FinishJobService::new(
+            MockRepository::new(),
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
         let result = service
             .finish_job_for_monitor(
                 gen_uuid("41ebffb4-A188-48E9-8ec1-61380085cde3"),
@@ -201,6 +333,7 @@ mod tests {
                 gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 true,
                 &Some("Job complete".to_owned()),
+                &JobMetadata::default(),
             )
             .await;
 
@@ -215,6 +348,61 @@ mod tests {
         });
     }
 
+    #[rstest]
+    #[case::missing_scope(
+        vec![ApiKeyScope::HeartbeatsWrite],
+        None,
+        "API key does not have the 'jobs:write' scope"
+    )]
+    #[case::other_monitor(
+        vec![ApiKeyScope::JobsWrite],
+        Some(vec![gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")]),
+        "API key cannot be used for Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3')"
+    )]
+    #[tokio::test]
+    async fn test_finish_job_forbidden(
+        #[case] scopes: Vec<ApiKeyScope>,
+        #[case] monitor_ids: Option<Vec<Uuid>>,
+        #[case] reason: &str,
+    ) {
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(move |_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    scopes.clone(),
+                    monitor_ids.clone(),
+                    None,
+                )))
+            });
+        mock_api_key_repo.expect_save().never();
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().never();
+
+        let mut service = FinishJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
+        let result = service
+            .finish_job_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+                gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                true,
+                &Some("Job complete".to_owned()),
+                &JobMetadata::default(),
+            )
+            .await;
+
+        assert_eq!(result, Err(Error::Forbidden(reason.to_owned())));
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_monitor_not_found() {
@@ -230,6 +418,9 @@ mod tests {
                     "Test key".to_owned(),
                     "foo-key".to_owned(),
                     "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    None,
                 )))
             });
         mock_api_key_repo.expect_save().never();
@@ -245,7 +436,12 @@ mod tests {
             .returning(|_, _| Ok(None));
         mock_monitor_repo.expect_save().never();
 
-        let mut service = FinishJobService::new(mock_monitor_repo, mock_api_key_repo);
+        let mut service = FinishJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
         let result = service
             .finish_job_for_monitor(
                 gen_uuid("41ebffb4-A188-48E9-8ec1-61380085cde3"),
@@ -253,6 +449,7 @@ mod tests {
                 gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 true,
                 &Some("Job complete".to_owned()),
+                &JobMetadata::default(),
             )
             .await;
 
@@ -287,12 +484,29 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
         mock_monitor_repo.expect_save().never();
 
-        let mut service = FinishJobService::new(mock_monitor_repo, setup_mock_api_key_repo());
+        let mut service = FinishJobService::new(
+            mock_monitor_repo,
+            setup_mock_api_key_repo(),
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
         let result = service
             .finish_job_for_monitor(
                 gen_uuid("41ebffb4-A188-48E9-8ec1-61380085cde3"),
@@ -300,6 +514,7 @@ mod tests {
                 gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 true,
                 &Some("Job complete".to_owned()),
+                &JobMetadata::default(),
             )
             .await;
 
@@ -344,6 +559,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![Job {
                         job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                         start_time: gen_relative_datetime(-320),
@@ -352,15 +579,29 @@ mod tests {
                             end_time: gen_relative_datetime(-100),
                             succeeded: true,
                             output: None,
+                            metadata: JobMetadata::default(),
                         }),
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     }],
                 }))
             });
         mock_monitor_repo.expect_save().never();
 
-        let mut service = FinishJobService::new(mock_monitor_repo, setup_mock_api_key_repo());
+        let mut service = FinishJobService::new(
+            mock_monitor_repo,
+            setup_mock_api_key_repo(),
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
         let job = service
             .finish_job_for_monitor(
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
@@ -368,6 +609,7 @@ mod tests {
                 gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 true,
                 &Some("Job complete".to_owned()),
+                &JobMetadata::default(),
             )
             .await;
 
@@ -405,6 +647,9 @@ mod tests {
                     "Test key".to_owned(),
                     "foo-key".to_owned(),
                     "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    None,
                 )))
             });
         mock_api_key_repo
@@ -421,4 +666,45 @@ mod tests {
 
         mock_api_key_repo
     }
+
+    #[tokio::test]
+    async fn test_finish_job_expired_key() {
+        let expires_at = gen_datetime("2024-12-01T00:00:00");
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(move |_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    Some(expires_at),
+                )))
+            });
+        mock_api_key_repo.expect_save().never();
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().never();
+
+        let mut service = FinishJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
+        let result = service
+            .finish_job_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+                gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                true,
+                &Some("Job complete".to_owned()),
+                &JobMetadata::default(),
+            )
+            .await;
+
+        assert_eq!(result, Err(Error::ApiKeyExpired(expires_at)));
+    }
 }
@@ -1,17 +1,33 @@
 pub mod alert_erroneous_jobs;
+pub mod alert_missed_starts;
 pub mod create_monitor;
 pub mod delete_monitor;
+pub mod fetch_duration_stats;
 pub mod fetch_job;
+pub mod fetch_job_history;
+pub mod fetch_job_metrics;
+pub mod fetch_metrics;
 pub mod fetch_monitors;
 pub mod finish_job;
+pub mod pause_monitor;
+pub mod prune_jobs;
+pub mod record_heartbeat;
 pub mod start_job;
 pub mod update_monitor;
 
 pub use alert_erroneous_jobs::AlertErroneousJobsService;
+pub use alert_missed_starts::AlertMissedStartsService;
 pub use create_monitor::CreateMonitorService;
 pub use delete_monitor::DeleteMonitorService;
+pub use fetch_duration_stats::FetchDurationStatsService;
 pub use fetch_job::FetchJobService;
+pub use fetch_job_history::FetchJobHistoryService;
+pub use fetch_job_metrics::FetchJobMetricsService;
+pub use fetch_metrics::FetchMetricsService;
 pub use fetch_monitors::FetchMonitorsService;
 pub use finish_job::FinishJobService;
+pub use pause_monitor::PauseMonitorService;
+pub use prune_jobs::PruneJobsService;
+pub use record_heartbeat::RecordHeartbeatService;
 pub use start_job::StartJobService;
 pub use update_monitor::UpdateMonitorService;
@@ -0,0 +1,327 @@
+use chrono::NaiveDateTime;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::models::{Actor, AuditEvent, LifecycleEvent, Monitor};
+use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
+use crate::infrastructure::repositories::Repository;
+
+pub struct PauseMonitorService<
+    MonitorRepo: Repository<Monitor>,
+    AuditLogRepo: AuditLog,
+    Queue: DeliveryQueue,
+> {
+    repo: MonitorRepo,
+    audit_log: AuditLogRepo,
+    delivery_queue: Queue,
+}
+
+impl<MonitorRepo: Repository<Monitor>, AuditLogRepo: AuditLog, Queue: DeliveryQueue>
+    PauseMonitorService<MonitorRepo, AuditLogRepo, Queue>
+{
+    pub fn new(repo: MonitorRepo, audit_log: AuditLogRepo, delivery_queue: Queue) -> Self {
+        Self {
+            repo,
+            audit_log,
+            delivery_queue,
+        }
+    }
+
+    /// Pause a Monitor, so that it doesn't alert until it's resumed, either explicitly or
+    /// automatically at `resume_at`.
+    pub async fn pause_by_id(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+        resume_at: Option<NaiveDateTime>,
+        actor: &Actor,
+    ) -> Result<Monitor, Error> {
+        let Some(mut monitor) = self.repo.get(monitor_id, tenant).await? else {
+            return Err(Error::MonitorNotFound(monitor_id));
+        };
+
+        let original = monitor.clone();
+        monitor.pause(resume_at)?;
+        self.save(&original, &monitor, actor).await?;
+        info!(
+            monitor_id = monitor.monitor_id.to_string(),
+            resume_at = ?resume_at,
+            "Paused Monitor('{}')", &monitor.name
+        );
+
+        Ok(monitor)
+    }
+
+    /// Resume a paused Monitor, so that it alerts again.
+    pub async fn resume_by_id(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+        actor: &Actor,
+    ) -> Result<Monitor, Error> {
+        let Some(mut monitor) = self.repo.get(monitor_id, tenant).await? else {
+            return Err(Error::MonitorNotFound(monitor_id));
+        };
+
+        let original = monitor.clone();
+        monitor.resume();
+        self.save(&original, &monitor, actor).await?;
+        info!(
+            monitor_id = monitor.monitor_id.to_string(),
+            "Resumed Monitor('{}')", &monitor.name
+        );
+
+        Ok(monitor)
+    }
+
+    async fn save(
+        &mut self,
+        original: &Monitor,
+        monitor: &Monitor,
+        actor: &Actor,
+    ) -> Result<(), Error> {
+        self.repo.save(monitor).await?;
+        self.audit_log
+            .record(&AuditEvent::updated(actor, original, monitor))
+            .await?;
+        self.delivery_queue
+            .enqueue(&LifecycleEvent::monitor_updated(monitor))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use pretty_assertions::assert_eq;
+    use tracing_test::traced_test;
+
+    use test_utils::logging::TracingLog;
+    use test_utils::{gen_datetime, gen_relative_datetime, gen_uuid};
+
+    use crate::domain::models::{
+        AlertThreshold, AuditAction, ConcurrencyPolicy, EventType, Labels, MonitorKind,
+    };
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    fn gen_monitor(paused: bool) -> Monitor {
+        Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        }
+    }
+
+    fn gen_audit_log(paused_before: bool, paused_after: bool) -> MockAuditLog {
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(move |event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Updated
+                    && event.entity.id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.before.as_ref().unwrap()["paused"] == paused_before
+                    && event.after.as_ref().unwrap()["paused"] == paused_after
+            })
+            .returning(|_| Ok(()));
+        audit_log
+    }
+
+    fn gen_delivery_queue(paused: bool) -> MockDeliveryQueue {
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(move |event: &LifecycleEvent| {
+                event.event_type == EventType::MonitorUpdated
+                    && event.data["monitor"]["paused"] == paused
+            })
+            .returning(|_| Ok(()));
+        delivery_queue
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_pausing_monitors() {
+        let resume_at = gen_relative_datetime(3_600);
+
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(
+                eq(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")),
+                eq("tenant"),
+            )
+            .returning(|_, _| Ok(Some(gen_monitor(false))));
+        mock.expect_save()
+            .once()
+            .withf(move |monitor: &Monitor| monitor.paused && monitor.resume_at == Some(resume_at))
+            .returning(|_| Ok(()));
+
+        let mut service =
+            PauseMonitorService::new(mock, gen_audit_log(false, true), gen_delivery_queue(true));
+
+        let monitor = service
+            .pause_by_id(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "tenant",
+                Some(resume_at),
+                &actor(),
+            )
+            .await
+            .unwrap();
+
+        assert!(monitor.is_paused());
+        assert_eq!(monitor.resume_at, Some(resume_at));
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                format!(
+                    "Paused Monitor('foo') \
+                    monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\" \
+                    resume_at=Some({resume_at:?})"
+                )
+            );
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_pausing_monitors_until_the_past() {
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .returning(|_, _| Ok(Some(gen_monitor(false))));
+        mock.expect_save().never();
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue.expect_enqueue().never();
+
+        let mut service = PauseMonitorService::new(mock, audit_log, delivery_queue);
+
+        let result = service
+            .pause_by_id(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "tenant",
+                Some(gen_datetime("2024-05-01T12:00:00")),
+                &actor(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidMonitor(
+                "Invalid resume time '2024-05-01 12:00:00', must be in the future".to_owned()
+            ))
+        );
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_resuming_monitors() {
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(
+                eq(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")),
+                eq("tenant"),
+            )
+            .returning(|_, _| Ok(Some(gen_monitor(true))));
+        mock.expect_save()
+            .once()
+            .withf(|monitor: &Monitor| !monitor.paused && monitor.resume_at.is_none())
+            .returning(|_| Ok(()));
+
+        let mut service =
+            PauseMonitorService::new(mock, gen_audit_log(true, false), gen_delivery_queue(false));
+
+        let monitor = service
+            .resume_by_id(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "tenant",
+                &actor(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!monitor.is_paused());
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                "Resumed Monitor('foo') monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
+            );
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_resuming_monitor_when_monitor_doesnt_exist() {
+        let mut mock = MockRepository::new();
+        mock.expect_get()
+            .once()
+            .with(
+                eq(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")),
+                eq("tenant"),
+            )
+            .returning(|_, _| Ok(None));
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue.expect_enqueue().never();
+
+        let mut service = PauseMonitorService::new(mock, audit_log, delivery_queue);
+
+        let result = service
+            .resume_by_id(
+                gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                "tenant",
+                &actor(),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::MonitorNotFound(gen_uuid(
+                "01a92c6c-6803-409d-b675-022fff62575a"
+            )))
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use tracing::info;
+
+use crate::errors::Error;
+use crate::infrastructure::repositories::monitor::PruneJobs;
+
+pub struct PruneJobsService<T: PruneJobs> {
+    repo: T,
+}
+
+impl<T: PruneJobs> PruneJobsService<T> {
+    pub fn new(repo: T) -> Self {
+        Self { repo }
+    }
+
+    /// Delete Jobs that are outside of their Monitor's retention, `batch_size` Jobs at a time,
+    /// returning the total number of Jobs that were deleted.
+    pub async fn prune(&mut self, batch_size: u32) -> Result<usize, Error> {
+        info!("Beginning pruning of Jobs...");
+        let retentions = self.repo.get_retentions().await?;
+        info!("Found {} Monitors with a job retention", retentions.len());
+
+        let mut total_pruned = 0;
+        for (monitor_id, retention) in retentions {
+            let mut pruned = 0;
+            loop {
+                let batch_pruned = self
+                    .repo
+                    .prune_jobs(monitor_id, &retention, batch_size)
+                    .await?;
+                pruned += batch_pruned;
+
+                if batch_pruned < batch_size as usize {
+                    break;
+                }
+            }
+
+            info!(
+                monitor_id = monitor_id.to_string(),
+                "Pruned {} Jobs from Monitor", pruned
+            );
+            total_pruned += pruned;
+        }
+        info!("Pruning of Jobs complete");
+
+        Ok(total_pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use tracing_test::traced_test;
+
+    use test_utils::gen_uuid;
+    use test_utils::logging::TracingLog;
+
+    use crate::domain::models::JobRetention;
+    use crate::infrastructure::repositories::monitor::MockPruneJobs;
+
+    use super::*;
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_prune_jobs_service() {
+        let mut mock = MockPruneJobs::new();
+        mock.expect_get_retentions().once().returning(|| {
+            Ok(vec![
+                (
+                    gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                    JobRetention::new(Some(30), None).unwrap(),
+                ),
+                (
+                    gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                    JobRetention::new(None, Some(100)).unwrap(),
+                ),
+            ])
+        });
+        // The first Monitor needs several batches, whereas the second has nothing to prune.
+        let mut batches = vec![2, 2, 1].into_iter();
+        mock.expect_prune_jobs()
+            .times(3)
+            .with(
+                eq(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")),
+                eq(JobRetention::new(Some(30), None).unwrap()),
+                eq(2),
+            )
+            .returning(move |_, _, _| Ok(batches.next().unwrap()));
+        mock.expect_prune_jobs()
+            .once()
+            .with(
+                eq(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")),
+                eq(JobRetention::new(None, Some(100)).unwrap()),
+                eq(2),
+            )
+            .returning(|_, _, _| Ok(0));
+
+        let mut service = PruneJobsService::new(mock);
+
+        let result = service.prune(2).await;
+
+        assert_eq!(result, Ok(5));
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            let messages = logs
+                .iter()
+                .map(|log| log.body.as_str())
+                .collect::<Vec<&str>>();
+            assert_eq!(
+                messages,
+                vec![
+                    "Beginning pruning of Jobs...",
+                    "Found 2 Monitors with a job retention",
+                    "Pruned 5 Jobs from Monitor \
+                    monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\"",
+                    "Pruned 0 Jobs from Monitor \
+                    monitor_id=\"01a92c6c-6803-409d-b675-022fff62575a\"",
+                    "Pruning of Jobs complete",
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_prune_jobs_service_with_repository_error() {
+        let mut mock = MockPruneJobs::new();
+        mock.expect_get_retentions().once().returning(|| {
+            Ok(vec![(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                JobRetention::new(Some(30), None).unwrap(),
+            )])
+        });
+        mock.expect_prune_jobs()
+            .once()
+            .returning(|_, _, _| Err(Error::RepositoryError("test error".to_owned())));
+
+        let mut service = PruneJobsService::new(mock);
+
+        let result = service.prune(2).await;
+
+        assert_eq!(result, Err(Error::RepositoryError("test error".to_owned())));
+    }
+}
@@ -0,0 +1,352 @@
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::domain::models::{ApiKey, ApiKeyScope, Job, Monitor};
+use crate::errors::Error;
+use crate::infrastructure::repositories::api_key::GetByKey;
+use crate::infrastructure::repositories::Repository;
+
+pub struct RecordHeartbeatService<
+    MonitorRepo: Repository<Monitor>,
+    ApiKeyRepo: Repository<ApiKey> + GetByKey,
+> {
+    monitor_repo: MonitorRepo,
+    api_key_repo: ApiKeyRepo,
+}
+
+impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey>
+    RecordHeartbeatService<MonitorRepo, ApiKeyRepo>
+{
+    pub fn new(monitor_repo: MonitorRepo, api_key_repo: ApiKeyRepo) -> Self {
+        Self {
+            monitor_repo,
+            api_key_repo,
+        }
+    }
+
+    pub async fn record_heartbeat_for_monitor(
+        &mut self,
+        monitor_id: Uuid,
+        api_key: &str,
+    ) -> Result<Job, Error> {
+        let mut key = self.validate_key(api_key).await?;
+        key.check_access(ApiKeyScope::HeartbeatsWrite, monitor_id)?;
+
+        let mut monitor_opt = self.monitor_repo.get(monitor_id, &key.tenant).await?;
+
+        match &mut monitor_opt {
+            Some(monitor) => {
+                // Evertime an API key is used to access a monitor, we record it's usage. This is
+                // useful for monitoring and auditing purposes, since API keys aren't as secure as
+                // JWTs.
+                self.record_monitor_usage(&mut key, monitor).await?;
+
+                let heartbeat = self.record_heartbeat(monitor).await?;
+
+                info!(
+                    monitor_id = monitor_id.to_string(),
+                    job_id = heartbeat.job_id.to_string(),
+                    "Recorded heartbeat for Monitor('{}')",
+                    monitor.name,
+                );
+                Ok(heartbeat)
+            }
+            None => Err(Error::MonitorNotFound(monitor_id)),
+        }
+    }
+
+    async fn validate_key(&mut self, key: &str) -> Result<ApiKey, Error> {
+        let api_key = self.api_key_repo.get_by_key(&ApiKey::hash_key(key)).await?;
+        match api_key {
+            Some(key) => {
+                key.check_expiry()?;
+                Ok(key)
+            }
+            None => Err(Error::Unauthorized("Invalid API key".to_owned())),
+        }
+    }
+
+    async fn record_monitor_usage(
+        &mut self,
+        key: &mut ApiKey,
+        monitor: &Monitor,
+    ) -> Result<(), Error> {
+        key.record_usage(monitor)?;
+        self.api_key_repo.save(key).await
+    }
+
+    async fn record_heartbeat(&mut self, monitor: &mut Monitor) -> Result<Job, Error> {
+        match monitor.record_heartbeat() {
+            Ok(heartbeat) => {
+                self.monitor_repo.save(monitor).await?;
+                Ok(heartbeat)
+            }
+            Err(err) => {
+                error!(
+                    monitor_id = monitor.monitor_id.to_string(),
+                    "Error recording heartbeat: {:?}", err
+                );
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use rstest::{fixture, rstest};
+    use tracing_test::traced_test;
+
+    use test_utils::logging::TracingLog;
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{AlertThreshold, ConcurrencyPolicy, Labels, MonitorKind};
+    use crate::infrastructure::repositories::api_key::MockApiKeyRepo;
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    #[fixture]
+    fn mock_api_key_repo() -> MockApiKeyRepo {
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .with(eq(
+                "104e4587f5340bd9264ea0fee2075627c74420bd5c48aa9e8a463f03a2675020",
+            ))
+            .returning(|_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    None,
+                )))
+            });
+        mock_api_key_repo
+            .expect_save()
+            .once()
+            .withf(|key: &ApiKey| {
+                key.last_used_monitor_id == Some(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"))
+            })
+            .returning(|_| Ok(()));
+        mock_api_key_repo
+    }
+
+    fn monitor_repo_returning(kind: MonitorKind) -> MockRepository<Monitor> {
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo
+            .expect_get()
+            .once()
+            .with(
+                eq(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")),
+                eq("tenant"),
+            )
+            .returning(move |_, _| {
+                Ok(Some(Monitor {
+                    monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                    tenant: "tenant".to_owned(),
+                    name: "foo".to_owned(),
+                    expected_duration: 300,
+                    grace_duration: 100,
+                    kind: kind.clone(),
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
+                    jobs: vec![],
+                }))
+            });
+        mock_monitor_repo
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_record_heartbeat_service(mock_api_key_repo: MockApiKeyRepo) {
+        let mut mock_monitor_repo = monitor_repo_returning(MonitorKind::Heartbeat { period: 60 });
+        mock_monitor_repo
+            .expect_save()
+            .once()
+            .withf(|monitor: &Monitor| {
+                monitor.jobs.len() == 1
+                    && !monitor.jobs[0].in_progress()
+                    && !monitor.jobs[0].errored()
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = RecordHeartbeatService::new(mock_monitor_repo, mock_api_key_repo);
+        let heartbeat = service
+            .record_heartbeat_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+            )
+            .await
+            .unwrap();
+
+        assert!(!heartbeat.in_progress());
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                format!(
+                    "Recorded heartbeat for Monitor('foo') \
+                    monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\" \
+                    job_id=\"{}\"",
+                    heartbeat.job_id
+                ),
+            );
+            Ok(())
+        });
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_record_heartbeat_for_non_heartbeat_monitor(mock_api_key_repo: MockApiKeyRepo) {
+        let mut mock_monitor_repo = monitor_repo_returning(MonitorKind::Job);
+        mock_monitor_repo.expect_save().never();
+
+        let mut service = RecordHeartbeatService::new(mock_monitor_repo, mock_api_key_repo);
+        let result = service
+            .record_heartbeat_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::NotHeartbeatMonitor(gen_uuid(
+                "41ebffb4-a188-48e9-8ec1-61380085cde3"
+            )))
+        );
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::ERROR);
+            assert_eq!(
+                logs[0].body,
+                "Error recording heartbeat: \
+                NotHeartbeatMonitor(41ebffb4-a188-48e9-8ec1-61380085cde3) \
+                monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\""
+            );
+            Ok(())
+        });
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_record_heartbeat_unauthorized() {
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(|_| Ok(None));
+
+        let mut service = RecordHeartbeatService::new(MockRepository::new(), mock_api_key_repo);
+        let result = service
+            .record_heartbeat_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::Unauthorized("Invalid API key".to_owned()))
+        );
+    }
+
+    #[rstest]
+    #[case::missing_scope(
+        vec![ApiKeyScope::JobsWrite],
+        None,
+        "API key does not have the 'heartbeats:write' scope"
+    )]
+    #[case::other_monitor(
+        vec![ApiKeyScope::HeartbeatsWrite],
+        Some(vec![gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")]),
+        "API key cannot be used for Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3')"
+    )]
+    #[tokio::test]
+    async fn test_record_heartbeat_forbidden(
+        #[case] scopes: Vec<ApiKeyScope>,
+        #[case] monitor_ids: Option<Vec<Uuid>>,
+        #[case] reason: &str,
+    ) {
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(move |_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    scopes.clone(),
+                    monitor_ids.clone(),
+                    None,
+                )))
+            });
+        mock_api_key_repo.expect_save().never();
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().never();
+
+        let mut service = RecordHeartbeatService::new(mock_monitor_repo, mock_api_key_repo);
+        let result = service
+            .record_heartbeat_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+            )
+            .await;
+
+        assert_eq!(result, Err(Error::Forbidden(reason.to_owned())));
+    }
+
+    #[tokio::test]
+    async fn test_record_heartbeat_expired_key() {
+        let expires_at = gen_datetime("2024-12-01T00:00:00");
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(move |_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    Some(expires_at),
+                )))
+            });
+        mock_api_key_repo.expect_save().never();
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().never();
+
+        let mut service = RecordHeartbeatService::new(mock_monitor_repo, mock_api_key_repo);
+        let result = service
+            .record_heartbeat_for_monitor(
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo-key",
+            )
+            .await;
+
+        assert_eq!(result, Err(Error::ApiKeyExpired(expires_at)));
+    }
+}
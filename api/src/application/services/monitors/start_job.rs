@@ -1,26 +1,45 @@
-use tracing::info;
+use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::domain::models::{ApiKey, Job, Monitor};
+use crate::domain::models::{
+    Activity, ActivityEvent, ApiKey, ApiKeyScope, Job, LifecycleEvent, Monitor,
+};
 use crate::errors::Error;
+use crate::infrastructure::activity::PublishActivity;
 use crate::infrastructure::repositories::api_key::GetByKey;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
 use crate::infrastructure::repositories::Repository;
 
 pub struct StartJobService<
     MonitorRepo: Repository<Monitor>,
     ApiKeyRepo: Repository<ApiKey> + GetByKey,
+    Publisher: PublishActivity,
+    Queue: DeliveryQueue,
 > {
     monitor_repo: MonitorRepo,
     api_key_repo: ApiKeyRepo,
+    publisher: Publisher,
+    delivery_queue: Queue,
 }
 
-impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey>
-    StartJobService<MonitorRepo, ApiKeyRepo>
+impl<
+        MonitorRepo: Repository<Monitor>,
+        ApiKeyRepo: Repository<ApiKey> + GetByKey,
+        Publisher: PublishActivity,
+        Queue: DeliveryQueue,
+    > StartJobService<MonitorRepo, ApiKeyRepo, Publisher, Queue>
 {
-    pub fn new(monitor_repo: MonitorRepo, api_key_repo: ApiKeyRepo) -> Self {
+    pub fn new(
+        monitor_repo: MonitorRepo,
+        api_key_repo: ApiKeyRepo,
+        publisher: Publisher,
+        delivery_queue: Queue,
+    ) -> Self {
         Self {
             monitor_repo,
             api_key_repo,
+            publisher,
+            delivery_queue,
         }
     }
 
@@ -30,6 +49,7 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
         api_key: &str,
     ) -> Result<Job, Error> {
         let mut key = self.validate_key(api_key).await?;
+        key.check_access(ApiKeyScope::JobsWrite, monitor_id)?;
 
         let mut monitor_opt = self.monitor_repo.get(monitor_id, &key.tenant).await?;
 
@@ -48,6 +68,7 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
                     "Started job for Monitor('{}')",
                     monitor.name,
                 );
+                self.publish_started(monitor, &job).await;
                 Ok(job)
             }
             None => Err(Error::MonitorNotFound(monitor_id)),
@@ -57,7 +78,10 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
     async fn validate_key(&mut self, key: &str) -> Result<ApiKey, Error> {
         let api_key = self.api_key_repo.get_by_key(&ApiKey::hash_key(key)).await?;
         match api_key {
-            Some(key) => Ok(key),
+            Some(key) => {
+                key.check_expiry()?;
+                Ok(key)
+            }
             None => Err(Error::Unauthorized("Invalid API key".to_owned())),
         }
     }
@@ -72,21 +96,55 @@ impl<MonitorRepo: Repository<Monitor>, ApiKeyRepo: Repository<ApiKey> + GetByKey
     }
 
     async fn start_job(&mut self, monitor: &mut Monitor) -> Result<Job, Error> {
-        let job = monitor.start_job();
+        let job = monitor.start_job()?;
         self.monitor_repo.save(monitor).await?;
         Ok(job)
     }
+
+    async fn publish_started(&mut self, monitor: &Monitor, job: &Job) {
+        let event = ActivityEvent::new(
+            &monitor.tenant,
+            monitor.monitor_id,
+            &monitor.name,
+            Some(job.job_id),
+            Activity::JobStarted,
+        );
+
+        // The Job has already started, so failing to publish it shouldn't fail the request.
+        if let Err(error) = self.publisher.publish(&event).await {
+            error!(
+                monitor_id = monitor.monitor_id.to_string(),
+                "Error publishing activity: {:?}", error
+            );
+        }
+        if let Err(error) = self
+            .delivery_queue
+            .enqueue(&LifecycleEvent::job_started(monitor, job))
+            .await
+        {
+            error!(
+                monitor_id = monitor.monitor_id.to_string(),
+                "Error queueing webhook deliveries: {:?}", error
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use mockall::predicate::*;
+    use rstest::rstest;
     use tracing_test::traced_test;
 
-    use test_utils::gen_uuid;
     use test_utils::logging::TracingLog;
+    use test_utils::{gen_datetime, gen_uuid};
 
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EventType, Labels, MonitorKind, MonitorSettings,
+    };
+    use crate::infrastructure::activity::MockPublishActivity;
     use crate::infrastructure::repositories::api_key::MockApiKeyRepo;
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
@@ -106,6 +164,9 @@ mod tests {
                     "Test key".to_owned(),
                     "foo-key".to_owned(),
                     "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    None,
                 )))
             });
         mock_api_key_repo
@@ -135,6 +196,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
@@ -149,7 +222,37 @@ mod tests {
             })
             .returning(|_| Ok(()));
 
-        let mut service = StartJobService::new(mock_monitor_repo, mock_api_key_repo);
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher
+            .expect_publish()
+            .once()
+            .withf(|event: &ActivityEvent| {
+                event.tenant == "tenant"
+                    && event.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.monitor_name == "foo"
+                    && event.job_id.is_some()
+                    && event.activity == Activity::JobStarted
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_delivery_queue = MockDeliveryQueue::new();
+        mock_delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(|event: &LifecycleEvent| {
+                event.tenant == "tenant"
+                    && event.event_type == EventType::JobStarted
+                    && event.data["monitor"]["name"] == "foo"
+                    && event.data["job"]["in_progress"] == true
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = StartJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            mock_publisher,
+            mock_delivery_queue,
+        );
         let job = service
             .start_job_for_monitor(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"), "foo-key")
             .await
@@ -186,7 +289,12 @@ mod tests {
             ))
             .returning(|_| Ok(None));
 
-        let mut service = StartJobService::new(MockRepository::new(), mock_api_key_repo);
+        let mut service = StartJobService::new(
+            MockRepository::new(),
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
         let start_result = service
             .start_job_for_monitor(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"), "foo-key")
             .await;
@@ -201,6 +309,53 @@ mod tests {
         });
     }
 
+    #[rstest]
+    #[case::missing_scope(
+        vec![ApiKeyScope::HeartbeatsWrite],
+        None,
+        "API key does not have the 'jobs:write' scope"
+    )]
+    #[case::other_monitor(
+        vec![ApiKeyScope::JobsWrite],
+        Some(vec![gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")]),
+        "API key cannot be used for Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3')"
+    )]
+    #[tokio::test]
+    async fn test_start_job_forbidden(
+        #[case] scopes: Vec<ApiKeyScope>,
+        #[case] monitor_ids: Option<Vec<Uuid>>,
+        #[case] reason: &str,
+    ) {
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(move |_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    scopes.clone(),
+                    monitor_ids.clone(),
+                    None,
+                )))
+            });
+        mock_api_key_repo.expect_save().never();
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().never();
+
+        let mut service = StartJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
+        let start_result = service
+            .start_job_for_monitor(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"), "foo-key")
+            .await;
+        assert_eq!(start_result, Err(Error::Forbidden(reason.to_owned())));
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_start_job_monitor_not_found() {
@@ -216,6 +371,9 @@ mod tests {
                     "Test key".to_owned(),
                     "foo-key".to_owned(),
                     "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    None,
                 )))
             });
         mock_api_key_repo.expect_save().never();
@@ -229,7 +387,12 @@ mod tests {
             )
             .returning(|_, _| Ok(None));
 
-        let mut service = StartJobService::new(mock_monitor_repo, mock_api_key_repo);
+        let mut service = StartJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
 
         let non_existent_id = gen_uuid("01a92c6c-6803-409d-b675-022fff62575a");
         let start_result = service
@@ -242,4 +405,101 @@ mod tests {
             Ok(())
         });
     }
+
+    #[tokio::test]
+    async fn test_start_job_expired_key() {
+        let expires_at = gen_datetime("2024-12-01T00:00:00");
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo
+            .expect_get_by_key()
+            .once()
+            .returning(move |_| {
+                Ok(Some(ApiKey::new(
+                    "Test key".to_owned(),
+                    "foo-key".to_owned(),
+                    "tenant".to_owned(),
+                    ApiKeyScope::ALL.to_vec(),
+                    None,
+                    Some(expires_at),
+                )))
+            });
+        mock_api_key_repo.expect_save().never();
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().never();
+
+        let mut service = StartJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            MockPublishActivity::new(),
+            MockDeliveryQueue::new(),
+        );
+        let result = service
+            .start_job_for_monitor(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"), "foo-key")
+            .await;
+
+        assert_eq!(result, Err(Error::ApiKeyExpired(expires_at)));
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_start_job_rejected_while_job_in_progress() {
+        let mut mock_api_key_repo = MockApiKeyRepo::new();
+        mock_api_key_repo.expect_get_by_key().once().returning(|_| {
+            Ok(Some(ApiKey::new(
+                "Test key".to_owned(),
+                "foo-key".to_owned(),
+                "tenant".to_owned(),
+                ApiKeyScope::ALL.to_vec(),
+                None,
+                None,
+            )))
+        });
+        mock_api_key_repo.expect_save().once().returning(|_| Ok(()));
+
+        let mut mock_monitor_repo = MockRepository::new();
+        mock_monitor_repo.expect_get().once().returning(|_, _| {
+            let mut monitor = Monitor::new(
+                "tenant".to_owned(),
+                "foo".to_owned(),
+                300,
+                100,
+                MonitorSettings {
+                    concurrency_policy: ConcurrencyPolicy::Reject,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            monitor.monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+            monitor.start_job().unwrap();
+            Ok(Some(monitor))
+        });
+        mock_monitor_repo.expect_save().never();
+
+        let mut mock_publisher = MockPublishActivity::new();
+        mock_publisher.expect_publish().never();
+        let mut mock_delivery_queue = MockDeliveryQueue::new();
+        mock_delivery_queue.expect_enqueue().never();
+
+        let mut service = StartJobService::new(
+            mock_monitor_repo,
+            mock_api_key_repo,
+            mock_publisher,
+            mock_delivery_queue,
+        );
+        let result = service
+            .start_job_for_monitor(gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"), "foo-key")
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::JobInProgress(gen_uuid(
+                "41ebffb4-a188-48e9-8ec1-61380085cde3"
+            )))
+        );
+
+        logs_assert(|logs| {
+            assert!(logs.is_empty());
+            Ok(())
+        });
+    }
 }
@@ -1,44 +1,61 @@
-use tracing::info;
+use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::domain::models::Monitor;
+use crate::domain::models::{Actor, AuditEvent, LifecycleEvent, Monitor};
+use crate::domain::services::duration_stats::calculate_duration_stats;
 use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::monitor::GetAutoTuned;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
 use crate::infrastructure::repositories::Repository;
 
-pub struct UpdateMonitorService<T: Repository<Monitor>> {
-    repo: T,
+pub struct UpdateMonitorService<
+    MonitorRepo: Repository<Monitor>,
+    AuditLogRepo: AuditLog,
+    Queue: DeliveryQueue,
+> {
+    repo: MonitorRepo,
+    audit_log: AuditLogRepo,
+    delivery_queue: Queue,
 }
 
-impl<T: Repository<Monitor>> UpdateMonitorService<T> {
-    pub fn new(repo: T) -> Self {
-        Self { repo }
+impl<MonitorRepo: Repository<Monitor>, AuditLogRepo: AuditLog, Queue: DeliveryQueue>
+    UpdateMonitorService<MonitorRepo, AuditLogRepo, Queue>
+{
+    pub fn new(repo: MonitorRepo, audit_log: AuditLogRepo, delivery_queue: Queue) -> Self {
+        Self {
+            repo,
+            audit_log,
+            delivery_queue,
+        }
     }
 
+    /// Update one of a tenant's Monitors, by editing it with `edit`.
     pub async fn update_by_id(
         &mut self,
         monitor_id: Uuid,
         tenant: &str,
-        new_name: &str,
-        new_expected: i32,
-        new_grace: i32,
+        edit: impl FnOnce(&mut Monitor) -> Result<(), Error>,
+        actor: &Actor,
     ) -> Result<Monitor, Error> {
         let monitor_opt = self.repo.get(monitor_id, tenant).await?;
 
         match monitor_opt {
             Some(mut monitor) => {
+                let original = monitor.clone();
                 let original_values = (
                     monitor.name.clone(),
                     monitor.expected_duration,
                     monitor.grace_duration,
                 );
-                monitor.edit_details(new_name.to_owned(), new_expected, new_grace);
+                edit(&mut monitor)?;
                 let new_values = (
                     monitor.name.clone(),
                     monitor.expected_duration,
                     monitor.grace_duration,
                 );
 
-                self.repo.save(&monitor).await?;
+                self.save(&original, &monitor, actor).await?;
                 info!(
                     monitor_id = monitor.monitor_id.to_string(),
                     original_values = ?original_values,
@@ -51,21 +68,182 @@ impl<T: Repository<Monitor>> UpdateMonitorService<T> {
             None => Err(Error::MonitorNotFound(monitor_id)),
         }
     }
+
+    async fn save(
+        &mut self,
+        original: &Monitor,
+        monitor: &Monitor,
+        actor: &Actor,
+    ) -> Result<(), Error> {
+        self.repo.save(monitor).await?;
+        self.audit_log
+            .record(&AuditEvent::updated(actor, original, monitor))
+            .await?;
+        self.delivery_queue
+            .enqueue(&LifecycleEvent::monitor_updated(monitor))
+            .await
+    }
+}
+
+impl<
+        MonitorRepo: Repository<Monitor> + GetAutoTuned,
+        AuditLogRepo: AuditLog,
+        Queue: DeliveryQueue,
+    > UpdateMonitorService<MonitorRepo, AuditLogRepo, Queue>
+{
+    /// Tune the expected and grace durations of every Monitor with auto-tuning enabled, across
+    /// all tenants, to suit how long their Jobs have taken, within each Monitor's bounds.
+    ///
+    /// Heartbeat Monitors are never tuned, since heartbeats don't have a duration, and nor are
+    /// Monitors without enough successful Jobs to suggest durations from.
+    pub async fn auto_tune(&mut self, actor: &Actor) -> Result<(), Error> {
+        let monitors = self.repo.get_auto_tuned().await?;
+
+        for mut monitor in monitors {
+            let Some(auto_tune) = monitor.auto_tune.clone() else {
+                continue;
+            };
+            if monitor.is_heartbeat() {
+                continue;
+            }
+            let Some(suggested) =
+                calculate_duration_stats(&monitor.jobs).and_then(|stats| stats.suggest())
+            else {
+                continue;
+            };
+
+            let original_values = (monitor.expected_duration, monitor.grace_duration);
+            let new_values = auto_tune.tune(&suggested);
+            if new_values == original_values {
+                continue;
+            }
+
+            let original = monitor.clone();
+            (monitor.expected_duration, monitor.grace_duration) = new_values;
+            if let Err(error) = self.save(&original, &monitor, actor).await {
+                // A Monitor that can't be saved shouldn't stop the rest from being tuned.
+                error!(
+                    monitor_id = ?monitor.monitor_id,
+                    "Error auto-tuning Monitor: {:?}", error
+                );
+                continue;
+            }
+            info!(
+                monitor_id = monitor.monitor_id.to_string(),
+                original_values = ?original_values,
+                new_values = ?new_values,
+                "Auto-tuned Monitor('{}')", &monitor.name
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use mockall::predicate::*;
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use mockall::{mock, predicate::*};
     use pretty_assertions::assert_eq;
     use tracing_test::traced_test;
 
-    use test_utils::gen_uuid;
     use test_utils::logging::TracingLog;
+    use test_utils::{gen_datetime, gen_uuid};
 
+    use crate::domain::models::{
+        AlertThreshold, AnomalyDetection, AuditAction, AutoTune, ConcurrencyPolicy, EndState,
+        EventType, Job, JobMetadata, Labels, MaintenanceWindow, MonitorKind, MonitorSettings,
+    };
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
     use crate::infrastructure::repositories::MockRepository;
 
     use super::*;
 
+    mock! {
+        pub MonitorRepo {}
+
+        #[async_trait]
+        impl GetAutoTuned for MonitorRepo {
+            async fn get_auto_tuned(&mut self) -> Result<Vec<Monitor>, Error>;
+        }
+
+        #[async_trait]
+        impl Repository<Monitor> for MonitorRepo {
+            async fn get(
+                &mut self, monitor_id: uuid::Uuid, tenant: &str
+            ) -> Result<Option<Monitor>, Error>;
+            async fn all(&mut self, tenant: &str) -> Result<Vec<Monitor>, Error>;
+            async fn delete(&mut self, monitor: &Monitor) -> Result<(), Error>;
+            async fn save(&mut self, monitor: &Monitor) -> Result<(), Error>;
+        }
+    }
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    fn gen_auto_tuned_monitor(
+        monitor_id: &str,
+        kind: MonitorKind,
+        durations: (i32, i32),
+        job_count: i64,
+    ) -> Monitor {
+        // All but one of the Jobs take 5 minutes, so durations of (300, 100) are suggested.
+        let jobs = (0..job_count)
+            .map(|i| {
+                let start_time = gen_datetime("2024-05-01T12:00:00") - Duration::hours(i);
+                let duration = if i == 0 { 400 } else { 300 };
+                Job {
+                    job_id: Uuid::new_v4(),
+                    start_time,
+                    max_end_time: start_time + Duration::seconds(2400),
+                    end_state: Some(EndState {
+                        end_time: start_time + Duration::seconds(duration),
+                        succeeded: true,
+                        output: None,
+                        metadata: JobMetadata::default(),
+                    }),
+                    late_alert_sent: false,
+                    error_alert_sent: false,
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                }
+            })
+            .collect();
+
+        Monitor {
+            monitor_id: gen_uuid(monitor_id),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: durations.0,
+            grace_duration: durations.1,
+            kind,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: Some(AutoTune::new(60, 600, 0, 60).unwrap()),
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs,
+        }
+    }
+
     #[traced_test]
     #[tokio::test]
     async fn test_update_monitor_service() {
@@ -83,6 +261,18 @@ mod tests {
                     name: "foo".to_owned(),
                     expected_duration: 300,
                     grace_duration: 100,
+                    kind: MonitorKind::Job,
+                    schedule: None,
+                    missed_start_cutoff: None,
+                    alert_threshold: AlertThreshold::default(),
+                    retention: None,
+                    labels: Labels::default(),
+                    paused: false,
+                    resume_at: None,
+                    maintenance_windows: vec![],
+                    auto_tune: None,
+                    anomaly_detection: None,
+                    concurrency_policy: ConcurrencyPolicy::Allow,
                     jobs: vec![],
                 }))
             });
@@ -94,18 +284,61 @@ mod tests {
                     && monitor.name == "new-name"
                     && monitor.expected_duration == 600
                     && monitor.grace_duration == 200
+                    && monitor.alert_threshold == AlertThreshold::new(3, None).unwrap()
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Updated
+                    && event.entity.id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.before.as_ref().unwrap()["name"] == "foo"
+                    && event.after.as_ref().unwrap()["name"] == "new-name"
             })
             .returning(|_| Ok(()));
 
-        let mut service = UpdateMonitorService::new(mock);
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(|event: &LifecycleEvent| {
+                event.event_type == EventType::MonitorUpdated
+                    && event.data["monitor"]["name"] == "new-name"
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = UpdateMonitorService::new(mock, audit_log, delivery_queue);
 
         let monitor_result = service
             .update_by_id(
                 gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
                 "tenant",
-                "new-name",
-                600,
-                200,
+                |monitor| {
+                    monitor.edit_details(
+                        "new-name".to_owned(),
+                        600,
+                        200,
+                        MonitorSettings {
+                            alert_threshold: AlertThreshold::new(3, None).unwrap(),
+                            maintenance_windows: vec![MaintenanceWindow::new(
+                                "0 2 * * 0",
+                                None,
+                                60,
+                            )
+                            .unwrap()],
+                            auto_tune: Some(AutoTune::new(60, 900, 0, 300).unwrap()),
+                            anomaly_detection: Some(
+                                AnomalyDetection::new(Some(120), None).unwrap(),
+                            ),
+                            ..Default::default()
+                        },
+                    )
+                },
+                &actor(),
             )
             .await;
 
@@ -117,6 +350,18 @@ mod tests {
                 name: "new-name".to_owned(),
                 expected_duration: 600,
                 grace_duration: 200,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::new(3, None).unwrap(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![MaintenanceWindow::new("0 2 * * 0", None, 60).unwrap()],
+                auto_tune: Some(AutoTune::new(60, 900, 0, 300).unwrap()),
+                anomaly_detection: Some(AnomalyDetection::new(Some(120), None).unwrap()),
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![],
             })
         );
@@ -146,16 +391,26 @@ mod tests {
                 eq("tenant"),
             )
             .returning(|_, _| Ok(None));
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue.expect_enqueue().never();
 
-        let mut service = UpdateMonitorService::new(mock);
+        let mut service = UpdateMonitorService::new(mock, audit_log, delivery_queue);
 
         let should_be_err = service
             .update_by_id(
                 gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 "tenant",
-                "new-name",
-                600,
-                200,
+                |monitor| {
+                    monitor.edit_details(
+                        "new-name".to_owned(),
+                        600,
+                        200,
+                        MonitorSettings::default(),
+                    )
+                },
+                &actor(),
             )
             .await;
         assert_eq!(
@@ -165,4 +420,174 @@ mod tests {
             )))
         );
     }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_auto_tuning_monitors() {
+        let mut mock = MockMonitorRepo::new();
+        mock.expect_get_auto_tuned().once().returning(|| {
+            Ok(vec![
+                gen_auto_tuned_monitor(
+                    "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                    MonitorKind::Job,
+                    (1800, 600),
+                    20,
+                ),
+                // Already tuned.
+                gen_auto_tuned_monitor(
+                    "01a92c6c-6803-409d-b675-022fff62575a",
+                    MonitorKind::Job,
+                    (300, 60),
+                    20,
+                ),
+                // Too few Jobs to tune from.
+                gen_auto_tuned_monitor(
+                    "cc6cf74e-b25d-4c8c-94a6-914e3f139c14",
+                    MonitorKind::Job,
+                    (1800, 600),
+                    3,
+                ),
+                gen_auto_tuned_monitor(
+                    "c1bf0515-df39-448b-aa95-686360a33b36",
+                    MonitorKind::Heartbeat { period: 300 },
+                    (1800, 600),
+                    20,
+                ),
+            ])
+        });
+        mock.expect_save()
+            .once()
+            .withf(|monitor: &Monitor| {
+                monitor.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && monitor.expected_duration == 300
+                    && monitor.grace_duration == 60
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Updated
+                    && event.entity.id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+                    && event.before.as_ref().unwrap()["expected_duration"] == 1800
+                    && event.after.as_ref().unwrap()["expected_duration"] == 300
+            })
+            .returning(|_| Ok(()));
+
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue
+            .expect_enqueue()
+            .once()
+            .withf(|event: &LifecycleEvent| {
+                event.event_type == EventType::MonitorUpdated
+                    && event.data["monitor"]["grace_duration"] == 60
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = UpdateMonitorService::new(mock, audit_log, delivery_queue);
+
+        let result = service.auto_tune(&actor()).await;
+
+        assert_eq!(result, Ok(()));
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                "Auto-tuned Monitor('foo') \
+                monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\" \
+                original_values=(1800, 600) \
+                new_values=(300, 60)"
+            );
+            Ok(())
+        });
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_auto_tuning_monitors_with_save_error() {
+        let mut mock = MockMonitorRepo::new();
+        mock.expect_get_auto_tuned().once().returning(|| {
+            Ok(vec![
+                gen_auto_tuned_monitor(
+                    "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                    MonitorKind::Job,
+                    (1800, 600),
+                    20,
+                ),
+                gen_auto_tuned_monitor(
+                    "01a92c6c-6803-409d-b675-022fff62575a",
+                    MonitorKind::Job,
+                    (1800, 600),
+                    20,
+                ),
+            ])
+        });
+        mock.expect_save()
+            .once()
+            .withf(|monitor: &Monitor| {
+                monitor.monitor_id == gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+            })
+            .returning(|_| Err(Error::RepositoryError("Failed to save".to_owned())));
+        mock.expect_save()
+            .once()
+            .withf(|monitor: &Monitor| {
+                monitor.monitor_id == gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().once().returning(|_| Ok(()));
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue.expect_enqueue().once().returning(|_| Ok(()));
+
+        let mut service = UpdateMonitorService::new(mock, audit_log, delivery_queue);
+
+        let result = service.auto_tune(&actor()).await;
+
+        assert_eq!(result, Ok(()));
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 2);
+            assert_eq!(logs[0].level, tracing::Level::ERROR);
+            assert_eq!(
+                logs[0].body,
+                "Error auto-tuning Monitor: RepositoryError(\"Failed to save\") \
+                monitor_id=41ebffb4-a188-48e9-8ec1-61380085cde3"
+            );
+            assert_eq!(logs[1].level, tracing::Level::INFO);
+            assert!(logs[1]
+                .body
+                .contains("monitor_id=\"01a92c6c-6803-409d-b675-022fff62575a\""));
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_auto_tuning_monitors_with_repository_error() {
+        let mut mock = MockMonitorRepo::new();
+        mock.expect_get_auto_tuned()
+            .once()
+            .returning(|| Err(Error::RepositoryError("Connection failed".to_owned())));
+        mock.expect_save().never();
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+        let mut delivery_queue = MockDeliveryQueue::new();
+        delivery_queue.expect_enqueue().never();
+
+        let mut service = UpdateMonitorService::new(mock, audit_log, delivery_queue);
+
+        let result = service.auto_tune(&actor()).await;
+
+        assert_eq!(
+            result,
+            Err(Error::RepositoryError("Connection failed".to_owned()))
+        );
+    }
 }
@@ -0,0 +1,159 @@
+use tracing::info;
+
+use crate::domain::models::{Actor, AuditEvent, WebhookSubscription};
+use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::Repository;
+
+use super::{validate_subscription, WebhookSubscriptionData};
+
+pub struct CreateSubscriptionService<
+    SubscriptionRepo: Repository<WebhookSubscription>,
+    AuditLogRepo: AuditLog,
+> {
+    repo: SubscriptionRepo,
+    audit_log: AuditLogRepo,
+}
+
+impl<SubscriptionRepo: Repository<WebhookSubscription>, AuditLogRepo: AuditLog>
+    CreateSubscriptionService<SubscriptionRepo, AuditLogRepo>
+{
+    pub fn new(repo: SubscriptionRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
+    }
+
+    pub async fn create_from_data(
+        &mut self,
+        tenant: &str,
+        data: WebhookSubscriptionData,
+        actor: &Actor,
+    ) -> Result<WebhookSubscription, Error> {
+        validate_subscription(&data)?;
+
+        let subscription = WebhookSubscription::new(
+            data.name,
+            tenant.to_owned(),
+            data.url,
+            data.secret,
+            data.event_types,
+            data.active,
+        );
+        self.repo.save(&subscription).await?;
+        self.audit_log
+            .record(&AuditEvent::created(actor, &subscription))
+            .await?;
+
+        info!(
+            subscription_id = subscription.subscription_id.to_string(),
+            "Created new Webhook Subscription - name: '{}', url: '{}'",
+            &subscription.name,
+            &subscription.url
+        );
+
+        Ok(subscription)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_test::traced_test;
+
+    use test_utils::logging::TracingLog;
+
+    use crate::domain::models::{AuditAction, EntityType, EventType};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    fn data(url: &str) -> WebhookSubscriptionData {
+        WebhookSubscriptionData {
+            name: "Pipeline".to_owned(),
+            url: url.to_owned(),
+            event_types: vec![EventType::MonitorCreated, EventType::JobFinished],
+            active: true,
+            secret: Some("secret".to_owned()),
+        }
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_create_subscription() {
+        let mut mock_repo = MockRepository::new();
+        mock_repo
+            .expect_save()
+            .once()
+            .withf(|subscription: &WebhookSubscription| {
+                subscription.tenant == "tenant"
+                    && subscription.name == "Pipeline"
+                    && subscription.url == "https://example.com/events"
+                    && subscription.secret == Some("secret".to_owned())
+                    && subscription.event_types
+                        == vec![EventType::MonitorCreated, EventType::JobFinished]
+                    && subscription.active
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.tenant == "tenant"
+                    && event.actor == actor()
+                    && event.action == AuditAction::Created
+                    && event.entity.type_ == EntityType::WebhookSubscription
+                    && event.before.is_none()
+                    && event.after.as_ref().unwrap()["name"] == "Pipeline"
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = CreateSubscriptionService::new(mock_repo, audit_log);
+        let subscription = service
+            .create_from_data("tenant", data("https://example.com/events"), &actor())
+            .await
+            .unwrap();
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert_eq!(
+                logs[0].body,
+                format!(
+                    "Created new Webhook Subscription - name: 'Pipeline', \
+                    url: 'https://example.com/events' subscription_id=\"{}\"",
+                    subscription.subscription_id
+                )
+            );
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_create_invalid_subscription() {
+        let mut mock_repo = MockRepository::new();
+        mock_repo.expect_save().never();
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+
+        let mut service = CreateSubscriptionService::new(mock_repo, audit_log);
+        let result = service
+            .create_from_data("tenant", data("ftp://example.com/events"), &actor())
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidSubscription(
+                "URL must use http or https".to_owned()
+            ))
+        );
+    }
+}
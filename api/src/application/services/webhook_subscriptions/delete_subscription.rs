@@ -0,0 +1,150 @@
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::models::{Actor, AuditEvent, WebhookSubscription};
+use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::Repository;
+
+pub struct DeleteSubscriptionService<
+    SubscriptionRepo: Repository<WebhookSubscription>,
+    AuditLogRepo: AuditLog,
+> {
+    repo: SubscriptionRepo,
+    audit_log: AuditLogRepo,
+}
+
+impl<SubscriptionRepo: Repository<WebhookSubscription>, AuditLogRepo: AuditLog>
+    DeleteSubscriptionService<SubscriptionRepo, AuditLogRepo>
+{
+    pub fn new(repo: SubscriptionRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
+    }
+
+    /// Delete a webhook subscription, along with its history of deliveries.
+    pub async fn delete_by_id(
+        &mut self,
+        subscription_id: Uuid,
+        tenant: &str,
+        actor: &Actor,
+    ) -> Result<(), Error> {
+        let subscription = self
+            .repo
+            .get(subscription_id, tenant)
+            .await?
+            .ok_or(Error::SubscriptionNotFound(subscription_id))?;
+
+        self.repo.delete(&subscription).await?;
+        self.audit_log
+            .record(&AuditEvent::deleted(actor, &subscription))
+            .await?;
+        info!(
+            subscription_id = subscription_id.to_string(),
+            "Deleted Webhook Subscription('{}')", &subscription.name
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use tracing_test::traced_test;
+
+    use test_utils::gen_uuid;
+    use test_utils::logging::TracingLog;
+
+    use crate::domain::models::{AuditAction, EventType};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    fn subscription() -> WebhookSubscription {
+        WebhookSubscription {
+            subscription_id: gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+            tenant: "tenant".to_owned(),
+            name: "Pipeline".to_owned(),
+            url: "https://example.com/events".to_owned(),
+            secret: None,
+            event_types: vec![EventType::JobStarted],
+            active: true,
+        }
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_delete_subscription() {
+        let mut mock_repo = MockRepository::new();
+        mock_repo
+            .expect_get()
+            .once()
+            .with(
+                eq(gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec")),
+                eq("tenant"),
+            )
+            .returning(|_, _| Ok(Some(subscription())));
+        mock_repo
+            .expect_delete()
+            .once()
+            .with(eq(subscription()))
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.actor == actor()
+                    && event.action == AuditAction::Deleted
+                    && event.before.as_ref().unwrap()["name"] == "Pipeline"
+                    && event.after.is_none()
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = DeleteSubscriptionService::new(mock_repo, audit_log);
+        let result = service
+            .delete_by_id(
+                gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+                "tenant",
+                &actor(),
+            )
+            .await;
+        assert_eq!(result, Ok(()));
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(
+                logs[0].body,
+                "Deleted Webhook Subscription('Pipeline') \
+                subscription_id=\"3867e53d-9c17-4ce9-b153-eff3d8c9edec\""
+            );
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_delete_subscription_not_found() {
+        let subscription_id = gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec");
+        let mut mock_repo = MockRepository::new();
+        mock_repo.expect_get().once().returning(|_, _| Ok(None));
+        mock_repo.expect_delete().never();
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+
+        let mut service = DeleteSubscriptionService::new(mock_repo, audit_log);
+        let result = service
+            .delete_by_id(subscription_id, "tenant", &actor())
+            .await;
+
+        assert_eq!(result, Err(Error::SubscriptionNotFound(subscription_id)));
+    }
+}
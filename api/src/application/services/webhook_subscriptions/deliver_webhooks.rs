@@ -0,0 +1,278 @@
+use tracing::{error, info, warn};
+
+use crate::domain::models::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+use crate::errors::Error;
+use crate::infrastructure::repositories::webhook_delivery::DeliveryQueue;
+use crate::infrastructure::webhooks::SendWebhook;
+
+/// The most deliveries that are attempted each time pending deliveries are processed. Any more are
+/// left until the next time.
+const DELIVERY_BATCH_SIZE: u32 = 100;
+
+pub struct DeliverWebhooksService<Queue: DeliveryQueue, Sender: SendWebhook> {
+    queue: Queue,
+    sender: Sender,
+}
+
+impl<Queue: DeliveryQueue, Sender: SendWebhook> DeliverWebhooksService<Queue, Sender> {
+    pub fn new(queue: Queue, sender: Sender) -> Self {
+        Self { queue, sender }
+    }
+
+    /// Attempt each pending delivery that is due, recording how it went.
+    pub async fn deliver_pending(&mut self) -> Result<(), Error> {
+        info!("Beginning webhook deliveries...");
+        let due = self.queue.get_due(DELIVERY_BATCH_SIZE).await?;
+        info!("Found {} due webhook deliveries", due.len());
+
+        let mut failed_deliveries = Vec::new();
+        for (subscription, mut delivery) in due {
+            self.attempt(&subscription, &mut delivery).await;
+
+            if let Err(error) = self.queue.save(&delivery).await {
+                error!(
+                    delivery_id = ?delivery.delivery_id,
+                    "Error saving webhook delivery: {:?}", error
+                );
+                failed_deliveries.push(delivery.delivery_id.to_string());
+            }
+        }
+
+        let result = if failed_deliveries.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::RepositoryError(format!(
+                "Failed to save webhook deliveries: {:?}",
+                failed_deliveries
+            )))
+        };
+        info!("Webhook deliveries complete");
+
+        result
+    }
+
+    async fn attempt(&self, subscription: &WebhookSubscription, delivery: &mut WebhookDelivery) {
+        let attempt = self.sender.send(subscription, delivery).await;
+        let reason = attempt.error.clone().unwrap_or_default();
+        delivery.record_attempt(attempt);
+
+        match delivery.status {
+            DeliveryStatus::Succeeded => info!(
+                subscription_id = ?subscription.subscription_id,
+                delivery_id = ?delivery.delivery_id,
+                "Delivered '{}' event to Webhook Subscription('{}')",
+                delivery.event_type,
+                &subscription.name
+            ),
+            DeliveryStatus::Pending => warn!(
+                subscription_id = ?subscription.subscription_id,
+                delivery_id = ?delivery.delivery_id,
+                "Failed to deliver '{}' event to Webhook Subscription('{}'), retrying at {}: {}",
+                delivery.event_type,
+                &subscription.name,
+                // Pending deliveries always have another attempt due.
+                delivery.next_attempt_at.unwrap(),
+                reason
+            ),
+            DeliveryStatus::Failed => error!(
+                subscription_id = ?subscription.subscription_id,
+                delivery_id = ?delivery.delivery_id,
+                "Giving up on delivering '{}' event to Webhook Subscription('{}') after {} \
+                attempts: {}",
+                delivery.event_type,
+                &subscription.name,
+                delivery.attempts.len(),
+                reason
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use tracing_test::traced_test;
+
+    use test_utils::gen_uuid;
+    use test_utils::logging::TracingLog;
+
+    use crate::domain::models::webhook_delivery::MAX_DELIVERY_ATTEMPTS;
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, DeliveryAttempt, EventType, Labels, LifecycleEvent,
+        Monitor, MonitorKind,
+    };
+    use crate::infrastructure::repositories::webhook_delivery::MockDeliveryQueue;
+    use crate::infrastructure::webhooks::MockSendWebhook;
+
+    use super::*;
+
+    fn subscription(name: &str, url: &str) -> WebhookSubscription {
+        WebhookSubscription::new(
+            name.to_owned(),
+            "tenant".to_owned(),
+            url.to_owned(),
+            None,
+            vec![EventType::MonitorCreated],
+            true,
+        )
+    }
+
+    fn delivery(subscription: &WebhookSubscription, previous_failures: usize) -> WebhookDelivery {
+        let monitor = Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        };
+        let mut delivery =
+            WebhookDelivery::new(subscription, &LifecycleEvent::monitor_created(&monitor));
+        for _ in 0..previous_failures {
+            delivery.record_attempt(DeliveryAttempt::failure(
+                None,
+                "Connection refused".to_owned(),
+            ));
+        }
+        delivery
+    }
+
+    #[traced_test]
+    #[tokio::test]
+    async fn test_deliver_pending() {
+        let working = subscription("Working", "https://example.com/working");
+        let flaky = subscription("Flaky", "https://example.com/flaky");
+        let broken = subscription("Broken", "https://example.com/broken");
+        let due = vec![
+            (working.clone(), delivery(&working, 0)),
+            (flaky.clone(), delivery(&flaky, 0)),
+            (broken.clone(), delivery(&broken, MAX_DELIVERY_ATTEMPTS - 1)),
+        ];
+
+        let mut mock_queue = MockDeliveryQueue::new();
+        mock_queue
+            .expect_get_due()
+            .once()
+            .with(eq(100))
+            .return_once(move |_| Ok(due));
+        mock_queue
+            .expect_save()
+            .once()
+            .withf(|delivery: &WebhookDelivery| {
+                delivery.status == DeliveryStatus::Succeeded && delivery.attempts.len() == 1
+            })
+            .returning(|_| Ok(()));
+        mock_queue
+            .expect_save()
+            .once()
+            .withf(|delivery: &WebhookDelivery| {
+                delivery.status == DeliveryStatus::Pending
+                    && delivery.attempts.len() == 1
+                    && delivery.next_attempt_at.is_some()
+            })
+            .returning(|_| Ok(()));
+        mock_queue
+            .expect_save()
+            .once()
+            .withf(|delivery: &WebhookDelivery| {
+                delivery.status == DeliveryStatus::Failed
+                    && delivery.attempts.len() == MAX_DELIVERY_ATTEMPTS
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_sender = MockSendWebhook::new();
+        mock_sender
+            .expect_send()
+            .times(3)
+            .returning(|subscription, _| {
+                if subscription.name == "Working" {
+                    DeliveryAttempt::success(200)
+                } else {
+                    DeliveryAttempt::failure(
+                        Some(503),
+                        "Received HTTP 503 Service Unavailable".to_owned(),
+                    )
+                }
+            });
+
+        let mut service = DeliverWebhooksService::new(mock_queue, mock_sender);
+        let result = service.deliver_pending().await;
+        assert_eq!(result, Ok(()));
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 6);
+            assert_eq!(logs[0].body, "Beginning webhook deliveries...");
+            assert_eq!(logs[1].body, "Found 3 due webhook deliveries");
+
+            assert_eq!(logs[2].level, tracing::Level::INFO);
+            assert!(logs[2].body.starts_with(
+                "Delivered 'monitor_created' event to Webhook Subscription('Working')"
+            ));
+
+            assert_eq!(logs[3].level, tracing::Level::WARN);
+            assert!(logs[3].body.starts_with(
+                "Failed to deliver 'monitor_created' event to Webhook Subscription('Flaky'), \
+                retrying at "
+            ));
+            assert!(logs[3]
+                .body
+                .contains(": Received HTTP 503 Service Unavailable"));
+
+            assert_eq!(logs[4].level, tracing::Level::ERROR);
+            assert!(logs[4].body.starts_with(
+                "Giving up on delivering 'monitor_created' event to \
+                Webhook Subscription('Broken') after 8 attempts: \
+                Received HTTP 503 Service Unavailable"
+            ));
+
+            assert_eq!(logs[5].body, "Webhook deliveries complete");
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_deliver_pending_when_saving_fails() {
+        let subscription = subscription("Working", "https://example.com/working");
+        let delivery = delivery(&subscription, 0);
+        let delivery_id = delivery.delivery_id;
+
+        let mut mock_queue = MockDeliveryQueue::new();
+        mock_queue
+            .expect_get_due()
+            .once()
+            .return_once(move |_| Ok(vec![(subscription, delivery)]));
+        mock_queue
+            .expect_save()
+            .once()
+            .returning(|_| Err(Error::RepositoryError("Connection lost".to_owned())));
+
+        let mut mock_sender = MockSendWebhook::new();
+        mock_sender
+            .expect_send()
+            .once()
+            .returning(|_, _| DeliveryAttempt::success(200));
+
+        let mut service = DeliverWebhooksService::new(mock_queue, mock_sender);
+        let result = service.deliver_pending().await;
+
+        assert_eq!(
+            result,
+            Err(Error::RepositoryError(format!(
+                "Failed to save webhook deliveries: [\"{delivery_id}\"]"
+            )))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+pub mod create_subscription;
+pub mod delete_subscription;
+pub mod deliver_webhooks;
+pub mod update_subscription;
+
+use reqwest::Url;
+
+use crate::errors::Error;
+
+pub use cron_mon_client::types::WebhookSubscriptionData;
+
+pub use create_subscription::CreateSubscriptionService;
+pub use delete_subscription::DeleteSubscriptionService;
+pub use deliver_webhooks::DeliverWebhooksService;
+pub use update_subscription::UpdateSubscriptionService;
+
+/// Validate the attributes of a webhook subscription.
+fn validate_subscription(data: &WebhookSubscriptionData) -> Result<(), Error> {
+    let url = Url::parse(&data.url)
+        .map_err(|error| Error::InvalidSubscription(format!("Invalid URL: {error}")))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(Error::InvalidSubscription(
+            "URL must use http or https".to_owned(),
+        ));
+    }
+
+    if data.event_types.is_empty() {
+        return Err(Error::InvalidSubscription(
+            "Webhook subscriptions need at least one event type".to_owned(),
+        ));
+    }
+
+    if data.secret.as_ref().is_some_and(|secret| secret.is_empty()) {
+        return Err(Error::InvalidSubscription(
+            "Secret cannot be empty".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::models::EventType;
+
+    use super::*;
+
+    #[rstest]
+    #[case::invalid_url(
+        "not a url",
+        vec![EventType::JobStarted],
+        None,
+        "Invalid URL: relative URL without a base"
+    )]
+    #[case::unsupported_scheme(
+        "ftp://example.com/events",
+        vec![EventType::JobStarted],
+        None,
+        "URL must use http or https"
+    )]
+    #[case::no_event_types(
+        "https://example.com/events",
+        vec![],
+        None,
+        "Webhook subscriptions need at least one event type"
+    )]
+    #[case::empty_secret(
+        "https://example.com/events",
+        vec![EventType::JobStarted],
+        Some(""),
+        "Secret cannot be empty"
+    )]
+    fn test_validate_subscription(
+        #[case] url: &str,
+        #[case] event_types: Vec<EventType>,
+        #[case] secret: Option<&str>,
+        #[case] expected_error: &str,
+    ) {
+        let data = WebhookSubscriptionData {
+            name: "Pipeline".to_owned(),
+            url: url.to_owned(),
+            event_types,
+            active: true,
+            secret: secret.map(str::to_owned),
+        };
+
+        assert_eq!(
+            validate_subscription(&data),
+            Err(Error::InvalidSubscription(expected_error.to_owned()))
+        );
+    }
+}
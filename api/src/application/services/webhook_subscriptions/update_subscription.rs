@@ -0,0 +1,197 @@
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::models::{Actor, AuditEvent, WebhookSubscription};
+use crate::errors::Error;
+use crate::infrastructure::repositories::audit_log::AuditLog;
+use crate::infrastructure::repositories::Repository;
+
+use super::{validate_subscription, WebhookSubscriptionData};
+
+pub struct UpdateSubscriptionService<
+    SubscriptionRepo: Repository<WebhookSubscription>,
+    AuditLogRepo: AuditLog,
+> {
+    repo: SubscriptionRepo,
+    audit_log: AuditLogRepo,
+}
+
+impl<SubscriptionRepo: Repository<WebhookSubscription>, AuditLogRepo: AuditLog>
+    UpdateSubscriptionService<SubscriptionRepo, AuditLogRepo>
+{
+    pub fn new(repo: SubscriptionRepo, audit_log: AuditLogRepo) -> Self {
+        Self { repo, audit_log }
+    }
+
+    pub async fn update_by_id(
+        &mut self,
+        subscription_id: Uuid,
+        tenant: &str,
+        new_data: WebhookSubscriptionData,
+        actor: &Actor,
+    ) -> Result<WebhookSubscription, Error> {
+        validate_subscription(&new_data)?;
+
+        let mut subscription = self
+            .repo
+            .get(subscription_id, tenant)
+            .await?
+            .ok_or(Error::SubscriptionNotFound(subscription_id))?;
+        let original = subscription.clone();
+        let original_values = (
+            original.name.clone(),
+            original.url.clone(),
+            original.event_types.clone(),
+            original.active,
+        );
+
+        subscription.edit_details(
+            new_data.name,
+            new_data.url,
+            new_data.secret,
+            new_data.event_types,
+            new_data.active,
+        );
+        self.repo.save(&subscription).await?;
+        self.audit_log
+            .record(&AuditEvent::updated(actor, &original, &subscription))
+            .await?;
+
+        let new_values = (
+            &subscription.name,
+            &subscription.url,
+            &subscription.event_types,
+            subscription.active,
+        );
+        info!(
+            subscription_id = subscription.subscription_id.to_string(),
+            original_values = ?original_values,
+            new_values = ?new_values,
+            "Modified Webhook Subscription('{}')", &subscription.name
+        );
+
+        Ok(subscription)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+    use rstest::{fixture, rstest};
+    use tracing_test::traced_test;
+
+    use test_utils::gen_uuid;
+    use test_utils::logging::TracingLog;
+
+    use crate::domain::models::{AuditAction, EventType};
+    use crate::infrastructure::repositories::audit_log::MockAuditLog;
+    use crate::infrastructure::repositories::MockRepository;
+
+    use super::*;
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    #[fixture]
+    fn subscription() -> WebhookSubscription {
+        WebhookSubscription {
+            subscription_id: gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+            tenant: "tenant".to_owned(),
+            name: "Pipeline".to_owned(),
+            url: "https://example.com/events".to_owned(),
+            secret: None,
+            event_types: vec![EventType::JobStarted],
+            active: true,
+        }
+    }
+
+    fn new_data() -> WebhookSubscriptionData {
+        WebhookSubscriptionData {
+            name: "New pipeline".to_owned(),
+            url: "https://example.com/new-events".to_owned(),
+            event_types: vec![EventType::JobFinished],
+            active: false,
+            secret: Some("secret".to_owned()),
+        }
+    }
+
+    #[rstest]
+    #[traced_test]
+    #[tokio::test]
+    async fn test_update_subscription(subscription: WebhookSubscription) {
+        let mut mock_repo = MockRepository::new();
+        mock_repo
+            .expect_get()
+            .once()
+            .with(
+                eq(gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec")),
+                eq("tenant"),
+            )
+            .returning(move |_, _| Ok(Some(subscription.clone())));
+        mock_repo
+            .expect_save()
+            .once()
+            .withf(|subscription: &WebhookSubscription| {
+                subscription.name == "New pipeline"
+                    && subscription.url == "https://example.com/new-events"
+                    && subscription.secret == Some("secret".to_owned())
+                    && subscription.event_types == vec![EventType::JobFinished]
+                    && !subscription.active
+            })
+            .returning(|_| Ok(()));
+
+        let mut audit_log = MockAuditLog::new();
+        audit_log
+            .expect_record()
+            .once()
+            .withf(|event: &AuditEvent| {
+                event.action == AuditAction::Updated
+                    && event.before.as_ref().unwrap()["name"] == "Pipeline"
+                    && event.after.as_ref().unwrap()["name"] == "New pipeline"
+            })
+            .returning(|_| Ok(()));
+
+        let mut service = UpdateSubscriptionService::new(mock_repo, audit_log);
+        let subscription = service
+            .update_by_id(
+                gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+                "tenant",
+                new_data(),
+                &actor(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(subscription.name, "New pipeline");
+
+        logs_assert(|logs| {
+            let logs = TracingLog::from_logs(logs);
+            assert_eq!(logs.len(), 1);
+            assert_eq!(logs[0].level, tracing::Level::INFO);
+            assert!(logs[0]
+                .body
+                .starts_with("Modified Webhook Subscription('New pipeline')"));
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_update_subscription_not_found() {
+        let subscription_id = gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec");
+        let mut mock_repo = MockRepository::new();
+        mock_repo.expect_get().once().returning(|_, _| Ok(None));
+        mock_repo.expect_save().never();
+        let mut audit_log = MockAuditLog::new();
+        audit_log.expect_record().never();
+
+        let mut service = UpdateSubscriptionService::new(mock_repo, audit_log);
+        let result = service
+            .update_by_id(subscription_id, "tenant", new_data(), &actor())
+            .await;
+
+        assert_eq!(result, Err(Error::SubscriptionNotFound(subscription_id)));
+    }
+}
@@ -3,15 +3,28 @@ extern crate rocket;
 
 use std::future::Future;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use clap::{Args, Parser, Subcommand};
+use cron_mon_client::types::JobMetadataData;
+use cron_mon_client::{Auth, Client};
+use uuid::Uuid;
 
+use cron_mon_api::application::routes::metrics;
 use cron_mon_api::application::services::{
-    get_alert_erroneous_jobs_service, get_create_monitor_service,
+    get_alert_erroneous_jobs_service, get_alert_missed_starts_service, get_create_monitor_service,
+    get_deliver_webhooks_service, get_prune_jobs_service, get_update_monitor_service,
+};
+use cron_mon_api::domain::models::{
+    Actor, AlertThreshold, FailureWindow, JobRetention, Labels, MonitorKind, MonitorSettings,
+    Schedule,
 };
 use cron_mon_api::infrastructure::database::{create_connection_pool, run_migrations};
-use cron_mon_api::infrastructure::logging::init_logging;
+use cron_mon_api::infrastructure::logging::{init_logging, init_stderr_logging};
+use cron_mon_api::infrastructure::metrics::record_alert_loop;
+use cron_mon_api::infrastructure::middleware::guards::scrape_token::ScrapeToken;
+use cron_mon_api::runner;
 
 /// The cron-mon CLI.
 #[derive(Parser)]
@@ -31,6 +44,15 @@ enum Command {
 
     /// Create a new monitor.
     CreateMonitor(CreateMonitorArgs),
+
+    /// Delete finished jobs that are outside of their monitor's retention.
+    Prune(PruneArgs),
+
+    /// Tune the durations of monitors with auto-tuning enabled to suit how long their jobs take.
+    AutoTune,
+
+    /// Run a command as a job of a monitor.
+    Run(RunArgs),
 }
 
 #[derive(Args)]
@@ -38,6 +60,16 @@ struct MonitorArgs {
     /// The interval, in seconds, to run the monitor at.
     #[arg(short, long, default_value = "10")]
     interval: u64,
+
+    /// Serve the monitor's metrics at `/metrics` on this port.
+    #[arg(long)]
+    metrics_port: Option<u16>,
+
+    /// The interval, in seconds, to auto-tune the durations of monitors with auto-tuning enabled
+    /// at. Durations only need to follow gradual changes, so this is far less often than alerts
+    /// are checked for.
+    #[arg(long, default_value = "3600")]
+    auto_tune_interval: u64,
 }
 
 #[derive(Args)]
@@ -57,42 +89,269 @@ struct CreateMonitorArgs {
     /// The tenant that the monitor is to belong to.
     #[arg(short, long)]
     tenant: String,
+
+    /// The cron schedule that jobs monitored by this monitor are expected to start on.
+    #[arg(short, long)]
+    schedule: Option<String>,
+
+    /// The timezone to evaluate the schedule in (defaults to UTC).
+    #[arg(long, requires = "schedule")]
+    timezone: Option<String>,
+
+    /// Create a heartbeat monitor, expecting a heartbeat at least this often, in seconds.
+    #[arg(long)]
+    heartbeat_period: Option<i32>,
+
+    /// Only alert once this many consecutive jobs have been late or failed.
+    #[arg(long, default_value = "1")]
+    consecutive_failures: i32,
+
+    /// Only alert once this many of the last `window_runs` jobs have been late or failed.
+    #[arg(long, requires = "window_runs")]
+    window_failures: Option<i32>,
+
+    /// The number of most recent jobs to count `window_failures` within.
+    #[arg(long, requires = "window_failures")]
+    window_runs: Option<i32>,
+
+    /// Only keep jobs for this many days after they start.
+    #[arg(long)]
+    retention_days: Option<i32>,
+
+    /// Only keep this many of the most recent jobs.
+    #[arg(long)]
+    retention_jobs: Option<i32>,
+
+    /// A `key=value` label to describe the monitor with. Can be given more than once.
+    #[arg(long = "label", value_parser = parse_label)]
+    labels: Vec<(String, String)>,
+}
+
+#[derive(Args)]
+struct PruneArgs {
+    /// The most jobs to delete at a time.
+    #[arg(short, long, default_value = "1000", value_parser = clap::value_parser!(u32).range(1..))]
+    batch_size: u32,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// The ID of the monitor to run the command as a job of.
+    #[arg(short, long)]
+    monitor_id: Uuid,
+
+    /// The API key to start and finish the job with.
+    #[arg(short, long, env = "CRON_MON_API_KEY", hide_env_values = true)]
+    api_key: String,
+
+    /// The URL of the CronMon API.
+    #[arg(
+        short,
+        long,
+        env = "CRON_MON_URL",
+        default_value = "http://127.0.0.1:8000"
+    )]
+    url: String,
+
+    /// The most output to record against the job, in KB. Only the end of the output is kept.
+    #[arg(short, long, default_value = "10")]
+    output_limit: usize,
+
+    /// The host to record the job as having run on.
+    #[arg(long, env = "HOSTNAME")]
+    hostname: Option<String>,
+
+    /// The version of the command to record against the job, such as a git SHA.
+    #[arg(long, env = "CRON_MON_JOB_VERSION")]
+    job_version: Option<String>,
+
+    /// The command to run, and its arguments.
+    #[arg(last = true, required = true)]
+    command: Vec<String>,
 }
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+
+    // Running a command doesn't touch the database, and shouldn't add to the command's output.
+    if let Command::Run(args) = cli.command {
+        init_stderr_logging();
+        let client = Client::new(&args.url, Auth::ApiKey(args.api_key));
+        let exit_code = runner::run(
+            &client,
+            args.monitor_id,
+            &args.command,
+            args.output_limit * 1024,
+            JobMetadataData {
+                hostname: args.hostname,
+                version: args.job_version,
+                ..Default::default()
+            },
+        )
+        .await;
+        std::process::exit(exit_code);
+    }
+
     init_logging();
     run_migrations();
 
-    let cli = Cli::parse();
     match cli.command {
         Command::Api => {
             cron_mon_api::rocket().launch().await.unwrap();
         }
         Command::Monitor(args) => {
-            run_periodically(args.interval, || async move {
-                match create_connection_pool() {
-                    Ok(pool) => {
-                        let mut service = get_alert_erroneous_jobs_service(&pool);
+            if let Some(port) = args.metrics_port {
+                serve_metrics(port);
+            }
+
+            let auto_tune_interval = Duration::from_secs(args.auto_tune_interval);
+            let last_auto_tuned = Arc::new(Mutex::new(None::<Instant>));
 
-                        if let Err(error) = service.send_pending_alerts().await {
-                            error!("Error processing late jobs: {:?}", error);
+            run_periodically(args.interval, move || {
+                let last_auto_tuned = Arc::clone(&last_auto_tuned);
+                async move {
+                    let started = Instant::now();
+                    match create_connection_pool() {
+                        Ok(pool) => {
+                            let mut service = get_alert_erroneous_jobs_service(&pool);
+
+                            if let Err(error) = service.send_pending_alerts().await {
+                                error!("Error processing late jobs: {:?}", error);
+                            }
+
+                            let mut service = get_alert_missed_starts_service(&pool);
+
+                            if let Err(error) = service.send_pending_alerts().await {
+                                error!("Error processing missed starts: {:?}", error);
+                            }
+
+                            let mut service = get_deliver_webhooks_service(&pool);
+
+                            if let Err(error) = service.deliver_pending().await {
+                                error!("Error delivering webhooks: {:?}", error);
+                            }
+
+                            if auto_tune_due(&last_auto_tuned, auto_tune_interval) {
+                                let mut service = get_update_monitor_service(&pool);
+
+                                if let Err(error) = service.auto_tune(&cli_actor()).await {
+                                    error!("Error auto-tuning monitors: {:?}", error);
+                                }
+                            }
                         }
+                        Err(error) => error!("Failed to create DB connection pool.: {:?}", error),
                     }
-                    Err(error) => error!("Failed to create DB connection pool.: {:?}", error),
+                    record_alert_loop(started.elapsed());
                 }
             })
             .await;
         }
         Command::CreateMonitor(args) => {
             let pool = create_connection_pool().expect("Failed to create DB connection pool.");
+            let schedule = args
+                .schedule
+                .map(|expression| Schedule::new(&expression, args.timezone.as_deref()))
+                .transpose()
+                .expect("Invalid schedule.");
+            let kind = match args.heartbeat_period {
+                Some(period) => MonitorKind::Heartbeat { period },
+                None => MonitorKind::Job,
+            };
+            let window = args
+                .window_failures
+                .zip(args.window_runs)
+                .map(|(failures, runs)| FailureWindow { failures, runs });
+            let alert_threshold = AlertThreshold::new(args.consecutive_failures, window)
+                .expect("Invalid alert threshold.");
+            let retention = match (args.retention_days, args.retention_jobs) {
+                (None, None) => None,
+                (max_age_days, max_jobs) => {
+                    Some(JobRetention::new(max_age_days, max_jobs).expect("Invalid job retention."))
+                }
+            };
+            let labels = Labels::new(args.labels.into_iter().collect()).expect("Invalid labels.");
             let mut service = get_create_monitor_service(&pool);
             service
-                .create_by_attributes(&args.tenant, &args.name, args.expected, args.grace)
+                .create_by_attributes(
+                    &args.tenant,
+                    &args.name,
+                    args.expected,
+                    args.grace,
+                    MonitorSettings {
+                        schedule,
+                        kind,
+                        alert_threshold,
+                        retention,
+                        labels,
+                        ..Default::default()
+                    },
+                    &cli_actor(),
+                )
                 .await
                 .expect("Failed to create monitor.");
         }
+        Command::Prune(args) => {
+            let pool = create_connection_pool().expect("Failed to create DB connection pool.");
+            let mut service = get_prune_jobs_service(&pool);
+
+            if let Err(error) = service.prune(args.batch_size).await {
+                error!("Error pruning jobs: {:?}", error);
+            }
+        }
+        Command::AutoTune => {
+            let pool = create_connection_pool().expect("Failed to create DB connection pool.");
+            let mut service = get_update_monitor_service(&pool);
+
+            if let Err(error) = service.auto_tune(&cli_actor()).await {
+                error!("Error auto-tuning monitors: {:?}", error);
+            }
+        }
+        Command::Run(_) => unreachable!("Commands are run before migrations"),
+    }
+}
+
+/// There's no user behind the CLI, so changes made with it are audited as being made by the CLI
+/// itself.
+fn cli_actor() -> Actor {
+    Actor {
+        id: "cron-mon-cli".to_owned(),
+        name: "cron-mon CLI".to_owned(),
+    }
+}
+
+/// Parse a `key=value` label given on the command line.
+fn parse_label(label: &str) -> Result<(String, String), String> {
+    label
+        .split_once('=')
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("Invalid label '{label}', expected 'key=value'"))
+}
+
+/// Serve the metrics of this process in the background, on the given port.
+fn serve_metrics(port: u16) {
+    let figment = rocket::Config::figment().merge(("port", port));
+    let metrics_server = rocket::custom(figment)
+        .manage(ScrapeToken::from_env())
+        .mount("/", routes![metrics::process_metrics]);
+
+    tokio::spawn(async move {
+        if let Err(error) = metrics_server.launch().await {
+            error!("Failed to serve metrics: {:?}", error);
+        }
+    });
+}
+
+/// Check whether monitors are due to be auto-tuned, given when they last were, recording that
+/// they're being tuned now if so.
+fn auto_tune_due(last_auto_tuned: &Mutex<Option<Instant>>, interval: Duration) -> bool {
+    let mut last_auto_tuned = last_auto_tuned.lock().unwrap();
+    if last_auto_tuned.is_some_and(|last| last.elapsed() < interval) {
+        return false;
     }
+
+    *last_auto_tuned = Some(Instant::now());
+    true
 }
 
 async fn run_periodically<F, Fut>(seconds: u64, func: F)
@@ -111,3 +370,34 @@ where
         func().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::default(&[], Some(1000))]
+    #[case::batch_size(&["--batch-size", "50"], Some(50))]
+    #[case::zero_batch_size(&["--batch-size", "0"], None)]
+    fn test_prune_batch_size(#[case] args: &[&str], #[case] expected: Option<u32>) {
+        let cli = Cli::try_parse_from(["cron-mon", "prune"].iter().chain(args));
+
+        let batch_size = cli.ok().map(|cli| match cli.command {
+            Command::Prune(args) => args.batch_size,
+            _ => unreachable!("Only the prune command is parsed"),
+        });
+        assert_eq!(batch_size, expected);
+    }
+
+    #[test]
+    fn test_auto_tune_due() {
+        let last_auto_tuned = Mutex::new(None);
+
+        // Monitors are tuned straight away, and then not again until the interval has passed.
+        assert!(auto_tune_due(&last_auto_tuned, Duration::from_secs(3600)));
+        assert!(!auto_tune_due(&last_auto_tuned, Duration::from_secs(3600)));
+        assert!(auto_tune_due(&last_auto_tuned, Duration::ZERO));
+    }
+}
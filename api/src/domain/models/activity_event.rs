@@ -0,0 +1,179 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::models::AlertConfig;
+
+/// The `ActivityEvent` struct describes something that just happened to one of a tenant's
+/// Monitors, such as one of its Jobs starting or an alert being sent for it. Unlike `AuditEvent`s,
+/// these aren't stored - they're only streamed to clients that are listening at the time.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ActivityEvent {
+    /// The tenant that the Monitor belongs to.
+    #[serde(skip_serializing)]
+    pub tenant: String,
+    /// The ID of the Monitor.
+    pub monitor_id: Uuid,
+    /// The name of the Monitor.
+    pub monitor_name: String,
+    /// The ID of the Job, unless the activity isn't about a specific Job (such as a missed start).
+    pub job_id: Option<Uuid>,
+    /// What happened.
+    #[serde(flatten)]
+    pub activity: Activity,
+    /// When it happened.
+    pub timestamp: NaiveDateTime,
+}
+
+/// The kinds of activity that are streamed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Activity {
+    JobStarted,
+    JobFinished {
+        succeeded: bool,
+    },
+    JobLate,
+    AlertSent {
+        alert: AlertKind,
+        alert_config_id: Uuid,
+        alert_config_name: String,
+    },
+    /// An alert that wasn't sent, because the Monitor was paused or in a maintenance window.
+    AlertSuppressed {
+        alert: AlertKind,
+    },
+}
+
+/// The kinds of alert that can be sent.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    LateJob,
+    ErroredJob,
+    Recovered,
+    AnomalousJob,
+    OverlappingJob,
+    MissedStart,
+    MissedHeartbeat,
+}
+
+impl ActivityEvent {
+    /// Describe activity that has just happened.
+    pub fn new(
+        tenant: &str,
+        monitor_id: Uuid,
+        monitor_name: &str,
+        job_id: Option<Uuid>,
+        activity: Activity,
+    ) -> Self {
+        Self {
+            tenant: tenant.to_owned(),
+            monitor_id,
+            monitor_name: monitor_name.to_owned(),
+            job_id,
+            activity,
+            timestamp: Utc::now().naive_utc(),
+        }
+    }
+
+    /// Describe an alert that has just been sent for a Monitor, via one of its tenant's alert
+    /// configurations.
+    pub fn alert_sent(
+        alert_config: &AlertConfig,
+        alert: AlertKind,
+        monitor_id: Uuid,
+        monitor_name: &str,
+        job_id: Option<Uuid>,
+    ) -> Self {
+        Self::new(
+            &alert_config.tenant,
+            monitor_id,
+            monitor_name,
+            job_id,
+            Activity::AlertSent {
+                alert,
+                alert_config_id: alert_config.alert_config_id,
+                alert_config_name: alert_config.name.clone(),
+            },
+        )
+    }
+}
+
+impl Activity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::JobStarted => "job_started",
+            Self::JobFinished { .. } => "job_finished",
+            Self::JobLate => "job_late",
+            Self::AlertSent { .. } => "alert_sent",
+            Self::AlertSuppressed { .. } => "alert_suppressed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::{json, Value};
+
+    use test_utils::gen_uuid;
+
+    use super::*;
+
+    #[rstest]
+    #[case::job_started(Activity::JobStarted, "job_started", json!({"type": "job_started"}))]
+    #[case::job_finished(
+        Activity::JobFinished { succeeded: false },
+        "job_finished",
+        json!({"type": "job_finished", "succeeded": false})
+    )]
+    #[case::job_late(Activity::JobLate, "job_late", json!({"type": "job_late"}))]
+    #[case::alert_sent(
+        Activity::AlertSent {
+            alert: AlertKind::LateJob,
+            alert_config_id: gen_uuid("fadd7266-648b-4102-8f85-c768655f4297"),
+            alert_config_name: "Slack".to_owned(),
+        },
+        "alert_sent",
+        json!({
+            "type": "alert_sent",
+            "alert": "late_job",
+            "alert_config_id": "fadd7266-648b-4102-8f85-c768655f4297",
+            "alert_config_name": "Slack"
+        })
+    )]
+    #[case::alert_suppressed(
+        Activity::AlertSuppressed { alert: AlertKind::ErroredJob },
+        "alert_suppressed",
+        json!({"type": "alert_suppressed", "alert": "errored_job"})
+    )]
+    fn test_serialize_activity_event(
+        #[case] activity: Activity,
+        #[case] name: &str,
+        #[case] expected_fields: Value,
+    ) {
+        let event = ActivityEvent::new(
+            "tenant",
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            "foo",
+            Some(gen_uuid("01a92c6c-6803-409d-b675-022fff62575a")),
+            activity,
+        );
+        assert_eq!(event.activity.as_str(), name);
+
+        let mut expected = json!({
+            "monitor_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
+            "monitor_name": "foo",
+            "job_id": "01a92c6c-6803-409d-b675-022fff62575a",
+            "timestamp": event.timestamp,
+        });
+        expected
+            .as_object_mut()
+            .unwrap()
+            .extend(expected_fields.as_object().unwrap().clone());
+
+        // The tenant is never included, since clients only receive their own tenant's activity.
+        assert_eq!(serde_json::to_value(&event).unwrap(), expected);
+    }
+}
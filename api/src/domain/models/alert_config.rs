@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::models::Monitor;
+use crate::domain::models::{LabelSelector, Monitor};
 use crate::errors::Error;
 
 /// A domain model representing user configuration for alerts.
@@ -22,6 +24,13 @@ pub struct AlertConfig {
     pub on_late: bool,
     /// Whether to send alerts for errored jobs.
     pub on_error: bool,
+    /// Whether to send alerts when a Monitor recovers from late or errored jobs.
+    pub on_recovery: bool,
+    /// Whether to send alerts for jobs that succeeded, but took an anomalous amount of time.
+    pub on_anomaly: bool,
+    /// If set, the alert configuration also applies to the tenant's Monitors whose labels match
+    /// this selector, without them needing to be associated with it.
+    pub label_selector: Option<LabelSelector>,
     /// The type of alert.
     #[serde(rename = "type")]
     pub type_: AlertType,
@@ -35,6 +44,15 @@ pub enum AlertType {
     /// An alert that sends a Slack message.
     #[serde(rename = "slack")]
     Slack(SlackAlertConfig),
+    /// An alert that sends a HTTP request to an arbitrary URL.
+    #[serde(rename = "webhook")]
+    Webhook(WebhookAlertConfig),
+    /// An alert that sends an email.
+    #[serde(rename = "email")]
+    Email(EmailAlertConfig),
+    /// An alert that triggers (and resolves) PagerDuty incidents.
+    #[serde(rename = "pagerduty")]
+    PagerDuty(PagerDutyAlertConfig),
 }
 
 /// Slack-specifc configuration for alerts.
@@ -46,6 +64,83 @@ pub struct SlackAlertConfig {
     pub token: String,
 }
 
+/// Webhook-specific configuration for alerts.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WebhookAlertConfig {
+    /// The URL to send the alert to.
+    pub url: String,
+    /// The HTTP method to use when sending the alert.
+    #[serde(default)]
+    pub method: WebhookMethod,
+    /// Any additional headers to include in the request.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// An optional secret, used to sign the request body with HMAC-SHA256.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// The HTTP methods that can be used to send webhook alerts.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum WebhookMethod {
+    #[default]
+    Post,
+    Put,
+    Patch,
+}
+
+/// Email-specific configuration for alerts.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EmailAlertConfig {
+    /// The addresses to send the alert to.
+    pub recipients: Vec<String>,
+    /// The SMTP relay to send the alert via. When not provided, the server's relay is used.
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// Connection details for an SMTP relay.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SmtpConfig {
+    /// The hostname of the relay.
+    pub host: String,
+    /// The port of the relay.
+    #[serde(default = "SmtpConfig::default_port")]
+    pub port: u16,
+    /// The username to authenticate with, if the relay requires authentication.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// The password to authenticate with, if the relay requires authentication.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// The address that alerts are sent from.
+    pub from: String,
+    /// How the connection to the relay is secured.
+    #[serde(default)]
+    pub tls: SmtpTls,
+}
+
+/// The ways a connection to an SMTP relay can be secured.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    /// Upgrade a plaintext connection with STARTTLS.
+    #[default]
+    Starttls,
+    /// Connect with TLS from the outset (SMTPS).
+    Tls,
+    /// Don't use TLS at all. Only suitable for local relays.
+    None,
+}
+
+/// PagerDuty-specific configuration for alerts.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PagerDutyAlertConfig {
+    /// The integration key of the PagerDuty service to send events to (via Events API v2).
+    pub routing_key: String,
+}
+
 /// Brief info on a Monitor using an alert configuration.
 #[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct AppliedMonitor {
@@ -57,12 +152,15 @@ pub struct AppliedMonitor {
 
 impl AlertConfig {
     /// Create a new `AlertConfig` for Slack.
+    #[allow(clippy::too_many_arguments)] // Each argument is a distinct attribute of the config
     pub fn new_slack_config(
         name: String,
         tenant: String,
         active: bool,
         on_late: bool,
         on_error: bool,
+        on_recovery: bool,
+        on_anomaly: bool,
         channel: String,
         token: String,
     ) -> Self {
@@ -73,33 +171,122 @@ impl AlertConfig {
             active,
             on_late,
             on_error,
+            on_recovery,
+            on_anomaly,
+            label_selector: None,
             type_: AlertType::Slack(SlackAlertConfig { channel, token }),
             monitors: Vec::new(),
         }
     }
 
+    /// Create a new `AlertConfig` for a webhook.
+    #[allow(clippy::too_many_arguments)] // Each argument is a distinct attribute of the config
+    pub fn new_webhook_config(
+        name: String,
+        tenant: String,
+        active: bool,
+        on_late: bool,
+        on_error: bool,
+        on_recovery: bool,
+        on_anomaly: bool,
+        webhook: WebhookAlertConfig,
+    ) -> Self {
+        Self {
+            alert_config_id: Uuid::new_v4(),
+            name,
+            tenant,
+            active,
+            on_late,
+            on_error,
+            on_recovery,
+            on_anomaly,
+            label_selector: None,
+            type_: AlertType::Webhook(webhook),
+            monitors: Vec::new(),
+        }
+    }
+
+    /// Create a new `AlertConfig` for email.
+    #[allow(clippy::too_many_arguments)] // Each argument is a distinct attribute of the config
+    pub fn new_email_config(
+        name: String,
+        tenant: String,
+        active: bool,
+        on_late: bool,
+        on_error: bool,
+        on_recovery: bool,
+        on_anomaly: bool,
+        email: EmailAlertConfig,
+    ) -> Self {
+        Self {
+            alert_config_id: Uuid::new_v4(),
+            name,
+            tenant,
+            active,
+            on_late,
+            on_error,
+            on_recovery,
+            on_anomaly,
+            label_selector: None,
+            type_: AlertType::Email(email),
+            monitors: Vec::new(),
+        }
+    }
+
+    /// Create a new `AlertConfig` for PagerDuty.
+    #[allow(clippy::too_many_arguments)] // Each argument is a distinct attribute of the config
+    pub fn new_pagerduty_config(
+        name: String,
+        tenant: String,
+        active: bool,
+        on_late: bool,
+        on_error: bool,
+        on_recovery: bool,
+        on_anomaly: bool,
+        pagerduty: PagerDutyAlertConfig,
+    ) -> Self {
+        Self {
+            alert_config_id: Uuid::new_v4(),
+            name,
+            tenant,
+            active,
+            on_late,
+            on_error,
+            on_recovery,
+            on_anomaly,
+            label_selector: None,
+            type_: AlertType::PagerDuty(pagerduty),
+            monitors: Vec::new(),
+        }
+    }
+
     /// Modify this alert config's details.
+    #[allow(clippy::too_many_arguments)] // Each argument is a distinct attribute of the config
     pub fn edit_details(
         &mut self,
         name: String,
         active: bool,
         on_late: bool,
         on_error: bool,
+        on_recovery: bool,
+        on_anomaly: bool,
+        label_selector: Option<LabelSelector>,
         type_: AlertType,
     ) -> Result<(), Error> {
-        // TODO: Implement this check when we have more than one alert type. Commented out for now
-        // as it's unreachable as we only have 1 type.
-        // if std::mem::discriminant(&self.type_) != std::mem::discriminant(&type_) {
-        //     return Err(Error::AlertConfigurationError(format!(
-        //         "Cannot change alert type from '{}' to '{}'",
-        //         self.type_, type_
-        //     )));
-        // }
+        if std::mem::discriminant(&self.type_) != std::mem::discriminant(&type_) {
+            return Err(Error::AlertConfigurationError(format!(
+                "Cannot change alert type from '{}' to '{}'",
+                self.type_, type_
+            )));
+        }
 
         self.name = name;
         self.active = active;
         self.on_late = on_late;
         self.on_error = on_error;
+        self.on_recovery = on_recovery;
+        self.on_anomaly = on_anomaly;
+        self.label_selector = label_selector;
         self.type_ = type_;
 
         Ok(())
@@ -145,12 +332,83 @@ impl AlertConfig {
             .collect();
         monitor_ids.contains(&monitor.monitor_id)
     }
+
+    /// Check if the alert configuration applies to a monitor, either because they're associated
+    /// or because the monitor's labels match the alert configuration's label selector.
+    pub fn applies_to_monitor(&self, monitor: &Monitor) -> bool {
+        self.is_associated_with_monitor(monitor)
+            || (self.tenant == monitor.tenant
+                && self
+                    .label_selector
+                    .as_ref()
+                    .is_some_and(|selector| selector.matches(&monitor.labels)))
+    }
+}
+
+impl Display for WebhookMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookMethod::Post => write!(f, "POST"),
+            WebhookMethod::Put => write!(f, "PUT"),
+            WebhookMethod::Patch => write!(f, "PATCH"),
+        }
+    }
+}
+
+impl FromStr for WebhookMethod {
+    type Err = Error;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        match method {
+            "POST" => Ok(WebhookMethod::Post),
+            "PUT" => Ok(WebhookMethod::Put),
+            "PATCH" => Ok(WebhookMethod::Patch),
+            _ => Err(Error::InvalidAlertConfig(format!(
+                "Unsupported webhook method '{method}'"
+            ))),
+        }
+    }
+}
+
+impl SmtpConfig {
+    /// The standard port for mail submission.
+    pub fn default_port() -> u16 {
+        587
+    }
+}
+
+impl Display for SmtpTls {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SmtpTls::Starttls => write!(f, "starttls"),
+            SmtpTls::Tls => write!(f, "tls"),
+            SmtpTls::None => write!(f, "none"),
+        }
+    }
+}
+
+impl FromStr for SmtpTls {
+    type Err = Error;
+
+    fn from_str(tls: &str) -> Result<Self, Self::Err> {
+        match tls {
+            "starttls" => Ok(SmtpTls::Starttls),
+            "tls" => Ok(SmtpTls::Tls),
+            "none" => Ok(SmtpTls::None),
+            _ => Err(Error::InvalidAlertConfig(format!(
+                "Unsupported SMTP TLS mode '{tls}'"
+            ))),
+        }
+    }
 }
 
 impl Display for AlertType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AlertType::Slack(_) => write!(f, "slack"),
+            AlertType::Webhook(_) => write!(f, "webhook"),
+            AlertType::Email(_) => write!(f, "email"),
+            AlertType::PagerDuty(_) => write!(f, "pagerduty"),
         }
     }
 }
@@ -162,6 +420,10 @@ mod tests {
 
     use test_utils::gen_uuid;
 
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, Labels, MonitorKind, MonitorSettings,
+    };
+
     use super::*;
 
     #[test]
@@ -172,6 +434,8 @@ mod tests {
             true,
             true,
             true,
+            false,
+            false,
             "test-channel".to_string(),
             "test-token".to_string(),
         );
@@ -202,6 +466,9 @@ mod tests {
             active: true,
             on_late: true,
             on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: Some("team=payments".parse().unwrap()),
             type_: AlertType::Slack(SlackAlertConfig {
                 channel: "test-channel".to_string(),
                 token: "test-token".to_string(),
@@ -221,6 +488,9 @@ mod tests {
                 "active": true,
                 "on_late": true,
                 "on_error": true,
+                "on_recovery": false,
+                "on_anomaly": false,
+                "label_selector": "team=payments",
                 "type": {
                     "slack": {
                         "channel": "test-channel",
@@ -245,6 +515,8 @@ mod tests {
             true,
             true,
             true,
+            false,
+            false,
             "test-channel".to_string(),
             "test-token".to_string(),
         );
@@ -254,6 +526,9 @@ mod tests {
             false,
             false,
             false,
+            false,
+            false,
+            Some("env=prod".parse().unwrap()),
             AlertType::Slack(SlackAlertConfig {
                 channel: "new-channel".to_string(),
                 token: "new-token".to_string(),
@@ -265,6 +540,10 @@ mod tests {
         assert!(!alert_config.active);
         assert!(!alert_config.on_late);
         assert!(!alert_config.on_error);
+        assert_eq!(
+            alert_config.label_selector,
+            Some("env=prod".parse().unwrap())
+        );
         assert_eq!(
             alert_config.type_,
             AlertType::Slack(SlackAlertConfig {
@@ -275,9 +554,154 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "Only works with the 'slack' type for now"]
     fn test_edit_details_fails_if_type_is_different() {
-        todo!()
+        let mut alert_config = AlertConfig::new_slack_config(
+            "test-name".to_string(),
+            "test-tenant".to_string(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            "test-channel".to_string(),
+            "test-token".to_string(),
+        );
+
+        let result = alert_config.edit_details(
+            "new-name".to_string(),
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            AlertType::Webhook(WebhookAlertConfig {
+                url: "https://example.com/hook".to_string(),
+                method: WebhookMethod::Post,
+                headers: BTreeMap::new(),
+                secret: None,
+            }),
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::AlertConfigurationError(
+                "Cannot change alert type from 'slack' to 'webhook'".to_string()
+            ))
+        );
+        assert_eq!(&alert_config.name, "test-name");
+    }
+
+    #[test]
+    fn new_webhook_config() {
+        let alert_config = AlertConfig::new_webhook_config(
+            "test-name".to_string(),
+            "test-tenant".to_string(),
+            true,
+            false,
+            true,
+            false,
+            false,
+            WebhookAlertConfig {
+                url: "https://example.com/hook".to_string(),
+                method: WebhookMethod::Put,
+                headers: BTreeMap::from([("X-Team".to_string(), "infra".to_string())]),
+                secret: Some("shh".to_string()),
+            },
+        );
+
+        assert_eq!(&alert_config.name, "test-name");
+        assert_eq!(&alert_config.tenant, "test-tenant");
+        assert!(alert_config.active);
+        assert!(!alert_config.on_late);
+        assert!(alert_config.on_error);
+        assert_eq!(alert_config.type_.to_string(), "webhook");
+        assert!(alert_config.monitors.is_empty());
+    }
+
+    #[test]
+    fn test_deserialising_webhook_with_defaults() {
+        let alert_type: AlertType = serde_json::from_value(json!({
+            "webhook": {"url": "https://example.com/hook"}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            alert_type,
+            AlertType::Webhook(WebhookAlertConfig {
+                url: "https://example.com/hook".to_string(),
+                method: WebhookMethod::Post,
+                headers: BTreeMap::new(),
+                secret: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_deserialising_email_with_defaults() {
+        let alert_type: AlertType = serde_json::from_value(json!({
+            "email": {
+                "recipients": ["oncall@example.com"],
+                "smtp": {"host": "smtp.example.com", "from": "cron-mon@example.com"}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            alert_type,
+            AlertType::Email(EmailAlertConfig {
+                recipients: vec!["oncall@example.com".to_string()],
+                smtp: Some(SmtpConfig {
+                    host: "smtp.example.com".to_string(),
+                    port: 587,
+                    username: None,
+                    password: None,
+                    from: "cron-mon@example.com".to_string(),
+                    tls: SmtpTls::Starttls,
+                }),
+            })
+        );
+        assert_eq!(alert_type.to_string(), "email");
+    }
+
+    #[test]
+    fn test_deserialising_pagerduty() {
+        let alert_type: AlertType =
+            serde_json::from_value(json!({"pagerduty": {"routing_key": "R0UT1NGK3Y"}})).unwrap();
+
+        assert_eq!(
+            alert_type,
+            AlertType::PagerDuty(PagerDutyAlertConfig {
+                routing_key: "R0UT1NGK3Y".to_string(),
+            })
+        );
+        assert_eq!(alert_type.to_string(), "pagerduty");
+    }
+
+    #[test]
+    fn test_parsing_smtp_tls_modes() {
+        assert_eq!("starttls".parse(), Ok(SmtpTls::Starttls));
+        assert_eq!("tls".parse(), Ok(SmtpTls::Tls));
+        assert_eq!("none".parse(), Ok(SmtpTls::None));
+        assert_eq!(
+            "ssl".parse::<SmtpTls>(),
+            Err(Error::InvalidAlertConfig(
+                "Unsupported SMTP TLS mode 'ssl'".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parsing_webhook_methods() {
+        assert_eq!("POST".parse(), Ok(WebhookMethod::Post));
+        assert_eq!("PUT".parse(), Ok(WebhookMethod::Put));
+        assert_eq!("PATCH".parse(), Ok(WebhookMethod::Patch));
+        assert_eq!(
+            "GET".parse::<WebhookMethod>(),
+            Err(Error::InvalidAlertConfig(
+                "Unsupported webhook method 'GET'".to_string()
+            ))
+        );
     }
 
     #[test]
@@ -288,10 +712,19 @@ mod tests {
             true,
             true,
             true,
+            false,
+            false,
             "test-channel".to_string(),
             "test-token".to_string(),
         );
-        let monitor = Monitor::new("test-tenant".to_string(), "test-name".to_string(), 200, 100);
+        let monitor = Monitor::new(
+            "test-tenant".to_string(),
+            "test-name".to_string(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
 
         // Sanity check to make sure we start from a clean slate.
         assert_eq!(alert_config.monitors, vec![]);
@@ -322,6 +755,18 @@ mod tests {
             tenant: "test-tenant".to_string(),
             expected_duration: 200,
             grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
             jobs: vec![],
         };
         let mut alert_config = AlertConfig {
@@ -331,6 +776,9 @@ mod tests {
             active: true,
             on_late: true,
             on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
             type_: AlertType::Slack(SlackAlertConfig {
                 channel: "test-channel".to_string(),
                 token: "test-token".to_string(),
@@ -361,6 +809,18 @@ mod tests {
             tenant: "test-tenant".to_string(),
             expected_duration: 200,
             grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
             jobs: vec![],
         };
         let mut alert_config = AlertConfig {
@@ -370,6 +830,9 @@ mod tests {
             active: true,
             on_late: true,
             on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
             type_: AlertType::Slack(SlackAlertConfig {
                 channel: "test-channel".to_string(),
                 token: "test-token".to_string(),
@@ -389,6 +852,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_applying_to_monitors_by_label_selector() {
+        let mut monitor = Monitor::new(
+            "test-tenant".to_string(),
+            "test-name".to_string(),
+            200,
+            100,
+            MonitorSettings {
+                labels: Labels::new(BTreeMap::from([(
+                    "team".to_string(),
+                    "payments".to_string(),
+                )]))
+                .unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut alert_config = AlertConfig::new_slack_config(
+            "test-name".to_string(),
+            "test-tenant".to_string(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            "test-channel".to_string(),
+            "test-token".to_string(),
+        );
+
+        // Without a label selector, only associated monitors are alerted on.
+        assert!(!alert_config.applies_to_monitor(&monitor));
+
+        alert_config.label_selector = Some("team=payments".parse().unwrap());
+        assert!(alert_config.applies_to_monitor(&monitor));
+        assert!(!alert_config.is_associated_with_monitor(&monitor));
+
+        alert_config.label_selector = Some("team=search".parse().unwrap());
+        assert!(!alert_config.applies_to_monitor(&monitor));
+
+        // Label selectors never match monitors belonging to other tenants.
+        alert_config.label_selector = Some("team=payments".parse().unwrap());
+        monitor.tenant = "other-tenant".to_string();
+        assert!(!alert_config.applies_to_monitor(&monitor));
+    }
+
     #[test]
     fn test_alert_type_to_string() {
         let alert_type = AlertType::Slack(SlackAlertConfig {
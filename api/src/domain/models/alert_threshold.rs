@@ -0,0 +1,280 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use cron_mon_client::types::AlertThresholdData;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::Job;
+use crate::errors::Error;
+
+pub use cron_mon_client::types::FailureWindow;
+
+/// The `AlertThreshold` struct represents how many late or errored Jobs a Monitor must see before
+/// it alerts on them, so that flaky jobs don't alert on every failure.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "AlertThresholdData")]
+pub struct AlertThreshold {
+    /// The number of consecutive late or errored Jobs needed before alerting.
+    pub consecutive: i32,
+    /// If set, the number of late or errored Jobs that are also needed within the most recent
+    /// Jobs before alerting.
+    pub window: Option<FailureWindow>,
+}
+
+/// The late or errored Jobs that were counted when deciding to alert on a Job.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct FailureCount {
+    /// The number of consecutive late or errored Jobs, up to and including the alerted Job.
+    pub consecutive: i32,
+    /// The number of late or errored Jobs within the Monitor's failure window, if it has one, up
+    /// to and including the alerted Job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<FailureWindow>,
+}
+
+impl TryFrom<AlertThresholdData> for AlertThreshold {
+    type Error = Error;
+
+    fn try_from(value: AlertThresholdData) -> Result<Self, Self::Error> {
+        AlertThreshold::new(value.consecutive, value.window)
+    }
+}
+
+impl Default for AlertThreshold {
+    /// By default, every late or errored Job is alerted on.
+    fn default() -> Self {
+        Self {
+            consecutive: 1,
+            window: None,
+        }
+    }
+}
+
+impl AlertThreshold {
+    /// Instantiate a new AlertThreshold. Note that this will return an `Error` if either of the
+    /// thresholds could never be reached, or would always be reached.
+    pub fn new(consecutive: i32, window: Option<FailureWindow>) -> Result<Self, Error> {
+        if consecutive < 1 {
+            return Err(Error::InvalidMonitor(format!(
+                "Invalid consecutive failure threshold '{consecutive}', must be at least 1"
+            )));
+        }
+
+        if let Some(FailureWindow { failures, runs }) = window {
+            if failures < 1 || runs < failures {
+                return Err(Error::InvalidMonitor(format!(
+                    "Invalid failure window '{failures} of {runs}', must be at least 1 failure \
+                    and no more failures than runs"
+                )));
+            }
+        }
+
+        Ok(Self {
+            consecutive,
+            window,
+        })
+    }
+
+    /// Count the late or errored Jobs towards this threshold, returning the count if the
+    /// threshold has been reached.
+    ///
+    /// `jobs` are expected to be ordered from newest to oldest, starting with the Job being
+    /// considered for alerting, so that only it and the Jobs before it are counted.
+    pub fn evaluate(&self, jobs: &[Job]) -> Option<FailureCount> {
+        let erroneous = |job: &&Job| job.late() || job.errored();
+
+        let consecutive = jobs.iter().take_while(erroneous).count() as i32;
+        let window = self.window.map(|window| {
+            let recent = &jobs[..jobs.len().min(window.runs as usize)];
+            FailureWindow {
+                failures: recent.iter().filter(erroneous).count() as i32,
+                runs: recent.len() as i32,
+            }
+        });
+
+        let window_reached = match (self.window, window) {
+            (Some(threshold), Some(count)) => count.failures >= threshold.failures,
+            _ => true,
+        };
+        if consecutive >= self.consecutive && window_reached {
+            Some(FailureCount {
+                consecutive,
+                window,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for FailureCount {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{} in a row", self.consecutive)?;
+        if let Some(window) = self.window {
+            write!(f, ", {} of the last {}", window.failures, window.runs)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use test_utils::gen_relative_datetime;
+
+    use crate::domain::models::{EndState, JobMetadata};
+
+    use super::*;
+
+    fn gen_job(succeeded: Option<bool>) -> Job {
+        Job {
+            job_id: Uuid::new_v4(),
+            start_time: gen_relative_datetime(-1000),
+            max_end_time: gen_relative_datetime(-500),
+            end_state: succeeded.map(|succeeded| EndState {
+                end_time: gen_relative_datetime(-600),
+                succeeded,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[rstest]
+    #[case::consecutive(1, None)]
+    #[case::consecutive_with_window(3, Some(FailureWindow { failures: 2, runs: 5 }))]
+    #[case::whole_window(1, Some(FailureWindow { failures: 5, runs: 5 }))]
+    fn creating_alert_thresholds(#[case] consecutive: i32, #[case] window: Option<FailureWindow>) {
+        let threshold = AlertThreshold::new(consecutive, window).unwrap();
+
+        assert_eq!(threshold.consecutive, consecutive);
+        assert_eq!(threshold.window, window);
+    }
+
+    #[rstest]
+    #[case(
+        0,
+        None,
+        "Invalid consecutive failure threshold '0', must be at least 1"
+    )]
+    #[case(
+        1,
+        Some(FailureWindow { failures: 0, runs: 5 }),
+        "Invalid failure window '0 of 5', must be at least 1 failure and no more failures than runs"
+    )]
+    #[case(
+        1,
+        Some(FailureWindow { failures: 6, runs: 5 }),
+        "Invalid failure window '6 of 5', must be at least 1 failure and no more failures than runs"
+    )]
+    fn creating_invalid_alert_thresholds(
+        #[case] consecutive: i32,
+        #[case] window: Option<FailureWindow>,
+        #[case] expected_error: &str,
+    ) {
+        assert_eq!(
+            AlertThreshold::new(consecutive, window),
+            Err(Error::InvalidMonitor(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn deserializing_alert_thresholds() {
+        let threshold: AlertThreshold = serde_json::from_value(json!({
+            "consecutive": 2,
+            "window": {"failures": 3, "runs": 10}
+        }))
+        .unwrap();
+        assert_eq!(
+            threshold,
+            AlertThreshold {
+                consecutive: 2,
+                window: Some(FailureWindow {
+                    failures: 3,
+                    runs: 10
+                })
+            }
+        );
+
+        let result = serde_json::from_value::<AlertThreshold>(json!({"consecutive": 0}));
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    // By default, every erroneous job is alerted on.
+    #[case::default(
+        AlertThreshold::default(),
+        vec![Some(false), Some(true)],
+        Some(FailureCount { consecutive: 1, window: None })
+    )]
+    // Late jobs count towards the threshold as well as errored ones.
+    #[case::late_and_errored(
+        AlertThreshold::new(3, None).unwrap(),
+        vec![Some(false), None, Some(false), Some(true)],
+        Some(FailureCount { consecutive: 3, window: None })
+    )]
+    #[case::too_few_consecutive(
+        AlertThreshold::new(3, None).unwrap(),
+        vec![Some(false), Some(false), Some(true), Some(false)],
+        None
+    )]
+    #[case::window_reached(
+        AlertThreshold::new(1, Some(FailureWindow { failures: 2, runs: 3 })).unwrap(),
+        vec![Some(false), Some(true), Some(false), Some(false)],
+        Some(FailureCount {
+            consecutive: 1,
+            window: Some(FailureWindow { failures: 2, runs: 3 })
+        })
+    )]
+    #[case::window_not_reached(
+        AlertThreshold::new(1, Some(FailureWindow { failures: 2, runs: 3 })).unwrap(),
+        vec![Some(false), Some(true), Some(true), Some(false)],
+        None
+    )]
+    // Both thresholds have to be reached when there's a window.
+    #[case::window_reached_but_not_consecutive(
+        AlertThreshold::new(2, Some(FailureWindow { failures: 2, runs: 3 })).unwrap(),
+        vec![Some(false), Some(true), Some(false)],
+        None
+    )]
+    // Windows can't count more jobs than there have been.
+    #[case::short_history(
+        AlertThreshold::new(1, Some(FailureWindow { failures: 2, runs: 5 })).unwrap(),
+        vec![Some(false), Some(false)],
+        Some(FailureCount {
+            consecutive: 2,
+            window: Some(FailureWindow { failures: 2, runs: 2 })
+        })
+    )]
+    fn evaluating_alert_thresholds(
+        #[case] threshold: AlertThreshold,
+        #[case] job_results: Vec<Option<bool>>,
+        #[case] expected_count: Option<FailureCount>,
+    ) {
+        let jobs: Vec<Job> = job_results.into_iter().map(gen_job).collect();
+
+        assert_eq!(threshold.evaluate(&jobs), expected_count);
+    }
+
+    #[rstest]
+    #[case(FailureCount { consecutive: 1, window: None }, "1 in a row")]
+    #[case(
+        FailureCount { consecutive: 2, window: Some(FailureWindow { failures: 3, runs: 5 }) },
+        "2 in a row, 3 of the last 5"
+    )]
+    fn displaying_failure_counts(#[case] count: FailureCount, #[case] expected: &str) {
+        assert_eq!(count.to_string(), expected);
+    }
+}
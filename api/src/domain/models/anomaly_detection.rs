@@ -0,0 +1,297 @@
+use cron_mon_client::types::AnomalyDetectionData;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::{DurationStats, Job};
+use crate::errors::Error;
+
+/// The `AnomalyDetection` struct represents how a Monitor detects Jobs that succeeded, but took a
+/// suspiciously short or long time to do so - such as a backup that "succeeds" in seconds rather
+/// than minutes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "AnomalyDetectionData")]
+pub struct AnomalyDetection {
+    /// If set, Jobs that take less than this many seconds are anomalous.
+    pub min_duration: Option<i32>,
+    /// If set, Jobs that take more than this many standard deviations more or less than the
+    /// median of the Jobs before them are anomalous.
+    pub max_deviations: Option<f64>,
+}
+
+/// The `DurationAnomaly` struct describes a Job that succeeded, but took an anomalous amount of
+/// time to do so.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DurationAnomaly {
+    /// How long the Job took, in seconds.
+    pub duration: u64,
+    /// The shortest the Job was expected to take, in seconds.
+    pub min_duration: u64,
+    /// The longest the Job was expected to take, in seconds, if it was expected to take no longer
+    /// than a certain time.
+    pub max_duration: Option<u64>,
+}
+
+impl TryFrom<AnomalyDetectionData> for AnomalyDetection {
+    type Error = Error;
+
+    fn try_from(value: AnomalyDetectionData) -> Result<Self, Self::Error> {
+        AnomalyDetection::new(value.min_duration, value.max_deviations)
+    }
+}
+
+impl AnomalyDetection {
+    /// Instantiate a new AnomalyDetection. Note that this will return an `Error` if neither a
+    /// minimum duration or maximum deviations are given, or if either isn't positive.
+    pub fn new(min_duration: Option<i32>, max_deviations: Option<f64>) -> Result<Self, Error> {
+        if min_duration.is_none() && max_deviations.is_none() {
+            return Err(Error::InvalidMonitor(
+                "Anomaly detection requires a minimum duration or maximum deviations".to_owned(),
+            ));
+        }
+        if let Some(min_duration) = min_duration.filter(|min| *min <= 0) {
+            return Err(Error::InvalidMonitor(format!(
+                "Invalid minimum duration '{min_duration}', must be greater than 0"
+            )));
+        }
+        if let Some(max_deviations) = max_deviations.filter(|max| !(max.is_finite() && *max > 0.0))
+        {
+            return Err(Error::InvalidMonitor(format!(
+                "Invalid maximum deviations '{max_deviations}', must be greater than 0"
+            )));
+        }
+
+        Ok(Self {
+            min_duration,
+            max_deviations,
+        })
+    }
+
+    /// Check whether a Job that succeeded took an anomalous amount of time, given statistics
+    /// describing the Jobs that came before it, if there are any.
+    ///
+    /// Jobs that took too long, but that finished late, aren't considered anomalous, since they
+    /// have already been alerted on for being late.
+    pub fn check(&self, job: &Job, stats: Option<&DurationStats>) -> Option<DurationAnomaly> {
+        if !job.succeeded() {
+            return None;
+        }
+        let duration = job.duration()?;
+
+        let expected_range = self
+            .max_deviations
+            .zip(stats)
+            .and_then(|(max_deviations, stats)| stats.expected_range(max_deviations));
+        let min_duration = self
+            .min_duration
+            .map_or(0, |min_duration| min_duration as u64)
+            .max(expected_range.map_or(0, |(min, _)| min));
+        let max_duration = expected_range.map(|(_, max)| max);
+
+        let too_short = duration < min_duration;
+        let too_long = max_duration.is_some_and(|max| duration > max) && !job.late();
+        (too_short || too_long).then_some(DurationAnomaly {
+            duration,
+            min_duration,
+            max_duration,
+        })
+    }
+}
+
+impl DurationAnomaly {
+    /// Ascertain whether the Job finished sooner than expected, rather than taking too long.
+    pub fn too_short(&self) -> bool {
+        self.duration < self.min_duration
+    }
+
+    /// Describe how long the Job was expected to take, i.e "between 60s and 120s".
+    pub fn expected(&self) -> String {
+        match self.max_duration {
+            Some(max_duration) => format!("between {}s and {max_duration}s", self.min_duration),
+            None => format!("at least {}s", self.min_duration),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use rstest::rstest;
+    use serde_json::json;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{EndState, JobMetadata};
+
+    use super::*;
+
+    fn gen_job(duration: i64, max_duration: i64, succeeded: bool) -> Job {
+        let start_time = gen_datetime("2024-05-01T12:00:00");
+        Job {
+            job_id: gen_uuid("2b2fb6a0-5f2b-4f5c-9d0e-58e0d6d7b1f4"),
+            start_time,
+            max_end_time: start_time + Duration::seconds(max_duration),
+            end_state: Some(EndState {
+                end_time: start_time + Duration::seconds(duration),
+                succeeded,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    fn gen_stats(job_count: usize) -> DurationStats {
+        DurationStats {
+            job_count,
+            p50: 1200,
+            p95: 1300,
+            p99: 1350,
+            max: 1400,
+            std_dev: 60.0,
+        }
+    }
+
+    #[rstest]
+    #[case(Some(60), None)]
+    #[case(None, Some(3.0))]
+    #[case(Some(60), Some(0.5))]
+    fn creating_anomaly_detections(
+        #[case] min_duration: Option<i32>,
+        #[case] max_deviations: Option<f64>,
+    ) {
+        let detection = AnomalyDetection::new(min_duration, max_deviations).unwrap();
+
+        assert_eq!(detection.min_duration, min_duration);
+        assert_eq!(detection.max_deviations, max_deviations);
+    }
+
+    #[rstest]
+    #[case(
+        None,
+        None,
+        "Invalid Monitor: Anomaly detection requires a minimum duration or maximum deviations"
+    )]
+    #[case(
+        Some(0),
+        None,
+        "Invalid Monitor: Invalid minimum duration '0', must be greater than 0"
+    )]
+    #[case(
+        None,
+        Some(-1.0),
+        "Invalid Monitor: Invalid maximum deviations '-1', must be greater than 0"
+    )]
+    #[case(
+        Some(60),
+        Some(f64::NAN),
+        "Invalid Monitor: Invalid maximum deviations 'NaN', must be greater than 0"
+    )]
+    fn creating_invalid_anomaly_detections(
+        #[case] min_duration: Option<i32>,
+        #[case] max_deviations: Option<f64>,
+        #[case] expected_error: &str,
+    ) {
+        let error = AnomalyDetection::new(min_duration, max_deviations).unwrap_err();
+
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    #[rstest]
+    #[case::long_enough(Some(600), None, 1200, None)]
+    #[case::too_short(Some(600), None, 2, Some((600, None)))]
+    #[case::within_deviations(None, Some(3.0), 1300, None)]
+    #[case::below_deviations(None, Some(3.0), 900, Some((1020, Some(1380))))]
+    #[case::above_deviations(None, Some(3.0), 1500, Some((1020, Some(1380))))]
+    #[case::minimum_above_deviations(Some(1100), Some(3.0), 1050, Some((1100, Some(1380))))]
+    fn checking_jobs_for_anomalies(
+        #[case] min_duration: Option<i32>,
+        #[case] max_deviations: Option<f64>,
+        #[case] duration: i64,
+        #[case] expected: Option<(u64, Option<u64>)>,
+    ) {
+        let detection = AnomalyDetection::new(min_duration, max_deviations).unwrap();
+
+        assert_eq!(
+            detection.check(&gen_job(duration, 3600, true), Some(&gen_stats(100))),
+            expected.map(|(min_duration, max_duration)| DurationAnomaly {
+                duration: duration as u64,
+                min_duration,
+                max_duration,
+            })
+        );
+    }
+
+    #[test]
+    fn checking_jobs_for_anomalies_without_enough_history() {
+        let detection = AnomalyDetection::new(None, Some(3.0)).unwrap();
+        let job = gen_job(2, 3600, true);
+
+        assert_eq!(detection.check(&job, None), None);
+        assert_eq!(detection.check(&job, Some(&gen_stats(9))), None);
+    }
+
+    #[test]
+    fn checking_failed_and_late_jobs_for_anomalies() {
+        let detection = AnomalyDetection::new(Some(600), Some(3.0)).unwrap();
+
+        // Failed jobs are alerted on as errored, and late jobs as late, instead.
+        assert_eq!(
+            detection.check(&gen_job(2, 3600, false), Some(&gen_stats(100))),
+            None
+        );
+        assert_eq!(
+            detection.check(&gen_job(1500, 1400, true), Some(&gen_stats(100))),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case(DurationAnomaly { duration: 2, min_duration: 600, max_duration: None }, true, "at least 600s")]
+    #[case(
+        DurationAnomaly { duration: 1500, min_duration: 1020, max_duration: Some(1380) },
+        false,
+        "between 1020s and 1380s"
+    )]
+    fn describing_anomalies(
+        #[case] anomaly: DurationAnomaly,
+        #[case] too_short: bool,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(anomaly.too_short(), too_short);
+        assert_eq!(anomaly.expected(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        json!({"min_duration": 600}),
+        Ok(AnomalyDetection::new(Some(600), None).unwrap())
+    )]
+    #[case(
+        json!({"min_duration": 600, "max_deviations": 2.5}),
+        Ok(AnomalyDetection::new(Some(600), Some(2.5)).unwrap())
+    )]
+    #[case(
+        json!({}),
+        Err(
+            "Invalid Monitor: Anomaly detection requires a minimum duration or maximum deviations"
+                .to_owned()
+        )
+    )]
+    fn deserializing_anomaly_detections(
+        #[case] value: serde_json::Value,
+        #[case] expected: Result<AnomalyDetection, String>,
+    ) {
+        let result =
+            serde_json::from_value::<AnomalyDetection>(value).map_err(|error| error.to_string());
+
+        assert_eq!(result, expected);
+    }
+}
@@ -1,4 +1,6 @@
-use chrono::{NaiveDateTime, Utc};
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+use rand::distr::Alphanumeric;
+use rand::Rng;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
@@ -6,6 +8,8 @@ use uuid::Uuid;
 use super::Monitor;
 use crate::errors::Error;
 
+pub use cron_mon_client::types::ApiKeyScope;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ApiKey {
     /// The unique identifier for the API key.
@@ -26,6 +30,12 @@ pub struct ApiKey {
     pub last_used_monitor_id: Option<Uuid>,
     /// The name of the monitor that last used the API key.
     pub last_used_monitor_name: Option<String>,
+    /// What the API key can be used for.
+    pub scopes: Vec<ApiKeyScope>,
+    /// The only monitors that the API key can be used for, if it's restricted to some.
+    pub monitor_ids: Option<Vec<Uuid>>,
+    /// The time the API key expires, if it does.
+    pub expires_at: Option<NaiveDateTime>,
 }
 
 impl ApiKey {
@@ -35,8 +45,35 @@ impl ApiKey {
         format!("{:x}", hasher.finalize())
     }
 
+    /// Generate a random API key value.
+    pub fn random_key() -> String {
+        rand::rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect()
+    }
+
+    /// Check that an API key would be usable for something with the given scopes.
+    pub fn validate_scopes(scopes: &[ApiKeyScope]) -> Result<(), Error> {
+        if scopes.is_empty() {
+            Err(Error::InvalidApiKey(
+                "An API key must have at least one scope".to_owned(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Create a new API key.
-    pub fn new(name: String, key: String, tenant: String) -> Self {
+    pub fn new(
+        name: String,
+        key: String,
+        tenant: String,
+        scopes: Vec<ApiKeyScope>,
+        monitor_ids: Option<Vec<Uuid>>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Self {
         // Create a masked version of the key.
         let key_len = key.len();
         let mask_len = if key_len < 5 { key_len } else { 5 };
@@ -56,7 +93,63 @@ impl ApiKey {
             last_used: None,
             last_used_monitor_id: None,
             last_used_monitor_name: None,
+            scopes,
+            monitor_ids,
+            expires_at,
+        }
+    }
+
+    /// Check that the API key hasn't expired.
+    pub fn check_expiry(&self) -> Result<(), Error> {
+        match self.expires_at {
+            Some(expires_at) if expires_at <= Utc::now().naive_utc() => {
+                Err(Error::ApiKeyExpired(expires_at))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Create a replacement for the API key, using the new `key`, and expire this API key once
+    /// `overlap` has passed so that it can be phased out. The replacement has the same name, scopes
+    /// and monitors, and if this API key expires, the replacement lasts for as long as it did.
+    pub fn rotate(&mut self, key: String, overlap: TimeDelta) -> ApiKey {
+        let now = Utc::now().naive_utc();
+        let replacement = ApiKey::new(
+            self.name.clone(),
+            key,
+            self.tenant.clone(),
+            self.scopes.clone(),
+            self.monitor_ids.clone(),
+            self.expires_at
+                .map(|expires_at| now + (expires_at - self.created)),
+        );
+
+        let overlap_end = now + overlap;
+        self.expires_at = Some(match self.expires_at {
+            Some(expires_at) => expires_at.min(overlap_end),
+            None => overlap_end,
+        });
+
+        replacement
+    }
+
+    /// Check that the API key can be used for the given scope, on the given monitor.
+    pub fn check_access(&self, scope: ApiKeyScope, monitor_id: Uuid) -> Result<(), Error> {
+        if !self.scopes.contains(&scope) {
+            return Err(Error::Forbidden(format!(
+                "API key does not have the '{scope}' scope"
+            )));
         }
+
+        if let Some(monitor_ids) = &self.monitor_ids {
+            if !monitor_ids.contains(&monitor_id) {
+                return Err(Error::Forbidden(format!(
+                    "API key cannot be used for Monitor('{monitor_id}')"
+                )));
+            }
+        }
+
+        Ok(())
     }
 
     pub fn record_usage(&mut self, monitor: &Monitor) -> Result<(), Error> {
@@ -88,15 +181,21 @@ impl Serialize for ApiKey {
             api_key_id: Uuid,
             name: String,
             masked: String,
+            scopes: Vec<ApiKeyScope>,
+            monitor_ids: Option<Vec<Uuid>>,
             last_used: Option<LastUsed>,
             created: NaiveDateTime,
+            expires_at: Option<NaiveDateTime>,
         }
 
         Key {
             api_key_id: self.api_key_id,
             name: self.name.clone(),
             masked: self.masked.clone(),
+            scopes: self.scopes.clone(),
+            monitor_ids: self.monitor_ids.clone(),
             created: self.created,
+            expires_at: self.expires_at,
             last_used: self.last_used.map(|time| LastUsed {
                 time,
                 monitor_id: self.last_used_monitor_id.unwrap(),
@@ -114,7 +213,9 @@ mod tests {
     use rstest::rstest;
     use serde_json::{json, Value};
 
-    use test_utils::{gen_datetime, gen_uuid};
+    use test_utils::{gen_datetime, gen_relative_datetime, gen_uuid};
+
+    use crate::domain::models::{AlertThreshold, ConcurrencyPolicy, Labels, MonitorKind};
 
     use super::*;
 
@@ -132,6 +233,9 @@ mod tests {
             "Some key".to_owned(),
             "YWI0Y2FkMTAtMmJmZi00MjMyLWE5MTEtNzQyZWU0NjY4ZjI1Cg==".to_owned(),
             "tenant".to_owned(),
+            ApiKeyScope::ALL.to_vec(),
+            None,
+            None,
         );
 
         // No need to check the api_key_id as it is a randomly generated UUID (and we know it's a
@@ -148,12 +252,26 @@ mod tests {
         assert_eq!(api_key.last_used_monitor_name, None);
     }
 
+    #[rstest]
+    #[case::all_scopes(ApiKeyScope::ALL.to_vec(), Ok(()))]
+    #[case::one_scope(vec![ApiKeyScope::JobsWrite], Ok(()))]
+    #[case::no_scopes(
+        vec![],
+        Err(Error::InvalidApiKey("An API key must have at least one scope".to_owned()))
+    )]
+    fn test_validate_scopes(#[case] scopes: Vec<ApiKeyScope>, #[case] expected: Result<(), Error>) {
+        assert_eq!(ApiKey::validate_scopes(&scopes), expected);
+    }
+
     #[tokio::test(start_paused = true)]
     async fn test_record_usage() {
         let mut key = ApiKey::new(
             "Some key".to_owned(),
             "test".to_owned(),
             "tenant".to_owned(),
+            ApiKeyScope::ALL.to_vec(),
+            None,
+            None,
         );
 
         let monitor = Monitor {
@@ -162,6 +280,18 @@ mod tests {
             name: "foo".to_owned(),
             expected_duration: 300,
             grace_duration: 10,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
             jobs: vec![],
         };
 
@@ -177,6 +307,157 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case::any_monitor(ApiKeyScope::JobsWrite, None)]
+    #[case::allowed_monitor(
+        ApiKeyScope::HeartbeatsWrite,
+        Some(vec![gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")])
+    )]
+    fn test_check_access(#[case] scope: ApiKeyScope, #[case] monitor_ids: Option<Vec<Uuid>>) {
+        let key = ApiKey::new(
+            "Some key".to_owned(),
+            "test".to_owned(),
+            "tenant".to_owned(),
+            vec![scope],
+            monitor_ids,
+            None,
+        );
+
+        assert_eq!(
+            key.check_access(scope, gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    #[case::missing_scope(
+        vec![ApiKeyScope::HeartbeatsWrite],
+        None,
+        "API key does not have the 'jobs:write' scope"
+    )]
+    #[case::other_monitor(
+        vec![ApiKeyScope::JobsWrite],
+        Some(vec![gen_uuid("eae3eb0b-350d-4783-bf9a-82ccc6cb0365")]),
+        "API key cannot be used for Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3')"
+    )]
+    #[case::no_monitors(vec![ApiKeyScope::JobsWrite], Some(vec![]), "API key cannot be used for Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3')")]
+    fn test_check_access_forbidden(
+        #[case] scopes: Vec<ApiKeyScope>,
+        #[case] monitor_ids: Option<Vec<Uuid>>,
+        #[case] reason: &str,
+    ) {
+        let key = ApiKey::new(
+            "Some key".to_owned(),
+            "test".to_owned(),
+            "tenant".to_owned(),
+            scopes,
+            monitor_ids,
+            None,
+        );
+
+        assert_eq!(
+            key.check_access(
+                ApiKeyScope::JobsWrite,
+                gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")
+            ),
+            Err(Error::Forbidden(reason.to_owned()))
+        );
+    }
+
+    #[rstest]
+    #[case::never_expires(None)]
+    #[case::not_expired(Some(gen_relative_datetime(60)))]
+    fn test_check_expiry(#[case] expires_at: Option<NaiveDateTime>) {
+        let key = ApiKey::new(
+            "Some key".to_owned(),
+            "test".to_owned(),
+            "tenant".to_owned(),
+            ApiKeyScope::ALL.to_vec(),
+            None,
+            expires_at,
+        );
+
+        assert_eq!(key.check_expiry(), Ok(()));
+    }
+
+    #[test]
+    fn test_check_expiry_expired() {
+        let expires_at = gen_relative_datetime(-60);
+        let key = ApiKey::new(
+            "Some key".to_owned(),
+            "test".to_owned(),
+            "tenant".to_owned(),
+            ApiKeyScope::ALL.to_vec(),
+            None,
+            Some(expires_at),
+        );
+
+        assert_eq!(key.check_expiry(), Err(Error::ApiKeyExpired(expires_at)));
+    }
+
+    #[rstest]
+    // Keys that never expire do so once the overlap has passed, and their replacements never do.
+    #[case::never_expires(None, 600, None)]
+    // Keys that expire after the overlap are brought forward, and their replacements last as long
+    // as they did (a day).
+    #[case::expires_after_overlap(Some(82800), 600, Some(86400))]
+    // Keys that expire before the overlap are left as they are.
+    #[case::expires_before_overlap(Some(300), 600, Some(86400))]
+    fn test_rotate(
+        #[case] expires_in: Option<i64>,
+        #[case] overlap: i64,
+        #[case] replacement_expires_in: Option<i64>,
+    ) {
+        let mut key = ApiKey::new(
+            "Some key".to_owned(),
+            "test".to_owned(),
+            "tenant".to_owned(),
+            vec![ApiKeyScope::JobsWrite],
+            Some(vec![gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")]),
+            None,
+        );
+        // Make the key a day old when it expires, wherever that is relative to now.
+        key.expires_at = expires_in.map(gen_relative_datetime);
+        key.created = key
+            .expires_at
+            .map_or(key.created, |expires_at| expires_at - TimeDelta::days(1));
+        let original_expiry = key.expires_at;
+
+        let replacement = key.rotate("new-key".to_owned(), TimeDelta::seconds(overlap));
+
+        assert_ne!(replacement.api_key_id, key.api_key_id);
+        assert_eq!(replacement.key, ApiKey::hash_key("new-key"));
+        assert_eq!(replacement.name, "Some key");
+        assert_eq!(replacement.tenant, "tenant");
+        assert_eq!(replacement.scopes, vec![ApiKeyScope::JobsWrite]);
+        assert_eq!(
+            replacement.monitor_ids,
+            Some(vec![gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3")])
+        );
+        assert_close(
+            replacement.expires_at,
+            replacement_expires_in.map(gen_relative_datetime),
+        );
+
+        let overlap_end = gen_relative_datetime(overlap);
+        match original_expiry {
+            Some(expiry) if expiry < overlap_end => assert_eq!(key.expires_at, Some(expiry)),
+            _ => assert_close(key.expires_at, Some(overlap_end)),
+        }
+    }
+
+    fn assert_close(actual: Option<NaiveDateTime>, expected: Option<NaiveDateTime>) {
+        match (actual, expected) {
+            (Some(actual), Some(expected)) => {
+                assert!(
+                    (actual - expected).num_seconds().abs() < 5,
+                    "{actual} != {expected}"
+                )
+            }
+            (actual, expected) => assert_eq!(actual, expected),
+        }
+    }
+
     #[rstest]
     #[case::not_used(ApiKey {
         api_key_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
@@ -188,11 +469,17 @@ mod tests {
         last_used: None,
         last_used_monitor_id: None,
         last_used_monitor_name: None,
+        scopes: ApiKeyScope::ALL.to_vec(),
+        monitor_ids: None,
+        expires_at: None,
     }, json!({
         "api_key_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
         "name": "Some key",
         "masked": "YWI0Y************1Cg==",
+        "scopes": ["jobs:write", "heartbeats:write"],
+        "monitor_ids": Value::Null,
         "created": "2024-10-27T21:28:00",
+        "expires_at": Value::Null,
         "last_used": Value::Null,
     }))]
     #[case::used(ApiKey {
@@ -205,11 +492,17 @@ mod tests {
         last_used: Some(gen_datetime("2024-10-27T21:28:00")),
         last_used_monitor_id: Some(gen_uuid("eae3eb0b-350d-4783-bf9a-82ccc6cb0365")),
         last_used_monitor_name: Some("Foo monitor".to_owned()),
+        scopes: vec![ApiKeyScope::JobsWrite],
+        monitor_ids: Some(vec![gen_uuid("eae3eb0b-350d-4783-bf9a-82ccc6cb0365")]),
+        expires_at: Some(gen_datetime("2025-10-27T21:28:00")),
     }, json!({
         "api_key_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
         "name": "Some key",
         "masked": "YWI0Y************1Cg==",
+        "scopes": ["jobs:write"],
+        "monitor_ids": ["eae3eb0b-350d-4783-bf9a-82ccc6cb0365"],
         "created": "2024-10-27T21:28:00",
+        "expires_at": "2025-10-27T21:28:00",
         "last_used": {
             "time": "2024-10-27T21:28:00",
             "monitor_id": "eae3eb0b-350d-4783-bf9a-82ccc6cb0365",
@@ -0,0 +1,591 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::domain::models::{AlertConfig, ApiKey, Monitor, WebhookSubscription};
+
+/// What secrets are replaced with in snapshots, since any of a tenant's users can read the audit
+/// log.
+const REDACTED: &str = "***";
+
+/// The `AuditEvent` struct records a change to a tenant's configuration - who made it, what they
+/// changed, and what it looked like before and after.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AuditEvent {
+    /// The unique identifier for the event.
+    pub audit_event_id: Uuid,
+    /// The tenant whose configuration was changed.
+    #[serde(skip_serializing)]
+    pub tenant: String,
+    /// The user that made the change.
+    pub actor: Actor,
+    /// What the user did.
+    pub action: AuditAction,
+    /// The entity that was changed.
+    pub entity: AuditEntity,
+    /// A snapshot of the entity before it was changed, unless it was created.
+    pub before: Option<Value>,
+    /// A snapshot of the entity after it was changed, unless it was deleted.
+    pub after: Option<Value>,
+    /// When the change was made.
+    pub timestamp: NaiveDateTime,
+}
+
+/// The user behind an audited change.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Actor {
+    /// The user's ID, from the `sub` claim of their token.
+    pub id: String,
+    /// The user's name.
+    pub name: String,
+}
+
+/// The kinds of change that are audited.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// The types of entity whose changes are audited.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    Monitor,
+    AlertConfig,
+    ApiKey,
+    WebhookSubscription,
+}
+
+/// The entity that an audited change was made to.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AuditEntity {
+    /// The type of the entity.
+    #[serde(rename = "type")]
+    pub type_: EntityType,
+    /// The entity's ID. Note that deleted entities no longer exist.
+    pub id: Uuid,
+}
+
+/// Entities whose changes are audited.
+pub trait Audited {
+    /// The entity that this is.
+    fn audit_entity(&self) -> AuditEntity;
+
+    /// The tenant that the entity belongs to.
+    fn audit_tenant(&self) -> &str;
+
+    /// A snapshot of the entity, as it's recorded in the audit log.
+    fn audit_snapshot(&self) -> Value;
+}
+
+impl AuditEvent {
+    /// Record that an entity was created.
+    pub fn created(actor: &Actor, after: &impl Audited) -> Self {
+        Self::new(actor, AuditAction::Created, None, Some(after))
+    }
+
+    /// Record that an entity was updated.
+    pub fn updated<T: Audited>(actor: &Actor, before: &T, after: &T) -> Self {
+        Self::new(actor, AuditAction::Updated, Some(before), Some(after))
+    }
+
+    /// Record that an entity was deleted.
+    pub fn deleted(actor: &Actor, before: &impl Audited) -> Self {
+        Self::new(actor, AuditAction::Deleted, Some(before), None)
+    }
+
+    fn new<T: Audited>(
+        actor: &Actor,
+        action: AuditAction,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Self {
+        // At least one of the snapshots is always given.
+        let entity = before.or(after).unwrap();
+
+        Self {
+            audit_event_id: Uuid::new_v4(),
+            tenant: entity.audit_tenant().to_owned(),
+            actor: actor.clone(),
+            action,
+            entity: entity.audit_entity(),
+            before: before.map(Audited::audit_snapshot),
+            after: after.map(Audited::audit_snapshot),
+            timestamp: Utc::now().naive_utc(),
+        }
+    }
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Deleted => "deleted",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Created, Self::Updated, Self::Deleted]
+            .into_iter()
+            .find(|action| action.as_str() == name)
+    }
+}
+
+impl EntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Monitor => "monitor",
+            Self::AlertConfig => "alert_config",
+            Self::ApiKey => "api_key",
+            Self::WebhookSubscription => "webhook_subscription",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Monitor,
+            Self::AlertConfig,
+            Self::ApiKey,
+            Self::WebhookSubscription,
+        ]
+        .into_iter()
+        .find(|entity_type| entity_type.as_str() == name)
+    }
+}
+
+impl Audited for Monitor {
+    fn audit_entity(&self) -> AuditEntity {
+        AuditEntity {
+            type_: EntityType::Monitor,
+            id: self.monitor_id,
+        }
+    }
+
+    fn audit_tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        // A Monitor's Jobs aren't part of its configuration.
+        let mut snapshot = serde_json::to_value(self).unwrap();
+        snapshot.as_object_mut().unwrap().remove("jobs");
+        snapshot
+    }
+}
+
+impl Audited for AlertConfig {
+    fn audit_entity(&self) -> AuditEntity {
+        AuditEntity {
+            type_: EntityType::AlertConfig,
+            id: self.alert_config_id,
+        }
+    }
+
+    fn audit_tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        let mut snapshot = serde_json::to_value(self).unwrap();
+        for pointer in [
+            "/type/slack/token",
+            "/type/webhook/secret",
+            "/type/email/smtp/password",
+            "/type/pagerduty/routing_key",
+        ] {
+            redact(&mut snapshot, pointer);
+        }
+        // Webhook headers often carry credentials, such as an `Authorization` header.
+        if let Some(Value::Object(headers)) = snapshot.pointer_mut("/type/webhook/headers") {
+            for value in headers.values_mut() {
+                *value = json!(REDACTED);
+            }
+        }
+        snapshot
+    }
+}
+
+impl Audited for ApiKey {
+    fn audit_entity(&self) -> AuditEntity {
+        AuditEntity {
+            type_: EntityType::ApiKey,
+            id: self.api_key_id,
+        }
+    }
+
+    fn audit_tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        // Usage isn't part of a key's configuration, and the key itself is never included.
+        let mut snapshot = serde_json::to_value(self).unwrap();
+        snapshot.as_object_mut().unwrap().remove("last_used");
+        snapshot
+    }
+}
+
+impl Audited for WebhookSubscription {
+    fn audit_entity(&self) -> AuditEntity {
+        AuditEntity {
+            type_: EntityType::WebhookSubscription,
+            id: self.subscription_id,
+        }
+    }
+
+    fn audit_tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        // The secret itself is never serialized, only whether it's set.
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// Replace the secret at the given JSON pointer within a snapshot, if it's set. Secrets that
+/// aren't set are left as they are, so that the audit log still shows when one is added or
+/// removed.
+fn redact(snapshot: &mut Value, pointer: &str) {
+    if let Some(value) = snapshot.pointer_mut(pointer) {
+        if !value.is_null() {
+            *value = json!(REDACTED);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use std::collections::BTreeMap;
+
+    use crate::domain::models::{
+        AlertThreshold, AlertType, ApiKeyScope, ConcurrencyPolicy, EmailAlertConfig, Labels,
+        MonitorKind, PagerDutyAlertConfig, SlackAlertConfig, SmtpConfig, SmtpTls,
+        WebhookAlertConfig, WebhookMethod,
+    };
+
+    use super::*;
+
+    fn actor() -> Actor {
+        Actor {
+            id: "1234567890".to_owned(),
+            name: "John Doe".to_owned(),
+        }
+    }
+
+    fn monitor(grace_duration: i32) -> Monitor {
+        Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_created() {
+        let event = AuditEvent::created(&actor(), &monitor(100));
+
+        assert_eq!(event.tenant, "tenant");
+        assert_eq!(event.actor, actor());
+        assert_eq!(event.action, AuditAction::Created);
+        assert_eq!(
+            event.entity,
+            AuditEntity {
+                type_: EntityType::Monitor,
+                id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            }
+        );
+        assert_eq!(event.before, None);
+        assert_eq!(event.after.unwrap()["grace_duration"], json!(100));
+    }
+
+    #[test]
+    fn test_updated() {
+        let event = AuditEvent::updated(&actor(), &monitor(100), &monitor(200));
+
+        assert_eq!(event.action, AuditAction::Updated);
+        assert_eq!(event.before.unwrap()["grace_duration"], json!(100));
+        assert_eq!(event.after.unwrap()["grace_duration"], json!(200));
+    }
+
+    #[test]
+    fn test_deleted() {
+        let event = AuditEvent::deleted(&actor(), &monitor(100));
+
+        assert_eq!(event.action, AuditAction::Deleted);
+        assert_eq!(event.before.unwrap()["grace_duration"], json!(100));
+        assert_eq!(event.after, None);
+    }
+
+    #[test]
+    fn test_monitor_snapshot() {
+        let mut monitor = monitor(100);
+        monitor.start_job().unwrap();
+
+        assert_eq!(
+            monitor.audit_snapshot(),
+            json!({
+                "monitor_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                "name": "foo",
+                "expected_duration": 300,
+                "grace_duration": 100,
+                "kind": {"type": "job"},
+                "schedule": null,
+                "alert_threshold": {"consecutive": 1, "window": null},
+                "retention": null,
+                "labels": {},
+                "paused": false,
+                "resume_at": null,
+                "maintenance_windows": [],
+                "auto_tune": null,
+                "anomaly_detection": null,
+                "concurrency_policy": "allow"
+            })
+        );
+    }
+
+    fn alert_config(type_: AlertType) -> AlertConfig {
+        AlertConfig {
+            alert_config_id: gen_uuid("fadd7266-648b-4102-8f85-c768655f4297"),
+            name: "Alerts".to_owned(),
+            tenant: "tenant".to_owned(),
+            active: true,
+            on_late: true,
+            on_error: false,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
+            type_,
+            monitors: vec![],
+        }
+    }
+
+    #[test]
+    fn test_alert_config_snapshot() {
+        let alert_config = alert_config(AlertType::Slack(SlackAlertConfig {
+            channel: "#alerts".to_owned(),
+            token: "s3cr3t-token".to_owned(),
+        }));
+
+        assert_eq!(
+            alert_config.audit_entity(),
+            AuditEntity {
+                type_: EntityType::AlertConfig,
+                id: gen_uuid("fadd7266-648b-4102-8f85-c768655f4297"),
+            }
+        );
+        assert_eq!(
+            alert_config.audit_snapshot(),
+            json!({
+                "alert_config_id": "fadd7266-648b-4102-8f85-c768655f4297",
+                "name": "Alerts",
+                "active": true,
+                "on_late": true,
+                "on_error": false,
+                "on_recovery": false,
+                "on_anomaly": false,
+                "label_selector": null,
+                "type": {"slack": {"channel": "#alerts", "token": "***"}},
+                "monitors": []
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::slack(
+        AlertType::Slack(SlackAlertConfig {
+            channel: "#alerts".to_owned(),
+            token: "s3cr3t-token".to_owned(),
+        }),
+        json!({"slack": {"channel": "#alerts", "token": "***"}})
+    )]
+    #[case::webhook(
+        AlertType::Webhook(WebhookAlertConfig {
+            url: "https://example.com/hook".to_owned(),
+            method: WebhookMethod::Post,
+            headers: BTreeMap::from([(
+                "Authorization".to_owned(),
+                "Bearer s3cr3t-header".to_owned(),
+            )]),
+            secret: Some("s3cr3t-hmac".to_owned()),
+        }),
+        json!({"webhook": {
+            "url": "https://example.com/hook",
+            "method": "POST",
+            "headers": {"Authorization": "***"},
+            "secret": "***"
+        }})
+    )]
+    #[case::webhook_without_secret(
+        AlertType::Webhook(WebhookAlertConfig {
+            url: "https://example.com/hook".to_owned(),
+            method: WebhookMethod::Post,
+            headers: BTreeMap::new(),
+            secret: None,
+        }),
+        json!({"webhook": {
+            "url": "https://example.com/hook",
+            "method": "POST",
+            "headers": {},
+            "secret": null
+        }})
+    )]
+    #[case::email(
+        AlertType::Email(EmailAlertConfig {
+            recipients: vec!["oncall@example.com".to_owned()],
+            smtp: Some(SmtpConfig {
+                host: "smtp.example.com".to_owned(),
+                port: 587,
+                username: Some("cron-mon".to_owned()),
+                password: Some("s3cr3t-password".to_owned()),
+                from: "cron-mon@example.com".to_owned(),
+                tls: SmtpTls::default(),
+            }),
+        }),
+        json!({"email": {
+            "recipients": ["oncall@example.com"],
+            "smtp": {
+                "host": "smtp.example.com",
+                "port": 587,
+                "username": "cron-mon",
+                "password": "***",
+                "from": "cron-mon@example.com",
+                "tls": serde_json::to_value(SmtpTls::default()).unwrap()
+            }
+        }})
+    )]
+    #[case::email_with_server_relay(
+        AlertType::Email(EmailAlertConfig {
+            recipients: vec!["oncall@example.com".to_owned()],
+            smtp: None,
+        }),
+        json!({"email": {"recipients": ["oncall@example.com"], "smtp": null}})
+    )]
+    #[case::pagerduty(
+        AlertType::PagerDuty(PagerDutyAlertConfig {
+            routing_key: "s3cr3t-routing-key".to_owned(),
+        }),
+        json!({"pagerduty": {"routing_key": "***"}})
+    )]
+    fn test_alert_config_snapshot_redacts_secrets(
+        #[case] type_: AlertType,
+        #[case] expected_type: Value,
+    ) {
+        let snapshot = alert_config(type_).audit_snapshot();
+
+        assert_eq!(snapshot["type"], expected_type);
+        assert!(!snapshot.to_string().contains("s3cr3t"));
+    }
+
+    #[rstest]
+    #[case(Some("s3cr3t-hmac".to_owned()), true)]
+    #[case(None, false)]
+    fn test_webhook_subscription_snapshot(
+        #[case] secret: Option<String>,
+        #[case] has_secret: bool,
+    ) {
+        let subscription = WebhookSubscription {
+            subscription_id: gen_uuid("b0b9d1c4-7a4e-4f2b-9a55-3f4f1c2d6e8a"),
+            tenant: "tenant".to_owned(),
+            name: "Events".to_owned(),
+            url: "https://example.com/events".to_owned(),
+            secret,
+            event_types: vec![],
+            active: true,
+        };
+
+        let snapshot = subscription.audit_snapshot();
+
+        assert_eq!(
+            snapshot,
+            json!({
+                "subscription_id": "b0b9d1c4-7a4e-4f2b-9a55-3f4f1c2d6e8a",
+                "name": "Events",
+                "url": "https://example.com/events",
+                "has_secret": has_secret,
+                "event_types": [],
+                "active": true
+            })
+        );
+        assert!(!snapshot.to_string().contains("s3cr3t"));
+    }
+
+    #[test]
+    fn test_api_key_snapshot() {
+        let mut api_key = ApiKey::new(
+            "Test key".to_owned(),
+            "foo-key".to_owned(),
+            "tenant".to_owned(),
+            vec![ApiKeyScope::JobsWrite],
+            None,
+            Some(gen_datetime("2025-01-01T00:00:00")),
+        );
+        api_key.record_usage(&monitor(100)).unwrap();
+
+        let snapshot = api_key.audit_snapshot();
+        assert_eq!(
+            snapshot,
+            json!({
+                "api_key_id": api_key.api_key_id,
+                "name": "Test key",
+                "masked": api_key.masked,
+                "scopes": ["jobs:write"],
+                "monitor_ids": null,
+                "created": api_key.created,
+                "expires_at": "2025-01-01T00:00:00"
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(AuditAction::Created, "created")]
+    #[case(AuditAction::Updated, "updated")]
+    #[case(AuditAction::Deleted, "deleted")]
+    fn test_audit_action_names(#[case] action: AuditAction, #[case] name: &str) {
+        assert_eq!(action.as_str(), name);
+        assert_eq!(AuditAction::from_name(name), Some(action));
+        assert_eq!(serde_json::to_value(action).unwrap(), json!(name));
+    }
+
+    #[rstest]
+    #[case(EntityType::Monitor, "monitor")]
+    #[case(EntityType::AlertConfig, "alert_config")]
+    #[case(EntityType::ApiKey, "api_key")]
+    #[case(EntityType::WebhookSubscription, "webhook_subscription")]
+    fn test_entity_type_names(#[case] entity_type: EntityType, #[case] name: &str) {
+        assert_eq!(entity_type.as_str(), name);
+        assert_eq!(EntityType::from_name(name), Some(entity_type));
+        assert_eq!(serde_json::to_value(entity_type).unwrap(), json!(name));
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        assert_eq!(AuditAction::from_name("rotated"), None);
+        assert_eq!(EntityType::from_name("job"), None);
+    }
+}
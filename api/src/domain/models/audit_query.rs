@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::domain::models::{AuditEvent, EntityType};
+
+/// The most events that can be retrieved in a single page of the audit log.
+pub const MAX_AUDIT_PAGE_SIZE: u32 = 100;
+
+/// The number of events retrieved in a page of the audit log, unless requested otherwise.
+pub const DEFAULT_AUDIT_PAGE_SIZE: u32 = 25;
+
+/// The `AuditQuery` struct represents a request for a single page of a tenant's audit log, newest
+/// events first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditQuery {
+    /// Only include changes to entities of this type.
+    pub entity_type: Option<EntityType>,
+    /// Only include changes to the entity with this ID.
+    pub entity_id: Option<Uuid>,
+    /// Only include changes made by the user with this ID.
+    pub actor_id: Option<String>,
+    /// The most events to retrieve.
+    pub limit: u32,
+    /// The ID of the last event on the previous page, if this isn't the first page.
+    pub cursor: Option<Uuid>,
+}
+
+/// A single page of a tenant's audit log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditPage {
+    /// The events in this page.
+    pub events: Vec<AuditEvent>,
+    /// The total number of events matching the query, across all pages.
+    pub total: usize,
+    /// The cursor to retrieve the next page with, if there is one.
+    pub next_cursor: Option<Uuid>,
+}
+
+impl Default for AuditQuery {
+    /// By default, the first page of all events is retrieved.
+    fn default() -> Self {
+        Self {
+            entity_type: None,
+            entity_id: None,
+            actor_id: None,
+            limit: DEFAULT_AUDIT_PAGE_SIZE,
+            cursor: None,
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use cron_mon_client::types::AutoTuneData;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::SuggestedDurations;
+use crate::errors::Error;
+
+/// The `AutoTune` struct represents the bounds within which a Monitor's expected and grace
+/// durations are automatically adjusted to suit how long its Jobs take.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "AutoTuneData")]
+pub struct AutoTune {
+    /// The shortest that the expected duration can be tuned to, in seconds.
+    pub min_expected_duration: i32,
+    /// The longest that the expected duration can be tuned to, in seconds.
+    pub max_expected_duration: i32,
+    /// The shortest that the grace duration can be tuned to, in seconds.
+    pub min_grace_duration: i32,
+    /// The longest that the grace duration can be tuned to, in seconds.
+    pub max_grace_duration: i32,
+}
+
+impl TryFrom<AutoTuneData> for AutoTune {
+    type Error = Error;
+
+    fn try_from(value: AutoTuneData) -> Result<Self, Self::Error> {
+        AutoTune::new(
+            value.min_expected_duration,
+            value.max_expected_duration,
+            value.min_grace_duration,
+            value.max_grace_duration,
+        )
+    }
+}
+
+impl AutoTune {
+    /// Instantiate a new AutoTune. Note that this will return an `Error` if either minimum is
+    /// negative or greater than its maximum.
+    pub fn new(
+        min_expected_duration: i32,
+        max_expected_duration: i32,
+        min_grace_duration: i32,
+        max_grace_duration: i32,
+    ) -> Result<Self, Error> {
+        for (name, min, max) in [
+            ("expected", min_expected_duration, max_expected_duration),
+            ("grace", min_grace_duration, max_grace_duration),
+        ] {
+            if min < 0 {
+                return Err(Error::InvalidMonitor(format!(
+                    "Invalid minimum {name} duration '{min}', must not be negative"
+                )));
+            }
+            if min > max {
+                return Err(Error::InvalidMonitor(format!(
+                    "Invalid maximum {name} duration '{max}', must be at least the minimum \
+                    '{min}'"
+                )));
+            }
+        }
+
+        Ok(Self {
+            min_expected_duration,
+            max_expected_duration,
+            min_grace_duration,
+            max_grace_duration,
+        })
+    }
+
+    /// Bring suggested durations within bounds, returning the expected and grace durations to
+    /// tune the Monitor to.
+    pub fn tune(&self, suggested: &SuggestedDurations) -> (i32, i32) {
+        (
+            suggested
+                .expected_duration
+                .clamp(self.min_expected_duration, self.max_expected_duration),
+            suggested
+                .grace_duration
+                .clamp(self.min_grace_duration, self.max_grace_duration),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn creating_auto_tunes() {
+        let auto_tune = AutoTune::new(60, 600, 0, 120).unwrap();
+
+        assert_eq!(auto_tune.min_expected_duration, 60);
+        assert_eq!(auto_tune.max_expected_duration, 600);
+        assert_eq!(auto_tune.min_grace_duration, 0);
+        assert_eq!(auto_tune.max_grace_duration, 120);
+    }
+
+    #[rstest]
+    #[case(
+        -1,
+        600,
+        0,
+        120,
+        "Invalid Monitor: Invalid minimum expected duration '-1', must not be negative"
+    )]
+    #[case(
+        601,
+        600,
+        0,
+        120,
+        "Invalid Monitor: Invalid maximum expected duration '600', must be at least the minimum \
+        '601'"
+    )]
+    #[case(
+        60,
+        600,
+        -1,
+        120,
+        "Invalid Monitor: Invalid minimum grace duration '-1', must not be negative"
+    )]
+    #[case(
+        60,
+        600,
+        121,
+        120,
+        "Invalid Monitor: Invalid maximum grace duration '120', must be at least the minimum '121'"
+    )]
+    fn creating_invalid_auto_tunes(
+        #[case] min_expected_duration: i32,
+        #[case] max_expected_duration: i32,
+        #[case] min_grace_duration: i32,
+        #[case] max_grace_duration: i32,
+        #[case] expected_error: &str,
+    ) {
+        let error = AutoTune::new(
+            min_expected_duration,
+            max_expected_duration,
+            min_grace_duration,
+            max_grace_duration,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    #[rstest]
+    #[case::within_bounds(300, 60, (300, 60))]
+    #[case::below_bounds(30, 0, (60, 10))]
+    #[case::above_bounds(900, 200, (600, 120))]
+    fn tuning_durations(
+        #[case] expected_duration: i32,
+        #[case] grace_duration: i32,
+        #[case] expected: (i32, i32),
+    ) {
+        let auto_tune = AutoTune::new(60, 600, 10, 120).unwrap();
+
+        assert_eq!(
+            auto_tune.tune(&SuggestedDurations {
+                expected_duration,
+                grace_duration
+            }),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(
+        json!({
+            "min_expected_duration": 60,
+            "max_expected_duration": 600,
+            "min_grace_duration": 0,
+            "max_grace_duration": 120
+        }),
+        Ok(AutoTune::new(60, 600, 0, 120).unwrap())
+    )]
+    #[case(
+        json!({
+            "min_expected_duration": 600,
+            "max_expected_duration": 60,
+            "min_grace_duration": 0,
+            "max_grace_duration": 120
+        }),
+        Err(
+            "Invalid Monitor: Invalid maximum expected duration '60', must be at least the \
+            minimum '600'"
+                .to_owned()
+        )
+    )]
+    fn deserializing_auto_tunes(
+        #[case] value: serde_json::Value,
+        #[case] expected: Result<AutoTune, String>,
+    ) {
+        let result = serde_json::from_value::<AutoTune>(value).map_err(|error| error.to_string());
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Serialize;
+
+/// The fewest Jobs that durations are suggested from, or judged against, since a handful of Jobs
+/// says little about how long a Monitor's Jobs usually take.
+const MIN_JOBS: usize = 10;
+
+/// The `DurationStats` struct represents statistics describing how long a Monitor's Jobs took to
+/// run, in seconds.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DurationStats {
+    /// The number of Jobs that the statistics were calculated from.
+    pub job_count: usize,
+    /// The median duration.
+    pub p50: u64,
+    /// The 95th percentile duration.
+    pub p95: u64,
+    /// The 99th percentile duration.
+    pub p99: u64,
+    /// The longest duration.
+    pub max: u64,
+    /// The (population) standard deviation of the durations.
+    pub std_dev: f64,
+}
+
+/// The `SuggestedDurations` struct represents an expected and grace duration for a Monitor, based
+/// on how long its Jobs have taken to run.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SuggestedDurations {
+    /// The suggested expected duration, in seconds.
+    pub expected_duration: i32,
+    /// The suggested grace duration, in seconds.
+    pub grace_duration: i32,
+}
+
+impl DurationStats {
+    /// Suggest an expected and grace duration, or `None` if there are too few Jobs to go on.
+    ///
+    /// Jobs are expected to take as long as 95% of them did, and are given enough grace to cover
+    /// 99% of them, or a standard deviation, whichever is greater.
+    pub fn suggest(&self) -> Option<SuggestedDurations> {
+        if self.job_count < MIN_JOBS {
+            return None;
+        }
+
+        let grace = (self.p99 - self.p95).max(self.std_dev.ceil() as u64);
+        Some(SuggestedDurations {
+            expected_duration: i32::try_from(self.p95).unwrap_or(i32::MAX),
+            grace_duration: i32::try_from(grace).unwrap_or(i32::MAX),
+        })
+    }
+
+    /// The range of durations within `max_deviations` standard deviations of the median, or
+    /// `None` if there are too few Jobs to go on.
+    ///
+    /// The range always extends at least a second either side of the median, since durations are
+    /// only measured to the second.
+    pub fn expected_range(&self, max_deviations: f64) -> Option<(u64, u64)> {
+        if self.job_count < MIN_JOBS {
+            return None;
+        }
+
+        let spread = (self.std_dev * max_deviations).max(1.0);
+        let median = self.p50 as f64;
+        Some((
+            (median - spread).max(0.0).floor() as u64,
+            (median + spread).ceil() as u64,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn gen_stats(job_count: usize, std_dev: f64) -> DurationStats {
+        DurationStats {
+            job_count,
+            p50: 300,
+            p95: 500,
+            p99: 560,
+            max: 900,
+            std_dev,
+        }
+    }
+
+    #[rstest]
+    #[case::percentiles(10, 20.0, 60)]
+    #[case::std_dev(100, 80.2, 81)]
+    fn suggesting_durations(
+        #[case] job_count: usize,
+        #[case] std_dev: f64,
+        #[case] expected_grace: i32,
+    ) {
+        assert_eq!(
+            gen_stats(job_count, std_dev).suggest(),
+            Some(SuggestedDurations {
+                expected_duration: 500,
+                grace_duration: expected_grace,
+            })
+        );
+    }
+
+    #[test]
+    fn suggesting_durations_from_too_few_jobs() {
+        assert_eq!(gen_stats(9, 20.0).suggest(), None);
+    }
+
+    #[rstest]
+    #[case::deviations(20.0, 2.5, Some((250, 350)))]
+    #[case::wide_deviations(200.0, 2.0, Some((0, 700)))]
+    #[case::no_deviation(0.0, 3.0, Some((299, 301)))]
+    fn calculating_expected_ranges(
+        #[case] std_dev: f64,
+        #[case] max_deviations: f64,
+        #[case] expected: Option<(u64, u64)>,
+    ) {
+        assert_eq!(
+            gen_stats(10, std_dev).expected_range(max_deviations),
+            expected
+        );
+    }
+
+    #[test]
+    fn calculating_expected_ranges_from_too_few_jobs() {
+        assert_eq!(gen_stats(9, 20.0).expected_range(3.0), None);
+    }
+}
@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+
 use chrono::{Duration, NaiveDateTime, Utc};
 use serde::{Serialize, Serializer};
 use uuid::Uuid;
 
+use crate::domain::models::JobMetadata;
 use crate::errors::Error;
 
 /// The Job struct represents a monitored job, encapsulating the time it started, the time it
@@ -20,6 +23,29 @@ pub struct Job {
     pub late_alert_sent: bool,
     /// Whether or not an error alert has been sent for this Job.
     pub error_alert_sent: bool,
+    /// Whether or not the late alert for this Job has been resolved, now that the Job has
+    /// finished.
+    pub late_alert_resolved: bool,
+    /// Whether or not the error alert for this Job has been resolved, now that a later Job has
+    /// succeeded.
+    pub error_alert_resolved: bool,
+    /// Whether or not a recovery alert has been sent for this Job, having succeeded after one or
+    /// more erroneous Jobs.
+    pub recovery_alert_sent: bool,
+    /// Whether or not the Job ran while its Monitor was paused or within one of its maintenance
+    /// windows, in which case it isn't alerted on.
+    pub during_maintenance: bool,
+    /// Whether or not the Job was late or errored without its Monitor's alert threshold being
+    /// reached, in which case it isn't alerted on.
+    pub below_threshold: bool,
+    /// Whether or not the Job has been checked for having taken an anomalous amount of time,
+    /// which only happens once it has succeeded.
+    pub anomaly_checked: bool,
+    /// Whether or not the Job started while another Job for the same Monitor was still in
+    /// progress.
+    pub overlapped: bool,
+    /// Whether or not an overlap alert has been sent for this Job.
+    pub overlap_alert_sent: bool,
 }
 
 /// The EndState struct represents the state of a Job when it has finished.
@@ -31,6 +57,8 @@ pub struct EndState {
     pub succeeded: bool,
     /// Any output from the Job.
     pub output: Option<String>,
+    /// Structured details of how the Job ran.
+    pub metadata: JobMetadata,
 }
 
 impl Job {
@@ -45,12 +73,25 @@ impl Job {
             end_state: None,
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         }
     }
 
     /// Finish the Job. Note that if the Job isn't currently in progress, this will return an
     /// `Error`.
-    pub fn finish(&mut self, succeeded: bool, output: Option<String>) -> Result<(), Error> {
+    pub fn finish(
+        &mut self,
+        succeeded: bool,
+        output: Option<String>,
+        metadata: JobMetadata,
+    ) -> Result<(), Error> {
         if !self.in_progress() {
             return Err(Error::JobAlreadyFinished(self.job_id));
         }
@@ -59,6 +100,7 @@ impl Job {
             end_time: Utc::now().naive_utc(),
             succeeded,
             output,
+            metadata,
         });
 
         Ok(())
@@ -89,6 +131,15 @@ impl Job {
         }
     }
 
+    /// Ascertain whether or not the Job has finished successfully.
+    pub fn succeeded(&self) -> bool {
+        if let Some(end_state) = &self.end_state {
+            end_state.succeeded
+        } else {
+            false
+        }
+    }
+
     /// Get the duration of the Job, if it has finished.
     pub fn duration(&self) -> Option<u64> {
         self.end_state.as_ref().map(|end_state| {
@@ -109,20 +160,28 @@ impl Serialize for Job {
             end_time: Option<NaiveDateTime>,
             succeeded: Option<bool>,
             output: Option<String>,
+            during_maintenance: bool,
+            overlapped: bool,
+            exit_code: Option<i32>,
+            hostname: Option<String>,
+            command: Option<String>,
+            version: Option<String>,
+            metrics: BTreeMap<String, f64>,
             // Computed attributes.
             duration: Option<u64>,
             in_progress: bool,
             late: bool,
         }
 
-        let (end_time, succeeded, output) = if let Some(end_state) = &self.end_state {
+        let (end_time, succeeded, output, metadata) = if let Some(end_state) = &self.end_state {
             (
                 Some(end_state.end_time),
                 Some(end_state.succeeded),
                 end_state.output.clone(),
+                end_state.metadata.clone(),
             )
         } else {
-            (None, None, None)
+            (None, None, None, JobMetadata::default())
         };
         SerializedJob {
             job_id: self.job_id,
@@ -130,6 +189,13 @@ impl Serialize for Job {
             end_time,
             succeeded,
             output,
+            during_maintenance: self.during_maintenance,
+            overlapped: self.overlapped,
+            exit_code: metadata.exit_code,
+            hostname: metadata.hostname,
+            command: metadata.command,
+            version: metadata.version,
+            metrics: metadata.metrics,
             duration: self.duration(),
             in_progress: self.in_progress(),
             late: self.late(),
@@ -167,7 +233,11 @@ mod tests {
     fn finishing_jobs() {
         let mut job = Job::start(300);
 
-        let result1 = job.finish(true, None);
+        let metadata = JobMetadata {
+            exit_code: Some(0),
+            ..Default::default()
+        };
+        let result1 = job.finish(true, None, metadata.clone());
         assert!(result1.is_ok());
         assert!(!job.in_progress());
         assert!(job.end_state.is_some());
@@ -175,11 +245,16 @@ mod tests {
         let original_end_time = end_state.end_time;
         assert!(end_state.succeeded);
         assert_eq!(end_state.output, None);
+        assert_eq!(end_state.metadata, metadata);
         assert!(!job.late_alert_sent);
         assert!(!job.error_alert_sent);
 
         // Cannot finish a job again once it's been finished.
-        let result2 = job.finish(false, Some("It won't wrong".to_owned()));
+        let result2 = job.finish(
+            false,
+            Some("It won't wrong".to_owned()),
+            JobMetadata::default(),
+        );
         assert_eq!(result2.unwrap_err(), Error::JobAlreadyFinished(job.job_id));
         let end_state = job.end_state.unwrap();
         assert_eq!(end_state.end_time, original_end_time);
@@ -203,9 +278,18 @@ mod tests {
                 end_time,
                 succeeded: succeeded.unwrap(),
                 output: None,
+                metadata: JobMetadata::default(),
             }),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         };
 
         assert_eq!(job.duration(), expected_duration);
@@ -239,21 +323,31 @@ mod tests {
                 end_time,
                 succeeded: result.1.unwrap(),
                 output: None,
+                metadata: JobMetadata::default(),
             }),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         };
 
         assert_eq!(job.late(), expected_late);
     }
 
     #[rstest]
-    #[case::not_finished(None, false)]
-    #[case::finished_successfully(Some(true), false)]
-    #[case::finished_not_successfully(Some(false), true)]
-    fn checking_if_job_errored(
+    #[case::not_finished(None, false, false)]
+    #[case::finished_successfully(Some(true), false, true)]
+    #[case::finished_not_successfully(Some(false), true, false)]
+    fn checking_if_job_errored_or_succeeded(
         #[case] need_end_state: Option<bool>,
         #[case] expected_errored: bool,
+        #[case] expected_succeeded: bool,
     ) {
         let job = Job {
             job_id: Uuid::new_v4(),
@@ -263,12 +357,22 @@ mod tests {
                 end_time: gen_relative_datetime(0),
                 succeeded,
                 output: None,
+                metadata: JobMetadata::default(),
             }),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         };
 
         assert_eq!(job.errored(), expected_errored);
+        assert_eq!(job.succeeded(), expected_succeeded);
     }
 
     #[test]
@@ -281,9 +385,24 @@ mod tests {
                 end_time: gen_datetime("2024-04-20T20:40:30"),
                 succeeded: true,
                 output: None,
+                metadata: JobMetadata {
+                    exit_code: Some(0),
+                    hostname: Some("worker-1".to_owned()),
+                    command: None,
+                    version: Some("4f2a9c1".to_owned()),
+                    metrics: BTreeMap::from([("rows_processed".to_owned(), 1500.0)]),
+                },
             }),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         };
 
         let serialized = json!({"job": job});
@@ -297,6 +416,13 @@ mod tests {
                         "end_time": "2024-04-20T20:40:30",
                         "succeeded": true,
                         "output": Value::Null,
+                        "during_maintenance": false,
+                        "overlapped": false,
+                        "exit_code": 0,
+                        "hostname": "worker-1",
+                        "command": Value::Null,
+                        "version": "4f2a9c1",
+                        "metrics": {"rows_processed": 1500.0},
                         "duration": 600,
                         "in_progress": false,
                         "late": false,
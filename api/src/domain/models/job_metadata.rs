@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use cron_mon_client::types::JobMetadataData;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+/// The longest that a Job's hostname or version can be.
+const MAX_ATTRIBUTE_LENGTH: usize = 255;
+
+/// The longest that a Job's command can be.
+const MAX_COMMAND_LENGTH: usize = 4096;
+
+/// The longest that a metric's name can be.
+const MAX_METRIC_NAME_LENGTH: usize = 63;
+
+/// The most metrics that a single Job can record.
+const MAX_METRICS: usize = 50;
+
+/// The `JobMetadata` struct holds structured details of how a Job ran, as reported when it
+/// finished, including any numeric metrics it recorded (i.e `rows_processed`).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "JobMetadataData")]
+pub struct JobMetadata {
+    /// The exit code of the Job's command.
+    pub exit_code: Option<i32>,
+    /// The host that the Job ran on.
+    pub hostname: Option<String>,
+    /// The command that the Job ran.
+    pub command: Option<String>,
+    /// The version of the Job that ran, such as a git SHA.
+    pub version: Option<String>,
+    /// Numeric metrics recorded by the Job, by name.
+    pub metrics: BTreeMap<String, f64>,
+}
+
+impl TryFrom<JobMetadataData> for JobMetadata {
+    type Error = Error;
+
+    fn try_from(value: JobMetadataData) -> Result<Self, Self::Error> {
+        JobMetadata::new(
+            value.exit_code,
+            value.hostname,
+            value.command,
+            value.version,
+            value.metrics,
+        )
+    }
+}
+
+impl JobMetadata {
+    /// Instantiate a new JobMetadata. Note that this will return an `Error` if any attribute is
+    /// empty or too long, if there are too many metrics, or if any metric's name is invalid or its
+    /// value isn't finite.
+    pub fn new(
+        exit_code: Option<i32>,
+        hostname: Option<String>,
+        command: Option<String>,
+        version: Option<String>,
+        metrics: BTreeMap<String, f64>,
+    ) -> Result<Self, Error> {
+        for (name, value, max_length) in [
+            ("hostname", &hostname, MAX_ATTRIBUTE_LENGTH),
+            ("command", &command, MAX_COMMAND_LENGTH),
+            ("version", &version, MAX_ATTRIBUTE_LENGTH),
+        ] {
+            if let Some(value) = value {
+                if value.is_empty() || value.chars().count() > max_length {
+                    return Err(Error::InvalidJob(format!(
+                        "Invalid {name}, must be between 1 and {max_length} characters"
+                    )));
+                }
+            }
+        }
+
+        if metrics.len() > MAX_METRICS {
+            return Err(Error::InvalidJob(format!(
+                "Too many metrics ({}), at most {MAX_METRICS} can be recorded",
+                metrics.len()
+            )));
+        }
+        for (name, value) in &metrics {
+            validate_metric_name(name)?;
+            if !value.is_finite() {
+                return Err(Error::InvalidJob(format!(
+                    "Invalid value for metric '{name}', must be a finite number"
+                )));
+            }
+        }
+
+        Ok(Self {
+            exit_code,
+            hostname,
+            command,
+            version,
+            metrics,
+        })
+    }
+}
+
+/// Check that a metric's name is non-empty, not too long, and contains only letters, digits,
+/// `.`, `_` or `-`.
+fn validate_metric_name(name: &str) -> Result<(), Error> {
+    if name.is_empty() || name.len() > MAX_METRIC_NAME_LENGTH {
+        return Err(Error::InvalidJob(format!(
+            "Invalid metric name '{name}', must be between 1 and {MAX_METRIC_NAME_LENGTH} \
+            characters"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        return Err(Error::InvalidJob(format!(
+            "Invalid metric name '{name}', must only contain letters, digits, '.', '_' or '-'"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn creating_job_metadata() {
+        let metadata = JobMetadata::new(
+            Some(0),
+            Some("worker-1".to_owned()),
+            Some("backup.sh --full".to_owned()),
+            Some("4f2a9c1".to_owned()),
+            BTreeMap::from([("rows_processed".to_owned(), 1500.0)]),
+        )
+        .unwrap();
+
+        assert_eq!(metadata.exit_code, Some(0));
+        assert_eq!(metadata.hostname, Some("worker-1".to_owned()));
+        assert_eq!(metadata.command, Some("backup.sh --full".to_owned()));
+        assert_eq!(metadata.version, Some("4f2a9c1".to_owned()));
+        assert_eq!(metadata.metrics.get("rows_processed"), Some(&1500.0));
+    }
+
+    #[rstest]
+    #[case::empty_hostname(
+        Some(String::new()),
+        None,
+        BTreeMap::new(),
+        "Invalid Job: Invalid hostname, must be between 1 and 255 characters"
+    )]
+    #[case::long_version(
+        None,
+        Some("a".repeat(256)),
+        BTreeMap::new(),
+        "Invalid Job: Invalid version, must be between 1 and 255 characters"
+    )]
+    #[case::empty_metric_name(
+        None,
+        None,
+        BTreeMap::from([(String::new(), 1.0)]),
+        "Invalid Job: Invalid metric name '', must be between 1 and 63 characters"
+    )]
+    #[case::invalid_metric_name(
+        None,
+        None,
+        BTreeMap::from([("rows processed".to_owned(), 1.0)]),
+        "Invalid Job: Invalid metric name 'rows processed', must only contain letters, digits, \
+        '.', '_' or '-'"
+    )]
+    #[case::non_finite_metric(
+        None,
+        None,
+        BTreeMap::from([("ratio".to_owned(), f64::NAN)]),
+        "Invalid Job: Invalid value for metric 'ratio', must be a finite number"
+    )]
+    #[case::too_many_metrics(
+        None,
+        None,
+        (0..51).map(|i| (format!("metric_{i}"), 1.0)).collect(),
+        "Invalid Job: Too many metrics (51), at most 50 can be recorded"
+    )]
+    fn creating_invalid_job_metadata(
+        #[case] hostname: Option<String>,
+        #[case] version: Option<String>,
+        #[case] metrics: BTreeMap<String, f64>,
+        #[case] expected_error: &str,
+    ) {
+        let error = JobMetadata::new(None, hostname, None, version, metrics).unwrap_err();
+
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    #[rstest]
+    #[case(json!({}), Ok(JobMetadata::default()))]
+    #[case(
+        json!({"exit_code": 3, "metrics": {"bytes_backed_up": 2048}}),
+        Ok(JobMetadata {
+            exit_code: Some(3),
+            metrics: BTreeMap::from([("bytes_backed_up".to_owned(), 2048.0)]),
+            ..Default::default()
+        })
+    )]
+    #[case(
+        json!({"hostname": ""}),
+        Err("Invalid Job: Invalid hostname, must be between 1 and 255 characters".to_owned())
+    )]
+    fn deserializing_job_metadata(
+        #[case] value: serde_json::Value,
+        #[case] expected: Result<JobMetadata, String>,
+    ) {
+        let result =
+            serde_json::from_value::<JobMetadata>(value).map_err(|error| error.to_string());
+
+        assert_eq!(result, expected);
+    }
+}
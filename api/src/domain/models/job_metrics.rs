@@ -0,0 +1,26 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// The `JobMetrics` struct represents the numeric metrics recorded by a Monitor's Jobs, as a
+/// series of values for each metric so that they can be charted over time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct JobMetrics {
+    /// The number of finished Jobs that the series were collected from.
+    pub job_count: usize,
+    /// The values recorded for each metric, oldest first, by the metric's name.
+    pub metrics: BTreeMap<String, Vec<MetricPoint>>,
+}
+
+/// The `MetricPoint` struct represents the value of a metric recorded by a single Job.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MetricPoint {
+    /// The Job that recorded the value.
+    pub job_id: Uuid,
+    /// The time that the Job finished.
+    pub end_time: NaiveDateTime,
+    /// The value of the metric.
+    pub value: f64,
+}
@@ -0,0 +1,74 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::domain::models::Job;
+
+/// The most Jobs that can be retrieved in a single page of a Monitor's job history.
+pub const MAX_JOB_PAGE_SIZE: u32 = 100;
+
+/// The number of Jobs retrieved in a page of a Monitor's job history, unless requested otherwise.
+pub const DEFAULT_JOB_PAGE_SIZE: u32 = 25;
+
+/// The `JobQuery` struct represents a request for a single page of a Monitor's job history,
+/// filtered and ordered by the Jobs' start times.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobQuery {
+    /// Only include Jobs with this status.
+    pub status: Option<JobStatus>,
+    /// Only include Jobs that started at or after this time.
+    pub started_after: Option<NaiveDateTime>,
+    /// Only include Jobs that started before this time.
+    pub started_before: Option<NaiveDateTime>,
+    /// The order to retrieve Jobs in.
+    pub order: JobOrder,
+    /// The most Jobs to retrieve.
+    pub limit: u32,
+    /// The ID of the last Job on the previous page, if this isn't the first page.
+    pub cursor: Option<Uuid>,
+}
+
+/// The statuses that a Monitor's job history can be filtered by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JobStatus {
+    /// Jobs that finished successfully.
+    Succeeded,
+    /// Jobs that finished with an error.
+    Failed,
+    /// Jobs that finished late, or are still running and are late.
+    Late,
+    /// Jobs that are still running.
+    InProgress,
+}
+
+/// The orders that a Monitor's job history can be retrieved in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum JobOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+/// A single page of a Monitor's job history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobPage {
+    /// The Jobs in this page.
+    pub jobs: Vec<Job>,
+    /// The total number of Jobs matching the query, across all pages.
+    pub total: usize,
+    /// The cursor to retrieve the next page with, if there is one.
+    pub next_cursor: Option<Uuid>,
+}
+
+impl Default for JobQuery {
+    /// By default, the first page of all Jobs is retrieved, newest first.
+    fn default() -> Self {
+        Self {
+            status: None,
+            started_after: None,
+            started_before: None,
+            order: JobOrder::default(),
+            limit: DEFAULT_JOB_PAGE_SIZE,
+            cursor: None,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{Duration, NaiveDateTime};
+use cron_mon_client::types::JobRetentionData;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+/// The `JobRetention` struct represents how many of a Monitor's Jobs are kept, and for how long,
+/// before they're pruned.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "JobRetentionData")]
+pub struct JobRetention {
+    /// If set, the number of days that Jobs are kept for after they start.
+    pub max_age_days: Option<i32>,
+    /// If set, the number of most recent Jobs that are kept.
+    pub max_jobs: Option<i32>,
+}
+
+impl TryFrom<JobRetentionData> for JobRetention {
+    type Error = Error;
+
+    fn try_from(value: JobRetentionData) -> Result<Self, Self::Error> {
+        JobRetention::new(value.max_age_days, value.max_jobs)
+    }
+}
+
+impl JobRetention {
+    /// Instantiate a new JobRetention. Note that this will return an `Error` if neither limit is
+    /// set, or if either would prune every Job.
+    pub fn new(max_age_days: Option<i32>, max_jobs: Option<i32>) -> Result<Self, Error> {
+        if max_age_days.is_none() && max_jobs.is_none() {
+            return Err(Error::InvalidMonitor(
+                "Job retention must have a maximum age or a maximum number of jobs".to_owned(),
+            ));
+        }
+        if let Some(days) = max_age_days.filter(|days| *days < 1) {
+            return Err(Error::InvalidMonitor(format!(
+                "Invalid job retention age '{days}', must be at least 1 day"
+            )));
+        }
+        if let Some(jobs) = max_jobs.filter(|jobs| *jobs < 1) {
+            return Err(Error::InvalidMonitor(format!(
+                "Invalid job retention count '{jobs}', must be at least 1 job"
+            )));
+        }
+
+        Ok(Self {
+            max_age_days,
+            max_jobs,
+        })
+    }
+
+    /// The start time before which Jobs are older than the maximum age, if there is one.
+    pub fn max_age_cutoff(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.max_age_days
+            .map(|days| now - Duration::days(days as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use test_utils::gen_datetime;
+
+    use super::*;
+
+    #[rstest]
+    #[case::age(Some(30), None)]
+    #[case::count(None, Some(1000))]
+    #[case::age_and_count(Some(30), Some(1000))]
+    fn creating_job_retentions(#[case] max_age_days: Option<i32>, #[case] max_jobs: Option<i32>) {
+        let retention = JobRetention::new(max_age_days, max_jobs).unwrap();
+
+        assert_eq!(retention.max_age_days, max_age_days);
+        assert_eq!(retention.max_jobs, max_jobs);
+    }
+
+    #[rstest]
+    #[case(
+        None,
+        None,
+        "Job retention must have a maximum age or a maximum number of jobs"
+    )]
+    #[case(
+        Some(0),
+        Some(1000),
+        "Invalid job retention age '0', must be at least 1 day"
+    )]
+    #[case(
+        Some(30),
+        Some(-1),
+        "Invalid job retention count '-1', must be at least 1 job"
+    )]
+    fn creating_invalid_job_retentions(
+        #[case] max_age_days: Option<i32>,
+        #[case] max_jobs: Option<i32>,
+        #[case] expected_error: &str,
+    ) {
+        assert_eq!(
+            JobRetention::new(max_age_days, max_jobs),
+            Err(Error::InvalidMonitor(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn deserializing_job_retentions() {
+        let retention: JobRetention = serde_json::from_value(json!({"max_age_days": 30})).unwrap();
+        assert_eq!(
+            retention,
+            JobRetention {
+                max_age_days: Some(30),
+                max_jobs: None
+            }
+        );
+
+        let result = serde_json::from_value::<JobRetention>(json!({}));
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case(Some(30), Some(gen_datetime("2024-04-01T12:00:00")))]
+    #[case(None, None)]
+    fn getting_max_age_cutoff(
+        #[case] max_age_days: Option<i32>,
+        #[case] expected_cutoff: Option<NaiveDateTime>,
+    ) {
+        let retention = JobRetention {
+            max_age_days,
+            max_jobs: Some(10),
+        };
+
+        assert_eq!(
+            retention.max_age_cutoff(gen_datetime("2024-05-01T12:00:00")),
+            expected_cutoff
+        );
+    }
+}
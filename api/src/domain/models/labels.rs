@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::errors::Error;
+
+/// The longest that a label's key or value can be.
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// The `Labels` struct holds the free-form key/value pairs attached to a Monitor (i.e
+/// `team=payments`, `env=prod`), which Monitors can be selected by.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, String>")]
+pub struct Labels(BTreeMap<String, String>);
+
+impl TryFrom<BTreeMap<String, String>> for Labels {
+    type Error = Error;
+
+    fn try_from(value: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+        Labels::new(value)
+    }
+}
+
+impl Labels {
+    /// Instantiate a new set of Labels. Note that this will return an `Error` if any key or value
+    /// is empty, too long, or contains anything other than letters, digits, `.`, `_`, `/` or `-`.
+    pub fn new(labels: BTreeMap<String, String>) -> Result<Self, Error> {
+        for (key, value) in &labels {
+            validate("key", key).and_then(|_| validate("value", value))?;
+        }
+
+        Ok(Self(labels))
+    }
+
+    /// Retrieve the value of a label, if the label is set.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// Iterate over the labels, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter()
+    }
+}
+
+/// The `LabelSelector` struct selects Monitors by their labels, using a comma-separated list of
+/// requirements that must all be met:
+///
+/// - `key=value`: the label is set to the value.
+/// - `key!=value`: the label isn't set to the value (including when it isn't set at all).
+/// - `key`: the label is set.
+/// - `!key`: the label isn't set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelSelector {
+    requirements: Vec<Requirement>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Requirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    Exists(String),
+    NotExists(String),
+}
+
+impl LabelSelector {
+    /// Check whether a set of labels meets every requirement of this selector.
+    pub fn matches(&self, labels: &Labels) -> bool {
+        self.requirements
+            .iter()
+            .all(|requirement| match requirement {
+                Requirement::Equals(key, value) => labels.get(key) == Some(value.as_str()),
+                Requirement::NotEquals(key, value) => labels.get(key) != Some(value.as_str()),
+                Requirement::Exists(key) => labels.get(key).is_some(),
+                Requirement::NotExists(key) => labels.get(key).is_none(),
+            })
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let requirements = s
+            .split(',')
+            .map(|requirement| {
+                let requirement = requirement.trim();
+                let parsed = if let Some((key, value)) = requirement.split_once("!=") {
+                    Requirement::NotEquals(key.trim().to_owned(), value.trim().to_owned())
+                } else if let Some((key, value)) = requirement.split_once('=') {
+                    Requirement::Equals(key.trim().to_owned(), value.trim().to_owned())
+                } else if let Some(key) = requirement.strip_prefix('!') {
+                    Requirement::NotExists(key.trim().to_owned())
+                } else {
+                    Requirement::Exists(requirement.to_owned())
+                };
+
+                match &parsed {
+                    Requirement::Equals(key, value) | Requirement::NotEquals(key, value) => {
+                        validate("key", key).and_then(|_| validate("value", value))
+                    }
+                    Requirement::Exists(key) | Requirement::NotExists(key) => validate("key", key),
+                }
+                .map_err(|_| format!("Invalid label selector requirement '{requirement}'"))?;
+
+                Ok(parsed)
+            })
+            .collect::<Result<Vec<Requirement>, String>>()?;
+
+        Ok(Self { requirements })
+    }
+}
+
+impl Display for LabelSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let requirements: Vec<String> = self
+            .requirements
+            .iter()
+            .map(|requirement| match requirement {
+                Requirement::Equals(key, value) => format!("{key}={value}"),
+                Requirement::NotEquals(key, value) => format!("{key}!={value}"),
+                Requirement::Exists(key) => key.clone(),
+                Requirement::NotExists(key) => format!("!{key}"),
+            })
+            .collect();
+
+        write!(f, "{}", requirements.join(","))
+    }
+}
+
+impl Serialize for LabelSelector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+fn validate(part: &str, text: &str) -> Result<(), Error> {
+    if text.is_empty() {
+        return Err(Error::InvalidMonitor(format!(
+            "Label {part} cannot be empty"
+        )));
+    }
+    if text.len() > MAX_LABEL_LENGTH {
+        return Err(Error::InvalidMonitor(format!(
+            "Label {part} '{text}' is longer than {MAX_LABEL_LENGTH} characters"
+        )));
+    }
+    if !text
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '/' | '-'))
+    {
+        return Err(Error::InvalidMonitor(format!(
+            "Label {part} '{text}' contains invalid characters"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        Labels::new(
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn creating_labels() {
+        let labels = labels(&[("team", "payments"), ("env", "prod")]);
+
+        assert_eq!(labels.get("team"), Some("payments"));
+        assert_eq!(labels.get("region"), None);
+        assert_eq!(
+            serde_json::to_value(&labels).unwrap(),
+            json!({"env": "prod", "team": "payments"})
+        );
+    }
+
+    #[rstest]
+    #[case::empty_key("", "prod", "Label key cannot be empty")]
+    #[case::empty_value("env", "", "Label value cannot be empty")]
+    #[case::invalid_key("my env", "prod", "Label key 'my env' contains invalid characters")]
+    #[case::invalid_value("env", "prod!", "Label value 'prod!' contains invalid characters")]
+    #[case::long_value(
+        "env",
+        &"a".repeat(64),
+        &format!("Label value '{}' is longer than 63 characters", "a".repeat(64))
+    )]
+    fn creating_invalid_labels(
+        #[case] key: &str,
+        #[case] value: &str,
+        #[case] expected_error: &str,
+    ) {
+        assert_eq!(
+            Labels::new(BTreeMap::from([(key.to_owned(), value.to_owned())])),
+            Err(Error::InvalidMonitor(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn deserializing_labels() {
+        let result = serde_json::from_value::<Labels>(json!({"env": "prod"}));
+        assert_eq!(result.unwrap(), labels(&[("env", "prod")]));
+
+        let result = serde_json::from_value::<Labels>(json!({"env": "not valid"}));
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case::equals("team=payments", true)]
+    #[case::equals_other_value("team=search", false)]
+    #[case::not_equals("env!=dev", true)]
+    #[case::not_equals_same_value("env!=prod", false)]
+    #[case::not_equals_missing("region!=eu", true)]
+    #[case::exists("env", true)]
+    #[case::exists_missing("region", false)]
+    #[case::not_exists("!region", true)]
+    #[case::not_exists_present("!env", false)]
+    #[case::all_met("team=payments, env!=dev, !region", true)]
+    #[case::one_not_met("team=payments,env=dev", false)]
+    fn matching_label_selectors(#[case] selector: &str, #[case] expected: bool) {
+        let selector: LabelSelector = selector.parse().unwrap();
+
+        assert_eq!(
+            selector.matches(&labels(&[("team", "payments"), ("env", "prod")])),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::empty("", "Invalid label selector requirement ''")]
+    #[case::empty_requirement("team=payments,", "Invalid label selector requirement ''")]
+    #[case::missing_key("=payments", "Invalid label selector requirement '=payments'")]
+    #[case::missing_value("team!=", "Invalid label selector requirement 'team!='")]
+    #[case::invalid_key("!my team", "Invalid label selector requirement '!my team'")]
+    fn parsing_invalid_label_selectors(#[case] selector: &str, #[case] expected_error: &str) {
+        assert_eq!(
+            selector.parse::<LabelSelector>(),
+            Err(expected_error.to_owned())
+        );
+    }
+
+    #[test]
+    fn displaying_label_selectors() {
+        let selector: LabelSelector = " team = payments,env!=dev,critical, !deprecated"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            selector.to_string(),
+            "team=payments,env!=dev,critical,!deprecated"
+        );
+        assert_eq!(
+            serde_json::to_value(&selector).unwrap(),
+            json!("team=payments,env!=dev,critical,!deprecated")
+        );
+    }
+}
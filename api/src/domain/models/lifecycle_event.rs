@@ -0,0 +1,208 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::domain::models::{Job, Monitor};
+
+pub use cron_mon_client::types::EventType;
+
+/// The `LifecycleEvent` struct describes a change to one of a tenant's Monitors, or to one of its
+/// Jobs. These are sent to the tenant's webhook subscriptions, and are serialized as the body of
+/// each request.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LifecycleEvent {
+    /// The unique identifier for the event, which stays the same across retried deliveries.
+    pub event_id: Uuid,
+    /// The tenant that the Monitor belongs to.
+    #[serde(skip_serializing)]
+    pub tenant: String,
+    /// What happened.
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    /// When it happened.
+    pub timestamp: NaiveDateTime,
+    /// The Monitor (and Job, for Job events) that it happened to.
+    pub data: Value,
+}
+
+impl LifecycleEvent {
+    /// Describe a Monitor that has just been created.
+    pub fn monitor_created(monitor: &Monitor) -> Self {
+        Self::new(
+            &monitor.tenant,
+            EventType::MonitorCreated,
+            monitor_data(monitor),
+        )
+    }
+
+    /// Describe a Monitor that has just been updated.
+    pub fn monitor_updated(monitor: &Monitor) -> Self {
+        Self::new(
+            &monitor.tenant,
+            EventType::MonitorUpdated,
+            monitor_data(monitor),
+        )
+    }
+
+    /// Describe a Monitor that has just been deleted.
+    pub fn monitor_deleted(monitor: &Monitor) -> Self {
+        Self::new(
+            &monitor.tenant,
+            EventType::MonitorDeleted,
+            monitor_data(monitor),
+        )
+    }
+
+    /// Describe a Job that has just started.
+    pub fn job_started(monitor: &Monitor, job: &Job) -> Self {
+        Self::new(
+            &monitor.tenant,
+            EventType::JobStarted,
+            job_data(monitor, job),
+        )
+    }
+
+    /// Describe a Job that has just finished.
+    pub fn job_finished(monitor: &Monitor, job: &Job) -> Self {
+        Self::new(
+            &monitor.tenant,
+            EventType::JobFinished,
+            job_data(monitor, job),
+        )
+    }
+
+    fn new(tenant: &str, event_type: EventType, data: Value) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            tenant: tenant.to_owned(),
+            event_type,
+            timestamp: Utc::now().naive_utc(),
+            data,
+        }
+    }
+}
+
+fn monitor_data(monitor: &Monitor) -> Value {
+    // A Monitor can have a lot of Jobs, which subscribers hear about individually anyway.
+    let mut monitor_data = serde_json::to_value(monitor).unwrap();
+    monitor_data.as_object_mut().unwrap().remove("jobs");
+    json!({ "monitor": monitor_data })
+}
+
+fn job_data(monitor: &Monitor, job: &Job) -> Value {
+    json!({
+        "monitor": {
+            "monitor_id": monitor.monitor_id,
+            "name": monitor.name,
+        },
+        "job": job,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::{fixture, rstest};
+
+    use test_utils::gen_uuid;
+
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, JobMetadata, Labels, MonitorKind,
+    };
+
+    use super::*;
+
+    #[fixture]
+    fn monitor() -> Monitor {
+        Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case::created(LifecycleEvent::monitor_created, EventType::MonitorCreated)]
+    #[case::updated(LifecycleEvent::monitor_updated, EventType::MonitorUpdated)]
+    #[case::deleted(LifecycleEvent::monitor_deleted, EventType::MonitorDeleted)]
+    fn test_monitor_events(
+        mut monitor: Monitor,
+        #[case] describe: fn(&Monitor) -> LifecycleEvent,
+        #[case] event_type: EventType,
+    ) {
+        monitor.start_job().unwrap();
+        let event = describe(&monitor);
+
+        assert_eq!(event.tenant, "tenant");
+        assert_eq!(event.event_type, event_type);
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "event_id": event.event_id,
+                "type": event_type.as_str(),
+                "timestamp": event.timestamp,
+                "data": {
+                    "monitor": {
+                        "monitor_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                        "name": "foo",
+                        "expected_duration": 300,
+                        "grace_duration": 100,
+                        "kind": {"type": "job"},
+                        "schedule": null,
+                        "alert_threshold": {"consecutive": 1, "window": null},
+                        "retention": null,
+                        "labels": {},
+                        "paused": false,
+                        "resume_at": null,
+                        "maintenance_windows": [],
+                        "auto_tune": null,
+                        "anomaly_detection": null,
+                        "concurrency_policy": "allow"
+                    }
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_job_events(mut monitor: Monitor) {
+        let mut job = monitor.start_job().unwrap();
+
+        let started = LifecycleEvent::job_started(&monitor, &job);
+        assert_eq!(started.event_type, EventType::JobStarted);
+        assert_eq!(
+            started.data,
+            json!({
+                "monitor": {
+                    "monitor_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                    "name": "foo"
+                },
+                "job": job
+            })
+        );
+
+        job.finish(true, None, JobMetadata::default()).unwrap();
+        let finished = LifecycleEvent::job_finished(&monitor, &job);
+        assert_eq!(finished.event_type, EventType::JobFinished);
+        assert_eq!(finished.data["job"]["succeeded"], json!(true));
+
+        // Each event is distinct, even when they're about the same Job.
+        assert_ne!(started.event_id, finished.event_id);
+    }
+}
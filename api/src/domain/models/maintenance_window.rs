@@ -0,0 +1,149 @@
+use chrono::{Duration, NaiveDateTime};
+use cron_mon_client::types::MaintenanceWindowData;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::Schedule;
+use crate::errors::Error;
+
+/// The `MaintenanceWindow` struct represents a recurring period of planned downtime for a
+/// Monitor, which starts on a cron schedule and lasts for a fixed number of minutes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "MaintenanceWindowData")]
+pub struct MaintenanceWindow {
+    /// The cron schedule that the maintenance window starts on.
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    /// How long the maintenance window lasts for each time it starts, in minutes.
+    pub duration_minutes: i32,
+}
+
+impl TryFrom<MaintenanceWindowData> for MaintenanceWindow {
+    type Error = Error;
+
+    fn try_from(value: MaintenanceWindowData) -> Result<Self, Self::Error> {
+        MaintenanceWindow::new(
+            &value.expression,
+            value.timezone.as_deref(),
+            value.duration_minutes,
+        )
+    }
+}
+
+impl MaintenanceWindow {
+    /// Instantiate a new MaintenanceWindow. Note that this will return an `Error` if the cron
+    /// expression or timezone are invalid, or if the duration is less than a minute.
+    pub fn new(
+        expression: &str,
+        timezone: Option<&str>,
+        duration_minutes: i32,
+    ) -> Result<Self, Error> {
+        let schedule = Schedule::new(expression, timezone)?;
+        if duration_minutes < 1 {
+            return Err(Error::InvalidMonitor(format!(
+                "Invalid maintenance window duration '{duration_minutes}', must be at least 1 \
+                minute"
+            )));
+        }
+
+        Ok(Self {
+            schedule,
+            duration_minutes,
+        })
+    }
+
+    /// Ascertain whether or not the maintenance window is in effect at the given (UTC) time.
+    pub fn is_active_at(&self, time: NaiveDateTime) -> bool {
+        // Previous runs are strictly before the time given, so we look from just after it in
+        // order for the window to be in effect from the moment it starts.
+        self.schedule
+            .previous_run(time + Duration::seconds(1))
+            .is_some_and(|start| time < start + Duration::minutes(self.duration_minutes as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use test_utils::gen_datetime;
+
+    use super::*;
+
+    #[test]
+    fn creating_maintenance_windows() {
+        let window = MaintenanceWindow::new("0 2 * * 0", Some("Europe/London"), 60).unwrap();
+
+        assert_eq!(window.schedule.expression, "0 2 * * 0");
+        assert_eq!(window.schedule.timezone, "Europe/London");
+        assert_eq!(window.duration_minutes, 60);
+    }
+
+    #[rstest]
+    #[case(
+        "not a cron expression",
+        60,
+        "Invalid Monitor: Invalid cron expression 'not a cron expression'"
+    )]
+    #[case(
+        "0 2 * * 0",
+        0,
+        "Invalid Monitor: Invalid maintenance window duration '0', must be at least 1 minute"
+    )]
+    fn creating_invalid_maintenance_windows(
+        #[case] expression: &str,
+        #[case] duration_minutes: i32,
+        #[case] expected_error: &str,
+    ) {
+        let error = MaintenanceWindow::new(expression, None, duration_minutes).unwrap_err();
+
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    #[rstest]
+    #[case("2024-05-01T01:59:59", false)]
+    #[case("2024-05-01T02:00:00", true)]
+    #[case("2024-05-01T02:29:59", true)]
+    #[case("2024-05-01T02:30:00", false)]
+    #[case("2024-05-01T12:00:00", false)]
+    fn checking_whether_maintenance_windows_are_active(
+        #[case] time: &str,
+        #[case] expected_active: bool,
+    ) {
+        let window = MaintenanceWindow::new("0 2 * * *", None, 30).unwrap();
+
+        assert_eq!(window.is_active_at(gen_datetime(time)), expected_active);
+    }
+
+    #[test]
+    fn serializing_maintenance_windows() {
+        let window = MaintenanceWindow::new("0 2 * * 0", Some("Europe/London"), 60).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&window).unwrap(),
+            json!({"expression": "0 2 * * 0", "timezone": "Europe/London", "duration_minutes": 60})
+        );
+    }
+
+    #[rstest]
+    #[case(
+        json!({"expression": "0 2 * * 0", "duration_minutes": 60}),
+        Ok(MaintenanceWindow::new("0 2 * * 0", None, 60).unwrap())
+    )]
+    #[case(
+        json!({"expression": "0 2 * * 0", "duration_minutes": -5}),
+        Err(
+            "Invalid Monitor: Invalid maintenance window duration '-5', must be at least 1 minute"
+                .to_owned()
+        )
+    )]
+    fn deserializing_maintenance_windows(
+        #[case] value: serde_json::Value,
+        #[case] expected: Result<MaintenanceWindow, String>,
+    ) {
+        let result =
+            serde_json::from_value::<MaintenanceWindow>(value).map_err(|error| error.to_string());
+
+        assert_eq!(result, expected);
+    }
+}
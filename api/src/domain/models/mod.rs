@@ -1,9 +1,48 @@
+pub mod activity_event;
 pub mod alert_config;
+pub mod alert_threshold;
+pub mod anomaly_detection;
 pub mod api_key;
+pub mod audit_event;
+pub mod audit_query;
+pub mod auto_tune;
+pub mod duration_stats;
 pub mod job;
+pub mod job_metadata;
+pub mod job_metrics;
+pub mod job_query;
+pub mod job_retention;
+pub mod labels;
+pub mod lifecycle_event;
+pub mod maintenance_window;
 pub mod monitor;
+pub mod monitor_metrics;
+pub mod schedule;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
 
-pub use alert_config::{AlertConfig, AlertType, AppliedMonitor, SlackAlertConfig};
-pub use api_key::ApiKey;
+pub use activity_event::{Activity, ActivityEvent, AlertKind};
+pub use alert_config::{
+    AlertConfig, AlertType, AppliedMonitor, EmailAlertConfig, PagerDutyAlertConfig,
+    SlackAlertConfig, SmtpConfig, SmtpTls, WebhookAlertConfig, WebhookMethod,
+};
+pub use alert_threshold::{AlertThreshold, FailureCount, FailureWindow};
+pub use anomaly_detection::{AnomalyDetection, DurationAnomaly};
+pub use api_key::{ApiKey, ApiKeyScope};
+pub use audit_event::{Actor, AuditAction, AuditEntity, AuditEvent, Audited, EntityType};
+pub use audit_query::{AuditPage, AuditQuery};
+pub use auto_tune::AutoTune;
+pub use duration_stats::{DurationStats, SuggestedDurations};
 pub use job::{EndState, Job};
-pub use monitor::Monitor;
+pub use job_metadata::JobMetadata;
+pub use job_metrics::{JobMetrics, MetricPoint};
+pub use job_query::{JobOrder, JobPage, JobQuery, JobStatus};
+pub use job_retention::JobRetention;
+pub use labels::{LabelSelector, Labels};
+pub use lifecycle_event::{EventType, LifecycleEvent};
+pub use maintenance_window::MaintenanceWindow;
+pub use monitor::{ConcurrencyPolicy, Monitor, MonitorKind, MonitorSettings};
+pub use monitor_metrics::MonitorMetrics;
+pub use schedule::Schedule;
+pub use webhook_delivery::{DeliveryAttempt, DeliveryStatus, WebhookDelivery};
+pub use webhook_subscription::WebhookSubscription;
@@ -1,8 +1,14 @@
-use chrono::Duration;
+use chrono::{Duration, NaiveDateTime, Utc};
 use serde::Serialize;
 use uuid::Uuid;
 
-use crate::domain::models::Job;
+pub use cron_mon_client::types::{ConcurrencyPolicy, MonitorKind};
+
+use crate::domain::models::{
+    AlertThreshold, AnomalyDetection, AutoTune, DurationAnomaly, FailureCount, Job, JobMetadata,
+    JobRetention, Labels, MaintenanceWindow, Schedule,
+};
+use crate::domain::services::duration_stats::calculate_duration_stats;
 use crate::errors::Error;
 
 /// The `Monitor` struct represents a Monitor for cron jobs and the like, and is ultimately the core
@@ -21,28 +27,289 @@ pub struct Monitor {
     /// The amount of time, in seconds, to allow the monitored cronjob to overrun by before
     /// considering them late.
     pub grace_duration: i32,
+    /// The kind of Monitor, which determines how it expects to hear from the monitored cronjob.
+    pub kind: MonitorKind,
+    /// The cron schedule that the monitored cronjob is expected to start on, if it has one.
+    pub schedule: Option<Schedule>,
+    /// Scheduled starts at or before this time are never considered to have been missed. This is
+    /// moved forward whenever a missed start is alerted on, so that each missed start is only
+    /// alerted on once, and whenever the schedule is changed, so that runs which were scheduled
+    /// before the schedule existed aren't considered missed. Heartbeat Monitors use this in the
+    /// same way for missed heartbeats.
+    #[serde(skip_serializing)]
+    pub missed_start_cutoff: Option<NaiveDateTime>,
+    /// How many late or errored Jobs the Monitor must see before it alerts on them.
+    pub alert_threshold: AlertThreshold,
+    /// How many of the Monitor's Jobs are kept, and for how long, if they aren't kept forever.
+    pub retention: Option<JobRetention>,
+    /// Free-form key/value pairs describing the Monitor, which it can be selected by.
+    pub labels: Labels,
+    /// Whether or not the Monitor has been paused, in which case it doesn't alert. Note that a
+    /// paused Monitor with a `resume_at` time that has passed is no longer paused.
+    pub paused: bool,
+    /// When a paused Monitor is automatically resumed, if it is.
+    pub resume_at: Option<NaiveDateTime>,
+    /// Recurring windows of planned downtime, during which the Monitor doesn't alert.
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// If set, the bounds within which the expected and grace durations are automatically tuned
+    /// to suit how long the monitored cronjob takes.
+    pub auto_tune: Option<AutoTune>,
+    /// If set, how Jobs that succeeded, but took an anomalous amount of time, are detected.
+    pub anomaly_detection: Option<AnomalyDetection>,
+    /// What happens when a Job starts while another of the Monitor's Jobs is still in progress.
+    pub concurrency_policy: ConcurrencyPolicy,
     /// The history of jobs that have been monitored.
     pub jobs: Vec<Job>,
 }
 
+/// The settings of a Monitor beyond its name and durations. By default, a Monitor has no schedule,
+/// isn't a heartbeat Monitor, alerts on every late or errored Job, keeps its Jobs forever and
+/// allows them to overlap, and has no labels, maintenance windows, auto-tuning or anomaly
+/// detection.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MonitorSettings {
+    pub schedule: Option<Schedule>,
+    pub kind: MonitorKind,
+    pub alert_threshold: AlertThreshold,
+    pub retention: Option<JobRetention>,
+    pub labels: Labels,
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    pub auto_tune: Option<AutoTune>,
+    pub anomaly_detection: Option<AnomalyDetection>,
+    pub concurrency_policy: ConcurrencyPolicy,
+}
+
 impl Monitor {
     /// Instatiate a new Monitor.
-    pub fn new(tenant: String, name: String, expected_duration: i32, grace_duration: i32) -> Self {
-        Self {
+    pub fn new(
+        tenant: String,
+        name: String,
+        expected_duration: i32,
+        grace_duration: i32,
+        settings: MonitorSettings,
+    ) -> Result<Self, Error> {
+        let MonitorSettings {
+            schedule,
+            kind,
+            alert_threshold,
+            retention,
+            labels,
+            maintenance_windows,
+            auto_tune,
+            anomaly_detection,
+            concurrency_policy,
+        } = settings;
+        Self::validate_kind(&kind)?;
+
+        let mut monitor = Self {
             monitor_id: Uuid::new_v4(),
             tenant,
             name,
             expected_duration,
             grace_duration,
+            kind,
+            schedule,
+            missed_start_cutoff: None,
+            alert_threshold,
+            retention,
+            labels,
+            paused: false,
+            resume_at: None,
+            maintenance_windows,
+            auto_tune,
+            anomaly_detection,
+            concurrency_policy,
             jobs: vec![],
-        }
+        };
+        monitor.reset_missed_start_cutoff();
+        Ok(monitor)
     }
 
     /// Modify the Monitor's details.
-    pub fn edit_details(&mut self, name: String, expected_duration: i32, grace_duration: i32) {
+    pub fn edit_details(
+        &mut self,
+        name: String,
+        expected_duration: i32,
+        grace_duration: i32,
+        settings: MonitorSettings,
+    ) -> Result<(), Error> {
+        let MonitorSettings {
+            schedule,
+            kind,
+            alert_threshold,
+            retention,
+            labels,
+            maintenance_windows,
+            auto_tune,
+            anomaly_detection,
+            concurrency_policy,
+        } = settings;
+        Self::validate_kind(&kind)?;
+
         self.name = name;
         self.expected_duration = expected_duration;
         self.grace_duration = grace_duration;
+        self.alert_threshold = alert_threshold;
+        self.retention = retention;
+        self.labels = labels;
+        self.maintenance_windows = maintenance_windows;
+        self.auto_tune = auto_tune;
+
+        // Jobs that finished before anomalies were being detected aren't checked, so that turning
+        // anomaly detection on doesn't alert on the Monitor's entire history.
+        if self.anomaly_detection.is_none() && anomaly_detection.is_some() {
+            for job in self.jobs.iter_mut().filter(|job| !job.in_progress()) {
+                job.anomaly_checked = true;
+            }
+        }
+        self.anomaly_detection = anomaly_detection;
+
+        // Likewise, jobs that overlapped before overlaps were being alerted on aren't alerted on.
+        if self.concurrency_policy != ConcurrencyPolicy::Alert
+            && concurrency_policy == ConcurrencyPolicy::Alert
+        {
+            for job in self.jobs.iter_mut().filter(|job| job.overlapped) {
+                job.overlap_alert_sent = true;
+            }
+        }
+        self.concurrency_policy = concurrency_policy;
+
+        if self.schedule != schedule || self.kind != kind {
+            self.schedule = schedule;
+            self.kind = kind;
+            self.reset_missed_start_cutoff();
+        }
+
+        Ok(())
+    }
+
+    /// Check that a kind of Monitor is valid. Note that this will return an `Error` if a
+    /// heartbeat Monitor's period is less than a second, since it would never stop missing
+    /// heartbeats.
+    pub fn validate_kind(kind: &MonitorKind) -> Result<(), Error> {
+        match kind {
+            MonitorKind::Heartbeat { period } if *period < 1 => Err(Error::InvalidMonitor(
+                format!("Invalid heartbeat period '{period}', must be at least 1 second"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that a time to automatically resume a Monitor at is valid. Note that this will return
+    /// an `Error` if `resume_at` isn't in the future.
+    pub fn validate_resume_at(resume_at: Option<NaiveDateTime>) -> Result<(), Error> {
+        match resume_at {
+            Some(resume_at) if resume_at <= Utc::now().naive_utc() => Err(Error::InvalidMonitor(
+                format!("Invalid resume time '{resume_at}', must be in the future"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Pause the Monitor, so that it doesn't alert until it's resumed, either explicitly or
+    /// automatically at `resume_at`. Note that this will return an `Error` if `resume_at` isn't in
+    /// the future.
+    pub fn pause(&mut self, resume_at: Option<NaiveDateTime>) -> Result<(), Error> {
+        Self::validate_resume_at(resume_at)?;
+
+        self.paused = true;
+        self.resume_at = resume_at;
+        Ok(())
+    }
+
+    /// Resume the Monitor, so that it alerts again.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.resume_at = None;
+    }
+
+    /// Ascertain whether or not the Monitor is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+            && self
+                .resume_at
+                .is_none_or(|resume_at| Utc::now().naive_utc() < resume_at)
+    }
+
+    /// Ascertain whether or not the Monitor is currently paused or within one of its maintenance
+    /// windows, in which case its alerts are suppressed.
+    pub fn in_maintenance(&self) -> bool {
+        let now = Utc::now().naive_utc();
+        self.is_paused()
+            || self
+                .maintenance_windows
+                .iter()
+                .any(|window| window.is_active_at(now))
+    }
+
+    /// Ascertain whether or not this is a heartbeat Monitor.
+    pub fn is_heartbeat(&self) -> bool {
+        matches!(self.kind, MonitorKind::Heartbeat { .. })
+    }
+
+    /// Retrieve the most recent scheduled start that has been missed, if there is one that hasn't
+    /// already been alerted on.
+    ///
+    /// A scheduled start is considered missed once `grace_duration` has passed since the time it
+    /// was scheduled for, without a Job having been started within `grace_duration` of that time.
+    /// Heartbeat Monitors without a schedule expect a heartbeat `period` seconds after the last
+    /// one (or the last missed heartbeat), and other Monitors without a schedule never miss a
+    /// start.
+    pub fn missed_start(&self) -> Option<NaiveDateTime> {
+        match (&self.schedule, &self.kind) {
+            (Some(schedule), _) => self.missed_scheduled_start(schedule),
+            (None, MonitorKind::Heartbeat { period }) => self.missed_heartbeat(*period),
+            (None, MonitorKind::Job) => None,
+        }
+    }
+
+    fn missed_scheduled_start(&self, schedule: &Schedule) -> Option<NaiveDateTime> {
+        let grace = Duration::seconds(self.grace_duration as i64);
+
+        let scheduled_start = schedule.previous_run(Utc::now().naive_utc() - grace)?;
+        if let Some(cutoff) = self.missed_start_cutoff {
+            if scheduled_start <= cutoff {
+                return None;
+            }
+        }
+
+        let window_start = scheduled_start - grace;
+        if self.jobs.iter().any(|job| job.start_time >= window_start) {
+            None
+        } else {
+            Some(scheduled_start)
+        }
+    }
+
+    fn missed_heartbeat(&self, period: i32) -> Option<NaiveDateTime> {
+        // Whichever is most recent out of the last heartbeat and the cutoff is what the next
+        // heartbeat is due relative to, so that missed heartbeats are alerted on once per period.
+        let last_heartbeat = self.last_started_job().map(|job| job.start_time);
+        let since = match (last_heartbeat, self.missed_start_cutoff) {
+            (Some(heartbeat), Some(cutoff)) => heartbeat.max(cutoff),
+            (heartbeat, cutoff) => heartbeat.or(cutoff)?,
+        };
+
+        let expected_heartbeat = since + Duration::seconds(period as i64);
+        let grace = Duration::seconds(self.grace_duration as i64);
+        if expected_heartbeat + grace < Utc::now().naive_utc() {
+            Some(expected_heartbeat)
+        } else {
+            None
+        }
+    }
+
+    /// Record that a missed start has been alerted on, so that it isn't alerted on again.
+    pub fn missed_start_alerted(&mut self, scheduled_start: NaiveDateTime) {
+        self.missed_start_cutoff = Some(scheduled_start);
+    }
+
+    fn reset_missed_start_cutoff(&mut self) {
+        self.missed_start_cutoff = if self.schedule.is_some() || self.is_heartbeat() {
+            Some(Utc::now().naive_utc())
+        } else {
+            None
+        };
     }
 
     /// Retrieve the jobs currently in progress.
@@ -59,13 +326,165 @@ impl Monitor {
         self.jobs.iter_mut().filter(|job| job.late()).collect()
     }
 
-    /// Retrieve jobs that are late or have finished with an error, that have pending alerts.
-    pub fn jobs_pending_alerts(&mut self) -> Vec<&mut Job> {
+    /// Retrieve jobs that are late or have finished with an error, that have pending alerts,
+    /// along with the late or errored jobs counted towards the Monitor's alert threshold.
+    ///
+    /// Jobs are only pending alerts once the alert threshold has been reached, counting each job
+    /// along with those that preceded it.
+    pub fn jobs_pending_alerts(&mut self) -> Vec<(&mut Job, FailureCount)> {
+        let failure_counts = self.evaluate_alert_threshold();
+
+        self.jobs
+            .iter_mut()
+            .zip(failure_counts)
+            .filter_map(|(job, failure_count)| failure_count.flatten().map(|count| (job, count)))
+            .collect()
+    }
+
+    /// Retrieve jobs that are late or have finished with an error, that haven't been alerted on,
+    /// and never will be since they fell short of the Monitor's alert threshold.
+    ///
+    /// Since the threshold is evaluated against each job along with those that preceded it, this
+    /// can't change once those jobs have all either finished or become late. Until then, the jobs
+    /// are left to be evaluated again.
+    pub fn jobs_below_alert_threshold(&mut self) -> Vec<&mut Job> {
+        let failure_counts = self.evaluate_alert_threshold();
+        // Jobs are ordered from newest to oldest, so we work back from the oldest job to find
+        // those preceded by jobs that are still running on time.
+        let mut settled = vec![false; self.jobs.len()];
+        let mut preceded_by_running_job = false;
+        for (i, job) in self.jobs.iter().enumerate().rev() {
+            settled[i] = !preceded_by_running_job;
+            preceded_by_running_job |= job.in_progress() && !job.late();
+        }
+
+        self.jobs
+            .iter_mut()
+            .zip(failure_counts.into_iter().zip(settled))
+            .filter_map(|(job, (failure_count, settled))| {
+                (failure_count == Some(None) && settled).then_some(job)
+            })
+            .collect()
+    }
+
+    /// Evaluate the Monitor's alert threshold for each job that is late or has finished with an
+    /// error, and hasn't yet been alerted on for it, giving `None` for all other jobs.
+    fn evaluate_alert_threshold(&self) -> Vec<Option<Option<FailureCount>>> {
+        // Jobs are ordered from newest to oldest, so the jobs preceding each job are those that
+        // follow it.
+        self.jobs
+            .iter()
+            .enumerate()
+            .map(|(i, job)| {
+                ((!job.late_alert_sent && job.late()) || (!job.error_alert_sent && job.errored()))
+                    .then(|| self.alert_threshold.evaluate(&self.jobs[i..]))
+            })
+            .collect()
+    }
+
+    /// Retrieve jobs that were alerted on for being late, and have since finished, so that the
+    /// late alert can be resolved.
+    pub fn jobs_pending_late_resolution(&mut self) -> Vec<&mut Job> {
         self.jobs
             .iter_mut()
-            .filter(|job| {
-                (!job.late_alert_sent && job.late()) || (!job.error_alert_sent && job.errored())
+            .filter(|job| job.late_alert_sent && !job.late_alert_resolved && !job.in_progress())
+            .collect()
+    }
+
+    /// Retrieve jobs that were alerted on for finishing with an error, and that have since been
+    /// followed by a successful job, so that the error alert can be resolved.
+    pub fn jobs_pending_error_resolution(&mut self) -> Vec<&mut Job> {
+        // Jobs are ordered from newest to oldest, so any job after the most recent successful job
+        // has been followed by a success.
+        let Some(last_success) = self.jobs.iter().position(|job| job.succeeded()) else {
+            return Vec::new();
+        };
+
+        self.jobs
+            .iter_mut()
+            .skip(last_success + 1)
+            .filter(|job| job.error_alert_sent && !job.error_alert_resolved)
+            .collect()
+    }
+
+    /// Retrieve jobs that succeeded on time straight after a job that was alerted on for being
+    /// late or errored, that have pending recovery alerts.
+    ///
+    /// Late or errored jobs that didn't reach the alert threshold, or that ran during
+    /// maintenance, aren't recovered from, since they were never alerted on.
+    pub fn jobs_pending_recovery_alerts(&mut self) -> Vec<&mut Job> {
+        // Jobs are ordered from newest to oldest, so the job preceding each job is the next one
+        // along.
+        let recovered: Vec<bool> = self
+            .jobs
+            .iter()
+            .enumerate()
+            .map(|(i, job)| {
+                !job.recovery_alert_sent
+                    && job.succeeded()
+                    && !job.late()
+                    && self.jobs.get(i + 1).is_some_and(|previous| {
+                        !previous.during_maintenance
+                            && !previous.below_threshold
+                            && (previous.late_alert_sent || previous.error_alert_sent)
+                    })
+            })
+            .collect();
+
+        self.jobs
+            .iter_mut()
+            .zip(recovered)
+            .filter_map(|(job, recovered)| recovered.then_some(job))
+            .collect()
+    }
+
+    /// Retrieve jobs that started while another job was still in progress, that have pending
+    /// overlap alerts. Only Monitors that alert on overlapping jobs have any.
+    pub fn jobs_pending_overlap_alerts(&mut self) -> Vec<&mut Job> {
+        if self.concurrency_policy != ConcurrencyPolicy::Alert {
+            return Vec::new();
+        }
+
+        self.jobs
+            .iter_mut()
+            .filter(|job| job.overlapped && !job.overlap_alert_sent)
+            .collect()
+    }
+
+    /// Retrieve jobs that have succeeded, but are yet to be checked for having taken an anomalous
+    /// amount of time, along with the anomaly each exhibits, if any.
+    ///
+    /// Each job is compared against the jobs that preceded it. Heartbeat Monitors, and those
+    /// without anomaly detection, have no jobs pending anomaly checks.
+    pub fn jobs_pending_anomaly_checks(&mut self) -> Vec<(&mut Job, Option<DurationAnomaly>)> {
+        let Some(detection) = self
+            .anomaly_detection
+            .as_ref()
+            .filter(|_| !self.is_heartbeat())
+        else {
+            return Vec::new();
+        };
+
+        // Jobs are ordered from newest to oldest, so the jobs preceding each job are those that
+        // follow it.
+        let anomalies: Vec<Option<Option<DurationAnomaly>>> = self
+            .jobs
+            .iter()
+            .enumerate()
+            .map(|(i, job)| {
+                (!job.anomaly_checked && job.succeeded()).then(|| {
+                    let stats = detection
+                        .max_deviations
+                        .and_then(|_| calculate_duration_stats(&self.jobs[i + 1..]));
+                    detection.check(job, stats.as_ref())
+                })
             })
+            .collect();
+
+        self.jobs
+            .iter_mut()
+            .zip(anomalies)
+            .filter_map(|(job, anomaly)| anomaly.map(|anomaly| (job, anomaly)))
             .collect()
     }
 
@@ -85,13 +504,35 @@ impl Monitor {
         }
     }
 
-    /// Start a new job
-    pub fn start_job(&mut self) -> Job {
+    /// Start a new job. Note that this will return an `Error` if another job is still in progress
+    /// and the Monitor rejects overlapping jobs.
+    pub fn start_job(&mut self) -> Result<Job, Error> {
+        let overlapped = self.jobs.iter().any(|job| job.in_progress());
+        if overlapped && self.concurrency_policy == ConcurrencyPolicy::Reject {
+            return Err(Error::JobInProgress(self.monitor_id));
+        }
+
         // We give the job the _current_ maximum duration here so that if the monitor is modified,
         // any previous and in progress jobs are not affected.
-        let new_job = Job::start(self.maximum_duration().num_seconds() as u64);
+        let mut new_job = Job::start(self.maximum_duration().num_seconds() as u64);
+        new_job.during_maintenance = self.in_maintenance();
+        new_job.overlapped = overlapped;
         self.jobs.push(new_job.clone());
-        new_job
+        Ok(new_job)
+    }
+
+    /// Record a heartbeat, as a Job that has already finished successfully. Note that this will
+    /// return an `Error` if this isn't a heartbeat Monitor.
+    pub fn record_heartbeat(&mut self) -> Result<Job, Error> {
+        if !self.is_heartbeat() {
+            return Err(Error::NotHeartbeatMonitor(self.monitor_id));
+        }
+
+        let mut heartbeat = Job::start(self.maximum_duration().num_seconds() as u64);
+        heartbeat.during_maintenance = self.in_maintenance();
+        heartbeat.finish(true, None, JobMetadata::default())?;
+        self.jobs.push(heartbeat.clone());
+        Ok(heartbeat)
     }
 
     /// Finish a job. Note that this will return an `Error` is a Job with the given `job_id`
@@ -101,12 +542,17 @@ impl Monitor {
         job_id: Uuid,
         succeeded: bool,
         output: Option<String>,
+        metadata: JobMetadata,
     ) -> Result<&Job, Error> {
         let monitor_id = self.monitor_id;
+        let in_maintenance = self.in_maintenance();
         let job = self.get_job(job_id);
         match job {
             Some(j) => {
-                j.finish(succeeded, output)?;
+                j.finish(succeeded, output, metadata)?;
+                // Jobs that overlap maintenance at either end are considered to have run during
+                // it.
+                j.during_maintenance |= in_maintenance;
                 Ok(j)
             }
             None => Err(Error::JobNotFound(monitor_id, job_id)),
@@ -125,6 +571,7 @@ impl Monitor {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
 
     use chrono::NaiveDateTime;
     use rstest::rstest;
@@ -137,12 +584,20 @@ mod tests {
 
     #[test]
     fn creating_new_monitors() {
-        let mon = Monitor::new("foo-tenant".to_owned(), "new-monitor".to_owned(), 3600, 600);
+        let mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
 
         assert_eq!(mon.tenant, "foo-tenant".to_owned());
         assert_eq!(mon.name, "new-monitor".to_owned());
         assert_eq!(mon.expected_duration, 3600);
         assert_eq!(mon.grace_duration, 600);
+        assert_eq!(mon.kind, MonitorKind::Job);
         assert!(mon.jobs_in_progress().is_empty());
         assert!(mon.jobs.is_empty());
     }
@@ -188,7 +643,14 @@ mod tests {
         #[case] input: Vec<(Uuid, NaiveDateTime)>,
         #[case] expected_ids: Vec<Uuid>,
     ) {
-        let mut mon = Monitor::new("foo-tenant".to_owned(), "new-monitor".to_owned(), 200, 100);
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
         mon.jobs = input
             .iter()
             .map(|i| Job {
@@ -198,6 +660,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             })
             .collect();
 
@@ -216,9 +686,18 @@ mod tests {
                     end_time: gen_relative_datetime(-5),
                     succeeded: true,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("15904641-2d0e-4d27-8fd0-b130f0ab5aa9"),
@@ -227,6 +706,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             }
         ],
         vec![]
@@ -240,6 +727,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("15904641-2d0e-4d27-8fd0-b130f0ab5aa9"),
@@ -248,6 +743,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: true,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             }
         ],
         vec![gen_uuid("79192674-0e87-4f79-b988-0efd5ae76420")]
@@ -262,9 +765,18 @@ mod tests {
                     end_time: gen_relative_datetime(-5),
                     succeeded: false,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("15904641-2d0e-4d27-8fd0-b130f0ab5aa9"),
@@ -274,9 +786,18 @@ mod tests {
                     end_time: gen_relative_datetime(-5),
                     succeeded: false,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: true,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             }
         ],
         vec![
@@ -293,9 +814,18 @@ mod tests {
                     end_time: gen_relative_datetime(5),
                     succeeded: true,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("15904641-2d0e-4d27-8fd0-b130f0ab5aa9"),
@@ -305,9 +835,18 @@ mod tests {
                     end_time: gen_relative_datetime(5),
                     succeeded: true,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: true,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("b1d00389-9c4e-43ab-9091-ae1be943629c"),
@@ -317,9 +856,18 @@ mod tests {
                     end_time: gen_relative_datetime(-5),
                     succeeded: false,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("8e60869e-700e-4fa0-831b-31d37ab8f2ae"),
@@ -329,9 +877,18 @@ mod tests {
                     end_time: gen_relative_datetime(-5),
                     succeeded: false,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: true,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             }
         ],
         vec![
@@ -343,18 +900,444 @@ mod tests {
         #[case] jobs: Vec<Job>,
         #[case] expected_ids: Vec<Uuid>,
     ) {
-        let mut mon = Monitor::new("foo-tenant".to_owned(), "new-monitor".to_owned(), 200, 100);
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
         mon.jobs = jobs;
 
         let jobs_pending_alerts = mon.jobs_pending_alerts();
 
-        let jobs_ids: Vec<Uuid> = jobs_pending_alerts.iter().map(|job| job.job_id).collect();
+        let jobs_ids: Vec<Uuid> = jobs_pending_alerts
+            .iter()
+            .map(|(job, _)| job.job_id)
+            .collect();
         assert_eq!(jobs_ids, expected_ids);
     }
 
+    #[test]
+    fn retrieving_jobs_with_pending_alerts_after_threshold() {
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings {
+                alert_threshold: AlertThreshold::new(2, None).unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // From newest to oldest: two failures in a row, then a success and a lone failure.
+        mon.jobs = [
+            ("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20", false),
+            ("4e8a2c6f-1d3b-4a5c-9e7f-0b2d4f6a8c10", false),
+            ("b7d1f3a5-6c8e-4b0a-8d2f-5e7a9c1b3d40", true),
+            ("2c4e6a8b-0d1f-4e3a-b5c7-9d1e3f5a7b60", false),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (job_id, succeeded))| {
+            let start = -100 * (i as i64 + 1);
+            Job {
+                job_id: gen_uuid(job_id),
+                start_time: gen_relative_datetime(start),
+                max_end_time: gen_relative_datetime(start + 50),
+                end_state: Some(EndState {
+                    end_time: gen_relative_datetime(start + 40),
+                    succeeded,
+                    output: None,
+                    metadata: JobMetadata::default(),
+                }),
+                late_alert_sent: false,
+                error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
+            }
+        })
+        .collect();
+
+        let jobs_pending_alerts = mon.jobs_pending_alerts();
+
+        // Only the second failure in a row reaches the threshold.
+        assert_eq!(jobs_pending_alerts.len(), 1);
+        let (job, failure_count) = &jobs_pending_alerts[0];
+        assert_eq!(job.job_id, gen_uuid("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20"));
+        assert_eq!(
+            *failure_count,
+            FailureCount {
+                consecutive: 2,
+                window: None
+            }
+        );
+    }
+
+    fn gen_unalerted_job(job_id: &str, start: i64, end_state: Option<bool>) -> Job {
+        Job {
+            late_alert_sent: false,
+            error_alert_sent: false,
+            ..gen_alerted_job(job_id, start, end_state)
+        }
+    }
+
+    fn monitor_with_alert_threshold(consecutive: i32) -> Monitor {
+        Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings {
+                alert_threshold: AlertThreshold::new(consecutive, None).unwrap(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn retrieving_jobs_below_alert_threshold() {
+        let mut mon = monitor_with_alert_threshold(2);
+        // From newest to oldest: two failures in a row, then a success and a lone failure.
+        mon.jobs = vec![
+            gen_unalerted_job("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20", -100, Some(false)),
+            gen_unalerted_job("4e8a2c6f-1d3b-4a5c-9e7f-0b2d4f6a8c10", -200, Some(false)),
+            Job {
+                max_end_time: gen_relative_datetime(-250),
+                ..gen_unalerted_job("b7d1f3a5-6c8e-4b0a-8d2f-5e7a9c1b3d40", -300, Some(true))
+            },
+            gen_unalerted_job("2c4e6a8b-0d1f-4e3a-b5c7-9d1e3f5a7b60", -400, Some(false)),
+        ];
+
+        let job_ids: Vec<Uuid> = mon
+            .jobs_below_alert_threshold()
+            .iter()
+            .map(|job| job.job_id)
+            .collect();
+
+        assert_eq!(
+            job_ids,
+            vec![
+                gen_uuid("4e8a2c6f-1d3b-4a5c-9e7f-0b2d4f6a8c10"),
+                gen_uuid("2c4e6a8b-0d1f-4e3a-b5c7-9d1e3f5a7b60"),
+            ]
+        );
+    }
+
+    #[test]
+    fn retrieving_jobs_below_alert_threshold_preceded_by_running_jobs() {
+        let mut mon = monitor_with_alert_threshold(2);
+        mon.jobs = vec![
+            gen_unalerted_job("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20", -100, Some(false)),
+            // Still running on time, so may yet become late.
+            Job {
+                max_end_time: gen_relative_datetime(100),
+                ..gen_unalerted_job("4e8a2c6f-1d3b-4a5c-9e7f-0b2d4f6a8c10", -200, None)
+            },
+        ];
+
+        assert!(mon.jobs_below_alert_threshold().is_empty());
+
+        // Once the running job becomes late, the failure after it reaches the threshold, while
+        // the late job falls short of it.
+        mon.jobs[1].max_end_time = gen_relative_datetime(-150);
+
+        let job_ids: Vec<Uuid> = mon
+            .jobs_pending_alerts()
+            .iter()
+            .map(|(job, _)| job.job_id)
+            .collect();
+        assert_eq!(
+            job_ids,
+            vec![gen_uuid("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20")]
+        );
+        let job_ids: Vec<Uuid> = mon
+            .jobs_below_alert_threshold()
+            .iter()
+            .map(|job| job.job_id)
+            .collect();
+        assert_eq!(
+            job_ids,
+            vec![gen_uuid("4e8a2c6f-1d3b-4a5c-9e7f-0b2d4f6a8c10")]
+        );
+    }
+
+    #[test]
+    fn jobs_below_alert_threshold_followed_by_more_failures() {
+        let mut mon = monitor_with_alert_threshold(2);
+        mon.jobs = vec![gen_unalerted_job(
+            "2c4e6a8b-0d1f-4e3a-b5c7-9d1e3f5a7b60",
+            -400,
+            Some(false),
+        )];
+
+        // The lone failure falls short of the threshold, so is never alerted on.
+        assert!(mon.jobs_pending_alerts().is_empty());
+        for job in mon.jobs_below_alert_threshold() {
+            job.late_alert_sent = true;
+            job.late_alert_resolved = true;
+            job.error_alert_sent = true;
+            job.error_alert_resolved = true;
+            job.below_threshold = true;
+        }
+        assert!(mon.jobs_below_alert_threshold().is_empty());
+
+        // But it still counts towards the threshold for the failures after it.
+        mon.jobs.insert(
+            0,
+            gen_unalerted_job("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20", -300, Some(false)),
+        );
+
+        assert!(mon.jobs_below_alert_threshold().is_empty());
+        let jobs_pending_alerts = mon.jobs_pending_alerts();
+        assert_eq!(jobs_pending_alerts.len(), 1);
+        let (job, failure_count) = &jobs_pending_alerts[0];
+        assert_eq!(job.job_id, gen_uuid("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20"));
+        assert_eq!(failure_count.consecutive, 2);
+    }
+
+    #[test]
+    fn jobs_below_alert_threshold_are_not_recovered_from() {
+        let mut mon = monitor_with_alert_threshold(2);
+        mon.jobs = vec![
+            Job {
+                max_end_time: gen_relative_datetime(-150),
+                ..gen_unalerted_job("9f3c1a4e-2b6d-4c8e-a1f0-3d5b7c9e1a20", -200, Some(true))
+            },
+            Job {
+                error_alert_sent: true,
+                error_alert_resolved: true,
+                below_threshold: true,
+                ..gen_unalerted_job("2c4e6a8b-0d1f-4e3a-b5c7-9d1e3f5a7b60", -400, Some(false))
+            },
+        ];
+
+        assert!(mon.jobs_pending_recovery_alerts().is_empty());
+    }
+
+    fn gen_alerted_job(job_id: &str, start: i64, end_state: Option<bool>) -> Job {
+        Job {
+            job_id: gen_uuid(job_id),
+            start_time: gen_relative_datetime(start),
+            max_end_time: gen_relative_datetime(start + 10),
+            end_state: end_state.map(|succeeded| EndState {
+                end_time: gen_relative_datetime(start + 20),
+                succeeded,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: true,
+            error_alert_sent: end_state == Some(false),
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[test]
+    fn retrieving_jobs_pending_late_resolution() {
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+        mon.jobs = vec![
+            // Still running, so can't be resolved yet.
+            gen_alerted_job("79192674-0e87-4f79-b988-0efd5ae76420", -100, None),
+            gen_alerted_job("15904641-2d0e-4d27-8fd0-b130f0ab5aa9", -200, Some(false)),
+            Job {
+                late_alert_resolved: true,
+                ..gen_alerted_job("b1d00389-9c4e-43ab-9091-ae1be943629c", -300, Some(true))
+            },
+            Job {
+                late_alert_sent: false,
+                ..gen_alerted_job("8e60869e-700e-4fa0-831b-31d37ab8f2ae", -400, Some(true))
+            },
+        ];
+
+        let job_ids: Vec<Uuid> = mon
+            .jobs_pending_late_resolution()
+            .iter()
+            .map(|job| job.job_id)
+            .collect();
+        assert_eq!(
+            job_ids,
+            vec![gen_uuid("15904641-2d0e-4d27-8fd0-b130f0ab5aa9")]
+        );
+    }
+
+    #[rstest]
+    #[case::no_success(vec![None, Some(false), Some(false)], vec![])]
+    #[case::success_before_errors(vec![Some(false), Some(false), Some(true)], vec![])]
+    #[case::success_after_errors(
+        vec![None, Some(true), Some(false), Some(false)],
+        vec!["cd2d6d4c-4bd0-4f0d-8f56-6dc4bc7a8f5c", "7c1bd0c8-8ecc-4d6f-a0a4-8d0b8ebc5c5b"]
+    )]
+    #[case::error_between_successes(
+        vec![Some(false), Some(true), Some(false), Some(true)],
+        vec!["cd2d6d4c-4bd0-4f0d-8f56-6dc4bc7a8f5c"]
+    )]
+    fn retrieving_jobs_pending_error_resolution(
+        #[case] end_states: Vec<Option<bool>>,
+        #[case] expected_ids: Vec<&str>,
+    ) {
+        let job_ids = [
+            "1a0e8b9f-0b5e-4d7c-9a39-3b1c6a6a1a01",
+            "5f5b6d0e-7b7f-4b4e-8f3e-0c0d7e6a9b02",
+            "cd2d6d4c-4bd0-4f0d-8f56-6dc4bc7a8f5c",
+            "7c1bd0c8-8ecc-4d6f-a0a4-8d0b8ebc5c5b",
+        ];
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+        mon.jobs = end_states
+            .into_iter()
+            .enumerate()
+            .map(|(i, end_state)| gen_alerted_job(job_ids[i], -100 * (i as i64 + 1), end_state))
+            .collect();
+
+        let pending_ids: Vec<Uuid> = mon
+            .jobs_pending_error_resolution()
+            .iter()
+            .map(|job| job.job_id)
+            .collect();
+        assert_eq!(
+            pending_ids,
+            expected_ids
+                .into_iter()
+                .map(gen_uuid)
+                .collect::<Vec<Uuid>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_erroneous_jobs(vec![Some((true, false)), Some((true, false))], true, vec![])]
+    #[case::success_after_error(
+        vec![None, Some((true, false)), Some((false, false)), Some((false, false))],
+        true,
+        vec!["5f5b6d0e-7b7f-4b4e-8f3e-0c0d7e6a9b02"]
+    )]
+    #[case::success_after_late_job(
+        vec![Some((true, false)), Some((true, true))],
+        true,
+        vec!["1a0e8b9f-0b5e-4d7c-9a39-3b1c6a6a1a01"]
+    )]
+    #[case::late_success_after_error(vec![Some((true, true)), Some((false, false))], true, vec![])]
+    #[case::still_erroring(vec![Some((false, false)), Some((true, false))], true, vec![])]
+    #[case::multiple_recoveries(
+        vec![Some((true, false)), Some((false, false)), Some((true, false)), Some((true, true))],
+        true,
+        vec!["1a0e8b9f-0b5e-4d7c-9a39-3b1c6a6a1a01", "cd2d6d4c-4bd0-4f0d-8f56-6dc4bc7a8f5c"]
+    )]
+    // Erroneous jobs that were never alerted on (i.e. below the alert threshold) can't be
+    // recovered from.
+    #[case::success_after_unalerted_error(
+        vec![Some((true, false)), Some((false, false))],
+        false,
+        vec![]
+    )]
+    fn retrieving_jobs_pending_recovery_alerts(
+        #[case] end_states: Vec<Option<(bool, bool)>>,
+        #[case] alerted: bool,
+        #[case] expected_ids: Vec<&str>,
+    ) {
+        let job_ids = [
+            "1a0e8b9f-0b5e-4d7c-9a39-3b1c6a6a1a01",
+            "5f5b6d0e-7b7f-4b4e-8f3e-0c0d7e6a9b02",
+            "cd2d6d4c-4bd0-4f0d-8f56-6dc4bc7a8f5c",
+            "7c1bd0c8-8ecc-4d6f-a0a4-8d0b8ebc5c5b",
+        ];
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+        // Each end state is whether the job succeeded, and whether it finished late. Late and
+        // errored jobs are alerted on if `alerted` is set.
+        mon.jobs = end_states
+            .into_iter()
+            .enumerate()
+            .map(|(i, end_state)| {
+                let start = -100 * (i as i64 + 1);
+                Job {
+                    job_id: gen_uuid(job_ids[i]),
+                    start_time: gen_relative_datetime(start),
+                    max_end_time: gen_relative_datetime(start + 50),
+                    end_state: end_state.map(|(succeeded, late)| EndState {
+                        end_time: gen_relative_datetime(start + if late { 60 } else { 40 }),
+                        succeeded,
+                        output: None,
+                        metadata: JobMetadata::default(),
+                    }),
+                    late_alert_sent: alerted && end_state.is_some_and(|(_, late)| late),
+                    error_alert_sent: alerted && end_state.is_some_and(|(succeeded, _)| !succeeded),
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
+                }
+            })
+            .collect();
+
+        let pending_ids: Vec<Uuid> = mon
+            .jobs_pending_recovery_alerts()
+            .iter()
+            .map(|job| job.job_id)
+            .collect();
+        assert_eq!(
+            pending_ids,
+            expected_ids
+                .into_iter()
+                .map(gen_uuid)
+                .collect::<Vec<Uuid>>()
+        );
+
+        // Recovery alerts are only ever sent once.
+        for job in mon.jobs_pending_recovery_alerts() {
+            job.recovery_alert_sent = true;
+        }
+        assert!(mon.jobs_pending_recovery_alerts().is_empty());
+    }
+
     #[test]
     fn getting_the_last_finished_job() {
-        let mut mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 200, 100);
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
         mon.jobs = vec![
             Job {
                 job_id: gen_uuid("70e7f11b-7ae3-4e69-adb0-52fdbf775ee1"),
@@ -363,6 +1346,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("139fbf11-eff1-44cf-9f58-b5febb4729d6"),
@@ -372,9 +1363,18 @@ mod tests {
                     end_time: gen_relative_datetime(0),
                     succeeded: true,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("a4a8d5ac-86c1-448d-aa82-3388d59ac43e"),
@@ -384,9 +1384,18 @@ mod tests {
                     end_time: gen_relative_datetime(-50),
                     succeeded: false,
                     output: None,
+                    metadata: JobMetadata::default(),
                 }),
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
         ];
 
@@ -399,7 +1408,14 @@ mod tests {
 
     #[test]
     fn getting_the_last_finished_job_when_no_jobs_have_finished() {
-        let mut mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 200, 100);
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
         mon.jobs = vec![
             Job {
                 job_id: gen_uuid("70e7f11b-7ae3-4e69-adb0-52fdbf775ee1"),
@@ -408,6 +1424,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("139fbf11-eff1-44cf-9f58-b5febb4729d6"),
@@ -416,6 +1440,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("a4a8d5ac-86c1-448d-aa82-3388d59ac43e"),
@@ -424,6 +1456,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
         ];
 
@@ -433,7 +1473,14 @@ mod tests {
 
     #[test]
     fn getting_the_last_started_job() {
-        let mut mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 200, 100);
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
         mon.jobs = vec![
             Job {
                 job_id: gen_uuid("70e7f11b-7ae3-4e69-adb0-52fdbf775ee1"),
@@ -442,6 +1489,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("139fbf11-eff1-44cf-9f58-b5febb4729d6"),
@@ -450,6 +1505,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
             Job {
                 job_id: gen_uuid("a4a8d5ac-86c1-448d-aa82-3388d59ac43e"),
@@ -458,6 +1521,14 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             },
         ];
 
@@ -470,7 +1541,14 @@ mod tests {
 
     #[test]
     fn getting_the_last_started_job_when_no_jobs_have_started() {
-        let mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 200, 100);
+        let mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
 
         let last_started_job = mon.last_started_job();
         assert!(last_started_job.is_none());
@@ -478,24 +1556,475 @@ mod tests {
 
     #[test]
     fn editing_monitors() {
-        let mut mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 3600, 600);
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
 
-        mon.edit_details("new-name".to_owned(), 360, 60);
+        mon.edit_details(
+            "new-name".to_owned(),
+            360,
+            60,
+            MonitorSettings {
+                alert_threshold: AlertThreshold::new(3, None).unwrap(),
+                retention: Some(JobRetention::new(Some(30), None).unwrap()),
+                labels: Labels::new(BTreeMap::from([("team".to_owned(), "payments".to_owned())]))
+                    .unwrap(),
+                maintenance_windows: vec![MaintenanceWindow::new("0 2 * * 0", None, 60).unwrap()],
+                auto_tune: Some(AutoTune::new(60, 600, 0, 120).unwrap()),
+                anomaly_detection: Some(AnomalyDetection::new(Some(120), None).unwrap()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
         assert_eq!(mon.name, "new-name".to_owned());
         assert_eq!(mon.expected_duration, 360);
         assert_eq!(mon.grace_duration, 60);
+        assert_eq!(mon.alert_threshold, AlertThreshold::new(3, None).unwrap());
+        assert_eq!(
+            mon.retention,
+            Some(JobRetention::new(Some(30), None).unwrap())
+        );
+        assert_eq!(mon.labels.get("team"), Some("payments"));
+        assert_eq!(
+            mon.maintenance_windows,
+            vec![MaintenanceWindow::new("0 2 * * 0", None, 60).unwrap()]
+        );
+        assert_eq!(mon.auto_tune, Some(AutoTune::new(60, 600, 0, 120).unwrap()));
+        assert_eq!(
+            mon.anomaly_detection,
+            Some(AnomalyDetection::new(Some(120), None).unwrap())
+        );
+        assert_eq!(mon.schedule, None);
+        assert_eq!(mon.missed_start_cutoff, None);
+    }
+
+    #[test]
+    fn editing_monitor_schedules() {
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+
+        // Adding a schedule means runs scheduled before now can't be missed.
+        let before = gen_relative_datetime(0);
+        mon.edit_details(
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings {
+                schedule: Some(Schedule::new("0 3 * * *", None).unwrap()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let cutoff = mon.missed_start_cutoff.unwrap();
+        assert!(cutoff >= before);
+
+        // Leaving the schedule as it is leaves the cutoff alone.
+        mon.missed_start_alerted(gen_relative_datetime(-60));
+        mon.edit_details(
+            "new-name".to_owned(),
+            360,
+            60,
+            MonitorSettings {
+                schedule: Some(Schedule::new("0 3 * * *", None).unwrap()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(mon.missed_start_cutoff.unwrap() < before);
+
+        // Changing the schedule resets the cutoff.
+        mon.edit_details(
+            "new-name".to_owned(),
+            360,
+            60,
+            MonitorSettings {
+                schedule: Some(Schedule::new("0 4 * * *", None).unwrap()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(mon.missed_start_cutoff.unwrap() >= before);
+
+        // Removing the schedule removes the cutoff.
+        mon.edit_details("new-name".to_owned(), 360, 60, MonitorSettings::default())
+            .unwrap();
+        assert_eq!(mon.schedule, None);
+        assert_eq!(mon.missed_start_cutoff, None);
+    }
+
+    fn scheduled_monitor(cutoff: Option<NaiveDateTime>, jobs: Vec<Job>) -> Monitor {
+        // Runs every 10 minutes, with a 1 minute grace period.
+        Monitor {
+            monitor_id: gen_uuid("a8e9d6ff-1b6c-4a8e-a2d0-2c1a7b5c1d3e"),
+            tenant: "foo-tenant".to_owned(),
+            name: "scheduled-monitor".to_owned(),
+            expected_duration: 120,
+            grace_duration: 60,
+            kind: MonitorKind::Job,
+            schedule: Some(Schedule::new("*/10 * * * *", None).unwrap()),
+            missed_start_cutoff: cutoff,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs,
+        }
+    }
+
+    fn job_started_at(start_time: NaiveDateTime) -> Job {
+        Job {
+            job_id: gen_uuid("ae5b8a59-a4a6-4e3f-9e5b-b4ab0e18c0b4"),
+            start_time,
+            max_end_time: start_time + Duration::seconds(180),
+            end_state: None,
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[test]
+    fn unscheduled_monitors_never_miss_starts() {
+        let mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            200,
+            100,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+
+        assert_eq!(mon.missed_start(), None);
+    }
+
+    #[test]
+    fn checking_for_missed_starts() {
+        let scheduled_start = Schedule::new("*/10 * * * *", None)
+            .unwrap()
+            .previous_run(gen_relative_datetime(-60))
+            .unwrap();
+
+        // No jobs at all since the schedule was set.
+        let mon = scheduled_monitor(Some(gen_relative_datetime(-3_600)), vec![]);
+        assert_eq!(mon.missed_start(), Some(scheduled_start));
+
+        // The only job started well before the scheduled start.
+        let mon = scheduled_monitor(
+            Some(gen_relative_datetime(-3_600)),
+            vec![job_started_at(scheduled_start - Duration::seconds(300))],
+        );
+        assert_eq!(mon.missed_start(), Some(scheduled_start));
+
+        // A job started a little early, within the grace period.
+        let mon = scheduled_monitor(
+            Some(gen_relative_datetime(-3_600)),
+            vec![job_started_at(scheduled_start - Duration::seconds(30))],
+        );
+        assert_eq!(mon.missed_start(), None);
+
+        // A job started a little late, within the grace period.
+        let mon = scheduled_monitor(
+            Some(gen_relative_datetime(-3_600)),
+            vec![job_started_at(scheduled_start + Duration::seconds(30))],
+        );
+        assert_eq!(mon.missed_start(), None);
+
+        // The schedule was set after the start was scheduled.
+        let mon = scheduled_monitor(Some(scheduled_start + Duration::seconds(1)), vec![]);
+        assert_eq!(mon.missed_start(), None);
+    }
+
+    #[test]
+    fn missed_starts_are_only_alerted_on_once() {
+        let mut mon = scheduled_monitor(Some(gen_relative_datetime(-3_600)), vec![]);
+
+        let scheduled_start = mon.missed_start().unwrap();
+        mon.missed_start_alerted(scheduled_start);
+
+        assert_eq!(mon.missed_start_cutoff, Some(scheduled_start));
+        assert_eq!(mon.missed_start(), None);
+    }
+
+    fn heartbeat_monitor(cutoff: Option<NaiveDateTime>, jobs: Vec<Job>) -> Monitor {
+        // Expects a heartbeat every 5 minutes, with a 1 minute grace period.
+        Monitor {
+            monitor_id: gen_uuid("a8e9d6ff-1b6c-4a8e-a2d0-2c1a7b5c1d3e"),
+            tenant: "foo-tenant".to_owned(),
+            name: "heartbeat-monitor".to_owned(),
+            expected_duration: 0,
+            grace_duration: 60,
+            kind: MonitorKind::Heartbeat { period: 300 },
+            schedule: None,
+            missed_start_cutoff: cutoff,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs,
+        }
+    }
+
+    #[test]
+    fn creating_heartbeat_monitors() {
+        let before = gen_relative_datetime(0);
+        let mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            0,
+            60,
+            MonitorSettings {
+                kind: MonitorKind::Heartbeat { period: 300 },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(mon.is_heartbeat());
+        // Heartbeats can't be missed until a period has passed since the Monitor was created.
+        assert!(mon.missed_start_cutoff.unwrap() >= before);
+        assert_eq!(mon.missed_start(), None);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(-300)]
+    fn creating_heartbeat_monitors_with_invalid_periods(#[case] period: i32) {
+        let error = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            0,
+            60,
+            MonitorSettings {
+                kind: MonitorKind::Heartbeat { period },
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::InvalidMonitor(format!(
+                "Invalid heartbeat period '{period}', must be at least 1 second"
+            ))
+        );
+    }
+
+    #[test]
+    fn editing_heartbeat_monitors_with_invalid_periods() {
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            0,
+            60,
+            MonitorSettings {
+                kind: MonitorKind::Heartbeat { period: 300 },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let result = mon.edit_details(
+            "renamed-monitor".to_owned(),
+            0,
+            60,
+            MonitorSettings {
+                kind: MonitorKind::Heartbeat { period: 0 },
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidMonitor(
+                "Invalid heartbeat period '0', must be at least 1 second".to_owned()
+            ))
+        );
+        // Nothing is changed.
+        assert_eq!(mon.name, "new-monitor");
+        assert_eq!(mon.kind, MonitorKind::Heartbeat { period: 300 });
+    }
+
+    #[test]
+    fn editing_monitor_kinds() {
+        let mut mon = Monitor::new(
+            "foo-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+        assert!(!mon.is_heartbeat());
+        assert_eq!(mon.missed_start_cutoff, None);
+
+        let before = gen_relative_datetime(0);
+        mon.edit_details(
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings {
+                kind: MonitorKind::Heartbeat { period: 300 },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(mon.is_heartbeat());
+        assert!(mon.missed_start_cutoff.unwrap() >= before);
+
+        mon.edit_details(
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
+        assert!(!mon.is_heartbeat());
+        assert_eq!(mon.missed_start_cutoff, None);
+    }
+
+    #[rstest]
+    // No heartbeats since the Monitor was created 10 minutes ago.
+    #[case(Some(-600), vec![], Some(-300))]
+    // No heartbeats since the Monitor was created 5 minutes ago, so we're still within grace.
+    #[case(Some(-330), vec![], None)]
+    // The last heartbeat was 10 minutes ago.
+    #[case(Some(-3_600), vec![-600], Some(-300))]
+    // The last heartbeat was 2 minutes ago.
+    #[case(Some(-3_600), vec![-120], None)]
+    // The last missed heartbeat was alerted on 2 minutes ago.
+    #[case(Some(-120), vec![-3_600], None)]
+    // The last missed heartbeat was alerted on 7 minutes ago, so another one has been missed.
+    #[case(Some(-420), vec![-3_600], Some(-120))]
+    // Without a cutoff or any heartbeats, there's nothing to go on.
+    #[case(None, vec![], None)]
+    fn checking_for_missed_heartbeats(
+        #[case] cutoff: Option<i64>,
+        #[case] heartbeats: Vec<i64>,
+        #[case] expected: Option<i64>,
+    ) {
+        // We use the same "now" for everything so that the expected times match exactly.
+        let now = gen_relative_datetime(0);
+        let relative = |seconds: i64| now + Duration::seconds(seconds);
+        let mon = heartbeat_monitor(
+            cutoff.map(relative),
+            heartbeats
+                .into_iter()
+                .map(|seconds| job_started_at(relative(seconds)))
+                .collect(),
+        );
+
+        assert_eq!(mon.missed_start(), expected.map(relative));
+    }
+
+    #[test]
+    fn scheduled_heartbeat_monitors_use_their_schedule() {
+        let scheduled_start = Schedule::new("*/10 * * * *", None)
+            .unwrap()
+            .previous_run(gen_relative_datetime(-60))
+            .unwrap();
+        let mut mon = scheduled_monitor(Some(gen_relative_datetime(-3_600)), vec![]);
+        mon.kind = MonitorKind::Heartbeat { period: 86_400 };
+
+        assert_eq!(mon.missed_start(), Some(scheduled_start));
+    }
+
+    #[test]
+    fn recording_heartbeats() {
+        let mut mon = heartbeat_monitor(Some(gen_relative_datetime(-600)), vec![]);
+        assert!(mon.missed_start().is_some());
+
+        let heartbeat = mon.record_heartbeat().unwrap();
+
+        assert!(!heartbeat.in_progress());
+        assert!(!heartbeat.errored());
+        assert_eq!(mon.jobs, vec![heartbeat]);
+        assert_eq!(mon.missed_start(), None);
+    }
+
+    #[test]
+    fn recording_heartbeats_for_non_heartbeat_monitors() {
+        let mut mon = scheduled_monitor(None, vec![]);
+
+        assert_eq!(
+            mon.record_heartbeat(),
+            Err(Error::NotHeartbeatMonitor(mon.monitor_id))
+        );
+        assert!(mon.jobs.is_empty());
+    }
+
+    #[test]
+    fn serializing_monitors() {
+        let mut mon = scheduled_monitor(Some(gen_relative_datetime(0)), vec![]);
+        mon.labels = Labels::new(BTreeMap::from([("env".to_owned(), "prod".to_owned())])).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&mon).unwrap(),
+            serde_json::json!({
+                "monitor_id": "a8e9d6ff-1b6c-4a8e-a2d0-2c1a7b5c1d3e",
+                "name": "scheduled-monitor",
+                "expected_duration": 120,
+                "grace_duration": 60,
+                "kind": {"type": "job"},
+                "schedule": {"expression": "*/10 * * * *", "timezone": "UTC"},
+                "alert_threshold": {"consecutive": 1, "window": null},
+                "retention": null,
+                "labels": {"env": "prod"},
+                "paused": false,
+                "resume_at": null,
+                "maintenance_windows": [],
+                "auto_tune": null,
+                "anomaly_detection": null,
+                "concurrency_policy": "allow",
+                "jobs": []
+            })
+        );
     }
 
     #[test]
     fn starting_jobs() {
-        let mut mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 3600, 600);
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
 
         assert!(mon.jobs_in_progress().is_empty());
 
-        let job1 = mon.start_job();
-        let job2 = mon.start_job();
-        let job3 = mon.start_job();
+        let job1 = mon.start_job().unwrap();
+        let job2 = mon.start_job().unwrap();
+        let job3 = mon.start_job().unwrap();
 
         assert_eq!(mon.jobs_in_progress().len(), 3);
 
@@ -507,13 +2036,20 @@ mod tests {
 
     #[test]
     fn finishing_jobs() {
-        let mut mon = Monitor::new("too-tenant".to_owned(), "new-monitor".to_owned(), 3600, 600);
+        let mut mon = Monitor::new(
+            "too-tenant".to_owned(),
+            "new-monitor".to_owned(),
+            3600,
+            600,
+            MonitorSettings::default(),
+        )
+        .unwrap();
 
-        let job1 = mon.start_job();
+        let job1 = mon.start_job().unwrap();
 
         assert_eq!(mon.jobs_in_progress().len(), 1);
 
-        let result1 = mon.finish_job(job1.job_id, true, None);
+        let result1 = mon.finish_job(job1.job_id, true, None, JobMetadata::default());
 
         assert!(result1.is_ok());
         assert_eq!(mon.jobs_in_progress().len(), 0);
@@ -522,6 +2058,7 @@ mod tests {
             gen_uuid("4631aa50-7780-455a-ab9a-78292f931832"),
             false,
             None,
+            JobMetadata::default(),
         );
         assert_eq!(
             result2.unwrap_err(),
@@ -531,4 +2068,273 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn pausing_and_resuming_monitors() {
+        let mut mon = scheduled_monitor(None, vec![]);
+        assert!(!mon.is_paused());
+        assert!(!mon.in_maintenance());
+
+        mon.pause(None).unwrap();
+        assert!(mon.is_paused());
+        assert!(mon.in_maintenance());
+
+        mon.resume();
+        assert!(!mon.is_paused());
+        assert!(!mon.in_maintenance());
+
+        // Monitors paused until a given time resume by themselves once it passes.
+        mon.pause(Some(gen_relative_datetime(60))).unwrap();
+        assert!(mon.is_paused());
+        mon.resume_at = Some(gen_relative_datetime(-60));
+        assert!(!mon.is_paused());
+    }
+
+    #[test]
+    fn pausing_monitors_until_the_past() {
+        let mut mon = scheduled_monitor(None, vec![]);
+
+        let result = mon.pause(Some(gen_relative_datetime(-60)));
+
+        assert!(matches!(result, Err(Error::InvalidMonitor(_))));
+        assert!(!mon.paused);
+    }
+
+    #[rstest]
+    #[case::always_in_effect("* * * * *", true)]
+    #[case::long_since_in_effect("0 0 0 1 1 * 2000", false)]
+    fn checking_for_maintenance_windows(#[case] expression: &str, #[case] expected: bool) {
+        let mut mon = scheduled_monitor(None, vec![]);
+        mon.maintenance_windows = vec![MaintenanceWindow::new(expression, None, 5).unwrap()];
+
+        assert_eq!(mon.in_maintenance(), expected);
+    }
+
+    #[test]
+    fn jobs_during_maintenance_are_flagged() {
+        let mut mon = heartbeat_monitor(None, vec![]);
+        let job = mon.start_job().unwrap();
+        assert!(!job.during_maintenance);
+
+        mon.pause(None).unwrap();
+        let job_during = mon.start_job().unwrap();
+        assert!(job_during.during_maintenance);
+        assert!(mon.record_heartbeat().unwrap().during_maintenance);
+
+        // Jobs that finish during maintenance are flagged, even if they started before it.
+        let finished = mon
+            .finish_job(job.job_id, false, None, JobMetadata::default())
+            .unwrap();
+        assert!(finished.during_maintenance);
+
+        // Jobs that start during maintenance stay flagged, even if they finish after it.
+        mon.resume();
+        let finished = mon
+            .finish_job(job_during.job_id, true, None, JobMetadata::default())
+            .unwrap();
+        assert!(finished.during_maintenance);
+    }
+
+    #[test]
+    fn jobs_during_maintenance_are_not_recovered_from() {
+        let mut mon = scheduled_monitor(None, vec![]);
+        let mut suppressed =
+            gen_alerted_job("7c1bd0c8-8ecc-4d6f-a0a4-8d0b8ebc5c5b", -100, Some(false));
+        suppressed.during_maintenance = true;
+        let mut recovered =
+            gen_alerted_job("1a0e8b9f-0b5e-4d7c-9a39-3b1c6a6a1a01", -50, Some(true));
+        recovered.late_alert_sent = false;
+        recovered.max_end_time = gen_relative_datetime(0);
+        mon.jobs = vec![recovered, suppressed];
+
+        assert!(mon.jobs_pending_recovery_alerts().is_empty());
+    }
+
+    fn gen_timed_job(hours_ago: i64, duration: i64, succeeded: Option<bool>) -> Job {
+        let start = -3600 * hours_ago;
+        Job {
+            job_id: gen_uuid("3e5b1f7a-8c2d-4e6f-9a0b-1c3d5e7f9a2b"),
+            start_time: gen_relative_datetime(start),
+            max_end_time: gen_relative_datetime(start + 3600),
+            end_state: succeeded.map(|succeeded| EndState {
+                end_time: gen_relative_datetime(start + duration),
+                succeeded,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[test]
+    fn retrieving_jobs_pending_anomaly_checks() {
+        let mut mon = scheduled_monitor(None, vec![]);
+        mon.anomaly_detection = Some(AnomalyDetection::new(Some(60), Some(3.0)).unwrap());
+        // From newest to oldest: a job that's still running, one that failed, one that finished
+        // far too fast, one that finished in a typical time, and a history of typical jobs that
+        // have already been checked.
+        mon.jobs = vec![
+            gen_timed_job(0, 0, None),
+            gen_timed_job(1, 5, Some(false)),
+            gen_timed_job(2, 30, Some(true)),
+            gen_timed_job(3, 1200, Some(true)),
+        ];
+        mon.jobs.extend((0..10).map(|i| {
+            let mut job = gen_timed_job(i + 4, 1180 + 4 * i, Some(true));
+            job.anomaly_checked = true;
+            job
+        }));
+
+        let checks: Vec<Option<DurationAnomaly>> = mon
+            .jobs_pending_anomaly_checks()
+            .into_iter()
+            .map(|(job, anomaly)| {
+                job.anomaly_checked = true;
+                anomaly
+            })
+            .collect();
+
+        assert_eq!(
+            checks,
+            vec![
+                Some(DurationAnomaly {
+                    duration: 30,
+                    min_duration: 1167,
+                    max_duration: Some(1233),
+                }),
+                None
+            ]
+        );
+        // Jobs are only ever checked once.
+        assert!(mon.jobs_pending_anomaly_checks().is_empty());
+    }
+
+    #[test]
+    fn retrieving_jobs_pending_anomaly_checks_without_detection() {
+        let mut mon = scheduled_monitor(None, vec![gen_timed_job(1, 5, Some(true))]);
+        assert!(mon.jobs_pending_anomaly_checks().is_empty());
+
+        let mut heartbeat = heartbeat_monitor(None, vec![gen_timed_job(1, 0, Some(true))]);
+        heartbeat.anomaly_detection = Some(AnomalyDetection::new(Some(60), None).unwrap());
+        assert!(heartbeat.jobs_pending_anomaly_checks().is_empty());
+    }
+
+    #[test]
+    fn enabling_anomaly_detection_skips_finished_jobs() {
+        let mut mon = scheduled_monitor(
+            None,
+            vec![gen_timed_job(0, 0, None), gen_timed_job(1, 5, Some(true))],
+        );
+
+        mon.edit_details(
+            mon.name.clone(),
+            mon.expected_duration,
+            mon.grace_duration,
+            MonitorSettings {
+                schedule: mon.schedule.clone(),
+                kind: mon.kind.clone(),
+                alert_threshold: mon.alert_threshold.clone(),
+                anomaly_detection: Some(AnomalyDetection::new(Some(60), None).unwrap()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(!mon.jobs[0].anomaly_checked);
+        assert!(mon.jobs[1].anomaly_checked);
+        assert!(mon.jobs_pending_anomaly_checks().is_empty());
+    }
+
+    #[rstest]
+    #[case::allowed(ConcurrencyPolicy::Allow)]
+    #[case::alerted(ConcurrencyPolicy::Alert)]
+    fn starting_overlapping_jobs(#[case] policy: ConcurrencyPolicy) {
+        let mut mon = scheduled_monitor(None, vec![]);
+        mon.concurrency_policy = policy;
+
+        let job1 = mon.start_job().unwrap();
+        let job2 = mon.start_job().unwrap();
+        assert!(!job1.overlapped);
+        assert!(job2.overlapped);
+
+        // Jobs that start once all others have finished don't overlap.
+        mon.finish_job(job1.job_id, true, None, JobMetadata::default())
+            .unwrap();
+        mon.finish_job(job2.job_id, true, None, JobMetadata::default())
+            .unwrap();
+        assert!(!mon.start_job().unwrap().overlapped);
+    }
+
+    #[test]
+    fn rejecting_overlapping_jobs() {
+        let mut mon = scheduled_monitor(None, vec![]);
+        mon.concurrency_policy = ConcurrencyPolicy::Reject;
+
+        let job = mon.start_job().unwrap();
+        assert_eq!(
+            mon.start_job().unwrap_err(),
+            Error::JobInProgress(mon.monitor_id)
+        );
+        assert_eq!(mon.jobs.len(), 1);
+
+        mon.finish_job(job.job_id, true, None, JobMetadata::default())
+            .unwrap();
+        assert!(mon.start_job().is_ok());
+    }
+
+    #[rstest]
+    #[case::allowed(ConcurrencyPolicy::Allow, 0)]
+    #[case::alerted(ConcurrencyPolicy::Alert, 1)]
+    #[case::rejected(ConcurrencyPolicy::Reject, 0)]
+    fn retrieving_jobs_pending_overlap_alerts(
+        #[case] policy: ConcurrencyPolicy,
+        #[case] expected: usize,
+    ) {
+        let mut alerted = gen_timed_job(0, 0, None);
+        alerted.overlapped = true;
+        alerted.overlap_alert_sent = true;
+        let mut pending = gen_timed_job(1, 5, Some(true));
+        pending.overlapped = true;
+        let mut mon = scheduled_monitor(
+            None,
+            vec![alerted, pending, gen_timed_job(2, 5, Some(true))],
+        );
+        mon.concurrency_policy = policy;
+
+        assert_eq!(mon.jobs_pending_overlap_alerts().len(), expected);
+    }
+
+    #[test]
+    fn alerting_on_overlaps_skips_existing_overlaps() {
+        let mut overlapping = gen_timed_job(0, 0, None);
+        overlapping.overlapped = true;
+        let mut mon = scheduled_monitor(None, vec![overlapping, gen_timed_job(0, 0, None)]);
+
+        mon.edit_details(
+            mon.name.clone(),
+            mon.expected_duration,
+            mon.grace_duration,
+            MonitorSettings {
+                schedule: mon.schedule.clone(),
+                kind: mon.kind.clone(),
+                alert_threshold: mon.alert_threshold.clone(),
+                concurrency_policy: ConcurrencyPolicy::Alert,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(mon.concurrency_policy, ConcurrencyPolicy::Alert);
+        assert!(mon.jobs[0].overlap_alert_sent);
+        assert!(mon.jobs_pending_overlap_alerts().is_empty());
+    }
 }
@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// The `MonitorMetrics` struct represents a snapshot of a Monitor's activity, as exposed to
+/// metrics scrapers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorMetrics {
+    /// The unique identifier for the Monitor.
+    pub monitor_id: Uuid,
+    /// The tenant that the Monitor belongs to.
+    pub tenant: String,
+    /// The name of the Monitor.
+    pub name: String,
+    /// When the Monitor's most recent Job started, if it has any Jobs.
+    pub last_started: Option<NaiveDateTime>,
+    /// When the Monitor's most recently finished Job finished, if any have finished.
+    pub last_finished: Option<NaiveDateTime>,
+    /// How long the Monitor's most recently finished Job ran for, in seconds.
+    pub last_duration: Option<i64>,
+    /// The number of Jobs started since the Monitor was created, including pruned Jobs.
+    pub jobs_started: i64,
+    /// The number of Jobs that have finished successfully since the Monitor was created.
+    pub jobs_succeeded: i64,
+    /// The number of Jobs that have finished with an error since the Monitor was created.
+    pub jobs_failed: i64,
+    /// The number of Jobs that have finished late since the Monitor was created.
+    pub jobs_late: i64,
+    /// The number of the Monitor's Jobs that are still running.
+    pub jobs_in_progress: i64,
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use chrono::{NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use cron_mon_client::types::ScheduleData;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+/// The `Schedule` struct represents the cron schedule that a monitored cronjob is expected to run
+/// on, along with the timezone that the schedule should be evaluated in.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ScheduleData")]
+pub struct Schedule {
+    /// The cron expression, i.e `0 3 * * *`.
+    pub expression: String,
+    /// The IANA timezone that the cron expression is evaluated in, i.e `Europe/London`.
+    pub timezone: String,
+    #[serde(skip)]
+    cron: cron::Schedule,
+    #[serde(skip)]
+    tz: Tz,
+}
+
+impl TryFrom<ScheduleData> for Schedule {
+    type Error = Error;
+
+    fn try_from(value: ScheduleData) -> Result<Self, Self::Error> {
+        Schedule::new(&value.expression, value.timezone.as_deref())
+    }
+}
+
+impl Schedule {
+    /// Instantiate a new Schedule. Note that this will return an `Error` if the cron expression
+    /// or timezone are invalid.
+    ///
+    /// Both standard 5-field cron expressions (`minute hour day month weekday`) and expressions
+    /// that include seconds are supported, as are shorthands such as `@daily`. If no timezone is
+    /// given then the schedule is evaluated in UTC.
+    pub fn new(expression: &str, timezone: Option<&str>) -> Result<Self, Error> {
+        let expression = expression.trim();
+        let invalid_expression =
+            || Error::InvalidMonitor(format!("Invalid cron expression '{expression}'"));
+
+        // The cron crate expects a seconds field, which standard crontab expressions don't have,
+        // and numbers the days of the week differently.
+        let cron_expression = match expression.split_whitespace().collect::<Vec<_>>()[..] {
+            [minute, hour, day, month, weekday] => {
+                let weekday = translate_weekdays(weekday).ok_or_else(invalid_expression)?;
+                format!("0 {minute} {hour} {day} {month} {weekday}")
+            }
+            _ => expression.to_owned(),
+        };
+        let cron = cron::Schedule::from_str(&cron_expression).map_err(|_| invalid_expression())?;
+
+        let timezone = timezone.unwrap_or("UTC");
+        let tz = Tz::from_str(timezone)
+            .map_err(|_| Error::InvalidMonitor(format!("Invalid timezone '{timezone}'")))?;
+
+        Ok(Self {
+            expression: expression.to_owned(),
+            timezone: timezone.to_owned(),
+            cron,
+            tz,
+        })
+    }
+
+    /// Retrieve the most recent time that the schedule fired at or before the given (UTC) time,
+    /// as a UTC time.
+    pub fn previous_run(&self, before: NaiveDateTime) -> Option<NaiveDateTime> {
+        let before = self.tz.from_utc_datetime(&before);
+        self.cron
+            .after(&before)
+            .next_back()
+            .map(|previous| previous.naive_utc())
+    }
+
+    /// Retrieve the next time that the schedule will fire after the given (UTC) time, as a UTC
+    /// time.
+    pub fn next_run(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let after = self.tz.from_utc_datetime(&after);
+        self.cron.after(&after).next().map(|next| next.naive_utc())
+    }
+}
+
+/// Translate the day of the week field of a standard crontab expression, where Sunday is 0 (or 7)
+/// and Saturday is 6, into the cron crate's numbering, where Sunday is 1 and Saturday is 7.
+/// Fields that use the names of days are left as they are, and `None` is returned if the field
+/// is invalid.
+fn translate_weekdays(field: &str) -> Option<String> {
+    if field == "*" || field == "?" || field.chars().any(|c| c.is_ascii_alphabetic()) {
+        return Some(field.to_owned());
+    }
+
+    let mut days = BTreeSet::new();
+    for term in field.split(',') {
+        let (range, step) = match term.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|step| *step > 0)?),
+            None => (term, 1),
+        };
+        let (first, last) = match range.split_once('-') {
+            Some((first, last)) => (first.parse::<u32>().ok()?, last.parse::<u32>().ok()?),
+            None if range == "*" => (0, 6),
+            // A single day with a step repeats until the end of the week.
+            None if term.contains('/') => (range.parse::<u32>().ok()?, 6),
+            None => {
+                let day = range.parse::<u32>().ok()?;
+                (day, day)
+            }
+        };
+        if first > last || last > 7 {
+            return None;
+        }
+        days.extend((first..=last).step_by(step as usize).map(|day| day % 7 + 1));
+    }
+
+    Some(
+        days.iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use test_utils::gen_datetime;
+
+    use super::*;
+
+    #[rstest]
+    #[case("0 3 * * *", None, "0 3 * * *", "UTC")]
+    #[case(
+        "  */5 * * * *  ",
+        Some("Europe/London"),
+        "*/5 * * * *",
+        "Europe/London"
+    )]
+    #[case("30 0 3 * * *", Some("UTC"), "30 0 3 * * *", "UTC")]
+    #[case("@daily", Some("America/New_York"), "@daily", "America/New_York")]
+    fn creating_schedules(
+        #[case] expression: &str,
+        #[case] timezone: Option<&str>,
+        #[case] expected_expression: &str,
+        #[case] expected_timezone: &str,
+    ) {
+        let schedule = Schedule::new(expression, timezone).unwrap();
+
+        assert_eq!(schedule.expression, expected_expression);
+        assert_eq!(schedule.timezone, expected_timezone);
+    }
+
+    #[rstest]
+    #[case(
+        "not a cron expression",
+        None,
+        "Invalid Monitor: Invalid cron expression 'not a cron expression'"
+    )]
+    #[case(
+        "0 3 * * 8",
+        None,
+        "Invalid Monitor: Invalid cron expression '0 3 * * 8'"
+    )]
+    #[case(
+        "0 3 * * 5-1",
+        None,
+        "Invalid Monitor: Invalid cron expression '0 3 * * 5-1'"
+    )]
+    #[case(
+        "0 3 * * */0",
+        None,
+        "Invalid Monitor: Invalid cron expression '0 3 * * */0'"
+    )]
+    #[case(
+        "0 3 * * *",
+        Some("Mars/Olympus_Mons"),
+        "Invalid Monitor: Invalid timezone 'Mars/Olympus_Mons'"
+    )]
+    fn creating_invalid_schedules(
+        #[case] expression: &str,
+        #[case] timezone: Option<&str>,
+        #[case] expected_error: &str,
+    ) {
+        let error = Schedule::new(expression, timezone).unwrap_err();
+
+        assert_eq!(error.to_string(), expected_error);
+    }
+
+    #[rstest]
+    #[case("0 3 * * *", "UTC", "2024-05-01T12:00:00", "2024-05-01T03:00:00")]
+    #[case("0 3 * * *", "UTC", "2024-05-01T02:59:59", "2024-04-30T03:00:00")]
+    // 03:00 in London is 02:00 UTC during British Summer Time.
+    #[case(
+        "0 3 * * *",
+        "Europe/London",
+        "2024-05-01T12:00:00",
+        "2024-05-01T02:00:00"
+    )]
+    #[case("*/15 * * * *", "UTC", "2024-05-01T12:14:00", "2024-05-01T12:00:00")]
+    // Days of the week are numbered from Sunday as 0 (or 7), as in a crontab. 2024-05-05 was a
+    // Sunday.
+    #[case("0 2 * * 0", "UTC", "2024-05-08T12:00:00", "2024-05-05T02:00:00")]
+    #[case("0 2 * * 7", "UTC", "2024-05-08T12:00:00", "2024-05-05T02:00:00")]
+    #[case("0 2 * * 1", "UTC", "2024-05-08T12:00:00", "2024-05-06T02:00:00")]
+    #[case("0 2 * * 1-5", "UTC", "2024-05-06T01:00:00", "2024-05-03T02:00:00")]
+    #[case("0 2 * * 5-7", "UTC", "2024-05-08T12:00:00", "2024-05-05T02:00:00")]
+    #[case("0 2 * * */3", "UTC", "2024-05-08T12:00:00", "2024-05-08T02:00:00")]
+    #[case("0 2 * * 0,6", "UTC", "2024-05-08T12:00:00", "2024-05-05T02:00:00")]
+    #[case("0 2 * * MON", "UTC", "2024-05-08T12:00:00", "2024-05-06T02:00:00")]
+    // Expressions with seconds use the cron crate's numbering, where Sunday is 1.
+    #[case("0 0 2 * * 1", "UTC", "2024-05-08T12:00:00", "2024-05-05T02:00:00")]
+    fn getting_previous_runs(
+        #[case] expression: &str,
+        #[case] timezone: &str,
+        #[case] before: &str,
+        #[case] expected: &str,
+    ) {
+        let schedule = Schedule::new(expression, Some(timezone)).unwrap();
+
+        assert_eq!(
+            schedule.previous_run(gen_datetime(before)),
+            Some(gen_datetime(expected))
+        );
+    }
+
+    #[rstest]
+    #[case("0 3 * * *", "UTC", "2024-05-01T12:00:00", "2024-05-02T03:00:00")]
+    #[case(
+        "0 3 * * *",
+        "Europe/London",
+        "2024-05-01T01:00:00",
+        "2024-05-01T02:00:00"
+    )]
+    fn getting_next_runs(
+        #[case] expression: &str,
+        #[case] timezone: &str,
+        #[case] after: &str,
+        #[case] expected: &str,
+    ) {
+        let schedule = Schedule::new(expression, Some(timezone)).unwrap();
+
+        assert_eq!(
+            schedule.next_run(gen_datetime(after)),
+            Some(gen_datetime(expected))
+        );
+    }
+
+    #[test]
+    fn serializing_schedules() {
+        let schedule = Schedule::new("0 3 * * *", Some("Europe/London")).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&schedule).unwrap(),
+            serde_json::json!({"expression": "0 3 * * *", "timezone": "Europe/London"})
+        );
+    }
+
+    #[rstest]
+    #[case(
+        serde_json::json!({"expression": "0 3 * * *"}),
+        Ok(Schedule::new("0 3 * * *", None).unwrap())
+    )]
+    #[case(
+        serde_json::json!({"expression": "0 3 * * *", "timezone": "Asia/Tokyo"}),
+        Ok(Schedule::new("0 3 * * *", Some("Asia/Tokyo")).unwrap())
+    )]
+    #[case(
+        serde_json::json!({"expression": "0 3 * * *", "timezone": "Nowhere"}),
+        Err("Invalid Monitor: Invalid timezone 'Nowhere'".to_owned())
+    )]
+    fn deserializing_schedules(
+        #[case] value: serde_json::Value,
+        #[case] expected: Result<Schedule, String>,
+    ) {
+        let result = serde_json::from_value::<Schedule>(value).map_err(|error| error.to_string());
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,263 @@
+use chrono::{NaiveDateTime, TimeDelta, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::models::{EventType, LifecycleEvent, WebhookSubscription};
+
+/// The most times that an event is sent to a webhook subscription before giving up on it.
+pub const MAX_DELIVERY_ATTEMPTS: usize = 8;
+
+/// How long to wait before retrying a failed delivery for the first time. This doubles with each
+/// subsequent attempt.
+const INITIAL_RETRY_DELAY_SECONDS: i64 = 30;
+
+/// The most deliveries that can be retrieved at once for a webhook subscription.
+pub const MAX_DELIVERY_PAGE_SIZE: u32 = 100;
+
+/// The number of deliveries retrieved for a webhook subscription, unless requested otherwise.
+pub const DEFAULT_DELIVERY_PAGE_SIZE: u32 = 25;
+
+/// The `WebhookDelivery` struct tracks the sending of a single lifecycle event to a single webhook
+/// subscription, including every attempt that has been made to send it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WebhookDelivery {
+    /// The unique identifier for the delivery.
+    pub delivery_id: Uuid,
+    /// The webhook subscription that the event is being sent to.
+    pub subscription_id: Uuid,
+    /// The tenant that the webhook subscription belongs to.
+    #[serde(skip_serializing)]
+    pub tenant: String,
+    /// The event being sent.
+    pub event_id: Uuid,
+    /// The type of the event being sent.
+    pub event_type: EventType,
+    /// The body of each request, which is the same for every attempt.
+    pub payload: Value,
+    /// Where the delivery is up to.
+    pub status: DeliveryStatus,
+    /// The attempts made so far, oldest first.
+    pub attempts: Vec<DeliveryAttempt>,
+    /// When the next attempt is due, if the delivery is still pending.
+    pub next_attempt_at: Option<NaiveDateTime>,
+    /// When the delivery was queued.
+    pub created: NaiveDateTime,
+}
+
+/// The states that a delivery can be in.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// The event hasn't been sent successfully yet, but will be (re)tried.
+    Pending,
+    /// The event was sent successfully.
+    Succeeded,
+    /// Every attempt to send the event failed, so we've given up on it.
+    Failed,
+}
+
+/// A single attempt to send an event to a webhook subscription.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DeliveryAttempt {
+    /// The unique identifier for the attempt.
+    pub attempt_id: Uuid,
+    /// When the attempt was made.
+    pub attempted_at: NaiveDateTime,
+    /// Whether the receiving server accepted the event.
+    pub succeeded: bool,
+    /// The HTTP status the receiving server responded with, if it responded at all.
+    pub response_status: Option<u16>,
+    /// Why the attempt failed, if it did.
+    pub error: Option<String>,
+}
+
+impl WebhookDelivery {
+    /// Queue a delivery of an event to a webhook subscription, due immediately.
+    pub fn new(subscription: &WebhookSubscription, event: &LifecycleEvent) -> Self {
+        let now = Utc::now().naive_utc();
+        Self {
+            delivery_id: Uuid::new_v4(),
+            subscription_id: subscription.subscription_id,
+            tenant: subscription.tenant.clone(),
+            event_id: event.event_id,
+            event_type: event.event_type,
+            payload: serde_json::to_value(event).unwrap(),
+            status: DeliveryStatus::Pending,
+            attempts: vec![],
+            next_attempt_at: Some(now),
+            created: now,
+        }
+    }
+
+    /// Record an attempt to send the event. Failed attempts are retried with an exponential
+    /// backoff, until `MAX_DELIVERY_ATTEMPTS` have been made.
+    pub fn record_attempt(&mut self, attempt: DeliveryAttempt) {
+        let attempted_at = attempt.attempted_at;
+        let succeeded = attempt.succeeded;
+        self.attempts.push(attempt);
+
+        if succeeded {
+            self.status = DeliveryStatus::Succeeded;
+            self.next_attempt_at = None;
+        } else if self.attempts.len() >= MAX_DELIVERY_ATTEMPTS {
+            self.status = DeliveryStatus::Failed;
+            self.next_attempt_at = None;
+        } else {
+            self.next_attempt_at = Some(attempted_at + Self::retry_delay(self.attempts.len()));
+        }
+    }
+
+    fn retry_delay(failed_attempts: usize) -> TimeDelta {
+        TimeDelta::seconds(INITIAL_RETRY_DELAY_SECONDS << (failed_attempts - 1))
+    }
+}
+
+impl DeliveryAttempt {
+    /// An attempt that the receiving server accepted.
+    pub fn success(response_status: u16) -> Self {
+        Self {
+            attempt_id: Uuid::new_v4(),
+            attempted_at: Utc::now().naive_utc(),
+            succeeded: true,
+            response_status: Some(response_status),
+            error: None,
+        }
+    }
+
+    /// An attempt that failed, either because the receiving server rejected the event, or because
+    /// we couldn't reach it at all.
+    pub fn failure(response_status: Option<u16>, error: String) -> Self {
+        Self {
+            attempt_id: Uuid::new_v4(),
+            attempted_at: Utc::now().naive_utc(),
+            succeeded: false,
+            response_status,
+            error: Some(error),
+        }
+    }
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Pending, Self::Succeeded, Self::Failed]
+            .into_iter()
+            .find(|status| status.as_str() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{AlertThreshold, ConcurrencyPolicy, Labels, Monitor, MonitorKind};
+
+    use super::*;
+
+    #[fixture]
+    fn delivery() -> WebhookDelivery {
+        let subscription = WebhookSubscription::new(
+            "Pipeline".to_owned(),
+            "tenant".to_owned(),
+            "https://example.com/events".to_owned(),
+            None,
+            vec![EventType::MonitorCreated],
+            true,
+        );
+        let monitor = Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        };
+
+        WebhookDelivery::new(&subscription, &LifecycleEvent::monitor_created(&monitor))
+    }
+
+    fn failure_at(attempted_at: &str) -> DeliveryAttempt {
+        DeliveryAttempt {
+            attempted_at: gen_datetime(attempted_at),
+            ..DeliveryAttempt::failure(Some(500), "Received HTTP 500".to_owned())
+        }
+    }
+
+    #[rstest]
+    fn test_new(delivery: WebhookDelivery) {
+        assert_eq!(delivery.tenant, "tenant");
+        assert_eq!(delivery.event_type, EventType::MonitorCreated);
+        assert_eq!(delivery.payload["type"], "monitor_created");
+        assert_eq!(delivery.payload["event_id"], delivery.event_id.to_string());
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert!(delivery.attempts.is_empty());
+        assert_eq!(delivery.next_attempt_at, Some(delivery.created));
+    }
+
+    #[rstest]
+    fn test_successful_attempt(mut delivery: WebhookDelivery) {
+        delivery.record_attempt(failure_at("2025-06-21T12:00:00"));
+        delivery.record_attempt(DeliveryAttempt::success(204));
+
+        assert_eq!(delivery.status, DeliveryStatus::Succeeded);
+        assert_eq!(delivery.attempts.len(), 2);
+        assert_eq!(delivery.next_attempt_at, None);
+    }
+
+    #[rstest]
+    fn test_failed_attempts_back_off(mut delivery: WebhookDelivery) {
+        let expected_retries = [
+            "2025-06-21T12:00:30",
+            "2025-06-21T12:01:00",
+            "2025-06-21T12:02:00",
+            "2025-06-21T12:04:00",
+            "2025-06-21T12:08:00",
+            "2025-06-21T12:16:00",
+            "2025-06-21T12:32:00",
+        ];
+        for expected_retry in expected_retries {
+            delivery.record_attempt(failure_at("2025-06-21T12:00:00"));
+
+            assert_eq!(delivery.status, DeliveryStatus::Pending);
+            assert_eq!(delivery.next_attempt_at, Some(gen_datetime(expected_retry)));
+        }
+
+        // Once we've run out of attempts, we give up.
+        delivery.record_attempt(failure_at("2025-06-21T12:00:00"));
+        assert_eq!(delivery.attempts.len(), MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(delivery.next_attempt_at, None);
+    }
+
+    #[rstest]
+    #[case(DeliveryStatus::Pending, "pending")]
+    #[case(DeliveryStatus::Succeeded, "succeeded")]
+    #[case(DeliveryStatus::Failed, "failed")]
+    fn test_delivery_status_names(#[case] status: DeliveryStatus, #[case] name: &str) {
+        assert_eq!(status.as_str(), name);
+        assert_eq!(DeliveryStatus::from_name(name), Some(status));
+        assert_eq!(serde_json::to_value(status).unwrap(), name);
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Serialize, Serializer};
+use uuid::Uuid;
+
+use crate::domain::models::EventType;
+
+/// A domain model representing a tenant's subscription to lifecycle events, which are sent to an
+/// arbitrary URL as they happen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookSubscription {
+    /// The unique identifier for the webhook subscription.
+    pub subscription_id: Uuid,
+    /// The tenant that the webhook subscription belongs to.
+    pub tenant: String,
+    /// The name of the webhook subscription.
+    pub name: String,
+    /// The URL that events are sent to.
+    pub url: String,
+    /// An optional secret, used to sign each request body with HMAC-SHA256. This is never
+    /// serialized, since anyone who can read it can forge signed requests; only whether it's set
+    /// is.
+    pub secret: Option<String>,
+    /// The types of event that are sent.
+    pub event_types: Vec<EventType>,
+    /// Whether events are currently sent.
+    pub active: bool,
+}
+
+impl WebhookSubscription {
+    /// Create a new `WebhookSubscription`.
+    pub fn new(
+        name: String,
+        tenant: String,
+        url: String,
+        secret: Option<String>,
+        event_types: Vec<EventType>,
+        active: bool,
+    ) -> Self {
+        Self {
+            subscription_id: Uuid::new_v4(),
+            tenant,
+            name,
+            url,
+            secret,
+            event_types,
+            active,
+        }
+    }
+
+    /// Modify this webhook subscription's details.
+    pub fn edit_details(
+        &mut self,
+        name: String,
+        url: String,
+        secret: Option<String>,
+        event_types: Vec<EventType>,
+        active: bool,
+    ) {
+        self.name = name;
+        self.url = url;
+        self.secret = secret;
+        self.event_types = event_types;
+        self.active = active;
+    }
+
+    /// Check if events of the given type are currently sent to this webhook subscription.
+    pub fn is_subscribed_to(&self, event_type: EventType) -> bool {
+        self.active && self.event_types.contains(&event_type)
+    }
+}
+
+impl Serialize for WebhookSubscription {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Subscription<'a> {
+            subscription_id: Uuid,
+            name: &'a str,
+            url: &'a str,
+            has_secret: bool,
+            event_types: &'a [EventType],
+            active: bool,
+        }
+
+        Subscription {
+            subscription_id: self.subscription_id,
+            name: &self.name,
+            url: &self.url,
+            has_secret: self.secret.is_some(),
+            event_types: &self.event_types,
+            active: self.active,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use test_utils::gen_uuid;
+
+    use super::*;
+
+    #[rstest]
+    #[case::subscribed(true, EventType::JobStarted, true)]
+    #[case::other_event_type(true, EventType::JobFinished, false)]
+    #[case::inactive(false, EventType::JobStarted, false)]
+    fn test_is_subscribed_to(
+        #[case] active: bool,
+        #[case] event_type: EventType,
+        #[case] expected: bool,
+    ) {
+        let subscription = WebhookSubscription::new(
+            "Pipeline".to_owned(),
+            "tenant".to_owned(),
+            "https://example.com/events".to_owned(),
+            None,
+            vec![EventType::MonitorCreated, EventType::JobStarted],
+            active,
+        );
+
+        assert_eq!(subscription.is_subscribed_to(event_type), expected);
+    }
+
+    #[test]
+    fn test_edit_details() {
+        let mut subscription = WebhookSubscription::new(
+            "Pipeline".to_owned(),
+            "tenant".to_owned(),
+            "https://example.com/events".to_owned(),
+            None,
+            vec![EventType::JobStarted],
+            true,
+        );
+        let subscription_id = subscription.subscription_id;
+
+        subscription.edit_details(
+            "New pipeline".to_owned(),
+            "https://example.com/new-events".to_owned(),
+            Some("secret".to_owned()),
+            vec![EventType::JobFinished],
+            false,
+        );
+
+        assert_eq!(
+            subscription,
+            WebhookSubscription {
+                subscription_id,
+                tenant: "tenant".to_owned(),
+                name: "New pipeline".to_owned(),
+                url: "https://example.com/new-events".to_owned(),
+                secret: Some("secret".to_owned()),
+                event_types: vec![EventType::JobFinished],
+                active: false,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::with_secret(Some("s3cr3t".to_owned()), true)]
+    #[case::without_secret(None, false)]
+    fn test_serialize(#[case] secret: Option<String>, #[case] has_secret: bool) {
+        let subscription = WebhookSubscription {
+            subscription_id: gen_uuid("b0b9d1c4-7a4e-4f2b-9a55-3f4f1c2d6e8a"),
+            tenant: "tenant".to_owned(),
+            name: "Pipeline".to_owned(),
+            url: "https://example.com/events".to_owned(),
+            secret,
+            event_types: vec![EventType::JobStarted],
+            active: true,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&subscription).unwrap(),
+            json!({
+                "subscription_id": "b0b9d1c4-7a4e-4f2b-9a55-3f4f1c2d6e8a",
+                "name": "Pipeline",
+                "url": "https://example.com/events",
+                "has_secret": has_secret,
+                "event_types": ["job_started"],
+                "active": true
+            })
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::cmp::Reverse;
+
+use crate::domain::models::{DurationStats, Job};
+
+/// The most recent Jobs that statistics are calculated from, so that they follow changes in how
+/// long Jobs take rather than being dominated by old ones.
+const SAMPLE_SIZE: usize = 100;
+
+/// Calculate statistics describing the durations of the most recent Jobs that finished
+/// successfully, or `None` if there aren't any.
+///
+/// Jobs that failed are left out, since they may have been cut short, or have hung until they
+/// were killed.
+pub fn calculate_duration_stats(jobs: &[Job]) -> Option<DurationStats> {
+    let mut finished: Vec<&Job> = jobs.iter().filter(|job| job.succeeded()).collect();
+    finished.sort_by_key(|job| Reverse(job.start_time));
+
+    let mut durations: Vec<u64> = finished
+        .iter()
+        .take(SAMPLE_SIZE)
+        .filter_map(|job| job.duration())
+        .collect();
+    if durations.is_empty() {
+        return None;
+    }
+    durations.sort_unstable();
+
+    let count = durations.len() as f64;
+    let mean = durations.iter().sum::<u64>() as f64 / count;
+    let variance = durations
+        .iter()
+        .map(|duration| (*duration as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+
+    Some(DurationStats {
+        job_count: durations.len(),
+        p50: percentile(&durations, 50),
+        p95: percentile(&durations, 95),
+        p99: percentile(&durations, 99),
+        max: *durations.last().unwrap(),
+        std_dev: variance.sqrt(),
+    })
+}
+
+/// Retrieve the given percentile of some sorted durations, using the nearest-rank method.
+fn percentile(sorted_durations: &[u64], percentile: usize) -> u64 {
+    let rank = (percentile * sorted_durations.len()).div_ceil(100);
+    sorted_durations[rank.saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{EndState, JobMetadata};
+
+    use super::*;
+
+    fn gen_job(minutes_ago: i64, duration: i64, succeeded: Option<bool>) -> Job {
+        let start_time = gen_datetime("2024-05-01T12:00:00") - Duration::minutes(minutes_ago);
+        Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time,
+            max_end_time: start_time + Duration::seconds(600),
+            end_state: succeeded.map(|succeeded| EndState {
+                end_time: start_time + Duration::seconds(duration),
+                succeeded,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[test]
+    fn calculating_duration_stats() {
+        let jobs = [2, 4, 4, 4, 5, 5, 7, 9]
+            .into_iter()
+            .enumerate()
+            .map(|(i, duration)| gen_job(i as i64, duration, Some(true)))
+            .collect::<Vec<Job>>();
+
+        assert_eq!(
+            calculate_duration_stats(&jobs),
+            Some(DurationStats {
+                job_count: 8,
+                p50: 4,
+                p95: 9,
+                p99: 9,
+                max: 9,
+                std_dev: 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn calculating_duration_stats_from_many_jobs() {
+        // The 50 oldest Jobs took far longer, but are outside of the sample.
+        let jobs = (1..=150)
+            .map(|i| gen_job(i, if i > 100 { 10_000 } else { 101 - i }, Some(true)))
+            .collect::<Vec<Job>>();
+
+        let stats = calculate_duration_stats(&jobs).unwrap();
+
+        assert_eq!(stats.job_count, 100);
+        assert_eq!(stats.p50, 50);
+        assert_eq!(stats.p95, 95);
+        assert_eq!(stats.p99, 99);
+        assert_eq!(stats.max, 100);
+    }
+
+    #[test]
+    fn calculating_duration_stats_ignores_unsuccessful_jobs() {
+        let jobs = vec![
+            gen_job(1, 300, None),
+            gen_job(2, 5, Some(false)),
+            gen_job(3, 120, Some(true)),
+        ];
+
+        let stats = calculate_duration_stats(&jobs).unwrap();
+
+        assert_eq!(stats.job_count, 1);
+        assert_eq!(stats.max, 120);
+        assert_eq!(stats.std_dev, 0.0);
+    }
+
+    #[test]
+    fn calculating_duration_stats_without_jobs() {
+        assert_eq!(calculate_duration_stats(&[gen_job(1, 300, None)]), None);
+    }
+}
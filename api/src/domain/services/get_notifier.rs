@@ -1,8 +1,12 @@
 #[cfg(test)]
 use mockall::automock;
 
-use crate::domain::models::{AlertConfig, AlertType};
-use crate::infrastructure::notify::{slack::SlackNotifier, Notifier};
+use crate::domain::models::{AlertConfig, AlertType, SmtpConfig};
+use crate::infrastructure::notify::email::{integration::smtp_relay_from_env, EmailNotifier};
+use crate::infrastructure::notify::{
+    metered::MeteredNotifier, pagerduty::PagerDutyNotifier, slack::SlackNotifier,
+    webhook::WebhookNotifier, Notifier,
+};
 
 /// Retrieve a notifier for a given alert configuration.
 #[cfg_attr(test, automock)]
@@ -16,12 +20,17 @@ pub trait GetNotifier {
 }
 
 /// A service that retrieves a notifier for a given alert configuration.
-pub struct GetNotifierService;
+pub struct GetNotifierService {
+    /// The server-level SMTP relay, for email alerts that don't specify their own.
+    smtp_relay: Option<SmtpConfig>,
+}
 
 impl GetNotifierService {
     /// Create a new instance of the service.
     pub fn new() -> Self {
-        Self
+        Self {
+            smtp_relay: smtp_relay_from_env(),
+        }
     }
 }
 
@@ -33,11 +42,25 @@ impl Default for GetNotifierService {
 
 impl GetNotifier for GetNotifierService {
     /// Retrieve a notifier for a given alert configuration.
+    ///
+    /// Notifiers are wrapped in a `MeteredNotifier`, so that the alerts they send are included in
+    /// the process' metrics.
     fn get_notifier(&self, alert_config: &AlertConfig) -> Box<dyn Notifier + Sync + Send> {
-        match &alert_config.type_ {
-            AlertType::Slack(config) => {
-                Box::new(SlackNotifier::new(&config.token, &config.channel))
-            }
-        }
+        let (notifier_type, notifier): (_, Box<dyn Notifier + Sync + Send>) = match &alert_config
+            .type_
+        {
+            AlertType::Slack(config) => (
+                "slack",
+                Box::new(SlackNotifier::new(&config.token, &config.channel)),
+            ),
+            AlertType::Webhook(config) => ("webhook", Box::new(WebhookNotifier::new(config))),
+            AlertType::Email(config) => (
+                "email",
+                Box::new(EmailNotifier::new(config, self.smtp_relay.as_ref())),
+            ),
+            AlertType::PagerDuty(config) => ("pagerduty", Box::new(PagerDutyNotifier::new(config))),
+        };
+
+        Box::new(MeteredNotifier::new(notifier_type, notifier))
     }
 }
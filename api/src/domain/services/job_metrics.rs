@@ -0,0 +1,139 @@
+use std::cmp::Reverse;
+
+use crate::domain::models::{Job, JobMetrics, MetricPoint};
+
+/// The most recent Jobs that metrics are collected from, to keep the series a chartable size.
+const SAMPLE_SIZE: usize = 100;
+
+/// Collect the metrics recorded by the most recent Jobs that have finished, whether or not they
+/// succeeded, into a series for each metric, ordered from oldest to newest.
+pub fn collect_job_metrics(jobs: &[Job]) -> JobMetrics {
+    let mut finished: Vec<&Job> = jobs.iter().filter(|job| !job.in_progress()).collect();
+    finished.sort_by_key(|job| Reverse(job.start_time));
+    finished.truncate(SAMPLE_SIZE);
+    finished.reverse();
+
+    let mut job_metrics = JobMetrics {
+        job_count: finished.len(),
+        ..Default::default()
+    };
+    for job in finished {
+        // Jobs are only collected once they've finished, so they'll always have an end state.
+        let end_state = job.end_state.as_ref().unwrap();
+        for (name, value) in &end_state.metadata.metrics {
+            job_metrics
+                .metrics
+                .entry(name.clone())
+                .or_default()
+                .push(MetricPoint {
+                    job_id: job.job_id,
+                    end_time: end_state.end_time,
+                    value: *value,
+                });
+        }
+    }
+
+    job_metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    use test_utils::gen_datetime;
+
+    use crate::domain::models::{EndState, JobMetadata};
+
+    use super::*;
+
+    fn gen_job(minutes_ago: i64, metrics: Option<&[(&str, f64)]>) -> Job {
+        let start_time = gen_datetime("2024-05-01T12:00:00") - Duration::minutes(minutes_ago);
+        Job {
+            job_id: Uuid::new_v4(),
+            start_time,
+            max_end_time: start_time + Duration::seconds(600),
+            end_state: metrics.map(|metrics| EndState {
+                end_time: start_time + Duration::seconds(60),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata {
+                    metrics: metrics
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), *value))
+                        .collect(),
+                    ..Default::default()
+                },
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[test]
+    fn collecting_metrics_without_jobs() {
+        assert_eq!(collect_job_metrics(&[]), JobMetrics::default());
+    }
+
+    #[test]
+    fn collecting_metrics() {
+        let jobs = vec![
+            gen_job(10, Some(&[("rows", 30.0)])),
+            // In progress Jobs haven't recorded any metrics yet.
+            gen_job(5, None),
+            gen_job(30, Some(&[("rows", 10.0), ("bytes", 2048.0)])),
+            gen_job(20, Some(&[])),
+        ];
+
+        let job_metrics = collect_job_metrics(&jobs);
+
+        assert_eq!(job_metrics.job_count, 3);
+        assert_eq!(
+            job_metrics
+                .metrics
+                .iter()
+                .map(|(name, points)| (
+                    name.as_str(),
+                    points
+                        .iter()
+                        .map(|point| (point.job_id, point.value))
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("bytes", vec![(jobs[2].job_id, 2048.0)]),
+                ("rows", vec![(jobs[2].job_id, 10.0), (jobs[0].job_id, 30.0)]),
+            ])
+        );
+        assert_eq!(
+            job_metrics.metrics["rows"][1].end_time,
+            gen_datetime("2024-05-01T11:51:00")
+        );
+    }
+
+    #[test]
+    fn collecting_metrics_from_most_recent_jobs() {
+        let jobs: Vec<Job> = (0..150)
+            .map(|minutes_ago| gen_job(minutes_ago, Some(&[("rows", minutes_ago as f64)])))
+            .collect();
+
+        let job_metrics = collect_job_metrics(&jobs);
+
+        assert_eq!(job_metrics.job_count, 100);
+        let rows = &job_metrics.metrics["rows"];
+        assert_eq!(rows.len(), 100);
+        assert_eq!(rows.first().unwrap().value, 99.0);
+        assert_eq!(rows.last().unwrap().value, 0.0);
+    }
+}
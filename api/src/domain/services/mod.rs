@@ -1,2 +1,4 @@
+pub mod duration_stats;
 pub mod get_notifier;
+pub mod job_metrics;
 pub mod monitors;
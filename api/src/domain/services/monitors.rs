@@ -34,7 +34,9 @@ mod tests {
 
     use test_utils::{gen_datetime, gen_uuid};
 
-    use crate::domain::models::{EndState, Job};
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EndState, Job, JobMetadata, Labels, MonitorKind,
+    };
 
     use super::*;
 
@@ -47,6 +49,18 @@ mod tests {
                 name: "db-backup.py".to_owned(),
                 expected_duration: 1800,
                 grace_duration: 600,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![],
             },
             Monitor {
@@ -55,6 +69,18 @@ mod tests {
                 name: "generate-orders.sh".to_owned(),
                 expected_duration: 3600,
                 grace_duration: 1200,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![
                     Job {
                         job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
@@ -64,9 +90,18 @@ mod tests {
                             end_time: gen_datetime("2024-05-01T00:49:00"),
                             succeeded: true,
                             output: Some("Orders generated successfully".to_owned()),
+                            metadata: JobMetadata::default(),
                         }),
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                     Job {
                         job_id: gen_uuid("c1893113-66d7-4707-9a51-c8be46287b2c"),
@@ -76,9 +111,18 @@ mod tests {
                             end_time: gen_datetime("2024-05-01T00:39:00"),
                             succeeded: false,
                             output: Some("Failed to generate orders".to_owned()),
+                            metadata: JobMetadata::default(),
                         }),
                         late_alert_sent: false,
                         error_alert_sent: false,
+                        late_alert_resolved: false,
+                        error_alert_resolved: false,
+                        recovery_alert_sent: false,
+                        during_maintenance: false,
+                        below_threshold: false,
+                        anomaly_checked: false,
+                        overlapped: false,
+                        overlap_alert_sent: false,
                     },
                 ],
             },
@@ -88,6 +132,18 @@ mod tests {
                 name: "send-emails.sh".to_owned(),
                 expected_duration: 7200,
                 grace_duration: 1800,
+                kind: MonitorKind::Job,
+                schedule: None,
+                missed_start_cutoff: None,
+                alert_threshold: AlertThreshold::default(),
+                retention: None,
+                labels: Labels::default(),
+                paused: false,
+                resume_at: None,
+                maintenance_windows: vec![],
+                auto_tune: None,
+                anomaly_detection: None,
+                concurrency_policy: ConcurrencyPolicy::Allow,
                 jobs: vec![Job {
                     job_id: gen_uuid("9d4e2d69-af63-4c1e-8639-60cb2683aee5"),
                     start_time: gen_datetime("2024-05-01T00:20:00"),
@@ -95,6 +151,14 @@ mod tests {
                     end_state: None,
                     late_alert_sent: false,
                     error_alert_sent: false,
+                    late_alert_resolved: false,
+                    error_alert_resolved: false,
+                    recovery_alert_sent: false,
+                    during_maintenance: false,
+                    below_threshold: false,
+                    anomaly_checked: false,
+                    overlapped: false,
+                    overlap_alert_sent: false,
                 }],
             },
         ]
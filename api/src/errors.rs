@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter, Result};
 
+use chrono::NaiveDateTime;
 use uuid::Uuid;
 
 /// Application-level errors.
@@ -10,14 +11,23 @@ pub enum Error {
     ApiKeyNotFound(Uuid),
     JobNotFound(Uuid, Uuid),
     AlertConfigNotFound(Vec<Uuid>),
+    AuditEventNotFound(Uuid),
+    SubscriptionNotFound(Uuid),
     JobAlreadyFinished(Uuid),
+    JobInProgress(Uuid),
+    NotHeartbeatMonitor(Uuid),
     ErroneousJobAlertFailure(String),
+    MissedStartAlertFailure(String),
     AlertConfigurationError(String),
     InvalidMonitor(String),
     InvalidJob(String),
     InvalidAlertConfig(String),
+    InvalidSubscription(String),
+    InvalidApiKey(String),
     NotifyError(String),
     Unauthorized(String),
+    Forbidden(String),
+    ApiKeyExpired(NaiveDateTime),
     AuthenticationError(String),
 }
 
@@ -48,20 +58,44 @@ impl Display for Error {
                     write!(f, "Failed to find alert configuration with id '{ac_id}'")
                 }
             }
+            Self::AuditEventNotFound(audit_event_id) => {
+                write!(f, "Failed to find audit event with id '{audit_event_id}'")
+            }
+            Self::SubscriptionNotFound(subscription_id) => {
+                write!(
+                    f,
+                    "Failed to find webhook subscription with id '{subscription_id}'"
+                )
+            }
             Self::JobAlreadyFinished(job_id) => {
                 write!(f, "Job('{job_id}') is already finished")
             }
+            Self::JobInProgress(monitor_id) => {
+                write!(f, "Monitor('{monitor_id}') already has a job in progress")
+            }
+            Self::NotHeartbeatMonitor(monitor_id) => {
+                write!(f, "Monitor('{monitor_id}') is not a heartbeat monitor")
+            }
             Self::ErroneousJobAlertFailure(reason) => {
                 write!(f, "Failed to process late job(s): {reason}")
             }
+            Self::MissedStartAlertFailure(reason) => {
+                write!(f, "Failed to process missed start(s): {reason}")
+            }
             Self::AlertConfigurationError(reason) => {
                 write!(f, "Failed to configure alert: {reason}")
             }
             Self::InvalidMonitor(reason) => write!(f, "Invalid Monitor: {reason}"),
             Self::InvalidJob(reason) => write!(f, "Invalid Job: {reason}"),
             Self::InvalidAlertConfig(reason) => write!(f, "Invalid Alert Configuration: {reason}"),
+            Self::InvalidSubscription(reason) => {
+                write!(f, "Invalid Webhook Subscription: {reason}")
+            }
+            Self::InvalidApiKey(reason) => write!(f, "Invalid API Key: {reason}"),
             Self::NotifyError(reason) => write!(f, "Failed to notify: {reason}"),
             Self::Unauthorized(reason) => write!(f, "Unauthorized: {reason}"),
+            Self::Forbidden(reason) => write!(f, "Forbidden: {reason}"),
+            Self::ApiKeyExpired(expires_at) => write!(f, "API key expired at {expires_at}"),
             Self::AuthenticationError(reason) => write!(f, "Authentication error: {reason}"),
         }
     }
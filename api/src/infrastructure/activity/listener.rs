@@ -0,0 +1,96 @@
+use std::future::poll_fn;
+use std::time::Duration;
+
+use tokio::sync::{broadcast, mpsc};
+use tokio_postgres::{AsyncMessage, Client, NoTls, Notification};
+use tracing::{error, info};
+
+use crate::domain::models::ActivityEvent;
+use crate::errors::Error;
+use crate::infrastructure::database::get_database_url;
+use crate::infrastructure::models::activity_event::ActivityEventData;
+
+use super::ACTIVITY_CHANNEL;
+
+/// How long to wait before reconnecting after losing the connection to the database.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Start listening for activity published by any process, forwarding it to `sender` in the
+/// background. If the connection to the database is lost, it's re-established, although any
+/// activity published in the meantime is missed.
+///
+/// Note that this will return an `Error` if we can't begin listening in the first place.
+pub async fn listen_for_activity(sender: broadcast::Sender<ActivityEvent>) -> Result<(), Error> {
+    let mut listener = ActivityListener::connect().await?;
+
+    tokio::spawn(async move {
+        loop {
+            listener.forward(&sender).await;
+            error!("Lost connection whilst listening for activity, reconnecting...");
+
+            listener = loop {
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                match ActivityListener::connect().await {
+                    Ok(listener) => break listener,
+                    Err(error) => error!("Failed to listen for activity: {:?}", error),
+                }
+            };
+        }
+    });
+
+    Ok(())
+}
+
+/// A dedicated connection to the database, listening on the activity channel. Notifications can't
+/// be received via pooled connections, since they're only delivered to the connection that
+/// listened for them.
+struct ActivityListener {
+    // The connection is closed once its client is dropped, so we need to hold on to it.
+    _client: Client,
+    notifications: mpsc::UnboundedReceiver<Notification>,
+}
+
+impl ActivityListener {
+    async fn connect() -> Result<Self, Error> {
+        let (client, mut connection) = tokio_postgres::connect(&get_database_url(), NoTls)
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        // The connection only makes progress whilst its messages are polled, so this needs to be
+        // happening before we can start listening.
+        let (notification_sender, notifications) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(Ok(message)) = poll_fn(|cx| connection.poll_message(cx)).await {
+                if let AsyncMessage::Notification(notification) = message {
+                    if notification_sender.send(notification).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        client
+            .batch_execute(&format!("LISTEN {ACTIVITY_CHANNEL}"))
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+        info!("Listening for activity");
+
+        Ok(Self {
+            _client: client,
+            notifications,
+        })
+    }
+
+    /// Forward activity to `sender` until the connection is lost.
+    async fn forward(&mut self, sender: &broadcast::Sender<ActivityEvent>) {
+        while let Some(notification) = self.notifications.recv().await {
+            match serde_json::from_str::<ActivityEventData>(notification.payload()) {
+                // Sending only fails when nobody is currently streaming activity, which is fine.
+                Ok(data) => {
+                    let _ = sender.send(ActivityEvent::from(data));
+                }
+                Err(error) => error!("Received invalid activity: {:?}", error),
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+pub mod listener;
+pub mod publisher;
+
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::models::ActivityEvent;
+use crate::errors::Error;
+
+pub use publisher::ActivityPublisher;
+
+/// The Postgres channel that activity is published on.
+pub const ACTIVITY_CHANNEL: &str = "cron_mon_activity";
+
+/// Publish activity to whichever API processes are streaming it to clients.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PublishActivity {
+    /// Publish something that has just happened. Note that activity is only received by the
+    /// processes listening at the time, so there's no guarantee that anyone receives it.
+    async fn publish(&self, event: &ActivityEvent) -> Result<(), Error>;
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use diesel::sql_types::Text;
+use diesel_async::RunQueryDsl;
+
+use crate::domain::models::ActivityEvent;
+use crate::errors::Error;
+use crate::infrastructure::database::{get_connection, DbPool};
+use crate::infrastructure::models::activity_event::ActivityEventData;
+
+use super::{PublishActivity, ACTIVITY_CHANNEL};
+
+/// Publishes activity with Postgres' `NOTIFY`, so that it reaches API processes other than the
+/// one publishing it (including from the alert loop, which runs separately).
+pub struct ActivityPublisher<'a> {
+    pool: &'a DbPool,
+}
+
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> ActivityPublisher<'a> {
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> PublishActivity for ActivityPublisher<'a> {
+    async fn publish(&self, event: &ActivityEvent) -> Result<(), Error> {
+        // Activity is kept small (it never includes a Job's output), so that it always fits
+        // within the 8000 byte limit on notification payloads.
+        let payload = serde_json::to_string(&ActivityEventData::from(event))
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        let mut connection = get_connection(self.pool).await?;
+        diesel::sql_query("SELECT pg_notify($1, $2)")
+            .bind::<Text, _>(ACTIVITY_CHANNEL)
+            .bind::<Text, _>(payload)
+            .execute(&mut connection)
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -1,25 +1,129 @@
 pub mod jwt;
 
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 use mockall::automock;
 
+use crate::domain::models::Actor;
 use crate::errors::Error;
 
+/// The audience that tokens are issued for by default.
+pub const CLIENT_ID: &str = "cron-mon";
+
+/// The claims of a decoded token. Claims that not every OpenID Connect provider includes are
+/// defaulted when they're missing.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct Jwt {
+    #[serde(default)]
     pub acr: String,
+    #[serde(default)]
     pub auth_time: u64,
+    #[serde(default)]
     pub azp: String,
     pub name: String,
     pub tenant: String,
     pub exp: u64,
     pub iat: u64,
     pub iss: String,
+    #[serde(default)]
     pub jti: String,
     pub sub: String,
+    /// The user's realm roles.
+    #[serde(default)]
+    pub realm_access: RoleClaim,
+    /// The user's client roles, by client.
+    #[serde(default)]
+    pub resource_access: HashMap<String, RoleClaim>,
+    /// The user's roles, as read from the configured roles claim and from their client roles for
+    /// the audiences that tokens are issued for.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl Jwt {
+    /// The user's role within their tenant. This is the most privileged role that they've been
+    /// given, which includes the configured default role for users who haven't been given any.
+    pub fn role(&self) -> Role {
+        self.roles
+            .iter()
+            .filter_map(|role| Role::from_name(role))
+            .max()
+            .unwrap_or(Role::Viewer)
+    }
+
+    /// Check that the user has at least the given role.
+    pub fn require_role(&self, role: Role) -> Result<(), Error> {
+        if self.role() >= role {
+            Ok(())
+        } else {
+            Err(Error::Forbidden(format!("Requires the '{role}' role")))
+        }
+    }
+
+    /// How long until the token expires, which is zero if it already has.
+    pub fn expires_in(&self) -> Duration {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Duration::from_secs(self.exp.saturating_sub(now))
+    }
+
+    /// The user, as they're recorded in the audit log.
+    pub fn actor(&self) -> Actor {
+        Actor {
+            id: self.sub.clone(),
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// The roles found in a token's `realm_access` and `resource_access` claims.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RoleClaim {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// The roles that users can have within their tenant, from least to most privileged. Each role can
+/// do everything that the roles before it can.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Role {
+    /// Can view Monitors, Jobs, API keys and alert configurations.
+    Viewer,
+    /// Can also create and update Monitors and alert configurations.
+    Operator,
+    /// Can also delete Monitors and alert configurations, manage API keys, and view the audit log.
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::Viewer, Self::Operator, Self::Admin]
+            .into_iter()
+            .find(|role| role.as_str() == name)
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 #[cfg_attr(test, automock)]
@@ -27,3 +131,112 @@ pub struct Jwt {
 pub trait JwtAuth {
     async fn decode_jwt(&self, token: &str) -> Result<Jwt, Error>;
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn jwt(roles: &[&str]) -> Jwt {
+        Jwt {
+            acr: "acr".to_string(),
+            auth_time: 1234567890,
+            azp: "azp".to_string(),
+            name: "John Doe".to_string(),
+            tenant: "tenant".to_string(),
+            exp: 1234567899,
+            iat: 1234567890,
+            iss: "iss".to_string(),
+            jti: "jti".to_string(),
+            sub: "1234567890".to_string(),
+            realm_access: RoleClaim::default(),
+            resource_access: HashMap::new(),
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+        }
+    }
+
+    #[rstest]
+    #[case::no_roles(jwt(&[]), Role::Viewer)]
+    #[case::unrelated_roles(jwt(&["offline_access"]), Role::Viewer)]
+    #[case::one_role(jwt(&["offline_access", "operator"]), Role::Operator)]
+    #[case::most_privileged(jwt(&["viewer", "admin"]), Role::Admin)]
+    fn test_role(#[case] jwt: Jwt, #[case] expected: Role) {
+        assert_eq!(jwt.role(), expected);
+    }
+
+    #[rstest]
+    #[case::viewer(&["viewer"], Role::Viewer, Ok(()))]
+    #[case::higher_role(&["admin"], Role::Operator, Ok(()))]
+    #[case::lower_role(
+        &["operator"],
+        Role::Admin,
+        Err(Error::Forbidden("Requires the 'admin' role".to_owned()))
+    )]
+    #[case::no_roles(
+        &[],
+        Role::Operator,
+        Err(Error::Forbidden("Requires the 'operator' role".to_owned()))
+    )]
+    fn test_require_role(
+        #[case] roles: &[&str],
+        #[case] required: Role,
+        #[case] expected: Result<(), Error>,
+    ) {
+        assert_eq!(jwt(roles).require_role(required), expected);
+    }
+
+    #[test]
+    fn test_expires_in() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let unexpired = Jwt {
+            exp: now + 3600,
+            ..jwt(&[])
+        };
+        assert!(unexpired.expires_in() > Duration::from_secs(3590));
+        assert!(unexpired.expires_in() <= Duration::from_secs(3600));
+
+        let expired = Jwt {
+            exp: now - 3600,
+            ..jwt(&[])
+        };
+        assert_eq!(expired.expires_in(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_actor() {
+        assert_eq!(
+            jwt(&[]).actor(),
+            Actor {
+                id: "1234567890".to_owned(),
+                name: "John Doe".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_without_roles() {
+        let jwt: Jwt = serde_json::from_value(serde_json::json!({
+            "acr": "acr",
+            "auth_time": 1234567890,
+            "azp": "azp",
+            "name": "John Doe",
+            "tenant": "tenant",
+            "exp": 1234567899,
+            "iat": 1234567890,
+            "iss": "iss",
+            "jti": "jti",
+            "sub": "1234567890"
+        }))
+        .unwrap();
+
+        assert_eq!(jwt.realm_access, RoleClaim::default());
+        assert!(jwt.resource_access.is_empty());
+        assert!(jwt.roles.is_empty());
+        assert_eq!(jwt.role(), Role::Viewer);
+    }
+}
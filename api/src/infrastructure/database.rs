@@ -41,6 +41,6 @@ pub fn run_migrations() {
     println!("Migrations complete");
 }
 
-fn get_database_url() -> String {
+pub(crate) fn get_database_url() -> String {
     env::var("DATABASE_URL").expect("'DATABASE_URL' missing from environment")
 }
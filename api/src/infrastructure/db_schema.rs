@@ -12,6 +12,9 @@ diesel::table! {
         active -> Bool,
         on_late -> Bool,
         on_error -> Bool,
+        on_recovery -> Bool,
+        on_anomaly -> Bool,
+        label_selector -> Nullable<Varchar>,
     }
 }
 
@@ -27,6 +30,39 @@ diesel::table! {
         last_used_monitor_name -> Nullable<Varchar>,
         name -> Varchar,
         masked -> Varchar,
+        scopes -> Array<Text>,
+        monitor_ids -> Nullable<Array<Uuid>>,
+        expires_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    audit_event (audit_event_id) {
+        audit_event_id -> Uuid,
+        created_at -> Timestamp,
+        tenant -> Varchar,
+        actor_id -> Varchar,
+        actor_name -> Varchar,
+        action -> Varchar,
+        entity_type -> Varchar,
+        entity_id -> Uuid,
+        before -> Nullable<Jsonb>,
+        after -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    email_alert_config (alert_config_id) {
+        alert_config_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        email_recipients -> Array<Text>,
+        email_smtp_host -> Nullable<Varchar>,
+        email_smtp_port -> Nullable<Int4>,
+        email_smtp_username -> Nullable<Varchar>,
+        email_smtp_password -> Nullable<Varchar>,
+        email_smtp_from -> Nullable<Varchar>,
+        email_smtp_tls -> Nullable<Varchar>,
     }
 }
 
@@ -43,6 +79,19 @@ diesel::table! {
         max_end_time -> Timestamp,
         late_alert_sent -> Bool,
         error_alert_sent -> Bool,
+        late_alert_resolved -> Bool,
+        error_alert_resolved -> Bool,
+        recovery_alert_sent -> Bool,
+        below_threshold -> Bool,
+        during_maintenance -> Bool,
+        anomaly_checked -> Bool,
+        overlapped -> Bool,
+        overlap_alert_sent -> Bool,
+        exit_code -> Nullable<Int4>,
+        hostname -> Nullable<Varchar>,
+        command -> Nullable<Text>,
+        version -> Nullable<Varchar>,
+        metrics -> Jsonb,
     }
 }
 
@@ -55,6 +104,30 @@ diesel::table! {
         expected_duration -> Int4,
         grace_duration -> Int4,
         tenant -> Varchar,
+        schedule -> Nullable<Varchar>,
+        schedule_timezone -> Nullable<Varchar>,
+        missed_start_cutoff -> Nullable<Timestamp>,
+        kind -> Varchar,
+        heartbeat_period -> Nullable<Int4>,
+        alert_threshold_consecutive -> Int4,
+        alert_threshold_window_failures -> Nullable<Int4>,
+        alert_threshold_window_runs -> Nullable<Int4>,
+        retention_max_age_days -> Nullable<Int4>,
+        retention_max_jobs -> Nullable<Int4>,
+        jobs_started -> Int8,
+        jobs_succeeded -> Int8,
+        jobs_failed -> Int8,
+        jobs_late -> Int8,
+        paused -> Bool,
+        resume_at -> Nullable<Timestamp>,
+        maintenance_windows -> Jsonb,
+        auto_tune_min_expected -> Nullable<Int4>,
+        auto_tune_max_expected -> Nullable<Int4>,
+        auto_tune_min_grace -> Nullable<Int4>,
+        auto_tune_max_grace -> Nullable<Int4>,
+        anomaly_min_duration -> Nullable<Int4>,
+        anomaly_max_deviations -> Nullable<Float8>,
+        concurrency_policy -> Varchar,
     }
 }
 
@@ -66,6 +139,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    monitor_label (monitor_id, key) {
+        monitor_id -> Uuid,
+        key -> Varchar,
+        value -> Varchar,
+    }
+}
+
+diesel::table! {
+    pagerduty_alert_config (alert_config_id) {
+        alert_config_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        pagerduty_routing_key -> Varchar,
+    }
+}
+
 diesel::table! {
     slack_alert_config (alert_config_id) {
         alert_config_id -> Uuid,
@@ -76,16 +166,82 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook_alert_config (alert_config_id) {
+        alert_config_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        webhook_url -> Varchar,
+        webhook_method -> Varchar,
+        webhook_headers -> Jsonb,
+        webhook_secret -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    webhook_delivery (delivery_id) {
+        delivery_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        subscription_id -> Uuid,
+        tenant -> Varchar,
+        event_id -> Uuid,
+        event_type -> Varchar,
+        payload -> Jsonb,
+        status -> Varchar,
+        next_attempt_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_delivery_attempt (attempt_id) {
+        attempt_id -> Uuid,
+        delivery_id -> Uuid,
+        attempted_at -> Timestamp,
+        succeeded -> Bool,
+        response_status -> Nullable<Int4>,
+        error -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    webhook_subscription (subscription_id) {
+        subscription_id -> Uuid,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        tenant -> Varchar,
+        name -> Varchar,
+        url -> Varchar,
+        secret -> Nullable<Varchar>,
+        event_types -> Array<Text>,
+        active -> Bool,
+    }
+}
+
+diesel::joinable!(email_alert_config -> alert_config (alert_config_id));
 diesel::joinable!(job -> monitor (monitor_id));
 diesel::joinable!(monitor_alert_config -> alert_config (alert_config_id));
 diesel::joinable!(monitor_alert_config -> monitor (monitor_id));
+diesel::joinable!(monitor_label -> monitor (monitor_id));
+diesel::joinable!(pagerduty_alert_config -> alert_config (alert_config_id));
 diesel::joinable!(slack_alert_config -> alert_config (alert_config_id));
+diesel::joinable!(webhook_alert_config -> alert_config (alert_config_id));
+diesel::joinable!(webhook_delivery -> webhook_subscription (subscription_id));
+diesel::joinable!(webhook_delivery_attempt -> webhook_delivery (delivery_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     alert_config,
     api_key,
+    audit_event,
+    email_alert_config,
     job,
     monitor,
     monitor_alert_config,
+    monitor_label,
+    pagerduty_alert_config,
     slack_alert_config,
+    webhook_alert_config,
+    webhook_delivery,
+    webhook_delivery_attempt,
+    webhook_subscription,
 );
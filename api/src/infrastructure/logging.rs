@@ -1,13 +1,28 @@
 use std::env;
+use std::io;
 
 use tracing_subscriber;
+use tracing_subscriber::fmt::MakeWriter;
 
+/// Log to stdout.
 pub fn init_logging() {
+    init(io::stdout);
+}
+
+/// Log to stderr, leaving stdout free for the output of commands being run.
+pub fn init_stderr_logging() {
+    init(io::stderr);
+}
+
+fn init<W>(writer: W)
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
     let json_logging = match env::var("JSON_LOGGING") {
         Ok(val) => val.trim().parse().unwrap(),
         Err(_) => false,
     };
-    let builder = tracing_subscriber::fmt();
+    let builder = tracing_subscriber::fmt().with_writer(writer);
     if json_logging {
         builder.json().init();
     } else {
@@ -0,0 +1,259 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use prometheus::{
+    Histogram, HistogramOpts, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use crate::domain::models::MonitorMetrics;
+
+/// The content type of metrics rendered in the Prometheus text format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// The labels identifying the notifier and kind of alert for alert metrics.
+const ALERT_LABELS: &[&str] = &["notifier", "alert"];
+
+/// The labels identifying the Monitor for per-Monitor metrics.
+const MONITOR_LABELS: &[&str] = &["tenant", "monitor_id", "monitor_name"];
+
+static ALERTS_SENT: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new(
+            "cronmon_alerts_sent_total",
+            "Alerts sent, by notifier and kind of alert.",
+        ),
+        ALERT_LABELS,
+    ))
+});
+
+static ALERT_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new(
+            "cronmon_alert_failures_total",
+            "Alerts that failed to send, by notifier and kind of alert.",
+        ),
+        ALERT_LABELS,
+    ))
+});
+
+static ALERT_LOOP_DURATION: LazyLock<Histogram> = LazyLock::new(|| {
+    register(Histogram::with_opts(HistogramOpts::new(
+        "cronmon_alert_loop_duration_seconds",
+        "How long each pass of the alert loop took.",
+    )))
+});
+
+/// Register a process metric with the default registry, so that it's included when rendering
+/// metrics.
+fn register<T: prometheus::core::Collector + Clone + 'static>(metric: prometheus::Result<T>) -> T {
+    let metric = metric.expect("Process metrics should be valid");
+    prometheus::register(Box::new(metric.clone()))
+        .expect("Process metrics should only be registered once");
+    metric
+}
+
+/// Record an attempt to send an alert via a notifier.
+pub fn record_alert(notifier: &str, alert: &str, succeeded: bool) {
+    let counter = if succeeded {
+        &ALERTS_SENT
+    } else {
+        &ALERT_FAILURES
+    };
+    counter.with_label_values(&[notifier, alert]).inc();
+}
+
+/// Record how long a pass of the alert loop took.
+pub fn record_alert_loop(duration: Duration) {
+    ALERT_LOOP_DURATION.observe(duration.as_secs_f64());
+}
+
+/// Render the metrics of this process in the Prometheus text format.
+pub fn render_process_metrics() -> String {
+    encode(&prometheus::gather())
+}
+
+/// Render the metrics of this process, along with per-Monitor metrics, in the Prometheus text
+/// format.
+pub fn render_metrics(monitors: &[MonitorMetrics]) -> String {
+    let registry = Registry::new();
+    let gauge = |name: &str, help: &str| {
+        let gauge = IntGaugeVec::new(Opts::new(name, help), MONITOR_LABELS)
+            .expect("Monitor metrics should be valid");
+        registry
+            .register(Box::new(gauge.clone()))
+            .expect("Monitor metrics should only be registered once");
+        gauge
+    };
+    let counter = |name: &str, help: &str| {
+        let counter = IntCounterVec::new(Opts::new(name, help), MONITOR_LABELS)
+            .expect("Monitor metrics should be valid");
+        registry
+            .register(Box::new(counter.clone()))
+            .expect("Monitor metrics should only be registered once");
+        counter
+    };
+
+    let last_started = gauge(
+        "cronmon_monitor_last_job_start_timestamp_seconds",
+        "When the Monitor's most recent job started.",
+    );
+    let last_finished = gauge(
+        "cronmon_monitor_last_job_finish_timestamp_seconds",
+        "When the Monitor's most recently finished job finished.",
+    );
+    let last_duration = gauge(
+        "cronmon_monitor_last_job_duration_seconds",
+        "How long the Monitor's most recently finished job ran for.",
+    );
+    let in_progress = gauge(
+        "cronmon_monitor_jobs_in_progress",
+        "The number of the Monitor's jobs that are still running.",
+    );
+    let started = counter(
+        "cronmon_monitor_jobs_started_total",
+        "Jobs started by the Monitor.",
+    );
+    let succeeded = counter(
+        "cronmon_monitor_jobs_succeeded_total",
+        "Jobs that finished successfully.",
+    );
+    let failed = counter(
+        "cronmon_monitor_jobs_failed_total",
+        "Jobs that finished with an error.",
+    );
+    let late = counter(
+        "cronmon_monitor_jobs_late_total",
+        "Jobs that finished late.",
+    );
+
+    for monitor in monitors {
+        let monitor_id = monitor.monitor_id.to_string();
+        let labels = [
+            monitor.tenant.as_str(),
+            monitor_id.as_str(),
+            monitor.name.as_str(),
+        ];
+
+        if let Some(start_time) = monitor.last_started {
+            last_started
+                .with_label_values(&labels)
+                .set(start_time.and_utc().timestamp());
+        }
+        if let Some(end_time) = monitor.last_finished {
+            last_finished
+                .with_label_values(&labels)
+                .set(end_time.and_utc().timestamp());
+        }
+        if let Some(duration) = monitor.last_duration {
+            last_duration.with_label_values(&labels).set(duration);
+        }
+        in_progress
+            .with_label_values(&labels)
+            .set(monitor.jobs_in_progress);
+        started
+            .with_label_values(&labels)
+            .inc_by(monitor.jobs_started as u64);
+        succeeded
+            .with_label_values(&labels)
+            .inc_by(monitor.jobs_succeeded as u64);
+        failed
+            .with_label_values(&labels)
+            .inc_by(monitor.jobs_failed as u64);
+        late.with_label_values(&labels)
+            .inc_by(monitor.jobs_late as u64);
+    }
+
+    let mut metric_families = prometheus::gather();
+    metric_families.extend(registry.gather());
+    encode(&metric_families)
+}
+
+fn encode(metric_families: &[prometheus::proto::MetricFamily]) -> String {
+    TextEncoder::new()
+        .encode_to_string(metric_families)
+        .expect("Metrics should always be encodable as text")
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use super::*;
+
+    #[test]
+    fn rendering_monitor_metrics() {
+        let metrics = render_metrics(&[
+            MonitorMetrics {
+                monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                tenant: "foo".to_owned(),
+                name: "db-backup.py".to_owned(),
+                last_started: Some(gen_datetime("2024-05-01T00:20:00")),
+                last_finished: Some(gen_datetime("2024-05-01T00:15:00")),
+                last_duration: Some(300),
+                jobs_started: 10,
+                jobs_succeeded: 7,
+                jobs_failed: 2,
+                jobs_late: 1,
+                jobs_in_progress: 1,
+            },
+            MonitorMetrics {
+                monitor_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
+                tenant: "bar".to_owned(),
+                name: "new-monitor".to_owned(),
+                last_started: None,
+                last_finished: None,
+                last_duration: None,
+                jobs_started: 0,
+                jobs_succeeded: 0,
+                jobs_failed: 0,
+                jobs_late: 0,
+                jobs_in_progress: 0,
+            },
+        ]);
+
+        let labels = "monitor_id=\"41ebffb4-a188-48e9-8ec1-61380085cde3\",\
+            monitor_name=\"db-backup.py\",tenant=\"foo\"";
+        for expected in [
+            format!("cronmon_monitor_last_job_start_timestamp_seconds{{{labels}}} 1714522800"),
+            format!("cronmon_monitor_last_job_finish_timestamp_seconds{{{labels}}} 1714522500"),
+            format!("cronmon_monitor_last_job_duration_seconds{{{labels}}} 300"),
+            format!("cronmon_monitor_jobs_in_progress{{{labels}}} 1"),
+            format!("cronmon_monitor_jobs_started_total{{{labels}}} 10"),
+            format!("cronmon_monitor_jobs_succeeded_total{{{labels}}} 7"),
+            format!("cronmon_monitor_jobs_failed_total{{{labels}}} 2"),
+            format!("cronmon_monitor_jobs_late_total{{{labels}}} 1"),
+            "# TYPE cronmon_monitor_jobs_started_total counter".to_owned(),
+        ] {
+            assert!(
+                metrics.contains(&expected),
+                "{expected} missing from {metrics}"
+            );
+        }
+
+        // Monitors without any finished jobs only have their totals and in progress jobs.
+        let labels = "monitor_id=\"01a92c6c-6803-409d-b675-022fff62575a\",\
+            monitor_name=\"new-monitor\",tenant=\"bar\"";
+        assert!(metrics.contains(&format!("cronmon_monitor_jobs_started_total{{{labels}}} 0")));
+        assert!(metrics.contains(&format!("cronmon_monitor_jobs_in_progress{{{labels}}} 0")));
+        assert!(!metrics.contains(&format!(
+            "cronmon_monitor_last_job_duration_seconds{{{labels}}}"
+        )));
+    }
+
+    #[test]
+    fn recording_process_metrics() {
+        record_alert("slack", "late_job", true);
+        record_alert("slack", "late_job", false);
+        record_alert_loop(Duration::from_millis(250));
+
+        let metrics = render_process_metrics();
+
+        assert!(
+            metrics.contains("cronmon_alerts_sent_total{alert=\"late_job\",notifier=\"slack\"}")
+        );
+        assert!(
+            metrics.contains("cronmon_alert_failures_total{alert=\"late_job\",notifier=\"slack\"}")
+        );
+        assert!(metrics.contains("cronmon_alert_loop_duration_seconds_count"));
+    }
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use rocket::fairing::{Fairing, Info, Kind, Result};
+use rocket::{Build, Rocket};
+use tokio::sync::broadcast;
+use tracing::error;
+
+use crate::domain::models::ActivityEvent;
+use crate::infrastructure::activity::listener::listen_for_activity;
+
+/// How many events each client can fall behind by before they start missing them.
+const ACTIVITY_CAPACITY: usize = 256;
+
+/// Listens for activity published by any process, making it available to routes as a managed
+/// `broadcast::Sender<ActivityEvent>` that they can subscribe to.
+pub struct ActivityFeed;
+
+#[async_trait]
+impl Fairing for ActivityFeed {
+    fn info(&self) -> Info {
+        Info {
+            name: "Activity feed",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> Result {
+        let (sender, _) = broadcast::channel::<ActivityEvent>(ACTIVITY_CAPACITY);
+
+        match listen_for_activity(sender.clone()).await {
+            Ok(()) => Ok(rocket.manage(sender)),
+            Err(error) => {
+                error!("Failed to listen for activity: {:?}", error);
+                Err(rocket)
+            }
+        }
+    }
+}
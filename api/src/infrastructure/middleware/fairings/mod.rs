@@ -1,2 +1,3 @@
+pub mod activity;
 pub mod cors;
 pub mod default_json;
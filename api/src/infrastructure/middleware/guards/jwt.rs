@@ -206,6 +206,9 @@ mod tests {
                     iat: 1234567890,
                     auth_time: 1234567890,
                     exp: 1234567899,
+                    realm_access: Default::default(),
+                    resource_access: Default::default(),
+                    roles: vec![],
                 })
             });
 
@@ -0,0 +1,116 @@
+use std::env;
+
+use async_trait::async_trait;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::State;
+use sha2::{Digest, Sha256};
+
+use crate::errors::Error;
+
+/// The token that metrics scrapers must provide as a bearer token, if metrics are enabled.
+pub struct ScrapeToken(pub Option<String>);
+
+impl ScrapeToken {
+    /// Read the scrape token from the `METRICS_SCRAPE_TOKEN` environment variable. Metrics are
+    /// disabled when it isn't set.
+    pub fn from_env() -> Self {
+        Self(
+            env::var("METRICS_SCRAPE_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+        )
+    }
+}
+
+/// A request guard for metrics scrapers, which must provide the scrape token.
+pub struct Scraper;
+
+#[async_trait]
+impl<'r> FromRequest<'r> for Scraper {
+    type Error = Error;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let expected_token = match request.guard::<&State<ScrapeToken>>().await {
+            Outcome::Success(scrape_token) => scrape_token.inner().0.as_deref(),
+            _ => None,
+        };
+        let Some(expected_token) = expected_token else {
+            return Outcome::Error((
+                Status::Unauthorized,
+                Error::Unauthorized("Metrics are disabled".to_string()),
+            ));
+        };
+
+        let provided_token = request
+            .headers()
+            .get_one("Authorization")
+            .filter(|bearer_token| bearer_token.to_lowercase().starts_with("bearer "))
+            .map(|bearer_token| &bearer_token[7..]);
+
+        // Compare digests rather than the tokens themselves, so that the comparison doesn't leak
+        // how much of the token is correct.
+        match provided_token {
+            Some(token) if Sha256::digest(token) == Sha256::digest(expected_token) => {
+                Outcome::Success(Scraper)
+            }
+            _ => Outcome::Error((
+                Status::Unauthorized,
+                Error::Unauthorized("Invalid scrape token".to_string()),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::http::Header;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+    use rstest::rstest;
+
+    use super::{ScrapeToken, Scraper};
+
+    #[rocket::get("/")]
+    async fn protected_index(_scraper: Scraper) -> &'static str {
+        "metrics"
+    }
+
+    fn client(token: Option<&str>) -> Client {
+        let test_rocket = rocket::build()
+            .manage(ScrapeToken(token.map(str::to_owned)))
+            .mount("/", rocket::routes![protected_index]);
+        Client::tracked(test_rocket)
+            .expect("Couldn't create test Rocket app for Scraper request guard test")
+    }
+
+    #[test]
+    fn test_scrape_token_provided() {
+        let client = client(Some("scrape-token"));
+
+        let response = client
+            .get("/")
+            .header(Header::new("Authorization", "Bearer scrape-token"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "metrics");
+    }
+
+    #[rstest]
+    #[case::not_provided(Some("scrape-token"), None)]
+    #[case::not_bearer(Some("scrape-token"), Some("scrape-token"))]
+    #[case::wrong_token(Some("scrape-token"), Some("Bearer other-token"))]
+    #[case::metrics_disabled(None, Some("Bearer scrape-token"))]
+    fn test_scrape_token_rejected(#[case] token: Option<&str>, #[case] header: Option<&str>) {
+        let client = client(token);
+        let mut request = client.get("/");
+        if let Some(header) = header {
+            request = request.header(Header::new("Authorization", header.to_owned()));
+        }
+
+        let response = request.dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+}
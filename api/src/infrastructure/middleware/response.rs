@@ -15,10 +15,17 @@ impl<'r> Responder<'r, 'static> for Error {
             Error::ApiKeyNotFound(_) => (Status::NotFound, "API Key Not Found"),
             Error::JobNotFound(_, _) => (Status::NotFound, "Job Not Found"),
             Error::AlertConfigNotFound(_) => (Status::NotFound, "Alert Configuration Not Found"),
+            Error::AuditEventNotFound(_) => (Status::NotFound, "Audit Event Not Found"),
+            Error::SubscriptionNotFound(_) => (Status::NotFound, "Webhook Subscription Not Found"),
             Error::JobAlreadyFinished(_) => (Status::BadRequest, "Job Already Finished"),
+            Error::JobInProgress(_) => (Status::Conflict, "Job In Progress"),
+            Error::NotHeartbeatMonitor(_) => (Status::BadRequest, "Not A Heartbeat Monitor"),
             Error::ErroneousJobAlertFailure(_) => {
                 (Status::InternalServerError, "Late Job Process Failure")
             }
+            Error::MissedStartAlertFailure(_) => {
+                (Status::InternalServerError, "Missed Start Process Failure")
+            }
             Error::AlertConfigurationError(_) => {
                 (Status::InternalServerError, "Alert Configuration Error")
             }
@@ -30,8 +37,14 @@ impl<'r> Responder<'r, 'static> for Error {
             Error::InvalidAlertConfig(_) => {
                 (Status::InternalServerError, "Invalid Alert Configuration")
             }
+            Error::InvalidSubscription(_) => {
+                (Status::InternalServerError, "Invalid Webhook Subscription")
+            }
+            Error::InvalidApiKey(_) => (Status::InternalServerError, "Invalid API Key"),
             Error::NotifyError(_) => (Status::InternalServerError, "Notify Error"),
             Error::Unauthorized(_) => (Status::Unauthorized, "Unauthorized"),
+            Error::Forbidden(_) => (Status::Forbidden, "Forbidden"),
+            Error::ApiKeyExpired(_) => (Status::Unauthorized, "API Key Expired"),
             Error::AuthenticationError(_) => (Status::InternalServerError, "Authentication Error"),
         };
         let body =
@@ -54,7 +67,7 @@ mod tests {
     use rstest::*;
     use serde_json::{json, Value};
 
-    use test_utils::gen_uuid;
+    use test_utils::{gen_datetime, gen_uuid};
 
     use super::*;
 
@@ -100,6 +113,20 @@ mod tests {
         ]))
     }
 
+    #[rocket::get("/audit_event_not_found")]
+    fn audit_event_not_found() -> Result<(), Error> {
+        Err(Error::AuditEventNotFound(gen_uuid(
+            "5c4b7c5e-1b8b-4f4d-9a33-2a3e0f5bd4c7",
+        )))
+    }
+
+    #[rocket::get("/subscription_not_found")]
+    fn subscription_not_found() -> Result<(), Error> {
+        Err(Error::SubscriptionNotFound(gen_uuid(
+            "3867e53d-9c17-4ce9-b153-eff3d8c9edec",
+        )))
+    }
+
     #[rocket::get("/job_already_finished")]
     fn job_already_finished() -> Result<(), Error> {
         Err(Error::JobAlreadyFinished(gen_uuid(
@@ -107,6 +134,20 @@ mod tests {
         )))
     }
 
+    #[rocket::get("/job_in_progress")]
+    fn job_in_progress() -> Result<(), Error> {
+        Err(Error::JobInProgress(gen_uuid(
+            "41ebffb4-a188-48e9-8ec1-61380085cde3",
+        )))
+    }
+
+    #[rocket::get("/not_heartbeat_monitor")]
+    fn not_heartbeat_monitor() -> Result<(), Error> {
+        Err(Error::NotHeartbeatMonitor(gen_uuid(
+            "41ebffb4-a188-48e9-8ec1-61380085cde3",
+        )))
+    }
+
     #[rocket::get("/late_job_process_failure")]
     fn late_job_process_failure() -> Result<(), Error> {
         Err(Error::ErroneousJobAlertFailure(
@@ -114,6 +155,13 @@ mod tests {
         ))
     }
 
+    #[rocket::get("/missed_start_process_failure")]
+    fn missed_start_process_failure() -> Result<(), Error> {
+        Err(Error::MissedStartAlertFailure(
+            "something went wrong".to_string(),
+        ))
+    }
+
     #[rocket::get("/alert_config_error")]
     fn alert_config_error() -> Result<(), Error> {
         Err(Error::AlertConfigurationError(
@@ -138,6 +186,18 @@ mod tests {
         ))
     }
 
+    #[rocket::get("/invalid_subscription")]
+    fn invalid_subscription() -> Result<(), Error> {
+        Err(Error::InvalidSubscription(
+            "invalid subscription".to_string(),
+        ))
+    }
+
+    #[rocket::get("/invalid_api_key")]
+    fn invalid_api_key() -> Result<(), Error> {
+        Err(Error::InvalidApiKey("invalid api key".to_string()))
+    }
+
     #[rocket::get("/notify_error")]
     fn notify_error() -> Result<(), Error> {
         Err(Error::NotifyError("something went wrong".to_string()))
@@ -148,6 +208,16 @@ mod tests {
         Err(Error::Unauthorized("insufficient permissions".to_string()))
     }
 
+    #[rocket::get("/forbidden")]
+    fn forbidden() -> Result<(), Error> {
+        Err(Error::Forbidden("key can't be used for this".to_string()))
+    }
+
+    #[rocket::get("/api_key_expired")]
+    fn api_key_expired() -> Result<(), Error> {
+        Err(Error::ApiKeyExpired(gen_datetime("2025-05-01T12:00:00")))
+    }
+
     #[rocket::get("/auth_error")]
     fn auth_error() -> Result<(), Error> {
         Err(Error::AuthenticationError(
@@ -166,14 +236,23 @@ mod tests {
                 job_not_found,
                 single_alert_config_not_found,
                 multiple_alert_config_not_found,
+                audit_event_not_found,
+                subscription_not_found,
                 job_already_finished,
+                job_in_progress,
+                not_heartbeat_monitor,
                 late_job_process_failure,
+                missed_start_process_failure,
                 alert_config_error,
                 invalid_monitor,
                 invalid_job,
                 invalid_alert_config,
+                invalid_subscription,
+                invalid_api_key,
                 notify_error,
                 unauthorized,
+                forbidden,
+                api_key_expired,
                 auth_error
             ],
         );
@@ -298,6 +377,44 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_audit_event_not_found(test_client: Client) {
+        let response = test_client.get("/audit_event_not_found").dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 404,
+                    "reason": "Audit Event Not Found",
+                    "description": "Failed to find audit event with id \
+                                    '5c4b7c5e-1b8b-4f4d-9a33-2a3e0f5bd4c7'"
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_subscription_not_found(test_client: Client) {
+        let response = test_client.get("/subscription_not_found").dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 404,
+                    "reason": "Webhook Subscription Not Found",
+                    "description": "Failed to find webhook subscription with id \
+                                    '3867e53d-9c17-4ce9-b153-eff3d8c9edec'"
+                }
+            })
+        );
+    }
+
     #[rstest]
     fn test_job_already_finished(test_client: Client) {
         let response = test_client.get("/job_already_finished").dispatch();
@@ -316,6 +433,44 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_job_in_progress(test_client: Client) {
+        let response = test_client.get("/job_in_progress").dispatch();
+
+        assert_eq!(response.status(), Status::Conflict);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 409,
+                    "reason": "Job In Progress",
+                    "description": "Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3') already has a \
+                        job in progress"
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_not_heartbeat_monitor(test_client: Client) {
+        let response = test_client.get("/not_heartbeat_monitor").dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 400,
+                    "reason": "Not A Heartbeat Monitor",
+                    "description": "Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3') is not a \
+                        heartbeat monitor"
+                }
+            })
+        );
+    }
+
     #[rstest]
     fn test_late_job_process_failure(test_client: Client) {
         let response = test_client.get("/late_job_process_failure").dispatch();
@@ -334,6 +489,24 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_missed_start_process_failure(test_client: Client) {
+        let response = test_client.get("/missed_start_process_failure").dispatch();
+
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 500,
+                    "reason": "Missed Start Process Failure",
+                    "description": "Failed to process missed start(s): something went wrong"
+                }
+            })
+        );
+    }
+
     #[rstest]
     fn test_alert_config_error(test_client: Client) {
         let response = test_client.get("/alert_config_error").dispatch();
@@ -406,6 +579,42 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_invalid_subscription(test_client: Client) {
+        let response = test_client.get("/invalid_subscription").dispatch();
+
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 500,
+                    "reason": "Invalid Webhook Subscription",
+                    "description": "Invalid Webhook Subscription: invalid subscription"
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_invalid_api_key(test_client: Client) {
+        let response = test_client.get("/invalid_api_key").dispatch();
+
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 500,
+                    "reason": "Invalid API Key",
+                    "description": "Invalid API Key: invalid api key"
+                }
+            })
+        );
+    }
+
     #[rstest]
     fn test_notify_error(test_client: Client) {
         let response = test_client.get("/notify_error").dispatch();
@@ -442,6 +651,42 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_forbidden(test_client: Client) {
+        let response = test_client.get("/forbidden").dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 403,
+                    "reason": "Forbidden",
+                    "description": "Forbidden: key can't be used for this"
+                }
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_api_key_expired(test_client: Client) {
+        let response = test_client.get("/api_key_expired").dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(
+            response.into_json::<Value>().unwrap(),
+            json!({
+                "error": {
+                    "code": 401,
+                    "reason": "API Key Expired",
+                    "description": "API key expired at 2025-05-01 12:00:00"
+                }
+            })
+        );
+    }
+
     #[rstest]
     fn test_authentication_error(test_client: Client) {
         let response = test_client.get("/auth_error").dispatch();
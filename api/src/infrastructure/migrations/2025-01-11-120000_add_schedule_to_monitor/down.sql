@@ -0,0 +1,4 @@
+ALTER TABLE monitor
+    DROP schedule,
+    DROP schedule_timezone,
+    DROP missed_start_cutoff;
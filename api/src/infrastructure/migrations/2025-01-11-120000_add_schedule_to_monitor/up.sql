@@ -0,0 +1,4 @@
+ALTER TABLE monitor
+    ADD schedule VARCHAR NULL,
+    ADD schedule_timezone VARCHAR NULL,
+    ADD missed_start_cutoff TIMESTAMP NULL;
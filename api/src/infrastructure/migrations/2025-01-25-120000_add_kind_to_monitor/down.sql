@@ -0,0 +1,3 @@
+ALTER TABLE monitor
+    DROP kind,
+    DROP heartbeat_period;
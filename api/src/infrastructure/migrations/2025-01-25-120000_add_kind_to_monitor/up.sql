@@ -0,0 +1,3 @@
+ALTER TABLE monitor
+    ADD kind VARCHAR NOT NULL DEFAULT 'job',
+    ADD heartbeat_period INTEGER NULL;
@@ -0,0 +1 @@
+DROP TABLE webhook_alert_config;
@@ -0,0 +1,13 @@
+CREATE TABLE webhook_alert_config (
+    alert_config_id uuid PRIMARY KEY REFERENCES alert_config ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- See slack_alert_config for why these columns are prefixed.
+    webhook_url VARCHAR NOT NULL,
+    webhook_method VARCHAR NOT NULL,
+    webhook_headers JSONB NOT NULL DEFAULT '{}',
+    webhook_secret VARCHAR NULL
+);
+
+SELECT diesel_manage_updated_at('webhook_alert_config');
@@ -0,0 +1,19 @@
+CREATE TABLE email_alert_config (
+    alert_config_id uuid PRIMARY KEY REFERENCES alert_config ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- See slack_alert_config for why these columns are prefixed.
+    email_recipients TEXT[] NOT NULL,
+
+    -- The SMTP relay to send alerts via. When email_smtp_host is NULL, the
+    -- server's relay is used instead, and the remaining columns are ignored.
+    email_smtp_host VARCHAR NULL,
+    email_smtp_port INTEGER NULL,
+    email_smtp_username VARCHAR NULL,
+    email_smtp_password VARCHAR NULL,
+    email_smtp_from VARCHAR NULL,
+    email_smtp_tls VARCHAR NULL
+);
+
+SELECT diesel_manage_updated_at('email_alert_config');
@@ -0,0 +1,3 @@
+ALTER TABLE job
+    DROP late_alert_resolved,
+    DROP error_alert_resolved;
@@ -0,0 +1,14 @@
+ALTER TABLE job
+    ADD late_alert_resolved boolean NULL,
+    ADD error_alert_resolved boolean NULL;
+
+-- Alerts sent before now can't be resolved, so treat them as though they already have been.
+-- Otherwise these jobs would be picked up for resolution the next time the monitor is checked.
+UPDATE job
+SET late_alert_resolved = late_alert_sent, error_alert_resolved = error_alert_sent;
+
+ALTER TABLE job
+    ALTER COLUMN late_alert_resolved SET NOT NULL,
+    ALTER COLUMN late_alert_resolved SET DEFAULT false,
+    ALTER COLUMN error_alert_resolved SET NOT NULL,
+    ALTER COLUMN error_alert_resolved SET DEFAULT false;
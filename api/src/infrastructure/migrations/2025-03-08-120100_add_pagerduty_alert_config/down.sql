@@ -0,0 +1 @@
+DROP TABLE pagerduty_alert_config;
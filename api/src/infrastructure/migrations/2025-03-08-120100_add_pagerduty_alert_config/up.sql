@@ -0,0 +1,10 @@
+CREATE TABLE pagerduty_alert_config (
+    alert_config_id uuid PRIMARY KEY REFERENCES alert_config ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- See slack_alert_config for why these columns are prefixed.
+    pagerduty_routing_key VARCHAR NOT NULL
+);
+
+SELECT diesel_manage_updated_at('pagerduty_alert_config');
@@ -0,0 +1,5 @@
+ALTER TABLE job
+    DROP recovery_alert_sent;
+
+ALTER TABLE alert_config
+    DROP on_recovery;
@@ -0,0 +1,15 @@
+ALTER TABLE alert_config
+    ADD on_recovery boolean NOT NULL DEFAULT false;
+
+ALTER TABLE job
+    ADD recovery_alert_sent boolean NULL;
+
+-- Jobs that finished before now have either already recovered or never will, so treat them as
+-- though recovery alerts have been sent for them. Otherwise any monitor that has recovered in the
+-- past would send a recovery alert the next time it is checked.
+UPDATE job
+SET recovery_alert_sent = end_time IS NOT NULL;
+
+ALTER TABLE job
+    ALTER COLUMN recovery_alert_sent SET NOT NULL,
+    ALTER COLUMN recovery_alert_sent SET DEFAULT false;
@@ -0,0 +1,7 @@
+ALTER TABLE job
+    DROP below_threshold;
+
+ALTER TABLE monitor
+    DROP alert_threshold_consecutive,
+    DROP alert_threshold_window_failures,
+    DROP alert_threshold_window_runs;
@@ -0,0 +1,7 @@
+ALTER TABLE monitor
+    ADD alert_threshold_consecutive INTEGER NOT NULL DEFAULT 1,
+    ADD alert_threshold_window_failures INTEGER NULL,
+    ADD alert_threshold_window_runs INTEGER NULL;
+
+ALTER TABLE job
+    ADD below_threshold boolean NOT NULL DEFAULT false;
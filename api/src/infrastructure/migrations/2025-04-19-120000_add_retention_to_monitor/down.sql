@@ -0,0 +1,3 @@
+ALTER TABLE monitor
+    DROP retention_max_age_days,
+    DROP retention_max_jobs;
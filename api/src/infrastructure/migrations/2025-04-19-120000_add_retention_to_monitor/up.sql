@@ -0,0 +1,3 @@
+ALTER TABLE monitor
+    ADD retention_max_age_days INTEGER NULL,
+    ADD retention_max_jobs INTEGER NULL;
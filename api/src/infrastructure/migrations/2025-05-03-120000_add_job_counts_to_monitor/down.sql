@@ -0,0 +1,5 @@
+ALTER TABLE monitor
+    DROP jobs_started,
+    DROP jobs_succeeded,
+    DROP jobs_failed,
+    DROP jobs_late;
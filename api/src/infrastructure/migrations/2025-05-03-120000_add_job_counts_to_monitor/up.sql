@@ -0,0 +1,18 @@
+ALTER TABLE monitor
+    ADD jobs_started BIGINT NOT NULL DEFAULT 0,
+    ADD jobs_succeeded BIGINT NOT NULL DEFAULT 0,
+    ADD jobs_failed BIGINT NOT NULL DEFAULT 0,
+    ADD jobs_late BIGINT NOT NULL DEFAULT 0;
+
+UPDATE monitor SET
+    jobs_started = (SELECT COUNT(*) FROM job WHERE job.monitor_id = monitor.monitor_id),
+    jobs_succeeded = (
+        SELECT COUNT(*) FROM job WHERE job.monitor_id = monitor.monitor_id AND succeeded
+    ),
+    jobs_failed = (
+        SELECT COUNT(*) FROM job WHERE job.monitor_id = monitor.monitor_id AND NOT succeeded
+    ),
+    jobs_late = (
+        SELECT COUNT(*) FROM job
+        WHERE job.monitor_id = monitor.monitor_id AND end_time > max_end_time
+    );
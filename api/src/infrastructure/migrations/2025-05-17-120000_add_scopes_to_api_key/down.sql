@@ -0,0 +1,3 @@
+ALTER TABLE api_key
+    DROP scopes,
+    DROP monitor_ids;
@@ -0,0 +1,6 @@
+-- Existing keys keep every scope, and can be used for any Monitor.
+ALTER TABLE api_key
+    ADD scopes TEXT[] NOT NULL DEFAULT '{jobs:write,heartbeats:write}',
+    ADD monitor_ids UUID[] NULL;
+ALTER TABLE api_key
+    ALTER scopes DROP DEFAULT;
@@ -0,0 +1 @@
+ALTER TABLE api_key ADD expires_at TIMESTAMP NULL;
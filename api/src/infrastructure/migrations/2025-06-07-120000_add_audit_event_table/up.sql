@@ -0,0 +1,16 @@
+CREATE TABLE audit_event (
+    audit_event_id uuid PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    tenant VARCHAR NOT NULL,
+    actor_id VARCHAR NOT NULL,
+    actor_name VARCHAR NOT NULL,
+    action VARCHAR NOT NULL,
+    entity_type VARCHAR NOT NULL,
+    -- Deliberately not a foreign key, since events outlive the entities they're about.
+    entity_id uuid NOT NULL,
+    before JSONB NULL,
+    after JSONB NULL
+);
+
+CREATE INDEX idx_audit_event_tenant ON audit_event(tenant, created_at);
@@ -0,0 +1,45 @@
+CREATE TABLE webhook_subscription (
+    subscription_id uuid PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    tenant VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    url VARCHAR NOT NULL,
+    secret VARCHAR NULL,
+    event_types TEXT[] NOT NULL,
+    active BOOLEAN NOT NULL
+);
+
+CREATE TABLE webhook_delivery (
+    delivery_id uuid PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    subscription_id uuid NOT NULL REFERENCES webhook_subscription ON DELETE CASCADE,
+    tenant VARCHAR NOT NULL,
+    event_id uuid NOT NULL,
+    event_type VARCHAR NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR NOT NULL,
+    next_attempt_at TIMESTAMP NULL
+);
+
+CREATE TABLE webhook_delivery_attempt (
+    attempt_id uuid PRIMARY KEY,
+    delivery_id uuid NOT NULL REFERENCES webhook_delivery ON DELETE CASCADE,
+    attempted_at TIMESTAMP NOT NULL,
+    succeeded BOOLEAN NOT NULL,
+    response_status INTEGER NULL,
+    error VARCHAR NULL
+);
+
+CREATE INDEX idx_webhook_subscription_tenant ON webhook_subscription(tenant);
+-- The monitor polls for pending deliveries that are due, so only those need indexing.
+CREATE INDEX idx_webhook_delivery_due ON webhook_delivery(next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX idx_webhook_delivery_subscription ON webhook_delivery(subscription_id, created_at);
+CREATE INDEX idx_webhook_delivery_attempt_delivery ON webhook_delivery_attempt(delivery_id);
+
+SELECT diesel_manage_updated_at('webhook_subscription');
+SELECT diesel_manage_updated_at('webhook_delivery');
@@ -0,0 +1,2 @@
+ALTER TABLE alert_config DROP COLUMN label_selector;
+DROP TABLE monitor_label;
@@ -0,0 +1,10 @@
+CREATE TABLE monitor_label (
+    monitor_id uuid NOT NULL REFERENCES monitor ON DELETE CASCADE,
+    key VARCHAR NOT NULL,
+    value VARCHAR NOT NULL,
+    PRIMARY KEY (monitor_id, key)
+);
+
+CREATE INDEX idx_monitor_label_key_value ON monitor_label(key, value);
+
+ALTER TABLE alert_config ADD COLUMN label_selector VARCHAR NULL;
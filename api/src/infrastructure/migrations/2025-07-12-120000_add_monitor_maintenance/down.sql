@@ -0,0 +1,7 @@
+ALTER TABLE job
+    DROP during_maintenance;
+
+ALTER TABLE monitor
+    DROP paused,
+    DROP resume_at,
+    DROP maintenance_windows;
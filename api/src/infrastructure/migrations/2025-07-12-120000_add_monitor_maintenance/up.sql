@@ -0,0 +1,7 @@
+ALTER TABLE monitor
+    ADD paused boolean NOT NULL DEFAULT false,
+    ADD resume_at timestamp NULL,
+    ADD maintenance_windows jsonb NOT NULL DEFAULT '[]';
+
+ALTER TABLE job
+    ADD during_maintenance boolean NOT NULL DEFAULT false;
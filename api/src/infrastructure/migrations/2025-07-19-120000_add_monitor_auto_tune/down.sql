@@ -0,0 +1,5 @@
+ALTER TABLE monitor
+    DROP auto_tune_min_expected,
+    DROP auto_tune_max_expected,
+    DROP auto_tune_min_grace,
+    DROP auto_tune_max_grace;
@@ -0,0 +1,5 @@
+ALTER TABLE monitor
+    ADD auto_tune_min_expected INTEGER NULL,
+    ADD auto_tune_max_expected INTEGER NULL,
+    ADD auto_tune_min_grace INTEGER NULL,
+    ADD auto_tune_max_grace INTEGER NULL;
@@ -0,0 +1,9 @@
+ALTER TABLE job
+    DROP anomaly_checked;
+
+ALTER TABLE monitor
+    DROP anomaly_min_duration,
+    DROP anomaly_max_deviations;
+
+ALTER TABLE alert_config
+    DROP on_anomaly;
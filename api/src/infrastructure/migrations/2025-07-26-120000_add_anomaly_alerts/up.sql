@@ -0,0 +1,18 @@
+ALTER TABLE alert_config
+    ADD on_anomaly boolean NOT NULL DEFAULT false;
+
+ALTER TABLE monitor
+    ADD anomaly_min_duration INTEGER NULL,
+    ADD anomaly_max_deviations DOUBLE PRECISION NULL;
+
+ALTER TABLE job
+    ADD anomaly_checked boolean NULL;
+
+-- Jobs that finished before now predate anomaly detection, so treat them as though they've already
+-- been checked. Otherwise enabling anomaly detection on a monitor would alert on its history.
+UPDATE job
+SET anomaly_checked = end_time IS NOT NULL;
+
+ALTER TABLE job
+    ALTER COLUMN anomaly_checked SET NOT NULL,
+    ALTER COLUMN anomaly_checked SET DEFAULT false;
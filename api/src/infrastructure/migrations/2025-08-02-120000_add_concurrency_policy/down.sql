@@ -0,0 +1,6 @@
+ALTER TABLE job
+    DROP overlapped,
+    DROP overlap_alert_sent;
+
+ALTER TABLE monitor
+    DROP concurrency_policy;
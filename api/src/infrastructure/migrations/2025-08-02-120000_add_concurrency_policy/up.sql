@@ -0,0 +1,6 @@
+ALTER TABLE monitor
+    ADD concurrency_policy VARCHAR NOT NULL DEFAULT 'allow';
+
+ALTER TABLE job
+    ADD overlapped boolean NOT NULL DEFAULT false,
+    ADD overlap_alert_sent boolean NOT NULL DEFAULT false;
@@ -0,0 +1,6 @@
+ALTER TABLE job
+    DROP exit_code,
+    DROP hostname,
+    DROP command,
+    DROP version,
+    DROP metrics;
@@ -0,0 +1,6 @@
+ALTER TABLE job
+    ADD exit_code integer,
+    ADD hostname VARCHAR,
+    ADD command TEXT,
+    ADD version VARCHAR,
+    ADD metrics JSONB NOT NULL DEFAULT '{}';
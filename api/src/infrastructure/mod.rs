@@ -1,9 +1,12 @@
+pub mod activity;
 pub mod auth;
 pub mod database;
 pub mod db_schema;
 pub mod logging;
+pub mod metrics;
 pub mod middleware;
 pub mod models;
 pub mod notify;
 pub mod paging;
 pub mod repositories;
+pub mod webhooks;
@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::models::{Activity, ActivityEvent};
+
+/// An `ActivityEvent` as it's sent between processes. Unlike when it's sent to clients, this
+/// includes the tenant, so that listening processes know who to send it to.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivityEventData {
+    pub tenant: String,
+    pub monitor_id: Uuid,
+    pub monitor_name: String,
+    pub job_id: Option<Uuid>,
+    pub activity: Activity,
+    pub timestamp: NaiveDateTime,
+}
+
+impl From<ActivityEventData> for ActivityEvent {
+    fn from(value: ActivityEventData) -> Self {
+        ActivityEvent {
+            tenant: value.tenant,
+            monitor_id: value.monitor_id,
+            monitor_name: value.monitor_name,
+            job_id: value.job_id,
+            activity: value.activity,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+impl From<&ActivityEvent> for ActivityEventData {
+    fn from(value: &ActivityEvent) -> Self {
+        ActivityEventData {
+            tenant: value.tenant.clone(),
+            monitor_id: value.monitor_id,
+            monitor_name: value.monitor_name.clone(),
+            job_id: value.job_id,
+            activity: value.activity.clone(),
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utils::gen_uuid;
+
+    use crate::domain::models::AlertKind;
+
+    use super::*;
+
+    #[test]
+    fn test_activity_event_round_trip() {
+        let event = ActivityEvent::new(
+            "tenant",
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            "foo",
+            None,
+            Activity::AlertSent {
+                alert: AlertKind::MissedStart,
+                alert_config_id: gen_uuid("fadd7266-648b-4102-8f85-c768655f4297"),
+                alert_config_name: "Slack".to_owned(),
+            },
+        );
+
+        let payload = serde_json::to_string(&ActivityEventData::from(&event)).unwrap();
+        let data = serde_json::from_str::<ActivityEventData>(&payload).unwrap();
+
+        assert_eq!(ActivityEvent::from(data), event);
+    }
+}
@@ -1,9 +1,16 @@
 use diesel::prelude::*;
+use serde_json::Value;
 use uuid::Uuid;
 
-use crate::domain::models::{AlertConfig, AlertType, AppliedMonitor, SlackAlertConfig};
+use crate::domain::models::{
+    AlertConfig, AlertType, AppliedMonitor, EmailAlertConfig, PagerDutyAlertConfig,
+    SlackAlertConfig, SmtpConfig, WebhookAlertConfig,
+};
 use crate::errors::Error;
-use crate::infrastructure::db_schema::{alert_config, monitor_alert_config, slack_alert_config};
+use crate::infrastructure::db_schema::{
+    alert_config, email_alert_config, monitor_alert_config, pagerduty_alert_config,
+    slack_alert_config, webhook_alert_config,
+};
 
 // Only used for reading data.
 #[derive(Clone, Identifiable, Queryable)]
@@ -18,8 +25,23 @@ pub struct AlertConfigData {
     pub active: bool,
     pub on_late: bool,
     pub on_error: bool,
+    pub on_recovery: bool,
+    pub on_anomaly: bool,
+    pub label_selector: Option<String>,
     pub slack_channel: Option<String>,
     pub slack_bot_oauth_token: Option<String>,
+    pub webhook_url: Option<String>,
+    pub webhook_method: Option<String>,
+    pub webhook_headers: Option<Value>,
+    pub webhook_secret: Option<String>,
+    pub email_recipients: Option<Vec<String>>,
+    pub email_smtp_host: Option<String>,
+    pub email_smtp_port: Option<i32>,
+    pub email_smtp_username: Option<String>,
+    pub email_smtp_password: Option<String>,
+    pub email_smtp_from: Option<String>,
+    pub email_smtp_tls: Option<String>,
+    pub pagerduty_routing_key: Option<String>,
 }
 
 // Used for reading and writing data.
@@ -42,6 +64,8 @@ pub struct MonitorAlertConfigData {
 #[diesel(table_name = alert_config)]
 #[diesel(primary_key(alert_config_id))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
+// Without this, removing the label selector on update would leave the old one in place.
+#[diesel(treat_none_as_null = true)]
 pub struct NewAlertConfigData {
     pub alert_config_id: Uuid,
     pub name: String,
@@ -50,6 +74,9 @@ pub struct NewAlertConfigData {
     pub active: bool,
     pub on_late: bool,
     pub on_error: bool,
+    pub on_recovery: bool,
+    pub on_anomaly: bool,
+    pub label_selector: Option<String>,
 }
 
 // Only used for writing data.
@@ -63,6 +90,57 @@ pub struct NewSlackAlertConfigData {
     pub slack_bot_oauth_token: String,
 }
 
+// Only used for writing data.
+#[derive(Identifiable, Insertable, AsChangeset)]
+#[diesel(table_name = webhook_alert_config)]
+#[diesel(primary_key(alert_config_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+// Without this, unsetting the secret on update would leave the old one in place.
+#[diesel(treat_none_as_null = true)]
+pub struct NewWebhookAlertConfigData {
+    pub alert_config_id: Uuid,
+    pub webhook_url: String,
+    pub webhook_method: String,
+    pub webhook_headers: Value,
+    pub webhook_secret: Option<String>,
+}
+
+// Only used for writing data.
+#[derive(Identifiable, Insertable, AsChangeset)]
+#[diesel(table_name = email_alert_config)]
+#[diesel(primary_key(alert_config_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+// Without this, switching to the server's relay on update would leave the old relay in place.
+#[diesel(treat_none_as_null = true)]
+pub struct NewEmailAlertConfigData {
+    pub alert_config_id: Uuid,
+    pub email_recipients: Vec<String>,
+    pub email_smtp_host: Option<String>,
+    pub email_smtp_port: Option<i32>,
+    pub email_smtp_username: Option<String>,
+    pub email_smtp_password: Option<String>,
+    pub email_smtp_from: Option<String>,
+    pub email_smtp_tls: Option<String>,
+}
+
+// Only used for writing data.
+#[derive(Identifiable, Insertable, AsChangeset)]
+#[diesel(table_name = pagerduty_alert_config)]
+#[diesel(primary_key(alert_config_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewPagerDutyAlertConfigData {
+    pub alert_config_id: Uuid,
+    pub pagerduty_routing_key: String,
+}
+
+/// The integration specific data for an alert configuration, which is stored in its own table.
+pub enum NewAlertTypeData {
+    Slack(NewSlackAlertConfigData),
+    Webhook(NewWebhookAlertConfigData),
+    Email(NewEmailAlertConfigData),
+    PagerDuty(NewPagerDutyAlertConfigData),
+}
+
 impl AlertConfigData {
     pub fn to_model(
         &self,
@@ -75,6 +153,14 @@ impl AlertConfigData {
             active: self.active,
             on_late: self.on_late,
             on_error: self.on_error,
+            on_recovery: self.on_recovery,
+            on_anomaly: self.on_anomaly,
+            label_selector: self
+                .label_selector
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(Error::InvalidAlertConfig)?,
             type_: match self.type_.as_str() {
                 // TODO: This constant should be in the domain layer.
                 "slack" => {
@@ -91,6 +177,47 @@ impl AlertConfigData {
                         ));
                     }
                 }
+                "webhook" => {
+                    if let (Some(url), Some(method), Some(headers)) = (
+                        &self.webhook_url,
+                        &self.webhook_method,
+                        &self.webhook_headers,
+                    ) {
+                        AlertType::Webhook(WebhookAlertConfig {
+                            url: url.clone(),
+                            method: method.parse()?,
+                            headers: serde_json::from_value(headers.clone()).map_err(|_| {
+                                Error::InvalidAlertConfig("Webhook headers are invalid".to_owned())
+                            })?,
+                            secret: self.webhook_secret.clone(),
+                        })
+                    } else {
+                        return Err(Error::InvalidAlertConfig(
+                            "Webhook URL, method and/ or headers are missing".to_owned(),
+                        ));
+                    }
+                }
+                "email" => {
+                    let Some(recipients) = &self.email_recipients else {
+                        return Err(Error::InvalidAlertConfig(
+                            "Email recipients are missing".to_owned(),
+                        ));
+                    };
+                    AlertType::Email(EmailAlertConfig {
+                        recipients: recipients.clone(),
+                        smtp: self.smtp_to_model()?,
+                    })
+                }
+                "pagerduty" => {
+                    let Some(routing_key) = &self.pagerduty_routing_key else {
+                        return Err(Error::InvalidAlertConfig(
+                            "PagerDuty routing key is missing".to_owned(),
+                        ));
+                    };
+                    AlertType::PagerDuty(PagerDutyAlertConfig {
+                        routing_key: routing_key.clone(),
+                    })
+                }
                 _ => return Err(Error::InvalidAlertConfig("Unknown alert type".to_owned())),
             },
             monitors: monitor_alert_configs
@@ -102,25 +229,83 @@ impl AlertConfigData {
                 .collect(),
         })
     }
+
+    fn smtp_to_model(&self) -> Result<Option<SmtpConfig>, Error> {
+        // No host means the server's relay is used, so the other columns are irrelevant.
+        let Some(host) = &self.email_smtp_host else {
+            return Ok(None);
+        };
+
+        if let (Some(port), Some(from), Some(tls)) = (
+            self.email_smtp_port,
+            &self.email_smtp_from,
+            &self.email_smtp_tls,
+        ) {
+            Ok(Some(SmtpConfig {
+                host: host.clone(),
+                port: port.try_into().map_err(|_| {
+                    Error::InvalidAlertConfig(format!("Invalid SMTP port '{port}'"))
+                })?,
+                username: self.email_smtp_username.clone(),
+                password: self.email_smtp_password.clone(),
+                from: from.clone(),
+                tls: tls.parse()?,
+            }))
+        } else {
+            Err(Error::InvalidAlertConfig(
+                "SMTP port, from address and/ or TLS mode are missing".to_owned(),
+            ))
+        }
+    }
 }
 
 impl NewAlertConfigData {
     pub fn from_model(
         alert_config: &AlertConfig,
-    ) -> (
-        Self,
-        Vec<MonitorAlertConfigData>,
-        Option<NewSlackAlertConfigData>,
-    ) {
+    ) -> (Self, Vec<MonitorAlertConfigData>, NewAlertTypeData) {
         let (type_, specific_data) = match &alert_config.type_ {
             AlertType::Slack(slack_config) => (
                 "slack".to_string(),
-                Some(NewSlackAlertConfigData {
+                NewAlertTypeData::Slack(NewSlackAlertConfigData {
                     alert_config_id: alert_config.alert_config_id,
                     slack_channel: slack_config.channel.clone(),
                     slack_bot_oauth_token: slack_config.token.clone(),
                 }),
             ),
+            AlertType::Webhook(webhook_config) => (
+                "webhook".to_string(),
+                NewAlertTypeData::Webhook(NewWebhookAlertConfigData {
+                    alert_config_id: alert_config.alert_config_id,
+                    webhook_url: webhook_config.url.clone(),
+                    webhook_method: webhook_config.method.to_string(),
+                    webhook_headers: serde_json::to_value(&webhook_config.headers)
+                        .expect("String maps are always serialisable"),
+                    webhook_secret: webhook_config.secret.clone(),
+                }),
+            ),
+            AlertType::Email(email_config) => {
+                let smtp = email_config.smtp.as_ref();
+                (
+                    "email".to_string(),
+                    NewAlertTypeData::Email(NewEmailAlertConfigData {
+                        alert_config_id: alert_config.alert_config_id,
+                        email_recipients: email_config.recipients.clone(),
+                        email_smtp_host: smtp.map(|smtp| smtp.host.clone()),
+                        email_smtp_port: smtp.map(|smtp| smtp.port.into()),
+                        email_smtp_username: smtp.and_then(|smtp| smtp.username.clone()),
+                        email_smtp_password: smtp.and_then(|smtp| smtp.password.clone()),
+                        email_smtp_from: smtp.map(|smtp| smtp.from.clone()),
+                        email_smtp_tls: smtp.map(|smtp| smtp.tls.to_string()),
+                    }),
+                )
+            }
+            AlertType::PagerDuty(pagerduty_config) => (
+                "pagerduty".to_string(),
+                NewAlertTypeData::PagerDuty(NewPagerDutyAlertConfigData {
+                    alert_config_id: alert_config.alert_config_id,
+                    pagerduty_routing_key: pagerduty_config.routing_key.clone(),
+                }),
+            ),
         };
 
         (
@@ -132,6 +317,12 @@ impl NewAlertConfigData {
                 active: alert_config.active,
                 on_late: alert_config.on_late,
                 on_error: alert_config.on_error,
+                on_recovery: alert_config.on_recovery,
+                on_anomaly: alert_config.on_anomaly,
+                label_selector: alert_config
+                    .label_selector
+                    .as_ref()
+                    .map(|selector| selector.to_string()),
             },
             alert_config
                 .monitors
@@ -149,11 +340,16 @@ impl NewAlertConfigData {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use pretty_assertions::assert_eq;
     use rstest::rstest;
+    use serde_json::json;
 
     use test_utils::gen_uuid;
 
+    use crate::domain::models::{SmtpTls, WebhookMethod};
+
     use super::*;
 
     #[test]
@@ -178,8 +374,23 @@ mod tests {
             active: true,
             on_late: true,
             on_error: false,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: Some("team=payments".to_owned()),
             slack_channel: Some("test-channel".to_owned()),
             slack_bot_oauth_token: Some("test-token".to_owned()),
+            webhook_url: None,
+            webhook_method: None,
+            webhook_headers: None,
+            webhook_secret: None,
+            email_recipients: None,
+            email_smtp_host: None,
+            email_smtp_port: None,
+            email_smtp_username: None,
+            email_smtp_password: None,
+            email_smtp_from: None,
+            email_smtp_tls: None,
+            pagerduty_routing_key: None,
         };
 
         let alert_config = alert_config_data.to_model(&monitor_alert_configs).unwrap();
@@ -193,6 +404,10 @@ mod tests {
         assert!(alert_config.active);
         assert!(alert_config.on_late);
         assert!(!alert_config.on_error);
+        assert_eq!(
+            alert_config.label_selector,
+            Some("team=payments".parse().unwrap())
+        );
         assert_eq!(
             alert_config.type_,
             AlertType::Slack(SlackAlertConfig {
@@ -215,6 +430,364 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_converting_db_to_webhook_alert_config() {
+        let alert_config_data = AlertConfigData {
+            alert_config_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            name: "test-webhook-alert".to_owned(),
+            tenant: "foo-tenant".to_owned(),
+            type_: "webhook".to_owned(),
+            active: true,
+            on_late: true,
+            on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
+            slack_channel: None,
+            slack_bot_oauth_token: None,
+            webhook_url: Some("https://example.com/hook".to_owned()),
+            webhook_method: Some("PUT".to_owned()),
+            webhook_headers: Some(json!({"X-Team": "infra"})),
+            webhook_secret: Some("shh".to_owned()),
+            email_recipients: None,
+            email_smtp_host: None,
+            email_smtp_port: None,
+            email_smtp_username: None,
+            email_smtp_password: None,
+            email_smtp_from: None,
+            email_smtp_tls: None,
+            pagerduty_routing_key: None,
+        };
+
+        let alert_config = alert_config_data.to_model(&[]).unwrap();
+
+        assert_eq!(
+            alert_config.type_,
+            AlertType::Webhook(WebhookAlertConfig {
+                url: "https://example.com/hook".to_owned(),
+                method: WebhookMethod::Put,
+                headers: BTreeMap::from([("X-Team".to_owned(), "infra".to_owned())]),
+                secret: Some("shh".to_owned()),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::missing_url(
+        None,
+        Some("POST".to_owned()),
+        Some(json!({})),
+        "Webhook URL, method and/ or headers are missing"
+    )]
+    #[case::missing_method(
+        Some("https://example.com/hook".to_owned()),
+        None,
+        Some(json!({})),
+        "Webhook URL, method and/ or headers are missing"
+    )]
+    #[case::unknown_method(
+        Some("https://example.com/hook".to_owned()),
+        Some("DELETE".to_owned()),
+        Some(json!({})),
+        "Unsupported webhook method 'DELETE'"
+    )]
+    #[case::invalid_headers(
+        Some("https://example.com/hook".to_owned()),
+        Some("POST".to_owned()),
+        Some(json!(["X-Team"])),
+        "Webhook headers are invalid"
+    )]
+    fn test_converting_invalid_webhook_db_data_to_model(
+        #[case] url: Option<String>,
+        #[case] method: Option<String>,
+        #[case] headers: Option<Value>,
+        #[case] expected_error: &str,
+    ) {
+        let alert_config_data = AlertConfigData {
+            alert_config_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            name: "test-webhook-alert".to_owned(),
+            tenant: "foo-tenant".to_owned(),
+            type_: "webhook".to_owned(),
+            active: true,
+            on_late: true,
+            on_error: false,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
+            slack_channel: None,
+            slack_bot_oauth_token: None,
+            webhook_url: url,
+            webhook_method: method,
+            webhook_headers: headers,
+            webhook_secret: None,
+            email_recipients: None,
+            email_smtp_host: None,
+            email_smtp_port: None,
+            email_smtp_username: None,
+            email_smtp_password: None,
+            email_smtp_from: None,
+            email_smtp_tls: None,
+            pagerduty_routing_key: None,
+        };
+
+        let result = alert_config_data.to_model(&[]);
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidAlertConfig(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_webhook_model_to_db_data() {
+        let alert_config = AlertConfig::new_webhook_config(
+            "test-webhook-alert".to_owned(),
+            "foo-tenant".to_owned(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            WebhookAlertConfig {
+                url: "https://example.com/hook".to_owned(),
+                method: WebhookMethod::Patch,
+                headers: BTreeMap::from([("X-Team".to_owned(), "infra".to_owned())]),
+                secret: None,
+            },
+        );
+
+        let (alert_config_data, _, webhook_data) = NewAlertConfigData::from_model(&alert_config);
+
+        assert_eq!(&alert_config_data.type_, "webhook");
+        let NewAlertTypeData::Webhook(webhook_data) = webhook_data else {
+            panic!("Expected webhook data");
+        };
+        assert_eq!(webhook_data.alert_config_id, alert_config.alert_config_id);
+        assert_eq!(&webhook_data.webhook_url, "https://example.com/hook");
+        assert_eq!(&webhook_data.webhook_method, "PATCH");
+        assert_eq!(webhook_data.webhook_headers, json!({"X-Team": "infra"}));
+        assert_eq!(webhook_data.webhook_secret, None);
+    }
+
+    fn email_alert_config_data(
+        recipients: Option<Vec<String>>,
+        smtp_host: Option<&str>,
+        smtp_port: Option<i32>,
+        smtp_tls: Option<&str>,
+    ) -> AlertConfigData {
+        AlertConfigData {
+            alert_config_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            name: "test-email-alert".to_owned(),
+            tenant: "foo-tenant".to_owned(),
+            type_: "email".to_owned(),
+            active: true,
+            on_late: true,
+            on_error: true,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
+            slack_channel: None,
+            slack_bot_oauth_token: None,
+            webhook_url: None,
+            webhook_method: None,
+            webhook_headers: None,
+            webhook_secret: None,
+            email_recipients: recipients,
+            email_smtp_host: smtp_host.map(|host| host.to_owned()),
+            email_smtp_port: smtp_port,
+            email_smtp_username: Some("user".to_owned()),
+            email_smtp_password: Some("pass".to_owned()),
+            email_smtp_from: Some("cron-mon@example.com".to_owned()),
+            email_smtp_tls: smtp_tls.map(|tls| tls.to_owned()),
+            pagerduty_routing_key: None,
+        }
+    }
+
+    #[test]
+    fn test_converting_db_to_email_alert_config() {
+        let alert_config_data = email_alert_config_data(
+            Some(vec!["oncall@example.com".to_owned()]),
+            Some("smtp.example.com"),
+            Some(465),
+            Some("tls"),
+        );
+
+        let alert_config = alert_config_data.to_model(&[]).unwrap();
+
+        assert_eq!(
+            alert_config.type_,
+            AlertType::Email(EmailAlertConfig {
+                recipients: vec!["oncall@example.com".to_owned()],
+                smtp: Some(SmtpConfig {
+                    host: "smtp.example.com".to_owned(),
+                    port: 465,
+                    username: Some("user".to_owned()),
+                    password: Some("pass".to_owned()),
+                    from: "cron-mon@example.com".to_owned(),
+                    tls: SmtpTls::Tls,
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_converting_db_to_email_alert_config_using_server_relay() {
+        // The other SMTP columns are ignored when there's no host.
+        let alert_config_data = email_alert_config_data(
+            Some(vec!["oncall@example.com".to_owned()]),
+            None,
+            None,
+            Some("tls"),
+        );
+
+        let alert_config = alert_config_data.to_model(&[]).unwrap();
+
+        assert_eq!(
+            alert_config.type_,
+            AlertType::Email(EmailAlertConfig {
+                recipients: vec!["oncall@example.com".to_owned()],
+                smtp: None,
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::missing_recipients(None, Some(587), Some("starttls"), "Email recipients are missing")]
+    #[case::missing_port(
+        Some(vec!["oncall@example.com".to_owned()]),
+        None,
+        Some("starttls"),
+        "SMTP port, from address and/ or TLS mode are missing"
+    )]
+    #[case::invalid_port(
+        Some(vec!["oncall@example.com".to_owned()]),
+        Some(-1),
+        Some("starttls"),
+        "Invalid SMTP port '-1'"
+    )]
+    #[case::unknown_tls(
+        Some(vec!["oncall@example.com".to_owned()]),
+        Some(587),
+        Some("ssl"),
+        "Unsupported SMTP TLS mode 'ssl'"
+    )]
+    fn test_converting_invalid_email_db_data_to_model(
+        #[case] recipients: Option<Vec<String>>,
+        #[case] port: Option<i32>,
+        #[case] tls: Option<&str>,
+        #[case] expected_error: &str,
+    ) {
+        let alert_config_data =
+            email_alert_config_data(recipients, Some("smtp.example.com"), port, tls);
+
+        let result = alert_config_data.to_model(&[]);
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidAlertConfig(expected_error.to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_converting_db_to_pagerduty_alert_config() {
+        let alert_config_data = AlertConfigData {
+            type_: "pagerduty".to_owned(),
+            email_recipients: None,
+            email_smtp_host: None,
+            email_smtp_port: None,
+            email_smtp_username: None,
+            email_smtp_password: None,
+            email_smtp_from: None,
+            email_smtp_tls: None,
+            pagerduty_routing_key: Some("R0UT1NGK3Y".to_owned()),
+            ..email_alert_config_data(None, None, None, None)
+        };
+
+        assert_eq!(
+            alert_config_data.to_model(&[]).unwrap().type_,
+            AlertType::PagerDuty(PagerDutyAlertConfig {
+                routing_key: "R0UT1NGK3Y".to_owned(),
+            })
+        );
+        assert_eq!(
+            AlertConfigData {
+                pagerduty_routing_key: None,
+                ..alert_config_data
+            }
+            .to_model(&[]),
+            Err(Error::InvalidAlertConfig(
+                "PagerDuty routing key is missing".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_pagerduty_model_to_db_data() {
+        let alert_config = AlertConfig::new_pagerduty_config(
+            "test-pagerduty-alert".to_owned(),
+            "foo-tenant".to_owned(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            PagerDutyAlertConfig {
+                routing_key: "R0UT1NGK3Y".to_owned(),
+            },
+        );
+
+        let (alert_config_data, _, pagerduty_data) = NewAlertConfigData::from_model(&alert_config);
+
+        assert_eq!(&alert_config_data.type_, "pagerduty");
+        let NewAlertTypeData::PagerDuty(pagerduty_data) = pagerduty_data else {
+            panic!("Expected PagerDuty data");
+        };
+        assert_eq!(pagerduty_data.alert_config_id, alert_config.alert_config_id);
+        assert_eq!(&pagerduty_data.pagerduty_routing_key, "R0UT1NGK3Y");
+    }
+
+    #[test]
+    fn test_email_model_to_db_data() {
+        let alert_config = AlertConfig::new_email_config(
+            "test-email-alert".to_owned(),
+            "foo-tenant".to_owned(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            EmailAlertConfig {
+                recipients: vec!["oncall@example.com".to_owned()],
+                smtp: Some(SmtpConfig {
+                    host: "localhost".to_owned(),
+                    port: 1025,
+                    username: None,
+                    password: None,
+                    from: "cron-mon@example.com".to_owned(),
+                    tls: SmtpTls::None,
+                }),
+            },
+        );
+
+        let (alert_config_data, _, email_data) = NewAlertConfigData::from_model(&alert_config);
+
+        assert_eq!(&alert_config_data.type_, "email");
+        let NewAlertTypeData::Email(email_data) = email_data else {
+            panic!("Expected email data");
+        };
+        assert_eq!(email_data.alert_config_id, alert_config.alert_config_id);
+        assert_eq!(email_data.email_recipients, vec!["oncall@example.com"]);
+        assert_eq!(email_data.email_smtp_host, Some("localhost".to_owned()));
+        assert_eq!(email_data.email_smtp_port, Some(1025));
+        assert_eq!(email_data.email_smtp_username, None);
+        assert_eq!(email_data.email_smtp_password, None);
+        assert_eq!(
+            email_data.email_smtp_from,
+            Some("cron-mon@example.com".to_owned())
+        );
+        assert_eq!(email_data.email_smtp_tls, Some("none".to_owned()));
+    }
+
     #[rstest]
     #[case::unknown_type("unknown", None, None, "Unknown alert type")]
     #[case::missing_channel(
@@ -249,8 +822,23 @@ mod tests {
             active: true,
             on_late: true,
             on_error: false,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: None,
             slack_channel: channel,
             slack_bot_oauth_token: token,
+            webhook_url: None,
+            webhook_method: None,
+            webhook_headers: None,
+            webhook_secret: None,
+            email_recipients: None,
+            email_smtp_host: None,
+            email_smtp_port: None,
+            email_smtp_username: None,
+            email_smtp_password: None,
+            email_smtp_from: None,
+            email_smtp_tls: None,
+            pagerduty_routing_key: None,
         };
 
         let result = alert_config_data.to_model(&[]);
@@ -270,6 +858,9 @@ mod tests {
             active: true,
             on_late: true,
             on_error: false,
+            on_recovery: false,
+            on_anomaly: false,
+            label_selector: Some("team=payments".parse().unwrap()),
             type_: AlertType::Slack(SlackAlertConfig {
                 channel: "test-channel".to_owned(),
                 token: "test-token".to_owned(),
@@ -299,6 +890,10 @@ mod tests {
         assert!(alert_config_data.active);
         assert!(alert_config_data.on_late);
         assert!(!alert_config_data.on_error);
+        assert_eq!(
+            alert_config_data.label_selector,
+            Some("team=payments".to_owned())
+        );
 
         assert_eq!(monitor_alert_configs.len(), 2);
         assert_eq!(
@@ -318,8 +913,9 @@ mod tests {
             gen_uuid("70810d10-1d86-4bde-b29d-b1f490528675")
         );
 
-        assert!(slack_data.is_some());
-        let slack_data = slack_data.unwrap();
+        let NewAlertTypeData::Slack(slack_data) = slack_data else {
+            panic!("Expected Slack data");
+        };
         assert_eq!(slack_data.alert_config_id, alert_config.alert_config_id);
         assert_eq!(&slack_data.slack_channel, "test-channel");
         assert_eq!(&slack_data.slack_bot_oauth_token, "test-token");
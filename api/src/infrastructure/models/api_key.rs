@@ -2,7 +2,7 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use uuid::Uuid;
 
-use crate::domain::models::ApiKey;
+use crate::domain::models::{ApiKey, ApiKeyScope};
 use crate::infrastructure::db_schema::api_key;
 
 // Note that we do not have a corresponding domain model for ApiKeyData, like we do for monitors and
@@ -22,6 +22,9 @@ pub struct ApiKeyData {
     pub last_used: Option<NaiveDateTime>,
     pub last_used_monitor_id: Option<Uuid>,
     pub last_used_monitor_name: Option<String>,
+    pub scopes: Vec<String>,
+    pub monitor_ids: Option<Vec<Uuid>>,
+    pub expires_at: Option<NaiveDateTime>,
 }
 
 impl From<&ApiKeyData> for ApiKey {
@@ -36,6 +39,15 @@ impl From<&ApiKeyData> for ApiKey {
             last_used: value.last_used,
             last_used_monitor_id: value.last_used_monitor_id,
             last_used_monitor_name: value.last_used_monitor_name.clone(),
+            // Scopes that we no longer recognise can't grant anything, so they're dropped rather
+            // than failing to read the key.
+            scopes: value
+                .scopes
+                .iter()
+                .filter_map(|scope| scope.parse::<ApiKeyScope>().ok())
+                .collect(),
+            monitor_ids: value.monitor_ids.clone(),
+            expires_at: value.expires_at,
         }
     }
 }
@@ -52,6 +64,13 @@ impl From<&ApiKey> for ApiKeyData {
             last_used: value.last_used,
             last_used_monitor_id: value.last_used_monitor_id,
             last_used_monitor_name: value.last_used_monitor_name.clone(),
+            scopes: value
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str().to_owned())
+                .collect(),
+            monitor_ids: value.monitor_ids.clone(),
+            expires_at: value.expires_at,
         }
     }
 }
@@ -73,6 +92,8 @@ mod tests {
         let last_used = Some(gen_relative_datetime(0));
         let last_used_monitor_id = Some(gen_uuid("8c8e7032-5de9-4dcf-b267-4e66e27b8a78"));
         let last_used_monitor_name = Some("test-monitor".to_owned());
+        let monitor_ids = Some(vec![gen_uuid("8c8e7032-5de9-4dcf-b267-4e66e27b8a78")]);
+        let expires_at = Some(gen_relative_datetime(3600));
 
         let data = ApiKeyData {
             api_key_id,
@@ -84,6 +105,9 @@ mod tests {
             last_used,
             last_used_monitor_id,
             last_used_monitor_name: last_used_monitor_name.clone(),
+            scopes: vec!["jobs:write".to_owned(), "unknown:scope".to_owned()],
+            monitor_ids: monitor_ids.clone(),
+            expires_at,
         };
 
         let model = ApiKey {
@@ -96,6 +120,9 @@ mod tests {
             last_used,
             last_used_monitor_id,
             last_used_monitor_name,
+            scopes: vec![ApiKeyScope::JobsWrite],
+            monitor_ids,
+            expires_at,
         };
 
         assert_eq!(ApiKey::from(&data), model);
@@ -111,6 +138,8 @@ mod tests {
         let last_used = Some(gen_relative_datetime(0));
         let last_used_monitor_id = Some(gen_uuid("8c8e7032-5de9-4dcf-b267-4e66e27b8a78"));
         let last_used_monitor_name = Some("test-monitor".to_owned());
+        let monitor_ids = Some(vec![gen_uuid("8c8e7032-5de9-4dcf-b267-4e66e27b8a78")]);
+        let expires_at = Some(gen_relative_datetime(3600));
 
         let model = ApiKey {
             api_key_id,
@@ -122,20 +151,27 @@ mod tests {
             last_used,
             last_used_monitor_id,
             last_used_monitor_name: last_used_monitor_name.clone(),
+            scopes: vec![ApiKeyScope::JobsWrite, ApiKeyScope::HeartbeatsWrite],
+            monitor_ids: monitor_ids.clone(),
+            expires_at,
         };
 
-        let data = ApiKeyData {
-            api_key_id,
-            created_at: model.created,
-            tenant,
-            name,
-            key,
-            masked,
-            last_used,
-            last_used_monitor_id,
-            last_used_monitor_name,
-        };
+        let data = ApiKeyData::from(&model);
 
-        assert_eq!(ApiKey::from(&data), model);
+        assert_eq!(data.api_key_id, api_key_id);
+        assert_eq!(data.created_at, model.created);
+        assert_eq!(data.tenant, tenant);
+        assert_eq!(data.name, name);
+        assert_eq!(data.key, key);
+        assert_eq!(data.masked, masked);
+        assert_eq!(data.last_used, last_used);
+        assert_eq!(data.last_used_monitor_id, last_used_monitor_id);
+        assert_eq!(data.last_used_monitor_name, last_used_monitor_name);
+        assert_eq!(
+            data.scopes,
+            vec!["jobs:write".to_owned(), "heartbeats:write".to_owned()]
+        );
+        assert_eq!(data.monitor_ids, monitor_ids);
+        assert_eq!(data.expires_at, expires_at);
     }
 }
@@ -0,0 +1,144 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::models::{Actor, AuditAction, AuditEntity, AuditEvent, EntityType};
+use crate::errors::Error;
+use crate::infrastructure::db_schema::audit_event;
+
+#[derive(Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = audit_event)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuditEventData {
+    pub audit_event_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub tenant: String,
+    pub actor_id: String,
+    pub actor_name: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl TryFrom<&AuditEventData> for AuditEvent {
+    type Error = Error;
+
+    fn try_from(value: &AuditEventData) -> Result<Self, Self::Error> {
+        Ok(AuditEvent {
+            audit_event_id: value.audit_event_id,
+            tenant: value.tenant.clone(),
+            actor: Actor {
+                id: value.actor_id.clone(),
+                name: value.actor_name.clone(),
+            },
+            action: AuditAction::from_name(&value.action).ok_or_else(|| {
+                Error::RepositoryError(format!("Unknown audit action '{}'", value.action))
+            })?,
+            entity: AuditEntity {
+                type_: EntityType::from_name(&value.entity_type).ok_or_else(|| {
+                    Error::RepositoryError(format!(
+                        "Unknown audited entity type '{}'",
+                        value.entity_type
+                    ))
+                })?,
+                id: value.entity_id,
+            },
+            before: value.before.clone(),
+            after: value.after.clone(),
+            timestamp: value.created_at,
+        })
+    }
+}
+
+impl From<&AuditEvent> for AuditEventData {
+    fn from(value: &AuditEvent) -> Self {
+        AuditEventData {
+            audit_event_id: value.audit_event_id,
+            created_at: value.timestamp,
+            tenant: value.tenant.clone(),
+            actor_id: value.actor.id.clone(),
+            actor_name: value.actor.name.clone(),
+            action: value.action.as_str().to_owned(),
+            entity_type: value.entity.type_.as_str().to_owned(),
+            entity_id: value.entity.id,
+            before: value.before.clone(),
+            after: value.after.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use super::*;
+
+    fn audit_event_data(action: &str, entity_type: &str) -> AuditEventData {
+        AuditEventData {
+            audit_event_id: gen_uuid("5c4b7c5e-1b8b-4f4d-9a33-2a3e0f5bd4c7"),
+            created_at: gen_datetime("2025-06-07T12:00:00"),
+            tenant: "tenant".to_owned(),
+            actor_id: "1234567890".to_owned(),
+            actor_name: "John Doe".to_owned(),
+            action: action.to_owned(),
+            entity_type: entity_type.to_owned(),
+            entity_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            before: Some(json!({"grace_duration": 100})),
+            after: Some(json!({"grace_duration": 200})),
+        }
+    }
+
+    #[test]
+    fn test_audit_event_data_to_audit_event() {
+        let audit_event = AuditEvent::try_from(&audit_event_data("updated", "monitor")).unwrap();
+
+        assert_eq!(
+            audit_event,
+            AuditEvent {
+                audit_event_id: gen_uuid("5c4b7c5e-1b8b-4f4d-9a33-2a3e0f5bd4c7"),
+                tenant: "tenant".to_owned(),
+                actor: Actor {
+                    id: "1234567890".to_owned(),
+                    name: "John Doe".to_owned(),
+                },
+                action: AuditAction::Updated,
+                entity: AuditEntity {
+                    type_: EntityType::Monitor,
+                    id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                },
+                before: Some(json!({"grace_duration": 100})),
+                after: Some(json!({"grace_duration": 200})),
+                timestamp: gen_datetime("2025-06-07T12:00:00"),
+            }
+        );
+
+        // And back again.
+        let audit_event_data = AuditEventData::from(&audit_event);
+        assert_eq!(audit_event_data.action, "updated");
+        assert_eq!(audit_event_data.entity_type, "monitor");
+        assert_eq!(
+            audit_event_data.created_at,
+            gen_datetime("2025-06-07T12:00:00")
+        );
+    }
+
+    #[rstest]
+    #[case("rotated", "monitor", "Unknown audit action 'rotated'")]
+    #[case("created", "job", "Unknown audited entity type 'job'")]
+    fn test_audit_event_data_with_unknown_names(
+        #[case] action: &str,
+        #[case] entity_type: &str,
+        #[case] error: &str,
+    ) {
+        assert_eq!(
+            AuditEvent::try_from(&audit_event_data(action, entity_type)),
+            Err(Error::RepositoryError(error.to_owned()))
+        );
+    }
+}
@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use serde_json::Value;
 use uuid::Uuid;
 
-use crate::domain::models::{EndState, Job};
+use crate::domain::models::{EndState, Job, JobMetadata};
 use crate::errors::Error;
 use crate::infrastructure::db_schema::job;
 use crate::infrastructure::models::monitor::MonitorData;
@@ -22,6 +25,19 @@ pub struct JobData {
     pub monitor_id: Uuid,
     pub late_alert_sent: bool,
     pub error_alert_sent: bool,
+    pub late_alert_resolved: bool,
+    pub error_alert_resolved: bool,
+    pub recovery_alert_sent: bool,
+    pub below_threshold: bool,
+    pub during_maintenance: bool,
+    pub anomaly_checked: bool,
+    pub overlapped: bool,
+    pub overlap_alert_sent: bool,
+    pub exit_code: Option<i32>,
+    pub hostname: Option<String>,
+    pub command: Option<String>,
+    pub version: Option<String>,
+    pub metrics: Value,
 }
 
 impl From<&JobData> for Result<Job, Error> {
@@ -32,6 +48,14 @@ impl From<&JobData> for Result<Job, Error> {
                 end_time,
                 succeeded,
                 output: val.output.clone(),
+                metadata: JobMetadata {
+                    exit_code: val.exit_code,
+                    hostname: val.hostname.clone(),
+                    command: val.command.clone(),
+                    version: val.version.clone(),
+                    metrics: serde_json::from_value::<BTreeMap<String, f64>>(val.metrics.clone())
+                        .map_err(|_| Error::InvalidJob("Metrics are invalid".to_owned()))?,
+                },
             }),
             (None, None) => None,
             _ => {
@@ -46,6 +70,14 @@ impl From<&JobData> for Result<Job, Error> {
             end_state,
             late_alert_sent: val.late_alert_sent,
             error_alert_sent: val.error_alert_sent,
+            late_alert_resolved: val.late_alert_resolved,
+            error_alert_resolved: val.error_alert_resolved,
+            recovery_alert_sent: val.recovery_alert_sent,
+            during_maintenance: val.during_maintenance,
+            below_threshold: val.below_threshold,
+            anomaly_checked: val.anomaly_checked,
+            overlapped: val.overlapped,
+            overlap_alert_sent: val.overlap_alert_sent,
         })
     }
 }
@@ -53,6 +85,7 @@ impl From<&JobData> for Result<Job, Error> {
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
+    use serde_json::json;
 
     use test_utils::{gen_datetime, gen_uuid};
 
@@ -70,6 +103,19 @@ mod tests {
             monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
             late_alert_sent: true,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: Some(0),
+            hostname: Some("worker-1".to_owned()),
+            command: Some("backup.sh".to_owned()),
+            version: None,
+            metrics: json!({"rows_processed": 1500}),
         };
 
         let job_result: Result<Job, Error> = (&job_data).into();
@@ -84,6 +130,13 @@ mod tests {
                 end_time: gen_datetime("2024-04-22T22:50:00"),
                 succeeded: true,
                 output: Some(String::from("Job completed successfully")),
+                metadata: JobMetadata {
+                    exit_code: Some(0),
+                    hostname: Some("worker-1".to_owned()),
+                    command: Some("backup.sh".to_owned()),
+                    version: None,
+                    metrics: BTreeMap::from([("rows_processed".to_owned(), 1500.0)]),
+                },
             })
         );
         assert!(job.late_alert_sent);
@@ -102,6 +155,19 @@ mod tests {
             monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
             late_alert_sent: true,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({}),
         };
 
         let job_result: Result<Job, Error> = (&job_data).into();
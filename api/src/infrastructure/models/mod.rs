@@ -1,4 +1,8 @@
+pub mod activity_event;
 pub mod alert_config;
 pub mod api_key;
+pub mod audit_event;
 pub mod job;
 pub mod monitor;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
@@ -1,31 +1,159 @@
+use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::*;
+use serde_json::Value;
 use uuid::Uuid;
 
-use crate::domain::models::{Job, Monitor};
+use crate::domain::models::{
+    AlertThreshold, AnomalyDetection, AutoTune, FailureWindow, Job, JobMetadata, JobRetention,
+    Labels, MaintenanceWindow, Monitor, MonitorKind, Schedule,
+};
 use crate::errors::Error;
-use crate::infrastructure::db_schema::monitor;
+use crate::infrastructure::db_schema::{monitor, monitor_label};
 use crate::infrastructure::models::job::JobData;
 
 #[derive(Queryable, Identifiable, Selectable, Insertable, AsChangeset)]
 #[diesel(table_name = monitor)]
 #[diesel(primary_key(monitor_id))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
+// Without this, resuming a Monitor that was paused until a given time would leave that time in
+// place.
+#[diesel(treat_none_as_null = true)]
 pub struct MonitorData {
     pub monitor_id: Uuid,
     pub tenant: String,
     pub name: String,
     pub expected_duration: i32,
     pub grace_duration: i32,
+    pub schedule: Option<String>,
+    pub schedule_timezone: Option<String>,
+    pub missed_start_cutoff: Option<NaiveDateTime>,
+    pub kind: String,
+    pub heartbeat_period: Option<i32>,
+    pub alert_threshold_consecutive: i32,
+    pub alert_threshold_window_failures: Option<i32>,
+    pub alert_threshold_window_runs: Option<i32>,
+    pub retention_max_age_days: Option<i32>,
+    pub retention_max_jobs: Option<i32>,
+    pub paused: bool,
+    pub resume_at: Option<NaiveDateTime>,
+    pub maintenance_windows: Value,
+    pub auto_tune_min_expected: Option<i32>,
+    pub auto_tune_max_expected: Option<i32>,
+    pub auto_tune_min_grace: Option<i32>,
+    pub auto_tune_max_grace: Option<i32>,
+    pub anomaly_min_duration: Option<i32>,
+    pub anomaly_max_deviations: Option<f64>,
+    pub concurrency_policy: String,
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Queryable, Identifiable, Selectable, Insertable, Associations,
+)]
+#[diesel(belongs_to(MonitorData, foreign_key = monitor_id))]
+#[diesel(table_name = monitor_label)]
+#[diesel(primary_key(monitor_id, key))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MonitorLabelData {
+    pub monitor_id: Uuid,
+    pub key: String,
+    pub value: String,
 }
 
 impl MonitorData {
-    pub fn to_model(&self, job_datas: &[JobData]) -> Result<Monitor, Error> {
+    pub fn to_model(
+        &self,
+        job_datas: &[JobData],
+        label_datas: &[MonitorLabelData],
+    ) -> Result<Monitor, Error> {
+        // Monitors that were paused until a time that has since passed have been resumed.
+        let paused = self.paused
+            && self
+                .resume_at
+                .is_none_or(|resume_at| Utc::now().naive_utc() < resume_at);
+
         Ok(Monitor {
             monitor_id: self.monitor_id,
             tenant: self.tenant.clone(),
             name: self.name.clone(),
             expected_duration: self.expected_duration,
             grace_duration: self.grace_duration,
+            kind: match (self.kind.as_str(), self.heartbeat_period) {
+                ("job", _) => MonitorKind::Job,
+                ("heartbeat", Some(period)) => MonitorKind::Heartbeat { period },
+                ("heartbeat", None) => {
+                    return Err(Error::InvalidMonitor(
+                        "Heartbeat period is missing".to_owned(),
+                    ))
+                }
+                (kind, _) => {
+                    return Err(Error::InvalidMonitor(format!(
+                        "Unknown monitor kind '{kind}'"
+                    )))
+                }
+            },
+            schedule: self
+                .schedule
+                .as_ref()
+                .map(|expression| Schedule::new(expression, self.schedule_timezone.as_deref()))
+                .transpose()?,
+            missed_start_cutoff: self.missed_start_cutoff,
+            alert_threshold: AlertThreshold::new(
+                self.alert_threshold_consecutive,
+                match (
+                    self.alert_threshold_window_failures,
+                    self.alert_threshold_window_runs,
+                ) {
+                    (Some(failures), Some(runs)) => Some(FailureWindow { failures, runs }),
+                    (None, None) => None,
+                    _ => {
+                        return Err(Error::InvalidMonitor(
+                            "Alert threshold window is incomplete".to_owned(),
+                        ))
+                    }
+                },
+            )?,
+            retention: match (self.retention_max_age_days, self.retention_max_jobs) {
+                (None, None) => None,
+                (max_age_days, max_jobs) => Some(JobRetention::new(max_age_days, max_jobs)?),
+            },
+            labels: Labels::new(
+                label_datas
+                    .iter()
+                    .map(|label| (label.key.clone(), label.value.clone()))
+                    .collect(),
+            )?,
+            paused,
+            resume_at: self.resume_at.filter(|_| paused),
+            maintenance_windows: serde_json::from_value::<Vec<MaintenanceWindow>>(
+                self.maintenance_windows.clone(),
+            )
+            .map_err(|_| Error::InvalidMonitor("Maintenance windows are invalid".to_owned()))?,
+            auto_tune: match (
+                self.auto_tune_min_expected,
+                self.auto_tune_max_expected,
+                self.auto_tune_min_grace,
+                self.auto_tune_max_grace,
+            ) {
+                (Some(min_expected), Some(max_expected), Some(min_grace), Some(max_grace)) => Some(
+                    AutoTune::new(min_expected, max_expected, min_grace, max_grace)?,
+                ),
+                (None, None, None, None) => None,
+                _ => {
+                    return Err(Error::InvalidMonitor(
+                        "Auto-tune bounds are incomplete".to_owned(),
+                    ))
+                }
+            },
+            anomaly_detection: match (self.anomaly_min_duration, self.anomaly_max_deviations) {
+                (None, None) => None,
+                (min_duration, max_deviations) => {
+                    Some(AnomalyDetection::new(min_duration, max_deviations)?)
+                }
+            },
+            concurrency_policy: self
+                .concurrency_policy
+                .parse()
+                .map_err(Error::InvalidMonitor)?,
             jobs: job_datas
                 .iter()
                 .map(|jd| jd.into())
@@ -34,7 +162,7 @@ impl MonitorData {
     }
 }
 
-impl From<&Monitor> for (MonitorData, Vec<JobData>) {
+impl From<&Monitor> for (MonitorData, Vec<JobData>, Vec<MonitorLabelData>) {
     fn from(value: &Monitor) -> Self {
         (
             MonitorData {
@@ -43,18 +171,80 @@ impl From<&Monitor> for (MonitorData, Vec<JobData>) {
                 name: value.name.clone(),
                 expected_duration: value.expected_duration,
                 grace_duration: value.grace_duration,
+                schedule: value
+                    .schedule
+                    .as_ref()
+                    .map(|schedule| schedule.expression.clone()),
+                schedule_timezone: value
+                    .schedule
+                    .as_ref()
+                    .map(|schedule| schedule.timezone.clone()),
+                missed_start_cutoff: value.missed_start_cutoff,
+                kind: match value.kind {
+                    MonitorKind::Job => "job",
+                    MonitorKind::Heartbeat { .. } => "heartbeat",
+                }
+                .to_owned(),
+                heartbeat_period: match value.kind {
+                    MonitorKind::Job => None,
+                    MonitorKind::Heartbeat { period } => Some(period),
+                },
+                alert_threshold_consecutive: value.alert_threshold.consecutive,
+                alert_threshold_window_failures: value
+                    .alert_threshold
+                    .window
+                    .map(|window| window.failures),
+                alert_threshold_window_runs: value.alert_threshold.window.map(|window| window.runs),
+                retention_max_age_days: value
+                    .retention
+                    .as_ref()
+                    .and_then(|retention| retention.max_age_days),
+                retention_max_jobs: value
+                    .retention
+                    .as_ref()
+                    .and_then(|retention| retention.max_jobs),
+                paused: value.paused,
+                resume_at: value.resume_at,
+                maintenance_windows: serde_json::to_value(&value.maintenance_windows)
+                    .expect("Maintenance windows are always serialisable"),
+                auto_tune_min_expected: value
+                    .auto_tune
+                    .as_ref()
+                    .map(|auto_tune| auto_tune.min_expected_duration),
+                auto_tune_max_expected: value
+                    .auto_tune
+                    .as_ref()
+                    .map(|auto_tune| auto_tune.max_expected_duration),
+                auto_tune_min_grace: value
+                    .auto_tune
+                    .as_ref()
+                    .map(|auto_tune| auto_tune.min_grace_duration),
+                auto_tune_max_grace: value
+                    .auto_tune
+                    .as_ref()
+                    .map(|auto_tune| auto_tune.max_grace_duration),
+                anomaly_min_duration: value
+                    .anomaly_detection
+                    .as_ref()
+                    .and_then(|detection| detection.min_duration),
+                anomaly_max_deviations: value
+                    .anomaly_detection
+                    .as_ref()
+                    .and_then(|detection| detection.max_deviations),
+                concurrency_policy: value.concurrency_policy.to_string(),
             },
             value
                 .jobs
                 .iter()
                 .map(|job| {
-                    let (end_time, succeeded, output) = match &job.end_state {
+                    let (end_time, succeeded, output, metadata) = match &job.end_state {
                         Some(end_state) => (
                             Some(end_state.end_time),
                             Some(end_state.succeeded),
                             end_state.output.clone(),
+                            end_state.metadata.clone(),
                         ),
-                        None => (None, None, None),
+                        None => (None, None, None, JobMetadata::default()),
                     };
                     JobData {
                         job_id: job.job_id,
@@ -66,19 +256,48 @@ impl From<&Monitor> for (MonitorData, Vec<JobData>) {
                         output,
                         late_alert_sent: job.late_alert_sent,
                         error_alert_sent: job.error_alert_sent,
+                        late_alert_resolved: job.late_alert_resolved,
+                        error_alert_resolved: job.error_alert_resolved,
+                        recovery_alert_sent: job.recovery_alert_sent,
+                        during_maintenance: job.during_maintenance,
+                        below_threshold: job.below_threshold,
+                        anomaly_checked: job.anomaly_checked,
+                        overlapped: job.overlapped,
+                        overlap_alert_sent: job.overlap_alert_sent,
+                        exit_code: metadata.exit_code,
+                        hostname: metadata.hostname,
+                        command: metadata.command,
+                        version: metadata.version,
+                        metrics: serde_json::to_value(&metadata.metrics)
+                            .expect("Metrics are always serialisable"),
                     }
                 })
                 .collect(),
+            value
+                .labels
+                .iter()
+                .map(|(key, label_value)| MonitorLabelData {
+                    monitor_id: value.monitor_id,
+                    key: key.clone(),
+                    value: label_value.clone(),
+                })
+                .collect(),
         )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use serde_json::json;
 
     use test_utils::{gen_datetime, gen_uuid};
 
+    use crate::domain::models::ConcurrencyPolicy;
+
     use super::*;
 
     #[test]
@@ -89,6 +308,31 @@ mod tests {
             name: "foo".to_owned(),
             expected_duration: 300,
             grace_duration: 100,
+            kind: MonitorKind::Heartbeat { period: 86_400 },
+            schedule: Some(Schedule::new("0 3 * * *", Some("Europe/London")).unwrap()),
+            missed_start_cutoff: Some(gen_datetime("2024-04-22T02:00:00")),
+            alert_threshold: AlertThreshold::new(
+                2,
+                Some(FailureWindow {
+                    failures: 3,
+                    runs: 5,
+                }),
+            )
+            .unwrap(),
+            retention: Some(JobRetention::new(Some(30), Some(1000)).unwrap()),
+            labels: Labels::new(BTreeMap::from([("team".to_owned(), "payments".to_owned())]))
+                .unwrap(),
+            paused: true,
+            resume_at: Some(gen_datetime("2024-04-23T06:00:00")),
+            maintenance_windows: vec![MaintenanceWindow::new(
+                "0 2 * * 0",
+                Some("Europe/London"),
+                60,
+            )
+            .unwrap()],
+            auto_tune: Some(AutoTune::new(60, 600, 0, 120).unwrap()),
+            anomaly_detection: Some(AnomalyDetection::new(Some(60), Some(3.0)).unwrap()),
+            concurrency_policy: ConcurrencyPolicy::Reject,
             jobs: vec![Job {
                 job_id: gen_uuid("01a92c6c-6803-409d-b675-022fff62575a"),
                 start_time: gen_datetime("2024-04-22T22:43:00"),
@@ -96,16 +340,57 @@ mod tests {
                 end_state: None,
                 late_alert_sent: false,
                 error_alert_sent: false,
+                late_alert_resolved: false,
+                error_alert_resolved: false,
+                recovery_alert_sent: false,
+                during_maintenance: false,
+                below_threshold: false,
+                anomaly_checked: false,
+                overlapped: false,
+                overlap_alert_sent: false,
             }],
         };
 
-        let (monitor_data, job_data) = <(MonitorData, Vec<JobData>)>::from(&monitor);
+        let (monitor_data, job_data, label_data) =
+            <(MonitorData, Vec<JobData>, Vec<MonitorLabelData>)>::from(&monitor);
 
         assert_eq!(monitor_data.monitor_id, monitor.monitor_id);
         assert_eq!(monitor_data.tenant, monitor.tenant);
         assert_eq!(monitor_data.name, monitor.name);
         assert_eq!(monitor_data.expected_duration, monitor.expected_duration);
         assert_eq!(monitor_data.grace_duration, monitor.grace_duration);
+        assert_eq!(monitor_data.schedule, Some("0 3 * * *".to_owned()));
+        assert_eq!(
+            monitor_data.schedule_timezone,
+            Some("Europe/London".to_owned())
+        );
+        assert_eq!(
+            monitor_data.missed_start_cutoff,
+            Some(gen_datetime("2024-04-22T02:00:00"))
+        );
+        assert_eq!(monitor_data.kind, "heartbeat".to_owned());
+        assert_eq!(monitor_data.heartbeat_period, Some(86_400));
+        assert_eq!(monitor_data.alert_threshold_consecutive, 2);
+        assert_eq!(monitor_data.alert_threshold_window_failures, Some(3));
+        assert_eq!(monitor_data.alert_threshold_window_runs, Some(5));
+        assert_eq!(monitor_data.retention_max_age_days, Some(30));
+        assert_eq!(monitor_data.retention_max_jobs, Some(1000));
+        assert!(monitor_data.paused);
+        assert_eq!(
+            monitor_data.resume_at,
+            Some(gen_datetime("2024-04-23T06:00:00"))
+        );
+        assert_eq!(
+            monitor_data.maintenance_windows,
+            json!([{"expression": "0 2 * * 0", "timezone": "Europe/London", "duration_minutes": 60}])
+        );
+        assert_eq!(monitor_data.auto_tune_min_expected, Some(60));
+        assert_eq!(monitor_data.auto_tune_max_expected, Some(600));
+        assert_eq!(monitor_data.auto_tune_min_grace, Some(0));
+        assert_eq!(monitor_data.auto_tune_max_grace, Some(120));
+        assert_eq!(monitor_data.anomaly_min_duration, Some(60));
+        assert_eq!(monitor_data.anomaly_max_deviations, Some(3.0));
+        assert_eq!(monitor_data.concurrency_policy, "reject".to_owned());
 
         assert_eq!(job_data.len(), 1);
         let job_data = &job_data[0];
@@ -122,6 +407,17 @@ mod tests {
         assert_eq!(job_data.end_time, None);
         assert_eq!(job_data.succeeded, None);
         assert_eq!(job_data.output, None);
+        assert_eq!(job_data.exit_code, None);
+        assert_eq!(job_data.metrics, json!({}));
+
+        assert_eq!(
+            label_data,
+            vec![MonitorLabelData {
+                monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                key: "team".to_owned(),
+                value: "payments".to_owned(),
+            }]
+        );
     }
 
     #[test]
@@ -132,6 +428,27 @@ mod tests {
             name: "foo".to_owned(),
             expected_duration: 300,
             grace_duration: 100,
+            schedule: Some("0 3 * * *".to_owned()),
+            schedule_timezone: Some("Europe/London".to_owned()),
+            missed_start_cutoff: Some(gen_datetime("2024-04-22T02:00:00")),
+            kind: "heartbeat".to_owned(),
+            heartbeat_period: Some(86_400),
+            alert_threshold_consecutive: 2,
+            alert_threshold_window_failures: Some(3),
+            alert_threshold_window_runs: Some(5),
+            retention_max_age_days: None,
+            retention_max_jobs: Some(1000),
+            // Paused until a time that has since passed, so no longer paused.
+            paused: true,
+            resume_at: Some(gen_datetime("2024-04-23T06:00:00")),
+            maintenance_windows: json!([{"expression": "0 2 * * 0", "duration_minutes": 60}]),
+            auto_tune_min_expected: Some(60),
+            auto_tune_max_expected: Some(600),
+            auto_tune_min_grace: Some(0),
+            auto_tune_max_grace: Some(120),
+            anomaly_min_duration: Some(600),
+            anomaly_max_deviations: None,
+            concurrency_policy: "alert".to_owned(),
         };
 
         let job_data = vec![JobData {
@@ -144,9 +461,28 @@ mod tests {
             output: None,
             late_alert_sent: true,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({}),
+        }];
+
+        let label_data = vec![MonitorLabelData {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            key: "env".to_owned(),
+            value: "prod".to_owned(),
         }];
 
-        let monitor = monitor_data.to_model(&job_data).unwrap();
+        let monitor = monitor_data.to_model(&job_data, &label_data).unwrap();
 
         assert_eq!(
             monitor.monitor_id,
@@ -156,6 +492,46 @@ mod tests {
         assert_eq!(monitor.name, "foo".to_owned());
         assert_eq!(monitor.expected_duration, 300);
         assert_eq!(monitor.grace_duration, 100);
+        assert_eq!(
+            monitor.schedule,
+            Some(Schedule::new("0 3 * * *", Some("Europe/London")).unwrap())
+        );
+        assert_eq!(
+            monitor.missed_start_cutoff,
+            Some(gen_datetime("2024-04-22T02:00:00"))
+        );
+        assert_eq!(monitor.kind, MonitorKind::Heartbeat { period: 86_400 });
+        assert_eq!(
+            monitor.alert_threshold,
+            AlertThreshold::new(
+                2,
+                Some(FailureWindow {
+                    failures: 3,
+                    runs: 5
+                })
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            monitor.retention,
+            Some(JobRetention::new(None, Some(1000)).unwrap())
+        );
+        assert_eq!(monitor.labels.get("env"), Some("prod"));
+        assert!(!monitor.paused);
+        assert_eq!(monitor.resume_at, None);
+        assert_eq!(
+            monitor.maintenance_windows,
+            vec![MaintenanceWindow::new("0 2 * * 0", None, 60).unwrap()]
+        );
+        assert_eq!(
+            monitor.auto_tune,
+            Some(AutoTune::new(60, 600, 0, 120).unwrap())
+        );
+        assert_eq!(
+            monitor.anomaly_detection,
+            Some(AnomalyDetection::new(Some(600), None).unwrap())
+        );
+        assert_eq!(monitor.concurrency_policy, ConcurrencyPolicy::Alert);
 
         assert_eq!(monitor.jobs.len(), 1);
         let job = &monitor.jobs[0];
@@ -166,4 +542,77 @@ mod tests {
         assert!(job.late_alert_sent);
         assert!(!job.error_alert_sent);
     }
+
+    #[rstest]
+    #[case(
+        Some("Not/A_Timezone"),
+        "job",
+        None,
+        (1, None),
+        "Invalid timezone 'Not/A_Timezone'"
+    )]
+    #[case(None, "heartbeat", None, (1, None), "Heartbeat period is missing")]
+    #[case(
+        None,
+        "carrier-pigeon",
+        Some(60),
+        (1, None),
+        "Unknown monitor kind 'carrier-pigeon'"
+    )]
+    #[case(
+        None,
+        "job",
+        None,
+        (0, None),
+        "Invalid consecutive failure threshold '0', must be at least 1"
+    )]
+    #[case(
+        None,
+        "job",
+        None,
+        (1, Some(3)),
+        "Alert threshold window is incomplete"
+    )]
+    fn test_converting_invalid_db_to_monitor(
+        #[case] schedule_timezone: Option<&str>,
+        #[case] kind: &str,
+        #[case] heartbeat_period: Option<i32>,
+        #[case] alert_threshold: (i32, Option<i32>),
+        #[case] expected_error: &str,
+    ) {
+        let monitor_data = MonitorData {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "foo-tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            schedule: Some("0 3 * * *".to_owned()),
+            schedule_timezone: schedule_timezone.map(|tz| tz.to_owned()),
+            missed_start_cutoff: None,
+            kind: kind.to_owned(),
+            heartbeat_period,
+            alert_threshold_consecutive: alert_threshold.0,
+            alert_threshold_window_failures: alert_threshold.1,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: None,
+            retention_max_jobs: None,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: None,
+            auto_tune_max_expected: None,
+            auto_tune_min_grace: None,
+            auto_tune_max_grace: None,
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
+        };
+
+        let result = monitor_data.to_model(&[], &[]);
+
+        assert_eq!(
+            result,
+            Err(Error::InvalidMonitor(expected_error.to_owned()))
+        );
+    }
 }
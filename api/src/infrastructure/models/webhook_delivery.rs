@@ -0,0 +1,177 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::models::{DeliveryAttempt, DeliveryStatus, EventType, WebhookDelivery};
+use crate::errors::Error;
+use crate::infrastructure::db_schema::{webhook_delivery, webhook_delivery_attempt};
+
+#[derive(Clone, Queryable, Identifiable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = webhook_delivery)]
+#[diesel(primary_key(delivery_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDeliveryData {
+    pub delivery_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub subscription_id: Uuid,
+    pub tenant: String,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub status: String,
+    pub next_attempt_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Queryable, Identifiable, Selectable, Insertable, Associations)]
+#[diesel(belongs_to(WebhookDeliveryData, foreign_key = delivery_id))]
+#[diesel(table_name = webhook_delivery_attempt)]
+#[diesel(primary_key(attempt_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeliveryAttemptData {
+    pub attempt_id: Uuid,
+    pub delivery_id: Uuid,
+    pub attempted_at: NaiveDateTime,
+    pub succeeded: bool,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+}
+
+impl WebhookDeliveryData {
+    /// Convert to a `WebhookDelivery`, given the delivery's attempts (in any order).
+    pub fn to_model(&self, attempts: &[DeliveryAttemptData]) -> Result<WebhookDelivery, Error> {
+        let mut attempts = attempts
+            .iter()
+            .map(|attempt| DeliveryAttempt {
+                attempt_id: attempt.attempt_id,
+                attempted_at: attempt.attempted_at,
+                succeeded: attempt.succeeded,
+                response_status: attempt
+                    .response_status
+                    .and_then(|status| u16::try_from(status).ok()),
+                error: attempt.error.clone(),
+            })
+            .collect::<Vec<DeliveryAttempt>>();
+        attempts.sort_by_key(|attempt| attempt.attempted_at);
+
+        Ok(WebhookDelivery {
+            delivery_id: self.delivery_id,
+            subscription_id: self.subscription_id,
+            tenant: self.tenant.clone(),
+            event_id: self.event_id,
+            event_type: self
+                .event_type
+                .parse::<EventType>()
+                .map_err(Error::RepositoryError)?,
+            payload: self.payload.clone(),
+            status: DeliveryStatus::from_name(&self.status).ok_or_else(|| {
+                Error::RepositoryError(format!("Unknown delivery status '{}'", self.status))
+            })?,
+            attempts,
+            next_attempt_at: self.next_attempt_at,
+            created: self.created_at,
+        })
+    }
+}
+
+impl From<&WebhookDelivery> for WebhookDeliveryData {
+    fn from(value: &WebhookDelivery) -> Self {
+        WebhookDeliveryData {
+            delivery_id: value.delivery_id,
+            created_at: value.created,
+            subscription_id: value.subscription_id,
+            tenant: value.tenant.clone(),
+            event_id: value.event_id,
+            event_type: value.event_type.as_str().to_owned(),
+            payload: value.payload.clone(),
+            status: value.status.as_str().to_owned(),
+            next_attempt_at: value.next_attempt_at,
+        }
+    }
+}
+
+impl DeliveryAttemptData {
+    pub fn from_model(delivery_id: Uuid, attempt: &DeliveryAttempt) -> Self {
+        DeliveryAttemptData {
+            attempt_id: attempt.attempt_id,
+            delivery_id,
+            attempted_at: attempt.attempted_at,
+            succeeded: attempt.succeeded,
+            response_status: attempt.response_status.map(i32::from),
+            error: attempt.error.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use super::*;
+
+    fn delivery() -> WebhookDelivery {
+        WebhookDelivery {
+            delivery_id: gen_uuid("9d4b8c36-5a2f-4f3e-8f3b-6c1d2a7e9b10"),
+            subscription_id: gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+            tenant: "tenant".to_owned(),
+            event_id: gen_uuid("b5e3f0a2-1c4d-4e6f-9a8b-7c6d5e4f3a21"),
+            event_type: EventType::JobStarted,
+            payload: json!({"type": "job_started"}),
+            status: DeliveryStatus::Pending,
+            attempts: vec![
+                DeliveryAttempt {
+                    attempt_id: gen_uuid("0e6c2f7a-3b1d-4c5e-8f9a-1b2c3d4e5f60"),
+                    attempted_at: gen_datetime("2025-06-21T12:00:00"),
+                    succeeded: false,
+                    response_status: None,
+                    error: Some("Connection refused".to_owned()),
+                },
+                DeliveryAttempt {
+                    attempt_id: gen_uuid("1f7d3a8b-4c2e-4d6f-9a0b-2c3d4e5f6a71"),
+                    attempted_at: gen_datetime("2025-06-21T12:00:30"),
+                    succeeded: false,
+                    response_status: Some(503),
+                    error: Some("Received HTTP 503 Service Unavailable".to_owned()),
+                },
+            ],
+            next_attempt_at: Some(gen_datetime("2025-06-21T12:01:30")),
+            created: gen_datetime("2025-06-21T12:00:00"),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let delivery = delivery();
+
+        let data = WebhookDeliveryData::from(&delivery);
+        // Attempts are stored separately, and may be read back in any order.
+        let attempt_datas = delivery
+            .attempts
+            .iter()
+            .rev()
+            .map(|attempt| DeliveryAttemptData::from_model(delivery.delivery_id, attempt))
+            .collect::<Vec<DeliveryAttemptData>>();
+
+        assert_eq!(data.event_type, "job_started");
+        assert_eq!(data.status, "pending");
+        assert_eq!(attempt_datas[0].response_status, Some(503));
+        assert_eq!(data.to_model(&attempt_datas), Ok(delivery));
+    }
+
+    #[test]
+    fn test_unknown_status() {
+        let mut data = WebhookDeliveryData::from(&delivery());
+        data.status = "exploded".to_owned();
+
+        assert_eq!(
+            data.to_model(&[]),
+            Err(Error::RepositoryError(
+                "Unknown delivery status 'exploded'".to_owned()
+            ))
+        );
+    }
+}
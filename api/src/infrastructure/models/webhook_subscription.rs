@@ -0,0 +1,105 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::models::{EventType, WebhookSubscription};
+use crate::infrastructure::db_schema::webhook_subscription;
+
+#[derive(Clone, Queryable, Identifiable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = webhook_subscription)]
+#[diesel(primary_key(subscription_id))]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookSubscriptionData {
+    pub subscription_id: Uuid,
+    pub tenant: String,
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    pub event_types: Vec<String>,
+    pub active: bool,
+}
+
+impl From<&WebhookSubscriptionData> for WebhookSubscription {
+    fn from(value: &WebhookSubscriptionData) -> Self {
+        WebhookSubscription {
+            subscription_id: value.subscription_id,
+            tenant: value.tenant.clone(),
+            name: value.name.clone(),
+            url: value.url.clone(),
+            secret: value.secret.clone(),
+            // Event types that we no longer recognise are never sent, so they're dropped rather
+            // than failing to read the subscription.
+            event_types: value
+                .event_types
+                .iter()
+                .filter_map(|event_type| event_type.parse::<EventType>().ok())
+                .collect(),
+            active: value.active,
+        }
+    }
+}
+
+impl From<&WebhookSubscription> for WebhookSubscriptionData {
+    fn from(value: &WebhookSubscription) -> Self {
+        WebhookSubscriptionData {
+            subscription_id: value.subscription_id,
+            tenant: value.tenant.clone(),
+            name: value.name.clone(),
+            url: value.url.clone(),
+            secret: value.secret.clone(),
+            event_types: value
+                .event_types
+                .iter()
+                .map(|event_type| event_type.as_str().to_owned())
+                .collect(),
+            active: value.active,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use test_utils::gen_uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let subscription = WebhookSubscription {
+            subscription_id: gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+            tenant: "tenant".to_owned(),
+            name: "Pipeline".to_owned(),
+            url: "https://example.com/events".to_owned(),
+            secret: Some("secret".to_owned()),
+            event_types: vec![EventType::MonitorDeleted, EventType::JobFinished],
+            active: true,
+        };
+
+        let data = WebhookSubscriptionData::from(&subscription);
+        assert_eq!(
+            data.event_types,
+            vec!["monitor_deleted".to_owned(), "job_finished".to_owned()]
+        );
+        assert_eq!(WebhookSubscription::from(&data), subscription);
+    }
+
+    #[test]
+    fn test_unknown_event_types_are_dropped() {
+        let data = WebhookSubscriptionData {
+            subscription_id: gen_uuid("3867e53d-9c17-4ce9-b153-eff3d8c9edec"),
+            tenant: "tenant".to_owned(),
+            name: "Pipeline".to_owned(),
+            url: "https://example.com/events".to_owned(),
+            secret: None,
+            event_types: vec!["job_started".to_owned(), "job_exploded".to_owned()],
+            active: true,
+        };
+
+        assert_eq!(
+            WebhookSubscription::from(&data).event_types,
+            vec![EventType::JobStarted]
+        );
+    }
+}
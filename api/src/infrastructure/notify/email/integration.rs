@@ -0,0 +1,484 @@
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use uuid::Uuid;
+
+use crate::domain::models::{
+    AlertConfig, DurationAnomaly, EmailAlertConfig, FailureCount, Job, SmtpConfig, SmtpTls,
+};
+use crate::errors::Error;
+use crate::infrastructure::notify::Notifier;
+
+use super::messages::{
+    AnomalousJobMessage, EmailTemplate, ErroredJobMessage, LateJobMessage, MissedHeartbeatMessage,
+    MissedStartMessage, OverlappingJobMessage, RecoveredMessage, TestMessage,
+};
+
+/// How long to wait for the SMTP relay before giving up on sending an email.
+const SMTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Email notifier, which sends alerts to a list of recipients via an SMTP relay.
+///
+/// The relay configured on the alert configuration itself is used if there is one, otherwise the
+/// server-level relay (see [`smtp_relay_from_env`]) is used.
+pub struct EmailNotifier {
+    recipients: Vec<String>,
+    smtp: Option<SmtpConfig>,
+}
+
+impl EmailNotifier {
+    pub fn new(config: &EmailAlertConfig, server_relay: Option<&SmtpConfig>) -> Self {
+        Self {
+            recipients: config.recipients.clone(),
+            smtp: config.smtp.as_ref().or(server_relay).cloned(),
+        }
+    }
+
+    async fn send_message(&self, template: impl EmailTemplate) -> Result<(), Error> {
+        let Some(smtp) = &self.smtp else {
+            return Err(Error::NotifyError(
+                "No SMTP relay is configured for email alerts".to_owned(),
+            ));
+        };
+
+        let mut builder = Message::builder()
+            .from(parse_mailbox(&smtp.from)?)
+            .subject(template.subject());
+        for recipient in &self.recipients {
+            builder = builder.to(parse_mailbox(recipient)?);
+        }
+        let message = builder
+            .multipart(MultiPart::alternative_plain_html(
+                template.text_body(),
+                template.html_body(),
+            ))
+            .map_err(|error| Error::NotifyError(error.to_string()))?;
+
+        build_transport(smtp)?
+            .send(message)
+            .await
+            .map_err(|error| Error::NotifyError(error.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, Error> {
+    address
+        .parse()
+        .map_err(|_| Error::NotifyError(format!("Invalid email address '{address}'")))
+}
+
+fn build_transport(smtp: &SmtpConfig) -> Result<AsyncSmtpTransport<Tokio1Executor>, Error> {
+    let builder = match smtp.tls {
+        SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host),
+        SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host),
+        SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+            &smtp.host,
+        )),
+    }
+    .map_err(|error| Error::NotifyError(error.to_string()))?
+    .port(smtp.port)
+    .timeout(Some(SMTP_TIMEOUT));
+
+    Ok(match (&smtp.username, &smtp.password) {
+        (Some(username), Some(password)) => builder
+            .credentials(Credentials::new(username.clone(), password.clone()))
+            .build(),
+        _ => builder.build(),
+    })
+}
+
+/// Read the server-level SMTP relay from the environment, which is used by email alert
+/// configurations that don't specify their own relay.
+///
+/// The relay is only configured when `SMTP_HOST` is set, in which case `SMTP_FROM` must be set
+/// too. `SMTP_PORT`, `SMTP_USERNAME`, `SMTP_PASSWORD` and `SMTP_TLS` are optional.
+pub fn smtp_relay_from_env() -> Option<SmtpConfig> {
+    smtp_relay_from(|key| env::var(key).ok())
+}
+
+fn smtp_relay_from(var: impl Fn(&str) -> Option<String>) -> Option<SmtpConfig> {
+    let host = var("SMTP_HOST").filter(|host| !host.is_empty())?;
+
+    Some(SmtpConfig {
+        host,
+        port: var("SMTP_PORT")
+            .map(|port| port.parse().expect("'SMTP_PORT' must be a valid port"))
+            .unwrap_or_else(SmtpConfig::default_port),
+        username: var("SMTP_USERNAME"),
+        password: var("SMTP_PASSWORD"),
+        from: var("SMTP_FROM").expect("'SMTP_FROM' missing from environment"),
+        tls: var("SMTP_TLS")
+            .map(|tls| {
+                tls.parse()
+                    .expect("'SMTP_TLS' must be one of starttls, tls or none")
+            })
+            .unwrap_or_default(),
+    })
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn notify_late_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        late_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        self.send_message(LateJobMessage {
+            monitor_id,
+            monitor_name,
+            job: late_job,
+            failures,
+        })
+        .await
+    }
+
+    async fn notify_errored_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        errored_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        self.send_message(ErroredJobMessage {
+            monitor_id,
+            monitor_name,
+            job: errored_job,
+            failures,
+        })
+        .await
+    }
+
+    async fn notify_recovered(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        recovered_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_message(RecoveredMessage {
+            monitor_id,
+            monitor_name,
+            job: recovered_job,
+        })
+        .await
+    }
+
+    async fn notify_anomalous_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        anomalous_job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Result<(), Error> {
+        self.send_message(AnomalousJobMessage {
+            monitor_id,
+            monitor_name,
+            job: anomalous_job,
+            anomaly,
+        })
+        .await
+    }
+
+    async fn notify_overlapping_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        overlapping_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_message(OverlappingJobMessage {
+            monitor_id,
+            monitor_name,
+            job: overlapping_job,
+        })
+        .await
+    }
+
+    async fn notify_missed_start(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_message(MissedStartMessage {
+            monitor_id,
+            monitor_name,
+            scheduled_start,
+        })
+        .await
+    }
+
+    async fn notify_missed_heartbeat(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_message(MissedHeartbeatMessage {
+            monitor_id,
+            monitor_name,
+            expected_heartbeat,
+        })
+        .await
+    }
+
+    async fn test_notification(
+        &mut self,
+        alert_config: &AlertConfig,
+        user: &str,
+    ) -> Result<(), Error> {
+        self.send_message(TestMessage { alert_config, user }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use super::*;
+
+    /// A bare-bones SMTP sink, which accepts a single email and returns the commands and data it
+    /// received.
+    async fn start_smtp_sink() -> (u16, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let mut received = Vec::new();
+            let mut in_data = false;
+
+            writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let reply: &[u8] = if in_data {
+                    if line == "." {
+                        in_data = false;
+                        b"250 OK\r\n"
+                    } else {
+                        received.push(line);
+                        continue;
+                    }
+                } else if line.starts_with("DATA") {
+                    in_data = true;
+                    b"354 Go ahead\r\n"
+                } else if line.starts_with("QUIT") {
+                    received.push(line);
+                    writer.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                if !line.is_empty() && !in_data {
+                    received.push(line);
+                }
+                writer.write_all(reply).await.unwrap();
+            }
+
+            received
+        });
+
+        (port, handle)
+    }
+
+    fn email_config(smtp: Option<SmtpConfig>) -> EmailAlertConfig {
+        EmailAlertConfig {
+            recipients: vec![
+                "oncall@example.com".to_owned(),
+                "Infra Team <infra@example.com>".to_owned(),
+            ],
+            smtp,
+        }
+    }
+
+    fn local_relay(port: u16) -> SmtpConfig {
+        SmtpConfig {
+            host: "127.0.0.1".to_owned(),
+            port,
+            username: None,
+            password: None,
+            from: "cron-mon@example.com".to_owned(),
+            tls: SmtpTls::None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_errored_job() {
+        let (port, sink) = start_smtp_sink().await;
+
+        let mut notifier = EmailNotifier::new(&email_config(Some(local_relay(port))), None);
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:00:00"),
+            max_end_time: gen_datetime("2024-05-01T00:10:00"),
+            end_state: Some(crate::domain::models::EndState {
+                end_time: gen_datetime("2024-05-01T00:05:00"),
+                succeeded: false,
+                output: Some("Oh no".to_owned()),
+                metadata: crate::domain::models::JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        notifier
+            .notify_errored_job(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &job,
+                &FailureCount {
+                    consecutive: 1,
+                    window: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let received = sink.await.unwrap();
+        assert!(received.contains(&"MAIL FROM:<cron-mon@example.com>".to_owned()));
+        assert!(received.contains(&"RCPT TO:<oncall@example.com>".to_owned()));
+        assert!(received.contains(&"RCPT TO:<infra@example.com>".to_owned()));
+        assert!(received.contains(&"Subject: Failed 'foo' job".to_owned()));
+        assert!(received
+            .iter()
+            .any(|line| line.starts_with("Content-Type: multipart/alternative")));
+        assert!(received.contains(&"Content-Type: text/plain; charset=utf-8".to_owned()));
+        assert!(received.contains(&"Content-Type: text/html; charset=utf-8".to_owned()));
+        assert!(received.contains(&"Job output: Oh no".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_server_relay() {
+        let (port, sink) = start_smtp_sink().await;
+
+        let relay = local_relay(port);
+        let mut notifier = EmailNotifier::new(&email_config(None), Some(&relay));
+        notifier
+            .notify_missed_heartbeat(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &gen_datetime("2024-05-01T00:00:00"),
+            )
+            .await
+            .unwrap();
+
+        let received = sink.await.unwrap();
+        assert!(received.contains(&"Subject: Missed 'foo' heartbeat".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_no_relay_is_an_error() {
+        let mut notifier = EmailNotifier::new(&email_config(None), None);
+
+        let result = notifier
+            .notify_missed_start(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &gen_datetime("2024-05-01T00:00:00"),
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(Error::NotifyError(
+                "No SMTP relay is configured for email alerts".to_owned()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_relay_is_an_error() {
+        // Bind and immediately drop a listener to find a port nothing is listening on.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let mut notifier = EmailNotifier::new(&email_config(Some(local_relay(port))), None);
+        let result = notifier
+            .notify_missed_start(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &gen_datetime("2024-05-01T00:00:00"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::NotifyError(_))));
+    }
+
+    #[test]
+    fn test_smtp_relay_from_env() {
+        let vars = HashMap::from([
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_PORT", "465"),
+            ("SMTP_USERNAME", "user"),
+            ("SMTP_PASSWORD", "pass"),
+            ("SMTP_FROM", "cron-mon@example.com"),
+            ("SMTP_TLS", "tls"),
+        ]);
+
+        assert_eq!(
+            smtp_relay_from(|key| vars.get(key).map(|value| value.to_string())),
+            Some(SmtpConfig {
+                host: "smtp.example.com".to_owned(),
+                port: 465,
+                username: Some("user".to_owned()),
+                password: Some("pass".to_owned()),
+                from: "cron-mon@example.com".to_owned(),
+                tls: SmtpTls::Tls,
+            })
+        );
+    }
+
+    #[test]
+    fn test_smtp_relay_from_env_defaults() {
+        let vars = HashMap::from([
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_FROM", "cron-mon@example.com"),
+        ]);
+
+        assert_eq!(
+            smtp_relay_from(|key| vars.get(key).map(|value| value.to_string())),
+            Some(SmtpConfig {
+                host: "smtp.example.com".to_owned(),
+                port: 587,
+                username: None,
+                password: None,
+                from: "cron-mon@example.com".to_owned(),
+                tls: SmtpTls::Starttls,
+            })
+        );
+    }
+
+    #[test]
+    fn test_smtp_relay_not_configured() {
+        assert_eq!(smtp_relay_from(|_| None), None);
+        assert_eq!(
+            smtp_relay_from(|key| (key == "SMTP_HOST").then(String::new)),
+            None
+        );
+    }
+}
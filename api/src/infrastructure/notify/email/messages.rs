@@ -0,0 +1,710 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
+
+/// A template for an email, which is sent as both plain text and HTML so that mail clients can
+/// pick whichever they prefer.
+pub trait EmailTemplate {
+    /// The subject line of the email.
+    fn subject(&self) -> String;
+
+    /// The plain text body of the email.
+    fn text_body(&self) -> String;
+
+    /// The HTML body of the email.
+    fn html_body(&self) -> String;
+}
+
+/// A message template for notifying that a job was late.
+#[derive(Debug, Clone)]
+pub struct LateJobMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+    pub failures: &'a FailureCount,
+}
+
+impl LateJobMessage<'_> {
+    fn summary(&self) -> String {
+        format!(
+            "The job started at {}, and was expected to finish by {} at the latest, but it \
+            hasn't reported that it's finished yet.",
+            self.job.start_time.format("%Y-%m-%d %H:%M:%S"),
+            self.job.max_end_time.format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+}
+
+impl EmailTemplate for LateJobMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Late '{}' job", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "{}\n\n{}\n\n{}\n\nMonitor ID: {}\nJob ID: {}\n",
+            self.subject(),
+            self.summary(),
+            failures_counted(self.failures),
+            self.monitor_id,
+            self.job.job_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[
+                paragraph(&self.summary()),
+                paragraph(&failures_counted(self.failures)),
+                ids(self.monitor_id, Some(&self.job.job_id)),
+            ],
+        )
+    }
+}
+
+/// A message template for notifying that a job finished with an error
+#[derive(Debug, Clone)]
+pub struct ErroredJobMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+    pub failures: &'a FailureCount,
+}
+
+impl ErroredJobMessage<'_> {
+    fn summary(&self) -> String {
+        // Unwrap is safe because we'll only ever call this on a job we know has finished (with an
+        // error).
+        let end_state = self.job.end_state.as_ref().unwrap();
+        format!(
+            "Job failed at {}.",
+            end_state.end_time.format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+
+    fn output(&self) -> Option<&str> {
+        self.job
+            .end_state
+            .as_ref()
+            .and_then(|end_state| end_state.output.as_deref())
+    }
+}
+
+impl EmailTemplate for ErroredJobMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Failed '{}' job", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        let mut body = format!(
+            "{}\n\n{}\n\n{}\n\n",
+            self.subject(),
+            self.summary(),
+            failures_counted(self.failures)
+        );
+        if let Some(output) = self.output() {
+            body.push_str(&format!("Job output: {output}\n\n"));
+        }
+        body.push_str(&format!(
+            "Monitor ID: {}\nJob ID: {}\n",
+            self.monitor_id, self.job.job_id
+        ));
+        body
+    }
+
+    fn html_body(&self) -> String {
+        let mut sections = vec![
+            paragraph(&self.summary()),
+            paragraph(&failures_counted(self.failures)),
+        ];
+        if let Some(output) = self.output() {
+            sections.push(format!(
+                "<p>Job output: <code>{}</code></p>",
+                escape_html(output)
+            ));
+        }
+        sections.push(ids(self.monitor_id, Some(&self.job.job_id)));
+
+        render_html(&self.subject(), &sections)
+    }
+}
+
+/// A message template for notifying that a job succeeded on time, after late or failed jobs.
+#[derive(Debug, Clone)]
+pub struct RecoveredMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+}
+
+impl RecoveredMessage<'_> {
+    fn summary(&self) -> String {
+        // Unwrap is safe because we'll only ever call this on a job we know has finished
+        // (successfully).
+        let end_state = self.job.end_state.as_ref().unwrap();
+        format!(
+            "The job finished successfully at {}, following one or more late or failed jobs.",
+            end_state.end_time.format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+}
+
+impl EmailTemplate for RecoveredMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Recovered '{}' job", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "{}\n\n{}\n\nMonitor ID: {}\nJob ID: {}\n",
+            self.subject(),
+            self.summary(),
+            self.monitor_id,
+            self.job.job_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[
+                paragraph(&self.summary()),
+                ids(self.monitor_id, Some(&self.job.job_id)),
+            ],
+        )
+    }
+}
+
+/// A message template for notifying that a job succeeded, but took an anomalous amount of time.
+#[derive(Debug, Clone)]
+pub struct AnomalousJobMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+    pub anomaly: &'a DurationAnomaly,
+}
+
+impl AnomalousJobMessage<'_> {
+    fn summary(&self) -> String {
+        // Unwrap is safe because we'll only ever call this on a job we know has finished
+        // (successfully).
+        let end_state = self.job.end_state.as_ref().unwrap();
+        format!(
+            "The job finished successfully at {}, but took {}s when it was expected to take {}.",
+            end_state.end_time.format("%Y-%m-%d %H:%M:%S"),
+            self.anomaly.duration,
+            self.anomaly.expected()
+        )
+    }
+}
+
+impl EmailTemplate for AnomalousJobMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Anomalous '{}' job", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "{}\n\n{}\n\nMonitor ID: {}\nJob ID: {}\n",
+            self.subject(),
+            self.summary(),
+            self.monitor_id,
+            self.job.job_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[
+                paragraph(&self.summary()),
+                ids(self.monitor_id, Some(&self.job.job_id)),
+            ],
+        )
+    }
+}
+
+/// A message template for notifying that a job started while a previous job was still in
+/// progress.
+#[derive(Debug, Clone)]
+pub struct OverlappingJobMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+}
+
+impl OverlappingJobMessage<'_> {
+    fn summary(&self) -> String {
+        format!(
+            "The job started at {} while a previous job was still in progress.",
+            self.job.start_time.format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+}
+
+impl EmailTemplate for OverlappingJobMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Overlapping '{}' job", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "{}\n\n{}\n\nMonitor ID: {}\nJob ID: {}\n",
+            self.subject(),
+            self.summary(),
+            self.monitor_id,
+            self.job.job_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[
+                paragraph(&self.summary()),
+                ids(self.monitor_id, Some(&self.job.job_id)),
+            ],
+        )
+    }
+}
+
+/// A message template for notifying that a job was scheduled to start, but never did.
+#[derive(Debug, Clone)]
+pub struct MissedStartMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub scheduled_start: &'a NaiveDateTime,
+}
+
+impl MissedStartMessage<'_> {
+    fn summary(&self) -> String {
+        format!(
+            "A job was scheduled to start at {}, but it hasn't reported that it's started.",
+            self.scheduled_start.format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+}
+
+impl EmailTemplate for MissedStartMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Missed '{}' job", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "{}\n\n{}\n\nMonitor ID: {}\n",
+            self.subject(),
+            self.summary(),
+            self.monitor_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[paragraph(&self.summary()), ids(self.monitor_id, None)],
+        )
+    }
+}
+
+/// A message template for notifying that a heartbeat was expected, but never received.
+#[derive(Debug, Clone)]
+pub struct MissedHeartbeatMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub expected_heartbeat: &'a NaiveDateTime,
+}
+
+impl MissedHeartbeatMessage<'_> {
+    fn summary(&self) -> String {
+        format!(
+            "A heartbeat was expected at {}, but one hasn't been received.",
+            self.expected_heartbeat.format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+}
+
+impl EmailTemplate for MissedHeartbeatMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Missed '{}' heartbeat", self.monitor_name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "{}\n\n{}\n\nMonitor ID: {}\n",
+            self.subject(),
+            self.summary(),
+            self.monitor_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[paragraph(&self.summary()), ids(self.monitor_id, None)],
+        )
+    }
+}
+
+/// A message template for testing alerts.
+#[derive(Debug, Clone)]
+pub struct TestMessage<'a> {
+    pub alert_config: &'a AlertConfig,
+    pub user: &'a str,
+}
+
+impl EmailTemplate for TestMessage<'_> {
+    fn subject(&self) -> String {
+        format!("Test '{}' alert", self.alert_config.name)
+    }
+
+    fn text_body(&self) -> String {
+        format!(
+            "Test alert triggered by '{}'\n\nalert_config_id: {}\n",
+            self.user, self.alert_config.alert_config_id
+        )
+    }
+
+    fn html_body(&self) -> String {
+        render_html(
+            &self.subject(),
+            &[
+                paragraph(&format!("Test alert triggered by '{}'", self.user)),
+                format!(
+                    "<p><code>alert_config_id: {}</code></p>",
+                    self.alert_config.alert_config_id
+                ),
+            ],
+        )
+    }
+}
+
+fn render_html(heading: &str, sections: &[String]) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<body>\n<h1>{}</h1>\n{}\n</body>\n</html>\n",
+        escape_html(heading),
+        sections.join("\n")
+    )
+}
+
+fn paragraph(text: &str) -> String {
+    format!("<p>{}</p>", escape_html(text))
+}
+
+fn failures_counted(failures: &FailureCount) -> String {
+    format!("Late or failed jobs counted: {failures}.")
+}
+
+fn ids(monitor_id: &Uuid, job_id: Option<&Uuid>) -> String {
+    match job_id {
+        Some(job_id) => {
+            format!("<p>Monitor ID: <code>{monitor_id}</code><br>Job ID: <code>{job_id}</code></p>")
+        }
+        None => format!("<p>Monitor ID: <code>{monitor_id}</code></p>"),
+    }
+}
+
+/// Escape text so that it can be safely embedded in HTML, since monitor names and job output are
+/// provided by users.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{
+        AlertType, EmailAlertConfig, EndState, FailureWindow, JobMetadata,
+    };
+
+    use super::*;
+
+    fn errored_job(output: Option<&str>) -> Job {
+        Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:30:00"),
+            max_end_time: gen_datetime("2024-05-01T01:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:49:00"),
+                succeeded: false,
+                output: output.map(|output| output.to_owned()),
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    fn failures() -> FailureCount {
+        FailureCount {
+            consecutive: 2,
+            window: Some(FailureWindow {
+                failures: 3,
+                runs: 5,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_late_job_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = Job {
+            end_state: None,
+            ..errored_job(None)
+        };
+        let message = LateJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+            failures: &failures(),
+        };
+
+        assert_eq!(message.subject(), "Late 'generate-orders.sh' job");
+        assert_eq!(
+            message.text_body(),
+            "Late 'generate-orders.sh' job\n\n\
+            The job started at 2024-05-01 00:30:00, and was expected to finish by \
+            2024-05-01 01:10:00 at the latest, but it hasn't reported that it's finished yet.\n\n\
+            Late or failed jobs counted: 2 in a row, 3 of the last 5.\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n\
+            Job ID: 8106bab7-d643-4ede-bd92-60c79f787344\n"
+        );
+        assert_eq!(
+            message.html_body(),
+            "<!DOCTYPE html>\n<html>\n<body>\n\
+            <h1>Late &#39;generate-orders.sh&#39; job</h1>\n\
+            <p>The job started at 2024-05-01 00:30:00, and was expected to finish by \
+            2024-05-01 01:10:00 at the latest, but it hasn&#39;t reported that it&#39;s finished \
+            yet.</p>\n\
+            <p>Late or failed jobs counted: 2 in a row, 3 of the last 5.</p>\n\
+            <p>Monitor ID: <code>c1bf0515-df39-448b-aa95-686360a33b36</code><br>\
+            Job ID: <code>8106bab7-d643-4ede-bd92-60c79f787344</code></p>\n\
+            </body>\n</html>\n"
+        );
+    }
+
+    #[test]
+    fn test_errored_job_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = errored_job(Some("Error: <stdin> is empty"));
+        let message = ErroredJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+            failures: &failures(),
+        };
+
+        assert_eq!(message.subject(), "Failed 'generate-orders.sh' job");
+        assert_eq!(
+            message.text_body(),
+            "Failed 'generate-orders.sh' job\n\n\
+            Job failed at 2024-05-01 00:49:00.\n\n\
+            Late or failed jobs counted: 2 in a row, 3 of the last 5.\n\n\
+            Job output: Error: <stdin> is empty\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n\
+            Job ID: 8106bab7-d643-4ede-bd92-60c79f787344\n"
+        );
+        assert_eq!(
+            message.html_body(),
+            "<!DOCTYPE html>\n<html>\n<body>\n\
+            <h1>Failed &#39;generate-orders.sh&#39; job</h1>\n\
+            <p>Job failed at 2024-05-01 00:49:00.</p>\n\
+            <p>Late or failed jobs counted: 2 in a row, 3 of the last 5.</p>\n\
+            <p>Job output: <code>Error: &lt;stdin&gt; is empty</code></p>\n\
+            <p>Monitor ID: <code>c1bf0515-df39-448b-aa95-686360a33b36</code><br>\
+            Job ID: <code>8106bab7-d643-4ede-bd92-60c79f787344</code></p>\n\
+            </body>\n</html>\n"
+        );
+    }
+
+    #[test]
+    fn test_errored_job_message_without_output() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = errored_job(None);
+        let message = ErroredJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+            failures: &failures(),
+        };
+
+        assert!(!message.text_body().contains("Job output"));
+        assert!(!message.html_body().contains("Job output"));
+    }
+
+    #[test]
+    fn test_recovered_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let mut job = errored_job(None);
+        job.end_state.as_mut().unwrap().succeeded = true;
+        let message = RecoveredMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+        };
+
+        assert_eq!(message.subject(), "Recovered 'generate-orders.sh' job");
+        assert_eq!(
+            message.text_body(),
+            "Recovered 'generate-orders.sh' job\n\n\
+            The job finished successfully at 2024-05-01 00:49:00, following one or more late or \
+            failed jobs.\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n\
+            Job ID: 8106bab7-d643-4ede-bd92-60c79f787344\n"
+        );
+    }
+
+    #[test]
+    fn test_anomalous_job_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let mut job = errored_job(None);
+        job.end_state.as_mut().unwrap().succeeded = true;
+        let anomaly = DurationAnomaly {
+            duration: 1140,
+            min_duration: 1800,
+            max_duration: None,
+        };
+        let message = AnomalousJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+            anomaly: &anomaly,
+        };
+
+        assert_eq!(message.subject(), "Anomalous 'generate-orders.sh' job");
+        assert_eq!(
+            message.text_body(),
+            "Anomalous 'generate-orders.sh' job\n\n\
+            The job finished successfully at 2024-05-01 00:49:00, but took 1140s when it was \
+            expected to take at least 1800s.\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n\
+            Job ID: 8106bab7-d643-4ede-bd92-60c79f787344\n"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_job_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let mut job = errored_job(None);
+        job.end_state = None;
+        job.overlapped = true;
+        let message = OverlappingJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+        };
+
+        assert_eq!(message.subject(), "Overlapping 'generate-orders.sh' job");
+        assert_eq!(
+            message.text_body(),
+            "Overlapping 'generate-orders.sh' job\n\n\
+            The job started at 2024-05-01 00:30:00 while a previous job was still in progress.\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n\
+            Job ID: 8106bab7-d643-4ede-bd92-60c79f787344\n"
+        );
+    }
+
+    #[test]
+    fn test_missed_start_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let scheduled_start = gen_datetime("2024-05-01T03:00:00");
+        let message = MissedStartMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            scheduled_start: &scheduled_start,
+        };
+
+        assert_eq!(message.subject(), "Missed 'generate-orders.sh' job");
+        assert_eq!(
+            message.text_body(),
+            "Missed 'generate-orders.sh' job\n\n\
+            A job was scheduled to start at 2024-05-01 03:00:00, but it hasn't reported that \
+            it's started.\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n"
+        );
+    }
+
+    #[test]
+    fn test_missed_heartbeat_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let expected_heartbeat = gen_datetime("2024-05-01T03:00:00");
+        let message = MissedHeartbeatMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "queue-consumer",
+            expected_heartbeat: &expected_heartbeat,
+        };
+
+        assert_eq!(message.subject(), "Missed 'queue-consumer' heartbeat");
+        assert_eq!(
+            message.text_body(),
+            "Missed 'queue-consumer' heartbeat\n\n\
+            A heartbeat was expected at 2024-05-01 03:00:00, but one hasn't been received.\n\n\
+            Monitor ID: c1bf0515-df39-448b-aa95-686360a33b36\n"
+        );
+    }
+
+    #[test]
+    fn test_test_message() {
+        let alert_config = AlertConfig::new_email_config(
+            "<b>test-alert</b>".to_owned(),
+            "foo".to_owned(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            EmailAlertConfig {
+                recipients: vec!["oncall@example.com".to_owned()],
+                smtp: None,
+            },
+        );
+        assert!(matches!(alert_config.type_, AlertType::Email(_)));
+        let message = TestMessage {
+            alert_config: &alert_config,
+            user: "test-user",
+        };
+
+        assert_eq!(message.subject(), "Test '<b>test-alert</b>' alert");
+        assert_eq!(
+            message.text_body(),
+            format!(
+                "Test alert triggered by 'test-user'\n\nalert_config_id: {}\n",
+                alert_config.alert_config_id
+            )
+        );
+        assert_eq!(
+            message.html_body(),
+            format!(
+                "<!DOCTYPE html>\n<html>\n<body>\n\
+                <h1>Test &#39;&lt;b&gt;test-alert&lt;/b&gt;&#39; alert</h1>\n\
+                <p>Test alert triggered by &#39;test-user&#39;</p>\n\
+                <p><code>alert_config_id: {}</code></p>\n\
+                </body>\n</html>\n",
+                alert_config.alert_config_id
+            )
+        );
+    }
+}
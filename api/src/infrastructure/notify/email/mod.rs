@@ -0,0 +1,4 @@
+pub mod integration;
+pub mod messages;
+
+pub use integration::EmailNotifier;
@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
+use crate::errors::Error;
+use crate::infrastructure::metrics::record_alert;
+use crate::infrastructure::notify::Notifier;
+
+/// A Notifier that records metrics for each alert sent (or that fails to send) by the Notifier it
+/// wraps.
+pub struct MeteredNotifier {
+    notifier_type: &'static str,
+    notifier: Box<dyn Notifier + Sync + Send>,
+}
+
+impl MeteredNotifier {
+    pub fn new(notifier_type: &'static str, notifier: Box<dyn Notifier + Sync + Send>) -> Self {
+        Self {
+            notifier_type,
+            notifier,
+        }
+    }
+
+    fn record(&self, alert: &str, result: Result<(), Error>) -> Result<(), Error> {
+        record_alert(self.notifier_type, alert, result.is_ok());
+        result
+    }
+}
+
+#[async_trait]
+impl Notifier for MeteredNotifier {
+    async fn notify_late_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        late_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_late_job(monitor_id, monitor_name, late_job, failures)
+            .await;
+        self.record("late_job", result)
+    }
+
+    async fn notify_errored_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        errored_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_errored_job(monitor_id, monitor_name, errored_job, failures)
+            .await;
+        self.record("errored_job", result)
+    }
+
+    async fn resolve_late_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        late_job: &Job,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .resolve_late_job(monitor_id, monitor_name, late_job)
+            .await;
+        self.record("late_job_resolved", result)
+    }
+
+    async fn resolve_errored_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        errored_job: &Job,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .resolve_errored_job(monitor_id, monitor_name, errored_job)
+            .await;
+        self.record("errored_job_resolved", result)
+    }
+
+    async fn notify_recovered(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        recovered_job: &Job,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_recovered(monitor_id, monitor_name, recovered_job)
+            .await;
+        self.record("recovered", result)
+    }
+
+    async fn notify_anomalous_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        anomalous_job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_anomalous_job(monitor_id, monitor_name, anomalous_job, anomaly)
+            .await;
+        self.record("anomalous_job", result)
+    }
+
+    async fn notify_overlapping_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        overlapping_job: &Job,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_overlapping_job(monitor_id, monitor_name, overlapping_job)
+            .await;
+        self.record("overlapping_job", result)
+    }
+
+    async fn notify_missed_start(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_missed_start(monitor_id, monitor_name, scheduled_start)
+            .await;
+        self.record("missed_start", result)
+    }
+
+    async fn notify_missed_heartbeat(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        let result = self
+            .notifier
+            .notify_missed_heartbeat(monitor_id, monitor_name, expected_heartbeat)
+            .await;
+        self.record("missed_heartbeat", result)
+    }
+
+    async fn test_notification(
+        &mut self,
+        alert_config: &AlertConfig,
+        user: &str,
+    ) -> Result<(), Error> {
+        let result = self.notifier.test_notification(alert_config, user).await;
+        self.record("test", result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::infrastructure::metrics::render_process_metrics;
+    use crate::infrastructure::notify::MockNotifier;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metered_notifier_records_alerts() {
+        let mut mock = MockNotifier::new();
+        mock.expect_notify_missed_start()
+            .once()
+            .returning(|_, _, _| Ok(()));
+        mock.expect_notify_missed_heartbeat()
+            .once()
+            .returning(|_, _, _| Err(Error::NotifyError("test error".to_owned())));
+
+        let mut notifier = MeteredNotifier::new("metered-test", Box::new(mock));
+
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let expected = gen_datetime("2024-05-01T00:00:00");
+        let sent = notifier
+            .notify_missed_start(&monitor_id, "foo", &expected)
+            .await;
+        let failed = notifier
+            .notify_missed_heartbeat(&monitor_id, "foo", &expected)
+            .await;
+
+        assert_eq!(sent, Ok(()));
+        assert_eq!(failed, Err(Error::NotifyError("test error".to_owned())));
+
+        let metrics = render_process_metrics();
+        assert!(metrics.contains(
+            "cronmon_alerts_sent_total{alert=\"missed_start\",notifier=\"metered-test\"} 1"
+        ));
+        assert!(metrics.contains(
+            "cronmon_alert_failures_total{alert=\"missed_heartbeat\",notifier=\"metered-test\"} 1"
+        ));
+    }
+}
@@ -1,32 +1,110 @@
+pub mod email;
+pub mod metered;
+pub mod pagerduty;
 pub mod slack;
+pub mod webhook;
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use uuid::Uuid;
 
 #[cfg(test)]
 use mockall::automock;
 
-use crate::domain::models::{AlertConfig, Job};
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
 use crate::errors::Error;
 
-/// Notify that a job is late or that it has errored - or send a test notification.
+/// Notify that a job is late, that it has errored, that it never started or that a heartbeat was
+/// missed - or send a test notification. Late and errored job notifications can also be resolved
+/// once the problem has gone away, and a Monitor can be notified as having recovered, as having
+/// had a job succeed in an anomalous amount of time, or as having had jobs overlap.
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait Notifier {
-    /// Notify that a job is late.
+    /// Notify that a job is late, along with the late or errored jobs counted towards the
+    /// Monitor's alert threshold.
     async fn notify_late_job(
         &mut self,
         monitor_id: &Uuid,
         monitor_name: &str,
         late_job: &Job,
+        failures: &FailureCount,
     ) -> Result<(), Error>;
 
-    /// Notify that a job has errored.
+    /// Notify that a job has errored, along with the late or errored jobs counted towards the
+    /// Monitor's alert threshold.
     async fn notify_errored_job(
         &mut self,
         monitor_id: &Uuid,
         monitor_name: &str,
         errored_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error>;
+
+    /// Notify that a job previously notified as being late has since finished.
+    ///
+    /// Most integrations have no notion of an open alert, so by default this does nothing.
+    async fn resolve_late_job(
+        &mut self,
+        _monitor_id: &Uuid,
+        _monitor_name: &str,
+        _late_job: &Job,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Notify that a job previously notified as having errored has since been followed by a
+    /// successful job.
+    ///
+    /// Most integrations have no notion of an open alert, so by default this does nothing.
+    async fn resolve_errored_job(
+        &mut self,
+        _monitor_id: &Uuid,
+        _monitor_name: &str,
+        _errored_job: &Job,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Notify that a job has succeeded on time, following one or more late or errored jobs.
+    async fn notify_recovered(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        recovered_job: &Job,
+    ) -> Result<(), Error>;
+
+    /// Notify that a job succeeded, but took an anomalous amount of time to do so.
+    async fn notify_anomalous_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        anomalous_job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Result<(), Error>;
+
+    /// Notify that a job started while another of the Monitor's jobs was still in progress.
+    async fn notify_overlapping_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        overlapping_job: &Job,
+    ) -> Result<(), Error>;
+
+    /// Notify that a job was scheduled to start, but never did.
+    async fn notify_missed_start(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Result<(), Error>;
+
+    /// Notify that a heartbeat was expected, but never received.
+    async fn notify_missed_heartbeat(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
     ) -> Result<(), Error>;
 
     /// Send a test notification.
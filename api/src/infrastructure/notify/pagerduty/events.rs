@@ -0,0 +1,491 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
+
+/// The `source` reported on every event, so that incidents can be traced back to CronMon.
+const EVENT_SOURCE: &str = "cron-mon";
+
+/// An event sent to PagerDuty's Events API v2.
+///
+/// See https://developer.pagerduty.com/docs/events-api-v2-overview for details.
+#[derive(Debug, Serialize)]
+pub struct PagerDutyEvent<'a> {
+    pub routing_key: &'a str,
+    pub event_action: EventAction,
+    /// Identifies the incident an event relates to, so that later events can resolve it.
+    pub dedup_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<EventPayload>,
+}
+
+/// What an event does to the incident identified by its `dedup_key`.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventAction {
+    Trigger,
+    Resolve,
+}
+
+/// The details of the incident a trigger event opens.
+#[derive(Debug, Serialize)]
+pub struct EventPayload {
+    pub summary: String,
+    pub source: &'static str,
+    pub severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    pub custom_details: Value,
+}
+
+/// The severity of a triggered incident.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl<'a> PagerDutyEvent<'a> {
+    /// Trigger an incident for a late job.
+    pub fn late_job(
+        routing_key: &'a str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &Job,
+        failures: &FailureCount,
+    ) -> Self {
+        Self::trigger(
+            routing_key,
+            late_job_dedup_key(monitor_id, &job.job_id),
+            EventPayload {
+                summary: format!("Late '{monitor_name}' job"),
+                source: EVENT_SOURCE,
+                severity: Severity::Warning,
+                timestamp: Some(job.max_end_time.and_utc()),
+                component: Some(monitor_name.to_owned()),
+                custom_details: job_details(monitor_id, job, failures),
+            },
+        )
+    }
+
+    /// Resolve the incident triggered for a late job.
+    pub fn resolve_late_job(routing_key: &'a str, monitor_id: &Uuid, job: &Job) -> Self {
+        Self::resolve(routing_key, late_job_dedup_key(monitor_id, &job.job_id))
+    }
+
+    /// Trigger an incident for a job that finished with an error.
+    pub fn errored_job(
+        routing_key: &'a str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &Job,
+        failures: &FailureCount,
+    ) -> Self {
+        Self::trigger(
+            routing_key,
+            errored_job_dedup_key(monitor_id, &job.job_id),
+            EventPayload {
+                summary: format!("Failed '{monitor_name}' job"),
+                source: EVENT_SOURCE,
+                severity: Severity::Error,
+                timestamp: job
+                    .end_state
+                    .as_ref()
+                    .map(|end_state| end_state.end_time.and_utc()),
+                component: Some(monitor_name.to_owned()),
+                custom_details: job_details(monitor_id, job, failures),
+            },
+        )
+    }
+
+    /// Resolve the incident triggered for a job that finished with an error.
+    pub fn resolve_errored_job(routing_key: &'a str, monitor_id: &Uuid, job: &Job) -> Self {
+        Self::resolve(routing_key, errored_job_dedup_key(monitor_id, &job.job_id))
+    }
+
+    /// Trigger an incident for a job that succeeded, but took an anomalous amount of time. Nothing
+    /// later resolves this, so it's only a warning.
+    pub fn anomalous_job(
+        routing_key: &'a str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Self {
+        let end_time = job.end_state.as_ref().map(|end_state| end_state.end_time);
+        Self::trigger(
+            routing_key,
+            format!("cron-mon/{monitor_id}/{}/anomalous", job.job_id),
+            EventPayload {
+                summary: format!("Anomalous '{monitor_name}' job"),
+                source: EVENT_SOURCE,
+                severity: Severity::Warning,
+                timestamp: end_time.map(|end_time| end_time.and_utc()),
+                component: Some(monitor_name.to_owned()),
+                custom_details: json!({
+                    "monitor_id": monitor_id,
+                    "job_id": job.job_id,
+                    "start_time": job.start_time,
+                    "end_time": end_time,
+                    "anomaly": anomaly,
+                }),
+            },
+        )
+    }
+
+    /// Trigger an incident for a job that started while a previous job was still in progress.
+    /// Nothing later resolves this, so it's only a warning.
+    pub fn overlapping_job(
+        routing_key: &'a str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        job: &Job,
+    ) -> Self {
+        Self::trigger(
+            routing_key,
+            format!("cron-mon/{monitor_id}/{}/overlapping", job.job_id),
+            EventPayload {
+                summary: format!("Overlapping '{monitor_name}' job"),
+                source: EVENT_SOURCE,
+                severity: Severity::Warning,
+                timestamp: Some(job.start_time.and_utc()),
+                component: Some(monitor_name.to_owned()),
+                custom_details: json!({
+                    "monitor_id": monitor_id,
+                    "job_id": job.job_id,
+                    "start_time": job.start_time,
+                }),
+            },
+        )
+    }
+
+    /// Trigger an incident for a job that was scheduled to start, but never did.
+    pub fn missed_start(
+        routing_key: &'a str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Self {
+        Self::trigger(
+            routing_key,
+            format!(
+                "cron-mon/{monitor_id}/missed-start/{}",
+                scheduled_start.and_utc().timestamp()
+            ),
+            EventPayload {
+                summary: format!("Missed '{monitor_name}' job"),
+                source: EVENT_SOURCE,
+                severity: Severity::Error,
+                timestamp: Some(scheduled_start.and_utc()),
+                component: Some(monitor_name.to_owned()),
+                custom_details: json!({
+                    "monitor_id": monitor_id,
+                    "scheduled_start": scheduled_start,
+                }),
+            },
+        )
+    }
+
+    /// Trigger an incident for a heartbeat that was expected, but never received.
+    pub fn missed_heartbeat(
+        routing_key: &'a str,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
+    ) -> Self {
+        Self::trigger(
+            routing_key,
+            format!(
+                "cron-mon/{monitor_id}/missed-heartbeat/{}",
+                expected_heartbeat.and_utc().timestamp()
+            ),
+            EventPayload {
+                summary: format!("Missed '{monitor_name}' heartbeat"),
+                source: EVENT_SOURCE,
+                severity: Severity::Error,
+                timestamp: Some(expected_heartbeat.and_utc()),
+                component: Some(monitor_name.to_owned()),
+                custom_details: json!({
+                    "monitor_id": monitor_id,
+                    "expected_heartbeat": expected_heartbeat,
+                }),
+            },
+        )
+    }
+
+    /// Trigger a test incident. Each test incident gets a unique dedup key, so that it can be
+    /// resolved straight away without affecting any others.
+    pub fn test(routing_key: &'a str, alert_config: &AlertConfig, user: &str) -> Self {
+        Self::trigger(
+            routing_key,
+            format!(
+                "cron-mon/test/{}/{}",
+                alert_config.alert_config_id,
+                Uuid::new_v4()
+            ),
+            EventPayload {
+                summary: format!("Test '{}' alert triggered by '{user}'", alert_config.name),
+                source: EVENT_SOURCE,
+                severity: Severity::Info,
+                timestamp: Some(Utc::now()),
+                component: None,
+                custom_details: json!({"alert_config_id": alert_config.alert_config_id}),
+            },
+        )
+    }
+
+    /// Resolve the incident identified by the given dedup key.
+    pub fn resolve(routing_key: &'a str, dedup_key: String) -> Self {
+        Self {
+            routing_key,
+            event_action: EventAction::Resolve,
+            dedup_key,
+            payload: None,
+        }
+    }
+
+    fn trigger(routing_key: &'a str, dedup_key: String, payload: EventPayload) -> Self {
+        Self {
+            routing_key,
+            event_action: EventAction::Trigger,
+            dedup_key,
+            payload: Some(payload),
+        }
+    }
+}
+
+fn late_job_dedup_key(monitor_id: &Uuid, job_id: &Uuid) -> String {
+    format!("cron-mon/{monitor_id}/{job_id}/late")
+}
+
+fn errored_job_dedup_key(monitor_id: &Uuid, job_id: &Uuid) -> String {
+    format!("cron-mon/{monitor_id}/{job_id}/errored")
+}
+
+fn job_details(monitor_id: &Uuid, job: &Job, failures: &FailureCount) -> Value {
+    let end_state = job.end_state.as_ref();
+    json!({
+        "monitor_id": monitor_id,
+        "job_id": job.job_id,
+        "start_time": job.start_time,
+        "max_end_time": job.max_end_time,
+        "end_time": end_state.map(|end_state| end_state.end_time),
+        "output": end_state.and_then(|end_state| end_state.output.as_deref()),
+        "failures": failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{EndState, FailureWindow, JobMetadata};
+
+    use super::*;
+
+    fn errored_job() -> Job {
+        Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:30:00"),
+            max_end_time: gen_datetime("2024-05-01T01:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:49:00"),
+                succeeded: false,
+                output: Some("Error: failed to generate orders".to_owned()),
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    #[test]
+    fn test_errored_job_event() {
+        let event = PagerDutyEvent::errored_job(
+            "R0UT1NGK3Y",
+            &gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
+            "generate-orders.sh",
+            &errored_job(),
+            &FailureCount {
+                consecutive: 2,
+                window: Some(FailureWindow {
+                    failures: 3,
+                    runs: 5,
+                }),
+            },
+        );
+
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            json!({
+                "routing_key": "R0UT1NGK3Y",
+                "event_action": "trigger",
+                "dedup_key": "cron-mon/c1bf0515-df39-448b-aa95-686360a33b36/\
+                    8106bab7-d643-4ede-bd92-60c79f787344/errored",
+                "payload": {
+                    "summary": "Failed 'generate-orders.sh' job",
+                    "source": "cron-mon",
+                    "severity": "error",
+                    "timestamp": "2024-05-01T00:49:00Z",
+                    "component": "generate-orders.sh",
+                    "custom_details": {
+                        "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
+                        "job_id": "8106bab7-d643-4ede-bd92-60c79f787344",
+                        "start_time": "2024-05-01T00:30:00",
+                        "max_end_time": "2024-05-01T01:10:00",
+                        "end_time": "2024-05-01T00:49:00",
+                        "output": "Error: failed to generate orders",
+                        "failures": {
+                            "consecutive": 2,
+                            "window": {"failures": 3, "runs": 5}
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_resolve_events_match_trigger_events() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = errored_job();
+        let failures = FailureCount {
+            consecutive: 1,
+            window: None,
+        };
+
+        let late = PagerDutyEvent::late_job("R0UT1NGK3Y", &monitor_id, "foo", &job, &failures);
+        let resolve_late = PagerDutyEvent::resolve_late_job("R0UT1NGK3Y", &monitor_id, &job);
+        assert_eq!(late.dedup_key, resolve_late.dedup_key);
+        assert_eq!(resolve_late.event_action, EventAction::Resolve);
+
+        let errored =
+            PagerDutyEvent::errored_job("R0UT1NGK3Y", &monitor_id, "foo", &job, &failures);
+        let resolve_errored = PagerDutyEvent::resolve_errored_job("R0UT1NGK3Y", &monitor_id, &job);
+        assert_eq!(errored.dedup_key, resolve_errored.dedup_key);
+
+        // Late and errored incidents for the same job are independent of each other.
+        assert_ne!(late.dedup_key, errored.dedup_key);
+
+        assert_eq!(
+            serde_json::to_value(resolve_errored).unwrap(),
+            json!({
+                "routing_key": "R0UT1NGK3Y",
+                "event_action": "resolve",
+                "dedup_key": "cron-mon/c1bf0515-df39-448b-aa95-686360a33b36/\
+                    8106bab7-d643-4ede-bd92-60c79f787344/errored"
+            })
+        );
+    }
+
+    #[test]
+    fn test_anomalous_job_event() {
+        let mut job = errored_job();
+        job.end_state.as_mut().unwrap().succeeded = true;
+        let event = PagerDutyEvent::anomalous_job(
+            "R0UT1NGK3Y",
+            &gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
+            "generate-orders.sh",
+            &job,
+            &DurationAnomaly {
+                duration: 1140,
+                min_duration: 1800,
+                max_duration: None,
+            },
+        );
+
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            json!({
+                "routing_key": "R0UT1NGK3Y",
+                "event_action": "trigger",
+                "dedup_key": "cron-mon/c1bf0515-df39-448b-aa95-686360a33b36/\
+                    8106bab7-d643-4ede-bd92-60c79f787344/anomalous",
+                "payload": {
+                    "summary": "Anomalous 'generate-orders.sh' job",
+                    "source": "cron-mon",
+                    "severity": "warning",
+                    "timestamp": "2024-05-01T00:49:00Z",
+                    "component": "generate-orders.sh",
+                    "custom_details": {
+                        "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
+                        "job_id": "8106bab7-d643-4ede-bd92-60c79f787344",
+                        "start_time": "2024-05-01T00:30:00",
+                        "end_time": "2024-05-01T00:49:00",
+                        "anomaly": {
+                            "duration": 1140,
+                            "min_duration": 1800,
+                            "max_duration": null
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_overlapping_job_event() {
+        let event = PagerDutyEvent::overlapping_job(
+            "R0UT1NGK3Y",
+            &gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
+            "generate-orders.sh",
+            &errored_job(),
+        );
+
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            json!({
+                "routing_key": "R0UT1NGK3Y",
+                "event_action": "trigger",
+                "dedup_key": "cron-mon/c1bf0515-df39-448b-aa95-686360a33b36/\
+                    8106bab7-d643-4ede-bd92-60c79f787344/overlapping",
+                "payload": {
+                    "summary": "Overlapping 'generate-orders.sh' job",
+                    "source": "cron-mon",
+                    "severity": "warning",
+                    "timestamp": "2024-05-01T00:30:00Z",
+                    "component": "generate-orders.sh",
+                    "custom_details": {
+                        "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
+                        "job_id": "8106bab7-d643-4ede-bd92-60c79f787344",
+                        "start_time": "2024-05-01T00:30:00"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_missed_start_event() {
+        let event = PagerDutyEvent::missed_start(
+            "R0UT1NGK3Y",
+            &gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
+            "generate-orders.sh",
+            &gen_datetime("2024-05-01T03:00:00"),
+        );
+
+        assert_eq!(
+            event.dedup_key,
+            "cron-mon/c1bf0515-df39-448b-aa95-686360a33b36/missed-start/1714532400"
+        );
+        let payload = event.payload.unwrap();
+        assert_eq!(payload.summary, "Missed 'generate-orders.sh' job");
+        assert_eq!(payload.severity, Severity::Error);
+    }
+}
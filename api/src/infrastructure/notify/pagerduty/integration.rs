@@ -0,0 +1,430 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use reqwest::Client;
+use uuid::Uuid;
+
+use crate::domain::models::{
+    AlertConfig, DurationAnomaly, FailureCount, Job, PagerDutyAlertConfig,
+};
+use crate::errors::Error;
+use crate::infrastructure::notify::Notifier;
+
+use super::events::PagerDutyEvent;
+
+/// The Events API v2 endpoint that all events are sent to.
+const EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// How long to wait for PagerDuty before giving up on a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// PagerDuty notifier, which triggers incidents via the Events API v2.
+///
+/// Incidents for late and errored jobs are identified by a dedup key derived from the Monitor and
+/// Job IDs, so that they can be resolved once the job finishes or a later job succeeds. Nothing
+/// resolves anomalous and overlapping jobs, so their incidents are resolved as soon as they're
+/// triggered rather than being left open.
+pub struct PagerDutyNotifier {
+    routing_key: String,
+    events_url: String,
+    client: Client,
+}
+
+impl PagerDutyNotifier {
+    pub fn new(config: &PagerDutyAlertConfig) -> Self {
+        Self::with_events_url(config, EVENTS_URL)
+    }
+
+    fn with_events_url(config: &PagerDutyAlertConfig, events_url: &str) -> Self {
+        Self {
+            routing_key: config.routing_key.clone(),
+            events_url: events_url.to_owned(),
+            client: Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    async fn send_event(&self, event: &PagerDutyEvent<'_>) -> Result<(), Error> {
+        self.client
+            .post(&self.events_url)
+            .json(event)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| Error::NotifyError(error.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Trigger an incident and then resolve it straight away, so that nobody has to clean it up.
+    async fn trigger_and_resolve(&self, trigger: PagerDutyEvent<'_>) -> Result<(), Error> {
+        self.send_event(&trigger).await?;
+        self.send_event(&PagerDutyEvent::resolve(
+            &self.routing_key,
+            trigger.dedup_key,
+        ))
+        .await
+    }
+}
+
+#[async_trait]
+impl Notifier for PagerDutyNotifier {
+    async fn notify_late_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        late_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        self.send_event(&PagerDutyEvent::late_job(
+            &self.routing_key,
+            monitor_id,
+            monitor_name,
+            late_job,
+            failures,
+        ))
+        .await
+    }
+
+    async fn notify_errored_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        errored_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        self.send_event(&PagerDutyEvent::errored_job(
+            &self.routing_key,
+            monitor_id,
+            monitor_name,
+            errored_job,
+            failures,
+        ))
+        .await
+    }
+
+    async fn resolve_late_job(
+        &mut self,
+        monitor_id: &Uuid,
+        _monitor_name: &str,
+        late_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_event(&PagerDutyEvent::resolve_late_job(
+            &self.routing_key,
+            monitor_id,
+            late_job,
+        ))
+        .await
+    }
+
+    async fn resolve_errored_job(
+        &mut self,
+        monitor_id: &Uuid,
+        _monitor_name: &str,
+        errored_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_event(&PagerDutyEvent::resolve_errored_job(
+            &self.routing_key,
+            monitor_id,
+            errored_job,
+        ))
+        .await
+    }
+
+    async fn notify_recovered(
+        &mut self,
+        _monitor_id: &Uuid,
+        _monitor_name: &str,
+        _recovered_job: &Job,
+    ) -> Result<(), Error> {
+        // The incidents for the late and errored jobs leading up to a recovery are resolved
+        // individually, so there's nothing left to tell PagerDuty.
+        Ok(())
+    }
+
+    async fn notify_anomalous_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        anomalous_job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Result<(), Error> {
+        self.trigger_and_resolve(PagerDutyEvent::anomalous_job(
+            &self.routing_key,
+            monitor_id,
+            monitor_name,
+            anomalous_job,
+            anomaly,
+        ))
+        .await
+    }
+
+    async fn notify_overlapping_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        overlapping_job: &Job,
+    ) -> Result<(), Error> {
+        self.trigger_and_resolve(PagerDutyEvent::overlapping_job(
+            &self.routing_key,
+            monitor_id,
+            monitor_name,
+            overlapping_job,
+        ))
+        .await
+    }
+
+    async fn notify_missed_start(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_event(&PagerDutyEvent::missed_start(
+            &self.routing_key,
+            monitor_id,
+            monitor_name,
+            scheduled_start,
+        ))
+        .await
+    }
+
+    async fn notify_missed_heartbeat(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_event(&PagerDutyEvent::missed_heartbeat(
+            &self.routing_key,
+            monitor_id,
+            monitor_name,
+            expected_heartbeat,
+        ))
+        .await
+    }
+
+    async fn test_notification(
+        &mut self,
+        alert_config: &AlertConfig,
+        user: &str,
+    ) -> Result<(), Error> {
+        self.trigger_and_resolve(PagerDutyEvent::test(&self.routing_key, alert_config, user))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use crate::domain::models::{EndState, JobMetadata};
+
+    use super::*;
+
+    fn notifier(server: &MockServer) -> PagerDutyNotifier {
+        PagerDutyNotifier::with_events_url(
+            &PagerDutyAlertConfig {
+                routing_key: "R0UT1NGK3Y".to_owned(),
+            },
+            &format!("{}/v2/enqueue", server.uri()),
+        )
+    }
+
+    fn job() -> Job {
+        Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:00:00"),
+            max_end_time: gen_datetime("2024-05-01T00:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:20:00"),
+                succeeded: false,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: true,
+            error_alert_sent: true,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        }
+    }
+
+    fn failures() -> FailureCount {
+        FailureCount {
+            consecutive: 1,
+            window: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trigger_and_resolve_late_job() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/enqueue"))
+            .and(body_partial_json(serde_json::json!({
+                "routing_key": "R0UT1NGK3Y",
+                "dedup_key": "cron-mon/41ebffb4-a188-48e9-8ec1-61380085cde3/\
+                    8106bab7-d643-4ede-bd92-60c79f787344/late"
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut notifier = notifier(&server);
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        notifier
+            .notify_late_job(&monitor_id, "foo", &job(), &failures())
+            .await
+            .unwrap();
+        notifier
+            .resolve_late_job(&monitor_id, "foo", &job())
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let actions: Vec<Value> = requests
+            .iter()
+            .map(|request| {
+                let body: Value = serde_json::from_slice(&request.body).unwrap();
+                body["event_action"].clone()
+            })
+            .collect();
+        assert_eq!(actions, vec!["trigger", "resolve"]);
+    }
+
+    #[tokio::test]
+    async fn test_test_notification_is_resolved() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/enqueue"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let alert_config = AlertConfig::new_pagerduty_config(
+            "test-alert".to_owned(),
+            "foo".to_owned(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            PagerDutyAlertConfig {
+                routing_key: "R0UT1NGK3Y".to_owned(),
+            },
+        );
+        notifier(&server)
+            .test_notification(&alert_config, "test-user")
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let trigger: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let resolve: Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(trigger["payload"]["severity"], "info");
+        assert_eq!(resolve["event_action"], "resolve");
+        assert_eq!(trigger["dedup_key"], resolve["dedup_key"]);
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_incidents_are_resolved() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/enqueue"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let mut notifier = notifier(&server);
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let anomaly = DurationAnomaly {
+            duration: 1200,
+            min_duration: 1800,
+            max_duration: None,
+        };
+        notifier
+            .notify_anomalous_job(&monitor_id, "foo", &job(), &anomaly)
+            .await
+            .unwrap();
+        notifier
+            .notify_overlapping_job(&monitor_id, "foo", &job())
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let events: Vec<Value> = requests
+            .iter()
+            .map(|request| serde_json::from_slice(&request.body).unwrap())
+            .collect();
+        let actions: Vec<&Value> = events.iter().map(|event| &event["event_action"]).collect();
+        assert_eq!(actions, ["trigger", "resolve"].repeat(2));
+        for pair in events.chunks(2) {
+            assert_eq!(pair[0]["payload"]["severity"], "warning");
+            assert_eq!(pair[0]["dedup_key"], pair[1]["dedup_key"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missed_start_is_left_open() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/enqueue"))
+            .and(body_partial_json(serde_json::json!({
+                "event_action": "trigger",
+                "payload": {"severity": "error"}
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        notifier(&server)
+            .notify_missed_start(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &gen_datetime("2024-05-01T00:00:00"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_event_is_an_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/enqueue"))
+            .respond_with(ResponseTemplate::new(400))
+            .mount(&server)
+            .await;
+
+        let result = notifier(&server)
+            .notify_errored_job(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &job(),
+                &failures(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::NotifyError(_))));
+    }
+}
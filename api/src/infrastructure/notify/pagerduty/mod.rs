@@ -0,0 +1,4 @@
+pub mod events;
+pub mod integration;
+
+pub use integration::PagerDutyNotifier;
@@ -1,12 +1,16 @@
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use slack_morphism::prelude::*;
 use uuid::Uuid;
 
-use crate::domain::models::{AlertConfig, Job};
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
 use crate::errors::Error;
 use crate::infrastructure::notify::Notifier;
 
-use super::messages::{ErroredJobMessage, LateJobMessage, TestMessage};
+use super::messages::{
+    AnomalousJobMessage, ErroredJobMessage, LateJobMessage, MissedHeartbeatMessage,
+    MissedStartMessage, OverlappingJobMessage, RecoveredMessage, TestMessage,
+};
 
 /// Slack notifier for late jobs.
 ///
@@ -52,11 +56,13 @@ impl Notifier for SlackNotifier {
         monitor_id: &Uuid,
         monitor_name: &str,
         late_job: &Job,
+        failures: &FailureCount,
     ) -> Result<(), Error> {
         self.send_message(LateJobMessage {
             monitor_id,
             monitor_name,
             job: late_job,
+            failures,
         })
         .await
     }
@@ -66,11 +72,85 @@ impl Notifier for SlackNotifier {
         monitor_id: &Uuid,
         monitor_name: &str,
         errored_job: &Job,
+        failures: &FailureCount,
     ) -> Result<(), Error> {
         self.send_message(ErroredJobMessage {
             monitor_id,
             monitor_name,
             job: errored_job,
+            failures,
+        })
+        .await
+    }
+
+    async fn notify_recovered(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        recovered_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_message(RecoveredMessage {
+            monitor_id,
+            monitor_name,
+            job: recovered_job,
+        })
+        .await
+    }
+
+    async fn notify_anomalous_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        anomalous_job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Result<(), Error> {
+        self.send_message(AnomalousJobMessage {
+            monitor_id,
+            monitor_name,
+            job: anomalous_job,
+            anomaly,
+        })
+        .await
+    }
+
+    async fn notify_overlapping_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        overlapping_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_message(OverlappingJobMessage {
+            monitor_id,
+            monitor_name,
+            job: overlapping_job,
+        })
+        .await
+    }
+
+    async fn notify_missed_start(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_message(MissedStartMessage {
+            monitor_id,
+            monitor_name,
+            scheduled_start,
+        })
+        .await
+    }
+
+    async fn notify_missed_heartbeat(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_message(MissedHeartbeatMessage {
+            monitor_id,
+            monitor_name,
+            expected_heartbeat,
         })
         .await
     }
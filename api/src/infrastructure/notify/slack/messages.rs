@@ -1,7 +1,8 @@
+use chrono::NaiveDateTime;
 use slack_morphism::prelude::*;
 use uuid::Uuid;
 
-use crate::domain::models::{AlertConfig, Job};
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
 
 /// A message template for notifying that a job was late.
 #[derive(Debug, Clone)]
@@ -9,6 +10,7 @@ pub struct LateJobMessage<'a> {
     pub monitor_id: &'a Uuid,
     pub monitor_name: &'a str,
     pub job: &'a Job,
+    pub failures: &'a FailureCount,
 }
 
 impl SlackMessageTemplate for LateJobMessage<'_> {
@@ -26,6 +28,10 @@ impl SlackMessageTemplate for LateJobMessage<'_> {
                     self.job.start_time.format("%Y-%m-%d %H:%M:%S"),
                     self.job.max_end_time.format("%Y-%m-%d %H:%M:%S")
                 ))),
+                some_into(
+                    SlackSectionBlock::new()
+                        .with_text(pt!("Late or failed jobs counted: {}.", self.failures))
+                ),
                 some_into(SlackSectionBlock::new().with_text(md!(
                     "Monitor ID: `{}`\nJob ID: `{}`",
                     self.monitor_id,
@@ -41,6 +47,7 @@ pub struct ErroredJobMessage<'a> {
     pub monitor_id: &'a Uuid,
     pub monitor_name: &'a str,
     pub job: &'a Job,
+    pub failures: &'a FailureCount,
 }
 
 impl SlackMessageTemplate for ErroredJobMessage<'_> {
@@ -57,7 +64,11 @@ impl SlackMessageTemplate for ErroredJobMessage<'_> {
             some_into(SlackSectionBlock::new().with_text(pt!(
                 "Job failed at {}.",
                 end_state.end_time.format("%Y-%m-%d %H:%M:%S")
-            )))
+            ))),
+            some_into(
+                SlackSectionBlock::new()
+                    .with_text(pt!("Late or failed jobs counted: {}.", self.failures))
+            )
         ];
 
         if let Some(output) = &end_state.output {
@@ -68,6 +79,36 @@ impl SlackMessageTemplate for ErroredJobMessage<'_> {
             );
         }
 
+        let metadata = &end_state.metadata;
+        let mut details = vec![];
+        if let Some(exit_code) = metadata.exit_code {
+            details.push(format!("Exit code: `{exit_code}`"));
+        }
+        if let Some(hostname) = &metadata.hostname {
+            details.push(format!("Host: `{hostname}`"));
+        }
+        if let Some(command) = &metadata.command {
+            details.push(format!("Command: `{command}`"));
+        }
+        if let Some(version) = &metadata.version {
+            details.push(format!("Version: `{version}`"));
+        }
+        if !metadata.metrics.is_empty() {
+            let metrics = metadata
+                .metrics
+                .iter()
+                .map(|(name, value)| format!("`{name}={value}`"))
+                .collect::<Vec<_>>();
+            details.push(format!("Metrics: {}", metrics.join(", ")));
+        }
+        if !details.is_empty() {
+            blocks.push(
+                SlackSectionBlock::new()
+                    .with_text(md!("{}", details.join("\n")))
+                    .into(),
+            );
+        }
+
         blocks.push(
             SlackSectionBlock::new()
                 .with_text(md!(
@@ -84,6 +125,167 @@ impl SlackMessageTemplate for ErroredJobMessage<'_> {
     }
 }
 
+/// A message template for notifying that a job succeeded on time, after late or failed jobs.
+#[derive(Debug, Clone)]
+pub struct RecoveredMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+}
+
+impl SlackMessageTemplate for RecoveredMessage<'_> {
+    fn render_template(&self) -> SlackMessageContent {
+        // Unwrap is safe because we'll only ever call this on a job we know has finished
+        // (successfully).
+        let end_state = self.job.end_state.as_ref().unwrap();
+
+        SlackMessageContent::new()
+            .with_text(format!("Recovered '{}' job", self.monitor_name))
+            .with_blocks(slack_blocks![
+                some_into(SlackHeaderBlock::new(pt!(
+                    "Recovered '{}' job",
+                    self.monitor_name
+                ))),
+                some_into(SlackSectionBlock::new().with_text(pt!(
+                    "The job finished successfully at {}, following one or more late or failed \
+                    jobs.",
+                    end_state.end_time.format("%Y-%m-%d %H:%M:%S")
+                ))),
+                some_into(SlackSectionBlock::new().with_text(md!(
+                    "Monitor ID: `{}`\nJob ID: `{}`",
+                    self.monitor_id,
+                    self.job.job_id
+                )))
+            ])
+    }
+}
+
+/// A message template for notifying that a job succeeded, but took an anomalous amount of time.
+#[derive(Debug, Clone)]
+pub struct AnomalousJobMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+    pub anomaly: &'a DurationAnomaly,
+}
+
+impl SlackMessageTemplate for AnomalousJobMessage<'_> {
+    fn render_template(&self) -> SlackMessageContent {
+        // Unwrap is safe because we'll only ever call this on a job we know has finished
+        // (successfully).
+        let end_state = self.job.end_state.as_ref().unwrap();
+
+        SlackMessageContent::new()
+            .with_text(format!("Anomalous '{}' job", self.monitor_name))
+            .with_blocks(slack_blocks![
+                some_into(SlackHeaderBlock::new(pt!(
+                    "Anomalous '{}' job",
+                    self.monitor_name
+                ))),
+                some_into(SlackSectionBlock::new().with_text(pt!(
+                    "The job finished successfully at {}, but took {}s when it was expected to \
+                    take {}.",
+                    end_state.end_time.format("%Y-%m-%d %H:%M:%S"),
+                    self.anomaly.duration,
+                    self.anomaly.expected()
+                ))),
+                some_into(SlackSectionBlock::new().with_text(md!(
+                    "Monitor ID: `{}`\nJob ID: `{}`",
+                    self.monitor_id,
+                    self.job.job_id
+                )))
+            ])
+    }
+}
+
+/// A message template for notifying that a job started while a previous job was still in
+/// progress.
+#[derive(Debug, Clone)]
+pub struct OverlappingJobMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub job: &'a Job,
+}
+
+impl SlackMessageTemplate for OverlappingJobMessage<'_> {
+    fn render_template(&self) -> SlackMessageContent {
+        SlackMessageContent::new()
+            .with_text(format!("Overlapping '{}' job", self.monitor_name))
+            .with_blocks(slack_blocks![
+                some_into(SlackHeaderBlock::new(pt!(
+                    "Overlapping '{}' job",
+                    self.monitor_name
+                ))),
+                some_into(SlackSectionBlock::new().with_text(pt!(
+                    "The job started at {} while a previous job was still in progress.",
+                    self.job.start_time.format("%Y-%m-%d %H:%M:%S")
+                ))),
+                some_into(SlackSectionBlock::new().with_text(md!(
+                    "Monitor ID: `{}`\nJob ID: `{}`",
+                    self.monitor_id,
+                    self.job.job_id
+                )))
+            ])
+    }
+}
+
+/// A message template for notifying that a job was scheduled to start, but never did.
+#[derive(Debug, Clone)]
+pub struct MissedStartMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub scheduled_start: &'a NaiveDateTime,
+}
+
+impl SlackMessageTemplate for MissedStartMessage<'_> {
+    fn render_template(&self) -> SlackMessageContent {
+        SlackMessageContent::new()
+            .with_text(format!("Missed '{}' job", self.monitor_name))
+            .with_blocks(slack_blocks![
+                some_into(SlackHeaderBlock::new(pt!(
+                    "Missed '{}' job",
+                    self.monitor_name
+                ))),
+                some_into(SlackSectionBlock::new().with_text(pt!(
+                    "A job was scheduled to start at {}, but it hasn't reported that it's \
+                    started.",
+                    self.scheduled_start.format("%Y-%m-%d %H:%M:%S")
+                ))),
+                some_into(
+                    SlackSectionBlock::new().with_text(md!("Monitor ID: `{}`", self.monitor_id))
+                )
+            ])
+    }
+}
+
+/// A message template for notifying that a heartbeat was expected, but never received.
+#[derive(Debug, Clone)]
+pub struct MissedHeartbeatMessage<'a> {
+    pub monitor_id: &'a Uuid,
+    pub monitor_name: &'a str,
+    pub expected_heartbeat: &'a NaiveDateTime,
+}
+
+impl SlackMessageTemplate for MissedHeartbeatMessage<'_> {
+    fn render_template(&self) -> SlackMessageContent {
+        SlackMessageContent::new()
+            .with_text(format!("Missed '{}' heartbeat", self.monitor_name))
+            .with_blocks(slack_blocks![
+                some_into(SlackHeaderBlock::new(pt!(
+                    "Missed '{}' heartbeat",
+                    self.monitor_name
+                ))),
+                some_into(SlackSectionBlock::new().with_text(pt!(
+                    "A heartbeat was expected at {}, but one hasn't been received.",
+                    self.expected_heartbeat.format("%Y-%m-%d %H:%M:%S")
+                ))),
+                some_into(
+                    SlackSectionBlock::new().with_text(md!("Monitor ID: `{}`", self.monitor_id))
+                )
+            ])
+    }
+}
+
 /// A message template for testing alerts.
 #[derive(Debug, Clone)]
 pub struct TestMessage<'a> {
@@ -114,10 +316,14 @@ impl SlackMessageTemplate for TestMessage<'_> {
 // relatively simple and don't test the actual API calls, so they're not too brittle.
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use pretty_assertions::assert_eq;
     use test_utils::{gen_datetime, gen_uuid};
 
-    use crate::domain::models::{AlertConfig, AlertType, EndState, Job, SlackAlertConfig};
+    use crate::domain::models::{
+        AlertConfig, AlertType, EndState, FailureWindow, Job, JobMetadata, SlackAlertConfig,
+    };
 
     use super::*;
 
@@ -133,14 +339,27 @@ mod tests {
                 end_time: gen_datetime("2024-05-01T00:49:00"),
                 succeeded: true,
                 output: Some("Orders generated successfully".to_owned()),
+                metadata: JobMetadata::default(),
             }),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         };
         let message = LateJobMessage {
             monitor_id: &monitor_id,
             monitor_name: "generate-orders.sh",
             job: &job,
+            failures: &FailureCount {
+                consecutive: 1,
+                window: None,
+            },
         };
 
         assert_eq!(
@@ -164,6 +383,13 @@ mod tests {
                         },
                         "type": "section"
                     },
+                    {
+                        "text": {
+                            "text": "Late or failed jobs counted: 1 in a row.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
                     {
                         "text": {
                             "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`\nJob ID: \
@@ -189,14 +415,39 @@ mod tests {
                 end_time: gen_datetime("2024-05-01T00:49:00"),
                 succeeded: false,
                 output: Some("Error: failed to generate orders".to_owned()),
+                metadata: JobMetadata {
+                    exit_code: Some(2),
+                    hostname: Some("worker-1".to_owned()),
+                    command: None,
+                    version: Some("4f2a9c1".to_owned()),
+                    metrics: BTreeMap::from([
+                        ("orders_generated".to_owned(), 120.0),
+                        ("orders_failed".to_owned(), 3.0),
+                    ]),
+                },
             }),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
         };
         let message = ErroredJobMessage {
             monitor_id: &monitor_id,
             monitor_name: "generate-orders.sh",
             job: &job,
+            failures: &FailureCount {
+                consecutive: 2,
+                window: Some(FailureWindow {
+                    failures: 3,
+                    runs: 5,
+                }),
+            },
         };
 
         assert_eq!(
@@ -218,6 +469,13 @@ mod tests {
                         },
                         "type": "section"
                     },
+                    {
+                        "text": {
+                            "text": "Late or failed jobs counted: 2 in a row, 3 of the last 5.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
                     {
                         "text": {
                             "text": "Job output: `Error: failed to generate orders`",
@@ -225,6 +483,146 @@ mod tests {
                         },
                         "type": "section"
                     },
+                    {
+                        "text": {
+                            "text": "Exit code: `2`\nHost: `worker-1`\nVersion: `4f2a9c1`\n\
+                                Metrics: `orders_failed=3`, `orders_generated=120`",
+                            "type": "mrkdwn"
+                        },
+                        "type": "section"
+                    },
+                    {
+                        "text": {
+                            "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`\nJob ID: \
+                                `8106bab7-d643-4ede-bd92-60c79f787344`",
+                            "type": "mrkdwn"
+                        },
+                        "type": "section"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_recovered_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:30:00"),
+            max_end_time: gen_datetime("2024-05-01T01:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:49:00"),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        let message = RecoveredMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+        };
+
+        assert_eq!(
+            serde_json::to_value(message.render_template()).unwrap(),
+            serde_json::json!({
+                "text": "Recovered 'generate-orders.sh' job",
+                "blocks": [
+                    {
+                        "text": {
+                            "text": "Recovered 'generate-orders.sh' job",
+                            "type": "plain_text"
+                        },
+                        "type": "header"
+                    },
+                    {
+                        "text": {
+                            "text": "The job finished successfully at 2024-05-01 00:49:00, \
+                                following one or more late or failed jobs.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
+                    {
+                        "text": {
+                            "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`\nJob ID: \
+                                `8106bab7-d643-4ede-bd92-60c79f787344`",
+                            "type": "mrkdwn"
+                        },
+                        "type": "section"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_anomalous_job_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:30:00"),
+            max_end_time: gen_datetime("2024-05-01T01:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:30:04"),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        let anomaly = DurationAnomaly {
+            duration: 4,
+            min_duration: 600,
+            max_duration: Some(1500),
+        };
+        let message = AnomalousJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+            anomaly: &anomaly,
+        };
+
+        assert_eq!(
+            serde_json::to_value(message.render_template()).unwrap(),
+            serde_json::json!({
+                "text": "Anomalous 'generate-orders.sh' job",
+                "blocks": [
+                    {
+                        "text": {
+                            "text": "Anomalous 'generate-orders.sh' job",
+                            "type": "plain_text"
+                        },
+                        "type": "header"
+                    },
+                    {
+                        "text": {
+                            "text": "The job finished successfully at 2024-05-01 00:30:04, but \
+                                took 4s when it was expected to take between 600s and 1500s.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
                     {
                         "text": {
                             "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`\nJob ID: \
@@ -238,6 +636,148 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_overlapping_job_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:30:00"),
+            max_end_time: gen_datetime("2024-05-01T01:10:00"),
+            end_state: None,
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: true,
+            overlap_alert_sent: false,
+        };
+        let message = OverlappingJobMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            job: &job,
+        };
+
+        assert_eq!(
+            serde_json::to_value(message.render_template()).unwrap(),
+            serde_json::json!({
+                "text": "Overlapping 'generate-orders.sh' job",
+                "blocks": [
+                    {
+                        "text": {
+                            "text": "Overlapping 'generate-orders.sh' job",
+                            "type": "plain_text"
+                        },
+                        "type": "header"
+                    },
+                    {
+                        "text": {
+                            "text": "The job started at 2024-05-01 00:30:00 while a previous job \
+                                was still in progress.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
+                    {
+                        "text": {
+                            "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`\nJob ID: \
+                                `8106bab7-d643-4ede-bd92-60c79f787344`",
+                            "type": "mrkdwn"
+                        },
+                        "type": "section"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_missed_start_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let scheduled_start = gen_datetime("2024-05-01T03:00:00");
+        let message = MissedStartMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "generate-orders.sh",
+            scheduled_start: &scheduled_start,
+        };
+
+        assert_eq!(
+            serde_json::to_value(message.render_template()).unwrap(),
+            serde_json::json!({
+                "text": "Missed 'generate-orders.sh' job",
+                "blocks": [
+                    {
+                        "text": {
+                            "text": "Missed 'generate-orders.sh' job",
+                            "type": "plain_text"
+                        },
+                        "type": "header"
+                    },
+                    {
+                        "text": {
+                            "text": "A job was scheduled to start at 2024-05-01 03:00:00, but it \
+                                hasn't reported that it's started.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
+                    {
+                        "text": {
+                            "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`",
+                            "type": "mrkdwn"
+                        },
+                        "type": "section"
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_missed_heartbeat_message() {
+        let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+        let expected_heartbeat = gen_datetime("2024-05-01T03:00:00");
+        let message = MissedHeartbeatMessage {
+            monitor_id: &monitor_id,
+            monitor_name: "queue-consumer",
+            expected_heartbeat: &expected_heartbeat,
+        };
+
+        assert_eq!(
+            serde_json::to_value(message.render_template()).unwrap(),
+            serde_json::json!({
+                "text": "Missed 'queue-consumer' heartbeat",
+                "blocks": [
+                    {
+                        "text": {
+                            "text": "Missed 'queue-consumer' heartbeat",
+                            "type": "plain_text"
+                        },
+                        "type": "header"
+                    },
+                    {
+                        "text": {
+                            "text": "A heartbeat was expected at 2024-05-01 03:00:00, but one \
+                                hasn't been received.",
+                            "type": "plain_text"
+                        },
+                        "type": "section"
+                    },
+                    {
+                        "text": {
+                            "text": "Monitor ID: `c1bf0515-df39-448b-aa95-686360a33b36`",
+                            "type": "mrkdwn"
+                        },
+                        "type": "section"
+                    }
+                ]
+            })
+        );
+    }
+
     #[test]
     fn test_test_message() {
         let alert_config_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
@@ -249,6 +789,9 @@ mod tests {
                 active: true,
                 on_late: true,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
                 type_: AlertType::Slack(SlackAlertConfig {
                     channel: "test-channel".to_owned(),
                     token: "test-token".to_owned(),
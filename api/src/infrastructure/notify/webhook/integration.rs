@@ -0,0 +1,366 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Method};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::domain::models::{
+    AlertConfig, DurationAnomaly, FailureCount, Job, WebhookAlertConfig, WebhookMethod,
+};
+use crate::errors::Error;
+use crate::infrastructure::notify::Notifier;
+
+use super::payloads::{
+    AnomalyInfo, FailureInfo, JobInfo, MonitorInfo, WebhookEvent, WebhookPayload,
+};
+
+/// The header containing the HMAC-SHA256 signature of the request body, when a secret is
+/// configured. The value is of the form `sha256=<hex digest>`.
+pub const SIGNATURE_HEADER: &str = "X-CronMon-Signature";
+
+/// How long to wait for the receiving server before giving up on a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Webhook notifier, which sends a JSON payload describing an event to an arbitrary URL.
+///
+/// Any non-2xx response from the receiving server is treated as a failure to notify.
+pub struct WebhookNotifier {
+    config: WebhookAlertConfig,
+    client: Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: &WebhookAlertConfig) -> Self {
+        Self {
+            config: config.clone(),
+            client: Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    async fn send_event(&self, event: WebhookEvent<'_>) -> Result<(), Error> {
+        let body = serde_json::to_vec(&WebhookPayload::new(event))
+            .map_err(|error| Error::NotifyError(error.to_string()))?;
+
+        let method = match self.config.method {
+            WebhookMethod::Post => Method::POST,
+            WebhookMethod::Put => Method::PUT,
+            WebhookMethod::Patch => Method::PATCH,
+        };
+        let mut request = self
+            .client
+            .request(method, &self.config.url)
+            .header(CONTENT_TYPE, "application/json");
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        if let Some(secret) = &self.config.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| Error::NotifyError(error.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// Sign a request body using HMAC-SHA256, so that receivers can verify it came from us.
+pub(crate) fn sign(secret: &str, body: &[u8]) -> String {
+    // HMAC can take a key of any size, so this will never fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify_late_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        late_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::LateJob {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            job: JobInfo::from(late_job),
+            failures: FailureInfo::from(failures),
+        })
+        .await
+    }
+
+    async fn notify_errored_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        errored_job: &Job,
+        failures: &FailureCount,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::ErroredJob {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            job: JobInfo::from(errored_job),
+            failures: FailureInfo::from(failures),
+        })
+        .await
+    }
+
+    async fn notify_recovered(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        recovered_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::Recovered {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            job: JobInfo::from(recovered_job),
+        })
+        .await
+    }
+
+    async fn notify_anomalous_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        anomalous_job: &Job,
+        anomaly: &DurationAnomaly,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::AnomalousJob {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            job: JobInfo::from(anomalous_job),
+            anomaly: AnomalyInfo::from(anomaly),
+        })
+        .await
+    }
+
+    async fn notify_overlapping_job(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        overlapping_job: &Job,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::OverlappingJob {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            job: JobInfo::from(overlapping_job),
+        })
+        .await
+    }
+
+    async fn notify_missed_start(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        scheduled_start: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::MissedStart {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            scheduled_start,
+        })
+        .await
+    }
+
+    async fn notify_missed_heartbeat(
+        &mut self,
+        monitor_id: &Uuid,
+        monitor_name: &str,
+        expected_heartbeat: &NaiveDateTime,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::MissedHeartbeat {
+            monitor: MonitorInfo {
+                monitor_id,
+                name: monitor_name,
+            },
+            expected_heartbeat,
+        })
+        .await
+    }
+
+    async fn test_notification(
+        &mut self,
+        alert_config: &AlertConfig,
+        user: &str,
+    ) -> Result<(), Error> {
+        self.send_event(WebhookEvent::Test {
+            alert_config: alert_config.into(),
+            user,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use test_utils::{gen_datetime, gen_uuid};
+
+    use super::*;
+
+    fn webhook_config(
+        server: &MockServer,
+        method: WebhookMethod,
+        secret: Option<&str>,
+    ) -> WebhookAlertConfig {
+        WebhookAlertConfig {
+            url: format!("{}/hook", server.uri()),
+            method,
+            headers: BTreeMap::from([("X-Team".to_owned(), "infra".to_owned())]),
+            secret: secret.map(|s| s.to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_late_job() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("Content-Type", "application/json"))
+            .and(header("X-Team", "infra"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut notifier =
+            WebhookNotifier::new(&webhook_config(&server, WebhookMethod::Post, None));
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:00:00"),
+            max_end_time: gen_datetime("2024-05-01T00:10:00"),
+            end_state: None,
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        notifier
+            .notify_late_job(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &job,
+                &FailureCount {
+                    consecutive: 1,
+                    window: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.get(SIGNATURE_HEADER).is_none());
+        let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["version"], 1);
+        assert_eq!(body["event"], "late_job");
+        assert_eq!(body["data"]["monitor"]["name"], "foo");
+        assert_eq!(
+            body["data"]["job"]["job_id"],
+            "8106bab7-d643-4ede-bd92-60c79f787344"
+        );
+        assert_eq!(body["data"]["job"]["end_time"], Value::Null);
+        assert_eq!(body["data"]["failures"]["consecutive"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_signs_requests_when_secret_is_set() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut notifier = WebhookNotifier::new(&webhook_config(
+            &server,
+            WebhookMethod::Put,
+            Some("top-secret"),
+        ));
+        notifier
+            .notify_missed_heartbeat(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &gen_datetime("2024-05-01T00:00:00"),
+            )
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let signature = requests[0]
+            .headers
+            .get(SIGNATURE_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert_eq!(signature, sign("top-secret", &requests[0].body));
+        assert!(signature.starts_with("sha256="));
+    }
+
+    #[tokio::test]
+    async fn test_unsuccessful_response_is_an_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let mut notifier =
+            WebhookNotifier::new(&webhook_config(&server, WebhookMethod::Post, None));
+        let result = notifier
+            .notify_missed_start(
+                &gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+                "foo",
+                &gen_datetime("2024-05-01T00:00:00"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::NotifyError(_))));
+    }
+
+    #[test]
+    fn test_sign() {
+        // Verified with `echo -n '{"foo":"bar"}' | openssl dgst -sha256 -hmac secret`
+        assert_eq!(
+            sign("secret", br#"{"foo":"bar"}"#),
+            "sha256=3f3ab3986b656abb17af3eb1443ed6c08ef8fff9fea83915909d1b421aec89be"
+        );
+    }
+}
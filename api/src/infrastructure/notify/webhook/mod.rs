@@ -0,0 +1,4 @@
+pub mod integration;
+pub mod payloads;
+
+pub use integration::WebhookNotifier;
@@ -0,0 +1,354 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::models::{AlertConfig, DurationAnomaly, FailureCount, Job};
+
+/// The version of the payload format. This should only be bumped when a breaking change is made to
+/// the payload, so that consumers can handle both formats during a migration.
+pub const PAYLOAD_VERSION: u8 = 1;
+
+/// The body of every request sent by a webhook alert.
+///
+/// This is deliberately decoupled from the API representations of our models, so that changes to
+/// the API don't break consumers of webhooks.
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload<'a> {
+    pub version: u8,
+    pub sent_at: NaiveDateTime,
+    #[serde(flatten)]
+    pub event: WebhookEvent<'a>,
+}
+
+/// The events that a webhook alert can be sent for.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum WebhookEvent<'a> {
+    LateJob {
+        monitor: MonitorInfo<'a>,
+        job: JobInfo<'a>,
+        failures: FailureInfo,
+    },
+    ErroredJob {
+        monitor: MonitorInfo<'a>,
+        job: JobInfo<'a>,
+        failures: FailureInfo,
+    },
+    Recovered {
+        monitor: MonitorInfo<'a>,
+        job: JobInfo<'a>,
+    },
+    AnomalousJob {
+        monitor: MonitorInfo<'a>,
+        job: JobInfo<'a>,
+        anomaly: AnomalyInfo,
+    },
+    OverlappingJob {
+        monitor: MonitorInfo<'a>,
+        job: JobInfo<'a>,
+    },
+    MissedStart {
+        monitor: MonitorInfo<'a>,
+        scheduled_start: &'a NaiveDateTime,
+    },
+    MissedHeartbeat {
+        monitor: MonitorInfo<'a>,
+        expected_heartbeat: &'a NaiveDateTime,
+    },
+    Test {
+        alert_config: AlertConfigInfo<'a>,
+        user: &'a str,
+    },
+}
+
+/// Brief info on the Monitor an event relates to.
+#[derive(Debug, Serialize)]
+pub struct MonitorInfo<'a> {
+    pub monitor_id: &'a Uuid,
+    pub name: &'a str,
+}
+
+/// Info on the Job an event relates to.
+#[derive(Debug, Serialize)]
+pub struct JobInfo<'a> {
+    pub job_id: &'a Uuid,
+    pub start_time: &'a NaiveDateTime,
+    pub max_end_time: &'a NaiveDateTime,
+    pub end_time: Option<&'a NaiveDateTime>,
+    pub succeeded: Option<bool>,
+    pub output: Option<&'a str>,
+}
+
+/// Info on the late or errored Jobs counted before alerting on a Job.
+#[derive(Debug, Serialize)]
+pub struct FailureInfo {
+    pub consecutive: i32,
+    pub window_failures: Option<i32>,
+    pub window_runs: Option<i32>,
+}
+
+/// Info on how long a Job that succeeded took, compared to how long it was expected to take.
+#[derive(Debug, Serialize)]
+pub struct AnomalyInfo {
+    pub duration: u64,
+    pub min_duration: u64,
+    pub max_duration: Option<u64>,
+}
+
+/// Brief info on the alert configuration a test event was sent for.
+#[derive(Debug, Serialize)]
+pub struct AlertConfigInfo<'a> {
+    pub alert_config_id: &'a Uuid,
+    pub name: &'a str,
+}
+
+impl<'a> WebhookPayload<'a> {
+    pub fn new(event: WebhookEvent<'a>) -> Self {
+        Self {
+            version: PAYLOAD_VERSION,
+            sent_at: Utc::now().naive_utc(),
+            event,
+        }
+    }
+}
+
+impl<'a> From<&'a Job> for JobInfo<'a> {
+    fn from(job: &'a Job) -> Self {
+        Self {
+            job_id: &job.job_id,
+            start_time: &job.start_time,
+            max_end_time: &job.max_end_time,
+            end_time: job.end_state.as_ref().map(|end_state| &end_state.end_time),
+            succeeded: job.end_state.as_ref().map(|end_state| end_state.succeeded),
+            output: job
+                .end_state
+                .as_ref()
+                .and_then(|end_state| end_state.output.as_deref()),
+        }
+    }
+}
+
+impl From<&FailureCount> for FailureInfo {
+    fn from(failures: &FailureCount) -> Self {
+        Self {
+            consecutive: failures.consecutive,
+            window_failures: failures.window.map(|window| window.failures),
+            window_runs: failures.window.map(|window| window.runs),
+        }
+    }
+}
+
+impl From<&DurationAnomaly> for AnomalyInfo {
+    fn from(anomaly: &DurationAnomaly) -> Self {
+        Self {
+            duration: anomaly.duration,
+            min_duration: anomaly.min_duration,
+            max_duration: anomaly.max_duration,
+        }
+    }
+}
+
+impl<'a> From<&'a AlertConfig> for AlertConfigInfo<'a> {
+    fn from(alert_config: &'a AlertConfig) -> Self {
+        Self {
+            alert_config_id: &alert_config.alert_config_id,
+            name: &alert_config.name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use test_utils::{gen_datetime, gen_uuid, is_datetime};
+
+    use crate::domain::models::job::EndState;
+    use crate::domain::models::{FailureWindow, JobMetadata};
+
+    use super::*;
+
+    #[test]
+    fn test_errored_job_payload() {
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:00:00"),
+            max_end_time: gen_datetime("2024-05-01T00:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:05:00"),
+                succeeded: false,
+                output: Some("It broke".to_owned()),
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let payload = WebhookPayload::new(WebhookEvent::ErroredJob {
+            monitor: MonitorInfo {
+                monitor_id: &monitor_id,
+                name: "foo",
+            },
+            job: JobInfo::from(&job),
+            failures: FailureInfo::from(&FailureCount {
+                consecutive: 2,
+                window: Some(FailureWindow {
+                    failures: 3,
+                    runs: 5,
+                }),
+            }),
+        });
+
+        let mut value = serde_json::to_value(&payload).unwrap();
+        assert!(is_datetime(value["sent_at"].as_str().unwrap()));
+        value.as_object_mut().unwrap().remove("sent_at");
+
+        assert_eq!(
+            value,
+            json!({
+                "version": 1,
+                "event": "errored_job",
+                "data": {
+                    "monitor": {
+                        "monitor_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                        "name": "foo"
+                    },
+                    "job": {
+                        "job_id": "8106bab7-d643-4ede-bd92-60c79f787344",
+                        "start_time": "2024-05-01T00:00:00",
+                        "max_end_time": "2024-05-01T00:10:00",
+                        "end_time": "2024-05-01T00:05:00",
+                        "succeeded": false,
+                        "output": "It broke"
+                    },
+                    "failures": {
+                        "consecutive": 2,
+                        "window_failures": 3,
+                        "window_runs": 5
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_anomalous_job_payload() {
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:00:00"),
+            max_end_time: gen_datetime("2024-05-01T00:10:00"),
+            end_state: Some(EndState {
+                end_time: gen_datetime("2024-05-01T00:00:03"),
+                succeeded: true,
+                output: None,
+                metadata: JobMetadata::default(),
+            }),
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+        };
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let payload = WebhookPayload::new(WebhookEvent::AnomalousJob {
+            monitor: MonitorInfo {
+                monitor_id: &monitor_id,
+                name: "foo",
+            },
+            job: JobInfo::from(&job),
+            anomaly: AnomalyInfo::from(&DurationAnomaly {
+                duration: 3,
+                min_duration: 240,
+                max_duration: Some(360),
+            }),
+        });
+
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["event"], "anomalous_job");
+        assert_eq!(value["data"]["job"]["succeeded"], true);
+        assert_eq!(
+            value["data"]["anomaly"],
+            json!({
+                "duration": 3,
+                "min_duration": 240,
+                "max_duration": 360
+            })
+        );
+    }
+
+    #[test]
+    fn test_overlapping_job_payload() {
+        let job = Job {
+            job_id: gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+            start_time: gen_datetime("2024-05-01T00:00:00"),
+            max_end_time: gen_datetime("2024-05-01T00:10:00"),
+            end_state: None,
+            late_alert_sent: false,
+            error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: true,
+            overlap_alert_sent: false,
+        };
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let payload = WebhookPayload::new(WebhookEvent::OverlappingJob {
+            monitor: MonitorInfo {
+                monitor_id: &monitor_id,
+                name: "foo",
+            },
+            job: JobInfo::from(&job),
+        });
+
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["event"], "overlapping_job");
+        assert_eq!(
+            value["data"]["job"]["job_id"],
+            "8106bab7-d643-4ede-bd92-60c79f787344"
+        );
+        assert_eq!(value["data"]["job"]["end_time"], json!(null));
+    }
+
+    #[test]
+    fn test_missed_start_payload() {
+        let monitor_id = gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3");
+        let scheduled_start = gen_datetime("2024-05-01T03:00:00");
+        let payload = WebhookPayload::new(WebhookEvent::MissedStart {
+            monitor: MonitorInfo {
+                monitor_id: &monitor_id,
+                name: "foo",
+            },
+            scheduled_start: &scheduled_start,
+        });
+
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["event"], "missed_start");
+        assert_eq!(
+            value["data"],
+            json!({
+                "monitor": {
+                    "monitor_id": "41ebffb4-a188-48e9-8ec1-61380085cde3",
+                    "name": "foo"
+                },
+                "scheduled_start": "2024-05-01T03:00:00"
+            })
+        );
+    }
+}
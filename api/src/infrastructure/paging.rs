@@ -1,6 +1,36 @@
 use serde::Serialize;
+use uuid::Uuid;
 
 #[derive(Serialize)]
 pub struct Paging {
     pub total: usize,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<CursorPaging>,
+}
+
+/// Paging for lists that are retrieved a page at a time, rather than all at once.
+#[derive(Serialize)]
+pub struct CursorPaging {
+    /// The most items that were requested for the page.
+    pub limit: u32,
+    /// The cursor to request the next page with, or `None` if this is the last page.
+    pub next_cursor: Option<Uuid>,
+}
+
+impl Paging {
+    /// Paging for a list that was retrieved in its entirety.
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            cursor: None,
+        }
+    }
+
+    /// Paging for a single page of a list, out of `total` items across all pages.
+    pub fn with_cursor(total: usize, limit: u32, next_cursor: Option<Uuid>) -> Self {
+        Self {
+            total,
+            cursor: Some(CursorPaging { limit, next_cursor }),
+        }
+    }
 }
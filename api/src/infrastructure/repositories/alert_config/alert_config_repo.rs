@@ -10,10 +10,12 @@ use uuid::Uuid;
 use crate::domain::models::AlertConfig;
 use crate::errors::Error;
 use crate::infrastructure::database::{get_connection, DbPool};
-use crate::infrastructure::db_schema::{alert_config, monitor_alert_config, slack_alert_config};
-use crate::infrastructure::models::alert_config::NewSlackAlertConfigData;
+use crate::infrastructure::db_schema::{
+    alert_config, email_alert_config, monitor_alert_config, pagerduty_alert_config,
+    slack_alert_config, webhook_alert_config,
+};
 use crate::infrastructure::models::alert_config::{
-    AlertConfigData, MonitorAlertConfigData, NewAlertConfigData,
+    AlertConfigData, MonitorAlertConfigData, NewAlertConfigData, NewAlertTypeData,
 };
 use crate::infrastructure::repositories::Repository;
 
@@ -26,6 +28,20 @@ macro_rules! build_polymorphic_query {
                 slack_alert_config::dsl::slack_alert_config
                     .on(slack_alert_config::dsl::alert_config_id.eq(alert_config::alert_config_id)),
             )
+            .left_join(
+                webhook_alert_config::dsl::webhook_alert_config
+                    .on(webhook_alert_config::dsl::alert_config_id
+                        .eq(alert_config::alert_config_id)),
+            )
+            .left_join(
+                email_alert_config::dsl::email_alert_config
+                    .on(email_alert_config::dsl::alert_config_id.eq(alert_config::alert_config_id)),
+            )
+            .left_join(
+                pagerduty_alert_config::dsl::pagerduty_alert_config
+                    .on(pagerduty_alert_config::dsl::alert_config_id
+                        .eq(alert_config::alert_config_id)),
+            )
             .select((
                 alert_config::alert_config_id,
                 alert_config::name,
@@ -34,10 +50,28 @@ macro_rules! build_polymorphic_query {
                 alert_config::active,
                 alert_config::on_late,
                 alert_config::on_error,
+                alert_config::on_recovery,
+                alert_config::on_anomaly,
+                alert_config::label_selector,
                 slack_alert_config::dsl::slack_channel.nullable(),
                 slack_alert_config::dsl::slack_bot_oauth_token.nullable(),
+                webhook_alert_config::dsl::webhook_url.nullable(),
+                webhook_alert_config::dsl::webhook_method.nullable(),
+                webhook_alert_config::dsl::webhook_headers.nullable(),
+                webhook_alert_config::dsl::webhook_secret.nullable(),
+                email_alert_config::dsl::email_recipients.nullable(),
+                email_alert_config::dsl::email_smtp_host.nullable(),
+                email_alert_config::dsl::email_smtp_port.nullable(),
+                email_alert_config::dsl::email_smtp_username.nullable(),
+                email_alert_config::dsl::email_smtp_password.nullable(),
+                email_alert_config::dsl::email_smtp_from.nullable(),
+                email_alert_config::dsl::email_smtp_tls.nullable(),
+                pagerduty_alert_config::dsl::pagerduty_routing_key.nullable(),
             ))
             .distinct()
+            // Without an explicit order, Postgres is free to return these in any order, which
+            // tends to change as more integrations (and so joins) are added.
+            .order(alert_config::alert_config_id)
             .into_boxed()
     }};
 }
@@ -75,17 +109,41 @@ impl<'a> AlertConfigRepository<'a> {
         &mut self,
         conn: &mut Object<AsyncPgConnection>,
         alert_config_data: &NewAlertConfigData,
-        slack_alert_config_data: &NewSlackAlertConfigData,
+        alert_type_data: &NewAlertTypeData,
         monitor_alert_configs: &[MonitorAlertConfigData],
     ) -> Result<(), DieselError> {
         diesel::update(alert_config_data)
             .set(alert_config_data)
             .execute(conn)
             .await?;
-        diesel::update(slack_alert_config_data)
-            .set(slack_alert_config_data)
-            .execute(conn)
-            .await?;
+        // The type of an alert configuration can't be changed, so the integration specific row
+        // will always already exist.
+        match alert_type_data {
+            NewAlertTypeData::Slack(slack_data) => {
+                diesel::update(slack_data)
+                    .set(slack_data)
+                    .execute(conn)
+                    .await?;
+            }
+            NewAlertTypeData::Webhook(webhook_data) => {
+                diesel::update(webhook_data)
+                    .set(webhook_data)
+                    .execute(conn)
+                    .await?;
+            }
+            NewAlertTypeData::Email(email_data) => {
+                diesel::update(email_data)
+                    .set(email_data)
+                    .execute(conn)
+                    .await?;
+            }
+            NewAlertTypeData::PagerDuty(pagerduty_data) => {
+                diesel::update(pagerduty_data)
+                    .set(pagerduty_data)
+                    .execute(conn)
+                    .await?;
+            }
+        }
 
         // Delete all monitor_alert_configs for the alert_config and insert the new ones. This is
         // inefficient in some scenarios, like when the list of monitors an alert is configured for
@@ -112,7 +170,7 @@ impl<'a> AlertConfigRepository<'a> {
         &mut self,
         conn: &mut Object<AsyncPgConnection>,
         alert_config_data: &NewAlertConfigData,
-        slack_alert_config_data: &NewSlackAlertConfigData,
+        alert_type_data: &NewAlertTypeData,
         monitor_alert_configs: &[MonitorAlertConfigData],
     ) -> Result<(), DieselError> {
         diesel::insert_into(alert_config::table)
@@ -120,10 +178,32 @@ impl<'a> AlertConfigRepository<'a> {
             .execute(conn)
             .await?;
 
-        diesel::insert_into(slack_alert_config::table)
-            .values(slack_alert_config_data)
-            .execute(conn)
-            .await?;
+        match alert_type_data {
+            NewAlertTypeData::Slack(slack_data) => {
+                diesel::insert_into(slack_alert_config::table)
+                    .values(slack_data)
+                    .execute(conn)
+                    .await?;
+            }
+            NewAlertTypeData::Webhook(webhook_data) => {
+                diesel::insert_into(webhook_alert_config::table)
+                    .values(webhook_data)
+                    .execute(conn)
+                    .await?;
+            }
+            NewAlertTypeData::Email(email_data) => {
+                diesel::insert_into(email_alert_config::table)
+                    .values(email_data)
+                    .execute(conn)
+                    .await?;
+            }
+            NewAlertTypeData::PagerDuty(pagerduty_data) => {
+                diesel::insert_into(pagerduty_alert_config::table)
+                    .values(pagerduty_data)
+                    .execute(conn)
+                    .await?;
+            }
+        }
 
         diesel::insert_into(monitor_alert_config::table)
             .values(monitor_alert_configs)
@@ -147,33 +227,37 @@ impl<'a> AlertConfigRepository<'a> {
                         if let Some(t) = tenant {
                             query = query.filter(alert_config::tenant.eq(t));
                         }
-                        let alert_configs: Vec<AlertConfigData> = if let Some(filterable_ids) =
-                            filterable_ids
-                        {
-                            match filterable_ids {
-                                FilterableIds::AlertConfigIds(ids) => {
-                                    query
-                                        .filter(alert_config::alert_config_id.eq_any(ids))
-                                        .load(conn)
-                                        .await?
+                        let alert_configs: Vec<AlertConfigData> =
+                            if let Some(filterable_ids) = filterable_ids {
+                                match filterable_ids {
+                                    FilterableIds::AlertConfigIds(ids) => {
+                                        query
+                                            .filter(alert_config::alert_config_id.eq_any(ids))
+                                            .load(conn)
+                                            .await?
+                                    }
+                                    FilterableIds::MonitorIds(monitor_ids) => {
+                                        // Alert configs with a label selector may apply to any of
+                                        // the Monitors, so it's left to the caller to check whether
+                                        // they do.
+                                        query
+                                            .left_join(
+                                                monitor_alert_config::table
+                                                    .on(monitor_alert_config::alert_config_id
+                                                        .eq(alert_config::alert_config_id)),
+                                            )
+                                            .filter(
+                                                monitor_alert_config::monitor_id
+                                                    .eq_any(monitor_ids)
+                                                    .or(alert_config::label_selector.is_not_null()),
+                                            )
+                                            .load(conn)
+                                            .await?
+                                    }
                                 }
-                                FilterableIds::MonitorIds(monitor_ids) => {
-                                    query
-                                        .inner_join(
-                                            monitor_alert_config::table
-                                                .on(monitor_alert_config::alert_config_id
-                                                    .eq(alert_config::alert_config_id)),
-                                        )
-                                        .filter(
-                                            monitor_alert_config::monitor_id.eq_any(monitor_ids),
-                                        )
-                                        .load(conn)
-                                        .await?
-                                }
-                            }
-                        } else {
-                            query.load(conn).await?
-                        };
+                            } else {
+                                query.load(conn).await?
+                            };
 
                         let monitor_alert_configs =
                             MonitorAlertConfigData::belonging_to(&alert_configs)
@@ -273,13 +357,9 @@ impl<'a> Repository<AlertConfig> for AlertConfigRepository<'a> {
     }
 
     async fn save(&mut self, alert_config: &AlertConfig) -> Result<(), Error> {
-        let (alert_config_data, monitor_alert_configs, slack_alert_config_data) =
+        let (alert_config_data, monitor_alert_configs, alert_type_data) =
             NewAlertConfigData::from_model(alert_config);
 
-        // We can do this now as we only support Slack, but when we add more integrations we will
-        // need to handle this differently.
-        let slack_alert_config_data = slack_alert_config_data.unwrap();
-
         let mut connection = get_connection(self.pool).await?;
         connection
             .transaction::<(), DieselError, _>(|conn| {
@@ -288,7 +368,7 @@ impl<'a> Repository<AlertConfig> for AlertConfigRepository<'a> {
                         self.update(
                             conn,
                             &alert_config_data,
-                            &slack_alert_config_data,
+                            &alert_type_data,
                             &monitor_alert_configs,
                         )
                         .await?;
@@ -296,7 +376,7 @@ impl<'a> Repository<AlertConfig> for AlertConfigRepository<'a> {
                         self.insert(
                             conn,
                             &alert_config_data,
-                            &slack_alert_config_data,
+                            &alert_type_data,
                             &monitor_alert_configs,
                         )
                         .await?;
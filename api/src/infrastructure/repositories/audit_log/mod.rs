@@ -0,0 +1,25 @@
+pub mod repo;
+
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::models::{AuditEvent, AuditPage, AuditQuery};
+use crate::errors::Error;
+
+pub use repo::AuditLogRepository;
+
+/// Record and retrieve changes to tenants' configuration.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AuditLog {
+    /// Record a change to a tenant's configuration.
+    async fn record(&mut self, event: &AuditEvent) -> Result<(), Error>;
+
+    /// Get a single page of a tenant's audit log, newest first.
+    ///
+    /// Note that this method will return an `Error::AuditEventNotFound` if the query's cursor
+    /// isn't one of the tenant's audit events.
+    async fn get_page(&mut self, tenant: &str, query: &AuditQuery) -> Result<AuditPage, Error>;
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use diesel_async::AsyncConnection;
+use diesel_async::RunQueryDsl;
+
+use crate::domain::models::{AuditEvent, AuditPage, AuditQuery};
+use crate::errors::Error;
+use crate::infrastructure::database::{get_connection, DbPool};
+use crate::infrastructure::db_schema::audit_event;
+use crate::infrastructure::models::audit_event::AuditEventData;
+
+use super::AuditLog;
+
+pub struct AuditLogRepository<'a> {
+    pool: &'a DbPool,
+}
+
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> AuditLogRepository<'a> {
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> AuditLog for AuditLogRepository<'a> {
+    async fn record(&mut self, event: &AuditEvent) -> Result<(), Error> {
+        let mut connection = get_connection(self.pool).await?;
+        diesel::insert_into(audit_event::table)
+            .values(&AuditEventData::from(event))
+            .execute(&mut connection)
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get_page(&mut self, tenant: &str, query: &AuditQuery) -> Result<AuditPage, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let (total, mut event_datas) = connection
+            .transaction::<(i64, Vec<AuditEventData>), DieselError, _>(|conn| {
+                Box::pin(async move {
+                    let total = filtered_events(tenant, query)
+                        .count()
+                        .get_result::<i64>(conn)
+                        .await?;
+
+                    let mut page = filtered_events(tenant, query);
+                    if let Some(cursor) = query.cursor {
+                        // This will fail with a `NotFound` error if the cursor isn't one of the
+                        // tenant's events.
+                        let cursor_created = audit_event::table
+                            .select(audit_event::created_at)
+                            .filter(
+                                audit_event::audit_event_id
+                                    .eq(cursor)
+                                    .and(audit_event::tenant.eq(tenant)),
+                            )
+                            .first::<NaiveDateTime>(conn)
+                            .await?;
+
+                        page = page.filter(
+                            audit_event::created_at
+                                .lt(cursor_created)
+                                .or(audit_event::created_at
+                                    .eq(cursor_created)
+                                    .and(audit_event::audit_event_id.lt(cursor))),
+                        );
+                    }
+
+                    // Get one more event than needed, to find out if there's another page.
+                    let event_datas = page
+                        .order((
+                            audit_event::created_at.desc(),
+                            audit_event::audit_event_id.desc(),
+                        ))
+                        .select(AuditEventData::as_select())
+                        .limit(i64::from(query.limit) + 1)
+                        .load(conn)
+                        .await?;
+
+                    Ok((total, event_datas))
+                })
+            })
+            .await
+            .map_err(|err| match (err, query.cursor) {
+                (DieselError::NotFound, Some(cursor)) => Error::AuditEventNotFound(cursor),
+                (err, _) => Error::RepositoryError(err.to_string()),
+            })?;
+
+        let next_cursor = if event_datas.len() > query.limit as usize {
+            event_datas.truncate(query.limit as usize);
+            event_datas
+                .last()
+                .map(|event_data| event_data.audit_event_id)
+        } else {
+            None
+        };
+
+        Ok(AuditPage {
+            events: event_datas
+                .iter()
+                .map(AuditEvent::try_from)
+                .collect::<Result<Vec<AuditEvent>, Error>>()?,
+            total: total as usize,
+            next_cursor,
+        })
+    }
+}
+
+fn filtered_events(tenant: &str, query: &AuditQuery) -> audit_event::BoxedQuery<'static, Pg> {
+    let mut events = audit_event::table
+        .filter(audit_event::tenant.eq(tenant.to_owned()))
+        .into_boxed();
+
+    if let Some(entity_type) = query.entity_type {
+        events = events.filter(audit_event::entity_type.eq(entity_type.as_str()));
+    }
+    if let Some(entity_id) = query.entity_id {
+        events = events.filter(audit_event::entity_id.eq(entity_id));
+    }
+    if let Some(actor_id) = &query.actor_id {
+        events = events.filter(audit_event::actor_id.eq(actor_id.clone()));
+    }
+
+    events
+}
@@ -1,6 +1,9 @@
 pub mod alert_config;
 pub mod api_key;
+pub mod audit_log;
 pub mod monitor;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
 
 use std::marker::{Send, Sync};
 
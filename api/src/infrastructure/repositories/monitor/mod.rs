@@ -1,11 +1,12 @@
 pub mod repo;
 
 use async_trait::async_trait;
+use uuid::Uuid;
 
 #[cfg(test)]
 use mockall::automock;
 
-use crate::domain::models::Monitor;
+use crate::domain::models::{JobPage, JobQuery, JobRetention, Monitor, MonitorMetrics};
 use crate::errors::Error;
 
 pub use repo::MonitorRepository;
@@ -20,3 +21,66 @@ pub trait GetWithErroneousJobs {
     /// been alerted on.
     async fn get_with_erroneous_jobs(&mut self) -> Result<Vec<Monitor>, Error>;
 }
+
+/// Get Monitors that have a schedule, or that expect regular heartbeats.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait GetScheduled {
+    /// Get all Monitors that have a schedule or are heartbeat Monitors, across all tenants, so
+    /// that they can be checked for missed starts (or heartbeats).
+    async fn get_scheduled(&mut self) -> Result<Vec<Monitor>, Error>;
+}
+
+/// Get Monitors that have their durations automatically tuned.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait GetAutoTuned {
+    /// Get all Monitors that have auto-tuning enabled, across all tenants, so that their
+    /// durations can be tuned to suit their Jobs.
+    async fn get_auto_tuned(&mut self) -> Result<Vec<Monitor>, Error>;
+}
+
+/// Get a page of a Monitor's job history.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait GetJobHistory {
+    /// Get a single page of a Monitor's Jobs, without retrieving the rest of the Monitor, or
+    /// `None` if the Monitor doesn't exist within the tenant.
+    ///
+    /// Note that this method will return an `Error::JobNotFound` if the query's cursor isn't one
+    /// of the Monitor's Jobs.
+    async fn get_job_history(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+        query: &JobQuery,
+    ) -> Result<Option<JobPage>, Error>;
+}
+
+/// Prune Jobs that are outside of their Monitor's retention.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PruneJobs {
+    /// Get the retention of every Monitor that has one, across all tenants.
+    async fn get_retentions(&mut self) -> Result<Vec<(Uuid, JobRetention)>, Error>;
+
+    /// Delete up to `batch_size` of a Monitor's oldest Jobs that are outside of its retention,
+    /// returning the number of Jobs that were deleted.
+    ///
+    /// Note that this method must never delete Jobs that are still in progress, or that still
+    /// have alerts to be sent or resolved.
+    async fn prune_jobs(
+        &mut self,
+        monitor_id: Uuid,
+        retention: &JobRetention,
+        batch_size: u32,
+    ) -> Result<usize, Error>;
+}
+
+/// Get metrics describing the activity of Monitors.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait GetMetrics {
+    /// Get the metrics of every Monitor, across all tenants.
+    async fn get_metrics(&mut self) -> Result<Vec<MonitorMetrics>, Error>;
+}
@@ -1,26 +1,32 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-use diesel::dsl::now;
+use chrono::{NaiveDateTime, Utc};
+use diesel::dsl::{count_star, exists, not, now};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::result::Error as DieselError;
 use diesel_async::AsyncConnection;
 use diesel_async::RunQueryDsl;
 use uuid::Uuid;
 
-use crate::domain::models::Monitor;
+use crate::domain::models::{
+    Job, JobOrder, JobPage, JobQuery, JobRetention, JobStatus, Monitor, MonitorMetrics,
+};
 use crate::errors::Error;
 use crate::infrastructure::database::{get_connection, DbPool};
 use crate::infrastructure::db_schema::job;
-use crate::infrastructure::db_schema::monitor;
+use crate::infrastructure::db_schema::{monitor, monitor_label};
 use crate::infrastructure::models::job::JobData;
-use crate::infrastructure::models::monitor::MonitorData;
-use crate::infrastructure::repositories::monitor::GetWithErroneousJobs;
+use crate::infrastructure::models::monitor::{MonitorData, MonitorLabelData};
+use crate::infrastructure::repositories::monitor::{
+    GetAutoTuned, GetJobHistory, GetMetrics, GetScheduled, GetWithErroneousJobs, PruneJobs,
+};
 use crate::infrastructure::repositories::Repository;
 
 pub struct MonitorRepository<'a> {
     pool: &'a DbPool,
-    data: HashMap<Uuid, (MonitorData, Vec<JobData>)>,
+    data: HashMap<Uuid, (MonitorData, Vec<JobData>, Vec<MonitorLabelData>)>,
 }
 
 #[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
@@ -36,67 +42,694 @@ impl<'a> MonitorRepository<'a> {
         &mut self,
         monitor_data: MonitorData,
         job_datas: Vec<JobData>,
+        label_datas: Vec<MonitorLabelData>,
     ) -> Result<Monitor, Error> {
-        let mon: Monitor = monitor_data.to_model(&job_datas)?;
-        self.data.insert(mon.monitor_id, (monitor_data, job_datas));
+        let mon: Monitor = monitor_data.to_model(&job_datas, &label_datas)?;
+        self.data
+            .insert(mon.monitor_id, (monitor_data, job_datas, label_datas));
         Ok(mon)
     }
+
+    fn db_to_monitors(
+        &mut self,
+        monitor_datas: Vec<MonitorData>,
+        job_datas: Vec<JobData>,
+        label_datas: Vec<MonitorLabelData>,
+    ) -> Result<Vec<Monitor>, Error> {
+        let job_datas = job_datas.grouped_by(&monitor_datas);
+        let label_datas = label_datas.grouped_by(&monitor_datas);
+
+        monitor_datas
+            .into_iter()
+            .zip(job_datas)
+            .zip(label_datas)
+            .map(|((monitor_data, job_datas), label_datas)| {
+                self.db_to_monitor(monitor_data, job_datas, label_datas)
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
 #[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
 impl<'a> GetWithErroneousJobs for MonitorRepository<'a> {
-    /// Get Monitors with jobs that are late or have finished with an error.
+    /// Get Monitors with jobs that are late or have finished with an error, that have been
+    /// alerted on and can now have their alerts resolved, that have recovered from late or
+    /// errored jobs, that have succeeded and are yet to be checked for having taken an anomalous
+    /// amount of time, or that started while a previous job was still in progress and are yet to
+    /// be alerted on.
     ///
     /// Note that this method will not return Monitors that have erroneous jobs that have already
-    /// been alerted on.
+    /// been alerted on, unless those alerts can be resolved. Erroneous jobs that are yet to reach
+    /// their Monitor's alert threshold are still returned, since the threshold is evaluated
+    /// against each Monitor's job history.
     async fn get_with_erroneous_jobs(&mut self) -> Result<Vec<Monitor>, Error> {
         let mut connection = get_connection(self.pool).await?;
-        let (monitor_datas, job_datas) = connection
-            .transaction::<(Vec<MonitorData>, Vec<JobData>), DieselError, _>(|conn| {
+        let (monitor_datas, job_datas, label_datas) = connection
+            .transaction::<(Vec<MonitorData>, Vec<JobData>, Vec<MonitorLabelData>), DieselError, _>(
+                |conn| {
+                    Box::pin(async move {
+                        let running_late_condition =
+                            job::end_time.is_null().and(now.gt(job::max_end_time));
+                        let finished_late_condition = job::end_time
+                            .is_not_null()
+                            .and(job::end_time.assume_not_null().gt(job::max_end_time));
+
+                        // Late alerts are resolved once the job finishes, and error alerts are
+                        // resolved once a later job in the same monitor succeeds.
+                        let later_job = diesel::alias!(job as later_job);
+                        let late_resolvable_condition = job::late_alert_sent
+                            .eq(true)
+                            .and(job::late_alert_resolved.eq(false))
+                            .and(job::end_time.is_not_null());
+                        let error_resolvable_condition = job::error_alert_sent
+                            .eq(true)
+                            .and(job::error_alert_resolved.eq(false))
+                            .and(exists(
+                                later_job
+                                    .filter(later_job.field(job::monitor_id).eq(job::monitor_id))
+                                    .filter(later_job.field(job::start_time).gt(job::start_time))
+                                    .filter(later_job.field(job::succeeded).eq(true)),
+                            ));
+
+                        // Recoveries are jobs that succeeded on time, straight after a job in the
+                        // same monitor that was alerted on for being late or errored, outside of
+                        // maintenance and having reached the monitor's alert threshold.
+                        let (previous_job, between_job) =
+                            diesel::alias!(job as previous_job, job as between_job);
+                        let recovered_condition = job::recovery_alert_sent
+                            .eq(false)
+                            .and(job::succeeded.eq(true))
+                            .and(job::end_time.assume_not_null().le(job::max_end_time))
+                            .and(exists(
+                                previous_job
+                                    .filter(previous_job.field(job::monitor_id).eq(job::monitor_id))
+                                    .filter(previous_job.field(job::start_time).lt(job::start_time))
+                                    .filter(previous_job.field(job::during_maintenance).eq(false))
+                                    .filter(previous_job.field(job::below_threshold).eq(false))
+                                    .filter(
+                                        previous_job
+                                            .field(job::late_alert_sent)
+                                            .eq(true)
+                                            .or(previous_job.field(job::error_alert_sent).eq(true)),
+                                    )
+                                    .filter(not(exists(
+                                        between_job
+                                            .filter(
+                                                between_job
+                                                    .field(job::monitor_id)
+                                                    .eq(job::monitor_id),
+                                            )
+                                            .filter(
+                                                between_job
+                                                    .field(job::start_time)
+                                                    .gt(previous_job.field(job::start_time)),
+                                            )
+                                            .filter(
+                                                between_job
+                                                    .field(job::start_time)
+                                                    .lt(job::start_time),
+                                            ),
+                                    ))),
+                            ));
+
+                        // Jobs that succeeded are checked for having taken an anomalous amount of
+                        // time once, in monitors that detect anomalies.
+                        let anomaly_unchecked_condition = job::anomaly_checked
+                            .eq(false)
+                            .and(job::succeeded.eq(true))
+                            .and(monitor::kind.ne("heartbeat"))
+                            .and(
+                                monitor::anomaly_min_duration
+                                    .is_not_null()
+                                    .or(monitor::anomaly_max_deviations.is_not_null()),
+                            );
+
+                        // Jobs that overlapped a previous job are alerted on once, in monitors that
+                        // alert on overlaps.
+                        let overlap_pending_condition = job::overlapped
+                            .eq(true)
+                            .and(job::overlap_alert_sent.eq(false))
+                            .and(monitor::concurrency_policy.eq("alert"));
+
+                        // Get all late and errored jobs, those with alerts to resolve, those that
+                        // have recovered, those yet to be checked for anomalies, and those that
+                        // overlapped.
+                        let monitor_datas: Vec<MonitorData> = monitor::table
+                            .inner_join(job::table)
+                            .filter(
+                                (job::late_alert_sent
+                                    .eq(false)
+                                    .and(running_late_condition.or(finished_late_condition)))
+                                .or(job::error_alert_sent
+                                    .eq(false)
+                                    .and(job::end_time.is_not_null())
+                                    .and(job::succeeded.eq(false)))
+                                .or(late_resolvable_condition)
+                                .or(error_resolvable_condition)
+                                .or(recovered_condition)
+                                .or(anomaly_unchecked_condition)
+                                .or(overlap_pending_condition),
+                            )
+                            .select(MonitorData::as_select())
+                            .distinct_on(monitor::monitor_id)
+                            .load(conn)
+                            .await?;
+
+                        let job_datas = JobData::belonging_to(&monitor_datas)
+                            .select(JobData::as_select())
+                            .order(job::start_time.desc())
+                            .load(conn)
+                            .await?;
+
+                        let label_datas = MonitorLabelData::belonging_to(&monitor_datas)
+                            .select(MonitorLabelData::as_select())
+                            .order(monitor_label::key)
+                            .load(conn)
+                            .await?;
+
+                        Ok((monitor_datas, job_datas, label_datas))
+                    })
+                },
+            )
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        self.db_to_monitors(monitor_datas, job_datas, label_datas)
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> GetScheduled for MonitorRepository<'a> {
+    /// Get all Monitors that have a schedule or are heartbeat Monitors, across all tenants.
+    async fn get_scheduled(&mut self) -> Result<Vec<Monitor>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let (monitor_datas, job_datas, label_datas) = connection
+            .transaction::<(Vec<MonitorData>, Vec<JobData>, Vec<MonitorLabelData>), DieselError, _>(
+                |conn| {
+                    Box::pin(async move {
+                        let monitor_datas: Vec<MonitorData> = monitor::table
+                            .select(MonitorData::as_select())
+                            .filter(
+                                monitor::schedule
+                                    .is_not_null()
+                                    .or(monitor::kind.eq("heartbeat")),
+                            )
+                            .load(conn)
+                            .await?;
+
+                        let job_datas = JobData::belonging_to(&monitor_datas)
+                            .select(JobData::as_select())
+                            .order(job::start_time.desc())
+                            .load(conn)
+                            .await?;
+
+                        let label_datas = MonitorLabelData::belonging_to(&monitor_datas)
+                            .select(MonitorLabelData::as_select())
+                            .order(monitor_label::key)
+                            .load(conn)
+                            .await?;
+
+                        Ok((monitor_datas, job_datas, label_datas))
+                    })
+                },
+            )
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        self.db_to_monitors(monitor_datas, job_datas, label_datas)
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> GetAutoTuned for MonitorRepository<'a> {
+    /// Get all Monitors that have auto-tuning enabled, across all tenants.
+    async fn get_auto_tuned(&mut self) -> Result<Vec<Monitor>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let (monitor_datas, job_datas, label_datas) = connection
+            .transaction::<(Vec<MonitorData>, Vec<JobData>, Vec<MonitorLabelData>), DieselError, _>(
+                |conn| {
+                    Box::pin(async move {
+                        let monitor_datas: Vec<MonitorData> = monitor::table
+                            .select(MonitorData::as_select())
+                            .filter(monitor::auto_tune_min_expected.is_not_null())
+                            .order(monitor::monitor_id)
+                            .load(conn)
+                            .await?;
+
+                        let job_datas = JobData::belonging_to(&monitor_datas)
+                            .select(JobData::as_select())
+                            .order(job::start_time.desc())
+                            .load(conn)
+                            .await?;
+
+                        let label_datas = MonitorLabelData::belonging_to(&monitor_datas)
+                            .select(MonitorLabelData::as_select())
+                            .order(monitor_label::key)
+                            .load(conn)
+                            .await?;
+
+                        Ok((monitor_datas, job_datas, label_datas))
+                    })
+                },
+            )
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        self.db_to_monitors(monitor_datas, job_datas, label_datas)
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> GetJobHistory for MonitorRepository<'a> {
+    /// Get a single page of a Monitor's Jobs, without retrieving the rest of the Monitor, or
+    /// `None` if the Monitor doesn't exist within the tenant.
+    ///
+    /// Pages are keyed on the start time and ID of the last Job in the previous page, so that
+    /// Jobs started whilst paging don't shift the pages that follow.
+    async fn get_job_history(
+        &mut self,
+        monitor_id: Uuid,
+        tenant: &str,
+        query: &JobQuery,
+    ) -> Result<Option<JobPage>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let result = connection
+            .transaction::<Option<(i64, Vec<JobData>)>, DieselError, _>(|conn| {
                 Box::pin(async move {
-                    let running_late_condition =
-                        job::end_time.is_null().and(now.gt(job::max_end_time));
-                    let finished_late_condition = job::end_time
-                        .is_not_null()
-                        .and(job::end_time.assume_not_null().gt(job::max_end_time));
-
-                    // Get all late and errored jobs.
-                    let monitor_datas: Vec<MonitorData> = monitor::table
-                        .inner_join(job::table)
+                    let monitor_exists = monitor::table
+                        .select(monitor::monitor_id)
                         .filter(
-                            (job::late_alert_sent
+                            monitor::monitor_id
+                                .eq(monitor_id)
+                                .and(monitor::tenant.eq(tenant)),
+                        )
+                        .first::<Uuid>(conn)
+                        .await
+                        .optional()?
+                        .is_some();
+                    if !monitor_exists {
+                        return Ok(None);
+                    }
+
+                    let total = filtered_jobs(monitor_id, query)
+                        .count()
+                        .get_result::<i64>(conn)
+                        .await?;
+
+                    let mut page = filtered_jobs(monitor_id, query);
+                    if let Some(cursor) = query.cursor {
+                        // This will fail with a `NotFound` error if the cursor isn't one of the
+                        // Monitor's Jobs.
+                        let cursor_start = job::table
+                            .select(job::start_time)
+                            .filter(job::job_id.eq(cursor).and(job::monitor_id.eq(monitor_id)))
+                            .first::<NaiveDateTime>(conn)
+                            .await?;
+
+                        page = match query.order {
+                            JobOrder::NewestFirst => {
+                                page.filter(job::start_time.lt(cursor_start).or(
+                                    job::start_time.eq(cursor_start).and(job::job_id.lt(cursor)),
+                                ))
+                            }
+                            JobOrder::OldestFirst => {
+                                page.filter(job::start_time.gt(cursor_start).or(
+                                    job::start_time.eq(cursor_start).and(job::job_id.gt(cursor)),
+                                ))
+                            }
+                        };
+                    }
+                    page = match query.order {
+                        JobOrder::NewestFirst => {
+                            page.order((job::start_time.desc(), job::job_id.desc()))
+                        }
+                        JobOrder::OldestFirst => {
+                            page.order((job::start_time.asc(), job::job_id.asc()))
+                        }
+                    };
+
+                    // Get one more Job than needed, to find out if there's another page.
+                    let job_datas = page
+                        .select(JobData::as_select())
+                        .limit(i64::from(query.limit) + 1)
+                        .load(conn)
+                        .await?;
+
+                    Ok(Some((total, job_datas)))
+                })
+            })
+            .await
+            .map_err(|err| match (err, query.cursor) {
+                (DieselError::NotFound, Some(cursor)) => Error::JobNotFound(monitor_id, cursor),
+                (err, _) => Error::RepositoryError(err.to_string()),
+            })?;
+
+        let Some((total, mut job_datas)) = result else {
+            return Ok(None);
+        };
+
+        let next_cursor = if job_datas.len() > query.limit as usize {
+            job_datas.truncate(query.limit as usize);
+            job_datas.last().map(|job_data| job_data.job_id)
+        } else {
+            None
+        };
+
+        Ok(Some(JobPage {
+            jobs: job_datas
+                .iter()
+                .map(|job_data| job_data.into())
+                .collect::<Result<Vec<Job>, Error>>()?,
+            total: total as usize,
+            next_cursor,
+        }))
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> PruneJobs for MonitorRepository<'a> {
+    async fn get_retentions(&mut self) -> Result<Vec<(Uuid, JobRetention)>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let retentions = monitor::table
+            .select((
+                monitor::monitor_id,
+                monitor::retention_max_age_days,
+                monitor::retention_max_jobs,
+            ))
+            .filter(
+                monitor::retention_max_age_days
+                    .is_not_null()
+                    .or(monitor::retention_max_jobs.is_not_null()),
+            )
+            .order(monitor::monitor_id)
+            .load::<(Uuid, Option<i32>, Option<i32>)>(&mut connection)
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        retentions
+            .into_iter()
+            .map(|(monitor_id, max_age_days, max_jobs)| {
+                Ok((monitor_id, JobRetention::new(max_age_days, max_jobs)?))
+            })
+            .collect()
+    }
+
+    /// Delete up to `batch_size` of a Monitor's oldest Jobs that are outside of its retention,
+    /// returning the number of Jobs that were deleted.
+    ///
+    /// Jobs are kept whilst they're in progress, late or errored without having been alerted on,
+    /// alerted on without having been resolved, or part of a recovery that's yet to be alerted
+    /// on.
+    async fn prune_jobs(
+        &mut self,
+        monitor_id: Uuid,
+        retention: &JobRetention,
+        batch_size: u32,
+    ) -> Result<usize, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        connection
+            .transaction::<usize, DieselError, _>(|conn| {
+                Box::pin(async move {
+                    // Jobs are outside of the retention if they started before the maximum age,
+                    // or before the oldest of the most recent Jobs that are kept.
+                    let mut cutoff = retention.max_age_cutoff(Utc::now().naive_utc());
+                    if let Some(max_jobs) = retention.max_jobs {
+                        let oldest_kept = job::table
+                            .select(job::start_time)
+                            .filter(job::monitor_id.eq(monitor_id))
+                            .order(job::start_time.desc())
+                            .offset(i64::from(max_jobs) - 1)
+                            .first::<NaiveDateTime>(conn)
+                            .await
+                            .optional()?;
+                        cutoff = match (cutoff, oldest_kept) {
+                            (Some(max_age_cutoff), Some(oldest_kept)) => {
+                                Some(max_age_cutoff.max(oldest_kept))
+                            }
+                            (max_age_cutoff, oldest_kept) => max_age_cutoff.or(oldest_kept),
+                        };
+                    }
+                    let Some(cutoff) = cutoff else {
+                        return Ok(0);
+                    };
+
+                    // Recoveries need both the recovered job and the alerted job straight before
+                    // it, so neither can be pruned until the recovery has been alerted on. Jobs
+                    // that ran during maintenance or fell short of the alert threshold were never
+                    // really alerted on, so aren't recovered from.
+                    let (other_job, between_job) =
+                        diesel::alias!(job as other_job, job as between_job);
+                    let alerted = job::during_maintenance
+                        .eq(false)
+                        .and(job::below_threshold.eq(false))
+                        .and(
+                            job::late_alert_sent
+                                .eq(true)
+                                .or(job::error_alert_sent.eq(true)),
+                        );
+                    let followed_by_pending_recovery = exists(
+                        other_job
+                            .filter(other_job.field(job::monitor_id).eq(job::monitor_id))
+                            .filter(other_job.field(job::start_time).gt(job::start_time))
+                            .filter(other_job.field(job::recovery_alert_sent).eq(false))
+                            .filter(other_job.field(job::succeeded).eq(true))
+                            .filter(
+                                other_job
+                                    .field(job::end_time)
+                                    .assume_not_null()
+                                    .le(other_job.field(job::max_end_time)),
+                            )
+                            .filter(not(exists(
+                                between_job
+                                    .filter(between_job.field(job::monitor_id).eq(job::monitor_id))
+                                    .filter(between_job.field(job::start_time).gt(job::start_time))
+                                    .filter(
+                                        between_job
+                                            .field(job::start_time)
+                                            .lt(other_job.field(job::start_time)),
+                                    ),
+                            ))),
+                    );
+                    let pending_recovery = job::recovery_alert_sent.eq(false).and(exists(
+                        other_job
+                            .filter(other_job.field(job::monitor_id).eq(job::monitor_id))
+                            .filter(other_job.field(job::start_time).lt(job::start_time))
+                            .filter(other_job.field(job::during_maintenance).eq(false))
+                            .filter(other_job.field(job::below_threshold).eq(false))
+                            .filter(
+                                other_job
+                                    .field(job::late_alert_sent)
+                                    .eq(true)
+                                    .or(other_job.field(job::error_alert_sent).eq(true)),
+                            )
+                            .filter(not(exists(
+                                between_job
+                                    .filter(between_job.field(job::monitor_id).eq(job::monitor_id))
+                                    .filter(
+                                        between_job
+                                            .field(job::start_time)
+                                            .gt(other_job.field(job::start_time)),
+                                    )
+                                    .filter(between_job.field(job::start_time).lt(job::start_time)),
+                            ))),
+                    ));
+
+                    let job_ids: Vec<Uuid> = job::table
+                        .select(job::job_id)
+                        .filter(job::monitor_id.eq(monitor_id))
+                        .filter(job::start_time.lt(cutoff))
+                        .filter(job::end_time.is_not_null())
+                        // Late and errored jobs must have been alerted on, and those alerts
+                        // resolved.
+                        .filter(
+                            job::late_alert_sent
+                                .eq(true)
+                                .or(job::end_time.assume_not_null().le(job::max_end_time)),
+                        )
+                        .filter(job::error_alert_sent.eq(true).or(job::succeeded.eq(true)))
+                        .filter(
+                            job::late_alert_sent
                                 .eq(false)
-                                .and(running_late_condition.or(finished_late_condition)))
-                            .or(job::error_alert_sent
+                                .or(job::late_alert_resolved.eq(true)),
+                        )
+                        .filter(
+                            job::error_alert_sent
                                 .eq(false)
-                                .and(job::end_time.is_not_null())
-                                .and(job::succeeded.eq(false))),
+                                .or(job::error_alert_resolved.eq(true)),
                         )
-                        .select(MonitorData::as_select())
-                        .distinct_on(monitor::monitor_id)
+                        .filter(not(alerted.and(followed_by_pending_recovery)))
+                        .filter(not(pending_recovery))
+                        .order(job::start_time.asc())
+                        .limit(i64::from(batch_size))
                         .load(conn)
                         .await?;
 
-                    let job_datas = JobData::belonging_to(&monitor_datas)
-                        .select(JobData::as_select())
-                        .order(job::start_time.desc())
-                        .load(conn)
+                    diesel::delete(job::table.filter(job::job_id.eq_any(&job_ids)))
+                        .execute(conn)
+                        .await
+                })
+            })
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> GetMetrics for MonitorRepository<'a> {
+    async fn get_metrics(&mut self) -> Result<Vec<MonitorMetrics>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let (monitors, last_starts, last_finishes, in_progress) = connection
+            .transaction::<_, DieselError, _>(|conn| {
+                Box::pin(async move {
+                    let monitors = monitor::table
+                        .select((
+                            monitor::monitor_id,
+                            monitor::tenant,
+                            monitor::name,
+                            monitor::jobs_started,
+                            monitor::jobs_succeeded,
+                            monitor::jobs_failed,
+                            monitor::jobs_late,
+                        ))
+                        .order(monitor::monitor_id)
+                        .load::<(Uuid, String, String, i64, i64, i64, i64)>(conn)
+                        .await?;
+
+                    let last_starts = job::table
+                        .select((job::monitor_id, job::start_time))
+                        .distinct_on(job::monitor_id)
+                        .order((job::monitor_id, job::start_time.desc()))
+                        .load::<(Uuid, NaiveDateTime)>(conn)
+                        .await?;
+
+                    let last_finishes = job::table
+                        .filter(job::end_time.is_not_null())
+                        .select((
+                            job::monitor_id,
+                            job::start_time,
+                            job::end_time.assume_not_null(),
+                        ))
+                        .distinct_on(job::monitor_id)
+                        .order((job::monitor_id, job::end_time.desc()))
+                        .load::<(Uuid, NaiveDateTime, NaiveDateTime)>(conn)
+                        .await?;
+
+                    let in_progress = job::table
+                        .filter(job::end_time.is_null())
+                        .group_by(job::monitor_id)
+                        .select((job::monitor_id, count_star()))
+                        .load::<(Uuid, i64)>(conn)
                         .await?;
 
-                    Ok((monitor_datas, job_datas))
+                    Ok((monitors, last_starts, last_finishes, in_progress))
                 })
             })
             .await
             .map_err(|err| Error::RepositoryError(err.to_string()))?;
 
-        Ok(job_datas
-            .grouped_by(&monitor_datas)
+        let last_starts: HashMap<Uuid, NaiveDateTime> = last_starts.into_iter().collect();
+        let last_finishes: HashMap<Uuid, (NaiveDateTime, NaiveDateTime)> = last_finishes
             .into_iter()
-            .zip(monitor_datas)
-            .map(|(job_datas, monitor_data)| self.db_to_monitor(monitor_data, job_datas))
-            .collect::<Result<Vec<Monitor>, Error>>()?)
+            .map(|(monitor_id, start_time, end_time)| (monitor_id, (start_time, end_time)))
+            .collect();
+        let in_progress: HashMap<Uuid, i64> = in_progress.into_iter().collect();
+
+        Ok(monitors
+            .into_iter()
+            .map(
+                |(monitor_id, tenant, name, started, succeeded, failed, late)| {
+                    let last_finished = last_finishes.get(&monitor_id);
+                    MonitorMetrics {
+                        monitor_id,
+                        tenant,
+                        name,
+                        last_started: last_starts.get(&monitor_id).copied(),
+                        last_finished: last_finished.map(|(_, end_time)| *end_time),
+                        last_duration: last_finished
+                            .map(|(start_time, end_time)| (*end_time - *start_time).num_seconds()),
+                        jobs_started: started,
+                        jobs_succeeded: succeeded,
+                        jobs_failed: failed,
+                        jobs_late: late,
+                        jobs_in_progress: in_progress.get(&monitor_id).copied().unwrap_or(0),
+                    }
+                },
+            )
+            .collect())
+    }
+}
+
+/// The number of a Monitor's Jobs that have started, and that have finished in each way, since
+/// the Monitor was retrieved. These are kept as running totals on the Monitor, so that they
+/// survive Jobs being pruned.
+#[derive(Debug, Default, PartialEq)]
+struct JobCounts {
+    started: i64,
+    succeeded: i64,
+    failed: i64,
+    late: i64,
+}
+
+impl JobCounts {
+    fn new(cached_jobs: &[JobData], job_datas: &[JobData]) -> Self {
+        let mut counts = Self::default();
+        for job_data in job_datas {
+            let cached_job = cached_jobs
+                .iter()
+                .find(|cached_job| cached_job.job_id == job_data.job_id);
+            if cached_job.is_none() {
+                counts.started += 1;
+            }
+
+            let already_finished =
+                cached_job.is_some_and(|cached_job| cached_job.end_time.is_some());
+            if let (Some(end_time), false) = (job_data.end_time, already_finished) {
+                if job_data.succeeded == Some(true) {
+                    counts.succeeded += 1;
+                } else {
+                    counts.failed += 1;
+                }
+                if end_time > job_data.max_end_time {
+                    counts.late += 1;
+                }
+            }
+        }
+        counts
+    }
+}
+
+/// Build a query for a Monitor's Jobs that match the filters of a `JobQuery`.
+fn filtered_jobs(monitor_id: Uuid, query: &JobQuery) -> job::BoxedQuery<'static, Pg> {
+    let mut jobs = job::table
+        .filter(job::monitor_id.eq(monitor_id))
+        .into_boxed();
+
+    if let Some(status) = query.status {
+        jobs = match status {
+            JobStatus::Succeeded => jobs.filter(job::succeeded.eq(true)),
+            JobStatus::Failed => jobs.filter(job::succeeded.eq(false)),
+            JobStatus::Late => jobs.filter(
+                (job::end_time.is_null().and(now.gt(job::max_end_time))).or(job::end_time
+                    .is_not_null()
+                    .and(job::end_time.assume_not_null().gt(job::max_end_time))),
+            ),
+            JobStatus::InProgress => jobs.filter(job::end_time.is_null()),
+        };
+    }
+    if let Some(started_after) = query.started_after {
+        jobs = jobs.filter(job::start_time.ge(started_after));
     }
+    if let Some(started_before) = query.started_before {
+        jobs = jobs.filter(job::start_time.lt(started_before));
+    }
+
+    jobs
 }
 
 #[async_trait]
@@ -105,7 +738,8 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
     async fn get(&mut self, monitor_id: Uuid, tenant: &str) -> Result<Option<Monitor>, Error> {
         let mut connection = get_connection(self.pool).await?;
         let result = connection
-            .transaction::<Option<(MonitorData, Vec<JobData>)>, DieselError, _>(|conn| {
+            .transaction::<Option<(MonitorData, Vec<JobData>, Vec<MonitorLabelData>)>, DieselError, _>(
+                |conn| {
                 Box::pin(async move {
                     let monitor_data = monitor::table
                         .select(MonitorData::as_select())
@@ -124,7 +758,12 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
                             .order(job::start_time.desc())
                             .load(conn)
                             .await?;
-                        Some((monitor, jobs))
+                        let labels = MonitorLabelData::belonging_to(&monitor)
+                            .select(MonitorLabelData::as_select())
+                            .order(monitor_label::key)
+                            .load(conn)
+                            .await?;
+                        Some((monitor, jobs, labels))
                     } else {
                         None
                     })
@@ -135,43 +774,56 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
 
         Ok(match result {
             None => None,
-            Some((monitor_data, job_datas)) => Some(self.db_to_monitor(monitor_data, job_datas)?),
+            Some((monitor_data, job_datas, label_datas)) => {
+                Some(self.db_to_monitor(monitor_data, job_datas, label_datas)?)
+            }
         })
     }
 
     async fn all(&mut self, tenant: &str) -> Result<Vec<Monitor>, Error> {
         let mut connection = get_connection(self.pool).await?;
-        let (monitor_datas, job_datas) = connection
-            .transaction::<(Vec<MonitorData>, Vec<JobData>), DieselError, _>(|conn| {
-                Box::pin(async move {
-                    let all_monitor_data = monitor::dsl::monitor
-                        .select(MonitorData::as_select())
-                        .filter(monitor::tenant.eq(tenant))
-                        .load(conn)
-                        .await?;
+        let (monitor_datas, job_datas, label_datas) = connection
+            .transaction::<(Vec<MonitorData>, Vec<JobData>, Vec<MonitorLabelData>), DieselError, _>(
+                |conn| {
+                    Box::pin(async move {
+                        let all_monitor_data = monitor::dsl::monitor
+                            .select(MonitorData::as_select())
+                            .filter(monitor::tenant.eq(tenant))
+                            .load(conn)
+                            .await?;
 
-                    let jobs = JobData::belonging_to(&all_monitor_data)
-                        .select(JobData::as_select())
-                        .order(job::start_time.desc())
-                        .load(conn)
-                        .await?;
+                        let jobs = JobData::belonging_to(&all_monitor_data)
+                            .select(JobData::as_select())
+                            .order(job::start_time.desc())
+                            .load(conn)
+                            .await?;
 
-                    Ok((all_monitor_data, jobs))
-                })
-            })
+                        let label_datas = MonitorLabelData::belonging_to(&all_monitor_data)
+                            .select(MonitorLabelData::as_select())
+                            .order(monitor_label::key)
+                            .load(conn)
+                            .await?;
+
+                        Ok((all_monitor_data, jobs, label_datas))
+                    })
+                },
+            )
             .await
             .map_err(|err| Error::RepositoryError(err.to_string()))?;
 
-        Ok(job_datas
-            .grouped_by(&monitor_datas)
-            .into_iter()
-            .zip(monitor_datas)
-            .map(|(job_datas, monitor_data)| self.db_to_monitor(monitor_data, job_datas))
-            .collect::<Result<Vec<Monitor>, Error>>()?)
+        self.db_to_monitors(monitor_datas, job_datas, label_datas)
     }
 
     async fn save(&mut self, monitor: &Monitor) -> Result<(), Error> {
-        let (monitor_data, job_datas) = <(MonitorData, Vec<JobData>)>::from(monitor);
+        let (monitor_data, job_datas, label_datas) =
+            <(MonitorData, Vec<JobData>, Vec<MonitorLabelData>)>::from(monitor);
+        let counts = JobCounts::new(
+            self.data
+                .get(&monitor.monitor_id)
+                .map(|(_, cached_jobs, _)| cached_jobs.as_slice())
+                .unwrap_or_default(),
+            &job_datas,
+        );
 
         let mut connection = get_connection(self.pool).await?;
         connection
@@ -185,8 +837,11 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
 
                         let job_ids = &cached.1.iter().map(|j| j.job_id).collect::<Vec<Uuid>>();
                         for j in &job_datas {
-                            // TODO: Handle jobs being deleted. Don't need to worry about this for
-                            // now since there isn't anything that deletes jobs within monitors.
+                            // Jobs are only ever deleted by pruning, which does so directly rather
+                            // than through here. Pruning can race with saving a Monitor that was
+                            // loaded beforehand, so Jobs that were loaded must only ever be
+                            // updated: that affects no rows for a Job that's since been pruned,
+                            // whereas inserting or upserting it would write it back.
                             if job_ids.contains(&j.job_id) {
                                 diesel::update(j).set(j).execute(conn).await?;
                             } else {
@@ -196,6 +851,20 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
                                     .await?;
                             }
                         }
+
+                        if cached.2 != label_datas {
+                            diesel::delete(
+                                monitor_label::table
+                                    .filter(monitor_label::monitor_id.eq(monitor.monitor_id)),
+                            )
+                            .execute(conn)
+                            .await?;
+
+                            diesel::insert_into(monitor_label::table)
+                                .values(&label_datas)
+                                .execute(conn)
+                                .await?;
+                        }
                     } else {
                         diesel::insert_into(monitor::table)
                             .values(&monitor_data)
@@ -206,10 +875,28 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
                             .values(&job_datas)
                             .execute(conn)
                             .await?;
+
+                        diesel::insert_into(monitor_label::table)
+                            .values(&label_datas)
+                            .execute(conn)
+                            .await?;
+                    }
+
+                    if counts != JobCounts::default() {
+                        diesel::update(monitor::table.find(monitor.monitor_id))
+                            .set((
+                                monitor::jobs_started.eq(monitor::jobs_started + counts.started),
+                                monitor::jobs_succeeded
+                                    .eq(monitor::jobs_succeeded + counts.succeeded),
+                                monitor::jobs_failed.eq(monitor::jobs_failed + counts.failed),
+                                monitor::jobs_late.eq(monitor::jobs_late + counts.late),
+                            ))
+                            .execute(conn)
+                            .await?;
                     }
 
                     self.data
-                        .insert(monitor.monitor_id, (monitor_data, job_datas));
+                        .insert(monitor.monitor_id, (monitor_data, job_datas, label_datas));
                     Ok(())
                 })
             })
@@ -218,7 +905,8 @@ impl<'a> Repository<Monitor> for MonitorRepository<'a> {
     }
 
     async fn delete(&mut self, monitor: &Monitor) -> Result<(), Error> {
-        let (monitor_data, _) = <(MonitorData, Vec<JobData>)>::from(monitor);
+        let (monitor_data, _, _) =
+            <(MonitorData, Vec<JobData>, Vec<MonitorLabelData>)>::from(monitor);
 
         let mut connection = get_connection(self.pool).await?;
         diesel::delete(&monitor_data)
@@ -0,0 +1,41 @@
+pub mod repo;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::models::{LifecycleEvent, WebhookDelivery, WebhookSubscription};
+use crate::errors::Error;
+
+pub use repo::WebhookDeliveryRepository;
+
+/// Queue lifecycle events for delivery to webhook subscriptions, and track how their delivery
+/// goes.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DeliveryQueue {
+    /// Queue an event for delivery to each of its tenant's active webhook subscriptions that are
+    /// subscribed to its type.
+    async fn enqueue(&mut self, event: &LifecycleEvent) -> Result<(), Error>;
+
+    /// Get up to `limit` pending deliveries that are due, across all tenants, along with the
+    /// webhook subscriptions that they're for. Deliveries for inactive webhook subscriptions are
+    /// left until the subscription is reactivated.
+    async fn get_due(
+        &mut self,
+        limit: u32,
+    ) -> Result<Vec<(WebhookSubscription, WebhookDelivery)>, Error>;
+
+    /// Save the status of a delivery, along with any attempts that haven't been saved yet.
+    async fn save(&mut self, delivery: &WebhookDelivery) -> Result<(), Error>;
+
+    /// Get up to `limit` of a webhook subscription's most recent deliveries, newest first.
+    async fn get_for_subscription(
+        &mut self,
+        subscription_id: Uuid,
+        tenant: &str,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, Error>;
+}
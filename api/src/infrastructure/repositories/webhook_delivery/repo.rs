@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use diesel_async::AsyncConnection;
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::domain::models::{DeliveryStatus, LifecycleEvent, WebhookDelivery, WebhookSubscription};
+use crate::errors::Error;
+use crate::infrastructure::database::{get_connection, DbPool};
+use crate::infrastructure::db_schema::{
+    webhook_delivery, webhook_delivery_attempt, webhook_subscription,
+};
+use crate::infrastructure::models::webhook_delivery::{DeliveryAttemptData, WebhookDeliveryData};
+use crate::infrastructure::models::webhook_subscription::WebhookSubscriptionData;
+
+use super::DeliveryQueue;
+
+pub struct WebhookDeliveryRepository<'a> {
+    pool: &'a DbPool,
+}
+
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> WebhookDeliveryRepository<'a> {
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> DeliveryQueue for WebhookDeliveryRepository<'a> {
+    async fn enqueue(&mut self, event: &LifecycleEvent) -> Result<(), Error> {
+        let mut connection = get_connection(self.pool).await?;
+        connection
+            .transaction::<(), DieselError, _>(|conn| {
+                Box::pin(async move {
+                    let subscription_datas = webhook_subscription::table
+                        .select(WebhookSubscriptionData::as_select())
+                        .filter(
+                            webhook_subscription::tenant
+                                .eq(&event.tenant)
+                                .and(webhook_subscription::active.eq(true))
+                                .and(
+                                    webhook_subscription::event_types
+                                        .contains(vec![event.event_type.as_str().to_owned()]),
+                                ),
+                        )
+                        .load(conn)
+                        .await?;
+
+                    let delivery_datas = subscription_datas
+                        .iter()
+                        .map(|subscription_data| {
+                            let subscription = WebhookSubscription::from(subscription_data);
+                            WebhookDeliveryData::from(&WebhookDelivery::new(&subscription, event))
+                        })
+                        .collect::<Vec<WebhookDeliveryData>>();
+
+                    if !delivery_datas.is_empty() {
+                        diesel::insert_into(webhook_delivery::table)
+                            .values(&delivery_datas)
+                            .execute(conn)
+                            .await?;
+                    }
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))
+    }
+
+    async fn get_due(
+        &mut self,
+        limit: u32,
+    ) -> Result<Vec<(WebhookSubscription, WebhookDelivery)>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let (due, attempt_datas): (
+            Vec<(WebhookSubscriptionData, WebhookDeliveryData)>,
+            Vec<DeliveryAttemptData>,
+        ) = connection
+            .transaction::<_, DieselError, _>(|conn| {
+                Box::pin(async move {
+                    let due = webhook_delivery::table
+                        .inner_join(webhook_subscription::table)
+                        .filter(
+                            webhook_delivery::status
+                                .eq(DeliveryStatus::Pending.as_str())
+                                .and(webhook_delivery::next_attempt_at.le(Utc::now().naive_utc()))
+                                .and(webhook_subscription::active.eq(true)),
+                        )
+                        .order(webhook_delivery::next_attempt_at.asc())
+                        .limit(i64::from(limit))
+                        .select((
+                            WebhookSubscriptionData::as_select(),
+                            WebhookDeliveryData::as_select(),
+                        ))
+                        .load::<(WebhookSubscriptionData, WebhookDeliveryData)>(conn)
+                        .await?;
+
+                    let delivery_datas = due
+                        .iter()
+                        .map(|(_, delivery_data)| delivery_data.clone())
+                        .collect::<Vec<WebhookDeliveryData>>();
+                    let attempt_datas = DeliveryAttemptData::belonging_to(&delivery_datas)
+                        .select(DeliveryAttemptData::as_select())
+                        .load(conn)
+                        .await?;
+
+                    Ok((due, attempt_datas))
+                })
+            })
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        let (subscription_datas, delivery_datas): (Vec<_>, Vec<_>) = due.into_iter().unzip();
+        attempt_datas
+            .grouped_by(&delivery_datas)
+            .iter()
+            .zip(subscription_datas.iter().zip(&delivery_datas))
+            .map(|(attempt_datas, (subscription_data, delivery_data))| {
+                Ok((
+                    WebhookSubscription::from(subscription_data),
+                    delivery_data.to_model(attempt_datas)?,
+                ))
+            })
+            .collect()
+    }
+
+    async fn save(&mut self, delivery: &WebhookDelivery) -> Result<(), Error> {
+        let mut connection = get_connection(self.pool).await?;
+        connection
+            .transaction::<(), DieselError, _>(|conn| {
+                Box::pin(async move {
+                    let delivery_data = WebhookDeliveryData::from(delivery);
+                    diesel::update(&delivery_data)
+                        .set(&delivery_data)
+                        .execute(conn)
+                        .await?;
+
+                    // Attempts are never changed once they're made, so only new ones need saving.
+                    let attempt_datas = delivery
+                        .attempts
+                        .iter()
+                        .map(|attempt| {
+                            DeliveryAttemptData::from_model(delivery.delivery_id, attempt)
+                        })
+                        .collect::<Vec<DeliveryAttemptData>>();
+                    diesel::insert_into(webhook_delivery_attempt::table)
+                        .values(&attempt_datas)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .await?;
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))
+    }
+
+    async fn get_for_subscription(
+        &mut self,
+        subscription_id: Uuid,
+        tenant: &str,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let (delivery_datas, attempt_datas) = connection
+            .transaction::<(Vec<WebhookDeliveryData>, Vec<DeliveryAttemptData>), DieselError, _>(
+                |conn| {
+                    Box::pin(async move {
+                        let delivery_datas = webhook_delivery::table
+                            .filter(
+                                webhook_delivery::subscription_id
+                                    .eq(subscription_id)
+                                    .and(webhook_delivery::tenant.eq(tenant)),
+                            )
+                            .order((
+                                webhook_delivery::created_at.desc(),
+                                webhook_delivery::delivery_id.desc(),
+                            ))
+                            .limit(i64::from(limit))
+                            .select(WebhookDeliveryData::as_select())
+                            .load(conn)
+                            .await?;
+
+                        let attempt_datas = DeliveryAttemptData::belonging_to(&delivery_datas)
+                            .select(DeliveryAttemptData::as_select())
+                            .load(conn)
+                            .await?;
+
+                        Ok((delivery_datas, attempt_datas))
+                    })
+                },
+            )
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        attempt_datas
+            .grouped_by(&delivery_datas)
+            .iter()
+            .zip(&delivery_datas)
+            .map(|(attempt_datas, delivery_data)| delivery_data.to_model(attempt_datas))
+            .collect()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod repo;
+
+pub use repo::WebhookSubscriptionRepository;
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use diesel_async::AsyncConnection;
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::domain::models::WebhookSubscription;
+use crate::errors::Error;
+use crate::infrastructure::database::{get_connection, DbPool};
+use crate::infrastructure::db_schema::webhook_subscription;
+use crate::infrastructure::models::webhook_subscription::WebhookSubscriptionData;
+use crate::infrastructure::repositories::Repository;
+
+pub struct WebhookSubscriptionRepository<'a> {
+    pool: &'a DbPool,
+    data: HashMap<Uuid, WebhookSubscriptionData>,
+}
+
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> WebhookSubscriptionRepository<'a> {
+    pub fn new(pool: &'a DbPool) -> Self {
+        Self {
+            pool,
+            data: HashMap::new(),
+        }
+    }
+
+    fn db_to_subscription(&mut self, data: &WebhookSubscriptionData) -> WebhookSubscription {
+        let subscription = WebhookSubscription::from(data);
+        self.data.insert(data.subscription_id, data.clone());
+        subscription
+    }
+}
+
+#[async_trait]
+#[allow(clippy::needless_lifetimes)] // This is needed for the lifetime of the pool
+impl<'a> Repository<WebhookSubscription> for WebhookSubscriptionRepository<'a> {
+    async fn get(
+        &mut self,
+        subscription_id: Uuid,
+        tenant: &str,
+    ) -> Result<Option<WebhookSubscription>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let subscription_data = webhook_subscription::table
+            .select(WebhookSubscriptionData::as_select())
+            .filter(
+                webhook_subscription::subscription_id
+                    .eq(subscription_id)
+                    .and(webhook_subscription::tenant.eq(tenant)),
+            )
+            .first(&mut connection)
+            .await
+            .optional()
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        Ok(subscription_data.map(|data| self.db_to_subscription(&data)))
+    }
+
+    async fn all(&mut self, tenant: &str) -> Result<Vec<WebhookSubscription>, Error> {
+        let mut connection = get_connection(self.pool).await?;
+        let subscription_datas = webhook_subscription::table
+            .select(WebhookSubscriptionData::as_select())
+            .filter(webhook_subscription::tenant.eq(tenant))
+            .order(webhook_subscription::name)
+            .load(&mut connection)
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        Ok(subscription_datas
+            .iter()
+            .map(|data| self.db_to_subscription(data))
+            .collect())
+    }
+
+    async fn save(&mut self, subscription: &WebhookSubscription) -> Result<(), Error> {
+        let mut connection = get_connection(self.pool).await?;
+        connection
+            .transaction::<(), DieselError, _>(|conn| {
+                Box::pin(async move {
+                    let subscription_data = WebhookSubscriptionData::from(subscription);
+                    if self.data.contains_key(&subscription_data.subscription_id) {
+                        diesel::update(&subscription_data)
+                            .set(&subscription_data)
+                            .execute(conn)
+                            .await?;
+                    } else {
+                        diesel::insert_into(webhook_subscription::table)
+                            .values(&subscription_data)
+                            .execute(conn)
+                            .await?;
+                    }
+
+                    self.db_to_subscription(&subscription_data);
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))
+    }
+
+    async fn delete(&mut self, subscription: &WebhookSubscription) -> Result<(), Error> {
+        let subscription_data = WebhookSubscriptionData::from(subscription);
+        let mut connection = get_connection(self.pool).await?;
+        diesel::delete(&subscription_data)
+            .execute(&mut connection)
+            .await
+            .map_err(|err| Error::RepositoryError(err.to_string()))?;
+
+        self.data.remove(&subscription.subscription_id);
+        Ok(())
+    }
+}
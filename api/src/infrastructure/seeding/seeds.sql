@@ -16,6 +16,17 @@ VALUES
     ('309a68f1-d6a2-4312-8012-49c1b9b9af25', 'cron-mon', 'gen-manifests | send-manifest', 300,   120),
     ('0798c530-34a4-4452-b2dc-f8140fd498d5', 'cron-mon', 'bill-and-invoice',              10800, 1800);
 
+-- Monitor labels.
+INSERT INTO monitor_label
+    (monitor_id, key, value)
+VALUES
+    ('c1bf0515-df39-448b-aa95-686360a33b36', 'team', 'platform'),
+    ('c1bf0515-df39-448b-aa95-686360a33b36', 'env',  'prod'),
+    ('f0b291fe-bd41-4787-bc2d-1329903f7a6a', 'team', 'payments'),
+    ('f0b291fe-bd41-4787-bc2d-1329903f7a6a', 'env',  'prod'),
+    ('0798c530-34a4-4452-b2dc-f8140fd498d5', 'team', 'payments'),
+    ('0798c530-34a4-4452-b2dc-f8140fd498d5', 'env',  'staging');
+
 -- Jobs.
 INSERT INTO job
     (job_id, monitor_id, start_time, max_end_time, end_time, succeeded, "output", late_alert_sent, error_alert_sent)
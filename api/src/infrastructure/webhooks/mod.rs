@@ -0,0 +1,23 @@
+pub mod sender;
+
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::domain::models::{DeliveryAttempt, WebhookDelivery, WebhookSubscription};
+
+pub use sender::WebhookSender;
+
+/// Send lifecycle events to webhook subscriptions.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SendWebhook {
+    /// Make an attempt to send a delivery's event to its webhook subscription. This never fails
+    /// outright - any failure is described by the attempt instead, so that it can be retried.
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> DeliveryAttempt;
+}
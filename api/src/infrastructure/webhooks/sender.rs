@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Client;
+
+use crate::domain::models::{DeliveryAttempt, WebhookDelivery, WebhookSubscription};
+use crate::infrastructure::notify::webhook::integration::{sign, SIGNATURE_HEADER};
+
+use super::SendWebhook;
+
+/// The header containing the type of the event being sent.
+pub const EVENT_HEADER: &str = "X-CronMon-Event";
+
+/// The header containing the ID of the delivery, which is the same across retries so that
+/// receivers can de-duplicate them.
+pub const DELIVERY_HEADER: &str = "X-CronMon-Delivery";
+
+/// How long to wait for the receiving server before giving up on an attempt.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends lifecycle events to webhook subscriptions as JSON `POST` requests, signed in the same way
+/// as webhook alerts when the subscription has a secret.
+///
+/// Any non-2xx response from the receiving server is treated as a failed attempt.
+pub struct WebhookSender {
+    client: Client,
+}
+
+impl WebhookSender {
+    pub fn new() -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SendWebhook for WebhookSender {
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> DeliveryAttempt {
+        let body = match serde_json::to_vec(&delivery.payload) {
+            Ok(body) => body,
+            Err(error) => return DeliveryAttempt::failure(None, error.to_string()),
+        };
+
+        let mut request = self
+            .client
+            .post(&subscription.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event_type.as_str())
+            .header(DELIVERY_HEADER, delivery.delivery_id.to_string());
+        if let Some(secret) = &subscription.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+        }
+
+        match request.body(body).send().await {
+            Ok(response) if response.status().is_success() => {
+                DeliveryAttempt::success(response.status().as_u16())
+            }
+            Ok(response) => DeliveryAttempt::failure(
+                Some(response.status().as_u16()),
+                format!("Received HTTP {}", response.status()),
+            ),
+            Err(error) => DeliveryAttempt::failure(None, error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use test_utils::gen_uuid;
+
+    use crate::domain::models::{
+        AlertThreshold, ConcurrencyPolicy, EventType, Labels, LifecycleEvent, Monitor, MonitorKind,
+    };
+
+    use super::*;
+
+    fn subscription(url: &str, secret: Option<&str>) -> WebhookSubscription {
+        WebhookSubscription::new(
+            "Pipeline".to_owned(),
+            "tenant".to_owned(),
+            url.to_owned(),
+            secret.map(str::to_owned),
+            vec![EventType::MonitorCreated],
+            true,
+        )
+    }
+
+    fn delivery(subscription: &WebhookSubscription) -> WebhookDelivery {
+        let monitor = Monitor {
+            monitor_id: gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            tenant: "tenant".to_owned(),
+            name: "foo".to_owned(),
+            expected_duration: 300,
+            grace_duration: 100,
+            kind: MonitorKind::Job,
+            schedule: None,
+            missed_start_cutoff: None,
+            alert_threshold: AlertThreshold::default(),
+            retention: None,
+            labels: Labels::default(),
+            paused: false,
+            resume_at: None,
+            maintenance_windows: vec![],
+            auto_tune: None,
+            anomaly_detection: None,
+            concurrency_policy: ConcurrencyPolicy::Allow,
+            jobs: vec![],
+        };
+        WebhookDelivery::new(subscription, &LifecycleEvent::monitor_created(&monitor))
+    }
+
+    #[tokio::test]
+    async fn test_send() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/events"))
+            .and(header("Content-Type", "application/json"))
+            .and(header(EVENT_HEADER, "monitor_created"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let subscription = subscription(&format!("{}/events", server.uri()), None);
+        let delivery = delivery(&subscription);
+        let attempt = WebhookSender::new().send(&subscription, &delivery).await;
+
+        assert!(attempt.succeeded);
+        assert_eq!(attempt.response_status, Some(204));
+        assert_eq!(attempt.error, None);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0]
+                .headers
+                .get(DELIVERY_HEADER)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            delivery.delivery_id.to_string()
+        );
+        assert!(requests[0].headers.get(SIGNATURE_HEADER).is_none());
+
+        let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body, delivery.payload);
+        assert_eq!(body["data"]["monitor"]["name"], json!("foo"));
+    }
+
+    #[tokio::test]
+    async fn test_signs_requests_when_secret_is_set() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let subscription = subscription(&server.uri(), Some("top-secret"));
+        let attempt = WebhookSender::new()
+            .send(&subscription, &delivery(&subscription))
+            .await;
+        assert!(attempt.succeeded);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0]
+                .headers
+                .get(SIGNATURE_HEADER)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            sign("top-secret", &requests[0].body)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsuccessful_response_is_a_failed_attempt() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let subscription = subscription(&server.uri(), None);
+        let attempt = WebhookSender::new()
+            .send(&subscription, &delivery(&subscription))
+            .await;
+
+        assert!(!attempt.succeeded);
+        assert_eq!(attempt.response_status, Some(503));
+        assert_eq!(
+            attempt.error,
+            Some("Received HTTP 503 Service Unavailable".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_server_is_a_failed_attempt() {
+        // Nothing listens on port 9 (discard) locally, so the connection is refused.
+        let subscription = subscription("http://127.0.0.1:9/events", None);
+        let attempt = WebhookSender::new()
+            .send(&subscription, &delivery(&subscription))
+            .await;
+
+        assert!(!attempt.succeeded);
+        assert_eq!(attempt.response_status, None);
+        assert!(attempt.error.is_some());
+    }
+}
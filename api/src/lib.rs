@@ -2,19 +2,25 @@ pub mod application;
 pub mod domain;
 pub mod errors;
 pub mod infrastructure;
+pub mod runner;
 
-use std::env;
 use std::time::Duration;
 
 use moka::sync::Cache;
 use rocket::fs::FileServer;
 use rocket::{routes, Build, Rocket};
 
-use crate::application::routes::{alert_config, api_keys, health, jobs, monitors};
-use crate::infrastructure::auth::jwt::{Jwk, JwtAuthService};
+use crate::application::routes::{
+    activity, alert_config, api_keys, audit_log, health, jobs, metrics, monitors,
+    webhook_subscriptions,
+};
+use crate::infrastructure::auth::jwt::{Jwk, JwtAuthService, OidcConfig};
 use crate::infrastructure::auth::JwtAuth;
 use crate::infrastructure::database::create_connection_pool;
-use crate::infrastructure::middleware::fairings::{cors::CORS, default_json::DefaultJSON};
+use crate::infrastructure::middleware::fairings::{
+    activity::ActivityFeed, cors::CORS, default_json::DefaultJSON,
+};
+use crate::infrastructure::middleware::guards::scrape_token::ScrapeToken;
 
 #[rocket::launch]
 pub fn rocket() -> Rocket<Build> {
@@ -23,10 +29,12 @@ pub fn rocket() -> Rocket<Build> {
     rocket::build()
         .attach(CORS)
         .attach(DefaultJSON)
+        .attach(ActivityFeed)
         .manage(db_pool)
+        .manage(ScrapeToken::from_env())
         .manage(Box::new(JwtAuthService::new(
-            env::var("KEYCLOAK_CERTS_URL").expect("'KEYCLOAK_CERTS_URL' missing from environment"),
-            Cache::<String, Jwk>::builder()
+            OidcConfig::from_env(),
+            Cache::<Option<String>, Jwk>::builder()
                 .max_capacity(100)
                 // Entries valid for 24 hours.
                 .time_to_live(Duration::from_secs(86400))
@@ -39,14 +47,23 @@ pub fn rocket() -> Rocket<Build> {
                 monitors::list_monitors,
                 monitors::create_monitor,
                 monitors::get_monitor,
+                monitors::get_monitor_stats,
+                monitors::get_job_metrics,
                 monitors::delete_monitor,
                 monitors::update_monitor,
+                monitors::pause_monitor,
+                monitors::resume_monitor,
+                jobs::list_jobs,
                 jobs::get_job,
                 jobs::start_job,
                 jobs::finish_job,
+                jobs::record_heartbeat,
                 api_keys::list_api_keys,
                 api_keys::generate_key,
+                api_keys::rotate_key,
                 api_keys::revoke_key,
+                audit_log::list_audit_events,
+                activity::stream_activity,
                 alert_config::associate_alert_configs,
                 alert_config::disassociate_alert_config,
                 alert_config::list_alert_configs,
@@ -56,7 +73,14 @@ pub fn rocket() -> Rocket<Build> {
                 alert_config::delete_alert_config,
                 alert_config::get_alert_configs_for_monitor,
                 alert_config::test_alert_config,
+                webhook_subscriptions::list_subscriptions,
+                webhook_subscriptions::create_subscription,
+                webhook_subscriptions::get_subscription,
+                webhook_subscriptions::update_subscription,
+                webhook_subscriptions::delete_subscription,
+                webhook_subscriptions::list_deliveries,
             ],
         )
         .mount("/api/v1/docs", FileServer::from("./docs"))
+        .mount("/", routes![metrics::metrics])
 }
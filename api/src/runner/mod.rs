@@ -0,0 +1,477 @@
+use std::collections::VecDeque;
+use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+
+use cron_mon_client::types::{FinishJobInfo, JobMetadataData};
+use cron_mon_client::{Client, Error};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::process::Command;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// The exit code used when the command couldn't be run at all, as used by shells when a command
+/// can't be found.
+const FAILED_TO_RUN_EXIT_CODE: i32 = 127;
+
+/// The exit code used when the command wasn't run because the Monitor rejected the Job, since
+/// another was still in progress. This is `EX_TEMPFAIL`, as the command can be run again later.
+const REJECTED_EXIT_CODE: i32 = 75;
+
+/// Run a command as a Job of a Monitor, returning the command's exit code.
+///
+/// A Job is started before the command is run, and finished once it exits, having succeeded if
+/// the command exited successfully and with the last `output_limit` bytes of the command's output
+/// (which is passed through as it's produced). If the Job can't be started - for example, if
+/// CronMon is unreachable - the command is still run, so that CronMon being down never stops jobs
+/// from running. The only exception is when the Monitor rejects the Job because another is still
+/// in progress, in which case the command isn't run at all.
+///
+/// The command and its exit code are recorded against the Job, along with any other `metadata`
+/// given, such as the host that it ran on.
+pub async fn run(
+    client: &Client,
+    monitor_id: Uuid,
+    command: &[String],
+    output_limit: usize,
+    metadata: JobMetadataData,
+) -> i32 {
+    let job_id = match client.start_job(monitor_id).await {
+        Ok(job_id) => {
+            info!(
+                monitor_id = monitor_id.to_string(),
+                job_id = job_id.to_string(),
+                "Started Job"
+            );
+            Some(job_id)
+        }
+        Err(Error::ApiError { status: 409, .. }) => {
+            warn!(
+                monitor_id = monitor_id.to_string(),
+                "Monitor already has a Job in progress, not running command"
+            );
+            return REJECTED_EXIT_CODE;
+        }
+        Err(error) => {
+            warn!(
+                monitor_id = monitor_id.to_string(),
+                "Failed to start Job, running command without monitoring it: {:?}", error
+            );
+            None
+        }
+    };
+
+    let (succeeded, exit_code, output) = match run_command(command, output_limit).await {
+        Ok((status, output)) => (status.success(), exit_code(status), output),
+        Err(error) => {
+            error!("Failed to run command: {:?}", error);
+            (
+                false,
+                FAILED_TO_RUN_EXIT_CODE,
+                Some(format!("Failed to run command: {error}")),
+            )
+        }
+    };
+
+    if let Some(job_id) = job_id {
+        let info = FinishJobInfo {
+            succeeded,
+            output,
+            metadata: JobMetadataData {
+                exit_code: Some(exit_code),
+                command: Some(command.join(" ")),
+                ..metadata
+            },
+        };
+        match client.finish_job(monitor_id, job_id, &info).await {
+            Ok(_) => info!(
+                monitor_id = monitor_id.to_string(),
+                job_id = job_id.to_string(),
+                "Finished Job"
+            ),
+            Err(error) => warn!(
+                monitor_id = monitor_id.to_string(),
+                job_id = job_id.to_string(),
+                "Failed to finish Job: {:?}",
+                error
+            ),
+        }
+    }
+
+    exit_code
+}
+
+/// Run a command, passing its output through whilst capturing the last `output_limit` bytes of
+/// it.
+async fn run_command(
+    command: &[String],
+    output_limit: usize,
+) -> io::Result<(ExitStatus, Option<String>)> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No command given"))?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let output = Arc::new(Mutex::new(OutputBuffer::new(output_limit)));
+    let stdout = tokio::spawn(tee(
+        child.stdout.take().expect("stdout should be piped"),
+        tokio::io::stdout(),
+        output.clone(),
+    ));
+    let stderr = tokio::spawn(tee(
+        child.stderr.take().expect("stderr should be piped"),
+        tokio::io::stderr(),
+        output.clone(),
+    ));
+
+    let status = child.wait().await?;
+    // The command may have exited before all of its output has been read.
+    let _ = tokio::join!(stdout, stderr);
+
+    let output = output
+        .lock()
+        .expect("Output buffer lock poisoned")
+        .to_output();
+    Ok((status, output))
+}
+
+/// Pass output through from a reader to a writer, capturing it as it goes.
+async fn tee<R, W>(mut reader: R, mut writer: W, output: Arc<Mutex<OutputBuffer>>)
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut chunk = [0; 8192];
+    while let Ok(read) = reader.read(&mut chunk).await {
+        if read == 0 {
+            break;
+        }
+
+        output
+            .lock()
+            .expect("Output buffer lock poisoned")
+            .push(&chunk[..read]);
+
+        // Failing to pass the output through shouldn't stop it from being captured.
+        let _ = writer.write_all(&chunk[..read]).await;
+        let _ = writer.flush().await;
+    }
+}
+
+/// The exit code of a command, following the shell convention of `128 + signal` for commands
+/// killed by a signal.
+fn exit_code(status: ExitStatus) -> i32 {
+    status
+        .code()
+        .or_else(|| status.signal().map(|signal| 128 + signal))
+        .unwrap_or(1)
+}
+
+/// The most recent output of a command, up to a limit in bytes.
+struct OutputBuffer {
+    limit: usize,
+    bytes: VecDeque<u8>,
+    truncated: bool,
+}
+
+impl OutputBuffer {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            bytes: VecDeque::new(),
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        self.bytes.extend(chunk);
+        if self.bytes.len() > self.limit {
+            self.bytes.drain(..self.bytes.len() - self.limit);
+            self.truncated = true;
+        }
+    }
+
+    /// The captured output as a string, or `None` if there wasn't any output.
+    fn to_output(&self) -> Option<String> {
+        let mut bytes: Vec<u8> = self.bytes.iter().copied().collect();
+        if self.truncated {
+            // Truncating may have split a character, so skip any partial character at the start.
+            let partial = bytes
+                .iter()
+                .take_while(|byte| (**byte & 0b1100_0000) == 0b1000_0000)
+                .count();
+            bytes.drain(..partial);
+        }
+
+        if bytes.is_empty() {
+            None
+        } else {
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cron_mon_client::Auth;
+    use rstest::rstest;
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use test_utils::gen_uuid;
+
+    use super::*;
+
+    fn command(script: &str) -> Vec<String> {
+        vec!["sh".to_owned(), "-c".to_owned(), script.to_owned()]
+    }
+
+    fn finished_job() -> serde_json::Value {
+        json!({"data": {
+            "job_id": "01a92c6c-6803-409d-b675-022fff62575a",
+            "start_time": "2024-05-01T00:00:00",
+            "end_time": "2024-05-01T00:00:01",
+            "succeeded": true,
+            "output": null,
+            "duration": 1,
+            "in_progress": false,
+            "late": false
+        }})
+    }
+
+    async fn mock_start_job(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/monitors/41ebffb4-a188-48e9-8ec1-61380085cde3/jobs/start",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    json!({"data": {"job_id": "01a92c6c-6803-409d-b675-022fff62575a"}}),
+                ),
+            )
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[rstest]
+    #[case::success(
+        "echo 'Done'",
+        0,
+        json!({
+            "succeeded": true,
+            "output": "Done\n",
+            "exit_code": 0,
+            "command": "sh -c echo 'Done'"
+        })
+    )]
+    #[case::failure(
+        "echo 'Oh no' >&2; exit 3",
+        3,
+        json!({
+            "succeeded": false,
+            "output": "Oh no\n",
+            "exit_code": 3,
+            "command": "sh -c echo 'Oh no' >&2; exit 3"
+        })
+    )]
+    #[case::no_output(
+        "true",
+        0,
+        json!({"succeeded": true, "output": null, "exit_code": 0, "command": "sh -c true"})
+    )]
+    #[case::killed(
+        "kill -9 $$",
+        137,
+        json!({
+            "succeeded": false,
+            "output": null,
+            "exit_code": 137,
+            "command": "sh -c kill -9 $$"
+        })
+    )]
+    #[tokio::test]
+    async fn test_run(
+        #[case] script: &str,
+        #[case] expected_exit_code: i32,
+        #[case] expected_finish: serde_json::Value,
+    ) {
+        let server = MockServer::start().await;
+        mock_start_job(&server).await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/monitors/41ebffb4-a188-48e9-8ec1-61380085cde3/jobs/\
+                01a92c6c-6803-409d-b675-022fff62575a/finish",
+            ))
+            .and(body_json(expected_finish))
+            .respond_with(ResponseTemplate::new(200).set_body_json(finished_job()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new(&server.uri(), Auth::ApiKey("foo-key".to_owned()));
+
+        let exit_code = run(
+            &client,
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            &command(script),
+            1024,
+            JobMetadataData::default(),
+        )
+        .await;
+
+        assert_eq!(exit_code, expected_exit_code);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_metadata() {
+        let server = MockServer::start().await;
+        mock_start_job(&server).await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/monitors/41ebffb4-a188-48e9-8ec1-61380085cde3/jobs/\
+                01a92c6c-6803-409d-b675-022fff62575a/finish",
+            ))
+            .and(body_json(json!({
+                "succeeded": true,
+                "output": null,
+                "exit_code": 0,
+                "hostname": "worker-1",
+                "command": "sh -c true",
+                "version": "4f2a9c1"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(finished_job()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new(&server.uri(), Auth::ApiKey("foo-key".to_owned()));
+
+        let exit_code = run(
+            &client,
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            &command("true"),
+            1024,
+            JobMetadataData {
+                hostname: Some("worker-1".to_owned()),
+                version: Some("4f2a9c1".to_owned()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert_eq!(exit_code, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_command_that_cant_be_run() {
+        let server = MockServer::start().await;
+        mock_start_job(&server).await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/monitors/41ebffb4-a188-48e9-8ec1-61380085cde3/jobs/\
+                01a92c6c-6803-409d-b675-022fff62575a/finish",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(finished_job()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new(&server.uri(), Auth::ApiKey("foo-key".to_owned()));
+
+        let exit_code = run(
+            &client,
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            &["not-a-real-command-for-cron-mon".to_owned()],
+            1024,
+            JobMetadataData::default(),
+        )
+        .await;
+
+        assert_eq!(exit_code, FAILED_TO_RUN_EXIT_CODE);
+        let requests = server.received_requests().await.unwrap();
+        let finish: serde_json::Value = requests[1].body_json().unwrap();
+        assert_eq!(finish["succeeded"], false);
+        assert_eq!(finish["exit_code"], FAILED_TO_RUN_EXIT_CODE);
+        assert!(finish["output"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to run command: "));
+    }
+
+    #[tokio::test]
+    async fn test_run_fails_open() {
+        // Nothing is listening at this address, so the Job can't be started.
+        let client = Client::new("http://127.0.0.1:1", Auth::ApiKey("foo-key".to_owned()));
+
+        let exit_code = run(
+            &client,
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            &command("exit 4"),
+            1024,
+            JobMetadataData::default(),
+        )
+        .await;
+
+        assert_eq!(exit_code, 4);
+    }
+
+    #[tokio::test]
+    async fn test_run_rejected_while_job_in_progress() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/monitors/41ebffb4-a188-48e9-8ec1-61380085cde3/jobs/start",
+            ))
+            .respond_with(ResponseTemplate::new(409).set_body_json(json!({"error": {
+                "code": 409,
+                "reason": "Job In Progress",
+                "description": "Monitor('41ebffb4-a188-48e9-8ec1-61380085cde3') already has a \
+                    job in progress"
+            }})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::new(&server.uri(), Auth::ApiKey("foo-key".to_owned()));
+
+        // The command would fail with a different exit code, were it run.
+        let exit_code = run(
+            &client,
+            gen_uuid("41ebffb4-a188-48e9-8ec1-61380085cde3"),
+            &command("exit 4"),
+            1024,
+            JobMetadataData::default(),
+        )
+        .await;
+
+        assert_eq!(exit_code, REJECTED_EXIT_CODE);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[rstest]
+    #[case::within_limit(&["foo", "bar"], 10, Some("foobar"))]
+    #[case::truncated(&["foo", "bar", "baz"], 5, Some("arbaz"))]
+    #[case::split_character(&["£1", "£2"], 5, Some("1£2"))]
+    #[case::partial_character_dropped(&["££"], 3, Some("£"))]
+    #[case::empty(&[], 10, None)]
+    fn test_output_buffer(
+        #[case] chunks: &[&str],
+        #[case] limit: usize,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut output = OutputBuffer::new(limit);
+        for chunk in chunks {
+            output.push(chunk.as_bytes());
+        }
+
+        assert_eq!(output.to_output().as_deref(), expected);
+    }
+}
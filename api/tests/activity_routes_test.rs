@@ -0,0 +1,142 @@
+pub mod common;
+
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+use cron_mon_client::types::{FinishJobInfo, JobMetadataData};
+use cron_mon_client::{Auth, Client};
+use test_utils::{gen_uuid, is_datetime};
+
+use common::{create_jwt, create_jwt_expiring_in, infrastructure, Infrastructure};
+
+/// Connect to the activity stream as a user in the given tenant.
+async fn stream_activity(base_url: &str, tenant: &str) -> reqwest::Response {
+    stream_activity_with_token(base_url, &create_jwt("test-kid", "Test User", tenant)).await
+}
+
+/// Connect to the activity stream with the given token.
+async fn stream_activity_with_token(base_url: &str, token: &str) -> reqwest::Response {
+    let response = reqwest::Client::new()
+        .get(format!("{base_url}/api/v1/activity"))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["Content-Type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+
+    response
+}
+
+/// Read the next `count` events from an activity stream, as their names and data.
+async fn read_events(response: &mut reqwest::Response, count: usize) -> Vec<(String, Value)> {
+    let mut buffer = String::new();
+    let mut events = vec![];
+    while events.len() < count {
+        let chunk = response
+            .chunk()
+            .await
+            .unwrap()
+            .expect("Activity stream ended");
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(end) = buffer.find("\n\n") {
+            let message = buffer.drain(..end + 2).collect::<String>();
+            let mut name = None;
+            let mut data = None;
+            for line in message.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    name = Some(value.trim().to_owned());
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data = Some(serde_json::from_str::<Value>(value.trim()).unwrap());
+                }
+            }
+
+            // Heartbeats are comments, which have neither a name nor data.
+            if let (Some(name), Some(data)) = (name, data) {
+                events.push((name, data));
+            }
+        }
+    }
+
+    events
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_stream_activity(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let mut foo_stream = stream_activity(&base_url, "foo").await;
+    let mut bar_stream = stream_activity(&base_url, "bar").await;
+
+    let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+    let key_client = Client::new(&base_url, Auth::ApiKey("foo-key".to_owned()));
+    let job_id = key_client.start_job(monitor_id).await.unwrap();
+    key_client
+        .finish_job(
+            monitor_id,
+            job_id,
+            &FinishJobInfo {
+                succeeded: true,
+                output: None,
+                metadata: JobMetadataData::default(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let events = timeout(Duration::from_secs(10), read_events(&mut foo_stream, 2))
+        .await
+        .expect("Timed out waiting for activity");
+    assert_eq!(
+        events
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["job_started", "job_finished"]
+    );
+    for (_, data) in &events {
+        assert!(is_datetime(data["timestamp"].as_str().unwrap()));
+    }
+
+    let mut finished = events[1].1.clone();
+    finished.as_object_mut().unwrap().remove("timestamp");
+    assert_eq!(
+        finished,
+        json!({
+            "type": "job_finished",
+            "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
+            "monitor_name": "db-backup.py",
+            "job_id": job_id.to_string(),
+            "succeeded": true
+        })
+    );
+
+    // Other tenants don't receive the activity.
+    let bar_events = timeout(Duration::from_secs(1), read_events(&mut bar_stream, 1)).await;
+    assert!(bar_events.is_err());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_stream_activity_ends_when_token_expires(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let token = create_jwt_expiring_in("test-kid", "Test User", "foo", &["admin"], 2);
+    let mut stream = stream_activity_with_token(&base_url, &token).await;
+
+    let ended = timeout(Duration::from_secs(10), async {
+        while stream.chunk().await.unwrap().is_some() {}
+    })
+    .await;
+    assert!(ended.is_ok(), "Activity stream outlived the token");
+}
@@ -1,11 +1,16 @@
 pub mod common;
 
+use std::collections::BTreeMap;
+
 use pretty_assertions::assert_eq;
 use rstest::rstest;
 
 use test_utils::gen_uuid;
 
-use cron_mon_api::domain::models::{AlertConfig, AlertType, AppliedMonitor, SlackAlertConfig};
+use cron_mon_api::domain::models::{
+    AlertConfig, AlertType, AppliedMonitor, EmailAlertConfig, PagerDutyAlertConfig,
+    SlackAlertConfig, SmtpConfig, SmtpTls, WebhookAlertConfig, WebhookMethod,
+};
 use cron_mon_api::errors::Error;
 use cron_mon_api::infrastructure::models::alert_config::NewAlertConfigData;
 use cron_mon_api::infrastructure::repositories::alert_config::{
@@ -211,6 +216,8 @@ async fn test_save_with_new(#[future] infrastructure: Infrastructure) {
         false,
         false,
         false,
+        false,
+        false,
         "#new-channel".to_string(),
         "new-test-token".to_string(),
     );
@@ -245,6 +252,162 @@ async fn test_save_with_new(#[future] infrastructure: Infrastructure) {
     assert_eq!(new_alert_config.monitors, read_new_alert_config.monitors);
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_save_webhook_config(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = AlertConfigRepository::new(&infra.pool);
+
+    let mut new_alert_config = AlertConfig::new_webhook_config(
+        "Webhook config".to_string(),
+        "foo".to_string(),
+        true,
+        true,
+        true,
+        false,
+        false,
+        WebhookAlertConfig {
+            url: "https://example.com/hook".to_string(),
+            method: WebhookMethod::Put,
+            headers: BTreeMap::from([("X-Team".to_string(), "infra".to_string())]),
+            secret: Some("shh".to_string()),
+        },
+    );
+    repo.save(&new_alert_config).await.unwrap();
+    assert_eq!(repo.all("foo").await.unwrap().len(), 4);
+
+    let read_alert_config = repo
+        .get(new_alert_config.alert_config_id, "foo")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_alert_config.type_, read_alert_config.type_);
+
+    // Ensure that removing the secret is persisted.
+    new_alert_config
+        .edit_details(
+            "Webhook config".to_string(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            None,
+            AlertType::Webhook(WebhookAlertConfig {
+                url: "https://example.com/other-hook".to_string(),
+                method: WebhookMethod::Post,
+                headers: BTreeMap::new(),
+                secret: None,
+            }),
+        )
+        .unwrap();
+    repo.save(&new_alert_config).await.unwrap();
+
+    let mut new_repo = AlertConfigRepository::new(&infra.pool);
+    let read_alert_config = new_repo
+        .get(new_alert_config.alert_config_id, "foo")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_alert_config.type_, read_alert_config.type_);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_save_email_config(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = AlertConfigRepository::new(&infra.pool);
+
+    let mut new_alert_config = AlertConfig::new_email_config(
+        "Email config".to_string(),
+        "foo".to_string(),
+        true,
+        true,
+        true,
+        false,
+        false,
+        EmailAlertConfig {
+            recipients: vec![
+                "oncall@example.com".to_string(),
+                "infra@example.com".to_string(),
+            ],
+            smtp: Some(SmtpConfig {
+                host: "smtp.example.com".to_string(),
+                port: 465,
+                username: Some("user".to_string()),
+                password: Some("pass".to_string()),
+                from: "cron-mon@example.com".to_string(),
+                tls: SmtpTls::Tls,
+            }),
+        },
+    );
+    repo.save(&new_alert_config).await.unwrap();
+    assert_eq!(repo.all("foo").await.unwrap().len(), 4);
+
+    let read_alert_config = repo
+        .get(new_alert_config.alert_config_id, "foo")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_alert_config.type_, read_alert_config.type_);
+
+    // Ensure that switching to the server's relay is persisted.
+    new_alert_config
+        .edit_details(
+            "Email config".to_string(),
+            true,
+            true,
+            true,
+            false,
+            false,
+            None,
+            AlertType::Email(EmailAlertConfig {
+                recipients: vec!["oncall@example.com".to_string()],
+                smtp: None,
+            }),
+        )
+        .unwrap();
+    repo.save(&new_alert_config).await.unwrap();
+
+    let mut new_repo = AlertConfigRepository::new(&infra.pool);
+    let read_alert_config = new_repo
+        .get(new_alert_config.alert_config_id, "foo")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_alert_config.type_, read_alert_config.type_);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_save_pagerduty_config(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = AlertConfigRepository::new(&infra.pool);
+
+    let new_alert_config = AlertConfig::new_pagerduty_config(
+        "PagerDuty config".to_string(),
+        "foo".to_string(),
+        true,
+        true,
+        true,
+        false,
+        false,
+        PagerDutyAlertConfig {
+            routing_key: "R0UT1NGK3Y".to_string(),
+        },
+    );
+    repo.save(&new_alert_config).await.unwrap();
+    assert_eq!(repo.all("foo").await.unwrap().len(), 4);
+
+    let mut new_repo = AlertConfigRepository::new(&infra.pool);
+    let read_alert_config = new_repo
+        .get(new_alert_config.alert_config_id, "foo")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_alert_config.type_, read_alert_config.type_);
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_save_with_existing(#[future] infrastructure: Infrastructure) {
@@ -324,6 +487,9 @@ async fn test_loading_invalid_config() {
                 active: true,
                 on_late: true,
                 on_error: false,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
             }],
             vec![],
             vec![],
@@ -37,6 +37,9 @@ async fn test_list_alert_configs(#[future] infrastructure: Infrastructure) {
                     "active": true,
                     "on_late": false,
                     "on_error": true,
+                    "on_recovery": false,
+                    "on_anomaly": false,
+                    "label_selector": null,
                     "monitors": 3,
                     "type": "slack",
                 },
@@ -46,6 +49,9 @@ async fn test_list_alert_configs(#[future] infrastructure: Infrastructure) {
                     "active": true,
                     "on_late": true,
                     "on_error": true,
+                    "on_recovery": false,
+                    "on_anomaly": false,
+                    "label_selector": null,
                     "monitors": 1,
                     "type": "slack",
                 },
@@ -55,6 +61,9 @@ async fn test_list_alert_configs(#[future] infrastructure: Infrastructure) {
                     "active": true,
                     "on_late": true,
                     "on_error": false,
+                    "on_recovery": false,
+                    "on_anomaly": false,
+                    "label_selector": null,
                     "monitors": 1,
                     "type": "slack",
                 },
@@ -92,6 +101,9 @@ async fn test_get_alert_config(#[future] infrastructure: Infrastructure) {
                 "active": true,
                 "on_late": false,
                 "on_error": true,
+                "on_recovery": false,
+                "on_anomaly": false,
+                "label_selector": null,
                 "monitors": [
                     {
                         "monitor_id": "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
@@ -163,6 +175,8 @@ async fn test_add_alert_config(#[future] infrastructure: Infrastructure) {
             "active": true,
             "on_late": true,
             "on_error": false,
+            "on_recovery": true,
+            "on_anomaly": true,
             "type": {
                 "slack": {
                     "channel": "#test-channel",
@@ -183,6 +197,8 @@ async fn test_add_alert_config(#[future] infrastructure: Infrastructure) {
     assert_eq!(alert_config["active"], true);
     assert_eq!(alert_config["on_late"], true);
     assert_eq!(alert_config["on_error"], false);
+    assert_eq!(alert_config["on_recovery"], true);
+    assert_eq!(alert_config["on_anomaly"], true);
     assert_eq!(alert_config["type"]["slack"]["channel"], "#test-channel");
     assert_eq!(alert_config["type"]["slack"]["token"], "test-token");
 
@@ -196,6 +212,128 @@ async fn test_add_alert_config(#[future] infrastructure: Infrastructure) {
     );
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_add_webhook_alert_config(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/alert-configs")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new webhook config",
+            "active": true,
+            "on_late": true,
+            "on_error": true,
+            "type": {
+                "webhook": {
+                    "url": "https://example.com/hook",
+                    "headers": {"X-Team": "infra"}
+                }
+            }
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(
+        response_body["data"]["type"],
+        json!({
+            "webhook": {
+                "url": "https://example.com/hook",
+                "method": "POST",
+                "headers": {"X-Team": "infra"},
+                "secret": null
+            }
+        })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_email_alert_config(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/alert-configs")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new email config",
+            "active": true,
+            "on_late": true,
+            "on_error": true,
+            "type": {
+                "email": {
+                    "recipients": ["oncall@example.com"],
+                    "smtp": {
+                        "host": "localhost",
+                        "port": 1025,
+                        "from": "cron-mon@example.com",
+                        "tls": "none"
+                    }
+                }
+            }
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(
+        response_body["data"]["type"],
+        json!({
+            "email": {
+                "recipients": ["oncall@example.com"],
+                "smtp": {
+                    "host": "localhost",
+                    "port": 1025,
+                    "username": null,
+                    "password": null,
+                    "from": "cron-mon@example.com",
+                    "tls": "none"
+                }
+            }
+        })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_pagerduty_alert_config(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/alert-configs")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new pagerduty config",
+            "active": true,
+            "on_late": true,
+            "on_error": true,
+            "type": {
+                "pagerduty": {
+                    "routing_key": "R0UT1NGK3Y"
+                }
+            }
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(
+        response_body["data"]["type"],
+        json!({"pagerduty": {"routing_key": "R0UT1NGK3Y"}})
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_add_alert_config_with_invalid_payload(#[future] infrastructure: Infrastructure) {
@@ -388,6 +526,9 @@ async fn test_list_alert_configs_for_monitor(#[future] infrastructure: Infrastru
                     "active": true,
                     "on_late": false,
                     "on_error": true,
+                    "on_recovery": false,
+                    "on_anomaly": false,
+                    "label_selector": null,
                     "monitors": 3,
                     "type": "slack",
                 },
@@ -397,6 +538,9 @@ async fn test_list_alert_configs_for_monitor(#[future] infrastructure: Infrastru
                     "active": true,
                     "on_late": true,
                     "on_error": true,
+                    "on_recovery": false,
+                    "on_anomaly": false,
+                    "label_selector": null,
                     "monitors": 1,
                     "type": "slack",
                 },
@@ -406,6 +550,9 @@ async fn test_list_alert_configs_for_monitor(#[future] infrastructure: Infrastru
                     "active": true,
                     "on_late": true,
                     "on_error": false,
+                    "on_recovery": false,
+                    "on_anomaly": false,
+                    "label_selector": null,
                     "monitors": 1,
                     "type": "slack",
                 },
@@ -417,6 +564,75 @@ async fn test_list_alert_configs_for_monitor(#[future] infrastructure: Infrastru
     );
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_alert_config_applies_to_monitors_by_label_selector(
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/alert-configs")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "Payments alerts",
+            "active": true,
+            "on_late": true,
+            "on_error": true,
+            "label_selector": "team=payments",
+            "type": {
+                "slack": {
+                    "channel": "#payments",
+                    "token": "test-token"
+                }
+            }
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["label_selector"], "team=payments");
+
+    // A Monitor created afterwards picks up the alert configuration, without being associated.
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "settle-payments",
+            "expected_duration": 300,
+            "grace_duration": 60,
+            "labels": {"team": "payments"}
+        }))
+        .dispatch()
+        .await;
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor_id = response_body["data"]["monitor_id"].as_str().unwrap();
+
+    let response = client
+        .get(format!("/api/v1/monitors/{monitor_id}/alert-configs"))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["paging"], json!({"total": 1}));
+    assert_eq!(response_body["data"][0]["name"], "Payments alerts");
+    assert_eq!(response_body["data"][0]["label_selector"], "team=payments");
+    assert_eq!(response_body["data"][0]["monitors"], 0);
+
+    // Monitors without matching labels don't.
+    let response = client
+        .get("/api/v1/monitors/a04376e2-0fb5-4949-9744-7c5d0a50b411/alert-configs")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["paging"], json!({"total": 0}));
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_list_alert_configs_for_non_existent_monitor(
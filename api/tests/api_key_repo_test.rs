@@ -5,7 +5,7 @@ use rstest::rstest;
 
 use test_utils::gen_uuid;
 
-use cron_mon_api::domain::models::ApiKey;
+use cron_mon_api::domain::models::{ApiKey, ApiKeyScope};
 use cron_mon_api::infrastructure::repositories::api_key::{ApiKeyRepository, GetByKey};
 use cron_mon_api::infrastructure::repositories::Repository;
 
@@ -90,6 +90,9 @@ async fn test_save(#[future] infrastructure: Infrastructure) {
         "New key".to_string(),
         "new-key".to_string(),
         "foo".to_string(),
+        vec![ApiKeyScope::HeartbeatsWrite],
+        Some(vec![gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36")]),
+        None,
     );
     repo.save(&new_api_key).await.unwrap();
     assert_eq!(repo.all("foo").await.unwrap().len(), 3);
@@ -110,6 +113,8 @@ async fn test_save(#[future] infrastructure: Infrastructure) {
         new_api_key.last_used_monitor_name,
         read_new_api_key.last_used_monitor_name
     );
+    assert_eq!(new_api_key.scopes, read_new_api_key.scopes);
+    assert_eq!(new_api_key.monitor_ids, read_new_api_key.monitor_ids);
 }
 
 #[rstest]
@@ -5,6 +5,8 @@ use rocket::http::{ContentType, Status};
 use rstest::rstest;
 use serde_json::{json, Value};
 
+use test_utils::is_datetime;
+
 use common::{create_auth_header, infrastructure, Infrastructure};
 
 #[rstest]
@@ -32,7 +34,10 @@ async fn test_list_api_keys(#[future] infrastructure: Infrastructure) {
               "api_key_id": "bfab6d41-8b00-49ef-86df-f562b701ee4f",
               "name": "Test foo key",
               "masked": "foo-k************-key",
+              "scopes": ["jobs:write", "heartbeats:write"],
+              "monitor_ids": Value::Null,
               "created": "2024-05-01T00:00:00",
+              "expires_at": Value::Null,
               "last_used": {
                 "time": "2024-11-01T00:00:00",
                 "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
@@ -43,7 +48,10 @@ async fn test_list_api_keys(#[future] infrastructure: Infrastructure) {
                 "api_key_id": "029d7c3b-00b5-4bb3-8e95-56d3f933e6a4",
                 "name": "Test bar key",
                 "masked": "bar-k************-key",
+                "scopes": ["jobs:write"],
+                "monitor_ids": ["a04376e2-0fb5-4949-9744-7c5d0a50b411"],
                 "created": "2024-11-02T00:00:00",
+                "expires_at": Value::Null,
                 "last_used": Value::Null,
             }
           ],
@@ -95,6 +103,32 @@ async fn test_generate_api_key(#[future] infrastructure: Infrastructure) {
     assert_eq!(data["paging"].as_object().unwrap()["total"], 3);
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_generate_api_key_without_scopes(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/keys")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({"name": "New API key", "scopes": []}))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    // No key should have been generated.
+    let response = client
+        .get("/api/v1/keys")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let data = response.into_json::<Value>().await.unwrap();
+    assert_eq!(data["paging"].as_object().unwrap()["total"], 2);
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_delete_api_key(#[future] infrastructure: Infrastructure) {
@@ -119,3 +153,69 @@ async fn test_delete_api_key(#[future] infrastructure: Infrastructure) {
     let data = response.into_json::<Value>().await.unwrap();
     assert_eq!(data["paging"].as_object().unwrap()["total"], 1);
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_rotate_api_key(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/keys/bfab6d41-8b00-49ef-86df-f562b701ee4f/rotate")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({"overlap_seconds": 600}))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let response_json = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_json["data"]["key"].as_str().unwrap().len(), 32);
+
+    // The replacement is added alongside the old key, which now expires.
+    let response = client
+        .get("/api/v1/keys")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let data = response.into_json::<Value>().await.unwrap();
+    assert_eq!(data["paging"]["total"], 3);
+    let keys = data["data"].as_array().unwrap();
+    let old_key = keys
+        .iter()
+        .find(|key| key["api_key_id"] == "bfab6d41-8b00-49ef-86df-f562b701ee4f")
+        .unwrap();
+    assert!(is_datetime(old_key["expires_at"].as_str().unwrap()));
+    let new_key = keys
+        .iter()
+        .find(|key| key["name"] == "Test foo key" && key != &old_key)
+        .unwrap();
+    assert_eq!(new_key["expires_at"], Value::Null);
+    assert_eq!(new_key["last_used"], Value::Null);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_rotate_api_key_when_key_does_not_exist(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/keys/4940ede2-72fc-4e0e-838e-f15f35e3594f/rotate")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({}))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 404,
+                "reason": "API Key Not Found",
+                "description": "Failed to find API key with id '4940ede2-72fc-4e0e-838e-f15f35e3594f'"
+            }
+        })
+    );
+}
@@ -0,0 +1,295 @@
+pub mod common;
+
+use pretty_assertions::assert_eq;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rstest::rstest;
+use serde_json::{json, Value};
+
+use test_utils::{is_datetime, is_uuid};
+
+use common::{create_auth_header, create_auth_header_with_roles, infrastructure, Infrastructure};
+
+/// Create, update and then delete a Monitor, returning its ID.
+async fn change_monitor(client: &Client) -> String {
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .json(&json!({"name": "new-monitor", "expected_duration": 100, "grace_duration": 10}))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let monitor_id = response.into_json::<Value>().await.unwrap()["data"]["monitor_id"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+
+    let response = client
+        .patch(format!("/api/v1/monitors/{monitor_id}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .json(&json!({"name": "new-name", "expected_duration": 100, "grace_duration": 10}))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .delete(format!("/api/v1/monitors/{monitor_id}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    monitor_id
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_audit_events(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let monitor_id = change_monitor(&client).await;
+
+    let response = client
+        .get("/api/v1/audit-log")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let events = response_body["data"].as_array().unwrap();
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        response_body["paging"],
+        json!({"total": 3, "limit": 25, "next_cursor": null})
+    );
+
+    // Newest first.
+    let actions = events
+        .iter()
+        .map(|event| event["action"].as_str().unwrap())
+        .collect::<Vec<&str>>();
+    assert_eq!(actions, vec!["deleted", "updated", "created"]);
+
+    for event in events {
+        assert!(is_uuid(event["audit_event_id"].as_str().unwrap()));
+        assert!(is_datetime(event["timestamp"].as_str().unwrap()));
+        assert_eq!(
+            event["actor"],
+            json!({"id": "test-user", "name": "Test User"})
+        );
+        assert_eq!(
+            event["entity"],
+            json!({"type": "monitor", "id": monitor_id})
+        );
+    }
+
+    let updated = &events[1];
+    assert_eq!(updated["before"]["name"], "new-monitor");
+    assert_eq!(updated["after"]["name"], "new-name");
+    assert_eq!(events[0]["after"], Value::Null);
+    assert_eq!(events[2]["before"], Value::Null);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_audit_events_pages(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    change_monitor(&client).await;
+
+    let response = client
+        .get("/api/v1/audit-log?limit=2")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let events = response_body["data"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(response_body["paging"]["total"], 3);
+    assert_eq!(
+        response_body["paging"]["next_cursor"],
+        events[1]["audit_event_id"]
+    );
+
+    let response = client
+        .get(format!(
+            "/api/v1/audit-log?limit=2&cursor={}",
+            events[1]["audit_event_id"].as_str().unwrap()
+        ))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let events = response_body["data"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["action"], "created");
+    assert_eq!(
+        response_body["paging"],
+        json!({"total": 3, "limit": 2, "next_cursor": null})
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_audit_events_with_filters(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let monitor_id = change_monitor(&client).await;
+
+    let response = client
+        .post("/api/v1/keys")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .json(&json!({"name": "New API key"}))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .delete(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36\
+            /alert-configs/fadd7266-648b-4102-8f85-c768655f4297",
+        )
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    for (query, expected_types) in [
+        (
+            "",
+            vec!["alert_config", "api_key", "monitor", "monitor", "monitor"],
+        ),
+        ("entity_type=api_key", vec!["api_key"]),
+        ("entity_type=alert_config", vec!["alert_config"]),
+        (
+            &format!("entity_id={monitor_id}"),
+            vec!["monitor", "monitor", "monitor"],
+        ),
+        ("entity_type=api_key&actor=test-user", vec!["api_key"]),
+        ("actor=someone-else", vec![]),
+    ] {
+        let response = client
+            .get(format!("/api/v1/audit-log?{query}"))
+            .header(create_auth_header("test-kid", "Test User", "foo"))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response_body = response.into_json::<Value>().await.unwrap();
+        let types = response_body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["entity"]["type"].as_str().unwrap())
+            .collect::<Vec<&str>>();
+        assert_eq!(types, expected_types, "query: '{query}'");
+        assert_eq!(response_body["paging"]["total"], expected_types.len());
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_audit_events_for_other_tenant(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    change_monitor(&client).await;
+
+    let response = client
+        .get("/api/v1/audit-log")
+        .header(create_auth_header("test-kid", "Test User", "bar"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "data": [],
+            "paging": {"total": 0, "limit": 25, "next_cursor": null}
+        })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_audit_events_with_unknown_cursor(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/audit-log?cursor=5c4b7c5e-1b8b-4f4d-9a33-2a3e0f5bd4c7")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 404,
+                "reason": "Audit Event Not Found",
+                "description": (
+                    "Failed to find audit event with id '5c4b7c5e-1b8b-4f4d-9a33-2a3e0f5bd4c7'"
+                )
+            }
+        })
+    );
+}
+
+#[rstest]
+#[case("entity_type=job")]
+#[case("entity_id=not-a-uuid")]
+#[case("limit=0")]
+#[case("limit=101")]
+#[case("cursor=not-a-uuid")]
+#[tokio::test]
+async fn test_list_audit_events_with_invalid_query(
+    #[case] query: &str,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get(format!("/api/v1/audit-log?{query}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_audit_events_requires_admin(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/audit-log")
+        .header(create_auth_header_with_roles(
+            "test-kid",
+            "Test User",
+            "foo",
+            &["operator"],
+        ))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
@@ -0,0 +1,505 @@
+pub mod common;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{TimeDelta, Utc};
+use pretty_assertions::assert_eq;
+use rstest::rstest;
+use serde_json::json;
+
+use cron_mon_client::models::{Job, JobFilter, JobStatus};
+use cron_mon_client::types::{
+    AlertConfigData, ApiKeyScope, ConcurrencyPolicy, FinishJobInfo, GenerateKeyInfo,
+    JobMetadataData, JobRetentionData, MonitorAssociationData, MonitorData, MonitorKind, PauseInfo,
+    RotateKeyInfo, ScheduleData,
+};
+use cron_mon_client::{Auth, Client, Error};
+use test_utils::gen_uuid;
+use uuid::Uuid;
+
+use common::{create_jwt, infrastructure, Infrastructure};
+
+fn monitor_data(name: &str) -> MonitorData {
+    MonitorData {
+        name: name.to_owned(),
+        expected_duration: 300,
+        grace_duration: 60,
+        schedule: Some(ScheduleData {
+            expression: "0 3 * * *".to_owned(),
+            timezone: Some("Europe/London".to_owned()),
+        }),
+        kind: MonitorKind::Job,
+        alert_threshold: Default::default(),
+        retention: Some(JobRetentionData {
+            max_age_days: Some(30),
+            max_jobs: None,
+        }),
+        labels: BTreeMap::from([("team".to_owned(), "payments".to_owned())]),
+        maintenance_windows: vec![],
+        auto_tune: None,
+        anomaly_detection: None,
+        concurrency_policy: ConcurrencyPolicy::Allow,
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_managing_monitors(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+
+    let monitors = client.list_monitors().await.unwrap();
+    assert_eq!(monitors.len(), 3);
+
+    let created = client
+        .create_monitor(&monitor_data("new-monitor"))
+        .await
+        .unwrap();
+    assert_eq!(created.name, "new-monitor");
+    assert_eq!(
+        created.schedule,
+        Some(ScheduleData {
+            expression: "0 3 * * *".to_owned(),
+            timezone: Some("Europe/London".to_owned()),
+        })
+    );
+    assert_eq!(created.labels["team"], "payments");
+    assert!(created.jobs.is_empty());
+
+    let stats = client.get_monitor_stats(created.monitor_id).await.unwrap();
+    assert_eq!(stats.stats, None);
+    assert_eq!(stats.suggested, None);
+
+    let matching = client
+        .list_monitors_matching("team=payments")
+        .await
+        .unwrap();
+    assert_eq!(matching.len(), 1);
+    assert_eq!(matching[0].monitor_id, created.monitor_id);
+
+    let updated = client
+        .update_monitor(created.monitor_id, &monitor_data("renamed-monitor"))
+        .await
+        .unwrap();
+    assert_eq!(updated.name, "renamed-monitor");
+
+    let fetched = client.get_monitor(created.monitor_id).await.unwrap();
+    assert_eq!(fetched, updated);
+
+    let paused = client
+        .pause_monitor(created.monitor_id, &PauseInfo::default())
+        .await
+        .unwrap();
+    assert!(paused.paused);
+
+    let resumed = client.resume_monitor(created.monitor_id).await.unwrap();
+    assert!(!resumed.paused);
+
+    client.delete_monitor(created.monitor_id).await.unwrap();
+
+    let error = client.get_monitor(created.monitor_id).await.unwrap_err();
+    assert_eq!(
+        error,
+        Error::ApiError {
+            status: 404,
+            reason: "Monitor Not Found".to_owned(),
+            description: format!("Failed to find monitor with id '{}'", created.monitor_id),
+        }
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_invalid_monitors_are_rejected(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+
+    let mut monitor = monitor_data("new-monitor");
+    monitor.schedule = Some(ScheduleData {
+        expression: "not a cron expression".to_owned(),
+        timezone: None,
+    });
+
+    let error = client.create_monitor(&monitor).await.unwrap_err();
+    assert!(
+        matches!(error, Error::ApiError { status: 422, .. }),
+        "Unexpected error: {error:?}"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_running_jobs(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+    let key_client = Client::new(&base_url, Auth::ApiKey("foo-key".to_owned()));
+    let jwt_client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+
+    let job_id = key_client.start_job(monitor_id).await.unwrap();
+    let job = key_client
+        .finish_job(
+            monitor_id,
+            job_id,
+            &FinishJobInfo {
+                succeeded: false,
+                output: Some("Oh no".to_owned()),
+                metadata: JobMetadataData {
+                    exit_code: Some(2),
+                    hostname: Some("worker-1".to_owned()),
+                    metrics: BTreeMap::from([("rows_processed".to_owned(), 1500.0)]),
+                    ..Default::default()
+                },
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(job.job_id, job_id);
+    assert_eq!(job.succeeded, Some(false));
+    assert_eq!(job.output, Some("Oh no".to_owned()));
+    assert_eq!(job.metadata.exit_code, Some(2));
+
+    let fetched = jwt_client.get_job(monitor_id, job_id).await.unwrap();
+    assert_eq!(fetched.succeeded, Some(false));
+    assert_eq!(fetched.output, Some("Oh no".to_owned()));
+    assert_eq!(fetched.metadata.hostname, Some("worker-1".to_owned()));
+
+    let job_metrics = jwt_client.get_job_metrics(monitor_id).await.unwrap();
+    let rows_processed = &job_metrics.metrics["rows_processed"];
+    assert_eq!(rows_processed.len(), 1);
+    assert_eq!(rows_processed[0].job_id, job_id);
+    assert_eq!(rows_processed[0].value, 1500.0);
+
+    let page = jwt_client
+        .list_jobs(
+            monitor_id,
+            &JobFilter {
+                status: Some(JobStatus::Failed),
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        page.jobs.iter().map(|job| job.job_id).collect::<Vec<_>>(),
+        vec![job_id]
+    );
+    assert_eq!(page.total, 2);
+    assert!(page.next_cursor.is_some());
+
+    // Jobs can only be finished once.
+    let error = key_client
+        .finish_job(
+            monitor_id,
+            job_id,
+            &FinishJobInfo {
+                succeeded: true,
+                output: None,
+                metadata: JobMetadataData::default(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, Error::ApiError { status: 400, .. }),
+        "Unexpected error: {error:?}"
+    );
+
+    let invalid_client = Client::new(&base_url, Auth::ApiKey("invalid-key".to_owned()));
+    let error = invalid_client.start_job(monitor_id).await.unwrap_err();
+    assert!(
+        matches!(error, Error::ApiError { status: 401, .. }),
+        "Unexpected error: {error:?}"
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_guarded_jobs(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+    let key_client = Client::new(&base_url, Auth::ApiKey("foo-key".to_owned()));
+    let jwt_client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+
+    // Finished explicitly.
+    let guard = key_client.start_guarded_job(monitor_id).await.unwrap();
+    let job = guard
+        .finish(true, Some("All done".to_owned()))
+        .await
+        .unwrap();
+    assert_eq!(job.succeeded, Some(true));
+    assert_eq!(job.output, Some("All done".to_owned()));
+
+    // Dropped by returning early with `?`, without being finished.
+    let invalid_client = Client::new(&base_url, Auth::ApiKey("invalid-key".to_owned()));
+    let mut dropped_job_id = None;
+    let result: Result<(), Error> = async {
+        let guard = key_client.start_guarded_job(monitor_id).await?;
+        dropped_job_id = Some(guard.job_id());
+        invalid_client.start_job(monitor_id).await?;
+        guard.finish(true, None).await?;
+        Ok(())
+    }
+    .await;
+    assert!(result.is_err());
+
+    // Dropped whilst panicking.
+    let guard = key_client.start_guarded_job(monitor_id).await.unwrap();
+    let panicked_job_id = guard.job_id();
+    let result = tokio::spawn(async move {
+        let _guard = guard;
+        panic!("Oh no");
+    })
+    .await;
+    assert!(result.unwrap_err().is_panic());
+
+    // Jobs dropped by guards are finished in the background.
+    let dropped_job = wait_for_finish(&jwt_client, monitor_id, dropped_job_id.unwrap()).await;
+    assert_eq!(dropped_job.succeeded, Some(false));
+    assert_eq!(
+        dropped_job.output,
+        Some("Job guard dropped without being finished".to_owned())
+    );
+
+    let panicked_job = wait_for_finish(&jwt_client, monitor_id, panicked_job_id).await;
+    assert_eq!(panicked_job.succeeded, Some(false));
+    assert_eq!(panicked_job.output, Some("Job panicked".to_owned()));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_managing_api_keys(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+
+    let key = client
+        .generate_key(&GenerateKeyInfo {
+            name: "New key".to_owned(),
+            scopes: None,
+            monitor_ids: None,
+            expires_at: None,
+        })
+        .await
+        .unwrap();
+
+    let keys = client.list_keys().await.unwrap();
+    assert_eq!(keys.len(), 3);
+    let new_key = keys.iter().find(|key| key.name == "New key").unwrap();
+    assert_eq!(new_key.last_used, None);
+    assert_eq!(new_key.scopes, ApiKeyScope::ALL.to_vec());
+    assert_eq!(new_key.monitor_ids, None);
+
+    // The new key can be used to start jobs.
+    let key_client = Client::new(&base_url, Auth::ApiKey(key));
+    key_client
+        .start_job(gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"))
+        .await
+        .unwrap();
+
+    let keys = client.list_keys().await.unwrap();
+    let used_key = keys.iter().find(|key| key.name == "New key").unwrap();
+    assert_eq!(
+        used_key.last_used.as_ref().unwrap().monitor_name,
+        "db-backup.py"
+    );
+
+    client.revoke_key(used_key.api_key_id).await.unwrap();
+    assert_eq!(client.list_keys().await.unwrap().len(), 2);
+
+    // Keys restricted to heartbeats can't be used to start jobs.
+    let restricted_key = client
+        .generate_key(&GenerateKeyInfo {
+            name: "Heartbeat key".to_owned(),
+            scopes: Some(vec![ApiKeyScope::HeartbeatsWrite]),
+            monitor_ids: Some(vec![gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36")]),
+            expires_at: None,
+        })
+        .await
+        .unwrap();
+    let error = Client::new(&base_url, Auth::ApiKey(restricted_key))
+        .start_job(gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        Error::ApiError {
+            status: 403,
+            reason: "Forbidden".to_owned(),
+            description: "Forbidden: API key does not have the 'jobs:write' scope".to_owned(),
+        }
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_rotating_api_keys(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+    let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+    let expires_at = (Utc::now() + TimeDelta::days(30)).naive_utc();
+
+    let old_key = client
+        .generate_key(&GenerateKeyInfo {
+            name: "Expiring key".to_owned(),
+            scopes: None,
+            monitor_ids: None,
+            expires_at: Some(expires_at),
+        })
+        .await
+        .unwrap();
+    let old_key_id = client
+        .list_keys()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|key| key.name == "Expiring key")
+        .unwrap()
+        .api_key_id;
+
+    // Without any overlap, the old key stops working straight away.
+    let new_key = client
+        .rotate_key(
+            old_key_id,
+            &RotateKeyInfo {
+                overlap_seconds: Some(0),
+            },
+        )
+        .await
+        .unwrap();
+
+    let error = Client::new(&base_url, Auth::ApiKey(old_key))
+        .start_job(monitor_id)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            Error::ApiError { status: 401, reason, .. } if reason == "API Key Expired"
+        ),
+        "Unexpected error: {error:?}"
+    );
+
+    Client::new(&base_url, Auth::ApiKey(new_key))
+        .start_job(monitor_id)
+        .await
+        .unwrap();
+
+    // The replacement lasts as long as the old key would have.
+    let keys = client.list_keys().await.unwrap();
+    let replacement = keys
+        .iter()
+        .find(|key| key.name == "Expiring key" && key.api_key_id != old_key_id)
+        .unwrap();
+    let lifetime = replacement.expires_at.unwrap() - replacement.created;
+    assert!((lifetime - TimeDelta::days(30)).num_seconds().abs() < 5);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_managing_alert_configs(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let base_url = infra.launch_api("test-kid").await;
+    let client = Client::new(
+        &base_url,
+        Auth::Jwt(create_jwt("test-kid", "test-user", "foo")),
+    );
+    let monitor_id = gen_uuid("a04376e2-0fb5-4949-9744-7c5d0a50b411");
+
+    let alert_configs = client.list_alert_configs().await.unwrap();
+    assert_eq!(alert_configs.len(), 3);
+
+    let mut alert_config_data = AlertConfigData {
+        name: "New Slack alert".to_owned(),
+        active: true,
+        on_late: true,
+        on_error: false,
+        on_recovery: false,
+        on_anomaly: false,
+        label_selector: None,
+        type_: json!({"slack": {"channel": "#alerts", "token": "test-token"}}),
+    };
+    let created = client
+        .create_alert_config(&alert_config_data)
+        .await
+        .unwrap();
+    assert_eq!(created.name, "New Slack alert");
+    assert_eq!(created.type_, alert_config_data.type_);
+
+    alert_config_data.on_error = true;
+    let updated = client
+        .update_alert_config(created.alert_config_id, &alert_config_data)
+        .await
+        .unwrap();
+    assert!(updated.on_error);
+
+    client
+        .associate_alert_configs(
+            monitor_id,
+            &MonitorAssociationData {
+                alert_config_ids: vec![created.alert_config_id],
+            },
+        )
+        .await
+        .unwrap();
+
+    let for_monitor = client.list_monitor_alert_configs(monitor_id).await.unwrap();
+    assert!(for_monitor
+        .iter()
+        .any(|ac| ac.alert_config_id == created.alert_config_id && ac.monitors == 1));
+
+    let fetched = client
+        .get_alert_config(created.alert_config_id)
+        .await
+        .unwrap();
+    assert_eq!(fetched.monitors.len(), 1);
+    assert_eq!(fetched.monitors[0].monitor_id, monitor_id);
+
+    client
+        .disassociate_alert_config(monitor_id, created.alert_config_id)
+        .await
+        .unwrap();
+    client
+        .delete_alert_config(created.alert_config_id)
+        .await
+        .unwrap();
+
+    assert_eq!(client.list_alert_configs().await.unwrap().len(), 3);
+}
+
+/// Wait for a Job to be finished in the background, failing if it takes too long.
+async fn wait_for_finish(client: &Client, monitor_id: Uuid, job_id: Uuid) -> Job {
+    for _ in 0..50 {
+        let job = client.get_job(monitor_id, job_id).await.unwrap();
+        if !job.in_progress {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    panic!("Job {job_id} wasn't finished");
+}
@@ -7,7 +7,7 @@ use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use test_utils::{encode_jwt, RSA_EXPONENT, RSA_MODULUS};
 
-use cron_mon_api::infrastructure::auth::Jwt;
+use cron_mon_api::infrastructure::auth::{Jwt, RoleClaim};
 
 pub async fn setup_mock_jwks_server(kid: &str) -> MockServer {
     let mock_server = MockServer::start().await;
@@ -33,30 +33,59 @@ pub async fn setup_mock_jwks_server(kid: &str) -> MockServer {
 }
 
 pub fn create_auth_header<'a>(kid: &str, name: &str, tenant: &str) -> Header<'a> {
+    create_auth_header_with_roles(kid, name, tenant, &["admin"])
+}
+
+pub fn create_auth_header_with_roles<'a>(
+    kid: &str,
+    name: &str,
+    tenant: &str,
+    roles: &[&str],
+) -> Header<'a> {
+    Header::new(
+        "Authorization",
+        format!("Bearer {}", create_jwt_with_roles(kid, name, tenant, roles)),
+    )
+}
+
+pub fn create_jwt(kid: &str, name: &str, tenant: &str) -> String {
+    create_jwt_with_roles(kid, name, tenant, &["admin"])
+}
+
+pub fn create_jwt_with_roles(kid: &str, name: &str, tenant: &str, roles: &[&str]) -> String {
+    create_jwt_expiring_in(kid, name, tenant, roles, 3600)
+}
+
+pub fn create_jwt_expiring_in(
+    kid: &str,
+    name: &str,
+    tenant: &str,
+    roles: &[&str],
+    expires_in: u64,
+) -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs();
 
-    Header::new(
-        "Authorization",
-        format!(
-            "Bearer {}",
-            encode_jwt(
-                kid,
-                &Jwt {
-                    acr: "acr".to_string(),
-                    azp: "azp".to_string(),
-                    iss: "iss".to_string(),
-                    jti: "jti".to_string(),
-                    iat: now,
-                    auth_time: now,
-                    exp: now + 3600,
-                    sub: "test-user".to_string(),
-                    name: name.to_string(),
-                    tenant: tenant.to_string(),
-                }
-            )
-        ),
+    encode_jwt(
+        kid,
+        &Jwt {
+            acr: "acr".to_string(),
+            azp: "azp".to_string(),
+            iss: "iss".to_string(),
+            jti: "jti".to_string(),
+            iat: now,
+            auth_time: now,
+            exp: now + expires_in,
+            sub: "test-user".to_string(),
+            name: name.to_string(),
+            tenant: tenant.to_string(),
+            realm_access: RoleClaim {
+                roles: roles.iter().map(|role| role.to_string()).collect(),
+            },
+            resource_access: Default::default(),
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+        },
     )
 }
@@ -1,5 +1,8 @@
+use rocket::fairing::AdHoc;
 use rocket::local::asynchronous::Client;
+use rocket::Config;
 use rstest::fixture;
+use tokio::sync::oneshot;
 use wiremock::MockServer;
 
 use cron_mon_api::infrastructure::database::{run_migrations, DbPool};
@@ -92,4 +95,22 @@ impl Infrastructure {
             .await
             .expect("Invalid rocket instance")
     }
+
+    /// Launch the API on a random port, linked to this Infrastructure, returning its base URL.
+    pub async fn launch_api(&mut self, kid: &str) -> String {
+        self.mock_server = Some(setup_mock_jwks_server(kid).await);
+
+        let (port_sender, port_receiver) = oneshot::channel();
+        let api = rocket()
+            .configure(Config::figment().merge(("port", 0)))
+            .attach(AdHoc::on_liftoff("Report port", |rocket| {
+                Box::pin(async move {
+                    let _ = port_sender.send(rocket.config().port);
+                })
+            }));
+        tokio::spawn(api.launch());
+
+        let port = port_receiver.await.expect("API failed to launch");
+        format!("http://127.0.0.1:{port}")
+    }
 }
@@ -3,6 +3,9 @@ pub mod infra;
 pub mod postgres;
 pub mod seeds;
 
-pub use auth::create_auth_header;
+pub use auth::{
+    create_auth_header, create_auth_header_with_roles, create_jwt, create_jwt_expiring_in,
+    create_jwt_with_roles,
+};
 pub use infra::{infrastructure, Infrastructure};
 pub use postgres::{postgres_container, PostgresContainer};
@@ -6,7 +6,8 @@ use testcontainers_modules::postgres::Postgres;
 
 use cron_mon_api::infrastructure::database::{create_connection_pool, DbPool};
 use cron_mon_api::infrastructure::db_schema::{
-    alert_config, api_key, job, monitor, monitor_alert_config, slack_alert_config,
+    alert_config, api_key, audit_event, job, monitor, monitor_alert_config, monitor_label,
+    slack_alert_config, webhook_delivery, webhook_delivery_attempt, webhook_subscription,
 };
 use cron_mon_api::infrastructure::models::{
     alert_config::{MonitorAlertConfigData, NewAlertConfigData, NewSlackAlertConfigData},
@@ -130,4 +131,29 @@ async fn delete_existing_data(conn: &mut AsyncPgConnection) {
         .execute(conn)
         .await
         .expect("Failed to delete existing monitor_alert_config data");
+
+    diesel::delete(audit_event::table)
+        .execute(conn)
+        .await
+        .expect("Failed to delete existing audit_event data");
+
+    diesel::delete(webhook_subscription::table)
+        .execute(conn)
+        .await
+        .expect("Failed to delete existing webhook_subscription data");
+
+    diesel::delete(webhook_delivery::table)
+        .execute(conn)
+        .await
+        .expect("Failed to delete existing webhook_delivery data");
+
+    diesel::delete(webhook_delivery_attempt::table)
+        .execute(conn)
+        .await
+        .expect("Failed to delete existing webhook_delivery_attempt data");
+
+    diesel::delete(monitor_label::table)
+        .execute(conn)
+        .await
+        .expect("Failed to delete existing monitor_label data");
 }
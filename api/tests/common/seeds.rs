@@ -4,6 +4,7 @@ use cron_mon_api::infrastructure::models::{
     job::JobData,
     monitor::MonitorData,
 };
+use serde_json::json;
 use test_utils::{gen_datetime, gen_uuid};
 
 pub fn monitor_seeds() -> Vec<MonitorData> {
@@ -14,6 +15,26 @@ pub fn monitor_seeds() -> Vec<MonitorData> {
             name: "init-philanges".to_string(),
             expected_duration: 900,
             grace_duration: 300,
+            schedule: None,
+            schedule_timezone: None,
+            missed_start_cutoff: None,
+            kind: "job".to_owned(),
+            heartbeat_period: None,
+            alert_threshold_consecutive: 1,
+            alert_threshold_window_failures: None,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: None,
+            retention_max_jobs: None,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: None,
+            auto_tune_max_expected: None,
+            auto_tune_min_grace: None,
+            auto_tune_max_grace: None,
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
         },
         MonitorData {
             monitor_id: gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
@@ -21,6 +42,26 @@ pub fn monitor_seeds() -> Vec<MonitorData> {
             name: "db-backup.py".to_string(),
             expected_duration: 1800,
             grace_duration: 600,
+            schedule: None,
+            schedule_timezone: None,
+            missed_start_cutoff: None,
+            kind: "job".to_owned(),
+            heartbeat_period: None,
+            alert_threshold_consecutive: 1,
+            alert_threshold_window_failures: None,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: None,
+            retention_max_jobs: None,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: None,
+            auto_tune_max_expected: None,
+            auto_tune_min_grace: None,
+            auto_tune_max_grace: None,
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
         },
         MonitorData {
             monitor_id: gen_uuid("f0b291fe-bd41-4787-bc2d-1329903f7a6a"),
@@ -28,6 +69,26 @@ pub fn monitor_seeds() -> Vec<MonitorData> {
             name: "generate-orders.sh".to_string(),
             expected_duration: 5400,
             grace_duration: 720,
+            schedule: None,
+            schedule_timezone: None,
+            missed_start_cutoff: None,
+            kind: "job".to_owned(),
+            heartbeat_period: None,
+            alert_threshold_consecutive: 1,
+            alert_threshold_window_failures: None,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: None,
+            retention_max_jobs: None,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: None,
+            auto_tune_max_expected: None,
+            auto_tune_min_grace: None,
+            auto_tune_max_grace: None,
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
         },
         MonitorData {
             monitor_id: gen_uuid("cc6cf74e-b25d-4c8c-94a6-914e3f139c14"),
@@ -35,6 +96,26 @@ pub fn monitor_seeds() -> Vec<MonitorData> {
             name: "data-snapshot.py".to_string(),
             expected_duration: 3600,
             grace_duration: 1200,
+            schedule: Some("0 3 * * *".to_owned()),
+            schedule_timezone: Some("Europe/London".to_owned()),
+            missed_start_cutoff: Some(gen_datetime("2024-05-01T02:00:00.000")),
+            kind: "job".to_owned(),
+            heartbeat_period: None,
+            alert_threshold_consecutive: 1,
+            alert_threshold_window_failures: None,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: None,
+            retention_max_jobs: None,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: Some(1800),
+            auto_tune_max_expected: Some(7200),
+            auto_tune_min_grace: Some(0),
+            auto_tune_max_grace: Some(1800),
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
         },
     ]
 }
@@ -51,6 +132,19 @@ pub fn job_seeds() -> Vec<JobData> {
             output: Some("Database successfully backed up".to_string()),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({"bytes_backed_up": 2048}),
         },
         JobData {
             job_id: gen_uuid("c1893113-66d7-4707-9a51-c8be46287b2c"),
@@ -62,6 +156,19 @@ pub fn job_seeds() -> Vec<JobData> {
             output: Some("Could not connect to database".to_string()),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({"bytes_backed_up": 1024}),
         },
         JobData {
             job_id: gen_uuid("9d4e2d69-af63-4c1e-8639-60cb2683aee5"),
@@ -73,6 +180,19 @@ pub fn job_seeds() -> Vec<JobData> {
             output: None,
             late_alert_sent: true,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({}),
         },
         JobData {
             job_id: gen_uuid("2a09c819-ed8c-4e3a-b085-889f3f475c02"),
@@ -84,6 +204,19 @@ pub fn job_seeds() -> Vec<JobData> {
             output: None,
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({}),
         },
         JobData {
             job_id: gen_uuid("db610603-5094-49a4-8838-204103cd5b78"),
@@ -95,6 +228,19 @@ pub fn job_seeds() -> Vec<JobData> {
             output: None,
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({}),
         },
     ]
 }
@@ -111,6 +257,9 @@ pub fn api_key_seeds() -> Vec<ApiKeyData> {
             last_used: Some(gen_datetime("2024-11-01T00:00:00.000")),
             last_used_monitor_id: Some(gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36")),
             last_used_monitor_name: Some("db-backup.py".to_string()),
+            scopes: vec!["jobs:write".to_owned(), "heartbeats:write".to_owned()],
+            monitor_ids: None,
+            expires_at: None,
         },
         ApiKeyData {
             api_key_id: gen_uuid("029d7c3b-00b5-4bb3-8e95-56d3f933e6a4"),
@@ -122,6 +271,9 @@ pub fn api_key_seeds() -> Vec<ApiKeyData> {
             last_used: None,
             last_used_monitor_id: None,
             last_used_monitor_name: None,
+            scopes: vec!["jobs:write".to_owned()],
+            monitor_ids: Some(vec![gen_uuid("a04376e2-0fb5-4949-9744-7c5d0a50b411")]),
+            expires_at: None,
         },
         ApiKeyData {
             api_key_id: gen_uuid("ea137deb-dfe0-4dca-bfd4-019492a522b1"),
@@ -133,6 +285,9 @@ pub fn api_key_seeds() -> Vec<ApiKeyData> {
             last_used: None,
             last_used_monitor_id: None,
             last_used_monitor_name: None,
+            scopes: vec!["jobs:write".to_owned(), "heartbeats:write".to_owned()],
+            monitor_ids: None,
+            expires_at: Some(gen_datetime("2024-12-01T00:00:00.000")),
         },
     ]
 }
@@ -152,6 +307,9 @@ pub fn alert_config_seeds() -> (
                 active: true,
                 on_late: true,
                 on_error: false,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
             },
             NewAlertConfigData {
                 alert_config_id: gen_uuid("3ba21f52-32c9-41dc-924d-d18d4fc0e81c"),
@@ -161,6 +319,9 @@ pub fn alert_config_seeds() -> (
                 active: true,
                 on_late: false,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
             },
             NewAlertConfigData {
                 alert_config_id: gen_uuid("8d307d12-4696-4801-bfb6-628f8f640864"),
@@ -170,6 +331,9 @@ pub fn alert_config_seeds() -> (
                 active: true,
                 on_late: true,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
             },
             NewAlertConfigData {
                 alert_config_id: gen_uuid("76725038-86a0-46d6-b97a-05735f71cb4f"),
@@ -179,6 +343,9 @@ pub fn alert_config_seeds() -> (
                 active: true,
                 on_late: true,
                 on_error: true,
+                on_recovery: false,
+                on_anomaly: false,
+                label_selector: None,
             },
         ],
         vec![
@@ -10,6 +10,159 @@ use test_utils::{is_datetime, is_uuid};
 
 use common::{create_auth_header, infrastructure, Infrastructure};
 
+#[rstest]
+#[tokio::test]
+async fn test_list_jobs(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs?limit=2")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let job_ids = response_body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|job| job["job_id"].as_str().unwrap())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        job_ids,
+        vec![
+            "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+            "8106bab7-d643-4ede-bd92-60c79f787344"
+        ]
+    );
+    assert_eq!(
+        response_body["paging"],
+        json!({
+            "total": 3,
+            "limit": 2,
+            "next_cursor": "8106bab7-d643-4ede-bd92-60c79f787344"
+        })
+    );
+
+    let response = client
+        .get(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs\
+            ?limit=2&cursor=8106bab7-d643-4ede-bd92-60c79f787344",
+        )
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let jobs = response_body["data"].as_array().unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["job_id"], "c1893113-66d7-4707-9a51-c8be46287b2c");
+    assert_eq!(
+        response_body["paging"],
+        json!({"total": 3, "limit": 2, "next_cursor": null})
+    );
+}
+
+#[rstest]
+#[case("status=succeeded", vec!["8106bab7-d643-4ede-bd92-60c79f787344"])]
+#[case("status=failed", vec!["c1893113-66d7-4707-9a51-c8be46287b2c"])]
+#[case("status=late", vec!["9d4e2d69-af63-4c1e-8639-60cb2683aee5"])]
+#[case("status=in_progress", vec!["9d4e2d69-af63-4c1e-8639-60cb2683aee5"])]
+#[case(
+    "started_after=2024-05-01T00:10:00&order=asc",
+    vec![
+        "8106bab7-d643-4ede-bd92-60c79f787344",
+        "9d4e2d69-af63-4c1e-8639-60cb2683aee5"
+    ]
+)]
+#[case("started_before=2024-05-01T00:10:00", vec!["c1893113-66d7-4707-9a51-c8be46287b2c"])]
+#[tokio::test]
+async fn test_list_jobs_with_filters(
+    #[case] query: &str,
+    #[case] expected_job_ids: Vec<&str>,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get(format!(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs?{query}"
+        ))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let job_ids = response_body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|job| job["job_id"].as_str().unwrap())
+        .collect::<Vec<&str>>();
+    assert_eq!(job_ids, expected_job_ids);
+    assert_eq!(response_body["paging"]["total"], expected_job_ids.len());
+}
+
+#[rstest]
+#[case("status=finished")]
+#[case("limit=0")]
+#[case("limit=101")]
+#[case("started_after=yesterday")]
+#[case("cursor=not-a-uuid")]
+#[tokio::test]
+async fn test_list_jobs_with_invalid_query(
+    #[case] query: &str,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get(format!(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs?{query}"
+        ))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_jobs_when_monitor_does_not_exist(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs")
+        .header(create_auth_header("test-kid", "test-user", "bar"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 404,
+                "reason": "Monitor Not Found",
+                "description": "Failed to find monitor with id \
+                                'c1bf0515-df39-448b-aa95-686360a33b36'"
+            }
+        })
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_get_job_when_job_exists(#[future] infrastructure: Infrastructure) {
@@ -85,8 +238,131 @@ async fn test_start_job(#[future] infrastructure: Infrastructure) {
     assert_eq!(response.content_type(), Some(ContentType::JSON));
 
     let response_body = response.into_json::<Value>().await.unwrap();
-    let job = &response_body["data"];
-    assert!(is_uuid(job["job_id"].as_str().unwrap()));
+    let job_id = response_body["data"]["job_id"].as_str().unwrap();
+    assert!(is_uuid(job_id));
+
+    let response = client
+        .get(format!(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/{job_id}"
+        ))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response.into_json::<Value>().await.unwrap();
+    // The Monitor already has a job in progress.
+    assert_eq!(response_body["data"]["overlapped"], true);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_start_job_while_job_in_progress_is_rejected(
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "nightly-backup",
+            "expected_duration": 1800,
+            "grace_duration": 600,
+            "concurrency_policy": "reject"
+        }))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor_id = response_body["data"]["monitor_id"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+
+    let response = client
+        .post(format!("/api/v1/monitors/{monitor_id}/jobs/start"))
+        .header(Header::new("X-API-Key", "foo-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post(format!("/api/v1/monitors/{monitor_id}/jobs/start"))
+        .header(Header::new("X-API-Key", "foo-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 409,
+                "reason": "Job In Progress",
+                "description": format!("Monitor('{monitor_id}') already has a job in progress")
+            }
+        })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_start_job_with_restricted_key(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    // The "bar-key" API key can only be used for the a04376e2 Monitor.
+    let response = client
+        .post("/api/v1/monitors/a04376e2-0fb5-4949-9744-7c5d0a50b411/jobs/start")
+        .header(Header::new("X-API-Key", "bar-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/start")
+        .header(Header::new("X-API-Key", "bar-key"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 403,
+                "reason": "Forbidden",
+                "description": "Forbidden: API key cannot be used for \
+                                Monitor('c1bf0515-df39-448b-aa95-686360a33b36')"
+            }
+        })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_start_job_with_expired_key(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/start")
+        .header(Header::new("X-API-Key", "baz-key"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 401,
+                "reason": "API Key Expired",
+                "description": "API key expired at 2024-12-01 00:00:00"
+            }
+        })
+    );
 }
 
 #[rstest]
@@ -105,7 +381,15 @@ async fn test_finish_job(#[future] infrastructure: Infrastructure) {
             /jobs/9d4e2d69-af63-4c1e-8639-60cb2683aee5/finish",
         )
         .header(Header::new("X-API-Key", "foo-key"))
-        .json(&json!({"succeeded": true, "output": "Test output"}))
+        .json(&json!({
+            "succeeded": true,
+            "output": "Test output",
+            "exit_code": 0,
+            "hostname": "worker-1",
+            "command": "python db-backup.py",
+            "version": "4f2a9c1",
+            "metrics": {"bytes_backed_up": 2048}
+        }))
         .dispatch()
         .await;
 
@@ -122,6 +406,11 @@ async fn test_finish_job(#[future] infrastructure: Infrastructure) {
     assert_eq!(job["succeeded"], true);
     assert_eq!(job["in_progress"], false);
     assert_eq!(job["late"], true);
+    assert_eq!(job["exit_code"], 0);
+    assert_eq!(job["hostname"], "worker-1");
+    assert_eq!(job["command"], "python db-backup.py");
+    assert_eq!(job["version"], "4f2a9c1");
+    assert_eq!(job["metrics"], json!({"bytes_backed_up": 2048.0}));
 
     // Ensure this has persisted.
     let job_finished =
@@ -171,6 +460,150 @@ async fn test_finish_job_errors(
     assert_eq!(response.into_json::<Value>().await.unwrap(), expected_body);
 }
 
+#[rstest]
+#[case::empty_hostname(json!({"succeeded": true, "hostname": ""}))]
+#[case::invalid_metric_name(json!({"succeeded": true, "metrics": {"rows processed": 10}}))]
+#[case::non_numeric_metric(json!({"succeeded": true, "metrics": {"rows": "ten"}}))]
+#[tokio::test]
+async fn test_finish_job_with_invalid_metadata(
+    #[case] body: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36\
+            /jobs/9d4e2d69-af63-4c1e-8639-60cb2683aee5/finish",
+        )
+        .header(Header::new("X-API-Key", "foo-key"))
+        .json(&body)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    // The Job is left in progress.
+    let job_finished =
+        get_job_finished(&client, "9d4e2d69-af63-4c1e-8639-60cb2683aee5", "foo").await;
+    assert_eq!(job_finished, false);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_record_heartbeat(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "queue-consumer",
+            "expected_duration": 0,
+            "grace_duration": 60,
+            "kind": {"type": "heartbeat", "period": 300}
+        }))
+        .dispatch()
+        .await;
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor_id = response_body["data"]["monitor_id"].as_str().unwrap();
+
+    let response = client
+        .post(format!("/api/v1/monitors/{}/jobs/heartbeat", monitor_id))
+        .header(Header::new("X-API-Key", "foo-key"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let job = &response_body["data"];
+    assert!(is_uuid(job["job_id"].as_str().unwrap()));
+    assert!(is_datetime(job["end_time"].as_str().unwrap()));
+    assert_eq!(job["succeeded"], true);
+    assert_eq!(job["in_progress"], false);
+    assert_eq!(job["late"], false);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_record_heartbeat_for_non_heartbeat_monitor(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/heartbeat")
+        .header(Header::new("X-API-Key", "foo-key"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 400,
+                "reason": "Not A Heartbeat Monitor",
+                "description": "Monitor('c1bf0515-df39-448b-aa95-686360a33b36') is not a \
+                                heartbeat monitor"
+            }
+        })
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_jobs_during_maintenance(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/pause")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({}))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Jobs are still started and finished whilst the Monitor is paused, but are flagged as
+    // having run during maintenance.
+    let response = client
+        .post(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36\
+            /jobs/9d4e2d69-af63-4c1e-8639-60cb2683aee5/finish",
+        )
+        .header(Header::new("X-API-Key", "foo-key"))
+        .json(&json!({"succeeded": false, "output": null}))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["during_maintenance"], true);
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/start")
+        .header(Header::new("X-API-Key", "foo-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let job_id = response_body["data"]["job_id"].as_str().unwrap().to_owned();
+
+    let response = client
+        .get(format!(
+            "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/{job_id}"
+        ))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["during_maintenance"], true);
+    assert_eq!(response_body["data"]["in_progress"], true);
+}
+
 pub async fn get_job_finished(client: &Client, job_id: &str, tenant: &str) -> bool {
     let response = client
         .get(format!(
@@ -1,17 +1,19 @@
 pub mod common;
 
 use pretty_assertions::assert_eq;
-use rocket::http::{ContentType, Status};
+use rocket::http::{ContentType, Method, Status};
 use rocket::local::asynchronous::LocalResponse;
 use rstest::rstest;
 use serde_json::{json, Value};
 
-use common::{infrastructure, Infrastructure};
+use common::{create_auth_header_with_roles, infrastructure, Infrastructure};
 
 #[rstest]
 #[case::get_monitor("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36")]
 #[case::list_monitors("/api/v1/monitors")]
 #[case::get_job("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/9d4e2d69-af63-4c1e-8639-60cb2683aee5")]
+#[case::stream_activity("/api/v1/activity")]
+#[case::list_webhook_subscriptions("/api/v1/webhook-subscriptions")]
 #[tokio::test]
 async fn test_authed_get_endpoints_with_no_jwt(
     #[case] endpoint: &str,
@@ -85,6 +87,128 @@ async fn test_delete_monitor_with_no_jwt(#[future] infrastructure: Infrastructur
     assert_response_is_unauthorized(response).await;
 }
 
+#[rstest]
+#[case::create_monitor_as_viewer(
+    Method::Post,
+    "/api/v1/monitors",
+    json!({"name": "new-monitor", "expected_duration": 10, "grace_duration": 5}),
+    &["viewer"],
+    "operator"
+)]
+#[case::delete_monitor_as_operator(
+    Method::Delete,
+    "/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36",
+    Value::Null,
+    &["operator"],
+    "admin"
+)]
+#[case::generate_key_as_operator(
+    Method::Post,
+    "/api/v1/keys",
+    json!({"name": "New key"}),
+    &["operator"],
+    "admin"
+)]
+#[case::revoke_key_as_operator(
+    Method::Delete,
+    "/api/v1/keys/bfab6d41-8b00-49ef-86df-f562b701ee4f",
+    Value::Null,
+    &["operator"],
+    "admin"
+)]
+#[case::delete_alert_config_as_viewer(
+    Method::Delete,
+    "/api/v1/alert-configs/fadd7266-648b-4102-8f85-c768655f4297",
+    Value::Null,
+    &["viewer"],
+    "admin"
+)]
+#[case::create_webhook_subscription_as_viewer(
+    Method::Post,
+    "/api/v1/webhook-subscriptions",
+    json!({
+        "name": "Data pipeline",
+        "url": "https://example.com/events",
+        "event_types": ["job_started"],
+        "active": true
+    }),
+    &["viewer"],
+    "operator"
+)]
+#[tokio::test]
+async fn test_endpoints_with_insufficient_role(
+    #[case] method: Method,
+    #[case] endpoint: &str,
+    #[case] body: Value,
+    #[case] roles: &[&str],
+    #[case] required_role: &str,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let mut request = client
+        .req(method, endpoint)
+        .header(create_auth_header_with_roles(
+            "test-kid",
+            "test-user",
+            "foo",
+            roles,
+        ));
+    if !body.is_null() {
+        request = request.header(ContentType::JSON).body(body.to_string());
+    }
+    let response = request.dispatch().await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 403,
+                "reason": "Forbidden",
+                "description": format!("Forbidden: Requires the '{required_role}' role")
+            }
+        })
+    );
+}
+
+#[rstest]
+#[case::list_monitors_as_viewer(Method::Get, "/api/v1/monitors", Value::Null, &["viewer"])]
+#[case::list_keys_without_a_role(Method::Get, "/api/v1/keys", Value::Null, &[])]
+#[case::create_monitor_as_operator(
+    Method::Post,
+    "/api/v1/monitors",
+    json!({"name": "new-monitor", "expected_duration": 10, "grace_duration": 5}),
+    &["operator"]
+)]
+#[tokio::test]
+async fn test_endpoints_with_sufficient_role(
+    #[case] method: Method,
+    #[case] endpoint: &str,
+    #[case] body: Value,
+    #[case] roles: &[&str],
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let mut request = client
+        .req(method, endpoint)
+        .header(create_auth_header_with_roles(
+            "test-kid",
+            "test-user",
+            "foo",
+            roles,
+        ));
+    if !body.is_null() {
+        request = request.header(ContentType::JSON).body(body.to_string());
+    }
+    let response = request.dispatch().await;
+
+    assert_eq!(response.status(), Status::Ok);
+}
+
 async fn assert_response_is_unauthorized(response: LocalResponse<'_>) {
     assert_eq!(response.status(), Status::Unauthorized);
     assert_eq!(response.content_type(), Some(ContentType::JSON));
@@ -0,0 +1,64 @@
+pub mod common;
+
+use rocket::http::{Header, Status};
+use rstest::rstest;
+
+use common::{infrastructure, Infrastructure};
+
+#[rstest]
+#[tokio::test]
+async fn test_get_metrics(#[future] infrastructure: Infrastructure) {
+    std::env::set_var("METRICS_SCRAPE_TOKEN", "scrape-token");
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/metrics")
+        .header(Header::new("Authorization", "Bearer scrape-token"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.headers().get_one("Content-Type"),
+        Some("text/plain; version=0.0.4")
+    );
+
+    let metrics = response.into_string().await.unwrap();
+    let labels = "monitor_id=\"c1bf0515-df39-448b-aa95-686360a33b36\",\
+        monitor_name=\"db-backup.py\",tenant=\"foo\"";
+    for expected in [
+        format!("cronmon_monitor_last_job_start_timestamp_seconds{{{labels}}} 1714522800"),
+        format!("cronmon_monitor_last_job_finish_timestamp_seconds{{{labels}}} 1714524540"),
+        format!("cronmon_monitor_last_job_duration_seconds{{{labels}}} 2340"),
+        format!("cronmon_monitor_jobs_in_progress{{{labels}}} 1"),
+    ] {
+        assert!(
+            metrics.contains(&expected),
+            "{expected} missing from {metrics}"
+        );
+    }
+    // Metrics cover every tenant.
+    assert!(metrics.contains("monitor_name=\"data-snapshot.py\",tenant=\"bar\""));
+}
+
+#[rstest]
+#[case(None)]
+#[case(Some("Bearer wrong-token"))]
+#[tokio::test]
+async fn test_get_metrics_without_scrape_token(
+    #[case] authorization: Option<&str>,
+    #[future] infrastructure: Infrastructure,
+) {
+    std::env::set_var("METRICS_SCRAPE_TOKEN", "scrape-token");
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let mut request = client.get("/metrics");
+    if let Some(authorization) = authorization {
+        request = request.header(Header::new("Authorization", authorization.to_owned()));
+    }
+    let response = request.dispatch().await;
+
+    assert_eq!(response.status(), Status::Unauthorized);
+}
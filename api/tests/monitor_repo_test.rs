@@ -1,16 +1,23 @@
 pub mod common;
 
+use std::collections::BTreeMap;
+
 use pretty_assertions::assert_eq;
 use rstest::rstest;
+use serde_json::json;
 use uuid::Uuid;
 
 use test_utils::{gen_datetime, gen_uuid};
 
-use cron_mon_api::domain::models::Monitor;
+use cron_mon_api::domain::models::{
+    AutoTune, JobMetadata, JobOrder, JobQuery, JobRetention, JobStatus, Labels, Monitor,
+    MonitorKind, MonitorMetrics, MonitorSettings, Schedule,
+};
 use cron_mon_api::errors::Error;
 use cron_mon_api::infrastructure::models::{job::JobData, monitor::MonitorData};
 use cron_mon_api::infrastructure::repositories::monitor::{
-    GetWithErroneousJobs, MonitorRepository,
+    GetAutoTuned, GetJobHistory, GetMetrics, GetScheduled, GetWithErroneousJobs, MonitorRepository,
+    PruneJobs,
 };
 use cron_mon_api::infrastructure::repositories::Repository;
 
@@ -97,14 +104,981 @@ async fn test_get_with_erroneous_jobs(#[future] infrastructure: Infrastructure)
     );
 }
 
+#[tokio::test]
+async fn test_get_with_erroneous_jobs_pending_resolution() {
+    let monitor = |monitor_id: &str, name: &str| MonitorData {
+        monitor_id: gen_uuid(monitor_id),
+        tenant: "foo".to_string(),
+        name: name.to_string(),
+        expected_duration: 900,
+        grace_duration: 300,
+        schedule: None,
+        schedule_timezone: None,
+        missed_start_cutoff: None,
+        kind: "job".to_owned(),
+        heartbeat_period: None,
+        alert_threshold_consecutive: 1,
+        alert_threshold_window_failures: None,
+        alert_threshold_window_runs: None,
+        retention_max_age_days: None,
+        retention_max_jobs: None,
+        paused: false,
+        resume_at: None,
+        maintenance_windows: json!([]),
+        auto_tune_min_expected: None,
+        auto_tune_max_expected: None,
+        auto_tune_min_grace: None,
+        auto_tune_max_grace: None,
+        anomaly_min_duration: None,
+        anomaly_max_deviations: None,
+        concurrency_policy: "allow".to_owned(),
+    };
+    let job = |job_id: &str, monitor_id: &str, start: &str, end: &str, succeeded: bool| JobData {
+        job_id: gen_uuid(job_id),
+        monitor_id: gen_uuid(monitor_id),
+        start_time: gen_datetime(&format!("2024-05-01T{start}:00.000")),
+        max_end_time: gen_datetime(&format!("2024-05-01T{start}:00.000")),
+        end_time: Some(gen_datetime(&format!("2024-05-01T{end}:00.000"))),
+        succeeded: Some(succeeded),
+        output: None,
+        late_alert_sent: start != end,
+        error_alert_sent: !succeeded,
+        late_alert_resolved: true,
+        error_alert_resolved: false,
+        recovery_alert_sent: false,
+        during_maintenance: false,
+        below_threshold: false,
+        anomaly_checked: false,
+        overlapped: false,
+        overlap_alert_sent: false,
+        exit_code: None,
+        hostname: None,
+        command: None,
+        version: None,
+        metrics: json!({}),
+    };
+
+    let infra = Infrastructure::from_seeds(
+        vec![
+            monitor("027820c0-ab21-47cd-bff0-bc298b3e6646", "recovered"),
+            monitor("c1bf0515-df39-448b-aa95-686360a33b36", "still-failing"),
+            monitor("f0b291fe-bd41-4787-bc2d-1329903f7a6a", "late-and-finished"),
+        ],
+        vec![
+            // An errored job, followed by a successful one.
+            job(
+                "73f01432-bf9b-4dc0-8d68-aa7289725bf4",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "00:10",
+                "00:20",
+                false,
+            ),
+            job(
+                "a3e1a4a0-9d6c-4a8d-9b4c-7a3f0f5f3f0e",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "01:10",
+                "01:20",
+                true,
+            ),
+            // A successful job, followed by an errored one.
+            job(
+                "8106bab7-d643-4ede-bd92-60c79f787344",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                "00:10",
+                "00:20",
+                true,
+            ),
+            job(
+                "c1893113-66d7-4707-9a51-c8be46287b2c",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                "01:10",
+                "01:20",
+                false,
+            ),
+            // A late job that has finished, but whose late alert hasn't been resolved.
+            JobData {
+                late_alert_resolved: false,
+                ..job(
+                    "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+                    "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
+                    "00:10",
+                    "00:20",
+                    true,
+                )
+            },
+        ],
+        vec![],
+        (vec![], vec![], vec![]),
+    )
+    .await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let monitors = repo.get_with_erroneous_jobs().await.unwrap();
+    let mut names: Vec<String> = monitors
+        .iter()
+        .map(|monitor| monitor.name.clone())
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["late-and-finished".to_owned(), "recovered".to_owned()]
+    );
+}
+
+#[tokio::test]
+async fn test_get_with_erroneous_jobs_pending_recovery() {
+    let monitor = |monitor_id: &str, name: &str| MonitorData {
+        monitor_id: gen_uuid(monitor_id),
+        tenant: "foo".to_string(),
+        name: name.to_string(),
+        expected_duration: 900,
+        grace_duration: 300,
+        schedule: None,
+        schedule_timezone: None,
+        missed_start_cutoff: None,
+        kind: "job".to_owned(),
+        heartbeat_period: None,
+        alert_threshold_consecutive: 1,
+        alert_threshold_window_failures: None,
+        alert_threshold_window_runs: None,
+        retention_max_age_days: None,
+        retention_max_jobs: None,
+        paused: false,
+        resume_at: None,
+        maintenance_windows: json!([]),
+        auto_tune_min_expected: None,
+        auto_tune_max_expected: None,
+        auto_tune_min_grace: None,
+        auto_tune_max_grace: None,
+        anomaly_min_duration: None,
+        anomaly_max_deviations: None,
+        concurrency_policy: "allow".to_owned(),
+    };
+    // All jobs have been alerted on where needed (jobs finishing after they started are late),
+    // so only recoveries should be picked up.
+    let job = |job_id: &str, monitor_id: &str, start: &str, end: &str, succeeded: bool| JobData {
+        job_id: gen_uuid(job_id),
+        monitor_id: gen_uuid(monitor_id),
+        start_time: gen_datetime(&format!("2024-05-01T{start}:00.000")),
+        max_end_time: gen_datetime(&format!("2024-05-01T{start}:30.000")),
+        end_time: Some(gen_datetime(&format!("2024-05-01T{end}:00.000"))),
+        succeeded: Some(succeeded),
+        output: None,
+        late_alert_sent: start != end,
+        error_alert_sent: !succeeded,
+        late_alert_resolved: true,
+        error_alert_resolved: true,
+        recovery_alert_sent: false,
+        during_maintenance: false,
+        below_threshold: false,
+        anomaly_checked: false,
+        overlapped: false,
+        overlap_alert_sent: false,
+        exit_code: None,
+        hostname: None,
+        command: None,
+        version: None,
+        metrics: json!({}),
+    };
+
+    let infra = Infrastructure::from_seeds(
+        vec![
+            monitor("027820c0-ab21-47cd-bff0-bc298b3e6646", "recovered"),
+            monitor("c1bf0515-df39-448b-aa95-686360a33b36", "recovered-earlier"),
+            monitor("f0b291fe-bd41-4787-bc2d-1329903f7a6a", "recovered-late"),
+        ],
+        vec![
+            // An errored job, followed by a successful one.
+            job(
+                "73f01432-bf9b-4dc0-8d68-aa7289725bf4",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "00:10",
+                "00:20",
+                false,
+            ),
+            job(
+                "a3e1a4a0-9d6c-4a8d-9b4c-7a3f0f5f3f0e",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "01:10",
+                "01:10",
+                true,
+            ),
+            // An errored job, followed by two successful ones - the first of which has already
+            // been alerted on.
+            job(
+                "8106bab7-d643-4ede-bd92-60c79f787344",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                "00:10",
+                "00:20",
+                false,
+            ),
+            JobData {
+                recovery_alert_sent: true,
+                during_maintenance: false,
+                ..job(
+                    "c1893113-66d7-4707-9a51-c8be46287b2c",
+                    "c1bf0515-df39-448b-aa95-686360a33b36",
+                    "01:10",
+                    "01:10",
+                    true,
+                )
+            },
+            job(
+                "2a09c819-ed8c-4e3a-b085-889f3f475c02",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                "02:10",
+                "02:10",
+                true,
+            ),
+            // An errored job, followed by a successful one that finished late.
+            job(
+                "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+                "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
+                "00:10",
+                "00:20",
+                false,
+            ),
+            job(
+                "db610603-5094-49a4-8838-204103cd5b78",
+                "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
+                "01:10",
+                "01:20",
+                true,
+            ),
+        ],
+        vec![],
+        (vec![], vec![], vec![]),
+    )
+    .await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let monitors = repo.get_with_erroneous_jobs().await.unwrap();
+    let names: Vec<String> = monitors
+        .iter()
+        .map(|monitor| monitor.name.clone())
+        .collect();
+    assert_eq!(names, vec!["recovered".to_owned()]);
+}
+
+#[tokio::test]
+async fn test_get_with_erroneous_jobs_pending_anomaly_checks() {
+    let monitor = |monitor_id: &str, name: &str, min_duration: Option<i32>| MonitorData {
+        monitor_id: gen_uuid(monitor_id),
+        tenant: "foo".to_string(),
+        name: name.to_string(),
+        expected_duration: 900,
+        grace_duration: 300,
+        schedule: None,
+        schedule_timezone: None,
+        missed_start_cutoff: None,
+        kind: "job".to_owned(),
+        heartbeat_period: None,
+        alert_threshold_consecutive: 1,
+        alert_threshold_window_failures: None,
+        alert_threshold_window_runs: None,
+        retention_max_age_days: None,
+        retention_max_jobs: None,
+        paused: false,
+        resume_at: None,
+        maintenance_windows: json!([]),
+        auto_tune_min_expected: None,
+        auto_tune_max_expected: None,
+        auto_tune_min_grace: None,
+        auto_tune_max_grace: None,
+        anomaly_min_duration: min_duration,
+        anomaly_max_deviations: None,
+        concurrency_policy: "allow".to_owned(),
+    };
+    // All jobs finished successfully and on time, so only anomaly checks should be picked up.
+    let job = |job_id: &str, monitor_id: &str, anomaly_checked: bool| JobData {
+        job_id: gen_uuid(job_id),
+        monitor_id: gen_uuid(monitor_id),
+        start_time: gen_datetime("2024-05-01T00:10:00.000"),
+        max_end_time: gen_datetime("2024-05-01T00:40:00.000"),
+        end_time: Some(gen_datetime("2024-05-01T00:10:05.000")),
+        succeeded: Some(true),
+        output: None,
+        late_alert_sent: false,
+        error_alert_sent: false,
+        late_alert_resolved: false,
+        error_alert_resolved: false,
+        recovery_alert_sent: false,
+        during_maintenance: false,
+        below_threshold: false,
+        anomaly_checked,
+        overlapped: false,
+        overlap_alert_sent: false,
+        exit_code: None,
+        hostname: None,
+        command: None,
+        version: None,
+        metrics: json!({}),
+    };
+
+    let infra = Infrastructure::from_seeds(
+        vec![
+            monitor(
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "unchecked",
+                Some(60),
+            ),
+            monitor("c1bf0515-df39-448b-aa95-686360a33b36", "checked", Some(60)),
+            monitor("f0b291fe-bd41-4787-bc2d-1329903f7a6a", "undetected", None),
+        ],
+        vec![
+            job(
+                "73f01432-bf9b-4dc0-8d68-aa7289725bf4",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                false,
+            ),
+            job(
+                "8106bab7-d643-4ede-bd92-60c79f787344",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                true,
+            ),
+            job(
+                "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+                "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
+                false,
+            ),
+        ],
+        vec![],
+        (vec![], vec![], vec![]),
+    )
+    .await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let monitors = repo.get_with_erroneous_jobs().await.unwrap();
+    let names: Vec<String> = monitors
+        .iter()
+        .map(|monitor| monitor.name.clone())
+        .collect();
+    assert_eq!(names, vec!["unchecked".to_owned()]);
+}
+
+#[tokio::test]
+async fn test_get_with_erroneous_jobs_pending_overlap_alerts() {
+    let monitor = |monitor_id: &str, name: &str, concurrency_policy: &str| MonitorData {
+        monitor_id: gen_uuid(monitor_id),
+        tenant: "foo".to_string(),
+        name: name.to_string(),
+        expected_duration: 900,
+        grace_duration: 300,
+        schedule: None,
+        schedule_timezone: None,
+        missed_start_cutoff: None,
+        kind: "job".to_owned(),
+        heartbeat_period: None,
+        alert_threshold_consecutive: 1,
+        alert_threshold_window_failures: None,
+        alert_threshold_window_runs: None,
+        retention_max_age_days: None,
+        retention_max_jobs: None,
+        paused: false,
+        resume_at: None,
+        maintenance_windows: json!([]),
+        auto_tune_min_expected: None,
+        auto_tune_max_expected: None,
+        auto_tune_min_grace: None,
+        auto_tune_max_grace: None,
+        anomaly_min_duration: None,
+        anomaly_max_deviations: None,
+        concurrency_policy: concurrency_policy.to_owned(),
+    };
+    // All jobs overlapped and finished successfully on time, so only overlaps that are yet to be
+    // alerted on should be picked up.
+    let job = |job_id: &str, monitor_id: &str, overlap_alert_sent: bool| JobData {
+        job_id: gen_uuid(job_id),
+        monitor_id: gen_uuid(monitor_id),
+        start_time: gen_datetime("2024-05-01T00:10:00.000"),
+        max_end_time: gen_datetime("2024-05-01T00:40:00.000"),
+        end_time: Some(gen_datetime("2024-05-01T00:20:00.000")),
+        succeeded: Some(true),
+        output: None,
+        late_alert_sent: false,
+        error_alert_sent: false,
+        late_alert_resolved: false,
+        error_alert_resolved: false,
+        recovery_alert_sent: false,
+        during_maintenance: false,
+        below_threshold: false,
+        anomaly_checked: true,
+        overlapped: true,
+        overlap_alert_sent,
+        exit_code: None,
+        hostname: None,
+        command: None,
+        version: None,
+        metrics: json!({}),
+    };
+
+    let infra = Infrastructure::from_seeds(
+        vec![
+            monitor("027820c0-ab21-47cd-bff0-bc298b3e6646", "pending", "alert"),
+            monitor("c1bf0515-df39-448b-aa95-686360a33b36", "alerted", "alert"),
+            monitor("f0b291fe-bd41-4787-bc2d-1329903f7a6a", "allowed", "allow"),
+        ],
+        vec![
+            job(
+                "73f01432-bf9b-4dc0-8d68-aa7289725bf4",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                false,
+            ),
+            job(
+                "8106bab7-d643-4ede-bd92-60c79f787344",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                true,
+            ),
+            job(
+                "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+                "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
+                false,
+            ),
+        ],
+        vec![],
+        (vec![], vec![], vec![]),
+    )
+    .await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let monitors = repo.get_with_erroneous_jobs().await.unwrap();
+    let names: Vec<String> = monitors
+        .iter()
+        .map(|monitor| monitor.name.clone())
+        .collect();
+    assert_eq!(names, vec!["pending".to_owned()]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_scheduled(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let scheduled_monitors = repo.get_scheduled().await.unwrap();
+
+    assert_eq!(scheduled_monitors.len(), 1);
+    let monitor = &scheduled_monitors[0];
+    assert_eq!(monitor.name, "data-snapshot.py");
+    assert_eq!(
+        monitor.schedule,
+        Some(Schedule::new("0 3 * * *", Some("Europe/London")).unwrap())
+    );
+    assert_eq!(
+        monitor.missed_start_cutoff,
+        Some(gen_datetime("2024-05-01T02:00:00.000"))
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_auto_tuned(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let auto_tuned_monitors = repo.get_auto_tuned().await.unwrap();
+
+    assert_eq!(auto_tuned_monitors.len(), 1);
+    let monitor = &auto_tuned_monitors[0];
+    assert_eq!(monitor.name, "data-snapshot.py");
+    assert_eq!(
+        monitor.auto_tune,
+        Some(AutoTune::new(1800, 7200, 0, 1800).unwrap())
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_job_history_pages(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+    let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+
+    let mut query = JobQuery {
+        limit: 2,
+        ..Default::default()
+    };
+    let first_page = repo
+        .get_job_history(monitor_id, "foo", &query)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(first_page.total, 3);
+    assert_eq!(
+        first_page
+            .jobs
+            .iter()
+            .map(|job| job.job_id)
+            .collect::<Vec<Uuid>>(),
+        vec![
+            gen_uuid("9d4e2d69-af63-4c1e-8639-60cb2683aee5"),
+            gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+        ]
+    );
+    assert_eq!(
+        first_page.next_cursor,
+        Some(gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"))
+    );
+
+    query.cursor = first_page.next_cursor;
+    let second_page = repo
+        .get_job_history(monitor_id, "foo", &query)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(second_page.total, 3);
+    assert_eq!(
+        second_page
+            .jobs
+            .iter()
+            .map(|job| job.job_id)
+            .collect::<Vec<Uuid>>(),
+        vec![gen_uuid("c1893113-66d7-4707-9a51-c8be46287b2c")]
+    );
+    assert_eq!(second_page.next_cursor, None);
+}
+
+#[rstest]
+#[case::oldest_first(
+    JobQuery { order: JobOrder::OldestFirst, ..Default::default() },
+    vec![
+        "c1893113-66d7-4707-9a51-c8be46287b2c",
+        "8106bab7-d643-4ede-bd92-60c79f787344",
+        "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+    ]
+)]
+#[case::succeeded(
+    JobQuery { status: Some(JobStatus::Succeeded), ..Default::default() },
+    vec!["8106bab7-d643-4ede-bd92-60c79f787344"]
+)]
+#[case::failed(
+    JobQuery { status: Some(JobStatus::Failed), ..Default::default() },
+    vec!["c1893113-66d7-4707-9a51-c8be46287b2c"]
+)]
+#[case::late(
+    JobQuery { status: Some(JobStatus::Late), ..Default::default() },
+    vec!["9d4e2d69-af63-4c1e-8639-60cb2683aee5"]
+)]
+#[case::in_progress(
+    JobQuery { status: Some(JobStatus::InProgress), ..Default::default() },
+    vec!["9d4e2d69-af63-4c1e-8639-60cb2683aee5"]
+)]
+#[case::time_range(
+    JobQuery {
+        started_after: Some(gen_datetime("2024-05-01T00:10:00.000")),
+        started_before: Some(gen_datetime("2024-05-01T00:20:00.000")),
+        ..Default::default()
+    },
+    vec!["8106bab7-d643-4ede-bd92-60c79f787344"]
+)]
+#[tokio::test]
+async fn test_get_job_history_filters(
+    #[case] query: JobQuery,
+    #[case] expected_job_ids: Vec<&str>,
+    #[future] infrastructure: Infrastructure,
+) {
+    let infra = infrastructure.await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let page = repo
+        .get_job_history(
+            gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
+            "foo",
+            &query,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(page.total, expected_job_ids.len());
+    assert_eq!(
+        page.jobs
+            .iter()
+            .map(|job| job.job_id)
+            .collect::<Vec<Uuid>>(),
+        expected_job_ids
+            .into_iter()
+            .map(gen_uuid)
+            .collect::<Vec<Uuid>>()
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_job_history_for_missing_monitor_or_cursor(
+    #[future] infrastructure: Infrastructure,
+) {
+    let infra = infrastructure.await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+    let monitor_id = gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36");
+
+    // Monitors in other tenants aren't found.
+    let result = repo
+        .get_job_history(monitor_id, "bar", &JobQuery::default())
+        .await;
+    assert_eq!(result, Ok(None));
+
+    // Nor are cursors from other monitors.
+    let cursor = gen_uuid("2a09c819-ed8c-4e3a-b085-889f3f475c02");
+    let result = repo
+        .get_job_history(
+            monitor_id,
+            "foo",
+            &JobQuery {
+                cursor: Some(cursor),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert_eq!(result, Err(Error::JobNotFound(monitor_id, cursor)));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_metrics(#[future] infrastructure: Infrastructure) {
+    let infra = infrastructure.await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    // Finish db-backup.py's in progress job (which is long overdue), then start another.
+    let mut monitor = repo
+        .get(gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"), "foo")
+        .await
+        .unwrap()
+        .unwrap();
+    monitor
+        .finish_job(
+            gen_uuid("9d4e2d69-af63-4c1e-8639-60cb2683aee5"),
+            false,
+            None,
+            JobMetadata::default(),
+        )
+        .unwrap();
+    let new_job = monitor.start_job().unwrap();
+    repo.save(&monitor).await.unwrap();
+    // Saving again without any changes doesn't count anything twice.
+    repo.save(&monitor).await.unwrap();
+
+    let metrics = repo.get_metrics().await.unwrap();
+    let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "init-philanges",
+            "db-backup.py",
+            "data-snapshot.py",
+            "generate-orders.sh"
+        ]
+    );
+
+    let db_backup = &metrics[1];
+    assert_eq!(
+        db_backup
+            .last_started
+            .map(|start_time| start_time.and_utc().timestamp_micros()),
+        Some(new_job.start_time.and_utc().timestamp_micros())
+    );
+    assert!(db_backup.last_finished.unwrap() > gen_datetime("2024-05-01T00:49:00"));
+    assert!(db_backup.last_duration.unwrap() > 0);
+    assert_eq!(
+        (
+            db_backup.jobs_started,
+            db_backup.jobs_succeeded,
+            db_backup.jobs_failed,
+            db_backup.jobs_late,
+            db_backup.jobs_in_progress
+        ),
+        (1, 0, 1, 1, 1)
+    );
+
+    assert_eq!(
+        metrics[3],
+        MonitorMetrics {
+            monitor_id: gen_uuid("f0b291fe-bd41-4787-bc2d-1329903f7a6a"),
+            tenant: "foo".to_owned(),
+            name: "generate-orders.sh".to_owned(),
+            last_started: Some(gen_datetime("2024-05-01T00:00:00")),
+            last_finished: None,
+            last_duration: None,
+            jobs_started: 0,
+            jobs_succeeded: 0,
+            jobs_failed: 0,
+            jobs_late: 0,
+            jobs_in_progress: 2,
+        }
+    );
+    assert_eq!(
+        metrics[0],
+        MonitorMetrics {
+            monitor_id: gen_uuid("a04376e2-0fb5-4949-9744-7c5d0a50b411"),
+            tenant: "foo".to_owned(),
+            name: "init-philanges".to_owned(),
+            last_started: None,
+            last_finished: None,
+            last_duration: None,
+            jobs_started: 0,
+            jobs_succeeded: 0,
+            jobs_failed: 0,
+            jobs_late: 0,
+            jobs_in_progress: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_prune_jobs() {
+    let monitor =
+        |monitor_id: &str, max_age_days: Option<i32>, max_jobs: Option<i32>| MonitorData {
+            monitor_id: gen_uuid(monitor_id),
+            tenant: "foo".to_string(),
+            name: "prune-me.sh".to_string(),
+            expected_duration: 900,
+            grace_duration: 300,
+            schedule: None,
+            schedule_timezone: None,
+            missed_start_cutoff: None,
+            kind: "job".to_owned(),
+            heartbeat_period: None,
+            alert_threshold_consecutive: 1,
+            alert_threshold_window_failures: None,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: max_age_days,
+            retention_max_jobs: max_jobs,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: None,
+            auto_tune_max_expected: None,
+            auto_tune_min_grace: None,
+            auto_tune_max_grace: None,
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
+        };
+    // Jobs that finish after they started are late, and all jobs are well over a day old.
+    let job = |job_id: &str, monitor_id: &str, start: &str, end: &str, succeeded: bool| JobData {
+        job_id: gen_uuid(job_id),
+        monitor_id: gen_uuid(monitor_id),
+        start_time: gen_datetime(&format!("2024-05-01T{start}:00.000")),
+        max_end_time: gen_datetime(&format!("2024-05-01T{start}:30.000")),
+        end_time: Some(gen_datetime(&format!("2024-05-01T{end}:00.000"))),
+        succeeded: Some(succeeded),
+        output: None,
+        late_alert_sent: false,
+        error_alert_sent: false,
+        late_alert_resolved: false,
+        error_alert_resolved: false,
+        recovery_alert_sent: false,
+        during_maintenance: false,
+        below_threshold: false,
+        anomaly_checked: false,
+        overlapped: false,
+        overlap_alert_sent: false,
+        exit_code: None,
+        hostname: None,
+        command: None,
+        version: None,
+        metrics: json!({}),
+    };
+
+    let infra = Infrastructure::from_seeds(
+        vec![
+            monitor("027820c0-ab21-47cd-bff0-bc298b3e6646", None, Some(2)),
+            monitor("c1bf0515-df39-448b-aa95-686360a33b36", Some(1), None),
+            monitor("f0b291fe-bd41-4787-bc2d-1329903f7a6a", None, None),
+        ],
+        vec![
+            // Only the 2 most recent jobs are kept in this Monitor.
+            job(
+                "73f01432-bf9b-4dc0-8d68-aa7289725bf4",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "00:10",
+                "00:10",
+                true,
+            ),
+            JobData {
+                error_alert_sent: true,
+                error_alert_resolved: true,
+                ..job(
+                    "a3e1a4a0-9d6c-4a8d-9b4c-7a3f0f5f3f0e",
+                    "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                    "00:20",
+                    "00:20",
+                    false,
+                )
+            },
+            // Errored, but yet to be alerted on.
+            job(
+                "8106bab7-d643-4ede-bd92-60c79f787344",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "00:30",
+                "00:30",
+                false,
+            ),
+            job(
+                "c1893113-66d7-4707-9a51-c8be46287b2c",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "00:40",
+                "00:40",
+                true,
+            ),
+            job(
+                "2a09c819-ed8c-4e3a-b085-889f3f475c02",
+                "027820c0-ab21-47cd-bff0-bc298b3e6646",
+                "00:50",
+                "00:50",
+                true,
+            ),
+            // Only jobs from the last day are kept in this Monitor.
+            job(
+                "9d4e2d69-af63-4c1e-8639-60cb2683aee5",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                "00:00",
+                "00:00",
+                true,
+            ),
+            // Late, and alerted on without being resolved.
+            JobData {
+                late_alert_sent: true,
+                ..job(
+                    "db610603-5094-49a4-8838-204103cd5b78",
+                    "c1bf0515-df39-448b-aa95-686360a33b36",
+                    "00:05",
+                    "00:07",
+                    true,
+                )
+            },
+            // Errored and alerted on, followed by a recovery that's yet to be alerted on.
+            JobData {
+                error_alert_sent: true,
+                error_alert_resolved: true,
+                ..job(
+                    "4bd8a5b3-54e1-4b1b-9c5d-2bb1d81d3c4a",
+                    "c1bf0515-df39-448b-aa95-686360a33b36",
+                    "00:10",
+                    "00:10",
+                    false,
+                )
+            },
+            job(
+                "e9b0b2c5-6a5e-4b47-a3f5-0f0e0e9b0a51",
+                "c1bf0515-df39-448b-aa95-686360a33b36",
+                "00:20",
+                "00:20",
+                true,
+            ),
+            // Still in progress.
+            JobData {
+                end_time: None,
+                succeeded: None,
+                ..job(
+                    "5a4b7ad5-0b8c-4f8c-8d5f-3b8a0f6c7e21",
+                    "c1bf0515-df39-448b-aa95-686360a33b36",
+                    "00:30",
+                    "00:30",
+                    true,
+                )
+            },
+            // This Monitor keeps its jobs forever.
+            job(
+                "0f7a3bd4-0c63-4a59-9b4e-2f6d1e1f8b17",
+                "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
+                "00:00",
+                "00:00",
+                true,
+            ),
+        ],
+        vec![],
+        (vec![], vec![], vec![]),
+    )
+    .await;
+    let mut repo = MonitorRepository::new(&infra.pool);
+
+    let retentions = repo.get_retentions().await.unwrap();
+    assert_eq!(
+        retentions,
+        vec![
+            (
+                gen_uuid("027820c0-ab21-47cd-bff0-bc298b3e6646"),
+                JobRetention::new(None, Some(2)).unwrap()
+            ),
+            (
+                gen_uuid("c1bf0515-df39-448b-aa95-686360a33b36"),
+                JobRetention::new(Some(1), None).unwrap()
+            ),
+        ]
+    );
+
+    // Jobs are pruned oldest first, a batch at a time.
+    let (monitor_id, retention) = &retentions[0];
+    assert_eq!(repo.prune_jobs(*monitor_id, retention, 1).await, Ok(1));
+    assert_eq!(repo.prune_jobs(*monitor_id, retention, 1).await, Ok(1));
+    assert_eq!(repo.prune_jobs(*monitor_id, retention, 1).await, Ok(0));
+    let (monitor_id, retention) = &retentions[1];
+    assert_eq!(repo.prune_jobs(*monitor_id, retention, 10).await, Ok(1));
+
+    let job_ids = |monitor_id: &str| {
+        let mut repo = MonitorRepository::new(&infra.pool);
+        let monitor_id = gen_uuid(monitor_id);
+        async move {
+            repo.get(monitor_id, "foo")
+                .await
+                .unwrap()
+                .unwrap()
+                .jobs
+                .iter()
+                .map(|job| job.job_id)
+                .collect::<Vec<Uuid>>()
+        }
+    };
+    assert_eq!(
+        job_ids("027820c0-ab21-47cd-bff0-bc298b3e6646").await,
+        vec![
+            gen_uuid("2a09c819-ed8c-4e3a-b085-889f3f475c02"),
+            gen_uuid("c1893113-66d7-4707-9a51-c8be46287b2c"),
+            gen_uuid("8106bab7-d643-4ede-bd92-60c79f787344"),
+        ]
+    );
+    assert_eq!(
+        job_ids("c1bf0515-df39-448b-aa95-686360a33b36").await,
+        vec![
+            gen_uuid("5a4b7ad5-0b8c-4f8c-8d5f-3b8a0f6c7e21"),
+            gen_uuid("e9b0b2c5-6a5e-4b47-a3f5-0f0e0e9b0a51"),
+            gen_uuid("4bd8a5b3-54e1-4b1b-9c5d-2bb1d81d3c4a"),
+            gen_uuid("db610603-5094-49a4-8838-204103cd5b78"),
+        ]
+    );
+    assert_eq!(
+        job_ids("f0b291fe-bd41-4787-bc2d-1329903f7a6a").await,
+        vec![gen_uuid("0f7a3bd4-0c63-4a59-9b4e-2f6d1e1f8b17")]
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_save(#[future] infrastructure: Infrastructure) {
     let infra = infrastructure.await;
     let mut repo = MonitorRepository::new(&infra.pool);
 
-    let mut new_monitor = Monitor::new("foo".to_owned(), "new-monitor".to_owned(), 100, 5);
-    let _ = new_monitor.start_job();
+    let mut new_monitor = Monitor::new(
+        "foo".to_owned(),
+        "new-monitor".to_owned(),
+        100,
+        5,
+        MonitorSettings {
+            schedule: Some(Schedule::new("*/5 * * * *", Some("Asia/Tokyo")).unwrap()),
+            kind: MonitorKind::Heartbeat { period: 300 },
+            labels: Labels::new(BTreeMap::from([
+                ("team".to_owned(), "payments".to_owned()),
+                ("env".to_owned(), "prod".to_owned()),
+            ]))
+            .unwrap(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let _ = new_monitor.start_job().unwrap();
     repo.save(&new_monitor).await.unwrap();
     assert_eq!(repo.all("foo").await.unwrap().len(), 4);
 
@@ -120,9 +1094,32 @@ async fn test_save(#[future] infrastructure: Infrastructure) {
         read_new_monitor.expected_duration
     );
     assert_eq!(new_monitor.grace_duration, read_new_monitor.grace_duration);
+    assert_eq!(new_monitor.kind, read_new_monitor.kind);
+    assert_eq!(new_monitor.schedule, read_new_monitor.schedule);
+    // Postgres only stores timestamps to microsecond precision.
+    assert_eq!(
+        new_monitor
+            .missed_start_cutoff
+            .map(|cutoff| cutoff.and_utc().timestamp_micros()),
+        read_new_monitor
+            .missed_start_cutoff
+            .map(|cutoff| cutoff.and_utc().timestamp_micros())
+    );
+    assert_eq!(new_monitor.labels, read_new_monitor.labels);
     assert_eq!(new_monitor.jobs.len(), 1);
     assert_eq!(read_new_monitor.jobs.len(), 1);
     assert_eq!(new_monitor.jobs[0].job_id, read_new_monitor.jobs[0].job_id);
+
+    // Changing a Monitor's labels replaces them.
+    let mut monitor = read_new_monitor;
+    monitor.labels =
+        Labels::new(BTreeMap::from([("team".to_owned(), "search".to_owned())])).unwrap();
+    repo.save(&monitor).await.unwrap();
+
+    let mut repo = MonitorRepository::new(&infra.pool);
+    let read_monitor = repo.get(monitor.monitor_id, "foo").await.unwrap().unwrap();
+    assert_eq!(read_monitor.labels.get("team"), Some("search"));
+    assert_eq!(read_monitor.labels.get("env"), None);
 }
 
 #[rstest]
@@ -151,6 +1148,26 @@ async fn test_loading_invalid_job() {
             name: "init-philanges".to_string(),
             expected_duration: 900,
             grace_duration: 300,
+            schedule: None,
+            schedule_timezone: None,
+            missed_start_cutoff: None,
+            kind: "job".to_owned(),
+            heartbeat_period: None,
+            alert_threshold_consecutive: 1,
+            alert_threshold_window_failures: None,
+            alert_threshold_window_runs: None,
+            retention_max_age_days: None,
+            retention_max_jobs: None,
+            paused: false,
+            resume_at: None,
+            maintenance_windows: json!([]),
+            auto_tune_min_expected: None,
+            auto_tune_max_expected: None,
+            auto_tune_min_grace: None,
+            auto_tune_max_grace: None,
+            anomaly_min_duration: None,
+            anomaly_max_deviations: None,
+            concurrency_policy: "allow".to_owned(),
         }],
         vec![JobData {
             job_id: gen_uuid("73f01432-bf9b-4dc0-8d68-aa7289725bf4"),
@@ -162,6 +1179,19 @@ async fn test_loading_invalid_job() {
             output: Some("Database successfully backed up".to_string()),
             late_alert_sent: false,
             error_alert_sent: false,
+            late_alert_resolved: false,
+            error_alert_resolved: false,
+            recovery_alert_sent: false,
+            during_maintenance: false,
+            below_threshold: false,
+            anomaly_checked: false,
+            overlapped: false,
+            overlap_alert_sent: false,
+            exit_code: None,
+            hostname: None,
+            command: None,
+            version: None,
+            metrics: json!({}),
         }],
         vec![],
         (vec![], vec![], vec![]),
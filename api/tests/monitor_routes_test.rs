@@ -34,6 +34,8 @@ async fn test_get_monitor_when_monitor_exists(#[future] infrastructure: Infrastr
     assert_eq!(monitor["name"], "db-backup.py");
     assert_eq!(monitor["expected_duration"], 1800);
     assert_eq!(monitor["grace_duration"], 600);
+    assert_eq!(monitor["kind"], json!({"type": "job"}));
+    assert_eq!(monitor["schedule"], Value::Null);
 
     let jobs = monitor["jobs"].as_array().unwrap();
     assert_eq!(jobs.len(), 3);
@@ -130,6 +132,17 @@ async fn test_list_monitors(#[future] infrastructure: Infrastructure) {
             {
               "expected_duration": 1800,
               "grace_duration": 600,
+              "kind": {"type": "job"},
+              "schedule": null,
+              "alert_threshold": {"consecutive": 1, "window": null},
+              "retention": null,
+              "labels": {},
+              "paused": false,
+              "resume_at": null,
+              "maintenance_windows": [],
+              "auto_tune": null,
+              "anomaly_detection": null,
+              "concurrency_policy": "allow",
               "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
               "name": "db-backup.py",
               "last_finished_job": {
@@ -139,6 +152,13 @@ async fn test_list_monitors(#[future] infrastructure: Infrastructure) {
                 "duration": 2340,
                 "in_progress": false,
                 "late": false,
+                "during_maintenance": false,
+                "overlapped": false,
+                "exit_code": Value::Null,
+                "hostname": Value::Null,
+                "command": Value::Null,
+                "version": Value::Null,
+                "metrics": {"bytes_backed_up": 2048.0},
                 "succeeded": true,
                 "output": "Database successfully backed up",
               },
@@ -149,6 +169,13 @@ async fn test_list_monitors(#[future] infrastructure: Infrastructure) {
                 "duration": Value::Null,
                 "in_progress": true,
                 "late": true,
+                "during_maintenance": false,
+                "overlapped": false,
+                "exit_code": Value::Null,
+                "hostname": Value::Null,
+                "command": Value::Null,
+                "version": Value::Null,
+                "metrics": {},
                 "succeeded": Value::Null,
                 "output": Value::Null
               }
@@ -156,6 +183,17 @@ async fn test_list_monitors(#[future] infrastructure: Infrastructure) {
             {
               "expected_duration": 5400,
               "grace_duration": 720,
+              "kind": {"type": "job"},
+              "schedule": null,
+              "alert_threshold": {"consecutive": 1, "window": null},
+              "retention": null,
+              "labels": {},
+              "paused": false,
+              "resume_at": null,
+              "maintenance_windows": [],
+              "auto_tune": null,
+              "anomaly_detection": null,
+              "concurrency_policy": "allow",
               "monitor_id": "f0b291fe-bd41-4787-bc2d-1329903f7a6a",
               "name": "generate-orders.sh",
               "last_started_job": {
@@ -165,6 +203,13 @@ async fn test_list_monitors(#[future] infrastructure: Infrastructure) {
                 "duration": Value::Null,
                 "in_progress": true,
                 "late": true,
+                "during_maintenance": false,
+                "overlapped": false,
+                "exit_code": Value::Null,
+                "hostname": Value::Null,
+                "command": Value::Null,
+                "version": Value::Null,
+                "metrics": {},
                 "succeeded": Value::Null,
                 "output": Value::Null,
               },
@@ -173,6 +218,17 @@ async fn test_list_monitors(#[future] infrastructure: Infrastructure) {
             {
               "expected_duration": 900,
               "grace_duration": 300,
+              "kind": {"type": "job"},
+              "schedule": null,
+              "alert_threshold": {"consecutive": 1, "window": null},
+              "retention": null,
+              "labels": {},
+              "paused": false,
+              "resume_at": null,
+              "maintenance_windows": [],
+              "auto_tune": null,
+              "anomaly_detection": null,
+              "concurrency_policy": "allow",
               "monitor_id": "a04376e2-0fb5-4949-9744-7c5d0a50b411",
               "name": "init-philanges",
               "last_started_job": Value::Null,
@@ -211,6 +267,7 @@ async fn test_add_monitor(#[future] infrastructure: Infrastructure) {
     assert_eq!(monitor["name"], "new-monitor");
     assert_eq!(monitor["expected_duration"], 500);
     assert_eq!(monitor["grace_duration"], 50);
+    assert_eq!(monitor["kind"], json!({"type": "job"}));
 
     let jobs = monitor["jobs"].as_array().unwrap();
     assert_eq!(jobs.len(), 0);
@@ -222,6 +279,769 @@ async fn test_add_monitor(#[future] infrastructure: Infrastructure) {
     );
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_schedule(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "schedule": {"expression": "0 3 * * *", "timezone": "Europe/London"}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor = &response_body["data"];
+    assert_eq!(
+        monitor["schedule"],
+        json!({"expression": "0 3 * * *", "timezone": "Europe/London"})
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_heartbeat_monitor(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "queue-consumer",
+            "expected_duration": 0,
+            "grace_duration": 60,
+            "kind": {"type": "heartbeat", "period": 300}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor = &response_body["data"];
+    assert_eq!(monitor["kind"], json!({"type": "heartbeat", "period": 300}));
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_alert_threshold(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "flaky-job",
+            "expected_duration": 300,
+            "grace_duration": 60,
+            "alert_threshold": {"consecutive": 2, "window": {"failures": 3, "runs": 5}}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor = &response_body["data"];
+    assert_eq!(
+        monitor["alert_threshold"],
+        json!({"consecutive": 2, "window": {"failures": 3, "runs": 5}})
+    );
+}
+
+#[rstest]
+#[case(0)]
+#[case(-300)]
+#[tokio::test]
+async fn test_add_heartbeat_monitor_with_invalid_period(
+    #[case] period: i32,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "kind": {"type": "heartbeat", "period": period}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[case(json!({"consecutive": 0}))]
+#[case(json!({"consecutive": 1, "window": {"failures": 6, "runs": 5}}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_alert_threshold(
+    #[case] alert_threshold: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "alert_threshold": alert_threshold
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_retention(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "chatty-job",
+            "expected_duration": 300,
+            "grace_duration": 60,
+            "retention": {"max_age_days": 30, "max_jobs": 1000}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor = &response_body["data"];
+    assert_eq!(
+        monitor["retention"],
+        json!({"max_age_days": 30, "max_jobs": 1000})
+    );
+}
+
+#[rstest]
+#[case(json!({}))]
+#[case(json!({"max_age_days": 0}))]
+#[case(json!({"max_jobs": -1}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_retention(
+    #[case] retention: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "retention": retention
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_auto_tune(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let auto_tune = json!({
+        "min_expected_duration": 60,
+        "max_expected_duration": 600,
+        "min_grace_duration": 0,
+        "max_grace_duration": 120
+    });
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "unpredictable-job",
+            "expected_duration": 300,
+            "grace_duration": 60,
+            "auto_tune": auto_tune
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["auto_tune"], auto_tune);
+}
+
+#[rstest]
+#[case(json!({
+    "min_expected_duration": 600,
+    "max_expected_duration": 60,
+    "min_grace_duration": 0,
+    "max_grace_duration": 120
+}))]
+#[case(json!({
+    "min_expected_duration": 60,
+    "max_expected_duration": 600,
+    "min_grace_duration": -1,
+    "max_grace_duration": 120
+}))]
+#[case(json!({"min_expected_duration": 60}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_auto_tune(
+    #[case] auto_tune: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "auto_tune": auto_tune
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_anomaly_detection(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "nightly-backup",
+            "expected_duration": 1800,
+            "grace_duration": 600,
+            "anomaly_detection": {"min_duration": 600}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(
+        response_body["data"]["anomaly_detection"],
+        json!({"min_duration": 600, "max_deviations": null})
+    );
+}
+
+#[rstest]
+#[case(json!({}))]
+#[case(json!({"min_duration": 0}))]
+#[case(json!({"max_deviations": -2.0}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_anomaly_detection(
+    #[case] anomaly_detection: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "anomaly_detection": anomaly_detection
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_concurrency_policy(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "nightly-backup",
+            "expected_duration": 1800,
+            "grace_duration": 600,
+            "concurrency_policy": "reject"
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["concurrency_policy"], "reject");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_concurrency_policy(
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "concurrency_policy": "queue"
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[case::with_jobs(
+    "c1bf0515-df39-448b-aa95-686360a33b36",
+    json!({
+        "stats": {
+            "job_count": 1,
+            "p50": 2340,
+            "p95": 2340,
+            "p99": 2340,
+            "max": 2340,
+            "std_dev": 0.0
+        },
+        "suggested": null
+    })
+)]
+#[case::without_jobs(
+    "a04376e2-0fb5-4949-9744-7c5d0a50b411",
+    json!({"stats": null, "suggested": null})
+)]
+#[tokio::test]
+async fn test_get_monitor_stats(
+    #[case] monitor_id: &str,
+    #[case] expected: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get(format!("/api/v1/monitors/{monitor_id}/stats"))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({"data": expected})
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_monitor_stats_when_monitor_does_not_exist(
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/monitors/cc6cf74e-b25d-4c8c-94a6-914e3f139c14/stats")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_job_metrics(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/job-metrics")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({"data": {
+            "job_count": 2,
+            "metrics": {
+                "bytes_backed_up": [
+                    {
+                        "job_id": "c1893113-66d7-4707-9a51-c8be46287b2c",
+                        "end_time": "2024-05-01T00:39:00",
+                        "value": 1024.0
+                    },
+                    {
+                        "job_id": "8106bab7-d643-4ede-bd92-60c79f787344",
+                        "end_time": "2024-05-01T00:49:00",
+                        "value": 2048.0
+                    }
+                ]
+            }
+        }})
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_get_job_metrics_when_monitor_does_not_exist(
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/monitors/cc6cf74e-b25d-4c8c-94a6-914e3f139c14/job-metrics")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_labels_and_filter_by_them(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "settle-payments",
+            "expected_duration": 300,
+            "grace_duration": 60,
+            "labels": {"team": "payments", "env": "prod"}
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor = &response_body["data"];
+    assert_eq!(
+        monitor["labels"],
+        json!({"env": "prod", "team": "payments"})
+    );
+
+    let response = client
+        .get("/api/v1/monitors?label_selector=team%3Dpayments,env!%3Ddev")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["paging"], json!({"total": 1}));
+    assert_eq!(response_body["data"][0]["name"], "settle-payments");
+
+    // Monitors without the label aren't selected by it, but are by its absence.
+    let response = client
+        .get("/api/v1/monitors?label_selector=!team")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["paging"], json!({"total": 3}));
+}
+
+#[rstest]
+#[case("team%3D")]
+#[case("my%20team")]
+#[tokio::test]
+async fn test_list_monitors_with_invalid_label_selector(
+    #[case] label_selector: &str,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get(format!("/api/v1/monitors?label_selector={label_selector}"))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
+
+#[rstest]
+#[case(json!({"team": ""}))]
+#[case(json!({"my team": "payments"}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_labels(
+    #[case] labels: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "labels": labels
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_monitor_with_maintenance_windows(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "maintenance_windows": [
+                {"expression": "0 2 * * 0", "timezone": "Europe/London", "duration_minutes": 60}
+            ]
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    let monitor_id = response_body["data"]["monitor_id"].as_str().unwrap();
+
+    // Ensure this has persisted.
+    let response = client
+        .get(format!("/api/v1/monitors/{monitor_id}"))
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(
+        response_body["data"]["maintenance_windows"],
+        json!([
+            {"expression": "0 2 * * 0", "timezone": "Europe/London", "duration_minutes": 60}
+        ])
+    );
+}
+
+#[rstest]
+#[case(json!({"expression": "not a cron expression", "duration_minutes": 60}))]
+#[case(json!({"expression": "0 2 * * 0", "duration_minutes": 0}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_maintenance_windows(
+    #[case] maintenance_window: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "maintenance_windows": [maintenance_window]
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_pausing_and_resuming_monitors(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/pause")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({"resume_at": "2999-01-01T00:00:00"}))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["paused"], true);
+    assert_eq!(response_body["data"]["resume_at"], "2999-01-01T00:00:00");
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/resume")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    // Ensure this has persisted.
+    let response = client
+        .get("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .dispatch()
+        .await;
+
+    let response_body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(response_body["data"]["paused"], false);
+    assert_eq!(response_body["data"]["resume_at"], Value::Null);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_pausing_monitors_until_the_past(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/pause")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({"resume_at": "2024-05-01T00:00:00"}))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
+
+#[rstest]
+#[case(json!({"expression": "not a cron expression"}))]
+#[case(json!({"expression": "0 3 * * *", "timezone": "Not/A_Timezone"}))]
+#[tokio::test]
+async fn test_add_monitor_with_invalid_schedule(
+    #[case] schedule: Value,
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let num_monitors = get_num_monitors("test-kid", "foo", &client).await;
+
+    let response = client
+        .post("/api/v1/monitors")
+        .header(create_auth_header("test-kid", "test-user", "foo"))
+        .json(&json!({
+            "name": "new-monitor",
+            "expected_duration": 500,
+            "grace_duration": 50,
+            "schedule": schedule
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        get_num_monitors("test-kid", "foo", &client).await,
+        num_monitors
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_modify_monitor_when_monitor_exists(#[future] infrastructure: Infrastructure) {
@@ -233,7 +1053,12 @@ async fn test_modify_monitor_when_monitor_exists(#[future] infrastructure: Infra
     let response = client
         .patch("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36")
         .header(create_auth_header("test-kid", "test-user", "foo"))
-        .json(&json!({"name": "new-name", "expected_duration": 100, "grace_duration": 10}))
+        .json(&json!({
+            "name": "new-name",
+            "expected_duration": 100,
+            "grace_duration": 10,
+            "schedule": {"expression": "*/30 * * * *"}
+        }))
         .dispatch()
         .await;
 
@@ -262,6 +1087,10 @@ async fn test_modify_monitor_when_monitor_exists(#[future] infrastructure: Infra
     assert_eq!(monitor["name"], "new-name");
     assert_eq!(monitor["expected_duration"], 100);
     assert_eq!(monitor["grace_duration"], 10);
+    assert_eq!(
+        monitor["schedule"],
+        json!({"expression": "*/30 * * * *", "timezone": "UTC"})
+    );
 }
 
 #[rstest]
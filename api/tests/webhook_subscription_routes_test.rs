@@ -0,0 +1,403 @@
+pub mod common;
+
+use pretty_assertions::assert_eq;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use rstest::rstest;
+use serde_json::{json, Value};
+use wiremock::matchers::{header, header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use cron_mon_api::application::services::get_deliver_webhooks_service;
+use test_utils::{is_datetime, is_uuid};
+
+use common::{create_auth_header, create_auth_header_with_roles, infrastructure, Infrastructure};
+
+/// Create a webhook subscription for tenant foo, returning its ID.
+async fn create_subscription(client: &Client, url: &str, event_types: Value) -> String {
+    let response = client
+        .post("/api/v1/webhook-subscriptions")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .json(&json!({
+            "name": "Data pipeline",
+            "url": url,
+            "event_types": event_types,
+            "active": true,
+            "secret": "pipeline-secret"
+        }))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    response.into_json::<Value>().await.unwrap()["data"]["subscription_id"]
+        .as_str()
+        .unwrap()
+        .to_owned()
+}
+
+/// Start a Job for the `db-backup.py` Monitor, using tenant foo's API key.
+async fn start_job(client: &Client) {
+    let response = client
+        .post("/api/v1/monitors/c1bf0515-df39-448b-aa95-686360a33b36/jobs/start")
+        .header(Header::new("X-API-Key", "foo-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_create_and_get_subscription(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let subscription_id = create_subscription(
+        &client,
+        "https://example.com/events",
+        json!(["monitor_created", "job_finished"]),
+    )
+    .await;
+    assert!(is_uuid(&subscription_id));
+
+    let response = client
+        .get(format!("/api/v1/webhook-subscriptions/{subscription_id}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "data": {
+                "subscription_id": subscription_id,
+                "name": "Data pipeline",
+                "url": "https://example.com/events",
+                "has_secret": true,
+                "event_types": ["monitor_created", "job_finished"],
+                "active": true
+            }
+        })
+    );
+
+    // Other tenants can't see the webhook subscription.
+    let response = client
+        .get(format!("/api/v1/webhook-subscriptions/{subscription_id}"))
+        .header(create_auth_header("test-kid", "Test User", "bar"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "error": {
+                "code": 404,
+                "reason": "Webhook Subscription Not Found",
+                "description": format!(
+                    "Failed to find webhook subscription with id '{subscription_id}'"
+                )
+            }
+        })
+    );
+
+    let response = client
+        .get("/api/v1/webhook-subscriptions")
+        .header(create_auth_header("test-kid", "Test User", "bar"))
+        .dispatch()
+        .await;
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({"data": [], "paging": {"total": 0}})
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_subscription_secrets_are_not_exposed(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let subscription_id = create_subscription(
+        &client,
+        "https://example.com/events",
+        json!(["job_started"]),
+    )
+    .await;
+
+    for url in [
+        "/api/v1/webhook-subscriptions".to_owned(),
+        format!("/api/v1/webhook-subscriptions/{subscription_id}"),
+    ] {
+        let response = client
+            .get(url)
+            .header(create_auth_header_with_roles(
+                "test-kid",
+                "Test User",
+                "foo",
+                &["viewer"],
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.into_string().await.unwrap();
+        assert!(!body.contains("pipeline-secret"));
+        assert!(!body.contains("\"secret\""));
+        assert!(body.contains("\"has_secret\":true"));
+    }
+}
+
+#[rstest]
+#[case::bad_url(json!("not a url"), json!(["job_started"]))]
+#[case::bad_scheme(json!("ftp://example.com/events"), json!(["job_started"]))]
+#[case::no_event_types(json!("https://example.com/events"), json!([]))]
+#[tokio::test]
+async fn test_create_invalid_subscription(
+    #[future] infrastructure: Infrastructure,
+    #[case] url: Value,
+    #[case] event_types: Value,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .post("/api/v1/webhook-subscriptions")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .json(&json!({
+            "name": "Data pipeline",
+            "url": url,
+            "event_types": event_types,
+            "active": true
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::InternalServerError);
+    let body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(body["error"]["reason"], "Invalid Webhook Subscription");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_update_and_delete_subscription(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let subscription_id = create_subscription(
+        &client,
+        "https://example.com/events",
+        json!(["job_started"]),
+    )
+    .await;
+
+    let response = client
+        .patch(format!("/api/v1/webhook-subscriptions/{subscription_id}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .json(&json!({
+            "name": "Paused pipeline",
+            "url": "https://example.com/new-events",
+            "event_types": ["job_started", "job_finished"],
+            "active": false
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({
+            "data": {
+                "subscription_id": subscription_id,
+                "name": "Paused pipeline",
+                "url": "https://example.com/new-events",
+                "has_secret": false,
+                "event_types": ["job_started", "job_finished"],
+                "active": false
+            }
+        })
+    );
+
+    let response = client
+        .delete(format!("/api/v1/webhook-subscriptions/{subscription_id}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = client
+        .get(format!("/api/v1/webhook-subscriptions/{subscription_id}"))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_deliver_job_started_event(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let receiver = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/events"))
+        .and(header("X-CronMon-Event", "job_started"))
+        .and(header_exists("X-CronMon-Delivery"))
+        .and(header_exists("X-CronMon-Signature"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&receiver)
+        .await;
+
+    let subscription_id = create_subscription(
+        &client,
+        &format!("{}/events", receiver.uri()),
+        json!(["job_started"]),
+    )
+    .await;
+    start_job(&client).await;
+
+    get_deliver_webhooks_service(&infra.pool)
+        .deliver_pending()
+        .await
+        .unwrap();
+
+    let request = &receiver.received_requests().await.unwrap()[0];
+    let payload = serde_json::from_slice::<Value>(&request.body).unwrap();
+    assert_eq!(payload["type"], "job_started");
+    assert_eq!(
+        payload["data"]["monitor"],
+        json!({
+            "monitor_id": "c1bf0515-df39-448b-aa95-686360a33b36",
+            "name": "db-backup.py"
+        })
+    );
+    assert_eq!(payload["data"]["job"]["in_progress"], true);
+
+    let response = client
+        .get(format!(
+            "/api/v1/webhook-subscriptions/{subscription_id}/deliveries"
+        ))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_json::<Value>().await.unwrap();
+    assert_eq!(body["paging"], json!({"total": 1}));
+
+    let delivery = &body["data"][0];
+    assert_eq!(delivery["subscription_id"], subscription_id);
+    assert_eq!(delivery["event_type"], "job_started");
+    assert_eq!(delivery["event_id"], payload["event_id"]);
+    assert_eq!(delivery["payload"], payload);
+    assert_eq!(delivery["status"], "succeeded");
+    assert_eq!(delivery["next_attempt_at"], Value::Null);
+    assert_eq!(delivery["attempts"].as_array().unwrap().len(), 1);
+    assert!(is_datetime(
+        delivery["attempts"][0]["attempted_at"].as_str().unwrap()
+    ));
+    assert_eq!(delivery["attempts"][0]["succeeded"], true);
+    assert_eq!(delivery["attempts"][0]["response_status"], 204);
+    assert_eq!(delivery["attempts"][0]["error"], Value::Null);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_failed_delivery_is_retried_later(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let receiver = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&receiver)
+        .await;
+
+    let subscription_id = create_subscription(
+        &client,
+        &format!("{}/events", receiver.uri()),
+        json!(["job_started"]),
+    )
+    .await;
+    start_job(&client).await;
+
+    // The retry isn't due yet, so the second run doesn't attempt the delivery again.
+    for _ in 0..2 {
+        get_deliver_webhooks_service(&infra.pool)
+            .deliver_pending()
+            .await
+            .unwrap();
+    }
+
+    let response = client
+        .get(format!(
+            "/api/v1/webhook-subscriptions/{subscription_id}/deliveries?limit=1"
+        ))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_json::<Value>().await.unwrap();
+    let delivery = &body["data"][0];
+    assert_eq!(delivery["status"], "pending");
+    assert!(is_datetime(delivery["next_attempt_at"].as_str().unwrap()));
+    assert_eq!(
+        delivery["attempts"],
+        json!([{
+            "attempt_id": delivery["attempts"][0]["attempt_id"],
+            "attempted_at": delivery["attempts"][0]["attempted_at"],
+            "succeeded": false,
+            "response_status": 503,
+            "error": "Received HTTP 503 Service Unavailable"
+        }])
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_only_subscribed_events_are_queued(#[future] infrastructure: Infrastructure) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let subscription_id = create_subscription(
+        &client,
+        "https://example.com/events",
+        json!(["monitor_deleted"]),
+    )
+    .await;
+    start_job(&client).await;
+
+    let response = client
+        .get(format!(
+            "/api/v1/webhook-subscriptions/{subscription_id}/deliveries"
+        ))
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(
+        response.into_json::<Value>().await.unwrap(),
+        json!({"data": [], "paging": {"total": 0}})
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_list_deliveries_for_non_existent_subscription(
+    #[future] infrastructure: Infrastructure,
+) {
+    let mut infra = infrastructure.await;
+    let client = infra.test_api_client("test-kid").await;
+
+    let response = client
+        .get("/api/v1/webhook-subscriptions/f794d1bf-91ef-430b-8caa-44e5098f8270/deliveries")
+        .header(create_auth_header("test-kid", "Test User", "foo"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}